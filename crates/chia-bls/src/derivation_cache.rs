@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use rayon::prelude::*;
+
+use crate::{DerivableKey, PublicKey};
+
+/// Computes the puzzle hash a wallet uses for a derived public key. For the
+/// standard transaction, this is the curried puzzle hash of the synthetic key.
+pub type PuzzleHashFn = fn(&PublicKey) -> [u8; 32];
+
+/// This caches unhardened child public keys (and their puzzle hashes) derived
+/// from an intermediate public key, typically the wallet's
+/// `master_to_wallet_unhardened_intermediate()` key.
+/// Keys that haven't been derived yet are derived in parallel, in a single
+/// batch. Keys that have already been derived are returned from the cache.
+#[derive(Debug, Clone)]
+pub struct DerivationCache {
+    intermediate: PublicKey,
+    puzzle_hash: PuzzleHashFn,
+    // index -> (child public key, puzzle hash)
+    entries: HashMap<u32, (PublicKey, [u8; 32])>,
+}
+
+impl DerivationCache {
+    pub fn new(intermediate: PublicKey, puzzle_hash: PuzzleHashFn) -> Self {
+        Self {
+            intermediate,
+            puzzle_hash,
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn intermediate_key(&self) -> &PublicKey {
+        &self.intermediate
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the child public key and puzzle hash at `index`, if it has
+    /// already been derived.
+    pub fn get(&self, index: u32) -> Option<&(PublicKey, [u8; 32])> {
+        self.entries.get(&index)
+    }
+
+    /// Derives `count` child keys starting at index `start`, and returns
+    /// (index, public key, puzzle hash) for each of them, in index order.
+    pub fn derive(&mut self, start: u32, count: u32) -> Vec<(u32, PublicKey, [u8; 32])> {
+        let end = start.saturating_add(count);
+
+        let missing: Vec<u32> = (start..end)
+            .filter(|idx| !self.entries.contains_key(idx))
+            .collect();
+
+        let intermediate = &self.intermediate;
+        let puzzle_hash = self.puzzle_hash;
+        let derived: Vec<(u32, PublicKey, [u8; 32])> = missing
+            .into_par_iter()
+            .map(|idx| {
+                let pk = intermediate.derive_unhardened(idx);
+                let ph = puzzle_hash(&pk);
+                (idx, pk, ph)
+            })
+            .collect();
+
+        for (idx, pk, ph) in derived {
+            self.entries.insert(idx, (pk, ph));
+        }
+
+        (start..end)
+            .map(|idx| {
+                let (pk, ph) = self.entries[&idx];
+                (idx, pk, ph)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::derive_keys::master_to_wallet_unhardened_intermediate;
+    use crate::SecretKey;
+    use hex::FromHex;
+    use sha2::{Digest, Sha256};
+
+    fn test_puzzle_hash(pk: &PublicKey) -> [u8; 32] {
+        Sha256::digest(pk.to_bytes()).into()
+    }
+
+    #[test]
+    fn test_derive() {
+        let sk = SecretKey::from_bytes(
+            &<[u8; 32]>::from_hex(
+                "6bb19282e27bc6e7e397fb19efc2627a412410fdfd13bf14f4ce5bfdce084c71",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let intermediate = master_to_wallet_unhardened_intermediate(&sk.public_key());
+
+        let mut cache = DerivationCache::new(intermediate, test_puzzle_hash);
+        assert!(cache.is_empty());
+
+        let keys = cache.derive(0, 10);
+        assert_eq!(keys.len(), 10);
+        assert_eq!(cache.len(), 10);
+        for (expected_idx, (idx, pk, ph)) in keys.iter().enumerate() {
+            assert_eq!(*idx, expected_idx as u32);
+            assert_eq!(*pk, intermediate.derive_unhardened(*idx));
+            assert_eq!(*ph, test_puzzle_hash(pk));
+            assert_eq!(cache.get(*idx), Some(&(*pk, *ph)));
+        }
+
+        // overlapping ranges only derive the missing keys
+        let more = cache.derive(5, 10);
+        assert_eq!(cache.len(), 15);
+        assert_eq!(&more[..5], &keys[5..]);
+        assert_eq!(more[9].0, 14);
+        assert_eq!(cache.get(15), None);
+    }
+}
@@ -15,6 +15,12 @@ pub enum Error {
     InvalidPublicKey(BLST_ERROR),
     #[error("Signature is invalid (BLST ERROR: {0:?})")]
     InvalidSignature(BLST_ERROR),
+    #[error("Expected one proof of possession per public key ({0} keys, {1} proofs)")]
+    ProofOfPossessionCount(usize, usize),
+    #[error("Public key at index {0} is a duplicate")]
+    DuplicatePublicKey(usize),
+    #[error("Invalid proof of possession for the public key at index {0}")]
+    InvalidProofOfPossession(usize),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -21,7 +21,11 @@ use pyo3::types::PyAnyMethods;
 #[cfg(feature = "py-bindings")]
 use pyo3::{pyclass, pymethods, IntoPy, PyAny, PyObject, PyResult, Python};
 
-#[cfg_attr(feature = "py-bindings", pyclass, derive(PyStreamable))]
+#[cfg_attr(
+    feature = "py-bindings",
+    pyclass(module = "gold_rs"),
+    derive(PyStreamable)
+)]
 #[derive(Clone)]
 pub struct GTElement(pub(crate) blst_fp12);
 
@@ -0,0 +1,538 @@
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use chia_traits::Streamable;
+use sha2::Sha256;
+use thiserror::Error;
+use zeroize::Zeroize;
+
+use crate::SecretKey;
+
+/// The current version of the keyring file format.
+pub const KEYRING_VERSION: u8 = 1;
+
+/// The number of PBKDF2 iterations used when saving a keyring. This is the
+/// same as the Python keychain uses.
+pub const DEFAULT_ITERATIONS: u32 = 100_000;
+
+/// The largest number of PBKDF2 iterations a keyring may use. The iteration
+/// count is read from the file before it's authenticated, so without a limit a
+/// crafted file could make opening it take practically forever.
+pub const MAX_ITERATIONS: u32 = 10 * DEFAULT_ITERATIONS;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+// version + iterations + salt + nonce
+const HEADER_LEN: usize = 1 + 4 + SALT_LEN + NONCE_LEN;
+
+#[derive(Debug, Error)]
+pub enum KeyringError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("unsupported keyring version {0}")]
+    UnsupportedVersion(u8),
+    #[error("invalid number of key derivation iterations {0}")]
+    InvalidIterations(u32),
+    #[error("invalid keyring file")]
+    InvalidFormat,
+    #[error("incorrect passphrase or corrupt keyring")]
+    DecryptionFailed,
+    #[error("a key with label \"{0}\" already exists")]
+    DuplicateLabel(String),
+    #[error("failed to generate random bytes")]
+    Random,
+}
+
+pub type Result<T> = std::result::Result<T, KeyringError>;
+
+/// A secret key stored in a keyring, along with its label.
+#[derive(Clone, PartialEq, Eq)]
+pub struct KeyringEntry {
+    pub label: String,
+    pub secret_key: SecretKey,
+}
+
+// SecretKey's Debug prints the key itself, so only the label and the
+// fingerprint are included here
+impl fmt::Debug for KeyringEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyringEntry")
+            .field("label", &self.label)
+            .field(
+                "fingerprint",
+                &self.secret_key.public_key().get_fingerprint(),
+            )
+            .finish()
+    }
+}
+
+/// A set of labelled secret keys that's stored encrypted with a passphrase.
+///
+/// The file starts with a header of the format version (1 byte), the number
+/// of PBKDF2 iterations (4 bytes, big endian), a 16 byte salt and a 12 byte
+/// nonce. The rest of the file is the ChaCha20-Poly1305 encrypted list of
+/// (label, secret key) pairs, in Streamable format. The encryption key is
+/// derived from the passphrase with PBKDF2-HMAC-SHA256, and the header is
+/// authenticated along with the payload.
+#[cfg_attr(
+    feature = "py-bindings",
+    pyo3::pyclass(name = "Keyring", module = "gold_rs")
+)]
+#[derive(Clone, Default)]
+pub struct Keyring {
+    entries: Vec<KeyringEntry>,
+}
+
+impl fmt::Debug for Keyring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keyring")
+            .field("entries", &self.entries)
+            .finish()
+    }
+}
+
+/// Creates a new file next to `path` that only the owner can read, for
+/// writing the keyring to before it's renamed into place.
+fn create_temp_file(path: &Path) -> Result<(PathBuf, File)> {
+    let mut name = path
+        .file_name()
+        .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::InvalidInput))?
+        .to_owned();
+    let mut suffix = [0_u8; 8];
+    getrandom::getrandom(&mut suffix).map_err(|_| KeyringError::Random)?;
+    name.push(format!(".{}.tmp", hex::encode(suffix)));
+    let tmp_path = path.with_file_name(name);
+
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let file = options.open(&tmp_path)?;
+    Ok((tmp_path, file))
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> Result<[u8; 32]> {
+    if iterations == 0 || iterations > MAX_ITERATIONS {
+        return Err(KeyringError::InvalidIterations(iterations));
+    }
+    let mut key = [0_u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, iterations, &mut key);
+    Ok(key)
+}
+
+impl Keyring {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> &[KeyringEntry] {
+        &self.entries
+    }
+
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|e| e.label.as_str())
+    }
+
+    pub fn get(&self, label: &str) -> Option<&SecretKey> {
+        self.entries
+            .iter()
+            .find(|e| e.label == label)
+            .map(|e| &e.secret_key)
+    }
+
+    /// Looks up a key by the fingerprint of its public key.
+    pub fn get_by_fingerprint(&self, fingerprint: u32) -> Option<&KeyringEntry> {
+        self.entries
+            .iter()
+            .find(|e| e.secret_key.public_key().get_fingerprint() == fingerprint)
+    }
+
+    /// Adds a key to the keyring. Labels must be unique.
+    pub fn add(&mut self, label: &str, secret_key: SecretKey) -> Result<()> {
+        if self.get(label).is_some() {
+            return Err(KeyringError::DuplicateLabel(label.to_string()));
+        }
+        self.entries.push(KeyringEntry {
+            label: label.to_string(),
+            secret_key,
+        });
+        Ok(())
+    }
+
+    pub fn remove(&mut self, label: &str) -> Option<SecretKey> {
+        let idx = self.entries.iter().position(|e| e.label == label)?;
+        Some(self.entries.remove(idx).secret_key)
+    }
+
+    /// Decrypts a serialized keyring.
+    pub fn from_bytes(buf: &[u8], passphrase: &str) -> Result<Self> {
+        if buf.len() < HEADER_LEN {
+            return Err(KeyringError::InvalidFormat);
+        }
+        let (header, ciphertext) = buf.split_at(HEADER_LEN);
+        if header[0] != KEYRING_VERSION {
+            return Err(KeyringError::UnsupportedVersion(header[0]));
+        }
+        let iterations = u32::from_be_bytes(header[1..5].try_into().unwrap());
+        let salt = &header[5..5 + SALT_LEN];
+        let nonce = &header[5 + SALT_LEN..];
+
+        let mut key = derive_key(passphrase, salt, iterations)?;
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+        key.zeroize();
+        let mut plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: header,
+                },
+            )
+            .map_err(|_| KeyringError::DecryptionFailed)?;
+
+        let mut input = Cursor::new(plaintext.as_slice());
+        let pairs = <Vec<(String, SecretKey)>>::parse::<false>(&mut input);
+        let consumed_all = input.position() as usize == plaintext.len();
+        plaintext.zeroize();
+        let Ok(pairs) = pairs else {
+            return Err(KeyringError::InvalidFormat);
+        };
+        if !consumed_all {
+            return Err(KeyringError::InvalidFormat);
+        }
+
+        let mut ret = Self::new();
+        for (label, secret_key) in pairs {
+            ret.add(&label, secret_key)?;
+        }
+        Ok(ret)
+    }
+
+    /// Encrypts the keyring with the passphrase, using a random salt and
+    /// nonce.
+    pub fn to_bytes(&self, passphrase: &str) -> Result<Vec<u8>> {
+        self.to_bytes_with_iterations(passphrase, DEFAULT_ITERATIONS)
+    }
+
+    pub fn to_bytes_with_iterations(&self, passphrase: &str, iterations: u32) -> Result<Vec<u8>> {
+        let mut header = Vec::with_capacity(HEADER_LEN);
+        header.push(KEYRING_VERSION);
+        header.extend_from_slice(&iterations.to_be_bytes());
+        let mut random = [0_u8; SALT_LEN + NONCE_LEN];
+        getrandom::getrandom(&mut random).map_err(|_| KeyringError::Random)?;
+        header.extend_from_slice(&random);
+        let salt = &header[5..5 + SALT_LEN];
+        let nonce = &header[5 + SALT_LEN..];
+
+        // this is the Streamable serialization of a Vec<(String, SecretKey)>,
+        // without copying the keys
+        let mut payload = (self.entries.len() as u32).to_be_bytes().to_vec();
+        for entry in &self.entries {
+            let streamed = entry
+                .label
+                .stream(&mut payload)
+                .and_then(|_| entry.secret_key.stream(&mut payload));
+            if streamed.is_err() {
+                payload.zeroize();
+                return Err(KeyringError::InvalidFormat);
+            }
+        }
+
+        let mut key = match derive_key(passphrase, salt, iterations) {
+            Ok(key) => key,
+            Err(err) => {
+                payload.zeroize();
+                return Err(err);
+            }
+        };
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+        key.zeroize();
+        let ciphertext = cipher.encrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: &payload,
+                aad: &header,
+            },
+        );
+        payload.zeroize();
+
+        let mut ret = header;
+        ret.extend_from_slice(&ciphertext.map_err(|_| KeyringError::InvalidFormat)?);
+        Ok(ret)
+    }
+
+    pub fn open<P: AsRef<Path>>(path: P, passphrase: &str) -> Result<Self> {
+        Self::from_bytes(&fs::read(path)?, passphrase)
+    }
+
+    /// Writes the encrypted keyring to `path`. The file is only readable by
+    /// its owner, and it's replaced atomically, so a failed save leaves any
+    /// existing keyring intact.
+    pub fn save<P: AsRef<Path>>(&self, path: P, passphrase: &str) -> Result<()> {
+        let path = path.as_ref();
+        let buf = self.to_bytes(passphrase)?;
+        let (tmp_path, mut file) = create_temp_file(path)?;
+        let written = file
+            .write_all(&buf)
+            .and_then(|_| file.sync_all())
+            .and_then(|_| fs::rename(&tmp_path, path));
+        if let Err(err) = written {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(err.into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "py-bindings")]
+mod pybindings {
+    use super::*;
+
+    use pyo3::exceptions::{PyOSError, PyValueError};
+    use pyo3::{pymethods, PyErr, PyResult};
+    use std::path::PathBuf;
+
+    impl From<KeyringError> for PyErr {
+        fn from(err: KeyringError) -> PyErr {
+            match err {
+                KeyringError::Io(e) => PyOSError::new_err(e.to_string()),
+                e => PyValueError::new_err(e.to_string()),
+            }
+        }
+    }
+
+    #[pymethods]
+    impl Keyring {
+        #[new]
+        pub fn init() -> Self {
+            Self::new()
+        }
+
+        #[staticmethod]
+        #[pyo3(name = "open")]
+        pub fn py_open(path: PathBuf, passphrase: &str) -> PyResult<Self> {
+            Ok(Self::open(path, passphrase)?)
+        }
+
+        #[pyo3(name = "save")]
+        pub fn py_save(&self, path: PathBuf, passphrase: &str) -> PyResult<()> {
+            Ok(self.save(path, passphrase)?)
+        }
+
+        #[pyo3(name = "add")]
+        pub fn py_add(&mut self, label: &str, secret_key: SecretKey) -> PyResult<()> {
+            Ok(self.add(label, secret_key)?)
+        }
+
+        #[pyo3(name = "remove")]
+        pub fn py_remove(&mut self, label: &str) -> Option<SecretKey> {
+            self.remove(label)
+        }
+
+        #[pyo3(name = "get")]
+        pub fn py_get(&self, label: &str) -> Option<SecretKey> {
+            self.get(label).cloned()
+        }
+
+        #[pyo3(name = "get_by_fingerprint")]
+        pub fn py_get_by_fingerprint(&self, fingerprint: u32) -> Option<(String, SecretKey)> {
+            self.get_by_fingerprint(fingerprint)
+                .map(|e| (e.label.clone(), e.secret_key.clone()))
+        }
+
+        #[pyo3(name = "labels")]
+        pub fn py_labels(&self) -> Vec<String> {
+            self.labels().map(str::to_string).collect()
+        }
+
+        pub fn __len__(&self) -> usize {
+            self.len()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_keyring() -> Keyring {
+        let mut keyring = Keyring::new();
+        keyring
+            .add("farmer", SecretKey::from_seed(&[1; 32]))
+            .unwrap();
+        keyring
+            .add("wallet", SecretKey::from_seed(&[2; 32]))
+            .unwrap();
+        keyring
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let keyring = make_keyring();
+        let buf = keyring.to_bytes_with_iterations("secret", 10).unwrap();
+        assert_eq!(buf[0], KEYRING_VERSION);
+
+        let loaded = Keyring::from_bytes(&buf, "secret").unwrap();
+        assert_eq!(loaded.entries(), keyring.entries());
+        assert_eq!(loaded.labels().collect::<Vec<_>>(), ["farmer", "wallet"]);
+        assert_eq!(loaded.get("wallet"), Some(&SecretKey::from_seed(&[2; 32])));
+        assert_eq!(loaded.get("pool"), None);
+
+        let fingerprint = SecretKey::from_seed(&[1; 32])
+            .public_key()
+            .get_fingerprint();
+        assert_eq!(
+            loaded.get_by_fingerprint(fingerprint).unwrap().label,
+            "farmer"
+        );
+
+        // the salt and nonce are random
+        assert_ne!(keyring.to_bytes_with_iterations("secret", 10).unwrap(), buf);
+    }
+
+    #[test]
+    fn test_empty() {
+        let buf = Keyring::new().to_bytes_with_iterations("", 1).unwrap();
+        assert!(Keyring::from_bytes(&buf, "").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_wrong_passphrase() {
+        let buf = make_keyring()
+            .to_bytes_with_iterations("secret", 10)
+            .unwrap();
+        assert!(matches!(
+            Keyring::from_bytes(&buf, "Secret"),
+            Err(KeyringError::DecryptionFailed)
+        ));
+    }
+
+    #[test]
+    fn test_tampered() {
+        let buf = make_keyring()
+            .to_bytes_with_iterations("secret", 10)
+            .unwrap();
+
+        // the header is authenticated too
+        for idx in [4, 10, HEADER_LEN, buf.len() - 1] {
+            let mut tampered = buf.clone();
+            tampered[idx] ^= 1;
+            assert!(matches!(
+                Keyring::from_bytes(&tampered, "secret"),
+                Err(KeyringError::DecryptionFailed)
+            ));
+        }
+
+        let mut tampered = buf.clone();
+        tampered[0] = 2;
+        assert!(matches!(
+            Keyring::from_bytes(&tampered, "secret"),
+            Err(KeyringError::UnsupportedVersion(2))
+        ));
+
+        assert!(matches!(
+            Keyring::from_bytes(&buf[..HEADER_LEN - 1], "secret"),
+            Err(KeyringError::InvalidFormat)
+        ));
+    }
+
+    #[test]
+    fn test_iterations_limit() {
+        let keyring = make_keyring();
+        assert!(keyring
+            .to_bytes_with_iterations("secret", MAX_ITERATIONS + 1)
+            .is_err());
+        assert!(keyring.to_bytes_with_iterations("secret", 0).is_err());
+
+        // a crafted file with a huge iteration count is rejected before the
+        // key is derived
+        let mut buf = keyring.to_bytes_with_iterations("secret", 10).unwrap();
+        buf[1..5].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(matches!(
+            Keyring::from_bytes(&buf, "secret"),
+            Err(KeyringError::InvalidIterations(u32::MAX))
+        ));
+        buf[1..5].copy_from_slice(&0_u32.to_be_bytes());
+        assert!(matches!(
+            Keyring::from_bytes(&buf, "secret"),
+            Err(KeyringError::InvalidIterations(0))
+        ));
+    }
+
+    #[test]
+    fn test_add_remove() {
+        let mut keyring = make_keyring();
+        assert!(matches!(
+            keyring.add("farmer", SecretKey::from_seed(&[3; 32])),
+            Err(KeyringError::DuplicateLabel(_))
+        ));
+        assert_eq!(
+            keyring.remove("farmer"),
+            Some(SecretKey::from_seed(&[1; 32]))
+        );
+        assert_eq!(keyring.remove("farmer"), None);
+        assert_eq!(keyring.len(), 1);
+    }
+
+    #[test]
+    fn test_save_open() {
+        let path = std::env::temp_dir().join(format!("keyring-test-{}", std::process::id()));
+        let keyring = make_keyring();
+        keyring.save(&path, "secret").unwrap();
+        let loaded = Keyring::open(&path, "secret").unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.entries(), keyring.entries());
+
+        assert!(matches!(
+            Keyring::open(&path, "secret"),
+            Err(KeyringError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_save_replaces() {
+        let dir = std::env::temp_dir().join(format!("keyring-dir-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("keyring.bin");
+        Keyring::new().save(&path, "secret").unwrap();
+        make_keyring().save(&path, "secret").unwrap();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        // no temporary files are left behind
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        let loaded = Keyring::open(&path, "secret").unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(loaded.len(), 2);
+    }
+
+    #[test]
+    fn test_debug_hides_keys() {
+        let keyring = make_keyring();
+        let sk = SecretKey::from_seed(&[1; 32]);
+        let debug = format!("{keyring:?}");
+        assert!(!debug.contains(&hex::encode(sk.to_bytes())));
+        assert!(debug.contains(&sk.public_key().get_fingerprint().to_string()));
+        assert!(debug.contains("farmer"));
+    }
+}
@@ -1,18 +1,32 @@
 pub mod cached_bls;
 pub mod derivable_key;
+pub mod derivation_cache;
 pub mod derive_keys;
 pub mod error;
 pub mod gtelement;
+#[cfg(feature = "keyring")]
+pub mod keyring;
+#[cfg(all(feature = "mlock", unix))]
+pub mod locked_secret_key;
 pub mod mnemonic;
+pub mod pop;
 pub mod public_key;
 pub mod secret_key;
 pub mod signature;
 
 pub use cached_bls::BlsCache;
 pub use derivable_key::DerivableKey;
+pub use derivation_cache::DerivationCache;
 pub use error::{Error, Result};
 pub use gtelement::GTElement;
-pub use public_key::{hash_to_g1, hash_to_g1_with_dst, PublicKey};
+#[cfg(feature = "keyring")]
+pub use keyring::Keyring;
+#[cfg(all(feature = "mlock", unix))]
+pub use locked_secret_key::LockedSecretKey;
+pub use pop::{
+    aggregate_public_keys, aggregate_public_keys_with_pop, pop_prove, pop_verify, POP_DST,
+};
+pub use public_key::{hash_to_g1, hash_to_g1_with_dst, validate_public_keys, PublicKey};
 pub use secret_key::SecretKey;
 pub use signature::{
     aggregate, aggregate_pairing, aggregate_verify, aggregate_verify_gt, hash_to_g2,
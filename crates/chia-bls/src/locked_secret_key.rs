@@ -0,0 +1,108 @@
+use crate::SecretKey;
+use std::alloc::{alloc_zeroed, dealloc, Layout};
+use std::io;
+use std::ops::Deref;
+use std::ptr::NonNull;
+use zeroize::Zeroize;
+
+/// A secret key stored in its own page of memory, which is locked (with
+/// `mlock()`) to prevent it from being written to swap. When dropped, the key
+/// is zeroed before the page is unlocked and freed.
+/// Memory locks aren't reference counted, unlocking a page unlocks it for all
+/// keys on it. That's why every key gets a page of its own.
+pub struct LockedSecretKey {
+    ptr: NonNull<SecretKey>,
+    layout: Layout,
+}
+
+// the key is owned by this object, just like a Box<SecretKey>
+unsafe impl Send for LockedSecretKey {}
+unsafe impl Sync for LockedSecretKey {}
+
+fn page_size() -> usize {
+    let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    if size <= 0 {
+        4096
+    } else {
+        size as usize
+    }
+}
+
+impl LockedSecretKey {
+    /// Copies the key into locked memory. This fails if the process isn't
+    /// allowed to lock any more memory (see RLIMIT_MEMLOCK).
+    pub fn new(sk: &SecretKey) -> io::Result<Self> {
+        let page = page_size();
+        let layout = Layout::from_size_align(page, page)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        let ptr = NonNull::new(unsafe { alloc_zeroed(layout) } as *mut SecretKey)
+            .ok_or_else(|| io::Error::from(io::ErrorKind::OutOfMemory))?;
+
+        if unsafe { libc::mlock(ptr.as_ptr() as *const libc::c_void, page) } != 0 {
+            let err = io::Error::last_os_error();
+            unsafe { dealloc(ptr.as_ptr() as *mut u8, layout) };
+            return Err(err);
+        }
+
+        // the scalar is copied straight into the locked page, without an
+        // intermediate SecretKey on the stack
+        unsafe {
+            std::ptr::write(ptr.as_ptr(), SecretKey(Default::default()));
+            (*ptr.as_ptr()).0 = sk.0;
+        }
+        Ok(Self { ptr, layout })
+    }
+
+    /// Overwrites the key with zeros, without waiting for it to be dropped.
+    pub fn clear(&mut self) {
+        unsafe { (*self.ptr.as_ptr()).0.b.zeroize() };
+    }
+}
+
+impl Deref for LockedSecretKey {
+    type Target = SecretKey;
+    fn deref(&self) -> &SecretKey {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl Drop for LockedSecretKey {
+    fn drop(&mut self) {
+        unsafe {
+            // this zeroes the key
+            std::ptr::drop_in_place(self.ptr.as_ptr());
+            libc::munlock(self.ptr.as_ptr() as *const libc::c_void, self.layout.size());
+            dealloc(self.ptr.as_ptr() as *mut u8, self.layout);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sign, verify};
+
+    #[test]
+    fn test_locked_secret_key() {
+        let sk = SecretKey::from_seed(&[7; 32]);
+        let locked = LockedSecretKey::new(&sk).expect("mlock");
+        assert_eq!(*locked, sk);
+        assert_eq!(locked.public_key(), sk.public_key());
+
+        let sig = sign(&locked, b"foobar");
+        assert!(verify(&sig, &sk.public_key(), b"foobar"));
+
+        // every key has its own page
+        let other = LockedSecretKey::new(&sk).expect("mlock");
+        assert_ne!(other.ptr, locked.ptr);
+    }
+
+    #[test]
+    fn test_clear() {
+        let sk = SecretKey::from_seed(&[7; 32]);
+        let mut locked = LockedSecretKey::new(&sk).expect("mlock");
+        locked.clear();
+        assert_eq!(locked.to_bytes(), [0; 32]);
+        assert_ne!(sk.to_bytes(), [0; 32]);
+    }
+}
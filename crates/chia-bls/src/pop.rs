@@ -0,0 +1,170 @@
+use crate::{hash_to_g2_with_dst, Error, PublicKey, Result, SecretKey, Signature};
+use blst::*;
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::mem::MaybeUninit;
+
+// the proof of possession scheme, the same as the PopSchemeMPL of blspy
+pub const POP_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+// Adds up the public keys. The aggregate key is only safe to verify signatures
+// with if every key holder proved they know the secret key of their key.
+// Otherwise, someone can pick their key as a function of the other keys, to
+// cancel them out (a rogue key attack). See aggregate_public_keys_with_pop()
+pub fn aggregate_public_keys<Pk: Borrow<PublicKey>, I>(pks: I) -> PublicKey
+where
+    I: IntoIterator<Item = Pk>,
+{
+    let mut ret = PublicKey::default();
+    for pk in pks {
+        ret += pk.borrow();
+    }
+    ret
+}
+
+// the proof of possession of the secret key, a signature of its public key
+pub fn pop_prove(sk: &SecretKey) -> Signature {
+    let hash = hash_to_g2_with_dst(&sk.public_key().to_bytes(), POP_DST);
+    let mut ret = Signature::default();
+    unsafe {
+        blst_sign_pk_in_g1(&mut ret.0, &hash.0, &*sk.scalar());
+    }
+    ret
+}
+
+pub fn pop_verify(pk: &PublicKey, proof: &Signature) -> bool {
+    if !pk.is_valid() || pk.is_inf() || !proof.is_valid() {
+        return false;
+    }
+    unsafe {
+        let mut pubkey_affine = MaybeUninit::<blst_p1_affine>::uninit();
+        let mut proof_affine = MaybeUninit::<blst_p2_affine>::uninit();
+
+        blst_p1_to_affine(pubkey_affine.as_mut_ptr(), &pk.0);
+        blst_p2_to_affine(proof_affine.as_mut_ptr(), &proof.0);
+
+        let msg = pk.to_bytes();
+        let err = blst_core_verify_pk_in_g1(
+            &pubkey_affine.assume_init(),
+            &proof_affine.assume_init(),
+            true, // hash
+            msg.as_ptr(),
+            msg.len(),
+            POP_DST.as_ptr(),
+            POP_DST.len(),
+            std::ptr::null(),
+            0,
+        );
+
+        err == BLST_ERROR::BLST_SUCCESS
+    }
+}
+
+// Like aggregate_public_keys(), but only if it's safe: every key needs a valid
+// proof of possession (pops, in the same order as pks) and a key may only be
+// included once. Adding a key twice doubles its weight, which a signature by
+// only that key could then satisfy. The errors refer to the index of the
+// offending key.
+pub fn aggregate_public_keys_with_pop(pks: &[PublicKey], pops: &[Signature]) -> Result<PublicKey> {
+    if pks.len() != pops.len() {
+        return Err(Error::ProofOfPossessionCount(pks.len(), pops.len()));
+    }
+    let mut seen = HashSet::<PublicKey>::with_capacity(pks.len());
+    for (idx, (pk, pop)) in pks.iter().zip(pops).enumerate() {
+        if !seen.insert(*pk) {
+            return Err(Error::DuplicatePublicKey(idx));
+        }
+        if !pop_verify(pk, pop) {
+            return Err(Error::InvalidProofOfPossession(idx));
+        }
+    }
+    Ok(aggregate_public_keys(pks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sign;
+
+    fn keys(count: u8) -> Vec<SecretKey> {
+        (1..=count)
+            .map(|i| SecretKey::from_seed(&[i; 32]))
+            .collect()
+    }
+
+    #[test]
+    fn test_aggregate_public_keys() {
+        let sks = keys(3);
+        let pks: Vec<PublicKey> = sks.iter().map(SecretKey::public_key).collect();
+        let agg = aggregate_public_keys(&pks);
+        assert_eq!(agg, pks[0] + &pks[1] + &pks[2]);
+        assert_eq!(
+            aggregate_public_keys(Vec::<PublicKey>::new()),
+            PublicKey::default()
+        );
+    }
+
+    #[test]
+    fn test_pop() {
+        let sks = keys(2);
+        let pk = sks[0].public_key();
+        let proof = pop_prove(&sks[0]);
+        assert!(pop_verify(&pk, &proof));
+        assert!(!pop_verify(&sks[1].public_key(), &proof));
+        assert!(!pop_verify(&PublicKey::default(), &proof));
+        // a proof of possession is not a regular (augmented) signature of the
+        // public key
+        assert!(!pop_verify(&pk, &sign(&sks[0], pk.to_bytes())));
+    }
+
+    #[test]
+    fn test_aggregate_with_pop() {
+        let sks = keys(3);
+        let pks: Vec<PublicKey> = sks.iter().map(SecretKey::public_key).collect();
+        let pops: Vec<Signature> = sks.iter().map(pop_prove).collect();
+        assert_eq!(
+            aggregate_public_keys_with_pop(&pks, &pops),
+            Ok(aggregate_public_keys(&pks))
+        );
+
+        assert_eq!(
+            aggregate_public_keys_with_pop(&pks, &pops[..2]),
+            Err(Error::ProofOfPossessionCount(3, 2))
+        );
+
+        let mut swapped = pops.clone();
+        swapped.swap(1, 2);
+        assert_eq!(
+            aggregate_public_keys_with_pop(&pks, &swapped),
+            Err(Error::InvalidProofOfPossession(1))
+        );
+
+        let dup_pks = [pks[0], pks[1], pks[0]];
+        let dup_pops = [pops[0].clone(), pops[1].clone(), pops[0].clone()];
+        assert_eq!(
+            aggregate_public_keys_with_pop(&dup_pks, &dup_pops),
+            Err(Error::DuplicatePublicKey(2))
+        );
+    }
+
+    #[test]
+    fn test_rogue_key() {
+        // the attacker picks their key to cancel out the victim's, so the
+        // aggregate is a key they know the secret key of
+        let victim = SecretKey::from_seed(&[1; 32]).public_key();
+        let attacker_sk = SecretKey::from_seed(&[2; 32]);
+        let rogue = attacker_sk.public_key() + &-victim;
+        let pks = [victim, rogue];
+        assert_eq!(aggregate_public_keys(&pks), attacker_sk.public_key());
+
+        // but they can't prove possession of the rogue key
+        let pops = [
+            pop_prove(&SecretKey::from_seed(&[1; 32])),
+            pop_prove(&attacker_sk),
+        ];
+        assert_eq!(
+            aggregate_public_keys_with_pop(&pks, &pops),
+            Err(Error::InvalidProofOfPossession(1))
+        );
+    }
+}
@@ -2,6 +2,7 @@ use crate::secret_key::is_all_zero;
 use crate::{DerivableKey, Error, Result};
 use blst::*;
 use chia_traits::{read_bytes, Streamable};
+use rayon::prelude::*;
 use sha2::{digest::FixedOutput, Digest, Sha256};
 use std::fmt;
 use std::hash::{Hash, Hasher};
@@ -24,7 +25,7 @@ use pyo3::{pyclass, pymethods, IntoPy, PyAny, PyObject, PyResult, Python};
 
 #[cfg_attr(
     feature = "py-bindings",
-    pyclass(name = "G1Element"),
+    pyclass(name = "G1Element", module = "gold_rs"),
     derive(PyStreamable)
 )]
 #[derive(Clone, Copy, Default)]
@@ -160,6 +161,22 @@ impl PublicKey {
     }
 }
 
+// Checks a batch of public keys, in parallel. This is meant for keys parsed
+// with from_bytes_unchecked(), where the (expensive) subgroup check was
+// skipped. Unlike is_valid(), the infinity point is only accepted if
+// allow_infinity is set.
+pub fn validate_public_keys(pks: &[PublicKey], allow_infinity: bool) -> Vec<bool> {
+    pks.par_iter()
+        .map(|pk| {
+            if pk.is_inf() {
+                allow_infinity
+            } else {
+                pk.is_valid()
+            }
+        })
+        .collect()
+}
+
 #[cfg(feature = "py-bindings")]
 #[pymethods]
 impl PublicKey {
@@ -186,6 +203,16 @@ impl PublicKey {
         self.get_fingerprint()
     }
 
+    #[pyo3(name = "is_valid")]
+    pub fn py_is_valid(&self) -> bool {
+        self.is_valid()
+    }
+
+    #[pyo3(name = "is_inf")]
+    pub fn py_is_inf(&self) -> bool {
+        self.is_inf()
+    }
+
     fn __str__(&self) -> pyo3::PyResult<String> {
         Ok(hex::encode(self.to_bytes()))
     }
@@ -350,6 +377,43 @@ pub fn parse_hex_string(o: &pyo3::Bound<PyAny>, len: usize, name: &str) -> PyRes
     }
 }
 
+// G1 and G2 elements are serialized as 0x-prefixed hex strings, the same way
+// the RPC endpoints encode them in JSON
+#[cfg(feature = "serde")]
+pub(crate) fn deserialize_hex<'de, D, const N: usize>(
+    deserializer: D,
+) -> std::result::Result<[u8; N], D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error as _;
+    let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+    let s = s.strip_prefix("0x").unwrap_or(&s);
+    let buf = hex::decode(s).map_err(D::Error::custom)?;
+    buf.try_into()
+        .map_err(|_| D::Error::custom(format!("invalid length, expected {N} bytes")))
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for PublicKey {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{}", hex::encode(self.to_bytes())))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PublicKey {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        use serde::de::Error as _;
+        Self::from_bytes(&deserialize_hex(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
 #[cfg(feature = "py-bindings")]
 impl FromJsonDict for PublicKey {
     fn from_json_dict(o: &pyo3::Bound<PyAny>) -> PyResult<Self> {
@@ -580,6 +644,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_public_keys() {
+        let mut rng = StdRng::seed_from_u64(1337);
+        let mut data = [0u8; 32];
+        let mut pks = Vec::new();
+        for _i in 0..20 {
+            rng.fill(data.as_mut_slice());
+            pks.push(SecretKey::from_seed(&data).public_key());
+        }
+        pks.push(PublicKey::default());
+        let mut expected = vec![true; 20];
+        expected.push(false);
+        assert_eq!(validate_public_keys(&pks, false), expected);
+        expected[20] = true;
+        assert_eq!(validate_public_keys(&pks, true), expected);
+        assert!(validate_public_keys(&[], false).is_empty());
+    }
+
     #[test]
     fn test_hash() {
         fn hash<T: std::hash::Hash>(v: T) -> u64 {
@@ -751,6 +833,20 @@ mod tests {
         let g1 = hash_to_g1_with_dst(input.as_bytes(), dst.as_bytes());
         assert_eq!(hex::encode(g1.to_bytes()), expect);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let sk = SecretKey::from_seed(&[1; 32]);
+        let pk = sk.public_key();
+        let json = serde_json::to_string(&pk).unwrap();
+        assert_eq!(json, format!("\"0x{}\"", hex::encode(pk.to_bytes())));
+        assert_eq!(serde_json::from_str::<PublicKey>(&json).unwrap(), pk);
+        // the 0x prefix is optional
+        let json = format!("\"{}\"", hex::encode(pk.to_bytes()));
+        assert_eq!(serde_json::from_str::<PublicKey>(&json).unwrap(), pk);
+        assert!(serde_json::from_str::<PublicKey>("\"0x00\"").is_err());
+    }
 }
 
 #[cfg(test)]
@@ -8,6 +8,7 @@ use std::hash::{Hash, Hasher};
 use std::io::Cursor;
 use std::mem::MaybeUninit;
 use std::ops::{Add, AddAssign};
+use zeroize::Zeroize;
 
 #[cfg(feature = "py-bindings")]
 use crate::public_key::parse_hex_string;
@@ -24,12 +25,21 @@ use pyo3::{pyclass, pymethods, IntoPy, PyAny, PyObject, PyResult, Python};
 
 #[cfg_attr(
     feature = "py-bindings",
-    pyclass(frozen, name = "PrivateKey"),
+    pyclass(frozen, name = "PrivateKey", module = "gold_rs"),
     derive(PyStreamable)
 )]
 #[derive(PartialEq, Eq, Clone)]
 pub struct SecretKey(pub(crate) blst_scalar);
 
+/// The scalar is overwritten with zeros when the key is dropped, so it isn't
+/// left behind in freed memory. Note that this doesn't cover copies of the key
+/// made by the caller, e.g. the bytes returned by `to_bytes()`.
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.0.b.zeroize();
+    }
+}
+
 #[cfg(feature = "arbitrary")]
 impl<'a> arbitrary::Arbitrary<'a> for SecretKey {
     fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
@@ -198,10 +208,8 @@ impl Add<&SecretKey> for &SecretKey {
 impl Add<&SecretKey> for SecretKey {
     type Output = SecretKey;
     fn add(mut self, rhs: &SecretKey) -> SecretKey {
-        unsafe {
-            blst_sk_add_n_check(&mut self.0, &self.0, &rhs.0);
-            self
-        }
+        self += rhs;
+        self
     }
 }
 
@@ -493,6 +501,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_add_to_itself() {
+        let mut rng = StdRng::seed_from_u64(1337);
+        let mut data = [0u8; 32];
+        rng.fill(data.as_mut_slice());
+        let sk = SecretKey::from_seed(&data);
+
+        let sk2 = &sk + &sk;
+        let mut sk3 = sk.clone();
+        sk3 += &sk;
+        assert_eq!(sk2, sk3);
+        assert_eq!(sk2.public_key(), sk.public_key() + &sk.public_key());
+    }
+
     #[test]
     fn test_roundtrip() {
         let mut rng = StdRng::seed_from_u64(1337);
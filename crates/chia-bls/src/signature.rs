@@ -25,7 +25,7 @@ pub const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG_";
 
 #[cfg_attr(
     feature = "py-bindings",
-    pyclass(name = "G2Element"),
+    pyclass(name = "G2Element", module = "gold_rs"),
     derive(PyStreamable)
 )]
 #[derive(Clone, Default)]
@@ -101,6 +101,10 @@ impl Signature {
         unsafe { blst_p2_is_inf(&self.0) || blst_p2_in_g2(&self.0) }
     }
 
+    pub fn is_inf(&self) -> bool {
+        unsafe { blst_p2_is_inf(&self.0) }
+    }
+
     pub fn negate(&mut self) {
         unsafe {
             blst_p2_cneg(&mut self.0, true);
@@ -176,6 +180,27 @@ impl fmt::Debug for Signature {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Signature {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{}", hex::encode(self.to_bytes())))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Signature {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        use serde::de::Error as _;
+        Self::from_bytes(&crate::public_key::deserialize_hex(deserializer)?)
+            .map_err(D::Error::custom)
+    }
+}
+
 impl AddAssign<&Signature> for Signature {
     fn add_assign(&mut self, rhs: &Signature) {
         unsafe {
@@ -269,6 +294,16 @@ impl Signature {
         self.pair(other)
     }
 
+    #[pyo3(name = "is_valid")]
+    pub fn py_is_valid(&self) -> bool {
+        self.is_valid()
+    }
+
+    #[pyo3(name = "is_inf")]
+    pub fn py_is_inf(&self) -> bool {
+        self.is_inf()
+    }
+
     #[staticmethod]
     #[pyo3(name = "generator")]
     pub fn py_generator() -> Self {
@@ -527,7 +562,7 @@ pub fn sign_raw<Msg: AsRef<[u8]>>(sk: &SecretKey, msg: Msg) -> Signature {
             std::ptr::null(),
             0,
         );
-        blst_sign_pk_in_g1(p2.as_mut_ptr(), p2.as_ptr(), &sk.0);
+        blst_sign_pk_in_g1(p2.as_mut_ptr(), p2.as_ptr(), &*sk.scalar());
         p2.assume_init()
     };
     Signature(p2)
@@ -601,6 +636,13 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_is_inf() {
+        assert!(Signature::default().is_inf());
+        let sk = SecretKey::from_seed(&[1; 32]);
+        assert!(!sign(&sk, b"foobar").is_inf());
+    }
+
     #[test]
     fn test_roundtrip() {
         let mut rng = StdRng::seed_from_u64(1337);
@@ -1235,6 +1277,17 @@ mod tests {
         let g2 = hash_to_g2_with_dst(input.as_bytes(), dst.as_bytes());
         assert_eq!(hex::encode(g2.to_bytes()), expect);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let sk = SecretKey::from_seed(&[1; 32]);
+        let sig = sign(&sk, b"foobar");
+        let json = serde_json::to_string(&sig).unwrap();
+        assert_eq!(json, format!("\"0x{}\"", hex::encode(sig.to_bytes())));
+        assert_eq!(serde_json::from_str::<Signature>(&json).unwrap(), sig);
+        assert!(serde_json::from_str::<Signature>("\"0xzz\"").is_err());
+    }
 }
 
 #[cfg(test)]
@@ -2,5 +2,11 @@ mod error;
 mod peer;
 mod utils;
 
+#[cfg(feature = "rpc")]
+mod rpc;
+
 pub use error::*;
 pub use peer::*;
+
+#[cfg(feature = "rpc")]
+pub use rpc::*;
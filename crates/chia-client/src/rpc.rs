@@ -0,0 +1,282 @@
+use chia_protocol::{BlockRecord, Bytes32, Coin, FullBlock, SpendBundle};
+use reqwest::{Certificate, Client, Identity};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RpcError {
+    #[error("{0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("rpc error: {0}")]
+    Rpc(String),
+}
+
+// The coin records the full node keeps for every coin it knows about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoinRecord {
+    pub coin: Coin,
+    pub confirmed_block_index: u32,
+    // 0 means the coin is unspent
+    pub spent_block_index: u32,
+    pub coinbase: bool,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncState {
+    pub sync_mode: bool,
+    pub synced: bool,
+    pub sync_tip_height: u32,
+    pub sync_progress_height: u32,
+}
+
+// A subset of the state returned by get_blockchain_state
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockchainState {
+    pub peak: Option<BlockRecord>,
+    pub genesis_challenge_initialized: bool,
+    pub sync: SyncState,
+    pub difficulty: u64,
+    pub sub_slot_iters: u64,
+    pub space: u128,
+    pub mempool_size: u32,
+    pub mempool_cost: u64,
+    pub mempool_max_total_cost: u64,
+    pub block_max_cost: u64,
+    pub node_id: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransactionStatus {
+    // added to the mempool
+    Success,
+    // not valid yet, kept in the pending pool
+    Pending,
+    Failed,
+}
+
+// every response has these fields, the remaining ones depend on the endpoint
+#[derive(Deserialize)]
+struct ResponseStatus {
+    success: bool,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PushTxResponse {
+    status: TransactionStatus,
+}
+
+#[derive(Deserialize)]
+struct CoinRecordsResponse {
+    coin_records: Vec<CoinRecord>,
+}
+
+#[derive(Deserialize)]
+struct BlockResponse {
+    block: FullBlock,
+}
+
+#[derive(Deserialize)]
+struct BlockchainStateResponse {
+    blockchain_state: BlockchainState,
+}
+
+// The body is parsed directly into the response type, rather than via
+// serde_json::Value, since Value can't hold the 128 bit integers some
+// responses have (like the weight of a block)
+fn parse_response<T: DeserializeOwned>(body: &[u8]) -> Result<T, RpcError> {
+    let status: ResponseStatus = serde_json::from_slice(body)?;
+    if !status.success {
+        return Err(RpcError::Rpc(
+            status.error.unwrap_or_else(|| "request failed".to_string()),
+        ));
+    }
+    Ok(serde_json::from_slice(body)?)
+}
+
+// A client for the HTTPS RPC interface of a full node (port 8555 by default)
+pub struct FullNodeClient {
+    client: Client,
+    base_url: String,
+}
+
+impl FullNodeClient {
+    // The full node only accepts connections authenticated with its private
+    // certificate (private_full_node.crt and .key in the node's ssl directory).
+    // Its own certificate is signed by the node's private CA (ca_pem, i.e.
+    // ssl/ca/private_ca.crt), which is the only root we trust. The certificate
+    // is issued for "chia.net" rather than the node's address, so the hostname
+    // isn't checked, but the chain still is.
+    pub fn new(
+        base_url: &str,
+        ca_pem: &[u8],
+        cert_pem: &[u8],
+        key_pem: &[u8],
+    ) -> Result<Self, RpcError> {
+        let mut identity = Vec::with_capacity(cert_pem.len() + key_pem.len());
+        identity.extend_from_slice(cert_pem);
+        identity.extend_from_slice(key_pem);
+        let client = Client::builder()
+            .use_rustls_tls()
+            .tls_built_in_root_certs(false)
+            .add_root_certificate(Certificate::from_pem(ca_pem)?)
+            .danger_accept_invalid_hostnames(true)
+            .identity(Identity::from_pem(&identity)?)
+            .build()?;
+        Ok(Self::with_client(client, base_url))
+    }
+
+    // use a pre-configured client, e.g. to set timeouts or a proxy
+    pub fn with_client(client: Client, base_url: &str) -> Self {
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        body: serde_json::Value,
+    ) -> Result<T, RpcError> {
+        let response = self
+            .client
+            .post(format!("{}/{endpoint}", self.base_url))
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?;
+        parse_response(&response.bytes().await?)
+    }
+
+    pub async fn push_tx(&self, spend_bundle: &SpendBundle) -> Result<TransactionStatus, RpcError> {
+        let response: PushTxResponse = self
+            .request("push_tx", json!({ "spend_bundle": spend_bundle }))
+            .await?;
+        Ok(response.status)
+    }
+
+    pub async fn get_coin_records_by_puzzle_hash(
+        &self,
+        puzzle_hash: Bytes32,
+        start_height: Option<u32>,
+        end_height: Option<u32>,
+        include_spent_coins: bool,
+    ) -> Result<Vec<CoinRecord>, RpcError> {
+        let mut body = json!({
+            "puzzle_hash": puzzle_hash,
+            "include_spent_coins": include_spent_coins,
+        });
+        if let Some(height) = start_height {
+            body["start_height"] = height.into();
+        }
+        if let Some(height) = end_height {
+            body["end_height"] = height.into();
+        }
+        let response: CoinRecordsResponse = self
+            .request("get_coin_records_by_puzzle_hash", body)
+            .await?;
+        Ok(response.coin_records)
+    }
+
+    pub async fn get_block(&self, header_hash: Bytes32) -> Result<FullBlock, RpcError> {
+        let response: BlockResponse = self
+            .request("get_block", json!({ "header_hash": header_hash }))
+            .await?;
+        Ok(response.block)
+    }
+
+    pub async fn get_blockchain_state(&self) -> Result<BlockchainState, RpcError> {
+        let response: BlockchainStateResponse =
+            self.request("get_blockchain_state", json!({})).await?;
+        Ok(response.blockchain_state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_response() {
+        let body = br#"{"success": false, "error": "Block 0x00 not found"}"#;
+        let err = parse_response::<BlockResponse>(body).unwrap_err();
+        assert_eq!(err.to_string(), "rpc error: Block 0x00 not found");
+
+        let body = br#"{"success": false}"#;
+        let err = parse_response::<BlockResponse>(body).unwrap_err();
+        assert_eq!(err.to_string(), "rpc error: request failed");
+    }
+
+    #[test]
+    fn test_push_tx_response() {
+        let body = br#"{"status": "PENDING", "success": true}"#;
+        let response: PushTxResponse = parse_response(body).unwrap();
+        assert_eq!(response.status, TransactionStatus::Pending);
+    }
+
+    #[test]
+    fn test_coin_records_response() {
+        let body = format!(
+            r#"{{"coin_records": [{{
+                "coin": {{
+                    "parent_coin_info": "0x{}",
+                    "puzzle_hash": "0x{}",
+                    "amount": 1750000000000
+                }},
+                "coinbase": true,
+                "confirmed_block_index": 2,
+                "spent": false,
+                "spent_block_index": 0,
+                "timestamp": 1616108230
+            }}], "success": true}}"#,
+            "11".repeat(32),
+            "22".repeat(32)
+        );
+        let response: CoinRecordsResponse = parse_response(body.as_bytes()).unwrap();
+        assert_eq!(
+            response.coin_records,
+            vec![CoinRecord {
+                coin: Coin::new(
+                    Bytes32::new([0x11; 32]),
+                    Bytes32::new([0x22; 32]),
+                    1750000000000
+                ),
+                confirmed_block_index: 2,
+                spent_block_index: 0,
+                coinbase: true,
+                timestamp: 1616108230,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_large_integers() {
+        // the space is larger than fits in a u64
+        let body = br#"{"blockchain_state": {
+            "peak": null,
+            "genesis_challenge_initialized": true,
+            "sync": {"sync_mode": false, "synced": true, "sync_tip_height": 0, "sync_progress_height": 0},
+            "difficulty": 2048,
+            "sub_slot_iters": 578813952,
+            "space": 36893488147419103232,
+            "mempool_size": 0,
+            "mempool_cost": 0,
+            "mempool_min_fees": {"cost_5000000": 0},
+            "mempool_max_total_cost": 550000000000,
+            "block_max_cost": 11000000000,
+            "node_id": "abcd"
+        }, "success": true}"#;
+        let response: BlockchainStateResponse = parse_response(body).unwrap();
+        assert_eq!(response.blockchain_state.space, 1_u128 << 65);
+        assert_eq!(response.blockchain_state.peak, None);
+    }
+}
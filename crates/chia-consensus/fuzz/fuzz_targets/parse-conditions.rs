@@ -70,6 +70,7 @@ fuzz_target!(|data: &[u8]| {
             agg_sig_parent_amount: Vec::new(),
             agg_sig_parent_puzzle: Vec::new(),
             flags: 0_u32,
+            ff_ineligible: 0_u32,
         };
         let mut visitor = MempoolVisitor::new_spend(&mut coin_spend);
         let mut max_cost: u64 = 3300000000;
@@ -0,0 +1,20 @@
+#![no_main]
+use arbitrary::{Arbitrary, Unstructured};
+use chia_consensus::consensus_constants::ConsensusConstants;
+use chia_consensus::gen::owned_conditions::{OwnedSpend, OwnedSpendBundleConditions};
+use chia_traits::Streamable;
+use libfuzzer_sys::fuzz_target;
+
+fn test<'a, T: Arbitrary<'a> + Streamable + std::fmt::Debug + PartialEq>(data: &'a [u8]) {
+    let mut u = Unstructured::new(data);
+    let obj = <T as Arbitrary<'a>>::arbitrary(&mut u).unwrap();
+    let bytes = obj.to_bytes().unwrap();
+    assert_eq!(T::from_bytes(&bytes).unwrap(), obj);
+    assert_eq!(T::from_bytes_unchecked(&bytes).unwrap(), obj);
+}
+
+fuzz_target!(|data: &[u8]| {
+    test::<OwnedSpend>(data);
+    test::<OwnedSpendBundleConditions>(data);
+    test::<ConsensusConstants>(data);
+});
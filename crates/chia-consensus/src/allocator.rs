@@ -2,9 +2,53 @@ use clvmr::allocator::Allocator;
 use clvmr::chia_dialect::LIMIT_HEAP;
 
 pub fn make_allocator(flags: u32) -> Allocator {
-    if flags & LIMIT_HEAP != 0 {
-        Allocator::new_limited(500000000)
+    make_allocator_with_limit(flags, None)
+}
+
+// like make_allocator(), but the heap may be limited further, to max_heap_bytes.
+// Running out of heap is a GeneratorRuntimeError, unless the program is run
+// with report_oom set (see run_puzzle_with_limits()), in which case it fails
+// with OutOfMemory.
+// Only the heap size can be limited. There's no limit on the number of nodes:
+// clvmr fixes the max number of atoms and pairs of an Allocator, and has no
+// way to lower it
+pub fn make_allocator_with_limit(flags: u32, max_heap_bytes: Option<usize>) -> Allocator {
+    let limit = if flags & LIMIT_HEAP != 0 {
+        500000000
     } else {
-        Allocator::new_limited(u32::MAX as usize)
+        u32::MAX as usize
+    };
+    Allocator::new_limited(max_heap_bytes.map_or(limit, |max| max.min(limit)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gen::validation_error::{eval_err, ErrorCode};
+    use clvmr::chia_dialect::ChiaDialect;
+    use clvmr::run_program::run_program;
+    use clvmr::serde::node_from_bytes;
+
+    #[test]
+    fn test_max_heap_bytes() {
+        // (concat 1 1 1 1 1 1 1 1) with a 100 byte atom as the argument
+        let program = hex::decode("ff0eff01ff01ff01ff01ff01ff01ff01ff0180").unwrap();
+        let mut args = vec![0xc0, 100];
+        args.extend_from_slice(&[0x42; 100]);
+
+        for (max_heap_bytes, report_oom, expect) in [
+            (None, false, None),
+            (None, true, None),
+            (Some(500), false, Some(ErrorCode::GeneratorRuntimeError)),
+            (Some(500), true, Some(ErrorCode::OutOfMemory)),
+        ] {
+            let mut a = make_allocator_with_limit(LIMIT_HEAP, max_heap_bytes);
+            let program = node_from_bytes(&mut a, &program).unwrap();
+            let args = node_from_bytes(&mut a, &args).unwrap();
+            let dialect = ChiaDialect::new(0);
+            let result = run_program(&mut a, &dialect, program, args, 11000000000)
+                .map_err(|e| eval_err(e, report_oom));
+            assert_eq!(result.err().map(|e| e.1), expect);
+        }
     }
 }
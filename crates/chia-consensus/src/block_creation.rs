@@ -0,0 +1,246 @@
+use crate::gen::validation_error::{ErrorCode, ValidationErr};
+use chia_protocol::{
+    BlockRecord, Bytes32, EndOfSubSlotBundle, FullBlock, NewInfusionPointVDF, RewardChainBlock,
+    UnfinishedBlock,
+};
+use chia_traits::Streamable;
+use clvmr::allocator::NodePtr;
+
+fn err(code: ErrorCode) -> ValidationErr {
+    ValidationErr(NodePtr::NIL, code)
+}
+
+// Turns an unfinished block into a full block, once the timelord has
+// infused it. ip holds the infusion point VDFs, and finished_sub_slots the
+// sub-slots that ended before the block (for overflow blocks, these may differ
+// from the ones in the unfinished block). prev is the block this one is
+// infused after (None for the genesis block) and is_transaction_block whether
+// this becomes a transaction block, which the caller determines from the
+// previous transaction block and the signage point. If it doesn't, the
+// transactions of the unfinished block are dropped.
+// The foliage of the unfinished block commits to the unfinished reward chain
+// block, which is why it stays valid; only the hash of the full reward chain
+// block is updated.
+pub fn finish_block(
+    unfinished: &UnfinishedBlock,
+    ip: &NewInfusionPointVDF,
+    finished_sub_slots: Vec<EndOfSubSlotBundle>,
+    prev: Option<&BlockRecord>,
+    difficulty: u64,
+    is_transaction_block: bool,
+) -> Result<FullBlock, ValidationErr> {
+    if ip.unfinished_reward_hash != unfinished.partial_hash() {
+        return Err(err(ErrorCode::InvalidRewardBlockHash));
+    }
+    if ip.infused_challenge_chain_ip_vdf.is_some() != ip.infused_challenge_chain_ip_proof.is_some()
+    {
+        return Err(err(ErrorCode::InvalidIccVdf));
+    }
+
+    // the genesis block is always a transaction block
+    let (weight, height, is_transaction_block) = match prev {
+        Some(prev) => (
+            prev.weight + difficulty as u128,
+            prev.height + 1,
+            is_transaction_block,
+        ),
+        None => (difficulty as u128, 0, true),
+    };
+
+    let mut foliage = unfinished.foliage.clone();
+    let (foliage_transaction_block, transactions_info, generator, generator_refs) =
+        if is_transaction_block {
+            (
+                unfinished.foliage_transaction_block.clone(),
+                unfinished.transactions_info.clone(),
+                unfinished.transactions_generator.clone(),
+                unfinished.transactions_generator_ref_list.clone(),
+            )
+        } else {
+            foliage.foliage_transaction_block_hash = None;
+            foliage.foliage_transaction_block_signature = None;
+            (None, None, None, vec![])
+        };
+
+    let rcb = &unfinished.reward_chain_block;
+    let reward_chain_block = RewardChainBlock::new(
+        weight,
+        height,
+        rcb.total_iters,
+        rcb.signage_point_index,
+        rcb.pos_ss_cc_challenge_hash,
+        rcb.proof_of_space.clone(),
+        rcb.challenge_chain_sp_vdf.clone(),
+        rcb.challenge_chain_sp_signature.clone(),
+        ip.challenge_chain_ip_vdf.clone(),
+        rcb.reward_chain_sp_vdf.clone(),
+        rcb.reward_chain_sp_signature.clone(),
+        ip.reward_chain_ip_vdf.clone(),
+        ip.infused_challenge_chain_ip_vdf.clone(),
+        is_transaction_block,
+    );
+    foliage.reward_block_hash = Bytes32::from(reward_chain_block.hash());
+
+    Ok(FullBlock::new(
+        finished_sub_slots,
+        reward_chain_block,
+        unfinished.challenge_chain_sp_proof.clone(),
+        ip.challenge_chain_ip_proof.clone(),
+        unfinished.reward_chain_sp_proof.clone(),
+        ip.reward_chain_ip_proof.clone(),
+        ip.infused_challenge_chain_ip_proof.clone(),
+        foliage,
+        foliage_transaction_block,
+        transactions_info,
+        generator,
+        generator_refs,
+    ))
+}
+
+#[cfg(all(test, feature = "test_blocks"))]
+mod tests {
+    use super::*;
+    use crate::consensus_constants::TEST_CONSTANTS;
+    use crate::test_block_record::BlockRecordBuilder;
+    use crate::test_blocks::BlockTools;
+    use chia_protocol::{ClassgroupElement, VDFInfo};
+
+    fn record(block: &FullBlock) -> BlockRecord {
+        BlockRecordBuilder::new(block.header_hash())
+            .prev_hash(block.prev_header_hash())
+            .height(block.height())
+            .weight(block.weight())
+            .total_iters(block.total_iters())
+            .signage_point_index(block.reward_chain_block.signage_point_index)
+            .build()
+    }
+
+    fn unfinished(block: &FullBlock) -> UnfinishedBlock {
+        UnfinishedBlock::new(
+            block.finished_sub_slots.clone(),
+            block.reward_chain_block.get_unfinished(),
+            block.challenge_chain_sp_proof.clone(),
+            block.reward_chain_sp_proof.clone(),
+            block.foliage.clone(),
+            block.foliage_transaction_block.clone(),
+            block.transactions_info.clone(),
+            block.transactions_generator.clone(),
+            block.transactions_generator_ref_list.clone(),
+        )
+    }
+
+    fn infusion_point(block: &FullBlock) -> NewInfusionPointVDF {
+        NewInfusionPointVDF::new(
+            block.reward_chain_block.get_unfinished().hash().into(),
+            block.reward_chain_block.challenge_chain_ip_vdf.clone(),
+            block.challenge_chain_ip_proof.clone(),
+            block.reward_chain_block.reward_chain_ip_vdf.clone(),
+            block.reward_chain_ip_proof.clone(),
+            block
+                .reward_chain_block
+                .infused_challenge_chain_ip_vdf
+                .clone(),
+            block.infused_challenge_chain_ip_proof.clone(),
+        )
+    }
+
+    #[test]
+    fn test_finish_block() {
+        let bt = BlockTools::new(&TEST_CONSTANTS, &[4; 32]);
+        let blocks = bt.get_consecutive_blocks(3, None);
+        let difficulty = TEST_CONSTANTS.difficulty_starting;
+
+        let genesis = &blocks[0];
+        let finished = finish_block(
+            &unfinished(genesis),
+            &infusion_point(genesis),
+            genesis.finished_sub_slots.clone(),
+            None,
+            difficulty,
+            false,
+        )
+        .expect("finish_block");
+        assert_eq!(&finished, genesis);
+
+        for (prev, block) in blocks.iter().zip(&blocks[1..]) {
+            let finished = finish_block(
+                &unfinished(block),
+                &infusion_point(block),
+                block.finished_sub_slots.clone(),
+                Some(&record(prev)),
+                difficulty,
+                true,
+            )
+            .expect("finish_block");
+            assert_eq!(&finished, block);
+        }
+    }
+
+    #[test]
+    fn test_not_transaction_block() {
+        let bt = BlockTools::new(&TEST_CONSTANTS, &[4; 32]);
+        let blocks = bt.get_consecutive_blocks(2, None);
+        let block = &blocks[1];
+
+        let finished = finish_block(
+            &unfinished(block),
+            &infusion_point(block),
+            vec![],
+            Some(&record(&blocks[0])),
+            TEST_CONSTANTS.difficulty_starting,
+            false,
+        )
+        .expect("finish_block");
+        assert!(!finished.is_transaction_block());
+        assert!(finished.foliage_transaction_block.is_none());
+        assert!(finished.transactions_info.is_none());
+        assert_eq!(finished.foliage.foliage_transaction_block_hash, None);
+        assert_eq!(
+            finished.foliage.reward_block_hash,
+            Bytes32::from(finished.reward_chain_block.hash())
+        );
+        assert_eq!(finished.height(), block.height());
+        assert_eq!(finished.weight(), block.weight());
+    }
+
+    #[test]
+    fn test_wrong_infusion_point() {
+        let bt = BlockTools::new(&TEST_CONSTANTS, &[4; 32]);
+        let blocks = bt.get_consecutive_blocks(2, None);
+
+        // the infusion point of another block
+        assert_eq!(
+            finish_block(
+                &unfinished(&blocks[1]),
+                &infusion_point(&blocks[0]),
+                vec![],
+                Some(&record(&blocks[0])),
+                TEST_CONSTANTS.difficulty_starting,
+                true,
+            )
+            .unwrap_err()
+            .1,
+            ErrorCode::InvalidRewardBlockHash
+        );
+
+        let mut ip = infusion_point(&blocks[1]);
+        ip.infused_challenge_chain_ip_vdf = Some(VDFInfo::new(
+            Bytes32::default(),
+            0,
+            ClassgroupElement::default(),
+        ));
+        assert_eq!(
+            finish_block(
+                &unfinished(&blocks[1]),
+                &ip,
+                vec![],
+                Some(&record(&blocks[0])),
+                TEST_CONSTANTS.difficulty_starting,
+                true,
+            )
+            .unwrap_err()
+            .1,
+            ErrorCode::InvalidIccVdf
+        );
+    }
+}
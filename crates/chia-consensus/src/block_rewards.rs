@@ -0,0 +1,300 @@
+use crate::consensus_constants::ConsensusConstants;
+use crate::error::Error;
+use crate::gen::validation_error::{ErrorCode, ValidationErr};
+use chia_protocol::{BlockRecord, Bytes32, Coin};
+use clvmr::allocator::NodePtr;
+use std::collections::HashSet;
+
+// These match chia.consensus.block_rewards and chia.consensus.coinbase
+
+pub const MOJO_PER_CHIA: u64 = 1_000_000_000_000;
+
+// 32 blocks every 10 minutes
+pub const BLOCKS_PER_YEAR: u32 = 1_681_920;
+
+// One eighth of the reward of the block at the height, without fees. The pool
+// gets seven eighths and the farmer the rest. The genesis block pays out the
+// pre-farm of 21 million chia, after that the reward is 2 chia per block,
+// halving every three years until it reaches 0.125 chia after 12 years.
+fn reward_eighth(height: u32) -> u64 {
+    if height == 0 {
+        return 21_000_000 / 8 * MOJO_PER_CHIA;
+    }
+    let halvings = (height / (3 * BLOCKS_PER_YEAR)).min(4);
+    (2 * MOJO_PER_CHIA / 8) >> halvings
+}
+
+pub fn calculate_pool_reward(height: u32) -> u64 {
+    7 * reward_eighth(height)
+}
+
+// the farmer reward of the block, not counting the fees of its transactions
+pub fn calculate_base_farmer_reward(height: u32) -> u64 {
+    reward_eighth(height)
+}
+
+// reward coins have a parent coin ID made up of half of the genesis challenge
+// and the height of the block they reward
+fn reward_parent_id(prefix: &[u8], height: u32) -> Bytes32 {
+    let mut parent = [0_u8; 32];
+    parent[..16].copy_from_slice(prefix);
+    parent[16..].copy_from_slice(&(height as u128).to_be_bytes());
+    parent.into()
+}
+
+pub fn pool_parent_id(height: u32, genesis_challenge: &Bytes32) -> Bytes32 {
+    reward_parent_id(&genesis_challenge[..16], height)
+}
+
+pub fn farmer_parent_id(height: u32, genesis_challenge: &Bytes32) -> Bytes32 {
+    reward_parent_id(&genesis_challenge[16..], height)
+}
+
+pub fn create_pool_coin(
+    height: u32,
+    puzzle_hash: Bytes32,
+    reward: u64,
+    constants: &ConsensusConstants,
+) -> Coin {
+    Coin::new(
+        pool_parent_id(height, &constants.genesis_challenge),
+        puzzle_hash,
+        reward,
+    )
+}
+
+pub fn create_farmer_coin(
+    height: u32,
+    puzzle_hash: Bytes32,
+    reward: u64,
+    constants: &ConsensusConstants,
+) -> Coin {
+    Coin::new(
+        farmer_parent_id(height, &constants.genesis_challenge),
+        puzzle_hash,
+        reward,
+    )
+}
+
+// The pool and farmer reward coins of the block. The fees of a transaction
+// block go to its farmer, non-transaction blocks don't have any.
+pub fn block_reward_coins(block: &BlockRecord, constants: &ConsensusConstants) -> [Coin; 2] {
+    [
+        create_pool_coin(
+            block.height,
+            block.pool_puzzle_hash,
+            calculate_pool_reward(block.height),
+            constants,
+        ),
+        create_farmer_coin(
+            block.height,
+            block.farmer_puzzle_hash,
+            calculate_base_farmer_reward(block.height) + block.fees.unwrap_or(0),
+            constants,
+        ),
+    ]
+}
+
+// The reward coins a transaction block claims, given the previous transaction
+// block: the rewards of that block and of the non-transaction blocks before it,
+// back to the transaction block before that. The genesis block doesn't claim
+// any rewards, so it has no previous transaction block. lookup returns the
+// block record with the specified header hash, or None if it's not known, like
+// for find_fork_point().
+pub fn expected_reward_coins<E, F>(
+    constants: &ConsensusConstants,
+    prev_transaction_block: &BlockRecord,
+    mut lookup: F,
+) -> Result<Vec<Coin>, E>
+where
+    E: From<Error>,
+    F: FnMut(&Bytes32) -> Result<Option<BlockRecord>, E>,
+{
+    let mut ret = block_reward_coins(prev_transaction_block, constants).to_vec();
+
+    // the genesis block doesn't have any blocks before it
+    if prev_transaction_block.height == 0 {
+        return Ok(ret);
+    }
+    let mut prev_hash = prev_transaction_block.prev_hash;
+    loop {
+        let curr = lookup(&prev_hash)?.ok_or(Error::BlockNotFound(prev_hash))?;
+        if curr.is_transaction_block() {
+            break;
+        }
+        ret.extend(block_reward_coins(&curr, constants));
+        prev_hash = curr.prev_hash;
+    }
+    Ok(ret)
+}
+
+// checks the reward claims incorporated of a transaction block against
+// expected_reward_coins(). The order doesn't matter, but every coin has to be
+// claimed exactly once
+pub fn validate_reward_claims(
+    reward_claims: &[Coin],
+    expected: &[Coin],
+) -> Result<(), ValidationErr> {
+    let expected: HashSet<&Coin> = expected.iter().collect();
+    let claims: HashSet<&Coin> = reward_claims.iter().collect();
+    if claims != expected || reward_claims.len() != expected.len() {
+        return Err(ValidationErr(NodePtr::NIL, ErrorCode::InvalidRewardCoins));
+    }
+    Ok(())
+}
+
+// The genesis block has to pay its rewards, the pre-farm, to the pool and
+// farmer puzzle hashes of the constants. Other blocks can pay anyone.
+pub fn validate_prefarm(
+    height: u32,
+    pool_puzzle_hash: &Bytes32,
+    farmer_puzzle_hash: &Bytes32,
+    constants: &ConsensusConstants,
+) -> Result<(), ValidationErr> {
+    if height == 0
+        && (*pool_puzzle_hash != constants.genesis_pre_farm_pool_puzzle_hash
+            || *farmer_puzzle_hash != constants.genesis_pre_farm_farmer_puzzle_hash)
+    {
+        return Err(ValidationErr(NodePtr::NIL, ErrorCode::InvalidPrefarm));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus_constants::TEST_CONSTANTS;
+    use crate::test_block_record::{hash, BlockRecordBuilder};
+    use rstest::rstest;
+    use std::collections::HashMap;
+
+    // every block pays the pool puzzle hash [1; 32] and the farmer puzzle hash
+    // [2; 32]. Transaction blocks have a fee of 10
+    fn record(height: u32, is_tx_block: bool) -> BlockRecord {
+        let mut builder = BlockRecordBuilder::new(hash(height))
+            .prev_hash(if height == 0 {
+                TEST_CONSTANTS.genesis_challenge
+            } else {
+                hash(height - 1)
+            })
+            .height(height)
+            .weight(height as u128)
+            .total_iters(height as u128 * 10)
+            .pool_puzzle_hash(Bytes32::new([1; 32]))
+            .farmer_puzzle_hash(Bytes32::new([2; 32]));
+        if is_tx_block {
+            builder = builder.timestamp(height as u64).fees(10);
+        }
+        builder.build()
+    }
+
+    #[rstest]
+    #[case(0, 18_375_000_000_000_000_000, 2_625_000_000_000_000_000)]
+    #[case(1, 1_750_000_000_000, 250_000_000_000)]
+    #[case(3 * BLOCKS_PER_YEAR - 1, 1_750_000_000_000, 250_000_000_000)]
+    #[case(3 * BLOCKS_PER_YEAR, 875_000_000_000, 125_000_000_000)]
+    #[case(6 * BLOCKS_PER_YEAR, 437_500_000_000, 62_500_000_000)]
+    #[case(9 * BLOCKS_PER_YEAR, 218_750_000_000, 31_250_000_000)]
+    #[case(12 * BLOCKS_PER_YEAR, 109_375_000_000, 15_625_000_000)]
+    #[case(u32::MAX, 109_375_000_000, 15_625_000_000)]
+    fn test_rewards(#[case] height: u32, #[case] pool: u64, #[case] farmer: u64) {
+        assert_eq!(calculate_pool_reward(height), pool);
+        assert_eq!(calculate_base_farmer_reward(height), farmer);
+    }
+
+    #[test]
+    fn test_reward_coins() {
+        let genesis = Bytes32::new([7; 32]);
+        let mut expected = [7_u8; 32];
+        expected[16..].copy_from_slice(&1000_u128.to_be_bytes());
+        assert_eq!(pool_parent_id(1000, &genesis), Bytes32::new(expected));
+        assert_eq!(farmer_parent_id(1000, &genesis), Bytes32::new(expected));
+
+        let genesis = TEST_CONSTANTS.genesis_challenge;
+        let pool = create_pool_coin(5, Bytes32::new([1; 32]), 100, &TEST_CONSTANTS);
+        assert_eq!(pool.parent_coin_info[..16], genesis[..16]);
+        assert_eq!(pool.amount, 100);
+        let farmer = create_farmer_coin(5, Bytes32::new([2; 32]), 200, &TEST_CONSTANTS);
+        assert_eq!(farmer.parent_coin_info[..16], genesis[16..]);
+        assert_ne!(pool.coin_id(), farmer.coin_id());
+
+        // the fees of a transaction block go to the farmer
+        let [_, farmer] = block_reward_coins(&record(5, true), &TEST_CONSTANTS);
+        assert_eq!(farmer.amount, calculate_base_farmer_reward(5) + 10);
+        let [_, farmer] = block_reward_coins(&record(5, false), &TEST_CONSTANTS);
+        assert_eq!(farmer.amount, calculate_base_farmer_reward(5));
+    }
+
+    #[test]
+    fn test_expected_reward_coins() {
+        // blocks 3 and 4 aren't transaction blocks
+        let blocks: HashMap<Bytes32, BlockRecord> = (0..7)
+            .map(|h| record(h, h != 3 && h != 4))
+            .map(|r| (r.header_hash, r))
+            .collect();
+        let expected = |height: u32| {
+            expected_reward_coins::<Error, _>(&TEST_CONSTANTS, &blocks[&hash(height)], |h| {
+                Ok(blocks.get(h).cloned())
+            })
+            .expect("expected_reward_coins")
+        };
+
+        // the block after genesis claims the pre-farm
+        let coins = expected(0);
+        assert_eq!(
+            coins,
+            block_reward_coins(&blocks[&hash(0)], &TEST_CONSTANTS)
+        );
+        assert_eq!(coins[0].amount, calculate_pool_reward(0));
+        assert_eq!(coins[0].puzzle_hash, Bytes32::new([1; 32]));
+
+        assert_eq!(expected(2).len(), 2);
+        assert_eq!(expected(5).len(), 6);
+        let mut coins = expected(5);
+        for h in [3, 4] {
+            for c in block_reward_coins(&blocks[&hash(h)], &TEST_CONSTANTS) {
+                assert!(coins.contains(&c));
+            }
+        }
+
+        validate_reward_claims(&coins, &expected(5)).expect("valid");
+        coins.reverse();
+        validate_reward_claims(&coins, &expected(5)).expect("any order");
+        let missing = &coins[1..];
+        assert_eq!(
+            validate_reward_claims(missing, &expected(5)).unwrap_err().1,
+            ErrorCode::InvalidRewardCoins
+        );
+        let mut duplicate = coins.clone();
+        duplicate.push(coins[0]);
+        assert_eq!(
+            validate_reward_claims(&duplicate, &expected(5))
+                .unwrap_err()
+                .1,
+            ErrorCode::InvalidRewardCoins
+        );
+
+        // block 4 is missing
+        let result = expected_reward_coins::<Error, _>(&TEST_CONSTANTS, &blocks[&hash(5)], |h| {
+            Ok(blocks.get(h).filter(|b| b.height != 4).cloned())
+        });
+        assert_eq!(result, Err(Error::BlockNotFound(hash(4))));
+    }
+
+    #[test]
+    fn test_prefarm() {
+        let pool = TEST_CONSTANTS.genesis_pre_farm_pool_puzzle_hash;
+        let farmer = TEST_CONSTANTS.genesis_pre_farm_farmer_puzzle_hash;
+        let other = Bytes32::new([3; 32]);
+        validate_prefarm(0, &pool, &farmer, &TEST_CONSTANTS).expect("prefarm");
+        for (pool, farmer) in [(other, farmer), (pool, other), (farmer, pool)] {
+            assert_eq!(
+                validate_prefarm(0, &pool, &farmer, &TEST_CONSTANTS)
+                    .unwrap_err()
+                    .1,
+                ErrorCode::InvalidPrefarm
+            );
+            validate_prefarm(1, &pool, &farmer, &TEST_CONSTANTS).expect("not genesis");
+        }
+    }
+}
@@ -0,0 +1,300 @@
+use crate::consensus_constants::ConsensusConstants;
+use crate::gen::owned_conditions::OwnedSpendBundleConditions;
+use crate::gen::signing::messages_to_sign;
+use crate::gen::validation_error::{ErrorCode, ValidationErr};
+use chia_bls::{aggregate_verify, verify};
+use chia_protocol::FullBlock;
+use chia_traits::Streamable;
+use clvmr::allocator::NodePtr;
+
+fn err(code: ErrorCode) -> ValidationErr {
+    ValidationErr(NodePtr::NIL, code)
+}
+
+// Validates the signatures of a block:
+// * the plot key signature of the foliage block data
+// * the plot key signature of the foliage transaction block (if any)
+// * the pool signature of the pool target, or, for pool contract plots, that
+//   the pool target pays to the pool contract puzzle hash
+// * the aggregate signature in the transactions info (if any)
+// conds are the conditions from running the block's transactions generator.
+// For blocks without a generator, pass None. Passing None for a block with a
+// generator means no signatures are expected, which will fail unless the
+// aggregate signature is the identity.
+pub fn validate_block_signatures(
+    block: &FullBlock,
+    constants: &ConsensusConstants,
+    conds: Option<&OwnedSpendBundleConditions>,
+) -> Result<(), ValidationErr> {
+    let pos = &block.reward_chain_block.proof_of_space;
+    let plot_key = pos.plot_public_key();
+    let foliage = &block.foliage;
+    let block_data = &foliage.foliage_block_data;
+
+    // foliage block data, signed by the plot key
+    if !verify(
+        &foliage.foliage_block_data_signature,
+        &plot_key,
+        block_data.hash(),
+    ) {
+        return Err(err(ErrorCode::InvalidPlotSignature));
+    }
+
+    // pool target
+    match (&pos.pool_public_key, &pos.pool_contract_puzzle_hash) {
+        (Some(pool_pk), None) => {
+            let Some(pool_sig) = &block_data.pool_signature else {
+                return Err(err(ErrorCode::InvalidPoolSignature));
+            };
+            let pool_target = block_data
+                .pool_target
+                .to_bytes()
+                .map_err(|_| err(ErrorCode::InvalidPoolTarget))?;
+            if !verify(pool_sig, pool_pk, pool_target) {
+                return Err(err(ErrorCode::InvalidPoolSignature));
+            }
+        }
+        (None, Some(contract_ph)) => {
+            if block_data.pool_signature.is_some() {
+                return Err(err(ErrorCode::InvalidPoolSignature));
+            }
+            if block_data.pool_target.puzzle_hash != *contract_ph {
+                return Err(err(ErrorCode::InvalidPoolTarget));
+            }
+        }
+        _ => {
+            return Err(err(ErrorCode::InvalidPospace));
+        }
+    }
+
+    // foliage transaction block, signed by the plot key
+    match (
+        &foliage.foliage_transaction_block_hash,
+        &foliage.foliage_transaction_block_signature,
+    ) {
+        (Some(hash), Some(sig)) => {
+            if !verify(sig, &plot_key, hash) {
+                return Err(err(ErrorCode::InvalidPlotSignature));
+            }
+        }
+        (None, None) => {}
+        _ => {
+            return Err(err(ErrorCode::InvalidFoliageBlockPresence));
+        }
+    }
+
+    // the aggregate signature of all spends in the block
+    if let Some(ti) = &block.transactions_info {
+        let pairs = conds.map_or_else(Vec::new, |c| messages_to_sign(c, constants));
+        if !aggregate_verify(
+            &ti.aggregated_signature,
+            pairs.iter().map(|(pk, msg)| (pk, msg.as_slice())),
+        ) {
+            return Err(err(ErrorCode::BadAggregateSignature));
+        }
+    } else if block.transactions_generator.is_some() {
+        return Err(err(ErrorCode::NotBlockButHasData));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus_constants::TEST_CONSTANTS;
+    use chia_bls::{sign, SecretKey, Signature};
+    use chia_protocol::{
+        Bytes32, ClassgroupElement, Foliage, FoliageBlockData, PoolTarget, ProofOfSpace,
+        RewardChainBlock, TransactionsInfo, VDFInfo, VDFProof,
+    };
+
+    fn vdf_info() -> VDFInfo {
+        VDFInfo::new(Bytes32::default(), 0, ClassgroupElement::default())
+    }
+
+    fn vdf_proof() -> VDFProof {
+        VDFProof::new(0, vec![].into(), true)
+    }
+
+    fn make_block(
+        farmer_sk: &SecretKey,
+        local_sk: &SecretKey,
+        pool_sk: &SecretKey,
+        transactions_info: Option<TransactionsInfo>,
+    ) -> FullBlock {
+        let pos = ProofOfSpace::new(
+            Bytes32::default(),
+            Some(pool_sk.public_key()),
+            None,
+            local_sk.public_key(),
+            32,
+            vec![0; 64].into(),
+            farmer_sk.public_key(),
+        );
+        let plot_sk = local_sk + farmer_sk;
+        assert_eq!(plot_sk.public_key(), pos.plot_public_key());
+
+        let reward_chain_block = RewardChainBlock::new(
+            1,
+            1,
+            1,
+            0,
+            Bytes32::default(),
+            pos,
+            None,
+            Signature::default(),
+            vdf_info(),
+            None,
+            Signature::default(),
+            vdf_info(),
+            None,
+            transactions_info.is_some(),
+        );
+
+        let pool_target = PoolTarget::new(Bytes32::from([1; 32]), 0);
+        let pool_signature = sign(pool_sk, pool_target.to_bytes().unwrap());
+        let block_data = FoliageBlockData::new(
+            Bytes32::default(),
+            pool_target,
+            Some(pool_signature),
+            Bytes32::from([2; 32]),
+            Bytes32::default(),
+        );
+        let block_data_signature = sign(&plot_sk, block_data.hash());
+
+        let (ftb_hash, ftb_signature) = if transactions_info.is_some() {
+            let hash = Bytes32::from([3; 32]);
+            (Some(hash), Some(sign(&plot_sk, hash)))
+        } else {
+            (None, None)
+        };
+
+        let foliage = Foliage::new(
+            Bytes32::default(),
+            Bytes32::default(),
+            block_data,
+            block_data_signature,
+            ftb_hash,
+            ftb_signature,
+        );
+
+        FullBlock::new(
+            vec![],
+            reward_chain_block,
+            None,
+            vdf_proof(),
+            None,
+            vdf_proof(),
+            None,
+            foliage,
+            None,
+            transactions_info,
+            None,
+            vec![],
+        )
+    }
+
+    fn keys() -> (SecretKey, SecretKey, SecretKey) {
+        (
+            SecretKey::from_seed(&[1; 32]),
+            SecretKey::from_seed(&[2; 32]),
+            SecretKey::from_seed(&[3; 32]),
+        )
+    }
+
+    #[test]
+    fn test_valid_block() {
+        let (farmer, local, pool) = keys();
+        let block = make_block(&farmer, &local, &pool, None);
+        assert_eq!(
+            validate_block_signatures(&block, &TEST_CONSTANTS, None),
+            Ok(())
+        );
+
+        let ti = TransactionsInfo::new(
+            Bytes32::default(),
+            Bytes32::default(),
+            Signature::default(),
+            0,
+            0,
+            vec![],
+        );
+        let block = make_block(&farmer, &local, &pool, Some(ti));
+        assert_eq!(
+            validate_block_signatures(&block, &TEST_CONSTANTS, None),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_invalid_plot_signature() {
+        let (farmer, local, pool) = keys();
+        let mut block = make_block(&farmer, &local, &pool, None);
+        block.foliage.foliage_block_data_signature = sign(&farmer, b"foobar");
+        assert_eq!(
+            validate_block_signatures(&block, &TEST_CONSTANTS, None)
+                .unwrap_err()
+                .1,
+            ErrorCode::InvalidPlotSignature
+        );
+    }
+
+    #[test]
+    fn test_invalid_pool_signature() {
+        let (farmer, local, pool) = keys();
+        let mut block = make_block(&farmer, &local, &pool, None);
+        block.foliage.foliage_block_data.pool_signature = None;
+        // the plot signature covers the pool signature, so we have to re-sign
+        let plot_sk = &local + &farmer;
+        block.foliage.foliage_block_data_signature =
+            sign(&plot_sk, block.foliage.foliage_block_data.hash());
+        assert_eq!(
+            validate_block_signatures(&block, &TEST_CONSTANTS, None)
+                .unwrap_err()
+                .1,
+            ErrorCode::InvalidPoolSignature
+        );
+    }
+
+    #[test]
+    fn test_bad_aggregate_signature() {
+        let (farmer, local, pool) = keys();
+        let ti = TransactionsInfo::new(
+            Bytes32::default(),
+            Bytes32::default(),
+            sign(&farmer, b"foobar"),
+            0,
+            0,
+            vec![],
+        );
+        let block = make_block(&farmer, &local, &pool, Some(ti));
+        assert_eq!(
+            validate_block_signatures(&block, &TEST_CONSTANTS, None)
+                .unwrap_err()
+                .1,
+            ErrorCode::BadAggregateSignature
+        );
+    }
+
+    #[test]
+    fn test_missing_transaction_block_signature() {
+        let (farmer, local, pool) = keys();
+        let ti = TransactionsInfo::new(
+            Bytes32::default(),
+            Bytes32::default(),
+            Signature::default(),
+            0,
+            0,
+            vec![],
+        );
+        let mut block = make_block(&farmer, &local, &pool, Some(ti));
+        block.foliage.foliage_transaction_block_signature = None;
+        assert_eq!(
+            validate_block_signatures(&block, &TEST_CONSTANTS, None)
+                .unwrap_err()
+                .1,
+            ErrorCode::InvalidFoliageBlockPresence
+        );
+    }
+}
@@ -0,0 +1,455 @@
+use crate::allocator::make_allocator;
+use crate::block_signatures::validate_block_signatures;
+use crate::consensus_constants::ConsensusConstants;
+use crate::fork_schedule::{Fork, ForkSchedule};
+use crate::gen::conditions::EmptyVisitor;
+use crate::gen::owned_conditions::OwnedSpendBundleConditions;
+use crate::gen::run_block_generator::{run_block_generator, run_block_generator2};
+use crate::gen::validation_error::{ErrorCode, ValidationErr};
+use crate::generator_hash::validate_generator_hashes;
+use chia_protocol::{Bytes32, FullBlock};
+use chia_traits::Streamable;
+use clvmr::allocator::NodePtr;
+
+#[cfg(feature = "py-bindings")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "py-bindings")]
+use pyo3::prelude::*;
+#[cfg(feature = "py-bindings")]
+use pyo3::pybacked::PyBackedBytes;
+
+fn err(code: ErrorCode) -> ValidationErr {
+    ValidationErr(NodePtr::NIL, code)
+}
+
+// Validates a block in separate stages, that can be run at different times
+// (and on different threads):
+// 1. validate_header(), the checks that only need the block itself
+// 2. run_generator(), which runs the transactions generator (if any) and
+//    keeps the resulting conditions
+// 3. validate_signatures(), which checks the signatures of the block,
+//    including the aggregate signature of the spends
+// Each stage expects the previous ones to have passed. The checks that depend
+// on the rest of the chain (like the previous block, the sub-slot iterations
+// and the coin set) are left to the caller.
+#[cfg_attr(feature = "py-bindings", pyclass(module = "gold_rs"))]
+#[derive(Debug, Clone)]
+pub struct BlockValidator {
+    block: FullBlock,
+    constants: ConsensusConstants,
+    schedule: ForkSchedule,
+    conds: Option<OwnedSpendBundleConditions>,
+    generator_ran: bool,
+}
+
+impl BlockValidator {
+    pub fn new(block: FullBlock, constants: &ConsensusConstants) -> Self {
+        Self {
+            block,
+            constants: constants.clone(),
+            schedule: ForkSchedule::new(constants),
+            conds: None,
+            generator_ran: false,
+        }
+    }
+
+    pub fn block(&self) -> &FullBlock {
+        &self.block
+    }
+
+    // the conditions of the block's spends, once run_generator() has
+    // succeeded. None for blocks without a generator
+    pub fn conds(&self) -> Option<&OwnedSpendBundleConditions> {
+        self.conds.as_ref()
+    }
+
+    pub fn generator_ran(&self) -> bool {
+        self.generator_ran
+    }
+
+    // checks that the transaction block parts of the block are all present
+    // (or all absent), that they hash to what the foliage commits to, and that
+    // the generator and its refs match the transactions info
+    pub fn validate_header(&self) -> Result<(), ValidationErr> {
+        let block = &self.block;
+        let is_tx_block = block.reward_chain_block.is_transaction_block;
+
+        match (
+            &block.foliage.foliage_transaction_block_hash,
+            &block.foliage_transaction_block,
+        ) {
+            (Some(hash), Some(ftb)) => {
+                if !is_tx_block {
+                    return Err(err(ErrorCode::InvalidFoliageBlockPresence));
+                }
+                if *hash != Bytes32::from(ftb.hash()) {
+                    return Err(err(ErrorCode::InvalidFoliageBlockHash));
+                }
+            }
+            (None, None) => {
+                if is_tx_block {
+                    return Err(err(ErrorCode::InvalidFoliageBlockPresence));
+                }
+            }
+            _ => return Err(err(ErrorCode::InvalidFoliageBlockPresence)),
+        }
+
+        match (&block.foliage_transaction_block, &block.transactions_info) {
+            (Some(ftb), Some(info)) => {
+                if ftb.transactions_info_hash != Bytes32::from(info.hash()) {
+                    return Err(err(ErrorCode::InvalidTransactionsInfoHash));
+                }
+                validate_generator_hashes(
+                    info,
+                    block.transactions_generator.as_ref(),
+                    &block.transactions_generator_ref_list,
+                )?;
+            }
+            (None, None) => {
+                if block.transactions_generator.is_some()
+                    || !block.transactions_generator_ref_list.is_empty()
+                {
+                    return Err(err(ErrorCode::NotBlockButHasData));
+                }
+            }
+            _ => return Err(err(ErrorCode::IsTransactionBlockButNoData)),
+        }
+
+        if block.transactions_generator_ref_list.len()
+            > self.constants.max_generator_ref_list_size as usize
+        {
+            return Err(err(ErrorCode::TooManyGeneratorRefs));
+        }
+        Ok(())
+    }
+
+    // runs the transactions generator with the consensus rules in effect at
+    // the block's height, and checks its cost against the transactions info.
+    // block_refs are the generators of the blocks in the block's ref list, in
+    // the same order. The conditions are kept for validate_signatures() (and
+    // the caller, see conds())
+    pub fn run_generator<B: AsRef<[u8]>>(
+        &mut self,
+        block_refs: &[B],
+    ) -> Result<Option<&OwnedSpendBundleConditions>, ValidationErr> {
+        self.conds = None;
+        self.generator_ran = false;
+
+        if let Some(generator) = &self.block.transactions_generator {
+            if block_refs.len() != self.block.transactions_generator_ref_list.len() {
+                return Err(err(ErrorCode::GeneratorRefHasNoGenerator));
+            }
+            let height = self.block.height();
+            let flags = self.schedule.flags_for_height(height);
+            let max_cost = self.constants.max_block_cost_clvm;
+            let mut a = make_allocator(flags);
+            let conds = if self.schedule.is_active(Fork::HardFork, height) {
+                run_block_generator2::<_, EmptyVisitor>(
+                    &mut a,
+                    generator.as_slice(),
+                    block_refs,
+                    max_cost,
+                    flags,
+                )?
+            } else {
+                run_block_generator::<_, EmptyVisitor>(
+                    &mut a,
+                    generator.as_slice(),
+                    block_refs,
+                    max_cost,
+                    flags,
+                )?
+            };
+            let conds = OwnedSpendBundleConditions::from(&a, conds)
+                .map_err(|_| err(ErrorCode::InvalidPublicKey))?;
+
+            let info = self
+                .block
+                .transactions_info
+                .as_ref()
+                .ok_or(err(ErrorCode::NotBlockButHasData))?;
+            if conds.cost != info.cost {
+                return Err(err(ErrorCode::InvalidBlockCost));
+            }
+            self.conds = Some(conds);
+        }
+        self.generator_ran = true;
+        Ok(self.conds.as_ref())
+    }
+
+    // validates the plot, pool and aggregate signatures of the block. For
+    // blocks with a generator, run_generator() must have succeeded first
+    pub fn validate_signatures(&self) -> Result<(), ValidationErr> {
+        if self.block.transactions_generator.is_some() && !self.generator_ran {
+            return Err(err(ErrorCode::GeneratorRuntimeError));
+        }
+        validate_block_signatures(&self.block, &self.constants, self.conds.as_ref())
+    }
+}
+
+#[cfg(feature = "py-bindings")]
+#[pymethods]
+impl BlockValidator {
+    #[new]
+    fn py_new(block: FullBlock, constants: &ConsensusConstants) -> Self {
+        Self::new(block, constants)
+    }
+
+    #[getter(block)]
+    fn py_block(&self) -> FullBlock {
+        self.block.clone()
+    }
+
+    #[getter(conds)]
+    fn py_conds(&self) -> Option<OwnedSpendBundleConditions> {
+        self.conds.clone()
+    }
+
+    #[pyo3(name = "validate_header")]
+    fn py_validate_header(&self, py: Python<'_>) -> PyResult<()> {
+        Ok(py.allow_threads(|| self.validate_header())?)
+    }
+
+    #[pyo3(name = "run_generator", signature = (block_refs=Vec::new()))]
+    fn py_run_generator(
+        &mut self,
+        py: Python<'_>,
+        block_refs: Vec<PyBackedBytes>,
+    ) -> PyResult<Option<OwnedSpendBundleConditions>> {
+        Ok(py
+            .allow_threads(|| self.run_generator(&block_refs))?
+            .cloned())
+    }
+
+    #[pyo3(name = "validate_signatures")]
+    fn py_validate_signatures(&self, py: Python<'_>) -> PyResult<()> {
+        if self.block.transactions_generator.is_some() && !self.generator_ran {
+            return Err(PyValueError::new_err(
+                "run_generator() must succeed before validating the signatures",
+            ));
+        }
+        Ok(py.allow_threads(|| self.validate_signatures())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus_constants::TEST_CONSTANTS;
+    use crate::generator_hash::{generator_refs_root, generator_root};
+    use chia_bls::G2Element;
+    use chia_protocol::{
+        ClassgroupElement, Foliage, FoliageBlockData, FoliageTransactionBlock, PoolTarget, Program,
+        ProofOfSpace, RewardChainBlock, TransactionsInfo, VDFInfo, VDFProof,
+    };
+
+    fn vdf_info() -> VDFInfo {
+        VDFInfo::new(Bytes32::default(), 0, ClassgroupElement::default())
+    }
+
+    fn vdf_proof() -> VDFProof {
+        VDFProof::new(0, vec![].into(), true)
+    }
+
+    // a block with the specified generator, but no valid signatures
+    fn make_block(generator: Option<Program>, cost: u64) -> FullBlock {
+        let pos = ProofOfSpace::new(
+            Bytes32::default(),
+            None,
+            Some(Bytes32::default()),
+            Default::default(),
+            32,
+            vec![0; 64].into(),
+            Default::default(),
+        );
+        let reward_chain_block = RewardChainBlock::new(
+            1,
+            1,
+            1,
+            0,
+            Bytes32::default(),
+            pos,
+            None,
+            G2Element::default(),
+            vdf_info(),
+            None,
+            G2Element::default(),
+            vdf_info(),
+            None,
+            true,
+        );
+        let info = TransactionsInfo::new(
+            generator_root(generator.as_ref().map(Program::as_slice)),
+            generator_refs_root(&[]),
+            G2Element::default(),
+            0,
+            cost,
+            vec![],
+        );
+        let ftb = FoliageTransactionBlock::new(
+            Bytes32::default(),
+            Bytes32::default(),
+            Bytes32::default(),
+            Bytes32::default(),
+            info.hash().into(),
+            0,
+        );
+        let block_data = FoliageBlockData::new(
+            Bytes32::default(),
+            PoolTarget::new(Bytes32::default(), 0),
+            None,
+            Bytes32::default(),
+            Bytes32::default(),
+        );
+        let foliage = Foliage::new(
+            Bytes32::default(),
+            Bytes32::default(),
+            block_data,
+            G2Element::default(),
+            Some(ftb.hash().into()),
+            Some(G2Element::default()),
+        );
+        FullBlock::new(
+            vec![],
+            reward_chain_block,
+            None,
+            vdf_proof(),
+            None,
+            vdf_proof(),
+            None,
+            foliage,
+            Some(ftb),
+            Some(info),
+            generator,
+            vec![],
+        )
+    }
+
+    // (q . ((spends)))
+    fn empty_generator() -> Program {
+        Program::from(vec![0xff, 0x01, 0xff, 0x80, 0x80])
+    }
+
+    #[test]
+    fn test_header() {
+        let block = make_block(Some(empty_generator()), 0);
+        assert_eq!(
+            BlockValidator::new(block.clone(), &TEST_CONSTANTS).validate_header(),
+            Ok(())
+        );
+
+        let mut wrong_generator = block.clone();
+        wrong_generator.transactions_generator = Some(Program::from(vec![0x80]));
+        assert_eq!(
+            BlockValidator::new(wrong_generator, &TEST_CONSTANTS)
+                .validate_header()
+                .unwrap_err()
+                .1,
+            ErrorCode::InvalidTransactionsGeneratorHash
+        );
+
+        let mut no_info = block.clone();
+        no_info.transactions_info = None;
+        assert_eq!(
+            BlockValidator::new(no_info, &TEST_CONSTANTS)
+                .validate_header()
+                .unwrap_err()
+                .1,
+            ErrorCode::IsTransactionBlockButNoData
+        );
+
+        let mut wrong_ftb = block;
+        wrong_ftb.foliage.foliage_transaction_block_hash = Some(Bytes32::default());
+        assert_eq!(
+            BlockValidator::new(wrong_ftb, &TEST_CONSTANTS)
+                .validate_header()
+                .unwrap_err()
+                .1,
+            ErrorCode::InvalidFoliageBlockHash
+        );
+    }
+
+    #[test]
+    fn test_run_generator() {
+        let block = make_block(Some(empty_generator()), 0);
+        let mut validator = BlockValidator::new(block, &TEST_CONSTANTS);
+        assert!(!validator.generator_ran());
+        let refs: &[&[u8]] = &[];
+
+        // the cost of the generator doesn't match the transactions info
+        assert_eq!(
+            validator.run_generator(refs).unwrap_err().1,
+            ErrorCode::InvalidBlockCost
+        );
+        assert!(validator.conds().is_none());
+        assert!(!validator.generator_ran());
+
+        // the block is from before the hard fork
+        let flags = ForkSchedule::new(&TEST_CONSTANTS).flags_for_height(1);
+        let mut a = make_allocator(flags);
+        let cost = run_block_generator::<_, EmptyVisitor>(
+            &mut a,
+            empty_generator().as_slice(),
+            refs,
+            TEST_CONSTANTS.max_block_cost_clvm,
+            flags,
+        )
+        .unwrap()
+        .cost;
+        let block = make_block(Some(empty_generator()), cost);
+        let mut validator = BlockValidator::new(block, &TEST_CONSTANTS);
+        let conds = validator.run_generator(refs).unwrap().unwrap();
+        assert!(conds.spends.is_empty());
+        assert!(validator.generator_ran());
+    }
+
+    #[test]
+    fn test_signatures_require_generator() {
+        let block = make_block(Some(empty_generator()), 0);
+        let validator = BlockValidator::new(block, &TEST_CONSTANTS);
+        assert_eq!(
+            validator.validate_signatures().unwrap_err().1,
+            ErrorCode::GeneratorRuntimeError
+        );
+    }
+
+    #[cfg(feature = "test_blocks")]
+    #[test]
+    fn test_staged_validation() {
+        use crate::test_blocks::BlockTools;
+        use chia_protocol::{CoinSpend, SpendBundle};
+        use clvmr::serde::node_to_bytes;
+
+        // BlockTools creates generators in the format of the hard fork
+        let mut constants = TEST_CONSTANTS.clone();
+        constants.hard_fork_height = 0;
+        let bt = BlockTools::new(&constants, &[3; 32]);
+        let blocks = bt.get_consecutive_blocks(2, None);
+        let [pool_coin, _] = bt.reward_coins(&blocks[0]);
+
+        // spend the pool reward with the puzzle "1", paying a fee of 10
+        let mut a = make_allocator(0);
+        let amount = a.new_number((pool_coin.amount - 10).into()).unwrap();
+        let ph = a.new_atom(&pool_coin.puzzle_hash).unwrap();
+        let op = a.new_small_number(51).unwrap();
+        let mut cond = a.new_pair(amount, NodePtr::NIL).unwrap();
+        cond = a.new_pair(ph, cond).unwrap();
+        cond = a.new_pair(op, cond).unwrap();
+        let solution = a.new_pair(cond, NodePtr::NIL).unwrap();
+        let spend = CoinSpend::new(
+            pool_coin,
+            Program::from(vec![1]),
+            Program::from(node_to_bytes(&a, solution).unwrap()),
+        );
+        let bundle = SpendBundle::new(vec![spend], G2Element::default());
+        let block = bt.next_block(blocks.last(), &[bundle]).unwrap();
+
+        let mut validator = BlockValidator::new(block, &constants);
+        validator.validate_header().expect("header");
+        let refs: &[&[u8]] = &[];
+        let conds = validator.run_generator(refs).expect("generator").unwrap();
+        assert_eq!(conds.spends.len(), 1);
+        assert_eq!(conds.spends[0].coin_id, pool_coin.coin_id());
+        validator.validate_signatures().expect("signatures");
+    }
+}
@@ -0,0 +1,406 @@
+use crate::error::{Error, Result};
+use chia_protocol::{Bytes32, Compressed, FullBlock};
+use chia_traits::Streamable;
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+
+#[cfg(feature = "py-bindings")]
+use pyo3::prelude::*;
+
+// the only database layout supported. v1 databases have to be upgraded by the
+// node first ("chia db upgrade")
+const DATABASE_VERSION: i64 = 2;
+
+// the frame header of zstd-compressed data. The node stores blocks compressed,
+// but uncompressed blocks are accepted too
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+// the number of blocks loaded by each query when iterating over the chain
+const BATCH_SIZE: u32 = 100;
+
+fn parse_block(buf: &[u8]) -> Result<FullBlock> {
+    // the blocks in the database have already been validated by the node
+    let block = if buf.starts_with(&ZSTD_MAGIC) {
+        FullBlock::from_bytes_compressed_unchecked(buf)?
+    } else {
+        FullBlock::from_bytes_unchecked(buf)?
+    };
+    Ok(block)
+}
+
+// Read-only access to the blocks in the blockchain database of a full node
+// (blockchain_v2_mainnet.sqlite). Only blocks in the main chain are returned.
+#[cfg_attr(feature = "py-bindings", pyclass(module = "gold_rs", frozen))]
+pub struct ChainDb {
+    connection: Mutex<Connection>,
+}
+
+impl ChainDb {
+    // opens the database in read-only mode, so it's safe to use while the
+    // node is running
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let connection = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        Self::from_connection(connection)
+    }
+
+    pub fn from_connection(connection: Connection) -> Result<Self> {
+        let version: i64 =
+            connection.query_row("SELECT version FROM database_version LIMIT 1", [], |row| {
+                row.get(0)
+            })?;
+        if version != DATABASE_VERSION {
+            return Err(Error::Database(format!(
+                "unsupported database version {version}"
+            )));
+        }
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    // a panic while the connection was in use leaves the lock poisoned. The
+    // connection may be in the middle of a statement, so it's not used again
+    fn connection(&self) -> Result<MutexGuard<'_, Connection>> {
+        self.connection.lock().map_err(|_| {
+            Error::Database("the connection was poisoned by an earlier panic".to_string())
+        })
+    }
+
+    // the height of the current peak, or None if the database has no blocks
+    pub fn peak_height(&self) -> Result<Option<u32>> {
+        let connection = self.connection()?;
+        Ok(connection
+            .query_row(
+                "SELECT full_blocks.height FROM current_peak \
+                JOIN full_blocks ON full_blocks.header_hash = current_peak.hash \
+                WHERE current_peak.key = 0",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    pub fn block_at_height(&self, height: u32) -> Result<Option<FullBlock>> {
+        let connection = self.connection()?;
+        let buf: Option<Vec<u8>> = connection
+            .query_row(
+                "SELECT block FROM full_blocks WHERE height = ? AND in_main_chain = 1",
+                [height],
+                |row| row.get(0),
+            )
+            .optional()?;
+        buf.map(|buf| parse_block(&buf)).transpose()
+    }
+
+    // unlike the other lookups, this also finds blocks that are not in the main
+    // chain (orphaned blocks)
+    pub fn block_by_hash(&self, header_hash: &Bytes32) -> Result<Option<FullBlock>> {
+        let connection = self.connection()?;
+        let buf: Option<Vec<u8>> = connection
+            .query_row(
+                "SELECT block FROM full_blocks WHERE header_hash = ?",
+                [header_hash.as_ref()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        buf.map(|buf| parse_block(&buf)).transpose()
+    }
+
+    // the main chain blocks with heights in the range [start, end), ordered by
+    // height
+    pub fn blocks_in_range(&self, start: u32, end: u32) -> Result<Vec<FullBlock>> {
+        let connection = self.connection()?;
+        let mut statement = connection.prepare_cached(
+            "SELECT block FROM full_blocks \
+            WHERE height >= ? AND height < ? AND in_main_chain = 1 \
+            ORDER BY height",
+        )?;
+        let mut rows = statement.query([start, end])?;
+        let mut ret = Vec::new();
+        while let Some(row) = rows.next()? {
+            let buf: Vec<u8> = row.get(0)?;
+            ret.push(parse_block(&buf)?);
+        }
+        Ok(ret)
+    }
+
+    // the transactions generators of the blocks referenced by the block, in the
+    // order they are passed to its generator
+    pub fn generator_refs(&self, block: &FullBlock) -> Result<Vec<Vec<u8>>> {
+        let mut ret = Vec::with_capacity(block.transactions_generator_ref_list.len());
+        for height in &block.transactions_generator_ref_list {
+            let ref_block = self
+                .block_at_height(*height)?
+                .ok_or_else(|| Error::Database(format!("missing block at height {height}")))?;
+            let generator = ref_block.transactions_generator.ok_or_else(|| {
+                Error::Database(format!("block at height {height} has no generator"))
+            })?;
+            ret.push(generator.into());
+        }
+        Ok(ret)
+    }
+
+    // an iterator over the main chain blocks, starting at start_height and
+    // ending at end_height (exclusive) or the peak
+    pub fn blocks(&self, start_height: u32, end_height: Option<u32>) -> Result<Blocks<'_>> {
+        Ok(Blocks {
+            db: self,
+            cursor: BlockCursor::new(self, start_height, end_height)?,
+        })
+    }
+
+    // Calls callback with every main chain block, starting at start_height
+    // and ending at end_height (exclusive) or the peak. Iteration stops early
+    // if the callback returns false.
+    pub fn for_each_block(
+        &self,
+        start_height: u32,
+        end_height: Option<u32>,
+        mut callback: impl FnMut(FullBlock) -> bool,
+    ) -> Result<()> {
+        for block in self.blocks(start_height, end_height)? {
+            if !callback(block?) {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+// loads the blocks in batches, to not hold on to a query (and the database
+// lock) while the caller processes the blocks
+struct BlockCursor {
+    next_height: u32,
+    end_height: u32,
+    buffer: VecDeque<FullBlock>,
+}
+
+impl BlockCursor {
+    fn new(db: &ChainDb, start_height: u32, end_height: Option<u32>) -> Result<Self> {
+        let peak_end = db.peak_height()?.map_or(0, |h| h + 1);
+        Ok(Self {
+            next_height: start_height,
+            end_height: end_height.map_or(peak_end, |h| h.min(peak_end)),
+            buffer: VecDeque::new(),
+        })
+    }
+
+    fn next(&mut self, db: &ChainDb) -> Result<Option<FullBlock>> {
+        while self.buffer.is_empty() && self.next_height < self.end_height {
+            let end = self
+                .next_height
+                .saturating_add(BATCH_SIZE)
+                .min(self.end_height);
+            self.buffer = db.blocks_in_range(self.next_height, end)?.into();
+            self.next_height = end;
+        }
+        Ok(self.buffer.pop_front())
+    }
+}
+
+pub struct Blocks<'a> {
+    db: &'a ChainDb,
+    cursor: BlockCursor,
+}
+
+impl Iterator for Blocks<'_> {
+    type Item = Result<FullBlock>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor.next(self.db).transpose()
+    }
+}
+
+#[cfg(feature = "py-bindings")]
+#[pymethods]
+impl ChainDb {
+    #[new]
+    fn py_new(path: std::path::PathBuf) -> PyResult<Self> {
+        Ok(Self::open(path)?)
+    }
+
+    #[pyo3(name = "peak_height")]
+    fn py_peak_height(&self) -> PyResult<Option<u32>> {
+        Ok(self.peak_height()?)
+    }
+
+    #[pyo3(name = "get_block")]
+    fn py_get_block(&self, py: Python<'_>, height: u32) -> PyResult<Option<FullBlock>> {
+        Ok(py.allow_threads(|| self.block_at_height(height))?)
+    }
+
+    #[pyo3(name = "get_block_by_hash")]
+    fn py_get_block_by_hash(
+        &self,
+        py: Python<'_>,
+        header_hash: Bytes32,
+    ) -> PyResult<Option<FullBlock>> {
+        Ok(py.allow_threads(|| self.block_by_hash(&header_hash))?)
+    }
+
+    #[pyo3(name = "get_blocks")]
+    fn py_get_blocks(&self, py: Python<'_>, start: u32, end: u32) -> PyResult<Vec<FullBlock>> {
+        Ok(py.allow_threads(|| self.blocks_in_range(start, end))?)
+    }
+
+    #[pyo3(name = "generator_refs")]
+    fn py_generator_refs(&self, py: Python<'_>, block: &FullBlock) -> PyResult<Vec<Vec<u8>>> {
+        Ok(py.allow_threads(|| self.generator_refs(block))?)
+    }
+
+    #[pyo3(name = "iter_blocks", signature = (start_height = 0, end_height = None))]
+    fn py_iter_blocks(
+        slf: Py<Self>,
+        start_height: u32,
+        end_height: Option<u32>,
+    ) -> PyResult<ChainDbIterator> {
+        let cursor = BlockCursor::new(slf.get(), start_height, end_height)?;
+        Ok(ChainDbIterator { db: slf, cursor })
+    }
+}
+
+#[cfg(feature = "py-bindings")]
+#[pyclass(module = "gold_rs")]
+pub struct ChainDbIterator {
+    db: Py<ChainDb>,
+    cursor: BlockCursor,
+}
+
+#[cfg(feature = "py-bindings")]
+#[pymethods]
+impl ChainDbIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<FullBlock>> {
+        let db = self.db.get();
+        let cursor = &mut self.cursor;
+        Ok(py.allow_threads(|| cursor.next(db))?)
+    }
+}
+
+#[cfg(all(test, feature = "test_blocks"))]
+mod tests {
+    use super::*;
+    use crate::consensus_constants::TEST_CONSTANTS;
+    use crate::test_blocks::BlockTools;
+    use chia_protocol::DEFAULT_COMPRESSION_LEVEL;
+
+    // creates a database with the node's schema, containing the blocks. Every
+    // other block is stored uncompressed
+    fn make_db(blocks: &[FullBlock]) -> ChainDb {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE database_version(version int);
+                INSERT INTO database_version VALUES(2);
+                CREATE TABLE full_blocks(header_hash blob PRIMARY KEY, prev_hash blob, \
+                height bigint, sub_epoch_summary blob, is_fully_compactified tinyint, \
+                in_main_chain tinyint, block blob, block_record blob);
+                CREATE TABLE current_peak(key int PRIMARY KEY, hash blob);",
+            )
+            .unwrap();
+        for (i, b) in blocks.iter().enumerate() {
+            let buf = if i % 2 == 0 {
+                b.to_bytes_compressed(DEFAULT_COMPRESSION_LEVEL).unwrap()
+            } else {
+                b.to_bytes().unwrap()
+            };
+            connection
+                .execute(
+                    "INSERT INTO full_blocks VALUES(?, ?, ?, NULL, 0, 1, ?, NULL)",
+                    rusqlite::params![
+                        b.header_hash().as_ref(),
+                        b.prev_header_hash().as_ref(),
+                        b.height(),
+                        buf
+                    ],
+                )
+                .unwrap();
+        }
+        if let Some(peak) = blocks.last() {
+            connection
+                .execute(
+                    "INSERT INTO current_peak VALUES(0, ?)",
+                    [peak.header_hash().as_ref()],
+                )
+                .unwrap();
+        }
+        ChainDb::from_connection(connection).unwrap()
+    }
+
+    #[test]
+    fn test_chain_db() {
+        let bt = BlockTools::new(&TEST_CONSTANTS, &[1; 32]);
+        let blocks = bt.get_consecutive_blocks(250, None);
+        let db = make_db(&blocks);
+
+        assert_eq!(db.peak_height().unwrap(), Some(249));
+        assert_eq!(db.block_at_height(7).unwrap().as_ref(), Some(&blocks[7]));
+        assert_eq!(db.block_at_height(8).unwrap().as_ref(), Some(&blocks[8]));
+        assert_eq!(db.block_at_height(250).unwrap(), None);
+        assert_eq!(
+            db.block_by_hash(&blocks[42].header_hash())
+                .unwrap()
+                .as_ref(),
+            Some(&blocks[42])
+        );
+        assert_eq!(db.block_by_hash(&Bytes32::default()).unwrap(), None);
+        assert_eq!(db.blocks_in_range(10, 13).unwrap(), blocks[10..13]);
+
+        let all: Vec<FullBlock> = db.blocks(0, None).unwrap().map(|b| b.unwrap()).collect();
+        assert_eq!(all, blocks);
+        let some: Vec<FullBlock> = db
+            .blocks(95, Some(205))
+            .unwrap()
+            .map(|b| b.unwrap())
+            .collect();
+        assert_eq!(some, blocks[95..205]);
+
+        let mut heights = Vec::new();
+        db.for_each_block(240, Some(1000), |b| {
+            heights.push(b.height());
+            heights.len() < 5
+        })
+        .unwrap();
+        assert_eq!(heights, [240, 241, 242, 243, 244]);
+    }
+
+    #[test]
+    fn test_empty_db() {
+        let db = make_db(&[]);
+        assert_eq!(db.peak_height().unwrap(), None);
+        assert_eq!(db.blocks(0, None).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_poisoned_connection() {
+        let db = make_db(&[]);
+        let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _guard = db.connection.lock().unwrap();
+            panic!("while holding the connection");
+        }));
+        assert!(matches!(db.peak_height(), Err(Error::Database(_))));
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE database_version(version int);
+                INSERT INTO database_version VALUES(1);",
+            )
+            .unwrap();
+        assert!(matches!(
+            ChainDb::from_connection(connection),
+            Err(Error::Database(_))
+        ));
+    }
+}
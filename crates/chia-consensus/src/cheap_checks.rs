@@ -0,0 +1,253 @@
+use crate::consensus_constants::ConsensusConstants;
+use crate::gen::conditions::MAX_ANNOUNCEMENTS_PER_SPEND;
+use crate::gen::opcodes::{
+    ASSERT_COIN_ANNOUNCEMENT, ASSERT_CONCURRENT_PUZZLE, ASSERT_CONCURRENT_SPEND,
+    ASSERT_PUZZLE_ANNOUNCEMENT, CREATE_COIN_ANNOUNCEMENT, CREATE_PUZZLE_ANNOUNCEMENT,
+    RECEIVE_MESSAGE, SEND_MESSAGE,
+};
+use crate::gen::validation_error::ErrorCode;
+use chia_protocol::{Bytes32, SpendBundle};
+use clvm_utils::tree_hash_from_bytes;
+use clvmr::allocator::{Allocator, NodePtr, SExp};
+use clvmr::serde::node_from_bytes_backrefs;
+use std::collections::HashSet;
+use thiserror::Error;
+
+// The reason a spend bundle failed the cheap checks, along with the coin whose
+// spend failed, if the failure can be attributed to a single spend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("cheap check failed: {code:?}")]
+pub struct CheapCheckErr {
+    pub code: ErrorCode,
+    pub coin_id: Option<Bytes32>,
+}
+
+impl CheapCheckErr {
+    fn new(code: ErrorCode, coin_id: Option<Bytes32>) -> Self {
+        Self { code, coin_id }
+    }
+}
+
+// Counts the announcement and message conditions of a puzzle of the form
+// (q . conditions), which returns its conditions without looking at the
+// solution. Returns None for any other puzzle, since we would have to run it
+// to know its conditions
+fn quoted_announcements(puzzle: &[u8]) -> Option<u32> {
+    // 0xff 0x01 is the serialization of a pair whose first element is 1 (q)
+    if !puzzle.starts_with(&[0xff, 0x01]) {
+        return None;
+    }
+    let mut a = Allocator::new();
+    let node = node_from_bytes_backrefs(&mut a, puzzle).ok()?;
+    let SExp::Pair(_, mut iter) = a.sexp(node) else {
+        return None;
+    };
+
+    let opcode = |a: &Allocator, c: NodePtr| -> Option<u16> {
+        let SExp::Pair(op, _) = a.sexp(c) else {
+            return None;
+        };
+        match a.atom(op).as_ref() {
+            [op] => Some(*op as u16),
+            _ => None,
+        }
+    };
+
+    let mut count = 0;
+    while let SExp::Pair(c, rest) = a.sexp(iter) {
+        iter = rest;
+        if matches!(
+            opcode(&a, c),
+            Some(
+                CREATE_COIN_ANNOUNCEMENT
+                    | ASSERT_COIN_ANNOUNCEMENT
+                    | CREATE_PUZZLE_ANNOUNCEMENT
+                    | ASSERT_PUZZLE_ANNOUNCEMENT
+                    | ASSERT_CONCURRENT_SPEND
+                    | ASSERT_CONCURRENT_PUZZLE
+                    | SEND_MESSAGE
+                    | RECEIVE_MESSAGE
+            )
+        ) {
+            count += 1;
+        }
+    }
+    Some(count)
+}
+
+// Validates the parts of a spend bundle that don't require running any CLVM,
+// to let the mempool reject invalid spend bundles before paying the cost of
+// running them. Passing these checks does not mean the spend bundle is valid.
+// The checks are:
+// * the cost of the puzzle and solution bytes alone must not exceed max_cost
+// * coin amounts must not exceed the max coin amount
+// * no coin may be spent more than once
+// * every puzzle reveal must hash to the puzzle hash of its coin
+// * puzzles that return quoted conditions must not exceed the announcement
+//   limit
+pub fn cheap_checks(
+    bundle: &SpendBundle,
+    constants: &ConsensusConstants,
+    max_cost: u64,
+) -> Result<(), CheapCheckErr> {
+    // the byte cost of the generator is at least the byte cost of all
+    // puzzles and solutions
+    let size: u64 = bundle
+        .coin_spends
+        .iter()
+        .map(|cs| (cs.puzzle_reveal.len() + cs.solution.len()) as u64)
+        .sum();
+    if size.saturating_mul(constants.cost_per_byte) > max_cost {
+        return Err(CheapCheckErr::new(ErrorCode::CostExceeded, None));
+    }
+
+    let mut removals = HashSet::<Bytes32>::with_capacity(bundle.coin_spends.len());
+    for cs in &bundle.coin_spends {
+        let coin_id = cs.coin.coin_id();
+        let fail = |code| Err(CheapCheckErr::new(code, Some(coin_id)));
+
+        if cs.coin.amount > constants.max_coin_amount {
+            return fail(ErrorCode::CoinAmountExceedsMaximum);
+        }
+
+        if !removals.insert(coin_id) {
+            return fail(ErrorCode::DoubleSpend);
+        }
+
+        let Ok(puzzle_hash) = tree_hash_from_bytes(cs.puzzle_reveal.as_slice()) else {
+            return fail(ErrorCode::InvalidCoinSolution);
+        };
+        if Bytes32::from(puzzle_hash) != cs.coin.puzzle_hash {
+            return fail(ErrorCode::WrongPuzzleHash);
+        }
+
+        if quoted_announcements(cs.puzzle_reveal.as_slice())
+            .is_some_and(|n| n > MAX_ANNOUNCEMENTS_PER_SPEND)
+        {
+            return fail(ErrorCode::TooManyAnnouncements);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus_constants::TEST_CONSTANTS;
+    use chia_bls::G2Element;
+    use chia_protocol::{Coin, CoinSpend, Program};
+    use clvm_traits::ToClvm;
+    use clvmr::serde::node_to_bytes;
+    use rstest::rstest;
+
+    const MAX_COST: u64 = 11_000_000_000;
+
+    // a spend of a coin whose puzzle returns the specified conditions
+    fn quoted_spend(conditions: &[(u16, Bytes32)], amount: u64) -> CoinSpend {
+        let mut a = Allocator::new();
+        let conditions: Vec<_> = conditions.iter().map(|(op, msg)| (op, (msg, ()))).collect();
+        let puzzle = (1, conditions).to_clvm(&mut a).unwrap();
+        let puzzle = Program::from(node_to_bytes(&a, puzzle).unwrap());
+        let puzzle_hash = tree_hash_from_bytes(puzzle.as_slice()).unwrap();
+        let coin = Coin::new(Bytes32::default(), puzzle_hash.into(), amount);
+        CoinSpend::new(coin, puzzle, Program::default())
+    }
+
+    fn announcements(n: usize) -> Vec<(u16, Bytes32)> {
+        vec![(CREATE_COIN_ANNOUNCEMENT, Bytes32::default()); n]
+    }
+
+    #[test]
+    fn test_valid() {
+        let bundle = SpendBundle::new(
+            vec![quoted_spend(&announcements(3), 1), quoted_spend(&[], 2)],
+            G2Element::default(),
+        );
+        assert_eq!(cheap_checks(&bundle, &TEST_CONSTANTS, MAX_COST), Ok(()));
+    }
+
+    #[rstest]
+    #[case(1024, None)]
+    #[case(1025, Some(ErrorCode::TooManyAnnouncements))]
+    fn test_announcement_limit(#[case] count: usize, #[case] expected: Option<ErrorCode>) {
+        let spend = quoted_spend(&announcements(count), 1);
+        let coin_id = spend.coin.coin_id();
+        let bundle = SpendBundle::new(vec![spend], G2Element::default());
+        assert_eq!(
+            cheap_checks(&bundle, &TEST_CONSTANTS, MAX_COST).err(),
+            expected.map(|code| CheapCheckErr::new(code, Some(coin_id)))
+        );
+    }
+
+    #[test]
+    fn test_double_spend() {
+        let spend = quoted_spend(&[], 1);
+        let coin_id = spend.coin.coin_id();
+        let bundle = SpendBundle::new(vec![spend.clone(), spend], G2Element::default());
+        assert_eq!(
+            cheap_checks(&bundle, &TEST_CONSTANTS, MAX_COST),
+            Err(CheapCheckErr::new(ErrorCode::DoubleSpend, Some(coin_id)))
+        );
+    }
+
+    #[test]
+    fn test_wrong_puzzle_hash() {
+        let mut spend = quoted_spend(&[], 1);
+        spend.coin.puzzle_hash = Bytes32::new([1; 32]);
+        let coin_id = spend.coin.coin_id();
+        let bundle = SpendBundle::new(vec![spend], G2Element::default());
+        assert_eq!(
+            cheap_checks(&bundle, &TEST_CONSTANTS, MAX_COST),
+            Err(CheapCheckErr::new(
+                ErrorCode::WrongPuzzleHash,
+                Some(coin_id)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_invalid_puzzle() {
+        let mut spend = quoted_spend(&[], 1);
+        spend.puzzle_reveal = Program::from(vec![0xff, 0x01]);
+        let coin_id = spend.coin.coin_id();
+        let bundle = SpendBundle::new(vec![spend], G2Element::default());
+        assert_eq!(
+            cheap_checks(&bundle, &TEST_CONSTANTS, MAX_COST),
+            Err(CheapCheckErr::new(
+                ErrorCode::InvalidCoinSolution,
+                Some(coin_id)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_coin_amount() {
+        let constants = ConsensusConstants {
+            max_coin_amount: 1000,
+            ..TEST_CONSTANTS
+        };
+        let spend = quoted_spend(&[], 1001);
+        let coin_id = spend.coin.coin_id();
+        let bundle = SpendBundle::new(vec![spend], G2Element::default());
+        assert_eq!(
+            cheap_checks(&bundle, &constants, MAX_COST),
+            Err(CheapCheckErr::new(
+                ErrorCode::CoinAmountExceedsMaximum,
+                Some(coin_id)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_cost_exceeded() {
+        let spend = quoted_spend(&announcements(10), 1);
+        let size = (spend.puzzle_reveal.len() + spend.solution.len()) as u64;
+        let cost = size * TEST_CONSTANTS.cost_per_byte;
+        let bundle = SpendBundle::new(vec![spend], G2Element::default());
+        assert_eq!(cheap_checks(&bundle, &TEST_CONSTANTS, cost), Ok(()));
+        assert_eq!(
+            cheap_checks(&bundle, &TEST_CONSTANTS, cost - 1),
+            Err(CheapCheckErr::new(ErrorCode::CostExceeded, None))
+        );
+    }
+}
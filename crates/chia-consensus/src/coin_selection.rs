@@ -0,0 +1,307 @@
+use crate::error::{Error, Result};
+use chia_protocol::Coin;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoinSelectionStrategy {
+    // the wallet's default. Prefers a single coin matching the amount, then
+    // a random subset of the smaller coins that's as close to the amount as
+    // possible, then the smallest coin larger than the amount
+    Knapsack,
+    // spends the smallest coins first, to consolidate dust
+    SmallestFirst,
+}
+
+// the knapsack algorithm is randomized, but seeded with a constant to make
+// coin selection deterministic
+const KNAPSACK_SEED: u64 = 0x6b6e_6170_7361_636b;
+const KNAPSACK_ROUNDS: usize = 1000;
+
+// splitmix64
+struct Rng(u64);
+
+impl Rng {
+    fn next_bit(&mut self) -> bool {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        (z ^ (z >> 31)) & 1 == 1
+    }
+}
+
+fn sum(coins: &[Coin]) -> u128 {
+    coins.iter().map(|c| c.amount as u128).sum()
+}
+
+// Randomly picks subsets of the coins (which all have a smaller amount than
+// the target), in two passes. The first pass picks every coin with 50%
+// probability, the second pass adds the remaining coins until the target is
+// reached. Every time the target is exceeded, the last coin is removed again,
+// to look for a subset closer to the target. Returns the subset with the
+// smallest sum at or above the target.
+fn knapsack(coins: &[Coin], target: u64, max_coin_count: usize) -> Option<Vec<Coin>> {
+    let target = target as u128;
+    let mut rng = Rng(KNAPSACK_SEED);
+    let mut best: Option<(u128, Vec<bool>)> = None;
+    let collect = |selected: &[bool]| -> Vec<Coin> {
+        coins
+            .iter()
+            .zip(selected)
+            .filter_map(|(c, s)| s.then_some(*c))
+            .collect()
+    };
+
+    for _ in 0..KNAPSACK_ROUNDS {
+        let mut selected = vec![false; coins.len()];
+        let mut count = 0;
+        let mut total = 0_u128;
+        let mut target_reached = false;
+        for pass in 0..2 {
+            if target_reached {
+                break;
+            }
+            for (i, coin) in coins.iter().enumerate() {
+                let pick = if pass == 0 {
+                    rng.next_bit()
+                } else {
+                    !selected[i]
+                };
+                if !pick {
+                    continue;
+                }
+                if count == max_coin_count {
+                    break;
+                }
+                selected[i] = true;
+                count += 1;
+                total += coin.amount as u128;
+                if total == target {
+                    return Some(collect(&selected));
+                }
+                if total > target {
+                    target_reached = true;
+                    let improved = match &best {
+                        Some((best_total, _)) => total < *best_total,
+                        None => true,
+                    };
+                    if improved {
+                        best = Some((total, selected.clone()));
+                    }
+                    selected[i] = false;
+                    count -= 1;
+                    total -= coin.amount as u128;
+                }
+            }
+        }
+    }
+    best.map(|(_, selected)| collect(&selected))
+}
+
+// the first coins of the list that add up to (at least) the target
+fn accumulate(coins: impl Iterator<Item = Coin>, target: u64) -> Vec<Coin> {
+    let mut ret = Vec::new();
+    let mut total = 0_u128;
+    for coin in coins {
+        if total >= target as u128 {
+            break;
+        }
+        total += coin.amount as u128;
+        ret.push(coin);
+    }
+    ret
+}
+
+// used when the strategy needs too many coins. Picks the smallest coin that
+// covers the target, or failing that, the largest coins
+fn fallback(sorted: &[Coin], target: u64, max_coin_count: usize) -> Result<Vec<Coin>> {
+    if max_coin_count == 0 {
+        return Err(Error::TooManyCoins(max_coin_count));
+    }
+    if let Some(coin) = sorted.iter().find(|c| c.amount >= target) {
+        return Ok(vec![*coin]);
+    }
+    let largest = accumulate(sorted.iter().rev().copied(), target);
+    if largest.len() > max_coin_count {
+        return Err(Error::TooManyCoins(max_coin_count));
+    }
+    Ok(largest)
+}
+
+// Selects coins adding up to at least target_amount, spending no more than
+// max_coin_count coins. The coins are (coin, confirmed height) pairs. Among
+// coins of the same amount, the ones confirmed first are preferred. Coin
+// selection is deterministic; the same coins and target always select the same
+// coins.
+pub fn select_coins(
+    coins: &[(Coin, u32)],
+    target_amount: u64,
+    strategy: CoinSelectionStrategy,
+    max_coin_count: usize,
+) -> Result<Vec<Coin>> {
+    if target_amount == 0 {
+        return Ok(vec![]);
+    }
+
+    let mut sorted: Vec<&(Coin, u32)> = coins.iter().collect();
+    sorted.sort_by(|(lhs, lhs_height), (rhs, rhs_height)| {
+        lhs.amount
+            .cmp(&rhs.amount)
+            .then(lhs_height.cmp(rhs_height))
+            .then_with(|| lhs.coin_id().cmp(&rhs.coin_id()))
+    });
+    let sorted: Vec<Coin> = sorted.into_iter().map(|(c, _)| *c).collect();
+
+    let available = sum(&sorted);
+    if available < target_amount as u128 {
+        return Err(Error::InsufficientFunds(available, target_amount));
+    }
+
+    let ret = match strategy {
+        CoinSelectionStrategy::SmallestFirst => accumulate(sorted.iter().copied(), target_amount),
+        CoinSelectionStrategy::Knapsack => {
+            let split = sorted.partition_point(|c| c.amount < target_amount);
+            let mut smaller: Vec<Coin> = sorted[..split].to_vec();
+            smaller.reverse();
+            let smaller_sum = sum(&smaller);
+            if sorted.get(split).is_some_and(|c| c.amount == target_amount) {
+                // a single coin matching the amount
+                vec![sorted[split]]
+            } else if smaller_sum < target_amount as u128 {
+                // the smallest coin larger than the target
+                vec![sorted[split]]
+            } else if smaller_sum == target_amount as u128 {
+                smaller
+            } else {
+                knapsack(&smaller, target_amount, max_coin_count)
+                    .unwrap_or_else(|| accumulate(smaller.into_iter(), target_amount))
+            }
+        }
+    };
+
+    if ret.len() > max_coin_count {
+        return fallback(&sorted, target_amount, max_coin_count);
+    }
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chia_protocol::Bytes32;
+    use rstest::rstest;
+
+    fn coins(amounts: &[u64]) -> Vec<(Coin, u32)> {
+        amounts
+            .iter()
+            .enumerate()
+            .map(|(i, amount)| {
+                (
+                    Coin::new(Bytes32::from([i as u8; 32]), Bytes32::default(), *amount),
+                    i as u32,
+                )
+            })
+            .collect()
+    }
+
+    fn amounts(coins: &[Coin]) -> Vec<u64> {
+        let mut ret: Vec<u64> = coins.iter().map(|c| c.amount).collect();
+        ret.sort_unstable();
+        ret
+    }
+
+    #[rstest]
+    #[case(CoinSelectionStrategy::Knapsack, 10, &[10])]
+    #[case(CoinSelectionStrategy::Knapsack, 15, &[5, 10])]
+    #[case(CoinSelectionStrategy::Knapsack, 18, &[3, 5, 10])]
+    #[case(CoinSelectionStrategy::Knapsack, 19, &[1, 3, 5, 10])]
+    #[case(CoinSelectionStrategy::Knapsack, 20, &[20])]
+    #[case(CoinSelectionStrategy::Knapsack, 21, &[1, 20])]
+    #[case(CoinSelectionStrategy::Knapsack, 40, &[100])]
+    #[case(CoinSelectionStrategy::Knapsack, 100, &[100])]
+    #[case(CoinSelectionStrategy::SmallestFirst, 10, &[1, 3, 5, 10])]
+    #[case(CoinSelectionStrategy::SmallestFirst, 4, &[1, 3])]
+    #[case(CoinSelectionStrategy::SmallestFirst, 138, &[1, 3, 5, 10, 20, 100])]
+    fn test_select_coins(
+        #[case] strategy: CoinSelectionStrategy,
+        #[case] target: u64,
+        #[case] expected: &[u64],
+    ) {
+        let coins = coins(&[20, 1, 100, 5, 3, 10]);
+        let selected = select_coins(&coins, target, strategy, 500).unwrap();
+        assert_eq!(amounts(&selected), expected);
+        assert!(sum(&selected) >= target as u128);
+    }
+
+    #[test]
+    fn test_insufficient_funds() {
+        let coins = coins(&[1, 2, 3]);
+        for strategy in [
+            CoinSelectionStrategy::Knapsack,
+            CoinSelectionStrategy::SmallestFirst,
+        ] {
+            assert_eq!(
+                select_coins(&coins, 7, strategy, 500).unwrap_err(),
+                Error::InsufficientFunds(6, 7)
+            );
+            assert!(select_coins(&coins, 0, strategy, 500).unwrap().is_empty());
+            assert!(select_coins(&[], 0, strategy, 500).unwrap().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_max_coin_count() {
+        let coins = coins(&[1, 1, 1, 1, 1, 3]);
+        // smallest-first needs 3 coins, so the 3 coin is used instead
+        assert_eq!(
+            amounts(&select_coins(&coins, 3, CoinSelectionStrategy::SmallestFirst, 2).unwrap()),
+            [3]
+        );
+        assert_eq!(
+            amounts(&select_coins(&coins, 4, CoinSelectionStrategy::SmallestFirst, 2).unwrap()),
+            [1, 3]
+        );
+        assert_eq!(
+            select_coins(&coins, 6, CoinSelectionStrategy::Knapsack, 2).unwrap_err(),
+            Error::TooManyCoins(2)
+        );
+
+        // not even a single coin may be spent
+        for (target, strategy) in [
+            (3, CoinSelectionStrategy::Knapsack),
+            (2, CoinSelectionStrategy::Knapsack),
+            (1, CoinSelectionStrategy::SmallestFirst),
+        ] {
+            assert_eq!(
+                select_coins(&coins, target, strategy, 0).unwrap_err(),
+                Error::TooManyCoins(0)
+            );
+        }
+    }
+
+    #[test]
+    fn test_prefer_older_coins() {
+        let mut coins = coins(&[5, 5, 5]);
+        coins[0].1 = 10;
+        coins[1].1 = 2;
+        coins[2].1 = 7;
+        let selected = select_coins(&coins, 5, CoinSelectionStrategy::Knapsack, 500).unwrap();
+        assert_eq!(selected, [coins[1].0]);
+        let selected = select_coins(&coins, 5, CoinSelectionStrategy::SmallestFirst, 500).unwrap();
+        assert_eq!(selected, [coins[1].0]);
+    }
+
+    #[test]
+    fn test_knapsack_many_coins() {
+        let amounts: Vec<u64> = (1..2000).map(|i| i * 7 + 1000).collect();
+        let coins = coins(&amounts);
+        let target = 1_000_003;
+        let selected = select_coins(&coins, target, CoinSelectionStrategy::Knapsack, 500).unwrap();
+        assert!(selected.len() <= 500);
+        assert!(sum(&selected) >= target as u128);
+        // deterministic
+        assert_eq!(
+            select_coins(&coins, target, CoinSelectionStrategy::Knapsack, 500).unwrap(),
+            selected
+        );
+    }
+}
@@ -0,0 +1,387 @@
+use crate::puzzle_state::{last_height, matches_filters};
+use chia_protocol::{Bytes32, Coin, CoinState, CoinStateFilters};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+#[cfg(feature = "py-bindings")]
+use pyo3::prelude::*;
+
+// the default page size of batch_coin_states(), the same as the full node's
+pub const DEFAULT_MAX_ITEMS: usize = 50000;
+
+// An in-memory coin set, indexed by coin ID, puzzle hash, hint, parent coin
+// and the heights coins were created and spent at. Coins are only ever added
+// and spent, there's no support for rolling back a reorg.
+#[cfg_attr(feature = "py-bindings", pyclass(module = "gold_rs"))]
+#[derive(Clone, Debug, Default)]
+pub struct CoinStore {
+    coins: HashMap<Bytes32, CoinState>,
+    by_puzzle_hash: HashMap<Bytes32, Vec<Bytes32>>,
+    by_hint: HashMap<Bytes32, Vec<Bytes32>>,
+    by_parent: HashMap<Bytes32, Vec<Bytes32>>,
+    // the coins created or spent at every height
+    by_height: BTreeMap<u32, Vec<Bytes32>>,
+}
+
+impl CoinStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.coins.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.coins.is_empty()
+    }
+
+    // adds an unspent coin, created at height. Returns false if the coin
+    // already exists
+    pub fn add_coin(&mut self, coin: Coin, height: u32, hint: Option<Bytes32>) -> bool {
+        let coin_id = coin.coin_id();
+        if self.coins.contains_key(&coin_id) {
+            return false;
+        }
+        self.coins
+            .insert(coin_id, CoinState::new(coin, None, Some(height)));
+        self.by_puzzle_hash
+            .entry(coin.puzzle_hash)
+            .or_default()
+            .push(coin_id);
+        self.by_parent
+            .entry(coin.parent_coin_info)
+            .or_default()
+            .push(coin_id);
+        if let Some(hint) = hint {
+            self.by_hint.entry(hint).or_default().push(coin_id);
+        }
+        self.by_height.entry(height).or_default().push(coin_id);
+        true
+    }
+
+    // marks the coin as spent at height. Returns false if the coin doesn't
+    // exist or is already spent
+    pub fn spend_coin(&mut self, coin_id: &Bytes32, height: u32) -> bool {
+        let Some(state) = self.coins.get_mut(coin_id) else {
+            return false;
+        };
+        if state.spent_height.is_some() {
+            return false;
+        }
+        state.spent_height = Some(height);
+        self.by_height.entry(height).or_default().push(*coin_id);
+        true
+    }
+
+    pub fn coin_state(&self, coin_id: &Bytes32) -> Option<CoinState> {
+        self.coins.get(coin_id).copied()
+    }
+
+    // the states of the coins that exist, in the order of coin_ids
+    pub fn coin_states(&self, coin_ids: &[Bytes32]) -> Vec<CoinState> {
+        coin_ids
+            .iter()
+            .filter_map(|id| self.coin_state(id))
+            .collect()
+    }
+
+    fn lookup<'a>(
+        &'a self,
+        index: &'a HashMap<Bytes32, Vec<Bytes32>>,
+        keys: &'a [Bytes32],
+    ) -> impl Iterator<Item = &'a CoinState> + 'a {
+        keys.iter()
+            .filter_map(|key| index.get(key))
+            .flatten()
+            .map(|id| &self.coins[id])
+    }
+
+    pub fn coin_states_by_puzzle_hash(
+        &self,
+        puzzle_hashes: &[Bytes32],
+        include_spent: bool,
+    ) -> Vec<CoinState> {
+        self.lookup(&self.by_puzzle_hash, puzzle_hashes)
+            .filter(|state| include_spent || state.spent_height.is_none())
+            .copied()
+            .collect()
+    }
+
+    pub fn coin_states_by_hint(&self, hints: &[Bytes32], include_spent: bool) -> Vec<CoinState> {
+        self.lookup(&self.by_hint, hints)
+            .filter(|state| include_spent || state.spent_height.is_none())
+            .copied()
+            .collect()
+    }
+
+    // the coins created by spending the coin
+    pub fn children(&self, coin_id: &Bytes32) -> Vec<CoinState> {
+        self.lookup(&self.by_parent, std::slice::from_ref(coin_id))
+            .copied()
+            .collect()
+    }
+
+    // Looks up the coins with any of the puzzle hashes (and, if the filters
+    // include hinted coins, the coins hinted to them) that were created or
+    // spent at min_height or later, the same way the full node answers a
+    // RequestPuzzleState. The coin states are ordered by the height they
+    // last changed at, and a page never ends in the middle of a height. That's
+    // why a page may have more than max_items coin states, if they all
+    // changed at the same height.
+    // Returns the coin states and, unless these were the last ones, the
+    // min_height to request the next page with.
+    pub fn batch_coin_states(
+        &self,
+        puzzle_hashes: &[Bytes32],
+        min_height: u32,
+        filters: &CoinStateFilters,
+        max_items: usize,
+    ) -> (Vec<CoinState>, Option<u32>) {
+        let mut seen = HashSet::<Bytes32>::new();
+        let hinted = if filters.include_hinted {
+            Some(self.lookup(&self.by_hint, puzzle_hashes))
+        } else {
+            None
+        };
+        let mut states: Vec<CoinState> = self
+            .lookup(&self.by_puzzle_hash, puzzle_hashes)
+            .chain(hinted.into_iter().flatten())
+            .filter(|state| matches_filters(state, filters, min_height))
+            .filter(|state| seen.insert(state.coin.coin_id()))
+            .copied()
+            .collect();
+        states.sort_by_cached_key(|state| (last_height(state), state.coin.coin_id()));
+
+        if states.len() <= max_items {
+            return (states, None);
+        }
+        let next_height = last_height(&states[max_items]);
+        let end = states.partition_point(|state| last_height(state) < next_height);
+        if end > 0 {
+            states.truncate(end);
+            return (states, Some(next_height));
+        }
+
+        // all of the first max_items coin states are at the same height. They
+        // have to be returned all together
+        let end = states.partition_point(|state| last_height(state) <= next_height);
+        let next = states.get(end).map(last_height);
+        states.truncate(end);
+        (states, next)
+    }
+
+    // The coins created or spent after from_height, up to and including
+    // to_height, with their states as of to_height. I.e. a coin spent after
+    // to_height is unspent.
+    pub fn changes_between(&self, from_height: u32, to_height: u32) -> Vec<CoinState> {
+        if from_height >= to_height {
+            return vec![];
+        }
+        let mut seen = HashSet::<Bytes32>::new();
+        self.by_height
+            .range(from_height + 1..=to_height)
+            .flat_map(|(_, ids)| ids)
+            .filter(|id| seen.insert(**id))
+            .map(|id| {
+                let mut state = self.coins[id];
+                if state.spent_height.is_some_and(|h| h > to_height) {
+                    state.spent_height = None;
+                }
+                state
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "py-bindings")]
+#[pymethods]
+impl CoinStore {
+    #[new]
+    fn py_new() -> Self {
+        Self::new()
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    fn __len__(&self) -> usize {
+        self.len()
+    }
+
+    #[pyo3(name = "add_coin", signature = (coin, height, hint = None))]
+    fn py_add_coin(&mut self, coin: Coin, height: u32, hint: Option<Bytes32>) -> bool {
+        self.add_coin(coin, height, hint)
+    }
+
+    #[pyo3(name = "spend_coin")]
+    fn py_spend_coin(&mut self, coin_id: Bytes32, height: u32) -> bool {
+        self.spend_coin(&coin_id, height)
+    }
+
+    #[pyo3(name = "coin_state")]
+    fn py_coin_state(&self, coin_id: Bytes32) -> Option<CoinState> {
+        self.coin_state(&coin_id)
+    }
+
+    #[pyo3(name = "coin_states")]
+    fn py_coin_states(&self, coin_ids: Vec<Bytes32>) -> Vec<CoinState> {
+        self.coin_states(&coin_ids)
+    }
+
+    #[pyo3(name = "coin_states_by_puzzle_hash", signature = (puzzle_hashes, include_spent = true))]
+    fn py_coin_states_by_puzzle_hash(
+        &self,
+        puzzle_hashes: Vec<Bytes32>,
+        include_spent: bool,
+    ) -> Vec<CoinState> {
+        self.coin_states_by_puzzle_hash(&puzzle_hashes, include_spent)
+    }
+
+    #[pyo3(name = "coin_states_by_hint", signature = (hints, include_spent = true))]
+    fn py_coin_states_by_hint(&self, hints: Vec<Bytes32>, include_spent: bool) -> Vec<CoinState> {
+        self.coin_states_by_hint(&hints, include_spent)
+    }
+
+    #[pyo3(name = "children")]
+    fn py_children(&self, coin_id: Bytes32) -> Vec<CoinState> {
+        self.children(&coin_id)
+    }
+
+    #[pyo3(name = "batch_coin_states", signature = (puzzle_hashes, min_height, filters, max_items = DEFAULT_MAX_ITEMS))]
+    fn py_batch_coin_states(
+        &self,
+        py: Python<'_>,
+        puzzle_hashes: Vec<Bytes32>,
+        min_height: u32,
+        filters: CoinStateFilters,
+        max_items: usize,
+    ) -> (Vec<CoinState>, Option<u32>) {
+        py.allow_threads(|| self.batch_coin_states(&puzzle_hashes, min_height, &filters, max_items))
+    }
+
+    #[pyo3(name = "changes_between")]
+    fn py_changes_between(&self, from_height: u32, to_height: u32) -> Vec<CoinState> {
+        self.changes_between(from_height, to_height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coin(parent: u8, puzzle_hash: u8, amount: u64) -> Coin {
+        Coin::new([parent; 32].into(), [puzzle_hash; 32].into(), amount)
+    }
+
+    fn filters(include_spent: bool, include_hinted: bool, min_amount: u64) -> CoinStateFilters {
+        CoinStateFilters::new(include_spent, true, include_hinted, min_amount)
+    }
+
+    #[test]
+    fn test_add_spend() {
+        let mut store = CoinStore::new();
+        assert!(store.is_empty());
+        let parent = coin(1, 2, 100);
+        let child = Coin::new(parent.coin_id(), [3; 32].into(), 50);
+        assert!(store.add_coin(parent, 1, None));
+        assert!(!store.add_coin(parent, 2, None));
+        assert!(store.add_coin(child, 5, Some([4; 32].into())));
+        assert_eq!(store.len(), 2);
+
+        assert!(store.spend_coin(&parent.coin_id(), 5));
+        assert!(!store.spend_coin(&parent.coin_id(), 6));
+        assert!(!store.spend_coin(&[9; 32].into(), 6));
+
+        assert_eq!(
+            store.coin_state(&parent.coin_id()),
+            Some(CoinState::new(parent, Some(5), Some(1)))
+        );
+        assert_eq!(
+            store.children(&parent.coin_id()),
+            [CoinState::new(child, None, Some(5))]
+        );
+        assert_eq!(store.coin_states_by_hint(&[[4; 32].into()], true).len(), 1);
+        assert!(store
+            .coin_states_by_puzzle_hash(&[[2; 32].into()], false)
+            .is_empty());
+        assert_eq!(
+            store.coin_states(&[child.coin_id(), [9; 32].into(), parent.coin_id()]),
+            [
+                CoinState::new(child, None, Some(5)),
+                CoinState::new(parent, Some(5), Some(1))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_batch_coin_states() {
+        let mut store = CoinStore::new();
+        let ph = Bytes32::from([2; 32]);
+        // two coins per height, at heights 0-9
+        for i in 0..20_u8 {
+            store.add_coin(coin(i, 2, i as u64), i as u32 / 2, None);
+        }
+        // a coin hinted to ph
+        store.add_coin(coin(100, 3, 1000), 3, Some(ph));
+        store.spend_coin(&coin(0, 2, 0).coin_id(), 20);
+
+        let (states, next) = store.batch_coin_states(&[ph], 0, &filters(true, true, 0), 100);
+        assert_eq!(states.len(), 21);
+        assert_eq!(next, None);
+        // ordered by height, the spent coin last
+        assert_eq!(states[20].coin, coin(0, 2, 0));
+        assert!(states
+            .windows(2)
+            .all(|w| last_height(&w[0]) <= last_height(&w[1])));
+
+        let (states, _) = store.batch_coin_states(&[ph], 0, &filters(false, false, 10), 100);
+        assert_eq!(states.len(), 10);
+        let (states, _) = store.batch_coin_states(&[ph], 8, &filters(true, false, 0), 100);
+        assert_eq!(states.len(), 5);
+
+        // pages don't split heights
+        let (states, next) = store.batch_coin_states(&[ph], 0, &filters(true, true, 0), 6);
+        assert_eq!(states.len(), 5);
+        assert_eq!(next, Some(3));
+        let (states, next) = store.batch_coin_states(&[ph], 3, &filters(true, true, 0), 6);
+        assert_eq!(states.len(), 5);
+        assert_eq!(next, Some(5));
+
+        // a page with more items at the same height than max_items
+        let (states, next) = store.batch_coin_states(&[ph], 0, &filters(true, false, 0), 1);
+        assert_eq!(states.len(), 1);
+        assert_eq!(next, Some(1));
+        let (states, next) = store.batch_coin_states(&[ph], 1, &filters(true, false, 0), 1);
+        assert_eq!(states.len(), 2);
+        assert_eq!(next, Some(2));
+    }
+
+    #[test]
+    fn test_changes_between() {
+        let mut store = CoinStore::new();
+        let a = coin(1, 1, 1);
+        let b = coin(2, 2, 2);
+        store.add_coin(a, 5, None);
+        store.add_coin(b, 8, None);
+        store.spend_coin(&a.coin_id(), 10);
+
+        assert_eq!(
+            store.changes_between(4, 8),
+            [
+                CoinState::new(a, None, Some(5)),
+                CoinState::new(b, None, Some(8))
+            ]
+        );
+        assert_eq!(
+            store.changes_between(8, 10),
+            [CoinState::new(a, Some(10), Some(5))]
+        );
+        assert!(store.changes_between(5, 7).is_empty());
+        assert!(store.changes_between(10, 4).is_empty());
+    }
+}
@@ -3,7 +3,15 @@ use chia_streamable_macro::streamable;
 
 #[cfg(feature = "py-bindings")]
 use chia_py_streamable_macro::{PyGetters, PyJsonDict, PyStreamable};
+#[cfg(feature = "py-bindings")]
+use chia_traits::{FromJsonDict, ToJsonDict};
 use hex_literal::hex;
+#[cfg(feature = "py-bindings")]
+use pyo3::exceptions::{PyKeyError, PyValueError};
+#[cfg(feature = "py-bindings")]
+use pyo3::prelude::*;
+#[cfg(feature = "py-bindings")]
+use pyo3::types::{PyBytes, PyDict, PyString};
 
 #[cfg_attr(
     feature = "py-bindings",
@@ -12,6 +20,7 @@ use hex_literal::hex;
     py_uppercase,
     py_pickle
 )]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 #[streamable]
 pub struct ConsensusConstants {
     /// How many blocks to target per sub-slot.
@@ -139,6 +148,71 @@ pub struct ConsensusConstants {
     plot_filter_32_height: u32,
 }
 
+// Testnets override some of the constants in their config. The overrides are
+// a partial dict, keyed by the (upper case) field names, with values in the
+// same form as to_json_dict() produces. Hashes may also be bytes or hex
+// strings without the 0x prefix, the way they appear in config files. Unknown
+// fields and values of the wrong type are errors.
+#[cfg(feature = "py-bindings")]
+#[pymethods]
+impl ConsensusConstants {
+    #[staticmethod]
+    fn from_dict_overrides(base: &Self, overrides: &Bound<'_, PyDict>) -> PyResult<Self> {
+        let py = overrides.py();
+        let fields = ToJsonDict::to_json_dict(base, py)?.into_bound(py);
+        let fields = fields.downcast::<PyDict>()?;
+        for (key, value) in overrides.iter() {
+            let name = key.extract::<String>()?;
+            if !fields.contains(&name)? {
+                return Err(PyKeyError::new_err(format!("unknown constant {name}")));
+            }
+
+            let mut alternatives = Vec::new();
+            if let Ok(buf) = value.downcast::<PyBytes>() {
+                let hex = format!("0x{}", hex::encode(buf.as_bytes()));
+                alternatives.push(PyString::new_bound(py, &hex).into_any());
+            } else {
+                alternatives.push(value.clone());
+                if let Ok(s) = value.downcast::<PyString>() {
+                    let s = s.to_cow()?;
+                    if !s.starts_with("0x") {
+                        let hex = format!("0x{s}");
+                        alternatives.push(PyString::new_bound(py, &hex).into_any());
+                    }
+                }
+            }
+
+            // every override is validated on its own, to report which one is
+            // invalid
+            let candidate = fields.copy()?;
+            let mut accepted = None;
+            for v in alternatives {
+                candidate.set_item(&name, &v)?;
+                if <Self as FromJsonDict>::from_json_dict(candidate.as_any()).is_ok() {
+                    accepted = Some(v);
+                    break;
+                }
+            }
+            let Some(v) = accepted else {
+                return Err(PyValueError::new_err(format!(
+                    "invalid value for {name}: {value}"
+                )));
+            };
+            fields.set_item(&name, v)?;
+        }
+        <Self as FromJsonDict>::from_json_dict(fields.as_any())
+    }
+
+    // like from_dict_overrides(), with the overrides as a JSON object
+    #[staticmethod]
+    fn from_json(py: Python<'_>, base: &Self, overrides: &str) -> PyResult<Self> {
+        let overrides = py
+            .import_bound("json")?
+            .call_method1("loads", (overrides,))?;
+        Self::from_dict_overrides(base, overrides.downcast::<PyDict>()?)
+    }
+}
+
 pub const TEST_CONSTANTS: ConsensusConstants = ConsensusConstants {
     slot_blocks_target: 32,
     min_blocks_per_challenge_block: 16,
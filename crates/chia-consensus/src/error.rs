@@ -1,4 +1,5 @@
 use crate::gen::validation_error::ValidationErr;
+use chia_protocol::Bytes32;
 use clvm_traits::{FromClvmError, ToClvmError};
 use clvmr::reduction::EvalErr;
 use thiserror::Error;
@@ -47,6 +48,30 @@ pub enum Error {
     #[error("expected lineage proof, found eve proof")]
     ExpectedLineageProof,
 
+    #[error("the parent of generation {0} is not the coin of the previous generation")]
+    BrokenLineage(usize),
+
+    #[error("not a CAT or singleton puzzle")]
+    NotCatOrSingleton,
+
+    #[error("not a pool singleton puzzle")]
+    NotPoolPuzzle,
+
+    #[error("insufficient funds: {0} available, {1} requested")]
+    InsufficientFunds(u128, u64),
+
+    #[error("the amount can't be reached with at most {0} coins")]
+    TooManyCoins(usize),
+
+    #[error("block not found: {0}")]
+    BlockNotFound(Bytes32),
+
+    #[error("Streamable {0}")]
+    Streamable(#[from] chia_traits::Error),
+
+    #[error("database error: {0}")]
+    Database(String),
+
     #[error("{0}")]
     Custom(String),
 }
@@ -58,4 +83,11 @@ impl std::convert::From<Error> for PyErr {
     }
 }
 
+#[cfg(feature = "chain_db")]
+impl std::convert::From<rusqlite::Error> for Error {
+    fn from(err: rusqlite::Error) -> Error {
+        Error::Database(err.to_string())
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
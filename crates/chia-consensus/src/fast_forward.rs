@@ -153,6 +153,31 @@ pub fn fast_forward_singleton(
     Ok(new_solution.to_clvm(a)?)
 }
 
+// fast-forwards a singleton spend across several generations at once. Each
+// element of the lineage is the (parent, coin) pair of one generation, oldest
+// first, where the parent of every generation must be the coin of the previous
+// one. The returned solution spends the coin of the last generation. This is
+// the same as calling fast_forward_singleton() once per generation, without
+// serializing the solution in between
+pub fn fast_forward_chain(
+    a: &mut Allocator,
+    puzzle: NodePtr,
+    solution: NodePtr,
+    coin: &Coin,
+    lineage: &[(Coin, Coin)],
+) -> Result<NodePtr> {
+    let mut solution = solution;
+    let mut coin = coin;
+    for (i, (new_parent, new_coin)) in lineage.iter().enumerate() {
+        if i > 0 && new_parent != coin {
+            return Err(Error::BrokenLineage(i));
+        }
+        solution = fast_forward_singleton(a, puzzle, solution, coin, new_coin, new_parent)?;
+        coin = new_coin;
+    }
+    Ok(solution)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -497,4 +522,58 @@ mod tests {
             Error::NotSingletonModHash,
         );
     }
+
+    #[test]
+    fn test_fast_forward_chain() {
+        let spend_bytes = fs::read("../../ff-tests/e3c0.spend").expect("read file");
+        let spend = CoinSpend::from_bytes(&spend_bytes).expect("parse CoinSpend");
+
+        let mut a = Allocator::new_limited(500000000);
+        let puzzle = spend.puzzle_reveal.to_node_ptr(&mut a).expect("to_clvm");
+        let solution = spend.solution.to_node_ptr(&mut a).expect("to_clvm");
+        let puzzle_hash = Bytes32::from(tree_hash(&a, puzzle));
+
+        // three generations, starting from an arbitrary parent
+        let mut lineage = Vec::<(Coin, Coin)>::new();
+        let mut parent = Coin::new([0xab; 32].into(), puzzle_hash, 3);
+        for amount in [5, 7, 9] {
+            let coin = Coin::new(parent.coin_id(), puzzle_hash, amount);
+            lineage.push((parent, coin));
+            parent = coin;
+        }
+
+        let chained = fast_forward_chain(&mut a, puzzle, solution, &spend.coin, &lineage)
+            .expect("fast_forward_chain");
+
+        // the result is the same as fast-forwarding directly to the last
+        // generation
+        let (last_parent, last_coin) = lineage.last().unwrap();
+        let direct = fast_forward_singleton(
+            &mut a,
+            puzzle,
+            solution,
+            &spend.coin,
+            last_coin,
+            last_parent,
+        )
+        .expect("fast-forward");
+        assert_eq!(
+            node_to_bytes(&a, chained).unwrap(),
+            node_to_bytes(&a, direct).unwrap()
+        );
+
+        // an empty lineage leaves the solution unchanged
+        assert_eq!(
+            fast_forward_chain(&mut a, puzzle, solution, &spend.coin, &[]).unwrap(),
+            solution
+        );
+
+        // every generation must descend from the previous one
+        let mut broken = lineage.clone();
+        broken[2].0 = lineage[0].0;
+        assert_eq!(
+            fast_forward_chain(&mut a, puzzle, solution, &spend.coin, &broken).unwrap_err(),
+            Error::BrokenLineage(2)
+        );
+    }
 }
@@ -0,0 +1,138 @@
+use crate::error::Error;
+use chia_protocol::{BlockRecord, Bytes32};
+use std::cmp::Ordering;
+
+// Finds the height of the last block two chains have in common, by walking
+// back from their peaks. lookup returns the block record with the specified
+// header hash, or None if it's not known (which is an error, since all blocks
+// of both chains must be available). Returns None if the chains don't share
+// any block, i.e. they have different genesis blocks. When one peak is an
+// ancestor of the other, the fork point is the height of that peak.
+pub fn find_fork_point<E, F>(
+    old_peak: &BlockRecord,
+    new_peak: &BlockRecord,
+    mut lookup: F,
+) -> Result<Option<u32>, E>
+where
+    E: From<Error>,
+    F: FnMut(&Bytes32) -> Result<Option<BlockRecord>, E>,
+{
+    let mut prev = |block: &BlockRecord| -> Result<BlockRecord, E> {
+        lookup(&block.prev_hash)?.ok_or_else(|| Error::BlockNotFound(block.prev_hash).into())
+    };
+
+    let mut old = old_peak.clone();
+    let mut new = new_peak.clone();
+    while old.height > new.height {
+        old = prev(&old)?;
+    }
+    while new.height > old.height {
+        new = prev(&new)?;
+    }
+    loop {
+        if old.header_hash == new.header_hash {
+            return Ok(Some(old.height));
+        }
+        if old.height == 0 {
+            return Ok(None);
+        }
+        old = prev(&old)?;
+        new = prev(&new)?;
+    }
+}
+
+// Orders chains by their peaks, the greater one being the chain a node should
+// follow. The heavier chain wins. Among chains of the same weight, the one
+// whose peak took fewer VDF iterations to reach wins, since it was infused
+// first. If those are the same too, the lower header hash wins, to make the
+// choice deterministic.
+pub fn compare_peaks(a: &BlockRecord, b: &BlockRecord) -> Ordering {
+    a.weight
+        .cmp(&b.weight)
+        .then_with(|| b.total_iters.cmp(&a.total_iters))
+        .then_with(|| b.header_hash.cmp(&a.header_hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+    use crate::test_block_record::{hash, BlockRecordBuilder};
+    use std::collections::HashMap;
+
+    fn record(header_hash: u32, prev_hash: u32, height: u32, weight: u128) -> BlockRecord {
+        BlockRecordBuilder::new(hash(header_hash))
+            .prev_hash(hash(prev_hash))
+            .height(height)
+            .weight(weight)
+            .total_iters(weight * 10)
+            .build()
+    }
+
+    // the chain 1 <- 2 <- 3 <- 4, with the fork 2 <- 13 <- 14 <- 15 and the
+    // unrelated chain 21 <- 22
+    fn chain() -> HashMap<Bytes32, BlockRecord> {
+        [
+            record(1, 0, 0, 1),
+            record(2, 1, 1, 2),
+            record(3, 2, 2, 3),
+            record(4, 3, 3, 4),
+            record(13, 2, 2, 3),
+            record(14, 13, 3, 4),
+            record(15, 14, 4, 5),
+            record(21, 20, 0, 1),
+            record(22, 21, 1, 2),
+        ]
+        .into_iter()
+        .map(|r| (r.header_hash, r))
+        .collect()
+    }
+
+    fn fork_point(blocks: &HashMap<Bytes32, BlockRecord>, a: u32, b: u32) -> Result<Option<u32>> {
+        find_fork_point(&blocks[&hash(a)], &blocks[&hash(b)], |h| {
+            Ok(blocks.get(h).cloned())
+        })
+    }
+
+    #[test]
+    fn test_find_fork_point() {
+        let blocks = chain();
+        assert_eq!(fork_point(&blocks, 4, 15), Ok(Some(1)));
+        assert_eq!(fork_point(&blocks, 15, 4), Ok(Some(1)));
+        assert_eq!(fork_point(&blocks, 3, 14), Ok(Some(1)));
+        // one peak is the ancestor of the other
+        assert_eq!(fork_point(&blocks, 2, 4), Ok(Some(1)));
+        assert_eq!(fork_point(&blocks, 4, 4), Ok(Some(3)));
+        assert_eq!(fork_point(&blocks, 1, 15), Ok(Some(0)));
+        // different genesis blocks
+        assert_eq!(fork_point(&blocks, 22, 4), Ok(None));
+    }
+
+    #[test]
+    fn test_missing_block() {
+        let mut blocks = chain();
+        blocks.remove(&hash(13));
+        assert_eq!(
+            fork_point(&blocks, 4, 15),
+            Err(Error::BlockNotFound(hash(13)))
+        );
+    }
+
+    #[test]
+    fn test_compare_peaks() {
+        let light = record(1, 0, 5, 100);
+        let heavy = record(2, 0, 4, 101);
+        assert_eq!(compare_peaks(&heavy, &light), Ordering::Greater);
+        assert_eq!(compare_peaks(&light, &heavy), Ordering::Less);
+
+        // same weight, fewer iterations wins
+        let mut fast = record(3, 0, 5, 100);
+        fast.total_iters -= 1;
+        assert_eq!(compare_peaks(&fast, &light), Ordering::Greater);
+
+        // all else equal, the lower header hash wins
+        let other = record(4, 0, 5, 100);
+        assert_eq!(compare_peaks(&light, &other), Ordering::Greater);
+        assert_eq!(compare_peaks(&light, &light), Ordering::Equal);
+    }
+}
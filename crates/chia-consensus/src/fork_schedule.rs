@@ -0,0 +1,290 @@
+use crate::consensus_constants::ConsensusConstants;
+use crate::gen::flags::{
+    AGG_SIG_ARGS, ALLOW_BACKREFS, DISALLOW_INFINITY_G1, ENABLE_MESSAGE_CONDITIONS,
+    NO_RELATIVE_CONDITIONS_ON_EPHEMERAL,
+};
+use clvmr::{ENABLE_BLS_OPS_OUTSIDE_GUARD, ENABLE_FIXED_DIV};
+
+#[cfg(feature = "py-bindings")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "py-bindings")]
+use pyo3::prelude::*;
+#[cfg(feature = "py-bindings")]
+use std::collections::HashSet;
+
+// The soft- and hard forks of the chain, in the order they were scheduled
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Fork {
+    SoftFork2,
+    SoftFork4,
+    SoftFork5,
+    HardFork,
+    HardForkFix,
+    PlotFilter128,
+    PlotFilter64,
+    PlotFilter32,
+}
+
+impl Fork {
+    pub const ALL: [Fork; 8] = [
+        Fork::SoftFork2,
+        Fork::SoftFork4,
+        Fork::SoftFork5,
+        Fork::HardFork,
+        Fork::HardForkFix,
+        Fork::PlotFilter128,
+        Fork::PlotFilter64,
+        Fork::PlotFilter32,
+    ];
+
+    // the name of the fork, matching the name of its height in the consensus
+    // constants (without the "_height" suffix)
+    pub fn name(self) -> &'static str {
+        match self {
+            Fork::SoftFork2 => "soft_fork2",
+            Fork::SoftFork4 => "soft_fork4",
+            Fork::SoftFork5 => "soft_fork5",
+            Fork::HardFork => "hard_fork",
+            Fork::HardForkFix => "hard_fork_fix",
+            Fork::PlotFilter128 => "plot_filter_128",
+            Fork::PlotFilter64 => "plot_filter_64",
+            Fork::PlotFilter32 => "plot_filter_32",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Fork> {
+        Fork::ALL.into_iter().find(|f| f.name() == name)
+    }
+
+    // the flags to pass to run_block_generator() and get_conditions_from_spendbundle()
+    // once this fork has activated
+    pub fn flags(self) -> u32 {
+        match self {
+            Fork::SoftFork2 => NO_RELATIVE_CONDITIONS_ON_EPHEMERAL,
+            Fork::SoftFork4 => ENABLE_MESSAGE_CONDITIONS,
+            Fork::SoftFork5 => DISALLOW_INFINITY_G1,
+            Fork::HardFork => {
+                ENABLE_BLS_OPS_OUTSIDE_GUARD | ENABLE_FIXED_DIV | AGG_SIG_ARGS | ALLOW_BACKREFS
+            }
+            Fork::HardForkFix | Fork::PlotFilter128 | Fork::PlotFilter64 | Fork::PlotFilter32 => 0,
+        }
+    }
+}
+
+// The activation heights of all forks, taken from the consensus constants.
+// A fork is active at its activation height and above.
+#[cfg_attr(feature = "py-bindings", pyclass(module = "gold_rs", frozen))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForkSchedule {
+    soft_fork2_height: u32,
+    soft_fork4_height: u32,
+    soft_fork5_height: u32,
+    hard_fork_height: u32,
+    hard_fork_fix_height: u32,
+    plot_filter_128_height: u32,
+    plot_filter_64_height: u32,
+    plot_filter_32_height: u32,
+    number_zero_bits_plot_filter: u8,
+}
+
+impl ForkSchedule {
+    pub fn new(constants: &ConsensusConstants) -> Self {
+        Self {
+            soft_fork2_height: constants.soft_fork2_height,
+            soft_fork4_height: constants.soft_fork4_height,
+            soft_fork5_height: constants.soft_fork5_height,
+            hard_fork_height: constants.hard_fork_height,
+            hard_fork_fix_height: constants.hard_fork_fix_height,
+            plot_filter_128_height: constants.plot_filter_128_height,
+            plot_filter_64_height: constants.plot_filter_64_height,
+            plot_filter_32_height: constants.plot_filter_32_height,
+            number_zero_bits_plot_filter: constants.number_zero_bits_plot_filter,
+        }
+    }
+
+    pub fn activation_height(&self, fork: Fork) -> u32 {
+        match fork {
+            Fork::SoftFork2 => self.soft_fork2_height,
+            Fork::SoftFork4 => self.soft_fork4_height,
+            Fork::SoftFork5 => self.soft_fork5_height,
+            Fork::HardFork => self.hard_fork_height,
+            Fork::HardForkFix => self.hard_fork_fix_height,
+            Fork::PlotFilter128 => self.plot_filter_128_height,
+            Fork::PlotFilter64 => self.plot_filter_64_height,
+            Fork::PlotFilter32 => self.plot_filter_32_height,
+        }
+    }
+
+    pub fn is_active(&self, fork: Fork, height: u32) -> bool {
+        height >= self.activation_height(fork)
+    }
+
+    // all forks that are active at the specified height
+    pub fn active_at(&self, height: u32) -> Vec<Fork> {
+        Fork::ALL
+            .into_iter()
+            .filter(|f| self.is_active(*f, height))
+            .collect()
+    }
+
+    // the condition and CLVM flags required to validate a block at the
+    // specified height. These are the consensus rules only, mempool
+    // validation also needs MEMPOOL_MODE
+    pub fn flags_for_height(&self, height: u32) -> u32 {
+        self.active_at(height)
+            .into_iter()
+            .fold(0, |flags, f| flags | f.flags())
+    }
+
+    // the number of zero bits the plot filter requires at the specified height.
+    // This mirrors calculate_prefix_bits() in chia: only the latest plot filter
+    // adjustment that's active applies, it's not the number of active ones
+    pub fn plot_filter_bits(&self, height: u32) -> u8 {
+        let reduction = if self.is_active(Fork::PlotFilter32, height) {
+            4
+        } else if self.is_active(Fork::PlotFilter64, height) {
+            3
+        } else if self.is_active(Fork::PlotFilter128, height) {
+            2
+        } else if self.is_active(Fork::HardFork, height) {
+            1
+        } else {
+            0
+        };
+        self.number_zero_bits_plot_filter.saturating_sub(reduction)
+    }
+}
+
+#[cfg(feature = "py-bindings")]
+fn parse_fork(name: &str) -> PyResult<Fork> {
+    Fork::from_name(name).ok_or_else(|| PyValueError::new_err(format!("unknown fork: {name}")))
+}
+
+#[cfg(feature = "py-bindings")]
+#[pymethods]
+impl ForkSchedule {
+    #[new]
+    fn py_new(constants: &ConsensusConstants) -> Self {
+        Self::new(constants)
+    }
+
+    // the names of all forks, in the order they were scheduled
+    #[staticmethod]
+    fn fork_names() -> Vec<&'static str> {
+        Fork::ALL.into_iter().map(Fork::name).collect()
+    }
+
+    #[pyo3(name = "activation_height")]
+    fn py_activation_height(&self, fork: &str) -> PyResult<u32> {
+        Ok(self.activation_height(parse_fork(fork)?))
+    }
+
+    #[pyo3(name = "is_active")]
+    fn py_is_active(&self, fork: &str, height: u32) -> PyResult<bool> {
+        Ok(self.is_active(parse_fork(fork)?, height))
+    }
+
+    #[pyo3(name = "active_at")]
+    fn py_active_at(&self, height: u32) -> HashSet<&'static str> {
+        self.active_at(height).into_iter().map(Fork::name).collect()
+    }
+
+    #[pyo3(name = "flags_for_height")]
+    fn py_flags_for_height(&self, height: u32) -> u32 {
+        self.flags_for_height(height)
+    }
+
+    #[pyo3(name = "plot_filter_bits")]
+    fn py_plot_filter_bits(&self, height: u32) -> u8 {
+        self.plot_filter_bits(height)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus_constants::TEST_CONSTANTS;
+    use rstest::rstest;
+
+    #[test]
+    fn test_fork_names() {
+        for fork in Fork::ALL {
+            assert_eq!(Fork::from_name(fork.name()), Some(fork));
+        }
+        assert_eq!(Fork::from_name("soft_fork3"), None);
+    }
+
+    #[rstest]
+    #[case(0, &[Fork::SoftFork2])]
+    #[case(5495999, &[Fork::SoftFork2])]
+    #[case(5496000, &[Fork::SoftFork2, Fork::HardFork, Fork::HardForkFix])]
+    #[case(5716000, &[Fork::SoftFork2, Fork::SoftFork4, Fork::HardFork, Fork::HardForkFix])]
+    #[case(
+        5940000,
+        &[Fork::SoftFork2, Fork::SoftFork4, Fork::SoftFork5, Fork::HardFork, Fork::HardForkFix]
+    )]
+    #[case(u32::MAX, &Fork::ALL)]
+    fn test_active_at(#[case] height: u32, #[case] expected: &[Fork]) {
+        let schedule = ForkSchedule::new(&TEST_CONSTANTS);
+        assert_eq!(schedule.active_at(height), expected);
+        for fork in Fork::ALL {
+            assert_eq!(schedule.is_active(fork, height), expected.contains(&fork));
+        }
+    }
+
+    #[rstest]
+    #[case(0, NO_RELATIVE_CONDITIONS_ON_EPHEMERAL)]
+    #[case(
+        5496000,
+        NO_RELATIVE_CONDITIONS_ON_EPHEMERAL
+            | ENABLE_BLS_OPS_OUTSIDE_GUARD
+            | ENABLE_FIXED_DIV
+            | AGG_SIG_ARGS
+            | ALLOW_BACKREFS
+    )]
+    #[case(
+        5940000,
+        NO_RELATIVE_CONDITIONS_ON_EPHEMERAL
+            | ENABLE_MESSAGE_CONDITIONS
+            | DISALLOW_INFINITY_G1
+            | ENABLE_BLS_OPS_OUTSIDE_GUARD
+            | ENABLE_FIXED_DIV
+            | AGG_SIG_ARGS
+            | ALLOW_BACKREFS
+    )]
+    fn test_flags_for_height(#[case] height: u32, #[case] expected: u32) {
+        let schedule = ForkSchedule::new(&TEST_CONSTANTS);
+        assert_eq!(schedule.flags_for_height(height), expected);
+    }
+
+    #[rstest]
+    #[case(0, 9)]
+    #[case(5496000, 8)]
+    #[case(10542000, 7)]
+    #[case(15592000, 6)]
+    #[case(20643000, 5)]
+    fn test_plot_filter_bits(#[case] height: u32, #[case] expected: u8) {
+        let schedule = ForkSchedule::new(&TEST_CONSTANTS);
+        assert_eq!(schedule.plot_filter_bits(height), expected);
+    }
+
+    #[rstest]
+    #[case(99, 9)]
+    #[case(100, 5)]
+    #[case(200, 5)]
+    fn test_plot_filter_bits_out_of_order(#[case] height: u32, #[case] expected: u8) {
+        // only the last plot filter adjustment is active. It still determines
+        // the number of bits on its own
+        let mut constants = TEST_CONSTANTS.clone();
+        constants.hard_fork_height = u32::MAX;
+        constants.plot_filter_128_height = u32::MAX;
+        constants.plot_filter_64_height = 200;
+        constants.plot_filter_32_height = 100;
+        let schedule = ForkSchedule::new(&constants);
+        assert_eq!(schedule.plot_filter_bits(height), expected);
+    }
+}
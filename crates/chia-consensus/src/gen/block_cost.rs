@@ -0,0 +1,94 @@
+use crate::allocator::make_allocator;
+use crate::consensus_constants::ConsensusConstants;
+use crate::gen::conditions::EmptyVisitor;
+use crate::gen::flags::{ALLOW_BACKREFS, SKIP_AGG_SIG_CHECKS};
+use crate::gen::run_block_generator::{run_block_generator, run_block_generator2};
+use crate::gen::validation_error::ValidationErr;
+use crate::generator_rom::COST_PER_BYTE;
+
+// The cost of a block, as recorded in its transactions info. byte_cost is the
+// part of the cost that's from the size of the generator, the rest is from
+// running it and from the conditions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockCost {
+    pub cost: u64,
+    pub byte_cost: u64,
+}
+
+// Runs the block generator and returns its cost, without keeping the
+// conditions of the spends or checking their public keys. This is meant for
+// tools like block explorers, that only need the cost of blocks that have
+// already been validated. The flags are the ones in effect at the block's
+// height (see ForkSchedule::flags_for_height()). Since the hard fork, whose
+// flags include ALLOW_BACKREFS, generators are run without the generator ROM,
+// which affects the cost.
+pub fn get_block_cost<GenBuf: AsRef<[u8]>>(
+    program: &[u8],
+    block_refs: &[GenBuf],
+    flags: u32,
+    constants: &ConsensusConstants,
+) -> Result<BlockCost, ValidationErr> {
+    let mut a = make_allocator(flags);
+    let max_cost = constants.max_block_cost_clvm;
+    let flags = flags | SKIP_AGG_SIG_CHECKS;
+    let conds = if (flags & ALLOW_BACKREFS) != 0 {
+        run_block_generator2::<_, EmptyVisitor>(&mut a, program, block_refs, max_cost, flags)?
+    } else {
+        run_block_generator::<_, EmptyVisitor>(&mut a, program, block_refs, max_cost, flags)?
+    };
+    Ok(BlockCost {
+        cost: conds.cost,
+        byte_cost: program.len() as u64 * COST_PER_BYTE,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus_constants::TEST_CONSTANTS;
+    use crate::gen::validation_error::ErrorCode;
+    use rstest::rstest;
+    use std::fs::read_to_string;
+
+    fn generator(name: &str) -> Vec<u8> {
+        let test_file =
+            read_to_string(format!("../../generator-tests/{name}.txt")).expect("test file");
+        let (generator, _) = test_file.split_once('\n').expect("invalid test file");
+        hex::decode(generator).expect("invalid hex encoded generator")
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(ALLOW_BACKREFS)]
+    fn test_get_block_cost(#[case] flags: u32) {
+        let program = generator("block-834752");
+        let refs: &[&[u8]] = &[];
+        let max_cost = TEST_CONSTANTS.max_block_cost_clvm;
+
+        let mut a = make_allocator(flags);
+        let expected = if flags == 0 {
+            run_block_generator::<_, EmptyVisitor>(&mut a, &program, refs, max_cost, flags)
+        } else {
+            run_block_generator2::<_, EmptyVisitor>(&mut a, &program, refs, max_cost, flags)
+        }
+        .expect("run_block_generator");
+
+        let cost = get_block_cost(&program, refs, flags, &TEST_CONSTANTS).expect("get_block_cost");
+        assert_eq!(
+            cost,
+            BlockCost {
+                cost: expected.cost,
+                byte_cost: program.len() as u64 * 12000,
+            }
+        );
+
+        let mut constants = TEST_CONSTANTS.clone();
+        constants.max_block_cost_clvm = expected.cost - 1;
+        assert_eq!(
+            get_block_cost(&program, refs, flags, &constants)
+                .unwrap_err()
+                .1,
+            ErrorCode::CostExceeded
+        );
+    }
+}
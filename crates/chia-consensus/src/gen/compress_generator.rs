@@ -0,0 +1,160 @@
+use crate::allocator::make_allocator;
+use crate::gen::flags::ALLOW_BACKREFS;
+use crate::gen::validation_error::{first, ValidationErr};
+use crate::generator_rom::CLVM_DESERIALIZER;
+use clvmr::chia_dialect::ChiaDialect;
+use clvmr::reduction::Reduction;
+use clvmr::run_program::run_program;
+use clvmr::serde::{
+    node_from_bytes, node_from_bytes_backrefs, node_to_bytes, node_to_bytes_backrefs,
+};
+use clvmr::LIMIT_HEAP;
+use std::io;
+
+// Re-encodes a transactions generator, that may reference the generators of
+// previous blocks (block_refs), as a self-contained generator serialized with
+// backrefs, the format blocks use since the hard fork. The generator is run
+// (but not the puzzles it returns) and the resulting list of spends is quoted,
+// the same way solution_generator() does it. The new generator returns
+// the same spends, but when run with run_block_generator() (rather than
+// run_block_generator2()) its cost will be different.
+// max_cost limits the cost of running the generator. With the ALLOW_BACKREFS
+// flag, the generator itself may be serialized with backrefs.
+pub fn compress_generator<GenBuf: AsRef<[u8]>>(
+    generator: &[u8],
+    block_refs: &[GenBuf],
+    max_cost: u64,
+    flags: u32,
+) -> Result<Vec<u8>, ValidationErr> {
+    let mut a = make_allocator(LIMIT_HEAP);
+    let clvm_deserializer = node_from_bytes(&mut a, &CLVM_DESERIALIZER)?;
+    let program = if (flags & ALLOW_BACKREFS) != 0 {
+        node_from_bytes_backrefs(&mut a, generator)?
+    } else {
+        node_from_bytes(&mut a, generator)?
+    };
+
+    let mut blocks = a.nil();
+    for g in block_refs.iter().rev() {
+        let ref_gen = a.new_atom(g.as_ref())?;
+        blocks = a.new_pair(ref_gen, blocks)?;
+    }
+    let mut args = a.new_pair(blocks, a.nil())?;
+    args = a.new_pair(clvm_deserializer, args)?;
+
+    let dialect = ChiaDialect::new(flags);
+    let Reduction(_, output) = run_program(&mut a, &dialect, program, args, max_cost)?;
+    let spends = first(&a, output)?;
+
+    // (q . (spends))
+    let quoted = a.new_pair(spends, a.nil())?;
+    let quoted = a.new_pair(a.one(), quoted)?;
+    Ok(node_to_bytes_backrefs(&a, quoted)?)
+}
+
+// Re-encodes a generator serialized with backrefs without them, e.g. to
+// measure how much smaller the backrefs serialization is. This doesn't
+// reintroduce any block references.
+pub fn decompress_generator(generator: &[u8]) -> io::Result<Vec<u8>> {
+    let mut a = make_allocator(LIMIT_HEAP);
+    let program = node_from_bytes_backrefs(&mut a, generator)?;
+    node_to_bytes(&a, program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gen::conditions::EmptyVisitor;
+    use crate::gen::owned_conditions::OwnedSpendBundleConditions;
+    use crate::gen::run_block_generator::run_block_generator2;
+    use crate::gen::solution_generator::solution_generator;
+    use crate::gen::validation_error::ErrorCode;
+    use chia_protocol::{Bytes32, Coin};
+    use clvmr::allocator::{Allocator, NodePtr};
+
+    // spends of coins with the puzzle "1" and a nil solution
+    fn spends_generator() -> Vec<u8> {
+        let puzzle: &[u8] = &[1];
+        let solution: &[u8] = &[0x80];
+        let spends = (0..10_u8).map(|i| {
+            (
+                Coin::new(Bytes32::from([i; 32]), Bytes32::from([42; 32]), i as u64),
+                puzzle,
+                solution,
+            )
+        });
+        solution_generator(spends).unwrap()
+    }
+
+    fn list(a: &mut Allocator, items: &[NodePtr]) -> NodePtr {
+        let mut ret = NodePtr::NIL;
+        for item in items.iter().rev() {
+            ret = a.new_pair(*item, ret).unwrap();
+        }
+        ret
+    }
+
+    // a generator that returns the spends of the first block it references:
+    // (r (a 2 (c (f 5) ())))
+    fn ref_generator() -> Vec<u8> {
+        let mut a = Allocator::new();
+        let [op_a, op_c, op_f, op_r] =
+            [2_u8, 4, 5, 6].map(|op| a.new_small_number(op as u32).unwrap());
+        let env_2 = a.new_small_number(2).unwrap();
+        let env_5 = a.new_small_number(5).unwrap();
+        let first_ref = list(&mut a, &[op_f, env_5]);
+        let args = list(&mut a, &[op_c, first_ref, NodePtr::NIL]);
+        let deserialize = list(&mut a, &[op_a, env_2, args]);
+        let program = list(&mut a, &[op_r, deserialize]);
+        node_to_bytes(&a, program).unwrap()
+    }
+
+    fn run(generator: &[u8], refs: &[&[u8]]) -> OwnedSpendBundleConditions {
+        let mut a = make_allocator(LIMIT_HEAP);
+        let conds = run_block_generator2::<&[u8], EmptyVisitor>(
+            &mut a,
+            generator,
+            refs,
+            11_000_000_000,
+            ALLOW_BACKREFS,
+        )
+        .unwrap();
+        OwnedSpendBundleConditions::from(&a, conds).unwrap()
+    }
+
+    #[test]
+    fn test_compress_generator() {
+        let block = spends_generator();
+        let generator = ref_generator();
+        let compressed = compress_generator(&generator, &[&block], 11_000_000_000, 0).unwrap();
+
+        let original = run(&generator, &[block.as_slice()]);
+        let new = run(&compressed, &[]);
+        assert_eq!(original.spends, new.spends);
+        assert_eq!(original.removal_amount, new.removal_amount);
+
+        // the decompressed generator is the same as the referenced one
+        assert_eq!(decompress_generator(&compressed).unwrap(), block);
+
+        // compressing a compressed generator doesn't change it
+        let again =
+            compress_generator(&compressed, &[] as &[&[u8]], 11_000_000_000, ALLOW_BACKREFS)
+                .unwrap();
+        assert_eq!(again, compressed);
+    }
+
+    #[test]
+    fn test_compress_failure() {
+        let generator = ref_generator();
+        // the generator requires a block reference
+        assert!(compress_generator(&generator, &[] as &[&[u8]], 11_000_000_000, 0).is_err());
+        // the generator is too expensive
+        let block = spends_generator();
+        assert_eq!(
+            compress_generator(&generator, &[&block], 10, 0)
+                .unwrap_err()
+                .1,
+            ErrorCode::CostExceeded
+        );
+    }
+}
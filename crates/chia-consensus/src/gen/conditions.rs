@@ -18,11 +18,12 @@ use super::sanitize_int::{sanitize_uint, SanitizedUint};
 use super::validation_error::{first, next, rest, ErrorCode, ValidationErr};
 use crate::gen::flags::{
     AGG_SIG_ARGS, COND_ARGS_NIL, DISALLOW_INFINITY_G1, NO_RELATIVE_CONDITIONS_ON_EPHEMERAL,
-    NO_UNKNOWN_CONDS, STRICT_ARGS_COUNT,
+    NO_UNKNOWN_CONDS, SKIP_AGG_SIG_CHECKS, STRICT_ARGS_COUNT,
 };
 use crate::gen::messages::{Message, SpendId};
 use crate::gen::spend_visitor::SpendVisitor;
 use crate::gen::validation_error::check_nil;
+use crate::gen::validation_policy::ValidationPolicy;
 use chia_bls::PublicKey;
 use chia_protocol::Bytes32;
 use clvmr::allocator::{Allocator, NodePtr, SExp};
@@ -51,6 +52,65 @@ pub const HAS_RELATIVE_CONDITION: u32 = 2;
 // 4. it has an output coin with the same puzzle hash as the spend itself
 pub const ELIGIBLE_FOR_FF: u32 = 4;
 
+// The reasons a spend may not be eligible for fast-forward (i.e. why
+// ELIGIBLE_FOR_FF isn't set). The mempool analysis records every reason that
+// applies to a spend, as a bit field in Spend::ff_ineligible
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FastForwardIneligible {
+    // singletons have odd amounts
+    EvenAmount = 1,
+    AssertMyCoinId = 2,
+    // an ASSERT_MY_PARENT_ID that isn't the second condition, which is the
+    // one the singleton top layer emits
+    AssertMyParentId = 4,
+    // AGG_SIG_ME, AGG_SIG_PARENT, AGG_SIG_PARENT_AMOUNT or
+    // AGG_SIG_PARENT_PUZZLE
+    AggSigCommitsToParent = 8,
+    // SEND_MESSAGE or RECEIVE_MESSAGE committing to the parent coin ID
+    MessageCommitsToParent = 16,
+    // there's no odd output coin with the same puzzle hash as the spent coin
+    NoSingletonOutput = 32,
+}
+
+impl FastForwardIneligible {
+    pub const ALL: [FastForwardIneligible; 6] = [
+        Self::EvenAmount,
+        Self::AssertMyCoinId,
+        Self::AssertMyParentId,
+        Self::AggSigCommitsToParent,
+        Self::MessageCommitsToParent,
+        Self::NoSingletonOutput,
+    ];
+
+    pub fn bit(self) -> u32 {
+        self as u32
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::EvenAmount => "EVEN_AMOUNT",
+            Self::AssertMyCoinId => "ASSERT_MY_COIN_ID",
+            Self::AssertMyParentId => "ASSERT_MY_PARENT_ID",
+            Self::AggSigCommitsToParent => "AGG_SIG_COMMITS_TO_PARENT",
+            Self::MessageCommitsToParent => "MESSAGE_COMMITS_TO_PARENT",
+            Self::NoSingletonOutput => "NO_SINGLETON_OUTPUT",
+        }
+    }
+
+    // the reasons set in the bit field, in the order of ALL
+    pub fn from_bits(bits: u32) -> Vec<Self> {
+        Self::ALL
+            .into_iter()
+            .filter(|r| (bits & r.bit()) != 0)
+            .collect()
+    }
+}
+
+// the max number of announcement and message conditions (creating or
+// asserting) a single spend may have
+pub const MAX_ANNOUNCEMENTS_PER_SPEND: u32 = 1024;
+
 pub struct EmptyVisitor {}
 
 impl SpendVisitor for EmptyVisitor {
@@ -73,6 +133,8 @@ impl SpendVisitor for MempoolVisitor {
         // spend eligible for fast-forward must be singletons, which use odd amounts
         if (spend.coin_amount & 1) == 1 {
             spend_flags |= ELIGIBLE_FOR_FF;
+        } else {
+            spend.ff_ineligible |= FastForwardIneligible::EvenAmount.bit();
         }
         spend.flags |= spend_flags;
 
@@ -84,7 +146,7 @@ impl SpendVisitor for MempoolVisitor {
     fn condition(&mut self, spend: &mut Spend, c: &Condition) {
         match c {
             Condition::AssertMyCoinId(_) => {
-                spend.flags &= !ELIGIBLE_FOR_FF;
+                spend.not_ff(FastForwardIneligible::AssertMyCoinId);
             }
             Condition::AssertMyParentId(_) => {
                 // the singleton_top_layer_v1_1.clsp will only emit two
@@ -94,16 +156,16 @@ impl SpendVisitor for MempoolVisitor {
                 // by the inner puzzle, which we don't have control over. So in
                 // that case this spend is not eligible for fast-forward.
                 if self.condition_counter != 1 {
-                    spend.flags &= !ELIGIBLE_FOR_FF;
+                    spend.not_ff(FastForwardIneligible::AssertMyParentId);
                 }
             }
             Condition::AggSigMe(_, _) => {
                 spend.flags &= !ELIGIBLE_FOR_DEDUP;
-                spend.flags &= !ELIGIBLE_FOR_FF;
+                spend.not_ff(FastForwardIneligible::AggSigCommitsToParent);
             }
             Condition::AggSigParent(_, _) => {
                 spend.flags &= !ELIGIBLE_FOR_DEDUP;
-                spend.flags &= !ELIGIBLE_FOR_FF;
+                spend.not_ff(FastForwardIneligible::AggSigCommitsToParent);
             }
             Condition::AggSigPuzzle(_, _) => {
                 spend.flags &= !ELIGIBLE_FOR_DEDUP;
@@ -116,18 +178,18 @@ impl SpendVisitor for MempoolVisitor {
             }
             Condition::AggSigParentAmount(_, _) => {
                 spend.flags &= !ELIGIBLE_FOR_DEDUP;
-                spend.flags &= !ELIGIBLE_FOR_FF;
+                spend.not_ff(FastForwardIneligible::AggSigCommitsToParent);
             }
             Condition::AggSigParentPuzzle(_, _) => {
                 spend.flags &= !ELIGIBLE_FOR_DEDUP;
-                spend.flags &= !ELIGIBLE_FOR_FF;
+                spend.not_ff(FastForwardIneligible::AggSigCommitsToParent);
             }
             Condition::AggSigUnsafe(_, _) => {
                 spend.flags &= !ELIGIBLE_FOR_DEDUP;
             }
             Condition::SendMessage(src_mode, _dst, _msg) => {
                 if (src_mode & super::messages::PARENT) != 0 {
-                    spend.flags &= !ELIGIBLE_FOR_FF;
+                    spend.not_ff(FastForwardIneligible::MessageCommitsToParent);
                 }
                 // de-duplicating a coin spend that's sending a message may
                 // leave a receiver without a message, which is a failure
@@ -135,7 +197,7 @@ impl SpendVisitor for MempoolVisitor {
             }
             Condition::ReceiveMessage(_src, dst_mode, _msg) => {
                 if (dst_mode & super::messages::PARENT) != 0 {
-                    spend.flags &= !ELIGIBLE_FOR_FF;
+                    spend.not_ff(FastForwardIneligible::MessageCommitsToParent);
                 }
                 // de-duplicating a coin spend that's receiving a message may
                 // leave a sent-message un-received, which is a failure
@@ -147,16 +209,14 @@ impl SpendVisitor for MempoolVisitor {
     }
 
     fn post_spend(&mut self, a: &Allocator, spend: &mut Spend) {
-        // if this still looks like it might be a singleton, check the output coins
-        // to look for something that looks like a singleton output, with the same
-        // puzzle hash as our input coin
-        if (spend.flags & ELIGIBLE_FOR_FF) != 0
-            && !spend.create_coin.iter().any(|c| {
-                (c.amount & 1) == 1
-                    && a.atom(spend.puzzle_hash).as_ref() == c.puzzle_hash.as_slice()
-            })
-        {
-            spend.flags &= !ELIGIBLE_FOR_FF;
+        // check the output coins to look for something that looks like a
+        // singleton output, with the same puzzle hash as our input coin. This
+        // is checked even if the spend is already known not to be eligible, to
+        // report all reasons
+        if !spend.create_coin.iter().any(|c| {
+            (c.amount & 1) == 1 && a.atom(spend.puzzle_hash).as_ref() == c.puzzle_hash.as_slice()
+        }) {
+            spend.not_ff(FastForwardIneligible::NoSingletonOutput);
         }
     }
 }
@@ -677,6 +737,9 @@ pub struct Spend {
     pub agg_sig_parent_puzzle: Vec<(PublicKey, NodePtr)>,
     // Flags describing properties of this spend. See flags above
     pub flags: u32,
+    // the reasons this spend isn't eligible for fast-forward, a bit field of
+    // FastForwardIneligible. Only set by the MempoolVisitor
+    pub ff_ineligible: u32,
 }
 
 impl Spend {
@@ -706,8 +769,21 @@ impl Spend {
             agg_sig_parent_amount: Vec::new(),
             agg_sig_parent_puzzle: Vec::new(),
             flags: 0,
+            ff_ineligible: 0,
         }
     }
+
+    // the reasons recorded in ff_ineligible. They aren't part of the
+    // streamable OwnedSpend, analyze_spend() reports them for a single spend
+    pub fn ff_ineligible_reasons(&self) -> Vec<FastForwardIneligible> {
+        FastForwardIneligible::from_bits(self.ff_ineligible)
+    }
+
+    // clears ELIGIBLE_FOR_FF and records why
+    fn not_ff(&mut self, reason: FastForwardIneligible) {
+        self.flags &= !ELIGIBLE_FOR_FF;
+        self.ff_ineligible |= reason.bit();
+    }
 }
 
 // these are all the conditions and properties of a complete spend bundle.
@@ -801,6 +877,21 @@ pub struct ParseState {
     // ASSERT_MY_BIRTH_HEIGHT
     // each item is the index into the SpendBundleConditions::spends vector
     assert_not_ephemeral: HashSet<usize>,
+
+    // the limits on the number of conditions
+    policy: ValidationPolicy,
+
+    // the number of conditions parsed so far, across all spends
+    num_conditions: u32,
+}
+
+impl ParseState {
+    pub fn new(policy: ValidationPolicy) -> Self {
+        Self {
+            policy,
+            ..Default::default()
+        }
+    }
 }
 
 // returns (parent-id, puzzle-hash, amount, condition-list)
@@ -892,6 +983,9 @@ fn decrement(cnt: &mut u32, n: NodePtr) -> Result<(), ValidationErr> {
 }
 
 fn to_key(a: &Allocator, pk: NodePtr, flags: u32) -> Result<Option<PublicKey>, ValidationErr> {
+    if (flags & SKIP_AGG_SIG_CHECKS) != 0 {
+        return Ok(None);
+    }
     let key = PublicKey::from_bytes(a.atom(pk).as_ref().try_into().expect("internal error"))
         .map_err(|_| ValidationErr(pk, ErrorCode::InvalidPublicKey))?;
     if key.is_inf() {
@@ -916,7 +1010,8 @@ pub fn parse_conditions<V: SpendVisitor>(
     max_cost: &mut Cost,
     visitor: &mut V,
 ) -> Result<(), ValidationErr> {
-    let mut announce_countdown: u32 = 1024;
+    let mut announce_countdown: u32 = state.policy.max_announcements_per_spend;
+    let mut create_coin_countdown: u32 = state.policy.max_create_coin_per_spend;
 
     while let Some((mut c, next)) = next(a, iter)? {
         iter = next;
@@ -929,6 +1024,11 @@ pub fn parse_conditions<V: SpendVisitor>(
             continue;
         };
 
+        if state.num_conditions >= state.policy.max_conditions {
+            return Err(ValidationErr(c, ErrorCode::TooManyConditions));
+        }
+        state.num_conditions += 1;
+
         // subtract the max_cost based on the current condition
         // in case we exceed the limit, we want to fail as early as possible
         match op {
@@ -937,6 +1037,10 @@ pub fn parse_conditions<V: SpendVisitor>(
                     return Err(ValidationErr(c, ErrorCode::CostExceeded));
                 }
                 *max_cost -= CREATE_COIN_COST;
+                if create_coin_countdown == 0 {
+                    return Err(ValidationErr(c, ErrorCode::TooManyCreateCoins));
+                }
+                create_coin_countdown -= 1;
             }
             AGG_SIG_UNSAFE
             | AGG_SIG_ME
@@ -1269,9 +1373,20 @@ pub fn parse_spends<V: SpendVisitor>(
     spends: NodePtr,
     max_cost: Cost,
     flags: u32,
+) -> Result<SpendBundleConditions, ValidationErr> {
+    parse_spends_with_policy::<V>(a, spends, max_cost, flags, &ValidationPolicy::CONSENSUS)
+}
+
+// like parse_spends(), but with custom limits on the number of conditions
+pub fn parse_spends_with_policy<V: SpendVisitor>(
+    a: &Allocator,
+    spends: NodePtr,
+    max_cost: Cost,
+    flags: u32,
+    policy: &ValidationPolicy,
 ) -> Result<SpendBundleConditions, ValidationErr> {
     let mut ret = SpendBundleConditions::default();
-    let mut state = ParseState::default();
+    let mut state = ParseState::new(*policy);
 
     let mut cost_left = max_cost;
 
@@ -3072,6 +3187,39 @@ fn test_single_agg_sig_me(
     assert_eq!(spend.flags, 0);
 }
 
+#[cfg(test)]
+#[rstest]
+#[case(AGG_SIG_ME)]
+#[case(AGG_SIG_PARENT)]
+#[case(AGG_SIG_PUZZLE)]
+#[case(AGG_SIG_AMOUNT)]
+#[case(AGG_SIG_PUZZLE_AMOUNT)]
+#[case(AGG_SIG_PARENT_PUZZLE)]
+#[case(AGG_SIG_PARENT_AMOUNT)]
+#[case(AGG_SIG_UNSAFE)]
+fn test_skip_agg_sig_checks(
+    #[case] condition: ConditionOpcode,
+    #[values(MEMPOOL_MODE, 0)] mempool: u32,
+) {
+    let (_, conds) = cond_test_flag(
+        &format!(
+            "((({{h1}} ({{h2}} (123 ((({} ({{pubkey}} ({{msg1}} )))))",
+            condition
+        ),
+        ENABLE_SOFTFORK_CONDITION | SKIP_AGG_SIG_CHECKS | mempool,
+    )
+    .unwrap();
+
+    // the condition is still paid for, but not collected
+    assert_eq!(conds.cost, AGG_SIG_COST);
+    assert_eq!(conds.spends.len(), 1);
+    assert_eq!(conds.removal_amount, 123);
+    assert!(conds.agg_sig_unsafe.is_empty());
+    if condition != AGG_SIG_UNSAFE {
+        assert!(agg_sig_vec(condition, &conds.spends[0]).is_empty());
+    }
+}
+
 #[cfg(test)]
 #[rstest]
 #[case(AGG_SIG_ME)]
@@ -4512,6 +4660,50 @@ fn test_limit_announcements(
     }
 }
 
+#[cfg(test)]
+#[rstest]
+#[case(ValidationPolicy::CONSENSUS, None)]
+#[case(ValidationPolicy { max_create_coin_per_spend: 2, ..ValidationPolicy::CONSENSUS }, None)]
+#[case(
+    ValidationPolicy { max_create_coin_per_spend: 1, ..ValidationPolicy::CONSENSUS },
+    Some(ErrorCode::TooManyCreateCoins)
+)]
+#[case(ValidationPolicy { max_announcements_per_spend: 1, ..ValidationPolicy::CONSENSUS }, None)]
+#[case(
+    ValidationPolicy { max_announcements_per_spend: 0, ..ValidationPolicy::CONSENSUS },
+    Some(ErrorCode::TooManyAnnouncements)
+)]
+#[case(ValidationPolicy { max_conditions: 5, ..ValidationPolicy::CONSENSUS }, None)]
+#[case(
+    ValidationPolicy { max_conditions: 4, ..ValidationPolicy::CONSENSUS },
+    Some(ErrorCode::TooManyConditions)
+)]
+fn test_validation_policy(#[case] policy: ValidationPolicy, #[case] expect_err: Option<ErrorCode>) {
+    // 51=CREATE_COIN
+    // 60=CREATE_COIN_ANNOUNCEMENT
+    // 2=unknown condition, which isn't counted
+    let test = "(\
+       (({h1} ({h2} (123 (\
+           ((51 ({h2} (42 ) \
+           ((51 ({h1} (42 ) \
+           ((60 ({msg1} ) \
+           ((2 ) \
+           ))\
+       (({h2} ({h2} (123 (\
+           ((51 ({h2} (10 ) \
+           ((60 ({msg1} ) \
+           ))\
+       ))";
+
+    let mut a = Allocator::new();
+    let n = parse_list(&mut a, test, &None);
+    let r = parse_spends_with_policy::<MempoolVisitor>(&a, n, 11000000000, 0, &policy);
+    match expect_err {
+        Some(err) => assert_eq!(r.unwrap_err().1, err),
+        None => assert_eq!(r.unwrap().spends.len(), 2),
+    }
+}
+
 #[test]
 fn test_eligible_for_ff_assert_parent() {
     // this is a model example of a spend that's eligible for FF
@@ -4553,6 +4745,54 @@ fn test_eligible_for_ff_even_amount() {
     assert!((cond.spends[0].flags & ELIGIBLE_FOR_FF) == 0);
 }
 
+#[cfg(test)]
+#[rstest]
+#[case("((73 (123 ) ((71 ({h1} ) ((51 ({h2} (123 )", &[])]
+#[case("((70 ({coin12} ) ((51 ({h2} (123 )", &[FastForwardIneligible::AssertMyCoinId])]
+#[case("((71 ({h1} ) ((51 ({h2} (123 )", &[FastForwardIneligible::AssertMyParentId])]
+#[case(
+    "((50 ({pubkey} ({msg1} ) ((51 ({h2} (123 )",
+    &[FastForwardIneligible::AggSigCommitsToParent]
+)]
+#[case(
+    "((66 (0x04 ({msg1} ({h1} ) ((67 (0x04 ({msg1} ) ((51 ({h2} (123 )",
+    &[FastForwardIneligible::MessageCommitsToParent]
+)]
+#[case(
+    "((70 ({coin12} ) ((50 ({pubkey} ({msg1} ) ((51 ({h1} (123 )",
+    &[
+        FastForwardIneligible::AssertMyCoinId,
+        FastForwardIneligible::AggSigCommitsToParent,
+        FastForwardIneligible::NoSingletonOutput
+    ]
+)]
+fn test_ff_ineligible_reasons(
+    #[case] conditions: &str,
+    #[case] expected: &[FastForwardIneligible],
+) {
+    // 73=ASSERT_MY_AMOUNT
+    // 71=ASSERT_MY_PARENT_ID
+    // 70=ASSERT_MY_COIN_ID
+    // 67=RECEIVE_MESSAGE
+    // 66=SEND_MESSAGE
+    // 51=CREATE_COIN
+    // 50=AGG_SIG_ME
+    let test: &str = &format!(
+        "(\
+       (({{h1}} ({{h2}} (123 (\
+           {} \
+           ))\
+       ))",
+        conditions
+    );
+
+    let (_a, cond) = cond_test(test).expect("cond_test");
+    assert!(cond.spends.len() == 1);
+    let spend = &cond.spends[0];
+    assert_eq!(spend.ff_ineligible_reasons(), expected);
+    assert_eq!((spend.flags & ELIGIBLE_FOR_FF) != 0, expected.is_empty());
+}
+
 #[cfg(test)]
 #[rstest]
 #[case(123, "{h2}", true)]
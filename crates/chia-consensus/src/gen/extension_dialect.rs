@@ -0,0 +1,191 @@
+use clvmr::allocator::{Allocator, NodePtr, SExp};
+use clvmr::chia_dialect::ChiaDialect;
+use clvmr::cost::Cost;
+use clvmr::dialect::{Dialect, OperatorSet};
+use clvmr::reduction::Response;
+use clvmr::run_program::run_program;
+use std::collections::HashMap;
+
+// An extension operator is passed the allocator, the (evaluated) argument
+// list and the remaining cost budget. Just like the built-in operators, it
+// returns its cost along with the result
+pub type ExtensionOperator = Box<dyn Fn(&mut Allocator, NodePtr, Cost) -> Response + Send + Sync>;
+
+// A ChiaDialect with additional operators, registered by the caller. This is
+// meant for prototyping new operators (e.g. for a softfork) against the
+// consensus rules of this crate, it must not be used to validate blocks.
+// Extension operators take precedence over the built-in operators with the
+// same opcode, and are available both inside and outside of the softfork
+// guard.
+pub struct ExtensionDialect {
+    inner: ChiaDialect,
+    operators: HashMap<Vec<u8>, ExtensionOperator>,
+}
+
+pub struct ExtensionDialectBuilder {
+    flags: u32,
+    operators: HashMap<Vec<u8>, ExtensionOperator>,
+}
+
+impl ExtensionDialectBuilder {
+    // the flags are the same as for ChiaDialect, and control the built-in
+    // operators
+    pub fn new(flags: u32) -> Self {
+        Self {
+            flags,
+            operators: HashMap::new(),
+        }
+    }
+
+    // registers (or replaces) the operator with the specified opcode
+    pub fn operator<F>(mut self, opcode: &[u8], op: F) -> Self
+    where
+        F: Fn(&mut Allocator, NodePtr, Cost) -> Response + Send + Sync + 'static,
+    {
+        self.operators.insert(opcode.to_vec(), Box::new(op));
+        self
+    }
+
+    pub fn build(self) -> ExtensionDialect {
+        ExtensionDialect {
+            inner: ChiaDialect::new(self.flags),
+            operators: self.operators,
+        }
+    }
+}
+
+impl ExtensionDialect {
+    pub fn builder(flags: u32) -> ExtensionDialectBuilder {
+        ExtensionDialectBuilder::new(flags)
+    }
+
+    // like run_chia_program(), but with the extension operators
+    pub fn run_program(
+        &self,
+        a: &mut Allocator,
+        program: NodePtr,
+        args: NodePtr,
+        max_cost: Cost,
+    ) -> Response {
+        run_program(a, self, program, args, max_cost)
+    }
+}
+
+impl Dialect for ExtensionDialect {
+    fn quote_kw(&self) -> u32 {
+        self.inner.quote_kw()
+    }
+
+    fn apply_kw(&self) -> u32 {
+        self.inner.apply_kw()
+    }
+
+    fn softfork_kw(&self) -> u32 {
+        self.inner.softfork_kw()
+    }
+
+    fn softfork_extension(&self, ext: u32) -> OperatorSet {
+        self.inner.softfork_extension(ext)
+    }
+
+    fn op(
+        &self,
+        allocator: &mut Allocator,
+        o: NodePtr,
+        argument_list: NodePtr,
+        max_cost: Cost,
+        extensions: OperatorSet,
+    ) -> Response {
+        if let SExp::Atom = allocator.sexp(o) {
+            if let Some(op) = self.operators.get(allocator.atom(o).as_ref()) {
+                return op(allocator, argument_list, max_cost);
+            }
+        }
+        self.inner
+            .op(allocator, o, argument_list, max_cost, extensions)
+    }
+
+    fn allow_unknown_ops(&self) -> bool {
+        self.inner.allow_unknown_ops()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clvmr::reduction::{EvalErr, Reduction};
+    use clvmr::serde::node_from_bytes;
+    use clvmr::NO_UNKNOWN_OPS;
+
+    // (op (q . 5) (q . 7))
+    fn call(a: &mut Allocator, opcode: &[u8]) -> NodePtr {
+        let op = a.new_atom(opcode).unwrap();
+        let five = a.new_small_number(5).unwrap();
+        let seven = a.new_small_number(7).unwrap();
+        let q5 = a.new_pair(a.one(), five).unwrap();
+        let q7 = a.new_pair(a.one(), seven).unwrap();
+        let args = a.new_pair(q7, a.nil()).unwrap();
+        let args = a.new_pair(q5, args).unwrap();
+        a.new_pair(op, args).unwrap()
+    }
+
+    // returns the second argument
+    fn second(a: &mut Allocator, args: NodePtr, _max_cost: Cost) -> Response {
+        let SExp::Pair(_, rest) = a.sexp(args) else {
+            return Err(EvalErr(args, "expected two arguments".to_string()));
+        };
+        let SExp::Pair(second, _) = a.sexp(rest) else {
+            return Err(EvalErr(args, "expected two arguments".to_string()));
+        };
+        Ok(Reduction(1000, second))
+    }
+
+    #[test]
+    fn test_extension_operator() {
+        let dialect = ExtensionDialect::builder(NO_UNKNOWN_OPS)
+            .operator(&[0x7e, 0x01], second)
+            .build();
+
+        let mut a = Allocator::new();
+        let nil = a.nil();
+        let program = call(&mut a, &[0x7e, 0x01]);
+        let Reduction(cost, result) = dialect
+            .run_program(&mut a, program, nil, 11_000_000_000)
+            .unwrap();
+        assert_eq!(a.number(result), 7.into());
+        assert!(cost >= 1000);
+
+        // built-in operators still work, (+ (q . 5) (q . 7))
+        let program = call(&mut a, &[16]);
+        let Reduction(_, result) = dialect
+            .run_program(&mut a, program, nil, 11_000_000_000)
+            .unwrap();
+        assert_eq!(a.number(result), 12.into());
+
+        // other unknown operators are still rejected
+        let program = call(&mut a, &[0x7e, 0x02]);
+        assert!(dialect
+            .run_program(&mut a, program, nil, 11_000_000_000)
+            .is_err());
+    }
+
+    #[test]
+    fn test_override_builtin() {
+        // (+ (q . 5) (q . 7)) with + replaced
+        let dialect = ExtensionDialect::builder(0).operator(&[16], second).build();
+        let mut a = Allocator::new();
+        let nil = a.nil();
+        let program = call(&mut a, &[16]);
+        let Reduction(_, result) = dialect
+            .run_program(&mut a, program, nil, 11_000_000_000)
+            .unwrap();
+        assert_eq!(a.number(result), 7.into());
+
+        // the regular dialect is unaffected
+        let program =
+            node_from_bytes(&mut a, &hex::decode("ff10ffff0105ffff010780").unwrap()).unwrap();
+        let Reduction(_, result) =
+            run_program(&mut a, &ChiaDialect::new(0), program, nil, 11_000_000_000).unwrap();
+        assert_eq!(a.number(result), 12.into());
+    }
+}
@@ -42,6 +42,14 @@ pub const ENABLE_MESSAGE_CONDITIONS: u32 = 0x8000000;
 // this flag is a soft-fork.
 pub const DISALLOW_INFINITY_G1: u32 = 0x10000000;
 
+// When set, AGG_SIG_* conditions are still parsed and their cost is still
+// counted, but their public keys are not decoded and the (public key, message)
+// pairs are not collected. This is for tools that only need the additions and
+// removals of a block, like explorers. It must not be used for validation,
+// since invalid public keys are not detected, and the signature can't be
+// verified without the pairs.
+pub const SKIP_AGG_SIG_CHECKS: u32 = 0x20000000;
+
 pub const MEMPOOL_MODE: u32 = CLVM_MEMPOOL_MODE
     | NO_UNKNOWN_CONDS
     | COND_ARGS_NIL
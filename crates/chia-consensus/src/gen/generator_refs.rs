@@ -0,0 +1,326 @@
+use crate::gen::conditions::SpendBundleConditions;
+use crate::gen::run_block_generator::{
+    run_block_generator2_with_refs, run_block_generator_with_refs,
+};
+use crate::gen::spend_visitor::SpendVisitor;
+use crate::gen::validation_error::{ErrorCode, ValidationErr};
+use chia_protocol::{
+    EndOfSubSlotBundle, Foliage, FoliageTransactionBlock, RewardChainBlock, TransactionsInfo,
+    VDFProof,
+};
+use chia_traits::{Error, Streamable};
+use clvmr::allocator::{Allocator, NodePtr};
+use clvmr::serde::serialized_length_from_bytes;
+use std::collections::HashMap;
+use std::io::Cursor;
+
+// Looks up the transactions generator of a previous block, by height. This is
+// used to fetch the generators referenced by a block (its
+// transactions_generator_ref_list) on demand, rather than having the caller
+// load all of them up-front.
+pub trait GeneratorRefResolver {
+    // returns the transactions generator of the block at the specified height,
+    // or None if that block doesn't have one (or doesn't exist)
+    fn resolve(&mut self, height: u32) -> Result<Option<Vec<u8>>, ValidationErr>;
+}
+
+impl<F> GeneratorRefResolver for F
+where
+    F: FnMut(u32) -> Result<Option<Vec<u8>>, ValidationErr>,
+{
+    fn resolve(&mut self, height: u32) -> Result<Option<Vec<u8>>, ValidationErr> {
+        self(height)
+    }
+}
+
+// Builds the list of generators passed to a block's generator program, by
+// resolving the heights in ref_list one at a time. Each generator is copied
+// into the allocator as soon as it's been resolved, so at most one of them is
+// held outside of it at any time. Every height is only requested from the
+// resolver once, even if the block refers to it multiple times, and resolution
+// stops at the first height that doesn't have a generator.
+pub fn resolve_block_refs<R: GeneratorRefResolver + ?Sized>(
+    a: &mut Allocator,
+    ref_list: &[u32],
+    resolver: &mut R,
+) -> Result<NodePtr, ValidationErr> {
+    let mut cache = HashMap::<u32, NodePtr>::new();
+    let mut refs = Vec::<NodePtr>::with_capacity(ref_list.len());
+    for height in ref_list {
+        if let Some(generator) = cache.get(height) {
+            refs.push(*generator);
+            continue;
+        }
+        let Some(generator) = resolver.resolve(*height)? else {
+            return Err(ValidationErr(
+                NodePtr::NIL,
+                ErrorCode::GeneratorRefHasNoGenerator,
+            ));
+        };
+        let generator = a.new_atom(&generator)?;
+        cache.insert(*height, generator);
+        refs.push(generator);
+    }
+
+    // iterate in reverse order since we're building a linked list from
+    // the tail
+    let mut list = a.nil();
+    for generator in refs.into_iter().rev() {
+        list = a.new_pair(generator, list)?;
+    }
+    Ok(list)
+}
+
+// Same as run_block_generator(), but the block references are specified as
+// heights. Their generators are fetched from the resolver once the program has
+// been deserialized, so nothing is fetched for a generator that fails to parse
+// or exceeds the cost limit by its size alone
+pub fn run_block_generator_with_resolver<R: GeneratorRefResolver + ?Sized, V: SpendVisitor>(
+    a: &mut Allocator,
+    program: &[u8],
+    ref_list: &[u32],
+    resolver: &mut R,
+    max_cost: u64,
+    flags: u32,
+) -> Result<SpendBundleConditions, ValidationErr> {
+    run_block_generator_with_refs::<V>(
+        a,
+        program,
+        |a: &mut Allocator| resolve_block_refs(a, ref_list, resolver),
+        max_cost,
+        flags,
+    )
+}
+
+// Same as run_block_generator2(), but the block references are specified as
+// heights, and their generators are fetched from the resolver on demand, the
+// same way as run_block_generator_with_resolver()
+pub fn run_block_generator2_with_resolver<R: GeneratorRefResolver + ?Sized, V: SpendVisitor>(
+    a: &mut Allocator,
+    program: &[u8],
+    ref_list: &[u32],
+    resolver: &mut R,
+    max_cost: u64,
+    flags: u32,
+) -> Result<SpendBundleConditions, ValidationErr> {
+    run_block_generator2_with_refs::<V>(
+        a,
+        program,
+        |a: &mut Allocator| resolve_block_refs(a, ref_list, resolver),
+        max_cost,
+        flags,
+        false,
+    )
+}
+
+// The transactions_generator_ref_list of a serialized FullBlock, without
+// running the generator. The ref list isn't part of the generator itself, it's
+// the last field of the block, right after the generator. The header fields
+// before it are parsed (they're small), but the generator is skipped by its
+// serialized length without copying it, so this is cheap even for large
+// blocks. This lets block fetching request the referenced blocks before the
+// block is validated. Blocks since the hard fork don't reference previous
+// generators, so their list is empty.
+pub fn block_generator_refs(block: &[u8]) -> chia_traits::Result<Vec<u32>> {
+    let mut input = Cursor::new(block);
+    Vec::<EndOfSubSlotBundle>::parse::<false>(&mut input)?;
+    RewardChainBlock::parse::<false>(&mut input)?;
+    Option::<VDFProof>::parse::<false>(&mut input)?;
+    VDFProof::parse::<false>(&mut input)?;
+    Option::<VDFProof>::parse::<false>(&mut input)?;
+    VDFProof::parse::<false>(&mut input)?;
+    Option::<VDFProof>::parse::<false>(&mut input)?;
+    Foliage::parse::<false>(&mut input)?;
+    Option::<FoliageTransactionBlock>::parse::<false>(&mut input)?;
+    Option::<TransactionsInfo>::parse::<false>(&mut input)?;
+    skip_generator(&mut input)?;
+    let ref_list = Vec::<u32>::parse::<false>(&mut input)?;
+    if input.position() as usize != block.len() {
+        return Err(Error::InputTooLarge);
+    }
+    Ok(ref_list)
+}
+
+// skips the serialized Option<Program> of the generator
+fn skip_generator(input: &mut Cursor<&[u8]>) -> chia_traits::Result<()> {
+    match u8::parse::<false>(input)? {
+        0 => Ok(()),
+        1 => {
+            let pos = input.position();
+            let buf = &input.get_ref()[pos as usize..];
+            let len = serialized_length_from_bytes(buf).map_err(|_| Error::EndOfBuffer)?;
+            if buf.len() < len as usize {
+                return Err(Error::EndOfBuffer);
+            }
+            input.set_position(pos + len);
+            Ok(())
+        }
+        _ => Err(Error::InvalidOptional),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gen::conditions::MempoolVisitor;
+
+    #[test]
+    fn test_skip_generator() {
+        // (1 . ()) followed by the next field
+        let buf = [1, 0xff, 0x01, 0x80, 7];
+        let mut input = Cursor::new(&buf[..]);
+        skip_generator(&mut input).expect("skip");
+        assert_eq!(input.position(), 4);
+
+        assert_eq!(skip_generator(&mut Cursor::new(&[0_u8][..])), Ok(()));
+        assert_eq!(
+            skip_generator(&mut Cursor::new(&[1_u8, 0xff, 0x01][..])),
+            Err(Error::EndOfBuffer)
+        );
+        assert_eq!(
+            skip_generator(&mut Cursor::new(&[2_u8][..])),
+            Err(Error::InvalidOptional)
+        );
+    }
+
+    #[test]
+    fn test_resolve_duplicates() {
+        let mut requested = Vec::<u32>::new();
+        let mut resolver = |height: u32| -> Result<Option<Vec<u8>>, ValidationErr> {
+            requested.push(height);
+            Ok(Some(vec![height as u8; 3]))
+        };
+        let mut a = Allocator::new();
+        let mut refs =
+            resolve_block_refs(&mut a, &[5, 2, 5, 7, 2], &mut resolver).expect("resolve");
+        assert_eq!(requested, [5, 2, 7]);
+        let mut items = Vec::<NodePtr>::new();
+        while let Some((item, rest)) = a.next(refs) {
+            items.push(item);
+            refs = rest;
+        }
+        // the same height is only stored in the allocator once
+        assert_eq!(items[0], items[2]);
+        assert_eq!(items[1], items[4]);
+        let items: Vec<Vec<u8>> = items.iter().map(|r| a.atom(*r).as_ref().to_vec()).collect();
+        assert_eq!(
+            items,
+            [[5, 5, 5], [2, 2, 2], [5, 5, 5], [7, 7, 7], [2, 2, 2]]
+        );
+    }
+
+    #[test]
+    fn test_resolve_missing() {
+        let mut requested = Vec::<u32>::new();
+        let mut resolver = |height: u32| -> Result<Option<Vec<u8>>, ValidationErr> {
+            requested.push(height);
+            Ok(if height == 2 { None } else { Some(vec![1]) })
+        };
+        let mut a = Allocator::new();
+        let err = resolve_block_refs(&mut a, &[1, 2, 3], &mut resolver).unwrap_err();
+        assert_eq!(err.1, ErrorCode::GeneratorRefHasNoGenerator);
+        // we stop at the first missing generator
+        assert_eq!(requested, [1, 2]);
+    }
+
+    #[test]
+    fn test_resolver_error() {
+        let mut resolver = |_height: u32| -> Result<Option<Vec<u8>>, ValidationErr> {
+            Err(ValidationErr(NodePtr::NIL, ErrorCode::FutureGeneratorRefs))
+        };
+        let mut a = Allocator::new();
+        let err = resolve_block_refs(&mut a, &[1], &mut resolver).unwrap_err();
+        assert_eq!(err.1, ErrorCode::FutureGeneratorRefs);
+    }
+
+    #[test]
+    fn test_run_with_resolver() {
+        // (q . (())) returns an empty list of spends
+        let program = [0xff, 0x01, 0xff, 0x80, 0x80];
+        let mut resolver =
+            |_height: u32| -> Result<Option<Vec<u8>>, ValidationErr> { Ok(Some(vec![0x80])) };
+
+        let mut a = Allocator::new();
+        let conds = run_block_generator2_with_resolver::<_, MempoolVisitor>(
+            &mut a,
+            &program,
+            &[1, 2],
+            &mut resolver,
+            11_000_000_000,
+            0,
+        )
+        .expect("run_block_generator2");
+        assert!(conds.spends.is_empty());
+
+        let mut missing = |_height: u32| -> Result<Option<Vec<u8>>, ValidationErr> { Ok(None) };
+        let err = run_block_generator2_with_resolver::<_, MempoolVisitor>(
+            &mut a,
+            &program,
+            &[1],
+            &mut missing,
+            11_000_000_000,
+            0,
+        )
+        .unwrap_err();
+        assert_eq!(err.1, ErrorCode::GeneratorRefHasNoGenerator);
+    }
+
+    #[test]
+    fn test_resolve_after_parse() {
+        // the refs aren't resolved if the generator can't be deserialized
+        let mut requested = 0;
+        let mut resolver = |_height: u32| -> Result<Option<Vec<u8>>, ValidationErr> {
+            requested += 1;
+            Ok(Some(vec![0x80]))
+        };
+        let mut a = Allocator::new();
+        assert!(run_block_generator_with_resolver::<_, MempoolVisitor>(
+            &mut a,
+            &[0xff, 0x01],
+            &[1, 2],
+            &mut resolver,
+            11_000_000_000,
+            0,
+        )
+        .is_err());
+        assert!(run_block_generator2_with_resolver::<_, MempoolVisitor>(
+            &mut a,
+            &[0xff, 0x01],
+            &[1, 2],
+            &mut resolver,
+            11_000_000_000,
+            0,
+        )
+        .is_err());
+        assert_eq!(requested, 0);
+    }
+}
+
+#[cfg(all(test, feature = "test_blocks"))]
+mod block_tests {
+    use super::*;
+    use crate::consensus_constants::TEST_CONSTANTS;
+    use crate::test_blocks::BlockTools;
+    use chia_protocol::Program;
+
+    #[test]
+    fn test_block_generator_refs() {
+        let bt = BlockTools::new(&TEST_CONSTANTS, &[1; 32]);
+        let blocks = bt.get_consecutive_blocks(3, None);
+        let mut block = blocks.last().expect("block").clone();
+        assert_eq!(block_generator_refs(&block.to_bytes().unwrap()), Ok(vec![]));
+
+        block.transactions_generator = Some(Program::from(vec![0xff, 0x01, 0xff, 0x80, 0x80]));
+        block.transactions_generator_ref_list = vec![2, 0, 2];
+        let buf = block.to_bytes().unwrap();
+        assert_eq!(block_generator_refs(&buf), Ok(vec![2, 0, 2]));
+
+        assert_eq!(
+            block_generator_refs(&buf[..buf.len() - 1]),
+            Err(Error::EndOfBuffer)
+        );
+        let mut trailing = buf.clone();
+        trailing.push(0);
+        assert_eq!(block_generator_refs(&trailing), Err(Error::InputTooLarge));
+    }
+}
@@ -2,6 +2,7 @@ use crate::gen::condition_sanitizers::sanitize_hash;
 use crate::gen::sanitize_int::{sanitize_uint, SanitizedUint};
 use crate::gen::validation_error::{first, rest, ErrorCode, ValidationErr};
 use chia_protocol::Bytes32;
+use clvmr::reduction::EvalErr;
 use clvmr::{Allocator, NodePtr};
 use std::sync::Arc;
 
@@ -109,6 +110,47 @@ impl SpendId {
         }
     }
 
+    // the mode bits (for one side of the message) that this SpendId was parsed
+    // from
+    pub fn mode(&self) -> u8 {
+        match self {
+            Self::OwnedCoinId(_) | Self::CoinId(_) => COINID,
+            Self::Parent(_) => PARENT,
+            Self::Puzzle(_) => PUZZLE,
+            Self::Amount(_) => AMOUNT,
+            Self::PuzzleAmount(_, _) => PUZZLEAMOUNT,
+            Self::ParentAmount(_, _) => PARENTAMOUNT,
+            Self::ParentPuzzle(_, _) => PARENTPUZZLE,
+            Self::None => 0,
+        }
+    }
+
+    // the inverse of parse(). Appends the condition arguments that identify
+    // the spend to args
+    pub fn to_args(&self, a: &mut Allocator, args: &mut Vec<NodePtr>) -> Result<(), EvalErr> {
+        match self {
+            Self::OwnedCoinId(coinid) => args.push(a.new_atom(coinid.as_slice())?),
+            Self::CoinId(coinid) => args.push(*coinid),
+            Self::Parent(parent) => args.push(*parent),
+            Self::Puzzle(puzzle) => args.push(*puzzle),
+            Self::Amount(amount) => args.push(a.new_number((*amount).into())?),
+            Self::PuzzleAmount(puzzle, amount) => {
+                args.push(*puzzle);
+                args.push(a.new_number((*amount).into())?);
+            }
+            Self::ParentAmount(parent, amount) => {
+                args.push(*parent);
+                args.push(a.new_number((*amount).into())?);
+            }
+            Self::ParentPuzzle(parent, puzzle) => {
+                args.push(*parent);
+                args.push(*puzzle);
+            }
+            Self::None => {}
+        }
+        Ok(())
+    }
+
     pub fn make_key(&self, out: &mut Vec<u8>, a: &Allocator) {
         match self {
             Self::OwnedCoinId(coinid) => {
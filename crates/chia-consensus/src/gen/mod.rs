@@ -1,17 +1,28 @@
+pub mod block_cost;
 mod coin_id;
+pub mod compress_generator;
 mod condition_sanitizers;
 pub mod conditions;
+pub mod extension_dialect;
 pub mod flags;
+pub mod generator_refs;
 pub mod get_puzzle_and_solution;
 pub mod messages;
 pub mod opcodes;
 pub mod owned_conditions;
+pub mod profiling_dialect;
 pub mod run_block_generator;
 pub mod run_puzzle;
 pub mod sanitize_int;
+pub mod serialize_conditions;
+pub mod signing;
 pub mod solution_generator;
+pub mod spend_context;
+pub mod spend_graph;
 pub mod spend_visitor;
+pub mod timings;
 pub mod validation_error;
+pub mod validation_policy;
 
 // these tests are large and expensive. They take a long time to run in
 // unoptimized builds. Only run these with --release
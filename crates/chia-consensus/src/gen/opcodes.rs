@@ -108,6 +108,34 @@ pub fn compute_unknown_condition_cost(op: ConditionOpcode) -> Cost {
     }
 }
 
+// the fixed cost a condition adds to the cost of a spend bundle, on top of the
+// cost of running the puzzle. The cost of SOFTFORK conditions is specified by
+// their first argument, so it's not included here. Unknown 2-byte conditions
+// are only charged their cost when ENABLE_SOFTFORK_CONDITION is set.
+pub fn condition_cost(op: ConditionOpcode) -> Cost {
+    match op {
+        CREATE_COIN => CREATE_COIN_COST,
+        AGG_SIG_UNSAFE
+        | AGG_SIG_ME
+        | AGG_SIG_PARENT
+        | AGG_SIG_PUZZLE
+        | AGG_SIG_AMOUNT
+        | AGG_SIG_PUZZLE_AMOUNT
+        | AGG_SIG_PARENT_AMOUNT
+        | AGG_SIG_PARENT_PUZZLE => AGG_SIG_COST,
+        _ => compute_unknown_condition_cost(op),
+    }
+}
+
+// the single byte conditions that have a cost, along with their cost. This
+// includes the AGG_SIG_* conditions introduced by the hard fork
+pub fn condition_cost_table() -> Vec<(ConditionOpcode, Cost)> {
+    (0..256)
+        .map(|op| (op, condition_cost(op)))
+        .filter(|(_, cost)| *cost > 0)
+        .collect()
+}
+
 pub fn parse_opcode(a: &Allocator, op: NodePtr, flags: u32) -> Option<ConditionOpcode> {
     let buf = match a.sexp(op) {
         SExp::Atom => a.atom(op),
@@ -301,3 +329,33 @@ fn test_parse_invalid_opcode() {
     let p = a.new_pair(v1, v2).unwrap();
     assert_eq!(parse_opcode(&a, p, 0), None);
 }
+
+#[cfg(test)]
+#[rstest]
+#[case(CREATE_COIN, CREATE_COIN_COST)]
+#[case(AGG_SIG_UNSAFE, AGG_SIG_COST)]
+#[case(AGG_SIG_ME, AGG_SIG_COST)]
+#[case(AGG_SIG_PARENT, AGG_SIG_COST)]
+#[case(AGG_SIG_PUZZLE, AGG_SIG_COST)]
+#[case(AGG_SIG_AMOUNT, AGG_SIG_COST)]
+#[case(AGG_SIG_PUZZLE_AMOUNT, AGG_SIG_COST)]
+#[case(AGG_SIG_PARENT_AMOUNT, AGG_SIG_COST)]
+#[case(AGG_SIG_PARENT_PUZZLE, AGG_SIG_COST)]
+#[case(RESERVE_FEE, 0)]
+#[case(REMARK, 0)]
+#[case(SOFTFORK, 0)]
+#[case(0x100, 100)]
+#[case(0xffff, compute_unknown_condition_cost(0xffff))]
+fn test_condition_cost(#[case] op: ConditionOpcode, #[case] expected: Cost) {
+    assert_eq!(condition_cost(op), expected);
+}
+
+#[cfg(test)]
+#[test]
+fn test_condition_cost_table() {
+    let table = condition_cost_table();
+    assert_eq!(table.len(), 9);
+    assert_eq!(table[0], (AGG_SIG_PARENT, AGG_SIG_COST));
+    assert!(table.contains(&(CREATE_COIN, CREATE_COIN_COST)));
+    assert!(table.contains(&(AGG_SIG_ME, AGG_SIG_COST)));
+}
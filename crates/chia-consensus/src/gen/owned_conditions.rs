@@ -5,16 +5,23 @@ use chia_streamable_macro::Streamable;
 use clvmr::{Allocator, NodePtr};
 
 use super::conditions::{Spend, SpendBundleConditions};
+#[cfg(feature = "py-bindings")]
+use super::spend_graph::to_dot;
 
 #[cfg(feature = "py-bindings")]
 use chia_py_streamable_macro::{PyJsonDict, PyStreamable};
+#[cfg(feature = "py-bindings")]
+use pyo3::prelude::*;
+#[cfg(feature = "py-bindings")]
+use pyo3::types::{PyBytes, PyDict};
 
 #[derive(Streamable, Hash, Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(
     feature = "py-bindings",
-    pyo3::pyclass(name = "Spend", get_all, frozen),
+    pyo3::pyclass(name = "Spend", module = "gold_rs", get_all, frozen),
     derive(PyJsonDict, PyStreamable)
 )]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct OwnedSpend {
     pub coin_id: Bytes32,
     pub parent_id: Bytes32,
@@ -40,9 +47,10 @@ pub struct OwnedSpend {
 #[derive(Streamable, Hash, Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(
     feature = "py-bindings",
-    pyo3::pyclass(name = "SpendBundleConditions", get_all, frozen),
+    pyo3::pyclass(name = "SpendBundleConditions", module = "gold_rs", get_all, frozen),
     derive(PyJsonDict, PyStreamable)
 )]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct OwnedSpendBundleConditions {
     pub spends: Vec<OwnedSpend>,
     pub reserve_fee: u64,
@@ -103,7 +111,75 @@ impl OwnedSpend {
     }
 }
 
+#[cfg(feature = "py-bindings")]
+#[pyo3::pymethods]
+impl OwnedSpendBundleConditions {
+    // the spends and the coins they create as a graph in the DOT language,
+    // without announcements. See spend_bundle_to_dot() for those
+    #[pyo3(name = "to_dot")]
+    fn py_to_dot(&self) -> String {
+        to_dot(self, &[])
+    }
+
+    // the spends as a dict of one bytes buffer per field, keyed by the name
+    // of the Spend field. The hashes are 32 bytes each, coin_amount is
+    // little-endian uint64 and flags little-endian uint32, so they can be
+    // loaded with numpy.frombuffer() (with dtype "S32", "<u8" and "<u4")
+    // without creating a python object per spend
+    #[pyo3(name = "to_arrays")]
+    fn py_to_arrays<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let arrays = self.to_arrays();
+        let ret = PyDict::new_bound(py);
+        for (key, hashes) in [
+            ("coin_id", &arrays.coin_ids),
+            ("parent_id", &arrays.parent_ids),
+            ("puzzle_hash", &arrays.puzzle_hashes),
+        ] {
+            let buf: Vec<u8> = hashes.iter().flat_map(|h| h.to_bytes()).collect();
+            ret.set_item(key, PyBytes::new_bound(py, &buf))?;
+        }
+        let buf: Vec<u8> = arrays
+            .coin_amounts
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        ret.set_item("coin_amount", PyBytes::new_bound(py, &buf))?;
+        let buf: Vec<u8> = arrays.flags.iter().flat_map(|v| v.to_le_bytes()).collect();
+        ret.set_item("flags", PyBytes::new_bound(py, &buf))?;
+        Ok(ret)
+    }
+}
+
+// The spends in columns rather than rows, one entry per spend, in the same
+// order as OwnedSpendBundleConditions::spends
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpendArrays {
+    pub coin_ids: Vec<Bytes32>,
+    pub parent_ids: Vec<Bytes32>,
+    pub puzzle_hashes: Vec<Bytes32>,
+    pub coin_amounts: Vec<u64>,
+    pub flags: Vec<u32>,
+}
+
 impl OwnedSpendBundleConditions {
+    pub fn to_arrays(&self) -> SpendArrays {
+        let mut ret = SpendArrays {
+            coin_ids: Vec::with_capacity(self.spends.len()),
+            parent_ids: Vec::with_capacity(self.spends.len()),
+            puzzle_hashes: Vec::with_capacity(self.spends.len()),
+            coin_amounts: Vec::with_capacity(self.spends.len()),
+            flags: Vec::with_capacity(self.spends.len()),
+        };
+        for s in &self.spends {
+            ret.coin_ids.push(s.coin_id);
+            ret.parent_ids.push(s.parent_id);
+            ret.puzzle_hashes.push(s.puzzle_hash);
+            ret.coin_amounts.push(s.coin_amount);
+            ret.flags.push(s.flags);
+        }
+        ret
+    }
+
     pub fn from(a: &Allocator, sb: SpendBundleConditions) -> Result<Self> {
         let mut spends = Vec::<OwnedSpend>::new();
         for s in sb.spends {
@@ -0,0 +1,122 @@
+use clvmr::allocator::{Allocator, NodePtr, SExp};
+use clvmr::cost::Cost;
+use clvmr::dialect::{Dialect, OperatorSet};
+use clvmr::reduction::{Reduction, Response};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OperatorStats {
+    pub calls: u64,
+    pub cost: Cost,
+}
+
+// operator (opcode) -> the number of times it was called and its total cost
+pub type CostProfile = HashMap<Vec<u8>, OperatorStats>;
+
+// Wraps a dialect and records the calls and cost of every operator executed
+// by it. The costs are the exact same ones charged by the wrapped dialect. The
+// cost of a program also includes the cost of the interpreter itself (like
+// applying and quoting), which isn't attributed to any operator, so the sum of
+// the operator costs is always lower than the cost of the program.
+pub struct ProfilingDialect<D> {
+    inner: D,
+    profile: RefCell<CostProfile>,
+}
+
+impl<D: Dialect> ProfilingDialect<D> {
+    pub fn new(inner: D) -> Self {
+        Self {
+            inner,
+            profile: RefCell::new(CostProfile::new()),
+        }
+    }
+
+    pub fn into_profile(self) -> CostProfile {
+        self.profile.into_inner()
+    }
+}
+
+impl<D: Dialect> Dialect for ProfilingDialect<D> {
+    fn quote_kw(&self) -> u32 {
+        self.inner.quote_kw()
+    }
+
+    fn apply_kw(&self) -> u32 {
+        self.inner.apply_kw()
+    }
+
+    fn softfork_kw(&self) -> u32 {
+        self.inner.softfork_kw()
+    }
+
+    fn softfork_extension(&self, ext: u32) -> OperatorSet {
+        self.inner.softfork_extension(ext)
+    }
+
+    fn op(
+        &self,
+        allocator: &mut Allocator,
+        o: NodePtr,
+        argument_list: NodePtr,
+        max_cost: Cost,
+        extensions: OperatorSet,
+    ) -> Response {
+        let ret = self
+            .inner
+            .op(allocator, o, argument_list, max_cost, extensions);
+        if let SExp::Atom = allocator.sexp(o) {
+            let mut profile = self.profile.borrow_mut();
+            let stats = profile
+                .entry(allocator.atom(o).as_ref().to_vec())
+                .or_default();
+            stats.calls += 1;
+            if let Ok(Reduction(cost, _)) = &ret {
+                stats.cost += cost;
+            }
+        }
+        ret
+    }
+
+    fn allow_unknown_ops(&self) -> bool {
+        self.inner.allow_unknown_ops()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clvmr::chia_dialect::ChiaDialect;
+    use clvmr::run_program::run_program;
+    use clvmr::serde::node_from_bytes;
+
+    #[test]
+    fn test_profile() {
+        let mut a = Allocator::new();
+        // (+ (q . 5) (* (q . 7) (q . 7)) (q . 1))
+        let program = node_from_bytes(
+            &mut a,
+            &hex::decode("ff10ffff0105ffff12ffff0107ffff010780ffff010180").unwrap(),
+        )
+        .unwrap();
+        let nil = a.nil();
+
+        let Reduction(expected_cost, _) =
+            run_program(&mut a, &ChiaDialect::new(0), program, nil, 11_000_000_000).unwrap();
+
+        let dialect = ProfilingDialect::new(ChiaDialect::new(0));
+        let Reduction(cost, result) =
+            run_program(&mut a, &dialect, program, nil, 11_000_000_000).unwrap();
+        assert_eq!(a.number(result), 55.into());
+        assert_eq!(cost, expected_cost);
+
+        let profile = dialect.into_profile();
+        assert_eq!(profile.len(), 2);
+        let add = profile[&vec![16_u8]];
+        let mul = profile[&vec![18_u8]];
+        assert_eq!(add.calls, 1);
+        assert_eq!(mul.calls, 1);
+        assert!(add.cost > 0 && mul.cost > 0);
+        assert!(add.cost + mul.cost < cost);
+    }
+}
@@ -2,13 +2,16 @@ use crate::gen::conditions::{
     parse_spends, process_single_spend, validate_conditions, ParseState, SpendBundleConditions,
 };
 use crate::gen::flags::ALLOW_BACKREFS;
+use crate::gen::profiling_dialect::{CostProfile, ProfilingDialect};
 use crate::gen::spend_visitor::SpendVisitor;
-use crate::gen::validation_error::{first, ErrorCode, ValidationErr};
+use crate::gen::timings::{timed, ValidationTimings};
+use crate::gen::validation_error::{eval_err, first, ErrorCode, ValidationErr};
 use crate::generator_rom::{CLVM_DESERIALIZER, COST_PER_BYTE, GENERATOR_ROM};
 use clvm_utils::{tree_hash_cached, TreeHash};
 use clvmr::allocator::{Allocator, NodePtr};
 use clvmr::chia_dialect::ChiaDialect;
 use clvmr::cost::Cost;
+use clvmr::dialect::Dialect;
 use clvmr::reduction::Reduction;
 use clvmr::run_program::run_program;
 use clvmr::serde::{node_from_bytes, node_from_bytes_backrefs, node_from_bytes_backrefs_record};
@@ -44,39 +47,153 @@ pub fn run_block_generator<GenBuf: AsRef<[u8]>, V: SpendVisitor>(
     max_cost: u64,
     flags: u32,
 ) -> Result<SpendBundleConditions, ValidationErr> {
-    let mut cost_left = max_cost;
-    let byte_cost = program.len() as u64 * COST_PER_BYTE;
-
-    subtract_cost(a, &mut cost_left, byte_cost)?;
+    run_block_generator_with_refs::<V>(
+        a,
+        program,
+        |a: &mut Allocator| new_ref_list(a, block_refs),
+        max_cost,
+        flags,
+    )
+}
 
-    let generator_rom = node_from_bytes(a, &GENERATOR_ROM)?;
-    let program = if (flags & ALLOW_BACKREFS) != 0 {
-        node_from_bytes_backrefs(a, program)?
-    } else {
-        node_from_bytes(a, program)?
-    };
+// Same as run_block_generator(), but the list of block references is built by
+// the make_refs function. It's called once the generator has been
+// deserialized, right before it's run, which allows the references to be
+// resolved lazily
+pub(crate) fn run_block_generator_with_refs<V: SpendVisitor>(
+    a: &mut Allocator,
+    program: &[u8],
+    make_refs: impl FnOnce(&mut Allocator) -> Result<NodePtr, ValidationErr>,
+    max_cost: u64,
+    flags: u32,
+) -> Result<SpendBundleConditions, ValidationErr> {
+    let dialect = ChiaDialect::new(flags);
+    run_block_generator_with_dialect::<V, _>(
+        a,
+        &dialect,
+        program,
+        make_refs,
+        max_cost,
+        flags,
+        false,
+        &mut ValidationTimings::default(),
+    )
+}
 
+// builds the CLVM list of the generators referenced by a block, as passed to
+// the generator program
+fn new_ref_list<GenBuf: AsRef<[u8]>>(
+    a: &mut Allocator,
+    block_refs: &[GenBuf],
+) -> Result<NodePtr, ValidationErr> {
     // iterate in reverse order since we're building a linked list from
     // the tail
-    let mut args = a.nil();
+    let mut list = a.nil();
     for g in block_refs.iter().rev() {
         let ref_gen = a.new_atom(g.as_ref())?;
-        args = a.new_pair(ref_gen, args)?;
+        list = a.new_pair(ref_gen, list)?;
     }
+    Ok(list)
+}
+
+// like run_block_generator(), but also measures the time spent deserializing
+// the generator, running it and parsing the conditions. The timings are
+// returned even if the generator fails. If report_oom is set, running out of
+// heap fails with OutOfMemory rather than GeneratorRuntimeError. This is for
+// callers that lower the heap limit with make_allocator_with_limit(). It
+// doesn't affect whether a block is valid, only the error code it fails with
+pub fn run_block_generator_with_timings<GenBuf: AsRef<[u8]>, V: SpendVisitor>(
+    a: &mut Allocator,
+    program: &[u8],
+    block_refs: &[GenBuf],
+    max_cost: u64,
+    flags: u32,
+    report_oom: bool,
+) -> (
+    Result<SpendBundleConditions, ValidationErr>,
+    ValidationTimings,
+) {
+    let dialect = ChiaDialect::new(flags);
+    let mut timings = ValidationTimings::default();
+    let ret = run_block_generator_with_dialect::<V, _>(
+        a,
+        &dialect,
+        program,
+        |a: &mut Allocator| new_ref_list(a, block_refs),
+        max_cost,
+        flags,
+        report_oom,
+        &mut timings,
+    );
+    (ret, timings)
+}
+
+// like run_block_generator(), but also records the number of calls and the
+// cost of every CLVM operator executed by the generator and the puzzles. The
+// profile is returned even if the generator fails
+pub fn profile_block_generator<GenBuf: AsRef<[u8]>, V: SpendVisitor>(
+    a: &mut Allocator,
+    program: &[u8],
+    block_refs: &[GenBuf],
+    max_cost: u64,
+    flags: u32,
+) -> (Result<SpendBundleConditions, ValidationErr>, CostProfile) {
+    let dialect = ProfilingDialect::new(ChiaDialect::new(flags));
+    let ret = run_block_generator_with_dialect::<V, _>(
+        a,
+        &dialect,
+        program,
+        |a: &mut Allocator| new_ref_list(a, block_refs),
+        max_cost,
+        flags,
+        false,
+        &mut ValidationTimings::default(),
+    );
+    (ret, dialect.into_profile())
+}
 
+#[allow(clippy::too_many_arguments)]
+fn run_block_generator_with_dialect<V: SpendVisitor, D: Dialect>(
+    a: &mut Allocator,
+    dialect: &D,
+    program: &[u8],
+    make_refs: impl FnOnce(&mut Allocator) -> Result<NodePtr, ValidationErr>,
+    max_cost: u64,
+    flags: u32,
+    report_oom: bool,
+    timings: &mut ValidationTimings,
+) -> Result<SpendBundleConditions, ValidationErr> {
+    let mut cost_left = max_cost;
+    let byte_cost = program.len() as u64 * COST_PER_BYTE;
+
+    subtract_cost(a, &mut cost_left, byte_cost)?;
+
+    let (generator_rom, program) = timed(&mut timings.deserialize, || {
+        let generator_rom = node_from_bytes(a, &GENERATOR_ROM)?;
+        let program = if (flags & ALLOW_BACKREFS) != 0 {
+            node_from_bytes_backrefs(a, program)?
+        } else {
+            node_from_bytes(a, program)?
+        };
+        Ok::<_, ValidationErr>((generator_rom, program))
+    })?;
+
+    let mut args = make_refs(a)?;
     args = a.new_pair(args, a.nil())?;
     let args = a.new_pair(args, a.nil())?;
     let args = a.new_pair(program, args)?;
 
-    let dialect = ChiaDialect::new(flags);
-    let Reduction(clvm_cost, generator_output) =
-        run_program(a, &dialect, generator_rom, args, cost_left)?;
+    let Reduction(clvm_cost, generator_output) = timed(&mut timings.clvm, || {
+        run_program(a, dialect, generator_rom, args, cost_left).map_err(|e| eval_err(e, report_oom))
+    })?;
 
     subtract_cost(a, &mut cost_left, clvm_cost)?;
 
     // we pass in what's left of max_cost here, to fail early in case the
     // cost of a condition brings us over the cost limit
-    let mut result = parse_spends::<V>(a, generator_output, cost_left, flags)?;
+    let mut result = timed(&mut timings.parse_conditions, || {
+        parse_spends::<V>(a, generator_output, cost_left, flags)
+    })?;
     result.cost += max_cost - cost_left;
     Ok(result)
 }
@@ -116,6 +233,40 @@ pub fn run_block_generator2<GenBuf: AsRef<[u8]>, V: SpendVisitor>(
     block_refs: &[GenBuf],
     max_cost: u64,
     flags: u32,
+) -> Result<SpendBundleConditions, ValidationErr> {
+    run_block_generator2_with_limits::<_, V>(a, program, block_refs, max_cost, flags, false)
+}
+
+// like run_block_generator2(), but if report_oom is set, running out of heap
+// fails with OutOfMemory rather than GeneratorRuntimeError. See
+// run_block_generator_with_timings()
+pub fn run_block_generator2_with_limits<GenBuf: AsRef<[u8]>, V: SpendVisitor>(
+    a: &mut Allocator,
+    program: &[u8],
+    block_refs: &[GenBuf],
+    max_cost: u64,
+    flags: u32,
+    report_oom: bool,
+) -> Result<SpendBundleConditions, ValidationErr> {
+    run_block_generator2_with_refs::<V>(
+        a,
+        program,
+        |a: &mut Allocator| new_ref_list(a, block_refs),
+        max_cost,
+        flags,
+        report_oom,
+    )
+}
+
+// Same as run_block_generator2(), but the list of block references is built by
+// the make_refs function, right before the generator is run
+pub(crate) fn run_block_generator2_with_refs<V: SpendVisitor>(
+    a: &mut Allocator,
+    program: &[u8],
+    make_refs: impl FnOnce(&mut Allocator) -> Result<NodePtr, ValidationErr>,
+    max_cost: u64,
+    flags: u32,
+    report_oom: bool,
 ) -> Result<SpendBundleConditions, ValidationErr> {
     let byte_cost = program.len() as u64 * COST_PER_BYTE;
 
@@ -129,13 +280,7 @@ pub fn run_block_generator2<GenBuf: AsRef<[u8]>, V: SpendVisitor>(
         (node_from_bytes(a, program)?, HashSet::<NodePtr>::new())
     };
 
-    // iterate in reverse order since we're building a linked list from
-    // the tail
-    let mut blocks = a.nil();
-    for g in block_refs.iter().rev() {
-        let ref_gen = a.new_atom(g.as_ref())?;
-        blocks = a.new_pair(ref_gen, blocks)?;
-    }
+    let blocks = make_refs(a)?;
 
     // the first argument to the generator is the serializer, followed by a list
     // of the blocks it requested.
@@ -144,7 +289,8 @@ pub fn run_block_generator2<GenBuf: AsRef<[u8]>, V: SpendVisitor>(
 
     let dialect = ChiaDialect::new(flags);
 
-    let Reduction(clvm_cost, mut all_spends) = run_program(a, &dialect, program, args, cost_left)?;
+    let Reduction(clvm_cost, mut all_spends) =
+        run_program(a, &dialect, program, args, cost_left).map_err(|e| eval_err(e, report_oom))?;
 
     subtract_cost(a, &mut cost_left, clvm_cost)?;
     all_spends = first(a, all_spends)?;
@@ -164,7 +310,8 @@ pub fn run_block_generator2<GenBuf: AsRef<[u8]>, V: SpendVisitor>(
             extract_n::<5>(a, spend, ErrorCode::InvalidCondition)?;
 
         let Reduction(clvm_cost, conditions) =
-            run_program(a, &dialect, puzzle, solution, cost_left)?;
+            run_program(a, &dialect, puzzle, solution, cost_left)
+                .map_err(|e| eval_err(e, report_oom))?;
 
         subtract_cost(a, &mut cost_left, clvm_cost)?;
 
@@ -1,11 +1,20 @@
-use crate::gen::conditions::{parse_conditions, ParseState, Spend, SpendBundleConditions};
+use crate::consensus_constants::ConsensusConstants;
+use crate::fork_schedule::ForkSchedule;
+use crate::gen::conditions::{
+    parse_conditions, FastForwardIneligible, MempoolVisitor, ParseState, Spend,
+    SpendBundleConditions, ELIGIBLE_FOR_DEDUP, ELIGIBLE_FOR_FF,
+};
 use crate::gen::flags::ALLOW_BACKREFS;
 use crate::gen::spend_visitor::SpendVisitor;
-use crate::gen::validation_error::ValidationErr;
+use crate::gen::timings::{timed, ValidationTimings};
+use crate::gen::validation_error::ErrorCode;
+use crate::gen::validation_error::{eval_err, ValidationErr};
+use crate::gen::validation_policy::ValidationPolicy;
 use chia_protocol::Bytes32;
 use chia_protocol::Coin;
+use chia_protocol::{CoinSpend, SpendBundle};
 use clvm_utils::tree_hash;
-use clvmr::allocator::Allocator;
+use clvmr::allocator::{Allocator, NodePtr};
 use clvmr::chia_dialect::ChiaDialect;
 use clvmr::reduction::Reduction;
 use clvmr::run_program::run_program;
@@ -20,23 +29,122 @@ pub fn run_puzzle<V: SpendVisitor>(
     amount: u64,
     max_cost: u64,
     flags: u32,
+) -> Result<SpendBundleConditions, ValidationErr> {
+    run_puzzle_with_limits::<V>(
+        a, puzzle, solution, parent_id, amount, max_cost, flags, false,
+    )
+}
+
+// like run_puzzle(), but if report_oom is set, running out of heap fails with
+// OutOfMemory rather than GeneratorRuntimeError. This is for callers that
+// lower the heap limit with make_allocator_with_limit(). It doesn't affect
+// whether the spend is valid, only the error code it fails with
+#[allow(clippy::too_many_arguments)]
+pub fn run_puzzle_with_limits<V: SpendVisitor>(
+    a: &mut Allocator,
+    puzzle: &[u8],
+    solution: &[u8],
+    parent_id: &[u8],
+    amount: u64,
+    max_cost: u64,
+    flags: u32,
+    report_oom: bool,
+) -> Result<SpendBundleConditions, ValidationErr> {
+    run_puzzle_with_state::<V>(
+        a,
+        &mut ParseState::default(),
+        puzzle,
+        solution,
+        parent_id,
+        amount,
+        max_cost,
+        flags,
+        report_oom,
+        &mut ValidationTimings::default(),
+    )
+}
+
+// The mempool's analysis of a single spend: whether it's eligible for
+// deduplication and fast-forward. flags has ELIGIBLE_FOR_DEDUP and
+// ELIGIBLE_FOR_FF set for the ones it's eligible for, and ff_ineligible is the
+// bit field of FastForwardIneligible reasons it's not eligible for
+// fast-forward.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpendAnalysis {
+    pub flags: u32,
+    pub ff_ineligible: u32,
+}
+
+impl SpendAnalysis {
+    pub fn eligible_for_dedup(&self) -> bool {
+        (self.flags & ELIGIBLE_FOR_DEDUP) != 0
+    }
+
+    pub fn eligible_for_ff(&self) -> bool {
+        (self.flags & ELIGIBLE_FOR_FF) != 0
+    }
+
+    pub fn ff_ineligible_reasons(&self) -> Vec<FastForwardIneligible> {
+        FastForwardIneligible::from_bits(self.ff_ineligible)
+    }
+}
+
+// Runs the puzzle and analyzes the spend the same way the mempool does
+// (with the ANALYZE_SPENDS flag), but without requiring mempool mode or a
+// whole block generator. This lets a wallet tell whether its own spends will
+// be eligible for deduplication and fast-forward before submitting them. The
+// puzzle still has to run successfully.
+pub fn analyze_spend(
+    a: &mut Allocator,
+    puzzle: &[u8],
+    solution: &[u8],
+    parent_id: &[u8],
+    amount: u64,
+    max_cost: u64,
+    flags: u32,
+) -> Result<SpendAnalysis, ValidationErr> {
+    let conds =
+        run_puzzle::<MempoolVisitor>(a, puzzle, solution, parent_id, amount, max_cost, flags)?;
+    let spend = &conds.spends[0];
+    Ok(SpendAnalysis {
+        flags: spend.flags & (ELIGIBLE_FOR_DEDUP | ELIGIBLE_FOR_FF),
+        ff_ineligible: spend.ff_ineligible,
+    })
+}
+
+// the ParseState may be shared between spends, to apply the limits of its
+// ValidationPolicy across all of them
+#[allow(clippy::too_many_arguments)]
+fn run_puzzle_with_state<V: SpendVisitor>(
+    a: &mut Allocator,
+    state: &mut ParseState,
+    puzzle: &[u8],
+    solution: &[u8],
+    parent_id: &[u8],
+    amount: u64,
+    max_cost: u64,
+    flags: u32,
+    report_oom: bool,
+    timings: &mut ValidationTimings,
 ) -> Result<SpendBundleConditions, ValidationErr> {
     let deserialize = if (flags & ALLOW_BACKREFS) != 0 {
         node_from_bytes_backrefs
     } else {
         node_from_bytes
     };
-    let puzzle = deserialize(a, puzzle)?;
-    let solution = deserialize(a, solution)?;
+    let (puzzle, solution) = timed(&mut timings.deserialize, || {
+        Ok::<_, ValidationErr>((deserialize(a, puzzle)?, deserialize(a, solution)?))
+    })?;
 
     let dialect = ChiaDialect::new(flags);
-    let Reduction(clvm_cost, conditions) = run_program(a, &dialect, puzzle, solution, max_cost)?;
+    let Reduction(clvm_cost, conditions) = timed(&mut timings.clvm, || {
+        run_program(a, &dialect, puzzle, solution, max_cost).map_err(|e| eval_err(e, report_oom))
+    })?;
 
     let mut ret = SpendBundleConditions {
         removal_amount: amount as u128,
         ..Default::default()
     };
-    let mut state = ParseState::default();
 
     let puzzle_hash = tree_hash(a, puzzle);
     let coin_id = Arc::<Bytes32>::new(
@@ -59,16 +167,363 @@ pub fn run_puzzle<V: SpendVisitor>(
 
     let mut cost_left = max_cost - clvm_cost;
 
-    parse_conditions(
+    timed(&mut timings.parse_conditions, || {
+        parse_conditions(
+            a,
+            &mut ret,
+            state,
+            spend,
+            conditions,
+            flags,
+            &mut cost_left,
+            &mut visitor,
+        )
+    })?;
+    ret.cost = max_cost - cost_left;
+    Ok(ret)
+}
+
+// like run_puzzle(), but with the consensus flags in effect at the specified
+// block height, according to the fork activation heights in constants. The
+// cost limit is the max block cost
+#[allow(clippy::too_many_arguments)]
+pub fn run_puzzle_at_height<V: SpendVisitor>(
+    a: &mut Allocator,
+    puzzle: &[u8],
+    solution: &[u8],
+    parent_id: &[u8],
+    amount: u64,
+    height: u32,
+    constants: &ConsensusConstants,
+) -> Result<SpendBundleConditions, ValidationErr> {
+    let flags = ForkSchedule::new(constants).flags_for_height(height);
+    run_puzzle::<V>(
         a,
-        &mut ret,
-        &mut state,
-        spend,
-        conditions,
+        puzzle,
+        solution,
+        parent_id,
+        amount,
+        constants.max_block_cost_clvm,
         flags,
-        &mut cost_left,
-        &mut visitor,
-    )?;
-    ret.cost = max_cost - cost_left;
+    )
+}
+
+// runs the puzzle and solution of a CoinSpend against its coin
+pub fn run_spend<V: SpendVisitor>(
+    a: &mut Allocator,
+    spend: &CoinSpend,
+    max_cost: u64,
+    flags: u32,
+) -> Result<SpendBundleConditions, ValidationErr> {
+    run_puzzle::<V>(
+        a,
+        spend.puzzle_reveal.as_slice(),
+        spend.solution.as_slice(),
+        &spend.coin.parent_coin_info,
+        spend.coin.amount,
+        max_cost,
+        flags,
+    )
+}
+
+// runs every CoinSpend in the bundle, in order, and returns the conditions of
+// each one. The spends share the max_cost budget. Note that this does not
+// validate the spends against each other (e.g. announcements or double spends)
+pub fn run_spend_bundle<V: SpendVisitor>(
+    a: &mut Allocator,
+    bundle: &SpendBundle,
+    max_cost: u64,
+    flags: u32,
+) -> Result<Vec<SpendBundleConditions>, ValidationErr> {
+    run_spend_bundle_with_policy::<V>(a, bundle, max_cost, flags, &ValidationPolicy::CONSENSUS)
+}
+
+// like run_spend_bundle(), but with custom limits on the number of
+// conditions. The limit on the total number of conditions applies to the
+// whole bundle
+pub fn run_spend_bundle_with_policy<V: SpendVisitor>(
+    a: &mut Allocator,
+    bundle: &SpendBundle,
+    max_cost: u64,
+    flags: u32,
+    policy: &ValidationPolicy,
+) -> Result<Vec<SpendBundleConditions>, ValidationErr> {
+    run_spends::<V>(
+        a,
+        bundle,
+        max_cost,
+        None,
+        flags,
+        policy,
+        &mut ValidationTimings::default(),
+    )
+}
+
+// like run_spend_bundle(), but also measures the time spent deserializing,
+// running the puzzles and parsing the conditions, across all spends. The
+// timings are returned even if a spend fails. If max_cost_per_spend is set, no
+// single spend may cost more than that, in addition to the limit on the total
+// cost
+pub fn run_spend_bundle_with_timings<V: SpendVisitor>(
+    a: &mut Allocator,
+    bundle: &SpendBundle,
+    max_cost: u64,
+    max_cost_per_spend: Option<u64>,
+    flags: u32,
+) -> (
+    Result<Vec<SpendBundleConditions>, ValidationErr>,
+    ValidationTimings,
+) {
+    let mut timings = ValidationTimings::default();
+    let ret = run_spends::<V>(
+        a,
+        bundle,
+        max_cost,
+        max_cost_per_spend,
+        flags,
+        &ValidationPolicy::CONSENSUS,
+        &mut timings,
+    );
+    (ret, timings)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_spends<V: SpendVisitor>(
+    a: &mut Allocator,
+    bundle: &SpendBundle,
+    max_cost: u64,
+    max_cost_per_spend: Option<u64>,
+    flags: u32,
+    policy: &ValidationPolicy,
+    timings: &mut ValidationTimings,
+) -> Result<Vec<SpendBundleConditions>, ValidationErr> {
+    let mut ret = Vec::<SpendBundleConditions>::with_capacity(bundle.coin_spends.len());
+    let mut state = ParseState::new(*policy);
+    let mut cost_left = max_cost;
+    for spend in &bundle.coin_spends {
+        // the puzzle is run with the smaller of the two limits as its budget,
+        // so an expensive spend is aborted as soon as it exceeds its own
+        // limit, rather than using up what's left for the rest of the bundle
+        let mut budget = max_cost_per_spend.map_or(cost_left, |limit| limit.min(cost_left));
+        let conds = run_spend_with_budget::<V>(a, &mut state, spend, &mut budget, flags, timings)?;
+        cost_left -= conds.cost;
+        ret.push(conds);
+    }
     Ok(ret)
 }
+
+// runs one spend of a bundle, sharing the ParseState and the remaining cost
+// (cost_left) with the other spends. The cost of the spend is deducted from
+// cost_left
+pub(crate) fn run_spend_with_budget<V: SpendVisitor>(
+    a: &mut Allocator,
+    state: &mut ParseState,
+    spend: &CoinSpend,
+    cost_left: &mut u64,
+    flags: u32,
+    timings: &mut ValidationTimings,
+) -> Result<SpendBundleConditions, ValidationErr> {
+    let conds = run_puzzle_with_state::<V>(
+        a,
+        state,
+        spend.puzzle_reveal.as_slice(),
+        spend.solution.as_slice(),
+        &spend.coin.parent_coin_info,
+        spend.coin.amount,
+        *cost_left,
+        flags,
+        false,
+        timings,
+    )?;
+    if conds.cost > *cost_left {
+        return Err(ValidationErr(NodePtr::NIL, ErrorCode::CostExceeded));
+    }
+    *cost_left -= conds.cost;
+    Ok(conds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus_constants::TEST_CONSTANTS;
+    use chia_bls::G2Element;
+    use chia_traits::Streamable;
+    use clvmr::serde::node_to_bytes;
+    use rstest::rstest;
+    use std::fs;
+
+    #[rstest]
+    #[case("e3c0")]
+    #[case("bb13")]
+    fn test_run_spend(#[case] spend_file: &str) {
+        let spend_bytes =
+            fs::read(format!("../../ff-tests/{spend_file}.spend")).expect("read file");
+        let spend = CoinSpend::from_bytes(&spend_bytes).expect("parse CoinSpend");
+
+        let mut a = Allocator::new();
+        let conds = run_spend::<MempoolVisitor>(&mut a, &spend, 11000000000, 0).expect("run_spend");
+
+        let expected = run_puzzle::<MempoolVisitor>(
+            &mut a,
+            spend.puzzle_reveal.as_slice(),
+            spend.solution.as_slice(),
+            &spend.coin.parent_coin_info,
+            spend.coin.amount,
+            11000000000,
+            0,
+        )
+        .expect("run_puzzle");
+
+        assert_eq!(conds.cost, expected.cost);
+        assert_eq!(conds.spends.len(), 1);
+        assert_eq!(*conds.spends[0].coin_id, spend.coin.coin_id());
+        assert!(conds.spends[0].create_coin == expected.spends[0].create_coin);
+    }
+
+    #[rstest]
+    #[case(0, None)]
+    #[case(5939999, None)]
+    // soft-fork 5 disallows the infinity public key
+    #[case(5940000, Some(ErrorCode::InvalidPublicKey))]
+    fn test_run_puzzle_at_height(#[case] height: u32, #[case] expected: Option<ErrorCode>) {
+        let mut a = Allocator::new();
+        // (q . ((AGG_SIG_ME <infinity> "msg")))
+        let mut infinity = [0_u8; 48];
+        infinity[0] = 0xc0;
+        let cond = a.nil();
+        let msg = a.new_atom(b"msg").unwrap();
+        let cond = a.new_pair(msg, cond).unwrap();
+        let pk = a.new_atom(&infinity).unwrap();
+        let cond = a.new_pair(pk, cond).unwrap();
+        let op = a.new_small_number(50).unwrap();
+        let cond = a.new_pair(op, cond).unwrap();
+        let conds = a.new_pair(cond, a.nil()).unwrap();
+        let puzzle = a.new_pair(a.one(), conds).unwrap();
+        let puzzle = node_to_bytes(&a, puzzle).unwrap();
+
+        let r = run_puzzle_at_height::<MempoolVisitor>(
+            &mut a,
+            &puzzle,
+            &[0x80],
+            &[0; 32],
+            1,
+            height,
+            &TEST_CONSTANTS,
+        );
+        match expected {
+            None => assert_eq!(r.unwrap().spends.len(), 1),
+            Some(err) => assert_eq!(r.unwrap_err().1, err),
+        }
+    }
+
+    #[test]
+    fn test_run_spend_bundle() {
+        let mut spends = Vec::<CoinSpend>::new();
+        for name in ["e3c0", "bb13"] {
+            let spend_bytes = fs::read(format!("../../ff-tests/{name}.spend")).expect("read file");
+            spends.push(CoinSpend::from_bytes(&spend_bytes).expect("parse CoinSpend"));
+        }
+        let bundle = SpendBundle::new(spends, G2Element::default());
+
+        let mut a = Allocator::new();
+        let conds = run_spend_bundle::<MempoolVisitor>(&mut a, &bundle, 11000000000, 0)
+            .expect("run_spend_bundle");
+        assert_eq!(conds.len(), 2);
+        for (c, spend) in conds.iter().zip(&bundle.coin_spends) {
+            assert_eq!(*c.spends[0].coin_id, spend.coin.coin_id());
+        }
+
+        // the spends share the cost budget
+        let total_cost = conds[0].cost + conds[1].cost;
+        assert!(run_spend_bundle::<MempoolVisitor>(&mut a, &bundle, total_cost, 0).is_ok());
+        assert_eq!(
+            run_spend_bundle::<MempoolVisitor>(&mut a, &bundle, total_cost - 1, 0)
+                .unwrap_err()
+                .1,
+            ErrorCode::CostExceeded
+        );
+    }
+
+    #[test]
+    fn test_max_cost_per_spend() {
+        let mut spends = Vec::<CoinSpend>::new();
+        for name in ["e3c0", "bb13"] {
+            let spend_bytes = fs::read(format!("../../ff-tests/{name}.spend")).expect("read file");
+            spends.push(CoinSpend::from_bytes(&spend_bytes).expect("parse CoinSpend"));
+        }
+        let bundle = SpendBundle::new(spends, G2Element::default());
+
+        let mut a = Allocator::new();
+        let (conds, _) =
+            run_spend_bundle_with_timings::<MempoolVisitor>(&mut a, &bundle, 11000000000, None, 0);
+        let conds = conds.expect("run_spend_bundle_with_timings");
+        let max_spend_cost = std::cmp::max(conds[0].cost, conds[1].cost);
+
+        let (conds, _) = run_spend_bundle_with_timings::<MempoolVisitor>(
+            &mut a,
+            &bundle,
+            11000000000,
+            Some(max_spend_cost),
+            0,
+        );
+        assert_eq!(conds.expect("run_spend_bundle_with_timings").len(), 2);
+
+        // the total budget is enough, but one of the spends exceeds its limit
+        let (conds, _) = run_spend_bundle_with_timings::<MempoolVisitor>(
+            &mut a,
+            &bundle,
+            11000000000,
+            Some(max_spend_cost - 1),
+            0,
+        );
+        assert_eq!(conds.unwrap_err().1, ErrorCode::CostExceeded);
+
+        // and the total budget still applies
+        let (conds, _) = run_spend_bundle_with_timings::<MempoolVisitor>(
+            &mut a,
+            &bundle,
+            max_spend_cost,
+            Some(max_spend_cost),
+            0,
+        );
+        assert_eq!(conds.unwrap_err().1, ErrorCode::CostExceeded);
+    }
+
+    // the puzzle 1 returns the solution as the conditions
+    const IDENTITY_HASH: &str = "9dcf97a184f32623d11a73124ceb99a5709b083721e878a16d78f596718ba7b2";
+
+    #[rstest]
+    // CREATE_COIN with the same puzzle hash
+    #[case(1, &format!("ffff33ffa0{IDENTITY_HASH}ff018080"), true, vec![])]
+    #[case(
+        2,
+        &format!("ffff33ffa0{IDENTITY_HASH}ff028080"),
+        true,
+        vec![FastForwardIneligible::EvenAmount, FastForwardIneligible::NoSingletonOutput]
+    )]
+    // CREATE_COIN to another puzzle hash, and an AGG_SIG_ME
+    #[case(
+        1,
+        &format!(
+            "ffff33ffa0{}ff0180ffff32ffb0c0{}ff836d73678080",
+            "ab".repeat(32),
+            "00".repeat(47)
+        ),
+        false,
+        vec![FastForwardIneligible::AggSigCommitsToParent, FastForwardIneligible::NoSingletonOutput]
+    )]
+    fn test_analyze_spend(
+        #[case] amount: u64,
+        #[case] solution: &str,
+        #[case] dedup: bool,
+        #[case] reasons: Vec<FastForwardIneligible>,
+    ) {
+        let mut a = Allocator::new();
+        let solution = hex::decode(solution).unwrap();
+        let analysis = analyze_spend(&mut a, &[1], &solution, &[0; 32], amount, 11000000000, 0)
+            .expect("analyze_spend");
+        assert_eq!(analysis.eligible_for_dedup(), dedup);
+        assert_eq!(analysis.eligible_for_ff(), reasons.is_empty());
+        assert_eq!(analysis.ff_ineligible_reasons(), reasons);
+    }
+}
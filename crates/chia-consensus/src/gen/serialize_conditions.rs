@@ -0,0 +1,290 @@
+use super::conditions::{parse_args, Condition};
+use super::flags::NO_UNKNOWN_CONDS;
+use super::opcodes::{
+    parse_opcode, ConditionOpcode, AGG_SIG_AMOUNT, AGG_SIG_ME, AGG_SIG_PARENT,
+    AGG_SIG_PARENT_AMOUNT, AGG_SIG_PARENT_PUZZLE, AGG_SIG_PUZZLE, AGG_SIG_PUZZLE_AMOUNT,
+    AGG_SIG_UNSAFE, ASSERT_BEFORE_HEIGHT_ABSOLUTE, ASSERT_BEFORE_HEIGHT_RELATIVE,
+    ASSERT_BEFORE_SECONDS_ABSOLUTE, ASSERT_BEFORE_SECONDS_RELATIVE, ASSERT_COIN_ANNOUNCEMENT,
+    ASSERT_CONCURRENT_PUZZLE, ASSERT_CONCURRENT_SPEND, ASSERT_EPHEMERAL, ASSERT_HEIGHT_ABSOLUTE,
+    ASSERT_HEIGHT_RELATIVE, ASSERT_MY_AMOUNT, ASSERT_MY_BIRTH_HEIGHT, ASSERT_MY_BIRTH_SECONDS,
+    ASSERT_MY_COIN_ID, ASSERT_MY_PARENT_ID, ASSERT_MY_PUZZLEHASH, ASSERT_PUZZLE_ANNOUNCEMENT,
+    ASSERT_SECONDS_ABSOLUTE, ASSERT_SECONDS_RELATIVE, CREATE_COIN, CREATE_COIN_ANNOUNCEMENT,
+    CREATE_PUZZLE_ANNOUNCEMENT, RECEIVE_MESSAGE, RESERVE_FEE, SEND_MESSAGE, SOFTFORK,
+};
+use super::validation_error::{first, next, rest, ErrorCode, ValidationErr};
+use clvmr::allocator::{Allocator, NodePtr};
+use clvmr::reduction::EvalErr;
+
+fn number(a: &mut Allocator, n: u64) -> Result<NodePtr, EvalErr> {
+    a.new_number(n.into())
+}
+
+fn make_list(a: &mut Allocator, items: &[NodePtr]) -> Result<NodePtr, EvalErr> {
+    let mut ret = a.nil();
+    for item in items.iter().rev() {
+        ret = a.new_pair(*item, ret)?;
+    }
+    Ok(ret)
+}
+
+// The inverse of parse_args(). Builds the condition (opcode and arguments) in
+// its canonical form, i.e. with minimally encoded integers and without any
+// extra arguments. Conditions that parse_args() turns into Skip or
+// SkipRelativeCondition (like REMARK) are always true and have no canonical
+// form, None is returned for them. Softfork conditions are returned as SOFTFORK
+// with the same cost, regardless of which opcode they were parsed from.
+// Any NodePtr in the condition must point into a.
+pub fn condition_to_clvm(a: &mut Allocator, c: &Condition) -> Result<Option<NodePtr>, EvalErr> {
+    let mut args = Vec::<NodePtr>::with_capacity(4);
+    let op: ConditionOpcode = match c {
+        Condition::AggSigUnsafe(pk, msg)
+        | Condition::AggSigMe(pk, msg)
+        | Condition::AggSigParent(pk, msg)
+        | Condition::AggSigPuzzle(pk, msg)
+        | Condition::AggSigAmount(pk, msg)
+        | Condition::AggSigPuzzleAmount(pk, msg)
+        | Condition::AggSigParentAmount(pk, msg)
+        | Condition::AggSigParentPuzzle(pk, msg) => {
+            args.push(*pk);
+            args.push(*msg);
+            match c {
+                Condition::AggSigUnsafe(..) => AGG_SIG_UNSAFE,
+                Condition::AggSigMe(..) => AGG_SIG_ME,
+                Condition::AggSigParent(..) => AGG_SIG_PARENT,
+                Condition::AggSigPuzzle(..) => AGG_SIG_PUZZLE,
+                Condition::AggSigAmount(..) => AGG_SIG_AMOUNT,
+                Condition::AggSigPuzzleAmount(..) => AGG_SIG_PUZZLE_AMOUNT,
+                Condition::AggSigParentAmount(..) => AGG_SIG_PARENT_AMOUNT,
+                _ => AGG_SIG_PARENT_PUZZLE,
+            }
+        }
+        Condition::CreateCoin(puzzle_hash, amount, hint) => {
+            args.push(*puzzle_hash);
+            args.push(number(a, *amount)?);
+            // the hint is the first (and only) memo. Only the hint is
+            // preserved by parse_args(), not any other memos
+            if !a.atom(*hint).as_ref().is_empty() {
+                let memos = make_list(a, &[*hint])?;
+                args.push(memos);
+            }
+            CREATE_COIN
+        }
+        Condition::ReserveFee(amount) => {
+            args.push(number(a, *amount)?);
+            RESERVE_FEE
+        }
+        Condition::CreateCoinAnnouncement(msg) => {
+            args.push(*msg);
+            CREATE_COIN_ANNOUNCEMENT
+        }
+        Condition::CreatePuzzleAnnouncement(msg) => {
+            args.push(*msg);
+            CREATE_PUZZLE_ANNOUNCEMENT
+        }
+        Condition::AssertCoinAnnouncement(id) => {
+            args.push(*id);
+            ASSERT_COIN_ANNOUNCEMENT
+        }
+        Condition::AssertPuzzleAnnouncement(id) => {
+            args.push(*id);
+            ASSERT_PUZZLE_ANNOUNCEMENT
+        }
+        Condition::AssertConcurrentSpend(id) => {
+            args.push(*id);
+            ASSERT_CONCURRENT_SPEND
+        }
+        Condition::AssertConcurrentPuzzle(id) => {
+            args.push(*id);
+            ASSERT_CONCURRENT_PUZZLE
+        }
+        Condition::AssertMyCoinId(id) => {
+            args.push(*id);
+            ASSERT_MY_COIN_ID
+        }
+        Condition::AssertMyParentId(id) => {
+            args.push(*id);
+            ASSERT_MY_PARENT_ID
+        }
+        Condition::AssertMyPuzzlehash(id) => {
+            args.push(*id);
+            ASSERT_MY_PUZZLEHASH
+        }
+        Condition::AssertMyAmount(amount) => {
+            args.push(number(a, *amount)?);
+            ASSERT_MY_AMOUNT
+        }
+        Condition::AssertMyBirthSeconds(s) => {
+            args.push(number(a, *s)?);
+            ASSERT_MY_BIRTH_SECONDS
+        }
+        Condition::AssertMyBirthHeight(h) => {
+            args.push(number(a, u64::from(*h))?);
+            ASSERT_MY_BIRTH_HEIGHT
+        }
+        Condition::AssertSecondsRelative(s) => {
+            args.push(number(a, *s)?);
+            ASSERT_SECONDS_RELATIVE
+        }
+        Condition::AssertSecondsAbsolute(s) => {
+            args.push(number(a, *s)?);
+            ASSERT_SECONDS_ABSOLUTE
+        }
+        Condition::AssertHeightRelative(h) => {
+            args.push(number(a, u64::from(*h))?);
+            ASSERT_HEIGHT_RELATIVE
+        }
+        Condition::AssertHeightAbsolute(h) => {
+            args.push(number(a, u64::from(*h))?);
+            ASSERT_HEIGHT_ABSOLUTE
+        }
+        Condition::AssertBeforeSecondsRelative(s) => {
+            args.push(number(a, *s)?);
+            ASSERT_BEFORE_SECONDS_RELATIVE
+        }
+        Condition::AssertBeforeSecondsAbsolute(s) => {
+            args.push(number(a, *s)?);
+            ASSERT_BEFORE_SECONDS_ABSOLUTE
+        }
+        Condition::AssertBeforeHeightRelative(h) => {
+            args.push(number(a, u64::from(*h))?);
+            ASSERT_BEFORE_HEIGHT_RELATIVE
+        }
+        Condition::AssertBeforeHeightAbsolute(h) => {
+            args.push(number(a, u64::from(*h))?);
+            ASSERT_BEFORE_HEIGHT_ABSOLUTE
+        }
+        Condition::AssertEphemeral => ASSERT_EPHEMERAL,
+        Condition::Softfork(cost) => {
+            // the argument is the cost, scaled down by 10000
+            args.push(number(a, cost / 10000)?);
+            SOFTFORK
+        }
+        Condition::SendMessage(src_mode, dst, msg) => {
+            let mode = (src_mode << 3) | dst.mode();
+            args.push(number(a, mode.into())?);
+            args.push(*msg);
+            dst.to_args(a, &mut args)?;
+            SEND_MESSAGE
+        }
+        Condition::ReceiveMessage(src, dst_mode, msg) => {
+            let mode = (src.mode() << 3) | dst_mode;
+            args.push(number(a, mode.into())?);
+            args.push(*msg);
+            src.to_args(a, &mut args)?;
+            RECEIVE_MESSAGE
+        }
+        Condition::Skip | Condition::SkipRelativeCondition => {
+            return Ok(None);
+        }
+    };
+    args.insert(0, number(a, op.into())?);
+    Ok(Some(make_list(a, &args)?))
+}
+
+// builds the list of conditions, leaving out the ones that don't have a
+// canonical form (see condition_to_clvm())
+pub fn conditions_to_clvm(a: &mut Allocator, conditions: &[Condition]) -> Result<NodePtr, EvalErr> {
+    let mut items = Vec::<NodePtr>::with_capacity(conditions.len());
+    for c in conditions {
+        if let Some(node) = condition_to_clvm(a, c)? {
+            items.push(node);
+        }
+    }
+    make_list(a, &items)
+}
+
+// Parses a list of conditions, the same way as the conditions returned by a
+// puzzle, and returns it in canonical form. Unknown conditions are left out,
+// unless the NO_UNKNOWN_CONDS flag is set, in which case they fail.
+pub fn canonicalize_conditions(
+    a: &mut Allocator,
+    mut iter: NodePtr,
+    flags: u32,
+) -> Result<NodePtr, ValidationErr> {
+    let mut conditions = Vec::<Condition>::new();
+    while let Some((c, next)) = next(a, iter)? {
+        iter = next;
+        let Some(op) = parse_opcode(a, first(a, c)?, flags) else {
+            if (flags & NO_UNKNOWN_CONDS) != 0 {
+                return Err(ValidationErr(c, ErrorCode::InvalidConditionOpcode));
+            }
+            continue;
+        };
+        conditions.push(parse_args(a, rest(a, c)?, op, flags)?);
+    }
+    Ok(conditions_to_clvm(a, &conditions)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gen::flags::{
+        ENABLE_MESSAGE_CONDITIONS, ENABLE_SOFTFORK_CONDITION, STRICT_ARGS_COUNT,
+    };
+    use clvmr::serde::{node_from_bytes, node_to_bytes};
+    use rstest::rstest;
+
+    fn canonical(hex: &str, flags: u32) -> Result<String, ErrorCode> {
+        let mut a = Allocator::new();
+        let conditions = node_from_bytes(&mut a, &hex::decode(hex).unwrap()).unwrap();
+        let ret = canonicalize_conditions(&mut a, conditions, flags).map_err(|e| e.1)?;
+        Ok(hex::encode(node_to_bytes(&a, ret).unwrap()))
+    }
+
+    const PH: &str = "a0abababababababababababababababababababababababababababababababab";
+    const PK: &str = "b0aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+
+    #[rstest]
+    // (CREATE_COIN ph 1000)
+    #[case(&format!("ffff33ff{PH}ff8203e88080"))]
+    // (CREATE_COIN ph 1000 (ph))
+    #[case(&format!("ffff33ff{PH}ff8203e8ffff{PH}808080"))]
+    // (AGG_SIG_ME pk "msg")
+    #[case(&format!("ffff32ff{PK}ff836d73678080"))]
+    // (RESERVE_FEE 0) (ASSERT_EPHEMERAL)
+    #[case("ffff34ff8080ffff4c8080")]
+    // (ASSERT_HEIGHT_RELATIVE 1) (ASSERT_BEFORE_SECONDS_ABSOLUTE 0x8000)
+    #[case("ffff52ff0180ffff55ff830080008080")]
+    // (SEND_MESSAGE 0x3f "msg" coin-id)
+    #[case(&format!("ffff42ff3fff836d7367ff{PH}8080"))]
+    // (RECEIVE_MESSAGE 0x12 "msg" puzzle-hash)
+    #[case(&format!("ffff43ff12ff836d7367ff{PH}8080"))]
+    // (SEND_MESSAGE 0x08 "msg")
+    #[case("ffff42ff08ff836d73678080")]
+    fn test_roundtrip(#[case] hex: &str) {
+        let flags = STRICT_ARGS_COUNT | ENABLE_MESSAGE_CONDITIONS;
+        assert_eq!(canonical(hex, flags).as_deref(), Ok(hex));
+    }
+
+    #[rstest]
+    // an empty memo list and an extra argument
+    #[case(&format!("ffff33ff{PH}ff8203e8ff80ff018080"), &format!("ffff33ff{PH}ff8203e88080"))]
+    // the memos after the hint are dropped
+    #[case(&format!("ffff33ff{PH}ff01ffff{PH}ff01808080"), &format!("ffff33ff{PH}ff01ffff{PH}808080"))]
+    // a nil hint is the same as no hint
+    #[case(&format!("ffff33ff{PH}ff01ffff80808080"), &format!("ffff33ff{PH}ff018080"))]
+    // REMARK and unknown conditions are always true
+    #[case("ffff01ff0280ffff1380ffff34ff058080", "ffff34ff058080")]
+    // negative relative times are always true
+    #[case("ffff50ff81ff8080", "80")]
+    fn test_canonicalize(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(canonical(input, 0).as_deref(), Ok(expected));
+    }
+
+    #[test]
+    fn test_softfork() {
+        // (SOFTFORK 2 "foo")
+        assert_eq!(
+            canonical("ffff5aff02ff83666f6f8080", ENABLE_SOFTFORK_CONDITION).as_deref(),
+            Ok("ffff5aff028080")
+        );
+    }
+
+    #[rstest]
+    #[case("ffff33ff01ff018080", ErrorCode::InvalidPuzzleHash)]
+    #[case("ffff3480", ErrorCode::InvalidCondition)]
+    #[case("ffff1380", ErrorCode::InvalidConditionOpcode)]
+    fn test_invalid(#[case] input: &str, #[case] expected: ErrorCode) {
+        assert_eq!(canonical(input, NO_UNKNOWN_CONDS), Err(expected));
+    }
+}
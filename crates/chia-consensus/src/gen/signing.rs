@@ -0,0 +1,261 @@
+use crate::consensus_constants::ConsensusConstants;
+use crate::gen::opcodes::{
+    ConditionOpcode, AGG_SIG_AMOUNT, AGG_SIG_ME, AGG_SIG_PARENT, AGG_SIG_PARENT_AMOUNT,
+    AGG_SIG_PARENT_PUZZLE, AGG_SIG_PUZZLE, AGG_SIG_PUZZLE_AMOUNT,
+};
+use crate::gen::owned_conditions::{OwnedSpend, OwnedSpendBundleConditions};
+use chia_bls::PublicKey;
+use chia_protocol::{Bytes, Bytes32};
+use clvmr::sha2::{Digest, Sha256};
+
+// The network a signature is bound to. This is implemented both by the
+// consensus constants and by NetworkInfo, so the signing helpers can't be
+// passed data that isn't tied to a network
+pub trait AggSigNetwork {
+    fn agg_sig_me_additional_data(&self) -> Bytes32;
+}
+
+impl AggSigNetwork for ConsensusConstants {
+    fn agg_sig_me_additional_data(&self) -> Bytes32 {
+        self.agg_sig_me_additional_data
+    }
+}
+
+// The additional data appended to the message of an AGG_SIG_* condition. This
+// binds signatures to a specific network (to prevent replay attacks across
+// forks).
+// AGG_SIG_ME uses the agg_sig_me_additional_data of the network as-is.
+// Every other variant (the ones introduced by the AGG_SIG_* hard fork) uses
+// sha256(agg_sig_me_additional_data + opcode), to also make signatures
+// unique per condition. AGG_SIG_UNSAFE (and any other opcode) doesn't have
+// any additional data, and returns None.
+pub fn agg_sig_additional_data<N: AggSigNetwork + ?Sized>(
+    network: &N,
+    opcode: ConditionOpcode,
+) -> Option<Bytes32> {
+    match opcode {
+        AGG_SIG_ME => Some(network.agg_sig_me_additional_data()),
+        AGG_SIG_PARENT
+        | AGG_SIG_PUZZLE
+        | AGG_SIG_AMOUNT
+        | AGG_SIG_PUZZLE_AMOUNT
+        | AGG_SIG_PARENT_AMOUNT
+        | AGG_SIG_PARENT_PUZZLE => {
+            let mut hasher = Sha256::new();
+            hasher.update(network.agg_sig_me_additional_data());
+            hasher.update([opcode as u8]);
+            let data: [u8; 32] = hasher.finalize().into();
+            Some(data.into())
+        }
+        _ => None,
+    }
+}
+
+// the amount is encoded as a CLVM integer, the same way it's encoded in the
+// coin ID computation
+fn amount_bytes(amount: u64) -> Vec<u8> {
+    if amount == 0 {
+        return Vec::new();
+    }
+    let buf = amount.to_be_bytes();
+    let mut start = 0;
+    while start < buf.len() - 1 && buf[start] == 0 && (buf[start + 1] & 0x80) == 0 {
+        start += 1;
+    }
+    let mut ret = Vec::<u8>::with_capacity(9);
+    if (buf[start] & 0x80) != 0 {
+        ret.push(0);
+    }
+    ret.extend_from_slice(&buf[start..]);
+    ret
+}
+
+// the bytes that go between the message and the additional data, for the
+// AGG_SIG_* condition with the specified opcode
+fn agg_sig_coin_data(spend: &OwnedSpend, opcode: ConditionOpcode) -> Vec<u8> {
+    let mut ret = Vec::<u8>::new();
+    match opcode {
+        AGG_SIG_ME => ret.extend_from_slice(&spend.coin_id),
+        AGG_SIG_PARENT => ret.extend_from_slice(&spend.parent_id),
+        AGG_SIG_PUZZLE => ret.extend_from_slice(&spend.puzzle_hash),
+        AGG_SIG_AMOUNT => ret.extend_from_slice(&amount_bytes(spend.coin_amount)),
+        AGG_SIG_PUZZLE_AMOUNT => {
+            ret.extend_from_slice(&spend.puzzle_hash);
+            ret.extend_from_slice(&amount_bytes(spend.coin_amount));
+        }
+        AGG_SIG_PARENT_AMOUNT => {
+            ret.extend_from_slice(&spend.parent_id);
+            ret.extend_from_slice(&amount_bytes(spend.coin_amount));
+        }
+        AGG_SIG_PARENT_PUZZLE => {
+            ret.extend_from_slice(&spend.parent_id);
+            ret.extend_from_slice(&spend.puzzle_hash);
+        }
+        _ => {}
+    }
+    ret
+}
+
+// returns all (public key, message) pairs that need to be signed (and
+// aggregated) for the spend bundle to be valid. AGG_SIG_UNSAFE conditions are
+// included as-is, followed by the conditions of each spend, with the coin
+// data and additional data appended to the message.
+pub fn messages_to_sign<N: AggSigNetwork + ?Sized>(
+    conds: &OwnedSpendBundleConditions,
+    network: &N,
+) -> Vec<(PublicKey, Bytes)> {
+    let mut ret = Vec::<(PublicKey, Bytes)>::new();
+    for (pk, msg) in &conds.agg_sig_unsafe {
+        ret.push((*pk, msg.clone()));
+    }
+
+    for spend in &conds.spends {
+        for (opcode, items) in [
+            (AGG_SIG_PARENT, &spend.agg_sig_parent),
+            (AGG_SIG_PUZZLE, &spend.agg_sig_puzzle),
+            (AGG_SIG_AMOUNT, &spend.agg_sig_amount),
+            (AGG_SIG_PUZZLE_AMOUNT, &spend.agg_sig_puzzle_amount),
+            (AGG_SIG_PARENT_AMOUNT, &spend.agg_sig_parent_amount),
+            (AGG_SIG_PARENT_PUZZLE, &spend.agg_sig_parent_puzzle),
+            (AGG_SIG_ME, &spend.agg_sig_me),
+        ] {
+            if items.is_empty() {
+                continue;
+            }
+            let additional_data = agg_sig_additional_data(network, opcode).expect("AGG_SIG opcode");
+            let coin_data = agg_sig_coin_data(spend, opcode);
+            for (pk, msg) in items {
+                let mut buf = msg.to_vec();
+                buf.extend_from_slice(&coin_data);
+                buf.extend_from_slice(&additional_data);
+                ret.push((*pk, buf.into()));
+            }
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus_constants::TEST_CONSTANTS;
+    use chia_bls::SecretKey;
+    use hex_literal::hex;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0, "")]
+    #[case(1, "01")]
+    #[case(0x7f, "7f")]
+    #[case(0x80, "0080")]
+    #[case(0xff, "00ff")]
+    #[case(0x100, "0100")]
+    #[case(1750000000000, "01977420dc00")]
+    #[case(u64::MAX, "00ffffffffffffffff")]
+    fn test_amount_bytes(#[case] amount: u64, #[case] expected: &str) {
+        assert_eq!(hex::encode(amount_bytes(amount)), expected);
+    }
+
+    #[test]
+    fn test_additional_data() {
+        let c = &TEST_CONSTANTS;
+        assert_eq!(
+            agg_sig_additional_data(c, AGG_SIG_ME),
+            Some(c.agg_sig_me_additional_data)
+        );
+        for opcode in [
+            AGG_SIG_PARENT,
+            AGG_SIG_PUZZLE,
+            AGG_SIG_AMOUNT,
+            AGG_SIG_PUZZLE_AMOUNT,
+            AGG_SIG_PARENT_AMOUNT,
+            AGG_SIG_PARENT_PUZZLE,
+        ] {
+            let mut buf = c.agg_sig_me_additional_data.to_vec();
+            buf.push(opcode as u8);
+            let expected: [u8; 32] = Sha256::digest(buf).into();
+            assert_eq!(
+                agg_sig_additional_data(c, opcode),
+                Some(Bytes32::from(expected))
+            );
+        }
+        assert_eq!(agg_sig_additional_data(c, 49), None);
+        assert_eq!(agg_sig_additional_data(c, 51), None);
+    }
+
+    #[test]
+    fn test_messages_to_sign() {
+        let c = &TEST_CONSTANTS;
+        let pk = SecretKey::from_seed(&[1_u8; 32]).public_key();
+        let msg: Bytes = b"msg".to_vec().into();
+        let parent = Bytes32::from(hex!(
+            "1111111111111111111111111111111111111111111111111111111111111111"
+        ));
+        let ph = Bytes32::from(hex!(
+            "2222222222222222222222222222222222222222222222222222222222222222"
+        ));
+        let coin_id = Bytes32::from(hex!(
+            "3333333333333333333333333333333333333333333333333333333333333333"
+        ));
+
+        let spend = OwnedSpend {
+            coin_id,
+            parent_id: parent,
+            puzzle_hash: ph,
+            coin_amount: 128,
+            height_relative: None,
+            seconds_relative: None,
+            before_height_relative: None,
+            before_seconds_relative: None,
+            birth_height: None,
+            birth_seconds: None,
+            create_coin: vec![],
+            agg_sig_me: vec![(pk, msg.clone())],
+            agg_sig_parent: vec![],
+            agg_sig_puzzle: vec![],
+            agg_sig_amount: vec![(pk, msg.clone())],
+            agg_sig_puzzle_amount: vec![],
+            agg_sig_parent_amount: vec![],
+            agg_sig_parent_puzzle: vec![(pk, msg.clone())],
+            flags: 0,
+        };
+        let conds = OwnedSpendBundleConditions {
+            spends: vec![spend],
+            reserve_fee: 0,
+            height_absolute: 0,
+            seconds_absolute: 0,
+            before_height_absolute: None,
+            before_seconds_absolute: None,
+            agg_sig_unsafe: vec![(pk, msg.clone())],
+            cost: 0,
+            removal_amount: 128,
+            addition_amount: 0,
+        };
+
+        let msgs = messages_to_sign(&conds, c);
+        assert_eq!(msgs.len(), 4);
+        assert!(msgs.iter().all(|(key, _)| *key == pk));
+
+        // AGG_SIG_UNSAFE
+        assert_eq!(msgs[0].1, msg);
+
+        // AGG_SIG_AMOUNT
+        let mut expected = b"msg".to_vec();
+        expected.extend_from_slice(&hex!("0080"));
+        expected.extend_from_slice(&agg_sig_additional_data(c, AGG_SIG_AMOUNT).unwrap());
+        assert_eq!(msgs[1].1.as_slice(), expected.as_slice());
+
+        // AGG_SIG_PARENT_PUZZLE
+        let mut expected = b"msg".to_vec();
+        expected.extend_from_slice(&parent);
+        expected.extend_from_slice(&ph);
+        expected.extend_from_slice(&agg_sig_additional_data(c, AGG_SIG_PARENT_PUZZLE).unwrap());
+        assert_eq!(msgs[2].1.as_slice(), expected.as_slice());
+
+        // AGG_SIG_ME
+        let mut expected = b"msg".to_vec();
+        expected.extend_from_slice(&coin_id);
+        expected.extend_from_slice(&c.agg_sig_me_additional_data);
+        assert_eq!(msgs[3].1.as_slice(), expected.as_slice());
+    }
+}
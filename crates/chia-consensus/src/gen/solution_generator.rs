@@ -1,8 +1,41 @@
-use chia_protocol::Coin;
+use crate::allocator::make_allocator;
+use crate::consensus_constants::ConsensusConstants;
+use crate::gen::conditions::{MempoolVisitor, ELIGIBLE_FOR_DEDUP};
+use crate::gen::flags::MEMPOOL_MODE;
+use crate::gen::run_puzzle::run_puzzle;
+use crate::gen::validation_error::{ErrorCode, ValidationErr};
+use crate::generator_rom::COST_PER_BYTE;
+use chia_protocol::{Bytes32, Coin};
 use clvmr::allocator::{Allocator, NodePtr};
 use clvmr::serde::{node_from_bytes_backrefs, node_to_bytes, node_to_bytes_backrefs};
+use clvmr::sha2::{Digest, Sha256};
+use clvmr::LIMIT_HEAP;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
 use std::io;
 
+// builds a single spend, (parent-id puzzle-reveal amount solution)
+fn build_spend(
+    a: &mut Allocator,
+    coin: &Coin,
+    puzzle: &[u8],
+    solution: &[u8],
+) -> io::Result<NodePtr> {
+    let item = a.nil();
+    // solution
+    let solution = node_from_bytes_backrefs(a, solution)?;
+    let item = a.new_pair(solution, item)?;
+    // amount
+    let amount = a.new_number(coin.amount.into())?;
+    let item = a.new_pair(amount, item)?;
+    // puzzle reveal
+    let puzzle = node_from_bytes_backrefs(a, puzzle)?;
+    let item = a.new_pair(puzzle, item)?;
+    // parent-id
+    let parent_id = a.new_atom(&coin.parent_coin_info)?;
+    a.new_pair(parent_id, item)
+}
+
 // the tuple has the Coin, puzzle-reveal and solution
 fn build_generator<BufRef, I>(a: &mut Allocator, spends: I) -> io::Result<NodePtr>
 where
@@ -15,20 +48,7 @@ where
 
     let mut spend_list = a.nil();
     for s in spends {
-        let item = a.nil();
-        // solution
-        let solution = node_from_bytes_backrefs(a, s.2.as_ref())?;
-        let item = a.new_pair(solution, item)?;
-        // amount
-        let amount = a.new_number(s.0.amount.into())?;
-        let item = a.new_pair(amount, item)?;
-        // puzzle reveal
-        let puzzle = node_from_bytes_backrefs(a, s.1.as_ref())?;
-        let item = a.new_pair(puzzle, item)?;
-        // parent-id
-        let parent_id = a.new_atom(&s.0.parent_coin_info)?;
-        let item = a.new_pair(parent_id, item)?;
-
+        let item = build_spend(a, &s.0, s.1.as_ref(), s.2.as_ref())?;
         spend_list = a.new_pair(item, spend_list)?;
     }
 
@@ -42,12 +62,37 @@ where
 // the tuple has the Coin, puzzle-reveal and solution
 pub fn solution_generator<BufRef, I>(spends: I) -> io::Result<Vec<u8>>
 where
-    BufRef: AsRef<[u8]>,
+    BufRef: AsRef<[u8]> + Sync,
     I: IntoIterator<Item = (Coin, BufRef, BufRef)>,
 {
-    let mut a = Allocator::new();
-    let generator = build_generator(&mut a, spends)?;
-    node_to_bytes(&a, generator)
+    // Without back references, the serialization of a list is just the
+    // serialization of each item, prefixed by 0xff, and terminated by 0x80.
+    // This lets us serialize the spends independently, on all cores, and
+    // then stitch them together into the same generator build_generator()
+    // produces.
+    let spends: Vec<(Coin, BufRef, BufRef)> = spends.into_iter().collect();
+    let items = spends
+        .par_iter()
+        .map(|(coin, puzzle, solution)| {
+            let mut a = Allocator::new();
+            let item = build_spend(&mut a, coin, puzzle.as_ref(), solution.as_ref())?;
+            node_to_bytes(&a, item)
+        })
+        .collect::<io::Result<Vec<Vec<u8>>>>()?;
+
+    let len = items.iter().map(|item| item.len() + 1).sum::<usize>() + 5;
+    let mut ret = Vec::<u8>::with_capacity(len);
+    // (q . ( <spend-list> ) )
+    ret.extend_from_slice(&[0xff, 0x01, 0xff]);
+    // build_generator() prepends the spends to the list, so they end up in
+    // reverse order
+    for item in items.iter().rev() {
+        ret.push(0xff);
+        ret.extend_from_slice(item);
+    }
+    // terminates the spend list and the outer list
+    ret.extend_from_slice(&[0x80, 0x80]);
+    Ok(ret)
 }
 
 pub fn solution_generator_backrefs<BufRef, I>(spends: I) -> io::Result<Vec<u8>>
@@ -60,11 +105,191 @@ where
     node_to_bytes_backrefs(&a, generator)
 }
 
+// builds a block generator (with back references) out of the spends, in the
+// order they are passed in. Every spend is run in mempool mode to compute its
+// cost and spend flags. Spends that are identical to an earlier spend (same
+// coin and same solution) and are eligible for dedup are only included once.
+// Spending the same coin twice in any other way is a double spend. Once the
+// next spend would push the total cost above max_cost, we stop and leave the
+// remaining spends out.
+// The cost counted here is the execution- and condition cost of each spend
+// plus the cost of its serialized puzzle and solution bytes.
+// returns the serialized generator, the cost and the indices (into spends) of
+// the spends that were included
+pub fn build_block_generator<BufRef, I>(
+    spends: I,
+    max_cost: u64,
+) -> Result<(Vec<u8>, u64, Vec<usize>), ValidationErr>
+where
+    BufRef: AsRef<[u8]>,
+    I: IntoIterator<Item = (Coin, BufRef, BufRef)>,
+{
+    let mut a = make_allocator(LIMIT_HEAP);
+    let checkpoint = a.checkpoint();
+
+    // maps coin ID -> index into "included"
+    let mut seen = HashMap::<Bytes32, usize>::new();
+    let mut included = Vec::<(Coin, BufRef, BufRef)>::new();
+    let mut indices = Vec::<usize>::new();
+    let mut total_cost: u64 = 0;
+
+    for (idx, (coin, puzzle, solution)) in spends.into_iter().enumerate() {
+        let byte_cost = (puzzle.as_ref().len() + solution.as_ref().len()) as u64 * COST_PER_BYTE;
+        let cost_left = max_cost - total_cost;
+        if byte_cost > cost_left {
+            break;
+        }
+
+        a.restore_checkpoint(&checkpoint);
+        let conds = match run_puzzle::<MempoolVisitor>(
+            &mut a,
+            puzzle.as_ref(),
+            solution.as_ref(),
+            &coin.parent_coin_info,
+            coin.amount,
+            cost_left - byte_cost,
+            MEMPOOL_MODE,
+        ) {
+            Ok(conds) => conds,
+            Err(ValidationErr(_, ErrorCode::CostExceeded)) => break,
+            Err(e) => return Err(e),
+        };
+        let spend = &conds.spends[0];
+        let coin_id = *spend.coin_id;
+
+        if let Some(prev) = seen.get(&coin_id) {
+            let (_, _, prev_solution) = &included[*prev];
+            if (spend.flags & ELIGIBLE_FOR_DEDUP) != 0
+                && prev_solution.as_ref() == solution.as_ref()
+            {
+                continue;
+            }
+            return Err(ValidationErr(NodePtr::NIL, ErrorCode::DoubleSpend));
+        }
+
+        let spend_cost = conds.cost + byte_cost;
+        if spend_cost > cost_left {
+            break;
+        }
+        total_cost += spend_cost;
+        seen.insert(coin_id, included.len());
+        included.push((coin, puzzle, solution));
+        indices.push(idx);
+    }
+
+    let mut a = Allocator::new();
+    let generator = build_generator(&mut a, included)?;
+    Ok((node_to_bytes_backrefs(&a, generator)?, total_cost, indices))
+}
+
+// the number of bytes a back reference to an earlier (identical) puzzle reveal
+// is assumed to take up. It's the 0xfe prefix followed by the path atom, which
+// in practice is rarely more than 3 bytes
+const BACKREF_SIZE_ESTIMATE: u64 = 4;
+
+// the serialized size of the amount atom
+fn amount_size(amount: u64) -> u64 {
+    if amount == 0 {
+        return 1;
+    }
+    let bytes = 8 - amount.leading_zeros() as u64 / 8;
+    // a leading zero byte is needed when the most significant bit is set,
+    // to keep the number positive
+    let bytes = if (amount >> (bytes * 8 - 1)) & 1 == 1 {
+        bytes + 1
+    } else {
+        bytes
+    };
+    if bytes == 1 && amount < 0x80 {
+        1
+    } else {
+        bytes + 1
+    }
+}
+
+// the serialized size of the list (parent-id puzzle-reveal amount solution),
+// including the 0xff prefix of the pair it's the first item of in the list of
+// spends
+fn spend_size(coin: &Coin, puzzle_size: u64, solution_size: u64) -> u64 {
+    // the 4 pairs of the list and its nil terminator
+    5 + 33 + puzzle_size + amount_size(coin.amount) + solution_size + 1
+}
+
+// predicts the size of the generators solution_generator() and
+// solution_generator_backrefs() would produce for these spends, without
+// building them. The puzzles and solutions are expected to be serialized
+// without back references. In that case, the first size is exact. The size
+// with back references only accounts for repeated puzzle reveals (which is
+// where almost all of the savings come from), so it's an approximation.
+// returns (size, size with back references)
+pub fn estimate_generator_size<BufRef, I>(spends: I) -> (u64, u64)
+where
+    BufRef: AsRef<[u8]>,
+    I: IntoIterator<Item = (Coin, BufRef, BufRef)>,
+{
+    // (q . ( <spend-list> ) ) and the terminators of the two lists
+    let mut size: u64 = 5;
+    let mut size_backrefs: u64 = 5;
+    let mut seen_puzzles = HashSet::<[u8; 32]>::new();
+    for (coin, puzzle, solution) in spends {
+        let puzzle = puzzle.as_ref();
+        let solution_size = solution.as_ref().len() as u64;
+        size += spend_size(&coin, puzzle.len() as u64, solution_size);
+
+        let puzzle_hash: [u8; 32] = Sha256::digest(puzzle).into();
+        let puzzle_size = if seen_puzzles.insert(puzzle_hash) {
+            puzzle.len() as u64
+        } else {
+            BACKREF_SIZE_ESTIMATE.min(puzzle.len() as u64)
+        };
+        size_backrefs += spend_size(&coin, puzzle_size, solution_size);
+    }
+    (size, size_backrefs)
+}
+
+// predicts the cost of a block generator made from these spends, without
+// building it. Every spend is run (in mempool mode) to compute its execution
+// and condition cost. The byte cost is based on the size of the generator
+// without back references, which makes this an upper bound of the byte cost
+// of a generator using back references.
+pub fn estimate_cost<BufRef, I>(
+    spends: I,
+    constants: &ConsensusConstants,
+) -> Result<u64, ValidationErr>
+where
+    BufRef: AsRef<[u8]>,
+    I: IntoIterator<Item = (Coin, BufRef, BufRef)>,
+{
+    let mut a = make_allocator(LIMIT_HEAP);
+    let checkpoint = a.checkpoint();
+
+    let mut size: u64 = 5;
+    let mut cost: u64 = 0;
+    for (coin, puzzle, solution) in spends {
+        let (puzzle, solution) = (puzzle.as_ref(), solution.as_ref());
+        size += spend_size(&coin, puzzle.len() as u64, solution.len() as u64);
+
+        a.restore_checkpoint(&checkpoint);
+        let conds = run_puzzle::<MempoolVisitor>(
+            &mut a,
+            puzzle,
+            solution,
+            &coin.parent_coin_info,
+            coin.amount,
+            constants.max_block_cost_clvm,
+            MEMPOOL_MODE,
+        )?;
+        cost = cost.saturating_add(conds.cost);
+    }
+    Ok(cost.saturating_add(size.saturating_mul(constants.cost_per_byte)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use clvmr::{run_program, ChiaDialect};
     use hex_literal::hex;
+    use rstest::rstest;
 
     const PUZZLE1: [u8; 291] = hex!(
         "
@@ -220,6 +445,114 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_solution_generator_many_spends() {
+        // the generator assembled from the spends serialized in parallel must
+        // be identical to serializing the whole generator at once
+        let spends: Vec<(Coin, &[u8], &[u8])> = (0..500_u32)
+            .map(|i| {
+                let mut parent = [0_u8; 32];
+                parent[..4].copy_from_slice(&i.to_be_bytes());
+                let coin = Coin::new(parent.into(), [i as u8; 32].into(), i as u64 * 1000);
+                if i % 2 == 0 {
+                    (coin, PUZZLE1.as_ref(), SOLUTION1.as_ref())
+                } else {
+                    (coin, PUZZLE2.as_ref(), SOLUTION2.as_ref())
+                }
+            })
+            .collect();
+
+        let mut a = Allocator::new();
+        let generator = build_generator(&mut a, spends.clone()).expect("build_generator");
+        let expected = node_to_bytes(&a, generator).expect("node_to_bytes");
+
+        assert_eq!(
+            solution_generator(spends).expect("solution_generator"),
+            expected
+        );
+
+        let empty: [(Coin, &[u8], &[u8]); 0] = [];
+        let mut a = Allocator::new();
+        let generator = build_generator(&mut a, empty).expect("build_generator");
+        assert_eq!(
+            solution_generator(empty).expect("solution_generator"),
+            node_to_bytes(&a, generator).expect("node_to_bytes")
+        );
+    }
+
+    #[rstest]
+    #[case(0, 1)]
+    #[case(1, 1)]
+    #[case(0x7f, 1)]
+    #[case(0x80, 3)]
+    #[case(0xff, 3)]
+    #[case(0x100, 3)]
+    #[case(1750000000000, 7)]
+    #[case(18375000000000000000, 10)]
+    #[case(u64::MAX, 10)]
+    fn test_amount_size(#[case] amount: u64, #[case] expected: u64) {
+        let mut a = Allocator::new();
+        let atom = a.new_number(amount.into()).unwrap();
+        assert_eq!(node_to_bytes(&a, atom).unwrap().len() as u64, expected);
+        assert_eq!(amount_size(amount), expected);
+    }
+
+    #[test]
+    fn test_estimate_generator_size() {
+        let spends: Vec<(Coin, &[u8], &[u8])> = (0..100_u64)
+            .map(|i| {
+                let coin = Coin::new([i as u8; 32].into(), [1; 32].into(), i * 1_000_003);
+                if i % 10 == 0 {
+                    (coin, PUZZLE1.as_ref(), SOLUTION1.as_ref())
+                } else {
+                    (coin, PUZZLE2.as_ref(), SOLUTION2.as_ref())
+                }
+            })
+            .collect();
+
+        let (size, size_backrefs) = estimate_generator_size(spends.clone());
+        assert_eq!(
+            size,
+            solution_generator(spends.clone()).unwrap().len() as u64
+        );
+        // the repeated puzzles make up most of the generator, which is what
+        // back references save
+        let actual_backrefs = solution_generator_backrefs(spends).unwrap().len() as u64;
+        assert!(size_backrefs < size / 2);
+        assert!(actual_backrefs < size / 2);
+
+        let empty: [(Coin, &[u8], &[u8]); 0] = [];
+        assert_eq!(
+            estimate_generator_size(empty),
+            (
+                solution_generator(empty).unwrap().len() as u64,
+                solution_generator_backrefs(empty).unwrap().len() as u64
+            )
+        );
+    }
+
+    #[test]
+    fn test_estimate_cost() {
+        let coin = Coin::new([1; 32].into(), [2; 32].into(), 100);
+        let spends = [(coin, PUZZLE2.as_ref(), SOLUTION2.as_ref())];
+        let constants = &crate::consensus_constants::TEST_CONSTANTS;
+        let cost = estimate_cost(spends, constants).unwrap();
+
+        let mut a = Allocator::new();
+        let conds = run_puzzle::<MempoolVisitor>(
+            &mut a,
+            &PUZZLE2,
+            &SOLUTION2,
+            &coin.parent_coin_info,
+            coin.amount,
+            constants.max_block_cost_clvm,
+            MEMPOOL_MODE,
+        )
+        .unwrap();
+        let size = solution_generator(spends).unwrap().len() as u64;
+        assert_eq!(cost, conds.cost + size * constants.cost_per_byte);
+    }
+
     #[test]
     fn test_solution_generator_backre() {
         let coin1: Coin = Coin::new(
@@ -299,4 +632,100 @@ mod tests {
         let generator_output = run_generator(&result);
         assert_eq!(generator_output, EXPECTED_GENERATOR_OUTPUT);
     }
+
+    #[test]
+    fn test_build_block_generator_dedup() {
+        let coin1: Coin = Coin::new(
+            hex!("ccd5bb71183532bff220ba46c268991a00000000000000000000000000036840").into(),
+            hex!("fcc78a9e396df6ceebc217d2446bc016e0b3d5922fb32e5783ec5a85d490cfb6").into(),
+            1750000000000,
+        );
+        let coin2: Coin = Coin::new(
+            hex!("ccd5bb71183532bff220ba46c268991a00000000000000000000000000000000").into(),
+            hex!("d23da14695a188ae5708dd152263c4db883eb27edeb936178d4d988b8f3ce5fc").into(),
+            18375000000000000000,
+        );
+
+        // the second spend of coin2 is identical and has no AGG_SIG
+        // conditions, so it's deduplicated
+        let (generator, cost, included) = build_block_generator(
+            [
+                (coin2, PUZZLE2.as_ref(), SOLUTION2.as_ref()),
+                (coin1, PUZZLE1.as_ref(), SOLUTION1.as_ref()),
+                (coin2, PUZZLE2.as_ref(), SOLUTION2.as_ref()),
+            ],
+            11000000000,
+        )
+        .expect("build_block_generator");
+        assert_eq!(included, [0, 1]);
+        assert!(cost > 0);
+        assert_eq!(
+            generator,
+            solution_generator_backrefs([
+                (coin2, PUZZLE2.as_ref(), SOLUTION2.as_ref()),
+                (coin1, PUZZLE1.as_ref(), SOLUTION1.as_ref()),
+            ])
+            .expect("solution_generator_backrefs")
+        );
+
+        // coin1 has an AGG_SIG_ME condition, so spending it twice is a double
+        // spend, even if the spends are identical
+        assert_eq!(
+            build_block_generator(
+                [
+                    (coin1, PUZZLE1.as_ref(), SOLUTION1.as_ref()),
+                    (coin1, PUZZLE1.as_ref(), SOLUTION1.as_ref()),
+                ],
+                11000000000,
+            )
+            .unwrap_err()
+            .1,
+            ErrorCode::DoubleSpend
+        );
+    }
+
+    #[test]
+    fn test_build_block_generator_cost_limit() {
+        let coin1: Coin = Coin::new(
+            hex!("ccd5bb71183532bff220ba46c268991a00000000000000000000000000036840").into(),
+            hex!("fcc78a9e396df6ceebc217d2446bc016e0b3d5922fb32e5783ec5a85d490cfb6").into(),
+            1750000000000,
+        );
+        let coin2: Coin = Coin::new(
+            hex!("ccd5bb71183532bff220ba46c268991a00000000000000000000000000000000").into(),
+            hex!("d23da14695a188ae5708dd152263c4db883eb27edeb936178d4d988b8f3ce5fc").into(),
+            18375000000000000000,
+        );
+
+        let (_, cost, included) =
+            build_block_generator([(coin2, PUZZLE2.as_ref(), SOLUTION2.as_ref())], 11000000000)
+                .expect("build_block_generator");
+        assert_eq!(included, [0]);
+
+        // with a cost limit of exactly the first spend, the second spend is
+        // left out
+        let (generator, limited_cost, included) = build_block_generator(
+            [
+                (coin2, PUZZLE2.as_ref(), SOLUTION2.as_ref()),
+                (coin1, PUZZLE1.as_ref(), SOLUTION1.as_ref()),
+            ],
+            cost,
+        )
+        .expect("build_block_generator");
+        assert_eq!(included, [0]);
+        assert_eq!(limited_cost, cost);
+        assert_eq!(
+            generator,
+            solution_generator_backrefs([(coin2, PUZZLE2.as_ref(), SOLUTION2.as_ref())])
+                .expect("solution_generator_backrefs")
+        );
+
+        // if not even the first spend fits, we get an empty generator
+        let (generator, cost, included) =
+            build_block_generator([(coin2, PUZZLE2.as_ref(), SOLUTION2.as_ref())], 1000)
+                .expect("build_block_generator");
+        assert!(included.is_empty());
+        assert_eq!(cost, 0);
+        assert_eq!(generator, hex!("ff01ff8080"));
+    }
 }
@@ -0,0 +1,179 @@
+use crate::allocator::make_allocator;
+use crate::gen::conditions::{ParseState, SpendBundleConditions};
+use crate::gen::run_puzzle::run_spend_with_budget;
+use crate::gen::spend_visitor::SpendVisitor;
+use crate::gen::timings::ValidationTimings;
+use crate::gen::validation_error::ValidationErr;
+use crate::gen::validation_policy::ValidationPolicy;
+use chia_protocol::CoinSpend;
+use clvmr::allocator::{Allocator, Checkpoint};
+
+#[cfg(feature = "py-bindings")]
+use crate::gen::conditions::MempoolVisitor;
+#[cfg(feature = "py-bindings")]
+use crate::gen::owned_conditions::OwnedSpendBundleConditions;
+#[cfg(feature = "py-bindings")]
+use pyo3::prelude::*;
+
+// Runs the spends of a bundle one at a time, all of them in the same
+// allocator, rather than allocating a new heap for every run_puzzle() call.
+// The spends share the max_cost budget and the limits of the
+// ValidationPolicy, the same as with run_spend_bundle(). The conditions
+// returned by run_spend() point into the allocator, so they're only valid
+// until reset(), which frees everything (but keeps the memory for the next
+// bundle) and restores the full budget.
+#[cfg_attr(feature = "py-bindings", pyclass(module = "gold_rs"))]
+pub struct SpendContext {
+    a: Allocator,
+    empty: Checkpoint,
+    state: ParseState,
+    policy: ValidationPolicy,
+    max_cost: u64,
+    cost_left: u64,
+    flags: u32,
+}
+
+impl SpendContext {
+    pub fn new(max_cost: u64, flags: u32, policy: ValidationPolicy) -> Self {
+        let a = make_allocator(flags);
+        let empty = a.checkpoint();
+        Self {
+            a,
+            empty,
+            state: ParseState::new(policy),
+            policy,
+            max_cost,
+            cost_left: max_cost,
+            flags,
+        }
+    }
+
+    pub fn allocator(&self) -> &Allocator {
+        &self.a
+    }
+
+    // the part of max_cost not used by the spends run since the last reset()
+    pub fn cost_left(&self) -> u64 {
+        self.cost_left
+    }
+
+    pub fn run_spend<V: SpendVisitor>(
+        &mut self,
+        spend: &CoinSpend,
+    ) -> Result<SpendBundleConditions, ValidationErr> {
+        run_spend_with_budget::<V>(
+            &mut self.a,
+            &mut self.state,
+            spend,
+            &mut self.cost_left,
+            self.flags,
+            &mut ValidationTimings::default(),
+        )
+    }
+
+    pub fn reset(&mut self) {
+        self.a.restore_checkpoint(&self.empty);
+        self.state = ParseState::new(self.policy);
+        self.cost_left = self.max_cost;
+    }
+}
+
+#[cfg(feature = "py-bindings")]
+#[pymethods]
+impl SpendContext {
+    #[new]
+    #[pyo3(signature = (max_cost, flags, policy=None))]
+    fn py_new(max_cost: u64, flags: u32, policy: Option<ValidationPolicy>) -> Self {
+        Self::new(max_cost, flags, policy.unwrap_or_default())
+    }
+
+    #[getter]
+    #[pyo3(name = "cost_left")]
+    fn py_cost_left(&self) -> u64 {
+        self.cost_left()
+    }
+
+    // the conditions are copied out of the allocator, so unlike in rust, they
+    // stay valid after reset()
+    #[pyo3(name = "run_spend")]
+    fn py_run_spend(&mut self, spend: &CoinSpend) -> PyResult<OwnedSpendBundleConditions> {
+        let conds = self.run_spend::<MempoolVisitor>(spend)?;
+        Ok(OwnedSpendBundleConditions::from(&self.a, conds)?)
+    }
+
+    #[pyo3(name = "reset")]
+    fn py_reset(&mut self) {
+        self.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gen::conditions::MempoolVisitor;
+    use crate::gen::run_puzzle::run_spend_bundle;
+    use crate::gen::validation_error::ErrorCode;
+    use chia_bls::G2Element;
+    use chia_protocol::SpendBundle;
+    use chia_traits::Streamable;
+    use std::fs;
+
+    fn bundle() -> SpendBundle {
+        let mut spends = Vec::<CoinSpend>::new();
+        for name in ["e3c0", "bb13"] {
+            let spend_bytes = fs::read(format!("../../ff-tests/{name}.spend")).expect("read file");
+            spends.push(CoinSpend::from_bytes(&spend_bytes).expect("parse CoinSpend"));
+        }
+        SpendBundle::new(spends, G2Element::default())
+    }
+
+    #[test]
+    fn test_spend_context() {
+        let bundle = bundle();
+        let mut a = Allocator::new();
+        let expected = run_spend_bundle::<MempoolVisitor>(&mut a, &bundle, 11000000000, 0)
+            .expect("run_spend_bundle");
+
+        let mut ctx = SpendContext::new(11000000000, 0, ValidationPolicy::CONSENSUS);
+        for _ in 0..2 {
+            for (spend, expected) in bundle.coin_spends.iter().zip(&expected) {
+                let conds = ctx.run_spend::<MempoolVisitor>(spend).expect("run_spend");
+                assert_eq!(conds.cost, expected.cost);
+                assert_eq!(*conds.spends[0].coin_id, spend.coin.coin_id());
+            }
+            assert_eq!(
+                ctx.cost_left(),
+                11000000000 - expected[0].cost - expected[1].cost
+            );
+            // reset() frees the nodes of the spends, but keeps the memory
+            let pair_count = ctx.allocator().pair_count();
+            ctx.reset();
+            assert_eq!(ctx.cost_left(), 11000000000);
+            assert!(ctx.allocator().pair_count() < pair_count);
+        }
+    }
+
+    #[test]
+    fn test_spend_context_cost() {
+        let bundle = bundle();
+        let mut a = Allocator::new();
+        let expected = run_spend_bundle::<MempoolVisitor>(&mut a, &bundle, 11000000000, 0)
+            .expect("run_spend_bundle");
+
+        // the spends share the budget
+        let mut ctx = SpendContext::new(expected[0].cost + 1, 0, ValidationPolicy::CONSENSUS);
+        ctx.run_spend::<MempoolVisitor>(&bundle.coin_spends[0])
+            .expect("run_spend");
+        assert_eq!(ctx.cost_left(), 1);
+        assert_eq!(
+            ctx.run_spend::<MempoolVisitor>(&bundle.coin_spends[1])
+                .unwrap_err()
+                .1,
+            ErrorCode::CostExceeded
+        );
+
+        ctx.reset();
+        ctx.run_spend::<MempoolVisitor>(&bundle.coin_spends[0])
+            .expect("run_spend");
+    }
+}
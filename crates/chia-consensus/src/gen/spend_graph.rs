@@ -0,0 +1,454 @@
+use super::conditions::{parse_args, Condition};
+use super::flags::ALLOW_BACKREFS;
+use super::opcodes::{
+    parse_opcode, ASSERT_COIN_ANNOUNCEMENT, ASSERT_PUZZLE_ANNOUNCEMENT, CREATE_COIN_ANNOUNCEMENT,
+    CREATE_PUZZLE_ANNOUNCEMENT,
+};
+use super::owned_conditions::OwnedSpendBundleConditions;
+use super::validation_error::{first, next, rest, ErrorCode, ValidationErr};
+use chia_protocol::{Bytes32, Coin, SpendBundle};
+use clvmr::allocator::{Allocator, NodePtr};
+use clvmr::chia_dialect::ChiaDialect;
+use clvmr::reduction::Reduction;
+use clvmr::run_program::run_program;
+use clvmr::serde::{node_from_bytes, node_from_bytes_backrefs};
+use clvmr::sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+// An ASSERT_COIN_ANNOUNCEMENT or ASSERT_PUZZLE_ANNOUNCEMENT of a spend, and the
+// spend that created the announcement. creator is None if no spend in the
+// bundle did, which makes the bundle invalid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnnouncementEdge {
+    pub announcement_id: Bytes32,
+    pub puzzle_announcement: bool,
+    pub creator: Option<Bytes32>,
+    pub asserter: Bytes32,
+}
+
+fn announcement_id(origin: &[u8], msg: &[u8]) -> Bytes32 {
+    let mut hasher = Sha256::new();
+    hasher.update(origin);
+    hasher.update(msg);
+    let ret: [u8; 32] = hasher.finalize().into();
+    ret.into()
+}
+
+// the ID an ASSERT_COIN_ANNOUNCEMENT condition refers to, of the announcement
+// created by CREATE_COIN_ANNOUNCEMENT with message in the spend of coin_id
+pub fn coin_announcement_id(coin_id: &Bytes32, message: &[u8]) -> Bytes32 {
+    announcement_id(coin_id, message)
+}
+
+// the ID an ASSERT_PUZZLE_ANNOUNCEMENT condition refers to, of the announcement
+// created by CREATE_PUZZLE_ANNOUNCEMENT with message in the spend of a coin
+// with puzzle_hash
+pub fn puzzle_announcement_id(puzzle_hash: &Bytes32, message: &[u8]) -> Bytes32 {
+    announcement_id(puzzle_hash, message)
+}
+
+// Runs the spends of the bundle and links every announcement assertion to the
+// spend that created the announcement. This doesn't validate any other
+// conditions, it's meant for debugging bundles (that may well be invalid). The
+// spends share the max_cost budget, like in run_spend_bundle().
+pub fn announcement_edges(
+    a: &mut Allocator,
+    bundle: &SpendBundle,
+    max_cost: u64,
+    flags: u32,
+) -> Result<Vec<AnnouncementEdge>, ValidationErr> {
+    let deserialize = if (flags & ALLOW_BACKREFS) != 0 {
+        node_from_bytes_backrefs
+    } else {
+        node_from_bytes
+    };
+    let dialect = ChiaDialect::new(flags);
+
+    let mut spends = Vec::<(Coin, NodePtr)>::with_capacity(bundle.coin_spends.len());
+    let mut cost_left = max_cost;
+
+    for cs in &bundle.coin_spends {
+        let puzzle = deserialize(a, cs.puzzle_reveal.as_slice())?;
+        let solution = deserialize(a, cs.solution.as_slice())?;
+        let Reduction(cost, conditions) = run_program(a, &dialect, puzzle, solution, cost_left)?;
+        cost_left = cost_left
+            .checked_sub(cost)
+            .ok_or(ValidationErr(NodePtr::NIL, ErrorCode::CostExceeded))?;
+        spends.push((cs.coin, conditions));
+    }
+
+    link_announcements(a, &spends, flags)
+}
+
+// Like announcement_edges(), but for spends that have already been run. Every
+// spend is the coin and the list of conditions its puzzle returned. Only the
+// announcement conditions are parsed, and they have to be well formed.
+pub fn link_announcements(
+    a: &Allocator,
+    spends: &[(Coin, NodePtr)],
+    flags: u32,
+) -> Result<Vec<AnnouncementEdge>, ValidationErr> {
+    // announcement ID -> the coin ID of the spend creating it
+    let mut created = HashMap::<(Bytes32, bool), Bytes32>::new();
+    // (announcement ID, whether it's a puzzle announcement, asserting coin ID)
+    let mut asserted = Vec::<(Bytes32, bool, Bytes32)>::new();
+
+    for (coin, conditions) in spends {
+        let coin_id = coin.coin_id();
+        let mut iter = *conditions;
+        while let Some((c, next_cond)) = next(a, iter)? {
+            iter = next_cond;
+            let op = match parse_opcode(a, first(a, c)?, flags) {
+                Some(
+                    op @ (CREATE_COIN_ANNOUNCEMENT
+                    | CREATE_PUZZLE_ANNOUNCEMENT
+                    | ASSERT_COIN_ANNOUNCEMENT
+                    | ASSERT_PUZZLE_ANNOUNCEMENT),
+                ) => op,
+                _ => continue,
+            };
+            match parse_args(a, rest(a, c)?, op, flags)? {
+                Condition::CreateCoinAnnouncement(msg) => {
+                    let id = coin_announcement_id(&coin_id, a.atom(msg).as_ref());
+                    created.entry((id, false)).or_insert(coin_id);
+                }
+                Condition::CreatePuzzleAnnouncement(msg) => {
+                    let id = puzzle_announcement_id(&coin.puzzle_hash, a.atom(msg).as_ref());
+                    created.entry((id, true)).or_insert(coin_id);
+                }
+                Condition::AssertCoinAnnouncement(id) => {
+                    let id = a.atom(id).as_ref().try_into().unwrap();
+                    asserted.push((id, false, coin_id));
+                }
+                Condition::AssertPuzzleAnnouncement(id) => {
+                    let id = a.atom(id).as_ref().try_into().unwrap();
+                    asserted.push((id, true, coin_id));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(asserted
+        .into_iter()
+        .map(|(id, puzzle, asserter)| AnnouncementEdge {
+            announcement_id: id,
+            puzzle_announcement: puzzle,
+            creator: created.get(&(id, puzzle)).copied(),
+            asserter,
+        })
+        .collect())
+}
+
+// the first 8 hex digits, to keep the labels readable
+fn short(id: &Bytes32) -> String {
+    hex::encode(&id[..4])
+}
+
+// Renders the spends as a graph in the DOT language (of graphviz). Every spent
+// coin has an edge to the coins it creates, which are dashed unless they're
+// spent in the same bundle. Announcement assertions (see announcement_edges())
+// are dotted edges from the coin creating the announcement to the one
+// asserting it. Assertions no spend satisfies point from a red node instead.
+pub fn to_dot(conds: &OwnedSpendBundleConditions, announcements: &[AnnouncementEdge]) -> String {
+    let spent: HashSet<Bytes32> = conds.spends.iter().map(|s| s.coin_id).collect();
+    let mut ret = String::new();
+    ret.push_str("digraph spend_bundle {\n");
+    ret.push_str("  node [shape=box, fontname=\"monospace\"];\n");
+
+    for spend in &conds.spends {
+        writeln!(
+            ret,
+            "  \"{}\" [label=\"{}\\n{} mojos\"];",
+            spend.coin_id,
+            short(&spend.coin_id),
+            spend.coin_amount
+        )
+        .unwrap();
+    }
+    for spend in &conds.spends {
+        for (puzzle_hash, amount, _) in &spend.create_coin {
+            let coin_id = Coin::new(spend.coin_id, *puzzle_hash, *amount).coin_id();
+            // ephemeral coins already have a node
+            if !spent.contains(&coin_id) {
+                writeln!(
+                    ret,
+                    "  \"{coin_id}\" [label=\"{}\\n{amount} mojos\", style=dashed];",
+                    short(&coin_id)
+                )
+                .unwrap();
+            }
+            writeln!(ret, "  \"{}\" -> \"{coin_id}\";", spend.coin_id).unwrap();
+        }
+    }
+    for edge in announcements {
+        let kind = if edge.puzzle_announcement {
+            "puzzle"
+        } else {
+            "coin"
+        };
+        let creator = match edge.creator {
+            Some(creator) => creator.to_string(),
+            None => {
+                let node = format!("missing {}", edge.announcement_id);
+                writeln!(
+                    ret,
+                    "  \"{node}\" [label=\"missing {kind} announcement\\n{}\", color=red];",
+                    short(&edge.announcement_id)
+                )
+                .unwrap();
+                node
+            }
+        };
+        writeln!(
+            ret,
+            "  \"{creator}\" -> \"{}\" [style=dotted, label=\"{kind} announcement {}\"];",
+            edge.asserter,
+            short(&edge.announcement_id)
+        )
+        .unwrap();
+    }
+    ret.push_str("}\n");
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::allocator::make_allocator;
+    use crate::gen::conditions::MempoolVisitor;
+    use crate::gen::flags::MEMPOOL_MODE;
+    use crate::gen::run_puzzle::run_spend;
+    use chia_bls::Signature;
+    use chia_protocol::{CoinSpend, Program};
+    use clvm_traits::{clvm_list, ToClvm};
+    use clvm_utils::tree_hash_atom;
+    use clvmr::serde::node_to_bytes;
+    use clvmr::LIMIT_HEAP;
+
+    const MAX_COST: u64 = 11_000_000_000;
+
+    // a spend of the puzzle "1", which returns its solution as the conditions
+    fn spend(a: &mut Allocator, coin: Coin, conditions: impl ToClvm<NodePtr>) -> CoinSpend {
+        let conditions = conditions.to_clvm(a).unwrap();
+        let solution = node_to_bytes(a, conditions).unwrap();
+        CoinSpend::new(
+            coin,
+            Program::new(vec![1].into()),
+            Program::new(solution.into()),
+        )
+    }
+
+    fn coin(parent: Bytes32) -> Coin {
+        Coin::new(parent, tree_hash_atom(&[1]).into(), 123)
+    }
+
+    // the conditions of all spends, like for a block
+    fn conditions(a: &mut Allocator, bundle: &SpendBundle) -> OwnedSpendBundleConditions {
+        let mut spends = Vec::new();
+        for cs in &bundle.coin_spends {
+            let conds = run_spend::<MempoolVisitor>(a, cs, MAX_COST, MEMPOOL_MODE).unwrap();
+            spends.extend(OwnedSpendBundleConditions::from(a, conds).unwrap().spends);
+        }
+        OwnedSpendBundleConditions {
+            spends,
+            reserve_fee: 0,
+            height_absolute: 0,
+            seconds_absolute: 0,
+            before_height_absolute: None,
+            before_seconds_absolute: None,
+            agg_sig_unsafe: vec![],
+            cost: 0,
+            removal_amount: 0,
+            addition_amount: 0,
+        }
+    }
+
+    #[test]
+    fn test_announcement_edges() {
+        let mut a = make_allocator(LIMIT_HEAP);
+        let creator = spend(
+            &mut a,
+            coin(Bytes32::new([1; 32])),
+            clvm_list!(clvm_list!(60, "hello"), clvm_list!(62, "world")),
+        );
+        let creator_id = creator.coin.coin_id();
+        let coin_ann = coin_announcement_id(&creator_id, b"hello");
+        let puzzle_ann = puzzle_announcement_id(&creator.coin.puzzle_hash, b"world");
+        let missing = Bytes32::new([9; 32]);
+        let asserter = spend(
+            &mut a,
+            coin(Bytes32::new([2; 32])),
+            clvm_list!(
+                clvm_list!(61, coin_ann),
+                clvm_list!(63, puzzle_ann),
+                clvm_list!(61, missing)
+            ),
+        );
+        let asserter_id = asserter.coin.coin_id();
+        let bundle = SpendBundle::new(vec![creator.clone(), asserter], Signature::default());
+
+        let edges = announcement_edges(&mut a, &bundle, MAX_COST, MEMPOOL_MODE).unwrap();
+        assert_eq!(
+            edges,
+            [
+                AnnouncementEdge {
+                    announcement_id: coin_ann,
+                    puzzle_announcement: false,
+                    creator: Some(creator_id),
+                    asserter: asserter_id,
+                },
+                AnnouncementEdge {
+                    announcement_id: puzzle_ann,
+                    puzzle_announcement: true,
+                    creator: Some(creator_id),
+                    asserter: asserter_id,
+                },
+                AnnouncementEdge {
+                    announcement_id: missing,
+                    puzzle_announcement: false,
+                    creator: None,
+                    asserter: asserter_id,
+                },
+            ]
+        );
+
+        // a coin announcement doesn't satisfy a puzzle announcement assertion
+        let wrong_kind = spend(
+            &mut a,
+            coin(Bytes32::new([3; 32])),
+            clvm_list!(clvm_list!(63, coin_ann)),
+        );
+        let bundle = SpendBundle::new(vec![creator, wrong_kind], Signature::default());
+        let edges = announcement_edges(&mut a, &bundle, MAX_COST, MEMPOOL_MODE).unwrap();
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].creator, None);
+
+        assert_eq!(
+            announcement_edges(&mut a, &bundle, 1, MEMPOOL_MODE)
+                .unwrap_err()
+                .1,
+            ErrorCode::CostExceeded
+        );
+    }
+
+    #[test]
+    fn test_announcement_ids() {
+        let coin_id = Bytes32::new([1; 32]);
+        let mut hasher = Sha256::new();
+        hasher.update([1; 32]);
+        hasher.update(b"msg");
+        let expected: [u8; 32] = hasher.finalize().into();
+        assert_eq!(coin_announcement_id(&coin_id, b"msg"), expected.into());
+        // the ID only depends on the bytes, not on the kind of announcement
+        assert_eq!(
+            puzzle_announcement_id(&coin_id, b"msg"),
+            coin_announcement_id(&coin_id, b"msg")
+        );
+        assert_ne!(
+            coin_announcement_id(&coin_id, b""),
+            coin_announcement_id(&coin_id, b"msg")
+        );
+    }
+
+    #[test]
+    fn test_link_announcements() {
+        let mut a = make_allocator(LIMIT_HEAP);
+        let creator = coin(Bytes32::new([1; 32]));
+        let asserter = coin(Bytes32::new([2; 32]));
+        let ann = puzzle_announcement_id(&creator.puzzle_hash, b"hi");
+        let spends = [
+            (
+                creator,
+                clvm_list!(clvm_list!(62, "hi"), clvm_list!(51, creator.puzzle_hash, 1))
+                    .to_clvm(&mut a)
+                    .unwrap(),
+            ),
+            (
+                asserter,
+                clvm_list!(clvm_list!(63, ann), clvm_list!(61, ann))
+                    .to_clvm(&mut a)
+                    .unwrap(),
+            ),
+        ];
+        let edges = link_announcements(&a, &spends, 0).unwrap();
+        assert_eq!(
+            edges,
+            [
+                AnnouncementEdge {
+                    announcement_id: ann,
+                    puzzle_announcement: true,
+                    creator: Some(creator.coin_id()),
+                    asserter: asserter.coin_id(),
+                },
+                AnnouncementEdge {
+                    announcement_id: ann,
+                    puzzle_announcement: false,
+                    creator: None,
+                    asserter: asserter.coin_id(),
+                },
+            ]
+        );
+
+        // an assertion that's not a 32 byte hash
+        let invalid = [(
+            asserter,
+            clvm_list!(clvm_list!(61, "hi")).to_clvm(&mut a).unwrap(),
+        )];
+        assert_eq!(
+            link_announcements(&a, &invalid, 0).unwrap_err().1,
+            ErrorCode::AssertCoinAnnouncementFailed
+        );
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let mut a = make_allocator(LIMIT_HEAP);
+        let other_ph = Bytes32::new([2; 32]);
+        let parent_coin = coin(Bytes32::new([1; 32]));
+        let parent_id = parent_coin.coin_id();
+        // the child coin is spent in the same bundle, so it's ephemeral
+        let child_coin = coin(parent_id);
+        let child_id = child_coin.coin_id();
+        let created = Coin::new(parent_id, other_ph, 7).coin_id();
+        let missing = Bytes32::new([9; 32]);
+
+        let parent = spend(
+            &mut a,
+            parent_coin,
+            clvm_list!(
+                clvm_list!(51, child_coin.puzzle_hash, 123),
+                clvm_list!(51, other_ph, 7),
+                clvm_list!(60, "hi")
+            ),
+        );
+        let child = spend(
+            &mut a,
+            child_coin,
+            clvm_list!(
+                clvm_list!(61, coin_announcement_id(&parent_id, b"hi")),
+                clvm_list!(61, missing)
+            ),
+        );
+        let bundle = SpendBundle::new(vec![parent, child], Signature::default());
+        let conds = conditions(&mut a, &bundle);
+        let edges = announcement_edges(&mut a, &bundle, MAX_COST, MEMPOOL_MODE).unwrap();
+
+        let dot = to_dot(&conds, &edges);
+        assert!(dot.starts_with("digraph spend_bundle {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains(&format!("\"{parent_id}\" -> \"{child_id}\";")));
+        assert!(dot.contains(&format!("\"{parent_id}\" -> \"{created}\";")));
+        assert!(dot.contains(&format!(
+            "\"{created}\" [label=\"{}\\n7 mojos\", style=dashed];",
+            short(&created)
+        )));
+        // the ephemeral coin is only declared once, as a spent coin
+        assert_eq!(dot.matches(&format!("\"{child_id}\" [")).count(), 1);
+        assert!(dot.contains(&format!("\"{parent_id}\" -> \"{child_id}\" [style=dotted")));
+        assert!(dot.contains(&format!("\"missing {missing}\" [")));
+
+        assert!(!to_dot(&conds, &[]).contains("dotted"));
+    }
+}
@@ -0,0 +1,48 @@
+use std::time::{Duration, Instant};
+
+// The time spent in the steps of validating a block generator or a spend
+// bundle, for monitoring. Steps that weren't performed are zero. When
+// running a block generator, the puzzles run as part of the generator, so the
+// clvm time includes them
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationTimings {
+    // parsing the serialized CLVM programs
+    pub deserialize: Duration,
+    // running CLVM
+    pub clvm: Duration,
+    // parsing and validating the conditions
+    pub parse_conditions: Duration,
+    // computing the messages to sign and verifying the aggregate signature
+    pub signature: Duration,
+}
+
+impl ValidationTimings {
+    pub fn total(&self) -> Duration {
+        self.deserialize + self.clvm + self.parse_conditions + self.signature
+    }
+}
+
+// calls f() and adds the time it took to total
+pub(crate) fn timed<T>(total: &mut Duration, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let ret = f();
+    *total += start.elapsed();
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timed() {
+        let mut timings = ValidationTimings::default();
+        assert_eq!(timed(&mut timings.clvm, || 1 + 1), 2);
+        timed(&mut timings.clvm, || {
+            std::thread::sleep(Duration::from_millis(2))
+        });
+        assert!(timings.clvm >= Duration::from_millis(2));
+        assert_eq!(timings.deserialize, Duration::ZERO);
+        assert_eq!(timings.total(), timings.clvm);
+    }
+}
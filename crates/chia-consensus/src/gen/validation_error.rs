@@ -163,6 +163,9 @@ pub enum ErrorCode {
     InvalidMessageMode,
     InvalidCoinId,
     MessageNotSentOrReceived,
+    TooManyCreateCoins,
+    TooManyConditions,
+    OutOfMemory,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
@@ -179,6 +182,17 @@ impl From<EvalErr> for ValidationErr {
     }
 }
 
+// converts the error of running a program. Like From<EvalErr>, except that
+// with report_oom, running out of heap fails with OutOfMemory rather than
+// GeneratorRuntimeError
+pub fn eval_err(err: EvalErr, report_oom: bool) -> ValidationErr {
+    if report_oom && err.1 == "out of memory" {
+        ValidationErr(err.0, ErrorCode::OutOfMemory)
+    } else {
+        err.into()
+    }
+}
+
 impl From<std::io::Error> for ValidationErr {
     fn from(_: std::io::Error) -> Self {
         ValidationErr(NodePtr::NIL, ErrorCode::GeneratorRuntimeError)
@@ -358,6 +372,9 @@ impl From<ErrorCode> for u32 {
             ErrorCode::InvalidMessageMode => 145,
             ErrorCode::InvalidCoinId => 146,
             ErrorCode::MessageNotSentOrReceived => 147,
+            ErrorCode::TooManyCreateCoins => 148,
+            ErrorCode::TooManyConditions => 149,
+            ErrorCode::OutOfMemory => 150,
         }
     }
 }
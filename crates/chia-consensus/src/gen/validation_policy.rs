@@ -0,0 +1,63 @@
+use crate::gen::conditions::MAX_ANNOUNCEMENTS_PER_SPEND;
+
+#[cfg(feature = "py-bindings")]
+use pyo3::prelude::*;
+
+// Limits on the number of conditions spends may have. The default policy is
+// the consensus rules, which mempool mode enforces as well. A stricter policy
+// can be used (e.g. by a wallet) to reject spends with the same condition
+// parser the full node uses.
+#[cfg_attr(feature = "py-bindings", pyclass(module = "gold_rs", frozen, get_all))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ValidationPolicy {
+    // the max number of CREATE_COIN conditions of a single spend
+    pub max_create_coin_per_spend: u32,
+
+    // the max number of announcement and message conditions (creating or
+    // asserting) of a single spend
+    pub max_announcements_per_spend: u32,
+
+    // the max number of conditions, across all spends. Unknown conditions
+    // that are ignored aren't counted
+    pub max_conditions: u32,
+}
+
+impl ValidationPolicy {
+    pub const CONSENSUS: ValidationPolicy = ValidationPolicy {
+        max_create_coin_per_spend: u32::MAX,
+        max_announcements_per_spend: MAX_ANNOUNCEMENTS_PER_SPEND,
+        max_conditions: u32::MAX,
+    };
+}
+
+impl Default for ValidationPolicy {
+    fn default() -> Self {
+        Self::CONSENSUS
+    }
+}
+
+#[cfg(feature = "py-bindings")]
+#[pymethods]
+impl ValidationPolicy {
+    #[new]
+    #[pyo3(signature = (
+        max_create_coin_per_spend=ValidationPolicy::CONSENSUS.max_create_coin_per_spend,
+        max_announcements_per_spend=ValidationPolicy::CONSENSUS.max_announcements_per_spend,
+        max_conditions=ValidationPolicy::CONSENSUS.max_conditions,
+    ))]
+    fn py_new(
+        max_create_coin_per_spend: u32,
+        max_announcements_per_spend: u32,
+        max_conditions: u32,
+    ) -> Self {
+        Self {
+            max_create_coin_per_spend,
+            max_announcements_per_spend,
+            max_conditions,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self:?}")
+    }
+}
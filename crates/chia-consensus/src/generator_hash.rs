@@ -0,0 +1,138 @@
+use crate::gen::validation_error::{ErrorCode, ValidationErr};
+use chia_protocol::{Bytes32, Program, TransactionsInfo};
+use clvmr::allocator::NodePtr;
+use clvmr::sha2::{Digest, Sha256};
+use std::io::{self, Read};
+
+// the generator_root of a block without a transactions generator
+pub const EMPTY_GENERATOR_ROOT: Bytes32 = Bytes32::new([0; 32]);
+
+// the generator_refs_root of a block without generator references
+pub const EMPTY_GENERATOR_REFS_ROOT: Bytes32 = Bytes32::new([1; 32]);
+
+// the generator_root is the sha256 of the serialized generator
+pub fn generator_root(generator: Option<&[u8]>) -> Bytes32 {
+    let Some(generator) = generator else {
+        return EMPTY_GENERATOR_ROOT;
+    };
+    let mut hasher = Sha256::new();
+    hasher.update(generator);
+    let ret: [u8; 32] = hasher.finalize().into();
+    ret.into()
+}
+
+// computes the generator_root of a generator read from the reader (e.g. a
+// file or a database blob), in fixed size chunks. This avoids having to load a
+// maximum size generator into memory just to check its hash
+pub fn generator_root_from_reader<R: Read>(reader: &mut R) -> io::Result<Bytes32> {
+    let mut hasher = Sha256::new();
+    let mut buf = [0_u8; 65536];
+    loop {
+        let len = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(len) => len,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        hasher.update(&buf[..len]);
+    }
+    let ret: [u8; 32] = hasher.finalize().into();
+    Ok(ret.into())
+}
+
+// the generator_refs_root is the sha256 of the concatenation of the (big
+// endian) heights in the ref list. They're fed to the hasher one at a time
+pub fn generator_refs_root(refs: &[u32]) -> Bytes32 {
+    if refs.is_empty() {
+        return EMPTY_GENERATOR_REFS_ROOT;
+    }
+    let mut hasher = Sha256::new();
+    for height in refs {
+        hasher.update(height.to_be_bytes());
+    }
+    let ret: [u8; 32] = hasher.finalize().into();
+    ret.into()
+}
+
+// checks the generator_root and generator_refs_root of a transaction block
+// against its generator and ref list
+pub fn validate_generator_hashes(
+    info: &TransactionsInfo,
+    generator: Option<&Program>,
+    refs: &[u32],
+) -> Result<(), ValidationErr> {
+    if info.generator_root != generator_root(generator.map(Program::as_slice)) {
+        return Err(ValidationErr(
+            NodePtr::NIL,
+            ErrorCode::InvalidTransactionsGeneratorHash,
+        ));
+    }
+    if info.generator_refs_root != generator_refs_root(refs) {
+        return Err(ValidationErr(
+            NodePtr::NIL,
+            ErrorCode::InvalidTransactionsGeneratorRefsRoot,
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chia_bls::G2Element;
+    use hex_literal::hex;
+
+    #[test]
+    fn test_generator_root() {
+        assert_eq!(generator_root(None), EMPTY_GENERATOR_ROOT);
+        // sha256("abc")
+        let expected = Bytes32::new(hex!(
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        ));
+        assert_eq!(generator_root(Some(&b"abc"[..])), expected);
+
+        let mut reader: &[u8] = b"abc";
+        assert_eq!(generator_root_from_reader(&mut reader).unwrap(), expected);
+
+        // larger than the chunk size
+        let large = vec![0x42_u8; 200_000];
+        let mut reader: &[u8] = &large;
+        assert_eq!(
+            generator_root_from_reader(&mut reader).unwrap(),
+            generator_root(Some(large.as_slice()))
+        );
+    }
+
+    #[test]
+    fn test_generator_refs_root() {
+        assert_eq!(generator_refs_root(&[]), EMPTY_GENERATOR_REFS_ROOT);
+        let mut hasher = Sha256::new();
+        hasher.update([0_u8, 0, 0, 1, 0, 1, 0, 0]);
+        let expected: [u8; 32] = hasher.finalize().into();
+        assert_eq!(generator_refs_root(&[1, 0x10000]), expected.into());
+    }
+
+    #[test]
+    fn test_validate_generator_hashes() {
+        let generator = Program::from(vec![0xff, 0x01, 0x80]);
+        let info = TransactionsInfo::new(
+            generator_root(Some(generator.as_slice())),
+            generator_refs_root(&[5]),
+            G2Element::default(),
+            0,
+            0,
+            vec![],
+        );
+        assert!(validate_generator_hashes(&info, Some(&generator), &[5]).is_ok());
+        assert_eq!(
+            validate_generator_hashes(&info, None, &[5]).unwrap_err().1,
+            ErrorCode::InvalidTransactionsGeneratorHash
+        );
+        assert_eq!(
+            validate_generator_hashes(&info, Some(&generator), &[])
+                .unwrap_err()
+                .1,
+            ErrorCode::InvalidTransactionsGeneratorRefsRoot
+        );
+    }
+}
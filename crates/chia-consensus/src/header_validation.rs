@@ -0,0 +1,1320 @@
+use crate::consensus_constants::ConsensusConstants;
+use crate::error::Error;
+use crate::fork_schedule::ForkSchedule;
+use crate::gen::validation_error::{ErrorCode, ValidationErr};
+use crate::pot_iterations::{calculate_ip_iters, is_overflow_block};
+use crate::proof_of_space::{
+    calculate_pos_challenge, get_plot_id, passes_plot_filter, signage_point_hash,
+};
+use chia_bls::{verify, PublicKey};
+use chia_protocol::{
+    BlockRecord, Bytes32, HeaderBlock, RewardChainBlockUnfinished, UnfinishedHeaderBlock,
+};
+use chia_traits::Streamable;
+use clvmr::allocator::NodePtr;
+use rayon::prelude::*;
+
+#[cfg(feature = "py-bindings")]
+use pyo3::types::PyDict;
+#[cfg(feature = "py-bindings")]
+use pyo3::{basic::CompareOp, prelude::*};
+
+fn err(code: ErrorCode) -> Error {
+    Error::Validation(ValidationErr(NodePtr::NIL, code))
+}
+
+// walks back from block (inclusive) to the first block stop() is true for,
+// or the genesis block
+fn find_block<E, F>(
+    block: &BlockRecord,
+    lookup: &mut F,
+    stop: impl Fn(&BlockRecord) -> bool,
+) -> Result<BlockRecord, E>
+where
+    E: From<Error>,
+    F: FnMut(&Bytes32) -> Result<Option<BlockRecord>, E>,
+{
+    let mut curr = block.clone();
+    while !stop(&curr) && curr.height > 0 {
+        curr = lookup(&curr.prev_hash)?.ok_or(Error::BlockNotFound(curr.prev_hash))?;
+    }
+    Ok(curr)
+}
+
+// The challenge and the number of iterations of the infused challenge chain
+// end of slot VDF, that the sub-slot at index n of the block's finished
+// sub-slots is expected to have. None if the sub-slot must not have an
+// infused challenge chain, which is the case once a challenge block's deficit
+// has been filled.
+fn expected_icc<E, F>(
+    header: &UnfinishedHeaderBlock,
+    n: usize,
+    prev: &BlockRecord,
+    constants: &ConsensusConstants,
+    lookup: &mut F,
+) -> Result<Option<(Bytes32, u64)>, E>
+where
+    E: From<Error>,
+    F: FnMut(&Bytes32) -> Result<Option<BlockRecord>, E>,
+{
+    let min_blocks = constants.min_blocks_per_challenge_block;
+    if prev.deficit >= min_blocks {
+        return Ok(None);
+    }
+
+    if n > 0 {
+        // an empty sub-slot continues the infused challenge chain of the
+        // previous sub-slot, as long as its deficit is below the limit
+        let prev_slot = &header.finished_sub_slots[n - 1];
+        if prev_slot.reward_chain.deficit >= min_blocks {
+            return Ok(None);
+        }
+        let Some(icc) = &prev_slot.infused_challenge_chain else {
+            return Err(err(ErrorCode::ShouldHaveIcc).into());
+        };
+        return Ok(Some((icc.hash().into(), prev.sub_slot_iters)));
+    }
+
+    // the first sub-slot after prev continues the infused challenge chain
+    // from the last challenge block, or from the start of prev's sub-slot
+    let curr = find_block(prev, lookup, |b| {
+        b.is_challenge_block(min_blocks) || b.first_in_sub_slot()
+    })?;
+    if curr.is_challenge_block(min_blocks) {
+        let ip_iters = calculate_ip_iters(
+            constants,
+            curr.sub_slot_iters,
+            curr.signage_point_index,
+            curr.required_iters,
+        )?;
+        let iters = prev
+            .sub_slot_iters
+            .checked_sub(ip_iters)
+            .ok_or(err(ErrorCode::InvalidIccEosVdf))?;
+        Ok(Some((curr.challenge_block_info_hash, iters)))
+    } else {
+        let hash = curr
+            .finished_infused_challenge_slot_hashes
+            .as_ref()
+            .and_then(|hashes| hashes.last())
+            .ok_or(err(ErrorCode::InvalidIccEosVdf))?;
+        Ok(Some((*hash, prev.sub_slot_iters)))
+    }
+}
+
+// Validates that the finished sub-slots of a block link up with the chain it
+// extends, following steps 1 and 2 of chia's header validation:
+// - the block's previous hash is prev (or the genesis challenge)
+// - the challenge chain and reward chain end of slot VDFs continue from the
+//   previous sub-slot, or from prev for the first one
+// - each sub-slot commits to its own challenge chain (and infused challenge
+//   chain) hash
+// - the infused challenge chain is present exactly while the deficit of a
+//   challenge block is being filled, and continues from that challenge block
+// - the deficit of every sub-slot follows from prev's deficit
+// - only the first sub-slot may include a sub-epoch summary, and new
+//   difficulty and sub-slot iterations only come with one.
+// prev is None for the genesis block. lookup returns the block record with the
+// specified header hash, or None if it's not known. It's used to walk back from
+// prev to the start of its sub-slot (or the last challenge block), so all of
+// those blocks must be available.
+// This doesn't validate the VDF proofs, the number of iterations of the
+// challenge chain and reward chain VDFs, or the contents of sub-epoch
+// summaries, which depend on the difficulty adjustments.
+pub fn validate_unfinished_header_block_linkage<E, F>(
+    header: &UnfinishedHeaderBlock,
+    prev: Option<&BlockRecord>,
+    constants: &ConsensusConstants,
+    mut lookup: F,
+) -> Result<(), E>
+where
+    E: From<Error>,
+    F: FnMut(&Bytes32) -> Result<Option<BlockRecord>, E>,
+{
+    let expected_prev_hash = prev.map_or(constants.genesis_challenge, |b| b.header_hash);
+    if header.prev_header_hash() != expected_prev_hash {
+        return Err(err(ErrorCode::InvalidPrevBlockHash).into());
+    }
+
+    let min_blocks = constants.min_blocks_per_challenge_block;
+    let slots = &header.finished_sub_slots;
+    for (n, sub_slot) in slots.iter().enumerate() {
+        let cc = &sub_slot.challenge_chain;
+        let rc = &sub_slot.reward_chain;
+
+        // the challenge chain continues from the end of the previous sub-slot
+        let cc_challenge: Bytes32 = if n > 0 {
+            slots[n - 1].challenge_chain.hash().into()
+        } else if let Some(prev) = prev {
+            let curr = find_block(prev, &mut lookup, BlockRecord::first_in_sub_slot)?;
+            match &curr.finished_challenge_slot_hashes {
+                Some(hashes) => *hashes
+                    .last()
+                    .ok_or(err(ErrorCode::InvalidPrevChallengeSlotHash))?,
+                None => constants.genesis_challenge,
+            }
+        } else {
+            constants.genesis_challenge
+        };
+        if cc.challenge_chain_end_of_slot_vdf.challenge != cc_challenge {
+            return Err(err(ErrorCode::InvalidPrevChallengeSlotHash).into());
+        }
+
+        if let Some(prev) = prev {
+            let icc = expected_icc(header, n, prev, constants, &mut lookup)?;
+            match (&sub_slot.infused_challenge_chain, icc) {
+                (None, None) => {
+                    if cc.infused_challenge_chain_sub_slot_hash.is_some() {
+                        return Err(err(ErrorCode::InvalidIccHashCc).into());
+                    }
+                    if rc.infused_challenge_chain_sub_slot_hash.is_some() {
+                        return Err(err(ErrorCode::InvalidIccHashRc).into());
+                    }
+                }
+                (Some(_), None) => return Err(err(ErrorCode::ShouldNotHaveIcc).into()),
+                (None, Some(_)) => return Err(err(ErrorCode::ShouldHaveIcc).into()),
+                (Some(icc), Some((challenge, iters))) => {
+                    let vdf = &icc.infused_challenge_chain_end_of_slot_vdf;
+                    if vdf.challenge != challenge || vdf.number_of_iterations != iters {
+                        return Err(err(ErrorCode::InvalidIccEosVdf).into());
+                    }
+                    // the challenge chain only includes the infused challenge
+                    // chain once the deficit is filled
+                    let icc_hash: Bytes32 = icc.hash().into();
+                    let expected = (rc.deficit == min_blocks).then_some(icc_hash);
+                    if cc.infused_challenge_chain_sub_slot_hash != expected {
+                        return Err(err(ErrorCode::InvalidIccHashCc).into());
+                    }
+                    if rc.infused_challenge_chain_sub_slot_hash != Some(icc_hash) {
+                        return Err(err(ErrorCode::InvalidIccHashRc).into());
+                    }
+                }
+            }
+        } else if sub_slot.infused_challenge_chain.is_some() {
+            return Err(err(ErrorCode::ShouldNotHaveIcc).into());
+        }
+
+        if cc.subepoch_summary_hash.is_some() {
+            if n > 0 {
+                return Err(err(ErrorCode::InvalidSubEpochSummaryHash).into());
+            }
+        } else {
+            if cc.new_sub_slot_iters.is_some() {
+                return Err(err(ErrorCode::InvalidNewSubSlotIters).into());
+            }
+            if cc.new_difficulty.is_some() {
+                return Err(err(ErrorCode::InvalidNewDifficulty).into());
+            }
+        }
+
+        if rc.challenge_chain_sub_slot_hash != Bytes32::from(cc.hash()) {
+            return Err(err(ErrorCode::InvalidChallengeSlotHashRc).into());
+        }
+
+        // the reward chain continues from prev's infusion, or from the end of
+        // the previous sub-slot
+        let rc_challenge: Bytes32 = if n > 0 {
+            slots[n - 1].reward_chain.hash().into()
+        } else {
+            prev.map_or(constants.genesis_challenge, |b| {
+                b.reward_infusion_new_challenge
+            })
+        };
+        if rc.end_of_slot_vdf.challenge != rc_challenge {
+            return Err(err(ErrorCode::InvalidRcEosVdf).into());
+        }
+
+        // the deficit is reset once it reaches 0, otherwise it stays the same
+        // until the next block
+        let deficit = match prev {
+            Some(prev) if prev.deficit > 0 => prev.deficit,
+            _ => min_blocks,
+        };
+        if rc.deficit != deficit {
+            return Err(err(ErrorCode::InvalidDeficit).into());
+        }
+    }
+    Ok(())
+}
+
+// The challenge chain and reward chain challenges of the sub-slot prev is
+// infused in, which is the sub-slot of the following block unless that one
+// finishes sub-slots of its own. These are the hashes of the last finished
+// sub-slot, or the genesis challenge if there is none (yet).
+pub fn sub_slot_challenges<E, F>(
+    prev: Option<&BlockRecord>,
+    constants: &ConsensusConstants,
+    mut lookup: F,
+) -> Result<(Bytes32, Bytes32), E>
+where
+    E: From<Error>,
+    F: FnMut(&Bytes32) -> Result<Option<BlockRecord>, E>,
+{
+    let Some(prev) = prev else {
+        return Ok((constants.genesis_challenge, constants.genesis_challenge));
+    };
+    let curr = find_block(prev, &mut lookup, BlockRecord::first_in_sub_slot)?;
+    let cc = match &curr.finished_challenge_slot_hashes {
+        Some(hashes) => *hashes.last().ok_or(err(ErrorCode::InvalidCcSignature))?,
+        None => constants.genesis_challenge,
+    };
+    let rc = match &curr.finished_reward_slot_hashes {
+        Some(hashes) => *hashes.last().ok_or(err(ErrorCode::InvalidRcSignature))?,
+        None => constants.genesis_challenge,
+    };
+    Ok((cc, rc))
+}
+
+// verifies the signage point signatures of a block in the sub-slot with the
+// specified challenges
+fn check_sp_signatures(
+    rcb: &RewardChainBlockUnfinished,
+    plot_key: &PublicKey,
+    cc_challenge: &Bytes32,
+    rc_challenge: &Bytes32,
+) -> Result<(), ValidationErr> {
+    let cc_sp_hash = signage_point_hash(cc_challenge, rcb.challenge_chain_sp_vdf.as_ref());
+    if !verify(&rcb.challenge_chain_sp_signature, plot_key, cc_sp_hash) {
+        return Err(ValidationErr(NodePtr::NIL, ErrorCode::InvalidCcSignature));
+    }
+    let rc_sp_hash = signage_point_hash(rc_challenge, rcb.reward_chain_sp_vdf.as_ref());
+    if !verify(&rcb.reward_chain_sp_signature, plot_key, rc_sp_hash) {
+        return Err(ValidationErr(NodePtr::NIL, ErrorCode::InvalidRcSignature));
+    }
+    Ok(())
+}
+
+// Validates the challenge chain and reward chain signage point signatures of
+// a contiguous range of headers. Both are signed by the plot key of the
+// proof of space, over the hash of the signage point VDF output, or for the
+// first signage point of a sub-slot, over the sub-slot's challenge.
+// challenges are the challenge chain and reward chain challenges of the
+// sub-slot the first header starts in (unless it finishes sub-slots), see
+// sub_slot_challenges(). They're updated by the sub-slots finished along the
+// way. The signatures are verified in parallel, but the error is the one of
+// the first header that fails.
+pub fn validate_sp_signatures(
+    headers: &[UnfinishedHeaderBlock],
+    challenges: (Bytes32, Bytes32),
+) -> Result<(), ValidationErr> {
+    let (mut cc_challenge, mut rc_challenge) = challenges;
+    let mut checks = Vec::with_capacity(headers.len());
+    for header in headers {
+        if let Some(slot) = header.finished_sub_slots.last() {
+            cc_challenge = slot.challenge_chain.hash().into();
+            rc_challenge = slot.reward_chain.hash().into();
+        }
+        checks.push((header, cc_challenge, rc_challenge));
+    }
+
+    let results: Vec<Result<(), ValidationErr>> = checks
+        .par_iter()
+        .map(|(header, cc_challenge, rc_challenge)| {
+            let rcb = &header.reward_chain_block;
+            let plot_key = rcb.proof_of_space.plot_public_key();
+            check_sp_signatures(rcb, &plot_key, cc_challenge, rc_challenge)
+        })
+        .collect();
+    results.into_iter().collect()
+}
+
+// A pre-check of a proof of space, with the checks that don't need the proof
+// itself: that it's from a plot of a valid size and plot ID, that its
+// challenge is derived from the signage point the block claims, and that the
+// plot passes the plot filter for it. The proof is NOT verified and no quality
+// string is derived from it, so a header with a made up proof passes. It must
+// still be verified by a proof of space verifier.
+fn precheck_proof_of_space(
+    rcb: &RewardChainBlockUnfinished,
+    constants: &ConsensusConstants,
+    filter_bits: u8,
+) -> Result<(), ValidationErr> {
+    let pos = &rcb.proof_of_space;
+    if pos.size < constants.min_plot_size || pos.size > constants.max_plot_size {
+        return Err(ValidationErr(NodePtr::NIL, ErrorCode::InvalidPospace));
+    }
+    let Some(plot_id) = get_plot_id(pos) else {
+        return Err(ValidationErr(NodePtr::NIL, ErrorCode::InvalidPospace));
+    };
+    let challenge = &rcb.pos_ss_cc_challenge_hash;
+    let sp_hash = signage_point_hash(challenge, rcb.challenge_chain_sp_vdf.as_ref());
+    if pos.challenge != calculate_pos_challenge(&plot_id, challenge, &sp_hash) {
+        return Err(ValidationErr(
+            NodePtr::NIL,
+            ErrorCode::InvalidPospaceChallenge,
+        ));
+    }
+    if !passes_plot_filter(filter_bits, &plot_id, challenge, &sp_hash) {
+        return Err(ValidationErr(NodePtr::NIL, ErrorCode::InvalidPospace));
+    }
+    Ok(())
+}
+
+// The state of the chain precheck_header_blocks() needs to validate the next
+// header. It's returned by every call, to pass in with the following range of
+// headers.
+#[cfg_attr(feature = "py-bindings", pyclass(module = "gold_rs", frozen, get_all))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderContext {
+    // the last block. Before the genesis block, the header hash is the
+    // genesis challenge and the height is None
+    pub header_hash: Bytes32,
+    pub height: Option<u32>,
+    pub weight: u128,
+    pub total_iters: u128,
+    // the challenge chain and reward chain challenges of the sub-slot the last
+    // block is in, and the challenge chain challenge of the sub-slot before it
+    pub cc_challenge: Bytes32,
+    pub rc_challenge: Bytes32,
+    pub prev_cc_challenge: Bytes32,
+}
+
+impl HeaderContext {
+    pub fn genesis(constants: &ConsensusConstants) -> Self {
+        Self {
+            header_hash: constants.genesis_challenge,
+            height: None,
+            weight: 0,
+            total_iters: 0,
+            cc_challenge: constants.genesis_challenge,
+            rc_challenge: constants.genesis_challenge,
+            prev_cc_challenge: constants.genesis_challenge,
+        }
+    }
+
+    // the context following prev. Like with sub_slot_challenges(), lookup is
+    // used to walk back to the start of prev's sub-slot, and here also to the
+    // start of the sub-slot before it, unless prev finished both
+    pub fn from_prev<E, F>(
+        prev: &BlockRecord,
+        constants: &ConsensusConstants,
+        mut lookup: F,
+    ) -> Result<Self, E>
+    where
+        E: From<Error>,
+        F: FnMut(&Bytes32) -> Result<Option<BlockRecord>, E>,
+    {
+        let (cc_challenge, rc_challenge) = sub_slot_challenges(Some(prev), constants, &mut lookup)?;
+        let start = find_block(prev, &mut lookup, BlockRecord::first_in_sub_slot)?;
+        let hashes = start
+            .finished_challenge_slot_hashes
+            .as_deref()
+            .unwrap_or_default();
+        let prev_cc_challenge = if hashes.len() >= 2 {
+            hashes[hashes.len() - 2]
+        } else if start.height == 0 {
+            constants.genesis_challenge
+        } else {
+            let before = lookup(&start.prev_hash)?.ok_or(Error::BlockNotFound(start.prev_hash))?;
+            sub_slot_challenges(Some(&before), constants, &mut lookup)?.0
+        };
+        Ok(Self {
+            header_hash: prev.header_hash,
+            height: Some(prev.height),
+            weight: prev.weight,
+            total_iters: prev.total_iters,
+            cc_challenge,
+            rc_challenge,
+            prev_cc_challenge,
+        })
+    }
+
+    // the checks of header that only depend on the headers before it. The
+    // context is moved past the header, and the challenges of the sub-slot
+    // it's in are returned
+    fn add_header(
+        &mut self,
+        header: &HeaderBlock,
+        constants: &ConsensusConstants,
+    ) -> Result<(Bytes32, Bytes32), ValidationErr> {
+        let rcb = &header.reward_chain_block;
+        if header.prev_header_hash() != self.header_hash {
+            return Err(ValidationErr(NodePtr::NIL, ErrorCode::InvalidPrevBlockHash));
+        }
+        if Some(rcb.height) != self.height.map_or(Some(0), |h| h.checked_add(1)) {
+            return Err(ValidationErr(NodePtr::NIL, ErrorCode::InvalidHeight));
+        }
+        if rcb.weight <= self.weight {
+            return Err(ValidationErr(NodePtr::NIL, ErrorCode::InvalidWeight));
+        }
+        if rcb.total_iters <= self.total_iters {
+            return Err(ValidationErr(NodePtr::NIL, ErrorCode::InvalidTotalIters));
+        }
+
+        for slot in &header.finished_sub_slots {
+            let cc = &slot.challenge_chain;
+            if cc.challenge_chain_end_of_slot_vdf.challenge != self.cc_challenge {
+                return Err(ValidationErr(
+                    NodePtr::NIL,
+                    ErrorCode::InvalidPrevChallengeSlotHash,
+                ));
+            }
+            let cc_hash: Bytes32 = cc.hash().into();
+            if slot.reward_chain.challenge_chain_sub_slot_hash != cc_hash {
+                return Err(ValidationErr(
+                    NodePtr::NIL,
+                    ErrorCode::InvalidChallengeSlotHashRc,
+                ));
+            }
+            self.prev_cc_challenge = self.cc_challenge;
+            self.cc_challenge = cc_hash;
+            self.rc_challenge = slot.reward_chain.hash().into();
+        }
+
+        // the signage point of an overflow block is in the previous sub-slot
+        let overflow = is_overflow_block(constants, rcb.signage_point_index)
+            .map_err(|_| ValidationErr(NodePtr::NIL, ErrorCode::InvalidSpIndex))?;
+        let pos_challenge = if overflow {
+            self.prev_cc_challenge
+        } else {
+            self.cc_challenge
+        };
+        if rcb.pos_ss_cc_challenge_hash != pos_challenge {
+            return Err(ValidationErr(NodePtr::NIL, ErrorCode::InvalidCcChallenge));
+        }
+
+        self.header_hash = header.header_hash();
+        self.height = Some(rcb.height);
+        self.weight = rcb.weight;
+        self.total_iters = rcb.total_iters;
+        Ok((self.cc_challenge, self.rc_challenge))
+    }
+}
+
+#[cfg(feature = "py-bindings")]
+#[pymethods]
+impl HeaderContext {
+    #[staticmethod]
+    #[pyo3(name = "genesis")]
+    fn py_genesis(constants: &ConsensusConstants) -> Self {
+        Self::genesis(constants)
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "from_prev")]
+    fn py_from_prev(
+        prev: &BlockRecord,
+        constants: &ConsensusConstants,
+        blocks: &Bound<'_, PyAny>,
+    ) -> PyResult<Self> {
+        if let Ok(blocks) = blocks.downcast::<PyDict>() {
+            Self::from_prev(prev, constants, |hash| {
+                blocks
+                    .get_item(*hash)?
+                    .map(|b| b.extract::<BlockRecord>())
+                    .transpose()
+            })
+        } else {
+            Self::from_prev(prev, constants, |hash| {
+                blocks.call1((*hash,))?.extract::<Option<BlockRecord>>()
+            })
+        }
+    }
+
+    fn __richcmp__(&self, other: &Self, op: CompareOp, py: Python<'_>) -> PyObject {
+        match op {
+            CompareOp::Eq => (self == other).into_py(py),
+            CompareOp::Ne => (self != other).into_py(py),
+            _ => py.NotImplemented(),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+// Checks a contiguous range of headers, following the chain described by
+// context, and returns the context following the last header. This is meant
+// for syncing many headers at a time (e.g. in a wallet), without the block
+// records of the chain. Every header is checked to:
+// - follow the previous block, with increasing height, weight and total
+//   iterations
+// - continue the challenge chain with its finished sub-slots
+// - have a proof of space for the challenge of the sub-slot its signage point
+//   is in, that passes precheck_proof_of_space()
+// - have valid signage point signatures
+// The proofs of space themselves are not verified (hence the name), so neither
+// is the quality string or the required iterations. The caller has to verify
+// them. The checks that need the whole chain, like the difficulty, the
+// sub-slot iterations, the reward chain infusions and the VDF proofs, are not
+// done either.
+// The proof of space and signature checks run in parallel, but the error is
+// the one of the first header that fails.
+pub fn precheck_header_blocks(
+    headers: &[HeaderBlock],
+    constants: &ConsensusConstants,
+    context: &HeaderContext,
+) -> Result<HeaderContext, ValidationErr> {
+    let schedule = ForkSchedule::new(constants);
+    let mut ctx = context.clone();
+    let mut checks = Vec::with_capacity(headers.len());
+    let mut failure = Ok(());
+    for header in headers {
+        match ctx.add_header(header, constants) {
+            Ok((cc_challenge, rc_challenge)) => {
+                checks.push((header, cc_challenge, rc_challenge));
+            }
+            Err(e) => {
+                failure = Err(e);
+                break;
+            }
+        }
+    }
+
+    // the headers before the first one that failed
+    let results: Vec<Result<(), ValidationErr>> = checks
+        .par_iter()
+        .map(|(header, cc_challenge, rc_challenge)| {
+            let rcb = header.reward_chain_block.get_unfinished();
+            let plot_key = rcb.proof_of_space.plot_public_key();
+            let filter_bits = schedule.plot_filter_bits(header.height());
+            precheck_proof_of_space(&rcb, constants, filter_bits)?;
+            check_sp_signatures(&rcb, &plot_key, cc_challenge, rc_challenge)
+        })
+        .collect();
+    results.into_iter().collect::<Result<(), _>>()?;
+    failure?;
+    Ok(ctx)
+}
+
+#[cfg(all(test, feature = "test_blocks"))]
+mod tests {
+    use super::*;
+    use crate::consensus_constants::TEST_CONSTANTS;
+    use crate::test_block_record::{hash, BlockRecordBuilder};
+    use crate::test_blocks::BlockTools;
+    use chia_bls::{sign, SecretKey, Signature};
+    use chia_protocol::{
+        ChallengeChainSubSlot, ClassgroupElement, EndOfSubSlotBundle, FullBlock,
+        InfusedChallengeChainSubSlot, RewardChainSubSlot, SubSlotProofs, VDFInfo, VDFProof,
+    };
+    use rstest::rstest;
+    use sha2::{Digest, Sha256};
+    use std::collections::HashMap;
+
+    const SSI: u64 = TEST_CONSTANTS.sub_slot_iters_starting;
+    const MIN_BLOCKS: u8 = TEST_CONSTANTS.min_blocks_per_challenge_block;
+
+    fn vdf(challenge: Bytes32, iters: u64) -> VDFInfo {
+        VDFInfo::new(challenge, iters, ClassgroupElement::default())
+    }
+
+    fn proof() -> VDFProof {
+        VDFProof::new(0, vec![].into(), true)
+    }
+
+    // a block record at the specified height, whose header hash is hash(height)
+    fn record(height: u32, deficit: u8, finished_cc: Option<Bytes32>) -> BlockRecord {
+        let mut builder = BlockRecordBuilder::new(hash(height))
+            .prev_hash(hash(height.wrapping_sub(1)))
+            .height(height)
+            .weight(height as u128)
+            .total_iters(height as u128 * 1000)
+            .signage_point_index(5)
+            .reward_infusion_new_challenge(hash(100 + height))
+            .challenge_block_info_hash(hash(200 + height))
+            .sub_slot_iters(SSI)
+            .required_iters(1)
+            .deficit(deficit);
+        if let Some(cc) = finished_cc {
+            builder = builder.finished_challenge_slot_hashes(vec![cc]);
+        }
+        builder.build()
+    }
+
+    // a sub-slot without an infused challenge chain
+    fn sub_slot(cc_challenge: Bytes32, rc_challenge: Bytes32, deficit: u8) -> EndOfSubSlotBundle {
+        let cc = ChallengeChainSubSlot::new(vdf(cc_challenge, SSI), None, None, None, None);
+        let rc = RewardChainSubSlot::new(vdf(rc_challenge, SSI), cc.hash().into(), None, deficit);
+        EndOfSubSlotBundle::new(cc, None, rc, SubSlotProofs::new(proof(), None, proof()))
+    }
+
+    // adds an infused challenge chain to the sub-slot, and commits to it
+    fn with_icc(
+        mut slot: EndOfSubSlotBundle,
+        challenge: Bytes32,
+        iters: u64,
+    ) -> EndOfSubSlotBundle {
+        let icc = InfusedChallengeChainSubSlot::new(vdf(challenge, iters));
+        let icc_hash: Bytes32 = icc.hash().into();
+        if slot.reward_chain.deficit == MIN_BLOCKS {
+            slot.challenge_chain.infused_challenge_chain_sub_slot_hash = Some(icc_hash);
+        }
+        slot.infused_challenge_chain = Some(icc);
+        slot.reward_chain.challenge_chain_sub_slot_hash = slot.challenge_chain.hash().into();
+        slot.reward_chain.infused_challenge_chain_sub_slot_hash = Some(icc_hash);
+        slot
+    }
+
+    // the sub-slot following slot, with the same deficit
+    fn next_slot(slot: &EndOfSubSlotBundle) -> EndOfSubSlotBundle {
+        sub_slot(
+            slot.challenge_chain.hash().into(),
+            slot.reward_chain.hash().into(),
+            slot.reward_chain.deficit,
+        )
+    }
+
+    fn header(prev_hash: Bytes32, slots: Vec<EndOfSubSlotBundle>) -> UnfinishedHeaderBlock {
+        let bt = BlockTools::new(&TEST_CONSTANTS, &[1; 32]);
+        let block = bt.next_block(None, &[]).expect("next_block");
+        let mut header = block.get_block_header().into_unfinished_header_block();
+        header.foliage.prev_block_hash = prev_hash;
+        header.finished_sub_slots = slots;
+        header
+    }
+
+    fn validate(
+        header: &UnfinishedHeaderBlock,
+        prev: Option<&BlockRecord>,
+        blocks: &HashMap<Bytes32, BlockRecord>,
+    ) -> Result<(), Error> {
+        validate_unfinished_header_block_linkage(header, prev, &TEST_CONSTANTS, |h| {
+            Ok(blocks.get(h).cloned())
+        })
+    }
+
+    fn code(r: Result<(), Error>) -> ErrorCode {
+        match r {
+            Err(Error::Validation(ValidationErr(_, code))) => code,
+            r => panic!("unexpected result: {r:?}"),
+        }
+    }
+
+    #[test]
+    fn test_genesis() {
+        let genesis = TEST_CONSTANTS.genesis_challenge;
+        let blocks = HashMap::new();
+        let slot = sub_slot(genesis, genesis, MIN_BLOCKS);
+        assert_eq!(validate(&header(genesis, vec![]), None, &blocks), Ok(()));
+        assert_eq!(
+            validate(
+                &header(genesis, vec![slot.clone(), next_slot(&slot)]),
+                None,
+                &blocks
+            ),
+            Ok(())
+        );
+
+        assert_eq!(
+            code(validate(&header(hash(1), vec![]), None, &blocks)),
+            ErrorCode::InvalidPrevBlockHash
+        );
+        assert_eq!(
+            code(validate(
+                &header(genesis, vec![sub_slot(hash(1), genesis, MIN_BLOCKS)]),
+                None,
+                &blocks
+            )),
+            ErrorCode::InvalidPrevChallengeSlotHash
+        );
+        assert_eq!(
+            code(validate(
+                &header(genesis, vec![sub_slot(genesis, hash(1), MIN_BLOCKS)]),
+                None,
+                &blocks
+            )),
+            ErrorCode::InvalidRcEosVdf
+        );
+        assert_eq!(
+            code(validate(
+                &header(genesis, vec![sub_slot(genesis, genesis, 0)]),
+                None,
+                &blocks
+            )),
+            ErrorCode::InvalidDeficit
+        );
+        assert_eq!(
+            code(validate(
+                &header(genesis, vec![with_icc(slot, genesis, SSI)]),
+                None,
+                &blocks
+            )),
+            ErrorCode::ShouldNotHaveIcc
+        );
+    }
+
+    #[test]
+    fn test_prev_first_in_sub_slot() {
+        let prev = record(10, MIN_BLOCKS, Some(hash(50)));
+        let blocks = HashMap::new();
+        let rc = prev.reward_infusion_new_challenge;
+
+        let slot = sub_slot(hash(50), rc, MIN_BLOCKS);
+        let header_ok = header(prev.header_hash, vec![slot.clone(), next_slot(&slot)]);
+        assert_eq!(validate(&header_ok, Some(&prev), &blocks), Ok(()));
+        // no new sub-slot
+        assert_eq!(
+            validate(&header(prev.header_hash, vec![]), Some(&prev), &blocks),
+            Ok(())
+        );
+
+        assert_eq!(
+            code(validate(
+                &header(hash(9), vec![slot.clone()]),
+                Some(&prev),
+                &blocks
+            )),
+            ErrorCode::InvalidPrevBlockHash
+        );
+        assert_eq!(
+            code(validate(
+                &header(prev.header_hash, vec![sub_slot(hash(51), rc, MIN_BLOCKS)]),
+                Some(&prev),
+                &blocks
+            )),
+            ErrorCode::InvalidPrevChallengeSlotHash
+        );
+        assert_eq!(
+            code(validate(
+                &header(
+                    prev.header_hash,
+                    vec![slot.clone(), sub_slot(hash(50), rc, MIN_BLOCKS)]
+                ),
+                Some(&prev),
+                &blocks
+            )),
+            ErrorCode::InvalidPrevChallengeSlotHash
+        );
+        let mut bad_rc = next_slot(&slot);
+        bad_rc.reward_chain.end_of_slot_vdf.challenge = rc;
+        assert_eq!(
+            code(validate(
+                &header(prev.header_hash, vec![slot.clone(), bad_rc]),
+                Some(&prev),
+                &blocks
+            )),
+            ErrorCode::InvalidRcEosVdf
+        );
+        assert_eq!(
+            code(validate(
+                &header(prev.header_hash, vec![sub_slot(hash(50), rc, 3)]),
+                Some(&prev),
+                &blocks
+            )),
+            ErrorCode::InvalidDeficit
+        );
+    }
+
+    #[rstest]
+    #[case::rc_hash(|s: &mut EndOfSubSlotBundle| s.reward_chain.challenge_chain_sub_slot_hash = Bytes32::default(), ErrorCode::InvalidChallengeSlotHashRc)]
+    #[case::icc_hash_cc(|s: &mut EndOfSubSlotBundle| {
+        s.challenge_chain.infused_challenge_chain_sub_slot_hash = Some(Bytes32::default());
+        s.reward_chain.challenge_chain_sub_slot_hash = s.challenge_chain.hash().into();
+    }, ErrorCode::InvalidIccHashCc)]
+    #[case::icc_hash_rc(|s: &mut EndOfSubSlotBundle| s.reward_chain.infused_challenge_chain_sub_slot_hash = Some(Bytes32::default()), ErrorCode::InvalidIccHashRc)]
+    #[case::new_ssi(|s: &mut EndOfSubSlotBundle| {
+        s.challenge_chain.new_sub_slot_iters = Some(SSI);
+        s.reward_chain.challenge_chain_sub_slot_hash = s.challenge_chain.hash().into();
+    }, ErrorCode::InvalidNewSubSlotIters)]
+    #[case::new_difficulty(|s: &mut EndOfSubSlotBundle| {
+        s.challenge_chain.new_difficulty = Some(100);
+        s.reward_chain.challenge_chain_sub_slot_hash = s.challenge_chain.hash().into();
+    }, ErrorCode::InvalidNewDifficulty)]
+    fn test_sub_slot_hashes(
+        #[case] modify: fn(&mut EndOfSubSlotBundle),
+        #[case] expected: ErrorCode,
+    ) {
+        let prev = record(10, MIN_BLOCKS, Some(hash(50)));
+        let mut slot = sub_slot(hash(50), prev.reward_infusion_new_challenge, MIN_BLOCKS);
+        modify(&mut slot);
+        assert_eq!(
+            code(validate(
+                &header(prev.header_hash, vec![slot]),
+                Some(&prev),
+                &HashMap::new()
+            )),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_sub_epoch_summary() {
+        let prev = record(10, MIN_BLOCKS, Some(hash(50)));
+        let mut slot = sub_slot(hash(50), prev.reward_infusion_new_challenge, MIN_BLOCKS);
+        slot.challenge_chain.subepoch_summary_hash = Some(hash(7));
+        slot.challenge_chain.new_difficulty = Some(100);
+        slot.reward_chain.challenge_chain_sub_slot_hash = slot.challenge_chain.hash().into();
+        let blocks = HashMap::new();
+        assert_eq!(
+            validate(
+                &header(prev.header_hash, vec![slot.clone()]),
+                Some(&prev),
+                &blocks
+            ),
+            Ok(())
+        );
+
+        // only the first sub-slot may include the summary
+        let mut empty = next_slot(&slot);
+        empty.challenge_chain.subepoch_summary_hash = Some(hash(7));
+        empty.reward_chain.challenge_chain_sub_slot_hash = empty.challenge_chain.hash().into();
+        assert_eq!(
+            code(validate(
+                &header(prev.header_hash, vec![slot, empty]),
+                Some(&prev),
+                &blocks
+            )),
+            ErrorCode::InvalidSubEpochSummaryHash
+        );
+    }
+
+    #[test]
+    fn test_lookup() {
+        // prev isn't the first block in its sub-slot, so the challenge comes
+        // from an earlier block
+        let mut blocks = HashMap::new();
+        for height in 5..10 {
+            let finished = (height == 5).then_some(hash(50));
+            let b = record(height, MIN_BLOCKS, finished);
+            blocks.insert(b.header_hash, b);
+        }
+        let prev = record(10, MIN_BLOCKS, None);
+        let slot = sub_slot(hash(50), prev.reward_infusion_new_challenge, MIN_BLOCKS);
+        assert_eq!(
+            validate(
+                &header(prev.header_hash, vec![slot.clone()]),
+                Some(&prev),
+                &blocks
+            ),
+            Ok(())
+        );
+
+        blocks.remove(&hash(7));
+        assert_eq!(
+            validate(&header(prev.header_hash, vec![slot]), Some(&prev), &blocks),
+            Err(Error::BlockNotFound(hash(7)))
+        );
+    }
+
+    #[test]
+    fn test_icc() {
+        // prev is a challenge block, so the infused challenge chain starts at
+        // its infusion point
+        let prev = record(10, MIN_BLOCKS - 1, Some(hash(50)));
+        let ip_iters = calculate_ip_iters(&TEST_CONSTANTS, SSI, 5, 1).unwrap();
+        let blocks = HashMap::new();
+        let rc = prev.reward_infusion_new_challenge;
+
+        let slot = with_icc(
+            sub_slot(hash(50), rc, MIN_BLOCKS - 1),
+            prev.challenge_block_info_hash,
+            SSI - ip_iters,
+        );
+        // an empty sub-slot continues the infused challenge chain from the
+        // start
+        let icc_hash: Bytes32 = slot.infused_challenge_chain.as_ref().unwrap().hash().into();
+        let empty = with_icc(next_slot(&slot), icc_hash, SSI);
+        assert_eq!(
+            validate(
+                &header(prev.header_hash, vec![slot.clone(), empty.clone()]),
+                Some(&prev),
+                &blocks
+            ),
+            Ok(())
+        );
+
+        // it's required
+        assert_eq!(
+            code(validate(
+                &header(
+                    prev.header_hash,
+                    vec![sub_slot(hash(50), rc, MIN_BLOCKS - 1)]
+                ),
+                Some(&prev),
+                &blocks
+            )),
+            ErrorCode::ShouldHaveIcc
+        );
+        // and has to start at the right place
+        let bad_iters = with_icc(
+            sub_slot(hash(50), rc, MIN_BLOCKS - 1),
+            prev.challenge_block_info_hash,
+            SSI,
+        );
+        assert_eq!(
+            code(validate(
+                &header(prev.header_hash, vec![bad_iters]),
+                Some(&prev),
+                &blocks
+            )),
+            ErrorCode::InvalidIccEosVdf
+        );
+        let bad_challenge = with_icc(next_slot(&slot), Bytes32::default(), SSI);
+        assert_eq!(
+            code(validate(
+                &header(prev.header_hash, vec![slot, bad_challenge]),
+                Some(&prev),
+                &blocks
+            )),
+            ErrorCode::InvalidIccEosVdf
+        );
+
+        // without a challenge block, there's no infused challenge chain
+        let prev = record(10, MIN_BLOCKS, Some(hash(50)));
+        let slot = with_icc(sub_slot(hash(50), rc, MIN_BLOCKS), Bytes32::default(), SSI);
+        assert_eq!(
+            code(validate(
+                &header(prev.header_hash, vec![slot]),
+                Some(&prev),
+                &blocks
+            )),
+            ErrorCode::ShouldNotHaveIcc
+        );
+    }
+
+    #[test]
+    fn test_icc_from_sub_slot_start() {
+        // prev isn't a challenge block, and neither is any block back to the
+        // start of the sub-slot. The infused challenge chain continues from
+        // the end of the previous sub-slot
+        let mut start = record(8, 2, Some(hash(50)));
+        start.finished_infused_challenge_slot_hashes = Some(vec![hash(60)]);
+        let mut blocks = HashMap::new();
+        blocks.insert(start.header_hash, start);
+        blocks.insert(hash(9), record(9, 1, None));
+        let rc = record(10, 0, None).reward_infusion_new_challenge;
+
+        let slot = with_icc(sub_slot(hash(50), rc, 1), hash(60), SSI);
+        let prev = record(10, 1, None);
+        assert_eq!(
+            validate(&header(prev.header_hash, vec![slot]), Some(&prev), &blocks),
+            Ok(())
+        );
+
+        // once the deficit reaches 0 it's reset, and the challenge chain
+        // includes the infused challenge chain that ends
+        let prev = record(10, 0, None);
+        let slot = with_icc(sub_slot(hash(50), rc, MIN_BLOCKS), hash(60), SSI);
+        assert!(slot
+            .challenge_chain
+            .infused_challenge_chain_sub_slot_hash
+            .is_some());
+        assert_eq!(
+            validate(
+                &header(prev.header_hash, vec![slot.clone()]),
+                Some(&prev),
+                &blocks
+            ),
+            Ok(())
+        );
+        // and the next sub-slot doesn't have one
+        assert_eq!(
+            validate(
+                &header(prev.header_hash, vec![slot.clone(), next_slot(&slot)]),
+                Some(&prev),
+                &blocks
+            ),
+            Ok(())
+        );
+        let icc_hash: Bytes32 = slot.infused_challenge_chain.as_ref().unwrap().hash().into();
+        let empty = with_icc(next_slot(&slot), icc_hash, SSI);
+        assert_eq!(
+            code(validate(
+                &header(prev.header_hash, vec![slot, empty]),
+                Some(&prev),
+                &blocks
+            )),
+            ErrorCode::ShouldNotHaveIcc
+        );
+    }
+
+    fn chain(count: u32) -> Vec<FullBlock> {
+        BlockTools::new(&TEST_CONSTANTS, &[1; 32]).get_consecutive_blocks(count, None)
+    }
+
+    fn unfinished(block: &FullBlock) -> UnfinishedHeaderBlock {
+        block.get_block_header().into_unfinished_header_block()
+    }
+
+    // the fields of the block record that are needed to find the challenges
+    // of the sub-slot
+    fn block_record(block: &FullBlock) -> BlockRecord {
+        let mut ret = record(0, MIN_BLOCKS, None);
+        ret.header_hash = block.header_hash();
+        ret.prev_hash = block.prev_header_hash();
+        ret.height = block.height();
+        ret.weight = block.weight();
+        ret.total_iters = block.total_iters();
+        if let Some(slot) = block.finished_sub_slots.last() {
+            ret.finished_challenge_slot_hashes = Some(vec![slot.challenge_chain.hash().into()]);
+            ret.finished_reward_slot_hashes = Some(vec![slot.reward_chain.hash().into()]);
+        }
+        ret
+    }
+
+    fn sp_signatures(
+        headers: &[UnfinishedHeaderBlock],
+        prev: Option<&BlockRecord>,
+        blocks: &HashMap<Bytes32, BlockRecord>,
+    ) -> Result<(), Error> {
+        let challenges =
+            sub_slot_challenges(prev, &TEST_CONSTANTS, |h| Ok(blocks.get(h).cloned()))?;
+        Ok(validate_sp_signatures(headers, challenges)?)
+    }
+
+    #[test]
+    fn test_sp_signatures() {
+        // the chain crosses two sub-slots, whose first blocks are at the
+        // first signage point
+        let blocks = chain(70);
+        let headers: Vec<_> = blocks.iter().map(unfinished).collect();
+        assert_eq!(headers[0].reward_chain_block.signage_point_index, 0);
+        assert_eq!(headers[32].reward_chain_block.signage_point_index, 0);
+        assert_eq!(sp_signatures(&headers, None, &HashMap::new()), Ok(()));
+
+        // starting in the middle of a sub-slot
+        let records: HashMap<_, _> = blocks
+            .iter()
+            .map(|b| (b.header_hash(), block_record(b)))
+            .collect();
+        let prev = block_record(&blocks[39]);
+        assert_eq!(sp_signatures(&headers[40..], Some(&prev), &records), Ok(()));
+        assert_eq!(
+            sp_signatures(&headers[40..], Some(&prev), &HashMap::new()),
+            Err(Error::BlockNotFound(blocks[38].header_hash()))
+        );
+
+        // the challenges of the sub-slot are found by walking back to its
+        // first block. Block 32 is signed over the challenges of its own
+        // finished sub-slot, which block 33 is also in
+        let mut header = headers[32].clone();
+        header.finished_sub_slots.clear();
+        let prev = block_record(&blocks[39]);
+        assert_eq!(
+            sp_signatures(&[header.clone()], Some(&prev), &records),
+            Ok(())
+        );
+        let prev = block_record(&blocks[31]);
+        assert_eq!(
+            code(sp_signatures(&[header], Some(&prev), &records)),
+            ErrorCode::InvalidCcSignature
+        );
+    }
+
+    #[test]
+    fn test_invalid_sp_signatures() {
+        let mut headers: Vec<_> = chain(10).iter().map(unfinished).collect();
+        let blocks = HashMap::new();
+        headers[5].reward_chain_block.reward_chain_sp_signature = Signature::default();
+        assert_eq!(
+            code(sp_signatures(&headers, None, &blocks)),
+            ErrorCode::InvalidRcSignature
+        );
+        // the first header that fails determines the error
+        headers[3].reward_chain_block.challenge_chain_sp_signature = Signature::default();
+        assert_eq!(
+            code(sp_signatures(&headers, None, &blocks)),
+            ErrorCode::InvalidCcSignature
+        );
+        assert_eq!(
+            code(sp_signatures(&headers[4..], None, &blocks)),
+            ErrorCode::InvalidRcSignature
+        );
+    }
+
+    #[test]
+    fn test_sp_signatures_pool_contract() {
+        // plots for a pool contract use the taproot of the local and farmer
+        // keys as the plot key
+        let local_sk = SecretKey::from_seed(&[2; 32]);
+        let farmer_sk = SecretKey::from_seed(&[3; 32]);
+        let sum = &local_sk + &farmer_sk;
+        let mut hasher = Sha256::new();
+        hasher.update(sum.public_key().to_bytes());
+        hasher.update(local_sk.public_key().to_bytes());
+        hasher.update(farmer_sk.public_key().to_bytes());
+        let taproot_sk = &sum + &SecretKey::from_seed(&hasher.finalize());
+
+        let mut header = unfinished(&chain(1)[0]);
+        let rcb = &mut header.reward_chain_block;
+        let pos = &mut rcb.proof_of_space;
+        pos.pool_public_key = None;
+        pos.pool_contract_puzzle_hash = Some(hash(4));
+        pos.local_public_key = local_sk.public_key();
+        pos.farmer_public_key = farmer_sk.public_key();
+        assert_eq!(pos.plot_public_key(), taproot_sk.public_key());
+
+        // the genesis block is at the first signage point
+        let challenge = TEST_CONSTANTS.genesis_challenge;
+        rcb.challenge_chain_sp_signature = sign(&taproot_sk, challenge);
+        rcb.reward_chain_sp_signature = sign(&taproot_sk, challenge);
+        let blocks = HashMap::new();
+        assert_eq!(sp_signatures(&[header.clone()], None, &blocks), Ok(()));
+
+        // the sum of the keys isn't the plot key
+        header.reward_chain_block.challenge_chain_sp_signature = sign(&sum, challenge);
+        assert_eq!(
+            code(sp_signatures(&[header], None, &blocks)),
+            ErrorCode::InvalidCcSignature
+        );
+    }
+
+    // BlockTools' proofs of space only pass the plot filter without one
+    fn no_plot_filter() -> ConsensusConstants {
+        let mut constants = TEST_CONSTANTS.clone();
+        constants.number_zero_bits_plot_filter = 0;
+        constants
+    }
+
+    fn header_chain(
+        constants: &ConsensusConstants,
+        count: u32,
+    ) -> (Vec<FullBlock>, Vec<HeaderBlock>) {
+        let blocks = BlockTools::new(constants, &[1; 32]).get_consecutive_blocks(count, None);
+        let headers = blocks.iter().map(FullBlock::get_block_header).collect();
+        (blocks, headers)
+    }
+
+    fn header_code(r: Result<HeaderContext, ValidationErr>) -> ErrorCode {
+        r.expect_err("expected failure").1
+    }
+
+    #[test]
+    fn test_precheck_header_blocks() {
+        let constants = no_plot_filter();
+        let (blocks, headers) = header_chain(&constants, 70);
+        let genesis = HeaderContext::genesis(&constants);
+        let ctx = precheck_header_blocks(&headers, &constants, &genesis).expect("validate");
+        assert_eq!(ctx.header_hash, blocks[69].header_hash());
+        assert_eq!(ctx.height, Some(69));
+        assert_eq!(ctx.weight, blocks[69].weight());
+        assert_eq!(ctx.total_iters, blocks[69].total_iters());
+
+        // the same, in ranges
+        let mut ranges = genesis.clone();
+        for range in headers.chunks(16) {
+            ranges = precheck_header_blocks(range, &constants, &ranges).expect("validate");
+        }
+        assert_eq!(ranges, ctx);
+        assert_eq!(
+            precheck_header_blocks(&[], &constants, &ctx),
+            Ok(ctx.clone())
+        );
+
+        // a range has to continue from the context
+        assert_eq!(
+            header_code(precheck_header_blocks(&headers[1..], &constants, &genesis)),
+            ErrorCode::InvalidPrevBlockHash
+        );
+        assert_eq!(
+            header_code(precheck_header_blocks(&headers[..1], &constants, &ctx)),
+            ErrorCode::InvalidPrevBlockHash
+        );
+
+        // with the plot filter, BlockTools' plots don't pass
+        assert_eq!(
+            header_code(precheck_header_blocks(&headers, &TEST_CONSTANTS, &genesis)),
+            ErrorCode::InvalidPospace
+        );
+    }
+
+    #[test]
+    fn test_header_context_from_prev() {
+        let constants = no_plot_filter();
+        let (blocks, headers) = header_chain(&constants, 70);
+        let records: HashMap<_, _> = blocks
+            .iter()
+            .map(|b| (b.header_hash(), block_record(b)))
+            .collect();
+        let lookup = |h: &Bytes32| -> Result<_, Error> { Ok(records.get(h).cloned()) };
+
+        let genesis = HeaderContext::genesis(&constants);
+        for n in [1, 20, 32, 33, 40, 64, 65, 70] {
+            let prev = block_record(&blocks[n - 1]);
+            let ctx = HeaderContext::from_prev(&prev, &constants, lookup).expect("from_prev");
+            assert_eq!(
+                precheck_header_blocks(&headers[..n], &constants, &genesis),
+                Ok(ctx.clone())
+            );
+            // and it's where the next range starts
+            assert!(precheck_header_blocks(&headers[n..], &constants, &ctx).is_ok());
+        }
+
+        let prev = block_record(&blocks[39]);
+        assert_eq!(
+            HeaderContext::from_prev(&prev, &constants, |_| Ok::<_, Error>(None)),
+            Err(Error::BlockNotFound(blocks[38].header_hash()))
+        );
+    }
+
+    #[test]
+    fn test_invalid_header_blocks() {
+        let constants = no_plot_filter();
+        let (_, headers) = header_chain(&constants, 40);
+        let genesis = HeaderContext::genesis(&constants);
+        let check = |n: usize, modify: fn(&mut HeaderBlock)| {
+            let mut headers = headers[..=n].to_vec();
+            modify(&mut headers[n]);
+            header_code(precheck_header_blocks(&headers, &constants, &genesis))
+        };
+
+        assert_eq!(
+            check(5, |h| h.reward_chain_block.height = 7),
+            ErrorCode::InvalidHeight
+        );
+        assert_eq!(
+            check(5, |h| h.reward_chain_block.weight = 1),
+            ErrorCode::InvalidWeight
+        );
+        assert_eq!(
+            check(5, |h| h.reward_chain_block.total_iters = 0),
+            ErrorCode::InvalidTotalIters
+        );
+        assert_eq!(
+            check(32, |h| h.finished_sub_slots[0]
+                .challenge_chain
+                .challenge_chain_end_of_slot_vdf
+                .challenge = Bytes32::default()),
+            ErrorCode::InvalidPrevChallengeSlotHash
+        );
+        assert_eq!(
+            check(32, |h| h.finished_sub_slots[0]
+                .reward_chain
+                .challenge_chain_sub_slot_hash =
+                Bytes32::default()),
+            ErrorCode::InvalidChallengeSlotHashRc
+        );
+        // the proof of space is for the challenge of the new sub-slot
+        assert_eq!(
+            check(32, |h| h.finished_sub_slots.clear()),
+            ErrorCode::InvalidCcChallenge
+        );
+        assert_eq!(
+            check(5, |h| h.reward_chain_block.signage_point_index = 64),
+            ErrorCode::InvalidSpIndex
+        );
+        assert_eq!(
+            check(5, |h| h.reward_chain_block.proof_of_space.size = 31),
+            ErrorCode::InvalidPospace
+        );
+        assert_eq!(
+            check(5, |h| h
+                .reward_chain_block
+                .proof_of_space
+                .pool_contract_puzzle_hash =
+                Some(Bytes32::default())),
+            ErrorCode::InvalidPospace
+        );
+        assert_eq!(
+            check(5, |h| h.reward_chain_block.proof_of_space.challenge =
+                Bytes32::default()),
+            ErrorCode::InvalidPospaceChallenge
+        );
+        assert_eq!(
+            check(5, |h| h.reward_chain_block.challenge_chain_sp_signature =
+                Signature::default()),
+            ErrorCode::InvalidCcSignature
+        );
+
+        // changing a header breaks the link to the next one, but the error is
+        // the one of the first header that fails
+        let mut broken = headers.clone();
+        broken[3].reward_chain_block.reward_chain_sp_signature = Signature::default();
+        assert_eq!(
+            header_code(precheck_header_blocks(&broken, &constants, &genesis)),
+            ErrorCode::InvalidRcSignature
+        );
+    }
+}
@@ -1,10 +1,44 @@
 #![doc = include_str!("../README.md")]
 
 pub mod allocator;
+pub mod block_creation;
+pub mod block_rewards;
+pub mod block_signatures;
+pub mod block_validator;
+#[cfg(feature = "chain_db")]
+pub mod chain_db;
+pub mod cheap_checks;
+pub mod coin_selection;
+pub mod coin_store;
 pub mod consensus_constants;
 pub mod error;
 pub mod fast_forward;
+pub mod fork_point;
+pub mod fork_schedule;
 pub mod gen;
+pub mod generator_hash;
 pub mod generator_rom;
+pub mod header_validation;
+pub mod lineage_proof;
+pub mod mempool_item;
+pub mod merkle_blob;
 pub mod merkle_set;
 pub mod merkle_tree;
+pub mod network_info;
+pub mod pool_puzzle;
+pub mod pot_iterations;
+pub mod proof_of_space;
+pub mod puzzle_state;
+pub mod signage_points;
+pub mod signed_message;
+#[cfg(feature = "simulator")]
+pub mod simulator;
+pub mod spend_bundle_signatures;
+pub mod sub_epoch_summary;
+pub mod subscriptions;
+#[cfg(test)]
+pub(crate) mod test_block_record;
+#[cfg(feature = "test_blocks")]
+pub mod test_blocks;
+pub mod timestamp;
+pub mod weight_proof;
@@ -0,0 +1,199 @@
+use crate::error::{Error, Result};
+use chia_protocol::{Bytes32, Coin, CoinSpend, Program};
+use chia_puzzles::cat::{CatArgs, CAT_PUZZLE_HASH};
+use chia_puzzles::singleton::{SingletonArgs, SingletonStruct, SINGLETON_TOP_LAYER_PUZZLE_HASH};
+use chia_puzzles::LineageProof;
+use clvm_traits::{FromClvm, ToNodePtr};
+use clvm_utils::{tree_hash, CurriedProgram, ToTreeHash, TreeHash};
+use clvmr::allocator::{Allocator, NodePtr};
+
+// The outer layer of a CAT or singleton puzzle. A coin and its parent share
+// the same outer layer, only the inner puzzle may change
+enum OuterLayer {
+    Cat(Bytes32),
+    Singleton(SingletonStruct),
+}
+
+impl OuterLayer {
+    // uncurries the puzzle into its outer layer and inner puzzle
+    fn parse(a: &Allocator, puzzle: NodePtr) -> Result<(Self, NodePtr)> {
+        let curried = CurriedProgram::<NodePtr, NodePtr>::from_clvm(a, puzzle)
+            .map_err(|_| Error::NotCatOrSingleton)?;
+        let mod_hash = tree_hash(a, curried.program);
+        if mod_hash == CAT_PUZZLE_HASH {
+            let args = CatArgs::<NodePtr>::from_clvm(a, curried.args)?;
+            if args.mod_hash != CAT_PUZZLE_HASH.into() {
+                return Err(Error::NotCatOrSingleton);
+            }
+            Ok((OuterLayer::Cat(args.asset_id), args.inner_puzzle))
+        } else if mod_hash == SINGLETON_TOP_LAYER_PUZZLE_HASH {
+            let args = SingletonArgs::<NodePtr>::from_clvm(a, curried.args)?;
+            if args.singleton_struct.mod_hash != SINGLETON_TOP_LAYER_PUZZLE_HASH.into() {
+                return Err(Error::NotSingletonModHash);
+            }
+            Ok((
+                OuterLayer::Singleton(args.singleton_struct),
+                args.inner_puzzle,
+            ))
+        } else {
+            Err(Error::NotCatOrSingleton)
+        }
+    }
+
+    // the full puzzle hash of this outer layer wrapping the inner puzzle
+    fn puzzle_hash(&self, inner_puzzle_hash: TreeHash) -> Bytes32 {
+        let puzzle_hash = match self {
+            OuterLayer::Cat(asset_id) => CatArgs::curry_tree_hash(*asset_id, inner_puzzle_hash),
+            OuterLayer::Singleton(singleton_struct) => CurriedProgram {
+                program: SINGLETON_TOP_LAYER_PUZZLE_HASH,
+                args: SingletonArgs {
+                    singleton_struct: *singleton_struct,
+                    inner_puzzle: inner_puzzle_hash,
+                },
+            }
+            .tree_hash(),
+        };
+        puzzle_hash.into()
+    }
+}
+
+// computes the lineage proof the children of a CAT or singleton spend need in
+// their solution, to prove their parent was a coin of the same kind
+pub fn lineage_proof_for_parent(a: &mut Allocator, parent: &CoinSpend) -> Result<LineageProof> {
+    let puzzle = parent.puzzle_reveal.to_node_ptr(a)?;
+    if tree_hash(a, puzzle) != parent.coin.puzzle_hash.into() {
+        return Err(Error::PuzzleHashMismatch);
+    }
+    let (_, inner_puzzle) = OuterLayer::parse(a, puzzle)?;
+    Ok(LineageProof {
+        parent_parent_coin_id: parent.coin.parent_coin_info,
+        parent_inner_puzzle_hash: tree_hash(a, inner_puzzle).into(),
+        parent_amount: parent.coin.amount,
+    })
+}
+
+// returns whether the lineage proof proves that the parent of coin is a CAT or
+// singleton with the same outer layer (i.e. the same asset ID or launcher ID)
+// as coin. The puzzle is the puzzle reveal of coin.
+pub fn verify_lineage_proof(
+    a: &mut Allocator,
+    proof: &LineageProof,
+    coin: &Coin,
+    puzzle: &Program,
+) -> Result<bool> {
+    let puzzle = puzzle.to_node_ptr(a)?;
+    if tree_hash(a, puzzle) != coin.puzzle_hash.into() {
+        return Err(Error::PuzzleHashMismatch);
+    }
+    let (outer, _) = OuterLayer::parse(a, puzzle)?;
+    let parent = Coin::new(
+        proof.parent_parent_coin_id,
+        outer.puzzle_hash(proof.parent_inner_puzzle_hash.into()),
+        proof.parent_amount,
+    );
+    Ok(parent.coin_id() == coin.parent_coin_info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chia_puzzles::cat::CAT_PUZZLE;
+    use chia_puzzles::singleton::SINGLETON_TOP_LAYER_PUZZLE;
+    use clvm_traits::{FromNodePtr, ToClvm};
+    use clvmr::serde::node_from_bytes;
+    use rstest::rstest;
+
+    // a spend of a CAT or singleton coin (depending on cat) with the inner
+    // puzzle (q . inner)
+    fn make_spend(
+        a: &mut Allocator,
+        cat: bool,
+        inner: u8,
+        parent: Bytes32,
+        amount: u64,
+    ) -> CoinSpend {
+        let inner_puzzle = (1, inner).to_clvm(a).unwrap();
+        let puzzle = if cat {
+            let program = node_from_bytes(a, &CAT_PUZZLE).unwrap();
+            CurriedProgram {
+                program,
+                args: CatArgs::new(Bytes32::new([7; 32]), inner_puzzle),
+            }
+            .to_clvm(a)
+            .unwrap()
+        } else {
+            let program = node_from_bytes(a, &SINGLETON_TOP_LAYER_PUZZLE).unwrap();
+            CurriedProgram {
+                program,
+                args: SingletonArgs::new(Bytes32::new([7; 32]), inner_puzzle),
+            }
+            .to_clvm(a)
+            .unwrap()
+        };
+        let coin = Coin::new(parent, tree_hash(a, puzzle).into(), amount);
+        CoinSpend::new(
+            coin,
+            Program::from_node_ptr(a, puzzle).unwrap(),
+            Program::default(),
+        )
+    }
+
+    #[rstest]
+    fn test_lineage_proof(#[values(true, false)] cat: bool) {
+        let mut a = Allocator::new();
+        let parent = make_spend(&mut a, cat, 1, Bytes32::new([1; 32]), 3);
+        // the inner puzzle of the child is different from its parent's
+        let child = make_spend(&mut a, cat, 2, parent.coin.coin_id(), 5);
+
+        let proof = lineage_proof_for_parent(&mut a, &parent).expect("lineage proof");
+        assert_eq!(proof.parent_parent_coin_id, parent.coin.parent_coin_info);
+        assert_eq!(proof.parent_amount, 3);
+        let parent_inner_puzzle = (1, 1_u8).to_clvm(&mut a).unwrap();
+        assert_eq!(
+            proof.parent_inner_puzzle_hash,
+            tree_hash(&a, parent_inner_puzzle).into()
+        );
+
+        assert!(verify_lineage_proof(&mut a, &proof, &child.coin, &child.puzzle_reveal).unwrap());
+
+        // a proof with the wrong amount doesn't match the parent coin
+        let bad_proof = LineageProof {
+            parent_amount: 4,
+            ..proof
+        };
+        assert!(
+            !verify_lineage_proof(&mut a, &bad_proof, &child.coin, &child.puzzle_reveal).unwrap()
+        );
+
+        // a coin of another kind can't use the proof
+        let other = make_spend(&mut a, !cat, 2, parent.coin.coin_id(), 5);
+        assert!(!verify_lineage_proof(&mut a, &proof, &other.coin, &other.puzzle_reveal).unwrap());
+    }
+
+    #[test]
+    fn test_not_cat_or_singleton() {
+        let mut a = Allocator::new();
+        let puzzle = (1, 1_u8).to_clvm(&mut a).unwrap();
+        let coin = Coin::new(Bytes32::default(), tree_hash(&a, puzzle).into(), 1);
+        let spend = CoinSpend::new(
+            coin,
+            Program::from_node_ptr(&a, puzzle).unwrap(),
+            Program::default(),
+        );
+        assert_eq!(
+            lineage_proof_for_parent(&mut a, &spend),
+            Err(Error::NotCatOrSingleton)
+        );
+    }
+
+    #[test]
+    fn test_puzzle_hash_mismatch() {
+        let mut a = Allocator::new();
+        let mut spend = make_spend(&mut a, true, 1, Bytes32::default(), 1);
+        spend.coin.puzzle_hash = Bytes32::default();
+        assert_eq!(
+            lineage_proof_for_parent(&mut a, &spend),
+            Err(Error::PuzzleHashMismatch)
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use crate::error::{Error, Result};
+use crate::gen::owned_conditions::OwnedSpendBundleConditions;
+use chia_protocol::{Bytes32, Coin, SpendBundle};
+use std::cmp::Ordering;
+
+#[cfg(feature = "py-bindings")]
+use pyo3::prelude::*;
+
+// A spend bundle that's been validated and admitted to the mempool, along with
+// the properties the mempool needs to prioritize it. These are all derived
+// from the conditions of the spend bundle, so they're only computed once.
+#[cfg_attr(feature = "py-bindings", pyclass(module = "gold_rs", frozen, get_all))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MempoolItem {
+    pub spend_bundle: SpendBundle,
+    pub spend_bundle_name: Bytes32,
+    pub cost: u64,
+    pub fee: u64,
+    pub additions: Vec<Coin>,
+    pub removals: Vec<Coin>,
+    // the flags (ELIGIBLE_FOR_DEDUP, ELIGIBLE_FOR_FF) of every spend, in the
+    // same order as removals
+    pub spend_flags: Vec<u32>,
+    pub height_added: u32,
+}
+
+impl MempoolItem {
+    pub fn new(
+        spend_bundle: SpendBundle,
+        conds: &OwnedSpendBundleConditions,
+        height_added: u32,
+    ) -> Result<Self> {
+        let Some(fee) = conds
+            .removal_amount
+            .checked_sub(conds.addition_amount)
+            .and_then(|fee| u64::try_from(fee).ok())
+        else {
+            return Err(Error::Custom(format!(
+                "invalid fee, removals: {} additions: {}",
+                conds.removal_amount, conds.addition_amount
+            )));
+        };
+
+        let mut additions = Vec::new();
+        let mut removals = Vec::with_capacity(conds.spends.len());
+        let mut spend_flags = Vec::with_capacity(conds.spends.len());
+        for spend in &conds.spends {
+            removals.push(Coin::new(
+                spend.parent_id,
+                spend.puzzle_hash,
+                spend.coin_amount,
+            ));
+            spend_flags.push(spend.flags);
+            for (puzzle_hash, amount, _hint) in &spend.create_coin {
+                additions.push(Coin::new(spend.coin_id, *puzzle_hash, *amount));
+            }
+        }
+
+        Ok(Self {
+            spend_bundle_name: spend_bundle.name(),
+            spend_bundle,
+            cost: conds.cost,
+            fee,
+            additions,
+            removals,
+            spend_flags,
+            height_added,
+        })
+    }
+
+    pub fn fee_per_cost(&self) -> f64 {
+        if self.cost == 0 {
+            return 0.0;
+        }
+        self.fee as f64 / self.cost as f64
+    }
+}
+
+// compares the fee per cost of two items, without rounding
+fn cmp_fee_per_cost(lhs: &MempoolItem, rhs: &MempoolItem) -> Ordering {
+    (lhs.fee as u128 * rhs.cost as u128).cmp(&(rhs.fee as u128 * lhs.cost as u128))
+}
+
+// The order items are evicted in, when the mempool is full. Items with the
+// lowest fee per cost go first, and among those, the most recently added ones.
+// The spend bundle name makes the order total.
+pub fn eviction_order(lhs: &MempoolItem, rhs: &MempoolItem) -> Ordering {
+    cmp_fee_per_cost(lhs, rhs)
+        .then_with(|| rhs.height_added.cmp(&lhs.height_added))
+        .then_with(|| lhs.spend_bundle_name.cmp(&rhs.spend_bundle_name))
+}
+
+// The order items are considered in, when packing a block. Items with the
+// highest fee per cost go first, and among those, the ones that have been
+// waiting the longest.
+pub fn block_inclusion_order(lhs: &MempoolItem, rhs: &MempoolItem) -> Ordering {
+    cmp_fee_per_cost(rhs, lhs)
+        .then_with(|| lhs.height_added.cmp(&rhs.height_added))
+        .then_with(|| lhs.spend_bundle_name.cmp(&rhs.spend_bundle_name))
+}
+
+#[cfg(feature = "py-bindings")]
+#[pymethods]
+impl MempoolItem {
+    #[new]
+    fn py_new(
+        spend_bundle: SpendBundle,
+        conds: &OwnedSpendBundleConditions,
+        height_added: u32,
+    ) -> Result<Self> {
+        Self::new(spend_bundle, conds, height_added)
+    }
+
+    #[getter(fee_per_cost)]
+    fn py_fee_per_cost(&self) -> f64 {
+        self.fee_per_cost()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "MempoolItem(name={}, fee={}, cost={}, height_added={})",
+            self.spend_bundle_name, self.fee, self.cost, self.height_added
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chia_bls::G2Element;
+
+    fn item(fee: u64, cost: u64, height_added: u32) -> MempoolItem {
+        MempoolItem {
+            spend_bundle: SpendBundle::new(vec![], G2Element::default()),
+            spend_bundle_name: Bytes32::new([fee as u8; 32]),
+            cost,
+            fee,
+            additions: vec![],
+            removals: vec![],
+            spend_flags: vec![],
+            height_added,
+        }
+    }
+
+    fn sorted(
+        mut items: Vec<MempoolItem>,
+        order: fn(&MempoolItem, &MempoolItem) -> Ordering,
+    ) -> Vec<(u64, u64, u32)> {
+        items.sort_by(order);
+        items
+            .iter()
+            .map(|i| (i.fee, i.cost, i.height_added))
+            .collect()
+    }
+
+    #[test]
+    fn test_eviction_order() {
+        let items = vec![
+            item(30, 10, 1),
+            item(10, 10, 1),
+            item(20, 10, 2),
+            item(2, 1, 1),
+        ];
+        assert_eq!(
+            sorted(items, eviction_order),
+            [(10, 10, 1), (20, 10, 2), (2, 1, 1), (30, 10, 1)]
+        );
+    }
+
+    #[test]
+    fn test_block_inclusion_order() {
+        let items = vec![
+            item(30, 10, 1),
+            item(10, 10, 1),
+            item(20, 10, 2),
+            item(2, 1, 1),
+        ];
+        assert_eq!(
+            sorted(items, block_inclusion_order),
+            [(30, 10, 1), (2, 1, 1), (20, 10, 2), (10, 10, 1)]
+        );
+    }
+
+    #[test]
+    fn test_fee_per_cost() {
+        assert_eq!(item(30, 10, 1).fee_per_cost(), 3.0);
+        assert_eq!(item(30, 0, 1).fee_per_cost(), 0.0);
+    }
+}
@@ -0,0 +1,766 @@
+use chia_protocol::Bytes32;
+use chia_streamable_macro::{streamable, Streamable};
+use clvmr::sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[cfg(feature = "py-bindings")]
+use chia_py_streamable_macro::{PyGetters, PyJsonDict, PyStreamable};
+#[cfg(feature = "py-bindings")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "py-bindings")]
+use pyo3::prelude::*;
+#[cfg(feature = "py-bindings")]
+use pyo3::types::PyBytes;
+
+// The DataLayer merkle tree. The tree is stored in a flat blob of fixed size
+// blocks, one per node, so it can be persisted (and loaded) as-is. The root is
+// always the node at index 0. Leaves refer to their key and value by ID
+// (KvId), the key and value themselves are stored elsewhere. The leaf hash is
+// computed by the caller, internal node hashes are computed here.
+
+pub type KvId = i64;
+pub type TreeIndex = u32;
+
+// node type (1 byte), parent (4 bytes), hash (32 bytes), and either the left
+// and right child indices (4 + 4 bytes, followed by 8 bytes of padding) or the
+// key and value IDs (8 + 8 bytes)
+pub const BLOCK_SIZE: usize = 1 + 4 + 32 + 8 + 8;
+
+// the parent of the root node
+const NULL_PARENT: TreeIndex = TreeIndex::MAX;
+
+const INTERNAL_NODE: u8 = 0;
+const LEAF_NODE: u8 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum MerkleBlobError {
+    #[error("blob length {0} is not a multiple of the block size")]
+    InvalidBlobLength(usize),
+
+    #[error("invalid node type {0}")]
+    InvalidNodeType(u8),
+
+    #[error("node index {0} out of range")]
+    IndexOutOfRange(TreeIndex),
+
+    #[error("invalid tree structure at node {0}")]
+    InvalidTree(TreeIndex),
+
+    #[error("key {0} already present")]
+    KeyAlreadyPresent(KvId),
+
+    #[error("unknown key {0}")]
+    UnknownKey(KvId),
+}
+
+#[cfg(feature = "py-bindings")]
+impl From<MerkleBlobError> for PyErr {
+    fn from(err: MerkleBlobError) -> PyErr {
+        PyValueError::new_err(err.to_string())
+    }
+}
+
+pub fn internal_hash(left: &Bytes32, right: &Bytes32) -> Bytes32 {
+    let mut hasher = Sha256::new();
+    hasher.update([2_u8]);
+    hasher.update(left);
+    hasher.update(right);
+    let ret: [u8; 32] = hasher.finalize().into();
+    ret.into()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeSpecific {
+    Internal { left: TreeIndex, right: TreeIndex },
+    Leaf { key: KvId, value: KvId },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Node {
+    pub parent: Option<TreeIndex>,
+    pub hash: Bytes32,
+    pub specific: NodeSpecific,
+}
+
+impl Node {
+    fn from_bytes(buf: &[u8]) -> Result<Self, MerkleBlobError> {
+        let parent = TreeIndex::from_be_bytes(buf[1..5].try_into().unwrap());
+        let hash: [u8; 32] = buf[5..37].try_into().unwrap();
+        let specific = match buf[0] {
+            INTERNAL_NODE => NodeSpecific::Internal {
+                left: TreeIndex::from_be_bytes(buf[37..41].try_into().unwrap()),
+                right: TreeIndex::from_be_bytes(buf[41..45].try_into().unwrap()),
+            },
+            LEAF_NODE => NodeSpecific::Leaf {
+                key: KvId::from_be_bytes(buf[37..45].try_into().unwrap()),
+                value: KvId::from_be_bytes(buf[45..53].try_into().unwrap()),
+            },
+            t => {
+                return Err(MerkleBlobError::InvalidNodeType(t));
+            }
+        };
+        Ok(Self {
+            parent: if parent == NULL_PARENT {
+                None
+            } else {
+                Some(parent)
+            },
+            hash: hash.into(),
+            specific,
+        })
+    }
+
+    fn to_bytes(self) -> [u8; BLOCK_SIZE] {
+        let mut buf = [0_u8; BLOCK_SIZE];
+        buf[1..5].copy_from_slice(&self.parent.unwrap_or(NULL_PARENT).to_be_bytes());
+        buf[5..37].copy_from_slice(&self.hash);
+        match self.specific {
+            NodeSpecific::Internal { left, right } => {
+                buf[0] = INTERNAL_NODE;
+                buf[37..41].copy_from_slice(&left.to_be_bytes());
+                buf[41..45].copy_from_slice(&right.to_be_bytes());
+            }
+            NodeSpecific::Leaf { key, value } => {
+                buf[0] = LEAF_NODE;
+                buf[37..45].copy_from_slice(&key.to_be_bytes());
+                buf[45..53].copy_from_slice(&value.to_be_bytes());
+            }
+        }
+        buf
+    }
+}
+
+// the bit of the hash that determines which way to go at the specified depth,
+// when inserting a leaf. This keeps the tree balanced (in expectation).
+fn hash_bit(hash: &Bytes32, depth: usize) -> bool {
+    let depth = depth % 256;
+    (hash[depth / 8] & (0x80 >> (depth % 8))) != 0
+}
+
+#[cfg_attr(
+    feature = "py-bindings",
+    pyclass(name = "MerkleBlob", module = "gold_rs")
+)]
+#[derive(Debug, Clone, Default)]
+pub struct MerkleBlob {
+    blob: Vec<u8>,
+    // indices of blocks that aren't part of the tree, and can be reused
+    free_indexes: Vec<TreeIndex>,
+    key_to_index: HashMap<KvId, TreeIndex>,
+}
+
+impl MerkleBlob {
+    // loads a tree from its blob. Blocks that aren't reachable from the root
+    // are considered free
+    pub fn new(blob: Vec<u8>) -> Result<Self, MerkleBlobError> {
+        if blob.len() % BLOCK_SIZE != 0 {
+            return Err(MerkleBlobError::InvalidBlobLength(blob.len()));
+        }
+        let block_count = (blob.len() / BLOCK_SIZE) as TreeIndex;
+        let mut ret = Self {
+            blob,
+            free_indexes: Vec::new(),
+            key_to_index: HashMap::new(),
+        };
+        if block_count == 0 {
+            return Ok(ret);
+        }
+
+        let mut reachable = vec![false; block_count as usize];
+        let mut stack = vec![(0, None)];
+        while let Some((index, parent)) = stack.pop() {
+            let node = ret.get_node(index)?;
+            if node.parent != parent || reachable[index as usize] {
+                return Err(MerkleBlobError::InvalidTree(index));
+            }
+            reachable[index as usize] = true;
+            match node.specific {
+                NodeSpecific::Internal { left, right } => {
+                    stack.push((left, Some(index)));
+                    stack.push((right, Some(index)));
+                }
+                NodeSpecific::Leaf { key, .. } => {
+                    if ret.key_to_index.insert(key, index).is_some() {
+                        return Err(MerkleBlobError::KeyAlreadyPresent(key));
+                    }
+                }
+            }
+        }
+        ret.free_indexes = (0..block_count)
+            .filter(|i| !reachable[*i as usize])
+            .collect();
+        Ok(ret)
+    }
+
+    pub fn blob(&self) -> &[u8] {
+        &self.blob
+    }
+
+    // the number of leaves
+    pub fn len(&self) -> usize {
+        self.key_to_index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.key_to_index.is_empty()
+    }
+
+    pub fn get_node(&self, index: TreeIndex) -> Result<Node, MerkleBlobError> {
+        let start = index as usize * BLOCK_SIZE;
+        let Some(buf) = self.blob.get(start..start + BLOCK_SIZE) else {
+            return Err(MerkleBlobError::IndexOutOfRange(index));
+        };
+        Node::from_bytes(buf)
+    }
+
+    fn set_node(&mut self, index: TreeIndex, node: Node) {
+        let start = index as usize * BLOCK_SIZE;
+        self.blob[start..start + BLOCK_SIZE].copy_from_slice(&node.to_bytes());
+    }
+
+    fn allocate(&mut self) -> TreeIndex {
+        if let Some(index) = self.free_indexes.pop() {
+            return index;
+        }
+        let index = (self.blob.len() / BLOCK_SIZE) as TreeIndex;
+        self.blob.resize(self.blob.len() + BLOCK_SIZE, 0);
+        index
+    }
+
+    pub fn key_to_index(&self, key: KvId) -> Option<TreeIndex> {
+        self.key_to_index.get(&key).copied()
+    }
+
+    pub fn get_root_hash(&self) -> Option<Bytes32> {
+        if self.is_empty() {
+            return None;
+        }
+        self.get_node(0).ok().map(|n| n.hash)
+    }
+
+    pub fn get_keys_values(&self) -> Result<HashMap<KvId, KvId>, MerkleBlobError> {
+        let mut ret = HashMap::with_capacity(self.key_to_index.len());
+        for index in self.key_to_index.values() {
+            if let NodeSpecific::Leaf { key, value } = self.get_node(*index)?.specific {
+                ret.insert(key, value);
+            }
+        }
+        Ok(ret)
+    }
+
+    // recomputes the hashes of the node at index and all its ancestors
+    fn update_hashes(&mut self, mut index: TreeIndex) -> Result<(), MerkleBlobError> {
+        loop {
+            let mut node = self.get_node(index)?;
+            if let NodeSpecific::Internal { left, right } = node.specific {
+                let left_hash = self.get_node(left)?.hash;
+                let right_hash = self.get_node(right)?.hash;
+                node.hash = internal_hash(&left_hash, &right_hash);
+                self.set_node(index, node);
+            }
+            match node.parent {
+                Some(parent) => index = parent,
+                None => return Ok(()),
+            }
+        }
+    }
+
+    fn replace_child(
+        &mut self,
+        parent: TreeIndex,
+        old_child: TreeIndex,
+        new_child: TreeIndex,
+    ) -> Result<(), MerkleBlobError> {
+        let mut node = self.get_node(parent)?;
+        let NodeSpecific::Internal { left, right } = node.specific else {
+            return Err(MerkleBlobError::InvalidTree(parent));
+        };
+        node.specific = if left == old_child {
+            NodeSpecific::Internal {
+                left: new_child,
+                right,
+            }
+        } else if right == old_child {
+            NodeSpecific::Internal {
+                left,
+                right: new_child,
+            }
+        } else {
+            return Err(MerkleBlobError::InvalidTree(parent));
+        };
+        self.set_node(parent, node);
+        Ok(())
+    }
+
+    // sets the parent of the node at index. Leaves have their location
+    // recorded in the key index, so this has to be updated when a leaf moves
+    fn set_parent(
+        &mut self,
+        index: TreeIndex,
+        parent: Option<TreeIndex>,
+    ) -> Result<(), MerkleBlobError> {
+        let mut node = self.get_node(index)?;
+        node.parent = parent;
+        self.set_node(index, node);
+        if let NodeSpecific::Leaf { key, .. } = node.specific {
+            self.key_to_index.insert(key, index);
+        }
+        Ok(())
+    }
+
+    // inserts a new leaf. The bits of its hash determine where in the tree it
+    // ends up.
+    pub fn insert(&mut self, key: KvId, value: KvId, hash: Bytes32) -> Result<(), MerkleBlobError> {
+        if self.key_to_index.contains_key(&key) {
+            return Err(MerkleBlobError::KeyAlreadyPresent(key));
+        }
+
+        let leaf = NodeSpecific::Leaf { key, value };
+
+        if self.is_empty() {
+            self.blob.clear();
+            self.free_indexes.clear();
+            let index = self.allocate();
+            self.set_node(
+                index,
+                Node {
+                    parent: None,
+                    hash,
+                    specific: leaf,
+                },
+            );
+            self.key_to_index.insert(key, index);
+            return Ok(());
+        }
+
+        // find the leaf to pair the new leaf with
+        let mut index: TreeIndex = 0;
+        let mut depth = 0;
+        let mut sibling = self.get_node(index)?;
+        while let NodeSpecific::Internal { left, right } = sibling.specific {
+            index = if hash_bit(&hash, depth) { right } else { left };
+            depth += 1;
+            sibling = self.get_node(index)?;
+        }
+
+        let new_leaf_index = self.allocate();
+        // the root must stay at index 0, so if the sibling is the root, it's
+        // moved to make room for the new internal node
+        let (parent_index, sibling_index) = if index == 0 {
+            (0, self.allocate())
+        } else {
+            (self.allocate(), index)
+        };
+
+        let (left, right) = if hash_bit(&hash, depth) {
+            (sibling_index, new_leaf_index)
+        } else {
+            (new_leaf_index, sibling_index)
+        };
+
+        self.set_node(
+            sibling_index,
+            Node {
+                parent: Some(parent_index),
+                ..sibling
+            },
+        );
+        if let NodeSpecific::Leaf { key, .. } = sibling.specific {
+            self.key_to_index.insert(key, sibling_index);
+        }
+        self.set_node(
+            new_leaf_index,
+            Node {
+                parent: Some(parent_index),
+                hash,
+                specific: leaf,
+            },
+        );
+        self.key_to_index.insert(key, new_leaf_index);
+        self.set_node(
+            parent_index,
+            Node {
+                parent: sibling.parent,
+                hash: Bytes32::default(),
+                specific: NodeSpecific::Internal { left, right },
+            },
+        );
+        if let Some(grandparent) = sibling.parent {
+            self.replace_child(grandparent, sibling_index, parent_index)?;
+        }
+        self.update_hashes(parent_index)
+    }
+
+    pub fn delete(&mut self, key: KvId) -> Result<(), MerkleBlobError> {
+        let Some(leaf_index) = self.key_to_index.remove(&key) else {
+            return Err(MerkleBlobError::UnknownKey(key));
+        };
+        let leaf = self.get_node(leaf_index)?;
+
+        let Some(parent_index) = leaf.parent else {
+            // this was the last leaf
+            self.blob.clear();
+            self.free_indexes.clear();
+            return Ok(());
+        };
+        let parent = self.get_node(parent_index)?;
+        let NodeSpecific::Internal { left, right } = parent.specific else {
+            return Err(MerkleBlobError::InvalidTree(parent_index));
+        };
+        let sibling_index = if left == leaf_index { right } else { left };
+        let sibling = self.get_node(sibling_index)?;
+
+        self.free_indexes.push(leaf_index);
+
+        match parent.parent {
+            None => {
+                // the parent is the root. The sibling becomes the new root,
+                // which means it has to move to index 0
+                self.set_node(
+                    0,
+                    Node {
+                        parent: None,
+                        ..sibling
+                    },
+                );
+                match sibling.specific {
+                    NodeSpecific::Internal { left, right } => {
+                        self.set_parent(left, Some(0))?;
+                        self.set_parent(right, Some(0))?;
+                    }
+                    NodeSpecific::Leaf { key, .. } => {
+                        self.key_to_index.insert(key, 0);
+                    }
+                }
+                self.free_indexes.push(sibling_index);
+                Ok(())
+            }
+            Some(grandparent_index) => {
+                self.set_parent(sibling_index, Some(grandparent_index))?;
+                self.replace_child(grandparent_index, parent_index, sibling_index)?;
+                self.free_indexes.push(parent_index);
+                self.update_hashes(grandparent_index)
+            }
+        }
+    }
+
+    pub fn get_proof_of_inclusion(&self, key: KvId) -> Result<ProofOfInclusion, MerkleBlobError> {
+        let Some(mut index) = self.key_to_index(key) else {
+            return Err(MerkleBlobError::UnknownKey(key));
+        };
+        let node = self.get_node(index)?;
+        let mut layers = Vec::<ProofOfInclusionLayer>::new();
+        let mut parent = node.parent;
+        while let Some(parent_index) = parent {
+            let parent_node = self.get_node(parent_index)?;
+            let NodeSpecific::Internal { left, right } = parent_node.specific else {
+                return Err(MerkleBlobError::InvalidTree(parent_index));
+            };
+            let (other_hash_side, other_index) = if left == index {
+                (Side::Right, right)
+            } else {
+                (Side::Left, left)
+            };
+            layers.push(ProofOfInclusionLayer {
+                other_hash_side,
+                other_hash: self.get_node(other_index)?.hash,
+                combined_hash: parent_node.hash,
+            });
+            index = parent_index;
+            parent = parent_node.parent;
+        }
+        Ok(ProofOfInclusion {
+            node_hash: node.hash,
+            layers,
+        })
+    }
+}
+
+#[repr(u8)]
+#[cfg_attr(feature = "py-bindings", derive(PyJsonDict, PyStreamable))]
+#[derive(Streamable, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Side {
+    Left = 0,
+    Right = 1,
+}
+
+#[cfg(feature = "py-bindings")]
+impl chia_traits::ChiaToPython for Side {
+    fn to_python<'a>(&self, py: pyo3::Python<'a>) -> pyo3::PyResult<pyo3::Bound<'a, pyo3::PyAny>> {
+        Ok(pyo3::IntoPy::into_py(*self, py).bind(py).clone())
+    }
+}
+
+#[cfg_attr(
+    feature = "py-bindings",
+    pyo3::pyclass(module = "gold_rs", frozen),
+    derive(PyJsonDict, PyStreamable, PyGetters)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[streamable]
+pub struct ProofOfInclusionLayer {
+    other_hash_side: Side,
+    other_hash: Bytes32,
+    combined_hash: Bytes32,
+}
+
+#[cfg_attr(
+    feature = "py-bindings",
+    pyo3::pyclass(module = "gold_rs", frozen),
+    derive(PyJsonDict, PyStreamable, PyGetters)
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[streamable]
+pub struct ProofOfInclusion {
+    node_hash: Bytes32,
+    // the layers, from the leaf up to the root
+    layers: Vec<ProofOfInclusionLayer>,
+}
+
+impl ProofOfInclusion {
+    pub fn root_hash(&self) -> Bytes32 {
+        self.layers
+            .last()
+            .map_or(self.node_hash, |layer| layer.combined_hash)
+    }
+
+    pub fn valid(&self) -> bool {
+        let mut existing_hash = self.node_hash;
+        for layer in &self.layers {
+            let calculated = match layer.other_hash_side {
+                Side::Right => internal_hash(&existing_hash, &layer.other_hash),
+                Side::Left => internal_hash(&layer.other_hash, &existing_hash),
+            };
+            if calculated != layer.combined_hash {
+                return false;
+            }
+            existing_hash = calculated;
+        }
+        existing_hash == self.root_hash()
+    }
+}
+
+#[cfg(feature = "py-bindings")]
+#[pymethods]
+impl ProofOfInclusion {
+    #[pyo3(name = "root_hash")]
+    fn py_root_hash(&self) -> Bytes32 {
+        self.root_hash()
+    }
+
+    #[pyo3(name = "valid")]
+    fn py_valid(&self) -> bool {
+        self.valid()
+    }
+}
+
+#[cfg(feature = "py-bindings")]
+#[pymethods]
+impl MerkleBlob {
+    #[new]
+    #[pyo3(signature = (blob = Vec::new()))]
+    fn py_new(blob: Vec<u8>) -> PyResult<Self> {
+        Ok(Self::new(blob)?)
+    }
+
+    #[pyo3(name = "insert")]
+    fn py_insert(&mut self, key: KvId, value: KvId, hash: Bytes32) -> PyResult<()> {
+        Ok(self.insert(key, value, hash)?)
+    }
+
+    #[pyo3(name = "delete")]
+    fn py_delete(&mut self, key: KvId) -> PyResult<()> {
+        Ok(self.delete(key)?)
+    }
+
+    #[pyo3(name = "get_root_hash")]
+    fn py_get_root_hash(&self) -> Option<Bytes32> {
+        self.get_root_hash()
+    }
+
+    #[pyo3(name = "get_proof_of_inclusion")]
+    fn py_get_proof_of_inclusion(&self, key: KvId) -> PyResult<ProofOfInclusion> {
+        Ok(self.get_proof_of_inclusion(key)?)
+    }
+
+    #[pyo3(name = "get_keys_values")]
+    fn py_get_keys_values(&self) -> PyResult<HashMap<KvId, KvId>> {
+        Ok(self.get_keys_values()?)
+    }
+
+    #[pyo3(name = "get_blob")]
+    fn py_get_blob<'a>(&self, py: Python<'a>) -> Bound<'a, PyBytes> {
+        PyBytes::new_bound(py, &self.blob)
+    }
+
+    fn __len__(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf_hash(key: KvId, value: KvId) -> Bytes32 {
+        let mut hasher = Sha256::new();
+        hasher.update(key.to_be_bytes());
+        hasher.update(value.to_be_bytes());
+        let ret: [u8; 32] = hasher.finalize().into();
+        ret.into()
+    }
+
+    // checks the structure, hashes and key index of the tree
+    fn check(blob: &MerkleBlob) {
+        if blob.is_empty() {
+            assert_eq!(blob.get_root_hash(), None);
+            return;
+        }
+        let mut leaves = 0;
+        let mut stack = vec![(0, None)];
+        while let Some((index, parent)) = stack.pop() {
+            let node = blob.get_node(index).unwrap();
+            assert_eq!(node.parent, parent);
+            match node.specific {
+                NodeSpecific::Internal { left, right } => {
+                    let left_hash = blob.get_node(left).unwrap().hash;
+                    let right_hash = blob.get_node(right).unwrap().hash;
+                    assert_eq!(node.hash, internal_hash(&left_hash, &right_hash));
+                    stack.push((left, Some(index)));
+                    stack.push((right, Some(index)));
+                }
+                NodeSpecific::Leaf { key, value } => {
+                    assert_eq!(node.hash, leaf_hash(key, value));
+                    assert_eq!(blob.key_to_index(key), Some(index));
+                    leaves += 1;
+                }
+            }
+        }
+        assert_eq!(leaves, blob.len());
+
+        // loading the blob gives us the same tree
+        let reloaded = MerkleBlob::new(blob.blob().to_vec()).unwrap();
+        assert_eq!(reloaded.get_root_hash(), blob.get_root_hash());
+        assert_eq!(reloaded.key_to_index, blob.key_to_index);
+    }
+
+    #[test]
+    fn test_insert_delete() {
+        let mut blob = MerkleBlob::default();
+        check(&blob);
+
+        for key in 0..100 {
+            blob.insert(key, key * 10, leaf_hash(key, key * 10))
+                .unwrap();
+            check(&blob);
+        }
+        assert_eq!(blob.len(), 100);
+        assert_eq!(
+            blob.insert(5, 0, leaf_hash(5, 0)).unwrap_err(),
+            MerkleBlobError::KeyAlreadyPresent(5)
+        );
+
+        let kv = blob.get_keys_values().unwrap();
+        assert_eq!(kv.len(), 100);
+        assert_eq!(kv[&42], 420);
+
+        for key in (0..100).step_by(3) {
+            blob.delete(key).unwrap();
+            check(&blob);
+        }
+        assert_eq!(blob.delete(0).unwrap_err(), MerkleBlobError::UnknownKey(0));
+
+        // freed blocks are reused
+        let size = blob.blob().len();
+        for key in (0..100).step_by(3) {
+            blob.insert(key, key, leaf_hash(key, key)).unwrap();
+            check(&blob);
+        }
+        assert_eq!(blob.blob().len(), size);
+
+        for key in 0..100 {
+            blob.delete(key).unwrap();
+            check(&blob);
+        }
+        assert!(blob.is_empty());
+        assert!(blob.blob().is_empty());
+    }
+
+    #[test]
+    fn test_root_hash_order_independent_for_single_pair() {
+        let mut a = MerkleBlob::default();
+        a.insert(1, 1, leaf_hash(1, 1)).unwrap();
+        assert_eq!(a.get_root_hash(), Some(leaf_hash(1, 1)));
+        a.insert(2, 2, leaf_hash(2, 2)).unwrap();
+
+        let mut b = MerkleBlob::default();
+        b.insert(2, 2, leaf_hash(2, 2)).unwrap();
+        b.insert(1, 1, leaf_hash(1, 1)).unwrap();
+
+        // the order of the children only depends on the hash of the inserted
+        // leaf, so we can't expect these to be the same in general. But they
+        // both must be one of the two possible trees
+        let h1 = leaf_hash(1, 1);
+        let h2 = leaf_hash(2, 2);
+        let candidates = [internal_hash(&h1, &h2), internal_hash(&h2, &h1)];
+        assert!(candidates.contains(&a.get_root_hash().unwrap()));
+        assert!(candidates.contains(&b.get_root_hash().unwrap()));
+    }
+
+    #[test]
+    fn test_proof_of_inclusion() {
+        let mut blob = MerkleBlob::default();
+        for key in 0..50 {
+            blob.insert(key, key, leaf_hash(key, key)).unwrap();
+        }
+        let root = blob.get_root_hash().unwrap();
+        for key in 0..50 {
+            let proof = blob.get_proof_of_inclusion(key).unwrap();
+            assert_eq!(proof.node_hash, leaf_hash(key, key));
+            assert_eq!(proof.root_hash(), root);
+            assert!(proof.valid());
+
+            let mut bad = proof.clone();
+            bad.node_hash = leaf_hash(key, key + 1);
+            assert!(!bad.valid());
+        }
+        assert_eq!(
+            blob.get_proof_of_inclusion(50).unwrap_err(),
+            MerkleBlobError::UnknownKey(50)
+        );
+
+        // a tree with a single leaf
+        let mut blob = MerkleBlob::default();
+        blob.insert(1, 1, leaf_hash(1, 1)).unwrap();
+        let proof = blob.get_proof_of_inclusion(1).unwrap();
+        assert!(proof.layers.is_empty());
+        assert_eq!(proof.root_hash(), leaf_hash(1, 1));
+        assert!(proof.valid());
+    }
+
+    #[test]
+    fn test_invalid_blob() {
+        assert_eq!(
+            MerkleBlob::new(vec![0; BLOCK_SIZE + 1]).unwrap_err(),
+            MerkleBlobError::InvalidBlobLength(BLOCK_SIZE + 1)
+        );
+        let mut buf = [0_u8; BLOCK_SIZE];
+        buf[0] = 7;
+        assert_eq!(
+            MerkleBlob::new(buf.to_vec()).unwrap_err(),
+            MerkleBlobError::InvalidNodeType(7)
+        );
+        // a root whose parent isn't null
+        let node = Node {
+            parent: Some(1),
+            hash: Bytes32::default(),
+            specific: NodeSpecific::Leaf { key: 1, value: 1 },
+        };
+        assert_eq!(
+            MerkleBlob::new(node.to_bytes().to_vec()).unwrap_err(),
+            MerkleBlobError::InvalidTree(0)
+        );
+    }
+}
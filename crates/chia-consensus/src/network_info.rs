@@ -0,0 +1,243 @@
+use crate::consensus_constants::ConsensusConstants;
+use crate::gen::signing::AggSigNetwork;
+use chia_protocol::Bytes32;
+use hex_literal::hex;
+
+#[cfg(feature = "py-bindings")]
+use crate::gen::{
+    opcodes::ConditionOpcode,
+    owned_conditions::OwnedSpendBundleConditions,
+    signing::{agg_sig_additional_data, messages_to_sign},
+};
+#[cfg(feature = "py-bindings")]
+use chia_bls::PublicKey;
+#[cfg(feature = "py-bindings")]
+use chia_protocol::Bytes;
+#[cfg(feature = "py-bindings")]
+use pyo3::{basic::CompareOp, prelude::*};
+
+const MAINNET_GENESIS_CHALLENGE: Bytes32 = Bytes32::new(hex!(
+    "ccd5bb71183532bff220ba46c268991a3ff07eb358e8255a65c30a2dce0e5fbb"
+));
+
+const TESTNET11_GENESIS_CHALLENGE: Bytes32 = Bytes32::new(hex!(
+    "37a90eb5185a9c4439a91ddc98bbadce7b4feba060d50116a067de66bf236615"
+));
+
+// Everything that identifies a network: its ID (as sent in the handshake),
+// the genesis challenge, the data signatures are bound to and the prefix of
+// its addresses. Signing messages through a NetworkInfo (rather than passing
+// around the AGG_SIG_ME additional data by itself) ensures a signature is
+// always made for the network the other properties refer to.
+#[cfg_attr(feature = "py-bindings", pyclass(module = "gold_rs", frozen, get_all))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NetworkInfo {
+    pub network_id: String,
+    pub genesis_challenge: Bytes32,
+    pub agg_sig_me_additional_data: Bytes32,
+    pub address_prefix: String,
+}
+
+impl NetworkInfo {
+    pub fn new(
+        network_id: &str,
+        genesis_challenge: Bytes32,
+        agg_sig_me_additional_data: Bytes32,
+        address_prefix: &str,
+    ) -> Self {
+        Self {
+            network_id: network_id.to_string(),
+            genesis_challenge,
+            agg_sig_me_additional_data,
+            address_prefix: address_prefix.to_string(),
+        }
+    }
+
+    // the main network and the current test network both use their genesis
+    // challenge as the AGG_SIG_ME additional data
+    pub fn mainnet() -> Self {
+        Self::new(
+            "mainnet",
+            MAINNET_GENESIS_CHALLENGE,
+            MAINNET_GENESIS_CHALLENGE,
+            "xch",
+        )
+    }
+
+    pub fn testnet11() -> Self {
+        Self::new(
+            "testnet11",
+            TESTNET11_GENESIS_CHALLENGE,
+            TESTNET11_GENESIS_CHALLENGE,
+            "txch",
+        )
+    }
+
+    // the preset with the specified network ID, if any
+    pub fn from_network_id(network_id: &str) -> Option<Self> {
+        match network_id {
+            "mainnet" => Some(Self::mainnet()),
+            "testnet11" => Some(Self::testnet11()),
+            _ => None,
+        }
+    }
+
+    // the network of a set of consensus constants, which don't include a
+    // network ID or address prefix
+    pub fn from_constants(
+        network_id: &str,
+        constants: &ConsensusConstants,
+        address_prefix: &str,
+    ) -> Self {
+        Self::new(
+            network_id,
+            constants.genesis_challenge,
+            constants.agg_sig_me_additional_data,
+            address_prefix,
+        )
+    }
+
+    // returns true if the consensus constants are the ones of this network.
+    // A node should refuse to run with constants that don't match the network
+    // it's connecting to
+    pub fn matches_constants(&self, constants: &ConsensusConstants) -> bool {
+        self.genesis_challenge == constants.genesis_challenge
+            && self.agg_sig_me_additional_data == constants.agg_sig_me_additional_data
+    }
+}
+
+impl AggSigNetwork for NetworkInfo {
+    fn agg_sig_me_additional_data(&self) -> Bytes32 {
+        self.agg_sig_me_additional_data
+    }
+}
+
+#[cfg(feature = "py-bindings")]
+#[pymethods]
+impl NetworkInfo {
+    #[new]
+    fn py_new(
+        network_id: &str,
+        genesis_challenge: Bytes32,
+        agg_sig_me_additional_data: Bytes32,
+        address_prefix: &str,
+    ) -> Self {
+        Self::new(
+            network_id,
+            genesis_challenge,
+            agg_sig_me_additional_data,
+            address_prefix,
+        )
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "mainnet")]
+    fn py_mainnet() -> Self {
+        Self::mainnet()
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "testnet11")]
+    fn py_testnet11() -> Self {
+        Self::testnet11()
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "from_network_id")]
+    fn py_from_network_id(network_id: &str) -> Option<Self> {
+        Self::from_network_id(network_id)
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "from_constants")]
+    fn py_from_constants(
+        network_id: &str,
+        constants: &ConsensusConstants,
+        address_prefix: &str,
+    ) -> Self {
+        Self::from_constants(network_id, constants, address_prefix)
+    }
+
+    #[pyo3(name = "matches_constants")]
+    fn py_matches_constants(&self, constants: &ConsensusConstants) -> bool {
+        self.matches_constants(constants)
+    }
+
+    #[pyo3(name = "agg_sig_additional_data")]
+    fn py_agg_sig_additional_data(&self, opcode: ConditionOpcode) -> Option<Bytes32> {
+        agg_sig_additional_data(self, opcode)
+    }
+
+    #[pyo3(name = "messages_to_sign")]
+    fn py_messages_to_sign(&self, conds: &OwnedSpendBundleConditions) -> Vec<(PublicKey, Bytes)> {
+        messages_to_sign(conds, self)
+    }
+
+    fn __richcmp__(&self, other: &Self, op: CompareOp, py: Python<'_>) -> PyObject {
+        match op {
+            CompareOp::Eq => (self == other).into_py(py),
+            CompareOp::Ne => (self != other).into_py(py),
+            _ => py.NotImplemented(),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus_constants::TEST_CONSTANTS;
+    use crate::gen::opcodes::{AGG_SIG_ME, AGG_SIG_PARENT};
+    use crate::gen::signing::agg_sig_additional_data;
+
+    #[test]
+    fn test_presets() {
+        assert_eq!(
+            NetworkInfo::from_network_id("mainnet"),
+            Some(NetworkInfo::mainnet())
+        );
+        assert_eq!(
+            NetworkInfo::from_network_id("testnet11"),
+            Some(NetworkInfo::testnet11())
+        );
+        assert_eq!(NetworkInfo::from_network_id("testnet10"), None);
+        assert_eq!(NetworkInfo::mainnet().address_prefix, "xch");
+        assert_eq!(NetworkInfo::testnet11().address_prefix, "txch");
+    }
+
+    #[test]
+    fn test_domain_separation() {
+        let mainnet = NetworkInfo::mainnet();
+        let testnet = NetworkInfo::testnet11();
+        for opcode in [AGG_SIG_ME, AGG_SIG_PARENT] {
+            assert_ne!(
+                agg_sig_additional_data(&mainnet, opcode),
+                agg_sig_additional_data(&testnet, opcode)
+            );
+        }
+
+        // the test constants use the mainnet AGG_SIG_ME additional data, so
+        // they sign the same messages
+        let test_network = NetworkInfo::from_constants("simulator0", &TEST_CONSTANTS, "txch");
+        assert_eq!(
+            agg_sig_additional_data(&test_network, AGG_SIG_PARENT),
+            agg_sig_additional_data(&TEST_CONSTANTS, AGG_SIG_PARENT)
+        );
+        assert_eq!(
+            agg_sig_additional_data(&test_network, AGG_SIG_PARENT),
+            agg_sig_additional_data(&mainnet, AGG_SIG_PARENT)
+        );
+    }
+
+    #[test]
+    fn test_matches_constants() {
+        let test_network = NetworkInfo::from_constants("simulator0", &TEST_CONSTANTS, "txch");
+        assert!(test_network.matches_constants(&TEST_CONSTANTS));
+        // the genesis challenge of the test constants isn't mainnet's
+        assert!(!NetworkInfo::mainnet().matches_constants(&TEST_CONSTANTS));
+        assert!(!NetworkInfo::testnet11().matches_constants(&TEST_CONSTANTS));
+    }
+}
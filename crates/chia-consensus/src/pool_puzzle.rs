@@ -0,0 +1,382 @@
+use crate::error::{Error, Result};
+use chia_bls::PublicKey;
+use chia_protocol::{Bytes32, CoinSpend, PoolSingletonState, PoolState};
+use chia_puzzles::singleton::{
+    LauncherSolution, SingletonArgs, SingletonSolution, SINGLETON_LAUNCHER_PUZZLE_HASH,
+    SINGLETON_TOP_LAYER_PUZZLE_HASH,
+};
+use chia_streamable_macro::streamable;
+use chia_traits::Streamable;
+use clvm_traits::{FromClvm, ToClvm, ToNodePtr};
+use clvm_utils::{tree_hash, CurriedProgram, TreeHash};
+use clvmr::allocator::{Allocator, NodePtr, SExp};
+use hex_literal::hex;
+
+#[cfg(feature = "py-bindings")]
+use chia_py_streamable_macro::{PyGetters, PyJsonDict, PyStreamable};
+
+// the mod hashes of pool_member_innerpuz.clsp and pool_waitingroom_innerpuz.clsp
+pub const POOL_MEMBER_INNERPUZ_HASH: TreeHash = TreeHash::new(hex!(
+    "a8490702e333ddd831a3ac9c22d0fa26d2bfeaf2d33608deb22f0e0123eb0494"
+));
+pub const POOL_WAITINGROOM_INNERPUZ_HASH: TreeHash = TreeHash::new(hex!(
+    "a317541a765bf8375e1c6e7c13503d0d2cbf56cacad5182befe947e78e2c0307"
+));
+
+// The curried arguments of the two inner puzzles of a plotNFT, the pool member
+// puzzle and the pool waiting room puzzle. They only differ in the last
+// argument, which is the escape puzzle hash (the waiting room puzzle the
+// member puzzle travels to) for the member puzzle and the relative lock height
+// for the waiting room puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToClvm, FromClvm)]
+#[clvm(curry)]
+pub struct PoolInnerPuzzleArgs<T> {
+    pub target_puzzle_hash: Bytes32,
+    pub p2_singleton_puzzle_hash: Bytes32,
+    pub owner_pubkey: PublicKey,
+    pub pool_reward_prefix: Bytes32,
+    pub last: T,
+}
+
+// The inner puzzle of a plotNFT, parsed from its puzzle reveal
+#[cfg_attr(
+    feature = "py-bindings",
+    pyo3::pyclass(module = "gold_rs", frozen),
+    derive(PyJsonDict, PyStreamable, PyGetters)
+)]
+#[streamable]
+pub struct PoolInnerPuzzle {
+    launcher_id: Bytes32,
+    // LEAVING_POOL for the waiting room puzzle, otherwise FARMING_TO_POOL. The
+    // member puzzle is also used when self pooling, the PoolState in the
+    // solution tells the two apart
+    state: u8,
+    target_puzzle_hash: Bytes32,
+    p2_singleton_puzzle_hash: Bytes32,
+    owner_pubkey: PublicKey,
+    pool_reward_prefix: Bytes32,
+    // only set for the member puzzle
+    escape_puzzle_hash: Option<Bytes32>,
+    // only set for the waiting room puzzle
+    relative_lock_height: Option<u32>,
+}
+
+// parses the full puzzle reveal of a plotNFT (a singleton whose inner puzzle
+// is the pool member or the pool waiting room puzzle)
+pub fn parse_pool_puzzle(a: &Allocator, puzzle: NodePtr) -> Result<PoolInnerPuzzle> {
+    parse_pool_puzzle_with_mods(
+        a,
+        puzzle,
+        POOL_MEMBER_INNERPUZ_HASH,
+        POOL_WAITINGROOM_INNERPUZ_HASH,
+    )
+}
+
+// the inner mod hashes are passed in to let the tests use their own mods
+fn parse_pool_puzzle_with_mods(
+    a: &Allocator,
+    puzzle: NodePtr,
+    member_mod_hash: TreeHash,
+    waiting_room_mod_hash: TreeHash,
+) -> Result<PoolInnerPuzzle> {
+    let curried = CurriedProgram::<NodePtr, NodePtr>::from_clvm(a, puzzle)
+        .map_err(|_| Error::NotPoolPuzzle)?;
+    if tree_hash(a, curried.program) != SINGLETON_TOP_LAYER_PUZZLE_HASH {
+        return Err(Error::NotPoolPuzzle);
+    }
+    let singleton = SingletonArgs::<NodePtr>::from_clvm(a, curried.args)?;
+    if singleton.singleton_struct.mod_hash != SINGLETON_TOP_LAYER_PUZZLE_HASH.into() {
+        return Err(Error::NotSingletonModHash);
+    }
+
+    let inner = CurriedProgram::<NodePtr, PoolInnerPuzzleArgs<NodePtr>>::from_clvm(
+        a,
+        singleton.inner_puzzle,
+    )
+    .map_err(|_| Error::NotPoolPuzzle)?;
+    let args = inner.args;
+
+    // the last argument is the escape puzzle hash for the member puzzle and
+    // the relative lock height for the waiting room puzzle. Any other inner
+    // puzzle is not a plotNFT, even if its curried arguments look like one
+    let mod_hash = tree_hash(a, inner.program);
+    let (state, escape_puzzle_hash, relative_lock_height) = if mod_hash == member_mod_hash {
+        (
+            PoolSingletonState::FarmingToPool,
+            Some(Bytes32::from_clvm(a, args.last).map_err(|_| Error::NotPoolPuzzle)?),
+            None,
+        )
+    } else if mod_hash == waiting_room_mod_hash {
+        (
+            PoolSingletonState::LeavingPool,
+            None,
+            Some(u32::from_clvm(a, args.last).map_err(|_| Error::NotPoolPuzzle)?),
+        )
+    } else {
+        return Err(Error::NotPoolPuzzle);
+    };
+
+    Ok(PoolInnerPuzzle {
+        launcher_id: singleton.singleton_struct.launcher_id,
+        state: state as u8,
+        target_puzzle_hash: args.target_puzzle_hash,
+        p2_singleton_puzzle_hash: args.p2_singleton_puzzle_hash,
+        owner_pubkey: args.owner_pubkey,
+        pool_reward_prefix: args.pool_reward_prefix,
+        escape_puzzle_hash,
+        relative_lock_height,
+    })
+}
+
+// The pool state is stored under the key "p" in a key-value list, like the
+// one in the launcher solution. Returns None if there is no (valid) pool state
+pub fn pool_state_from_extra_data(a: &Allocator, extra_data: NodePtr) -> Option<PoolState> {
+    let mut next = extra_data;
+    while let SExp::Pair(item, rest) = a.sexp(next) {
+        next = rest;
+        let SExp::Pair(key, value) = a.sexp(item) else {
+            return None;
+        };
+        if !matches!(a.sexp(key), SExp::Atom) || a.atom(key).as_ref() != b"p" {
+            continue;
+        }
+        let SExp::Atom = a.sexp(value) else {
+            return None;
+        };
+        return PoolState::from_bytes(a.atom(value).as_ref()).ok();
+    }
+    None
+}
+
+fn is_nil(a: &Allocator, node: NodePtr) -> bool {
+    matches!(a.sexp(node), SExp::Atom) && a.atom_len(node) == 0
+}
+
+// like solution_to_pool_state() in chia.pools.pool_puzzles. Returns the pool
+// state a plotNFT spend sets, if any. Only the launcher spend and travel
+// spends set the pool state, absorb spends (claiming pool rewards) don't
+pub fn solution_to_pool_state(a: &mut Allocator, spend: &CoinSpend) -> Result<Option<PoolState>> {
+    let solution = spend.solution.to_node_ptr(a)?;
+
+    if spend.coin.puzzle_hash == SINGLETON_LAUNCHER_PUZZLE_HASH.into() {
+        let solution = LauncherSolution::<NodePtr>::from_clvm(a, solution)?;
+        return Ok(pool_state_from_extra_data(a, solution.key_value_list));
+    }
+
+    let solution = SingletonSolution::<Vec<NodePtr>>::from_clvm(a, solution)?;
+    match solution.inner_solution.as_slice() {
+        // the member puzzle: (extra_data pool_reward_height). Absorb spends
+        // have a pool reward height and an atom in place of the extra data
+        [extra_data, pool_reward_height] => {
+            if !is_nil(a, *pool_reward_height) {
+                return Ok(None);
+            }
+            if let SExp::Atom = a.sexp(*extra_data) {
+                return Ok(None);
+            }
+            Ok(pool_state_from_extra_data(a, *extra_data))
+        }
+        // the waiting room puzzle: (spend_type extra_data pool_reward_height).
+        // A spend type of 0 means absorb
+        [spend_type, extra_data, _] => {
+            if is_nil(a, *spend_type) {
+                return Ok(None);
+            }
+            Ok(pool_state_from_extra_data(a, *extra_data))
+        }
+        _ => Err(Error::NotPoolPuzzle),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chia_bls::SecretKey;
+    use chia_protocol::{Coin, Program};
+    use clvm_traits::FromNodePtr;
+    use clvmr::serde::node_from_bytes;
+    use rstest::rstest;
+
+    fn owner_pubkey() -> PublicKey {
+        SecretKey::from_seed(&[1; 32]).public_key()
+    }
+
+    fn pool_state(state: PoolSingletonState) -> PoolState {
+        PoolState::new(
+            1,
+            state as u8,
+            Bytes32::new([0x11; 32]),
+            owner_pubkey(),
+            Some("https://pool.example.com".to_string()),
+            100,
+        )
+    }
+
+    // the tests stand in the atoms 1 and 2 for the member and the waiting
+    // room mods
+    fn parse(a: &Allocator, puzzle: NodePtr) -> Result<PoolInnerPuzzle> {
+        parse_pool_puzzle_with_mods(
+            a,
+            puzzle,
+            clvm_utils::tree_hash_atom(&[1]),
+            clvm_utils::tree_hash_atom(&[2]),
+        )
+    }
+
+    fn plot_nft(a: &mut Allocator, inner_mod: u8, last: NodePtr) -> NodePtr {
+        let program = a.new_atom(&[inner_mod]).unwrap();
+        let inner = CurriedProgram {
+            program,
+            args: PoolInnerPuzzleArgs {
+                target_puzzle_hash: Bytes32::new([0x11; 32]),
+                p2_singleton_puzzle_hash: Bytes32::new([0x22; 32]),
+                owner_pubkey: owner_pubkey(),
+                pool_reward_prefix: Bytes32::new([0x33; 32]),
+                last,
+            },
+        }
+        .to_clvm(a)
+        .unwrap();
+        let program =
+            node_from_bytes(a, &chia_puzzles::singleton::SINGLETON_TOP_LAYER_PUZZLE).unwrap();
+        CurriedProgram {
+            program,
+            args: SingletonArgs::new(Bytes32::new([0x44; 32]), inner),
+        }
+        .to_clvm(a)
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_member_puzzle() {
+        let mut a = Allocator::new();
+        let escape = Bytes32::new([0x55; 32]).to_clvm(&mut a).unwrap();
+        let puzzle = plot_nft(&mut a, 1, escape);
+        let parsed = parse(&a, puzzle).unwrap();
+        assert_eq!(parsed.launcher_id, Bytes32::new([0x44; 32]));
+        assert_eq!(parsed.state, PoolSingletonState::FarmingToPool as u8);
+        assert_eq!(parsed.target_puzzle_hash, Bytes32::new([0x11; 32]));
+        assert_eq!(parsed.p2_singleton_puzzle_hash, Bytes32::new([0x22; 32]));
+        assert_eq!(parsed.owner_pubkey, owner_pubkey());
+        assert_eq!(parsed.pool_reward_prefix, Bytes32::new([0x33; 32]));
+        assert_eq!(parsed.escape_puzzle_hash, Some(Bytes32::new([0x55; 32])));
+        assert_eq!(parsed.relative_lock_height, None);
+    }
+
+    #[test]
+    fn test_parse_waiting_room_puzzle() {
+        let mut a = Allocator::new();
+        let lock_height = 100_u32.to_clvm(&mut a).unwrap();
+        let puzzle = plot_nft(&mut a, 2, lock_height);
+        let parsed = parse(&a, puzzle).unwrap();
+        assert_eq!(parsed.state, PoolSingletonState::LeavingPool as u8);
+        assert_eq!(parsed.escape_puzzle_hash, None);
+        assert_eq!(parsed.relative_lock_height, Some(100));
+    }
+
+    #[test]
+    fn test_parse_not_pool_puzzle() {
+        let mut a = Allocator::new();
+        let puzzle = node_from_bytes(&mut a, &chia_puzzles::standard::STANDARD_PUZZLE).unwrap();
+        assert_eq!(
+            parse_pool_puzzle(&a, puzzle).unwrap_err(),
+            Error::NotPoolPuzzle
+        );
+    }
+
+    #[rstest]
+    // a foreign inner puzzle with the same curried arguments
+    #[case(3, true)]
+    #[case(3, false)]
+    // the member puzzle with a lock height, the waiting room with a hash
+    #[case(1, false)]
+    #[case(2, true)]
+    fn test_parse_foreign_inner_puzzle(#[case] inner_mod: u8, #[case] escape: bool) {
+        let mut a = Allocator::new();
+        let last = if escape {
+            Bytes32::new([0x55; 32]).to_clvm(&mut a).unwrap()
+        } else {
+            100_u32.to_clvm(&mut a).unwrap()
+        };
+        let puzzle = plot_nft(&mut a, inner_mod, last);
+        assert_eq!(parse(&a, puzzle).unwrap_err(), Error::NotPoolPuzzle);
+        // the real mod hashes don't match the test mods either
+        assert_eq!(
+            parse_pool_puzzle(&a, puzzle).unwrap_err(),
+            Error::NotPoolPuzzle
+        );
+    }
+
+    fn spend(a: &mut Allocator, puzzle_hash: Bytes32, solution: NodePtr) -> CoinSpend {
+        CoinSpend::new(
+            Coin::new(Bytes32::default(), puzzle_hash, 1),
+            Program::default(),
+            Program::from_node_ptr(a, solution).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_launcher_solution() {
+        let mut a = Allocator::new();
+        let state = pool_state(PoolSingletonState::SelfPooling);
+        let solution = (
+            Bytes32::default(),
+            (
+                1,
+                (
+                    (
+                        ("p", chia_protocol::Bytes::new(state.to_bytes().unwrap())),
+                        (("t", 100), ()),
+                    ),
+                    (),
+                ),
+            ),
+        )
+            .to_clvm(&mut a)
+            .unwrap();
+        let spend = spend(&mut a, SINGLETON_LAUNCHER_PUZZLE_HASH.into(), solution);
+        assert_eq!(solution_to_pool_state(&mut a, &spend).unwrap(), Some(state));
+    }
+
+    #[rstest]
+    // travel from the member puzzle
+    #[case(false, true, Some(PoolSingletonState::LeavingPool))]
+    // absorb with the member puzzle
+    #[case(false, false, None)]
+    // travel from the waiting room puzzle
+    #[case(true, true, Some(PoolSingletonState::FarmingToPool))]
+    // absorb with the waiting room puzzle
+    #[case(true, false, None)]
+    fn test_singleton_solution(
+        #[case] waiting_room: bool,
+        #[case] travel: bool,
+        #[case] expected: Option<PoolSingletonState>,
+    ) {
+        let mut a = Allocator::new();
+        let state = pool_state(expected.unwrap_or(PoolSingletonState::FarmingToPool));
+        let extra_data = (
+            ("p", chia_protocol::Bytes::new(state.to_bytes().unwrap())),
+            (),
+        )
+            .to_clvm(&mut a)
+            .unwrap();
+        let inner_solution = match (waiting_room, travel) {
+            (false, true) => (extra_data, (0, ())).to_clvm(&mut a),
+            (false, false) => (Bytes32::default(), (1000, ())).to_clvm(&mut a),
+            (true, true) => (1, (extra_data, (0, ()))).to_clvm(&mut a),
+            (true, false) => (0, (0, (1000, ()))).to_clvm(&mut a),
+        }
+        .unwrap();
+        let lineage_proof = (Bytes32::default(), (Bytes32::default(), (1, ())))
+            .to_clvm(&mut a)
+            .unwrap();
+        let solution = (lineage_proof, (1, (inner_solution, ())))
+            .to_clvm(&mut a)
+            .unwrap();
+        let spend = spend(&mut a, Bytes32::new([0x66; 32]), solution);
+        assert_eq!(
+            solution_to_pool_state(&mut a, &spend).unwrap(),
+            expected.map(|_| state)
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use crate::consensus_constants::ConsensusConstants;
+use crate::error::{Error, Result};
+
+// These match chia.consensus.pot_iterations
+
+pub fn is_overflow_block(constants: &ConsensusConstants, signage_point_index: u8) -> Result<bool> {
+    if signage_point_index as u32 >= constants.num_sps_sub_slot {
+        return Err(Error::Custom("SP index too high".to_string()));
+    }
+    Ok(signage_point_index as u32
+        >= constants.num_sps_sub_slot - constants.num_sp_intervals_extra as u32)
+}
+
+pub fn calculate_sp_interval_iters(
+    constants: &ConsensusConstants,
+    sub_slot_iters: u64,
+) -> Result<u64> {
+    let num_sps = constants.num_sps_sub_slot as u64;
+    if num_sps == 0 || sub_slot_iters % num_sps != 0 {
+        return Err(Error::Custom(format!(
+            "sub slot iters {sub_slot_iters} is not a multiple of {num_sps}"
+        )));
+    }
+    Ok(sub_slot_iters / num_sps)
+}
+
+pub fn calculate_sp_iters(
+    constants: &ConsensusConstants,
+    sub_slot_iters: u64,
+    signage_point_index: u8,
+) -> Result<u64> {
+    if signage_point_index as u32 >= constants.num_sps_sub_slot {
+        return Err(Error::Custom("SP index too high".to_string()));
+    }
+    Ok(calculate_sp_interval_iters(constants, sub_slot_iters)? * signage_point_index as u64)
+}
+
+// the iterations from the start of the sub-slot to the infusion point. For
+// overflow blocks, that's the sub-slot after the one of the signage point
+pub fn calculate_ip_iters(
+    constants: &ConsensusConstants,
+    sub_slot_iters: u64,
+    signage_point_index: u8,
+    required_iters: u64,
+) -> Result<u64> {
+    let sp_iters = calculate_sp_iters(constants, sub_slot_iters, signage_point_index)?;
+    let sp_interval_iters = calculate_sp_interval_iters(constants, sub_slot_iters)?;
+    if required_iters >= sp_interval_iters || required_iters == 0 {
+        return Err(Error::Custom(format!(
+            "Required iters {required_iters} is not below the sp interval iters \
+            {sp_interval_iters} {sub_slot_iters} or not >0."
+        )));
+    }
+    let ip_iters = sp_iters as u128
+        + constants.num_sp_intervals_extra as u128 * sp_interval_iters as u128
+        + required_iters as u128;
+    Ok((ip_iters % sub_slot_iters as u128) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus_constants::TEST_CONSTANTS;
+
+    const SSI: u64 = 64 * 1000;
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(is_overflow_block(&TEST_CONSTANTS, 0), Ok(false));
+        assert_eq!(is_overflow_block(&TEST_CONSTANTS, 60), Ok(false));
+        assert_eq!(is_overflow_block(&TEST_CONSTANTS, 61), Ok(true));
+        assert_eq!(is_overflow_block(&TEST_CONSTANTS, 63), Ok(true));
+        assert!(is_overflow_block(&TEST_CONSTANTS, 64).is_err());
+    }
+
+    #[test]
+    fn test_sp_iters() {
+        assert_eq!(calculate_sp_interval_iters(&TEST_CONSTANTS, SSI), Ok(1000));
+        assert!(calculate_sp_interval_iters(&TEST_CONSTANTS, SSI + 1).is_err());
+        assert_eq!(calculate_sp_iters(&TEST_CONSTANTS, SSI, 0), Ok(0));
+        assert_eq!(calculate_sp_iters(&TEST_CONSTANTS, SSI, 13), Ok(13000));
+        assert!(calculate_sp_iters(&TEST_CONSTANTS, SSI, 64).is_err());
+    }
+
+    #[test]
+    fn test_ip_iters() {
+        assert_eq!(calculate_ip_iters(&TEST_CONSTANTS, SSI, 13, 1), Ok(16001));
+        // overflow blocks are infused in the next sub-slot
+        assert_eq!(calculate_ip_iters(&TEST_CONSTANTS, SSI, 62, 999), Ok(1999));
+        assert!(calculate_ip_iters(&TEST_CONSTANTS, SSI, 13, 0).is_err());
+        assert!(calculate_ip_iters(&TEST_CONSTANTS, SSI, 13, 1000).is_err());
+    }
+}
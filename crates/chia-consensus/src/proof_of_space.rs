@@ -0,0 +1,188 @@
+use chia_bls::PublicKey;
+use chia_protocol::{Bytes32, ProofOfSpace, VDFInfo};
+use chia_traits::Streamable;
+use sha2::{Digest, Sha256};
+
+// These match chia.types.blockchain_format.proof_of_space
+
+fn sha256(parts: &[&[u8]]) -> Bytes32 {
+    let mut hasher = Sha256::new();
+    for p in parts {
+        hasher.update(p);
+    }
+    let ret: [u8; 32] = hasher.finalize().into();
+    ret.into()
+}
+
+// the ID of a plot created for a pool public key (an original plot)
+pub fn calculate_plot_id_pk(pool_public_key: &PublicKey, plot_public_key: &PublicKey) -> Bytes32 {
+    sha256(&[&pool_public_key.to_bytes(), &plot_public_key.to_bytes()])
+}
+
+// the ID of a plot created for a pool contract (a plotNFT)
+pub fn calculate_plot_id_ph(
+    pool_contract_puzzle_hash: &Bytes32,
+    plot_public_key: &PublicKey,
+) -> Bytes32 {
+    sha256(&[
+        pool_contract_puzzle_hash.as_ref(),
+        &plot_public_key.to_bytes(),
+    ])
+}
+
+// the plot ID of a proof of space. None if it has neither or both of a pool
+// public key and a pool contract puzzle hash, which makes it invalid
+pub fn get_plot_id(pos: &ProofOfSpace) -> Option<Bytes32> {
+    let plot_public_key = pos.plot_public_key();
+    match (&pos.pool_public_key, &pos.pool_contract_puzzle_hash) {
+        (Some(pk), None) => Some(calculate_plot_id_pk(pk, &plot_public_key)),
+        (None, Some(ph)) => Some(calculate_plot_id_ph(ph, &plot_public_key)),
+        _ => None,
+    }
+}
+
+// The hash of the challenge chain signage point a proof of space is for. For
+// the first signage point of the sub-slot (which has no VDF) that's the
+// challenge of the sub-slot itself, otherwise it's the hash of the output of
+// the signage point VDF.
+pub fn signage_point_hash(challenge_hash: &Bytes32, cc_sp_vdf: Option<&VDFInfo>) -> Bytes32 {
+    match cc_sp_vdf {
+        Some(vdf) => vdf.output.hash().into(),
+        None => *challenge_hash,
+    }
+}
+
+// the hash the plot filter is applied to
+pub fn calculate_plot_filter_input(
+    plot_id: &Bytes32,
+    challenge_hash: &Bytes32,
+    sp_hash: &Bytes32,
+) -> Bytes32 {
+    sha256(&[plot_id.as_ref(), challenge_hash.as_ref(), sp_hash.as_ref()])
+}
+
+// the challenge the plot looks up its proof of space for, once it passed the
+// plot filter
+pub fn calculate_pos_challenge(
+    plot_id: &Bytes32,
+    challenge_hash: &Bytes32,
+    sp_hash: &Bytes32,
+) -> Bytes32 {
+    sha256(&[calculate_plot_filter_input(plot_id, challenge_hash, sp_hash).as_ref()])
+}
+
+// whether the plot passes the filter for the signage point. prefix_bits is the
+// number of leading zero bits the filter input needs, see
+// ForkSchedule::plot_filter_bits()
+pub fn passes_plot_filter(
+    prefix_bits: u8,
+    plot_id: &Bytes32,
+    challenge_hash: &Bytes32,
+    sp_hash: &Bytes32,
+) -> bool {
+    let input = calculate_plot_filter_input(plot_id, challenge_hash, sp_hash);
+    let mut zero_bits = 0_u32;
+    for b in input.as_ref() {
+        zero_bits += b.leading_zeros();
+        if *b != 0 {
+            break;
+        }
+    }
+    zero_bits >= prefix_bits as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chia_bls::SecretKey;
+    use chia_protocol::ClassgroupElement;
+    use hex_literal::hex;
+
+    const CHALLENGE: Bytes32 = Bytes32::new([2; 32]);
+    const SP_HASH: Bytes32 = Bytes32::new([3; 32]);
+
+    fn plot_id(n: u16) -> Bytes32 {
+        let mut ret = [0; 32];
+        ret[30..].copy_from_slice(&n.to_be_bytes());
+        ret.into()
+    }
+
+    #[test]
+    fn test_pos_challenge() {
+        let plot_id = Bytes32::new([1; 32]);
+        assert_eq!(
+            calculate_plot_filter_input(&plot_id, &CHALLENGE, &SP_HASH),
+            Bytes32::new(hex!(
+                "8a2e491356cfdb05a1d13785e0794d7cd163f91af79a146c976b1d2ac643b679"
+            ))
+        );
+        assert_eq!(
+            calculate_pos_challenge(&plot_id, &CHALLENGE, &SP_HASH),
+            Bytes32::new(hex!(
+                "df894b9b3b174c30244cfb29c744a47f07c030a6bbd2e7a835f79f8133066d45"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_plot_filter() {
+        // the filter input of plot 402 starts with 9 zero bits
+        assert!(passes_plot_filter(0, &plot_id(402), &CHALLENGE, &SP_HASH));
+        assert!(passes_plot_filter(9, &plot_id(402), &CHALLENGE, &SP_HASH));
+        assert!(!passes_plot_filter(10, &plot_id(402), &CHALLENGE, &SP_HASH));
+        // this one starts with a one bit
+        assert!(passes_plot_filter(
+            0,
+            &Bytes32::new([1; 32]),
+            &CHALLENGE,
+            &SP_HASH
+        ));
+        assert!(!passes_plot_filter(
+            1,
+            &Bytes32::new([1; 32]),
+            &CHALLENGE,
+            &SP_HASH
+        ));
+    }
+
+    #[test]
+    fn test_plot_id() {
+        let local_sk = SecretKey::from_seed(&[1; 32]);
+        let farmer_sk = SecretKey::from_seed(&[2; 32]);
+        let pool_pk = SecretKey::from_seed(&[3; 32]).public_key();
+        let pool_ph = Bytes32::new([4; 32]);
+        let pos = |pool_pk: Option<PublicKey>, pool_ph: Option<Bytes32>| {
+            ProofOfSpace::new(
+                CHALLENGE,
+                pool_pk,
+                pool_ph,
+                local_sk.public_key(),
+                32,
+                vec![].into(),
+                farmer_sk.public_key(),
+            )
+        };
+
+        let p = pos(Some(pool_pk), None);
+        assert_eq!(
+            get_plot_id(&p),
+            Some(calculate_plot_id_pk(&pool_pk, &p.plot_public_key()))
+        );
+        let p = pos(None, Some(pool_ph));
+        assert_eq!(
+            get_plot_id(&p),
+            Some(calculate_plot_id_ph(&pool_ph, &p.plot_public_key()))
+        );
+        assert_eq!(get_plot_id(&pos(None, None)), None);
+        assert_eq!(get_plot_id(&pos(Some(pool_pk), Some(pool_ph))), None);
+    }
+
+    #[test]
+    fn test_signage_point_hash() {
+        assert_eq!(signage_point_hash(&CHALLENGE, None), CHALLENGE);
+        let vdf = VDFInfo::new(CHALLENGE, 100, ClassgroupElement::default());
+        let hash = signage_point_hash(&CHALLENGE, Some(&vdf));
+        assert_eq!(hash, Bytes32::from(ClassgroupElement::default().hash()));
+        assert_ne!(hash, CHALLENGE);
+    }
+}
@@ -0,0 +1,251 @@
+use crate::error::Error;
+use chia_protocol::{Bytes32, CoinState, CoinStateFilters, RespondPuzzleState};
+use std::collections::HashSet;
+
+// the max size of a RespondPuzzleState message, per its rate limit
+pub const DEFAULT_MAX_RESPONSE_SIZE: usize = 100 * 1024 * 1024;
+
+// the height a coin state last changed at
+pub(crate) fn last_height(state: &CoinState) -> u32 {
+    state
+        .spent_height
+        .unwrap_or(0)
+        .max(state.created_height.unwrap_or(0))
+}
+
+// whether a RequestPuzzleState with these filters asks for the coin state,
+// given it's for the changes at min_height or later
+pub(crate) fn matches_filters(
+    state: &CoinState,
+    filters: &CoinStateFilters,
+    min_height: u32,
+) -> bool {
+    let wanted = if state.spent_height.is_some() {
+        filters.include_spent
+    } else {
+        filters.include_unspent
+    };
+    wanted && state.coin.amount >= filters.min_amount && last_height(state) >= min_height
+}
+
+// The coin states the filters of a RequestPuzzleState ask for, that changed at
+// min_height or later, without duplicates (a coin can be found both by its
+// puzzle hash and its hint). The order of the states is kept.
+// include_hinted is up to the caller, since it decides which coins are looked
+// up in the first place.
+pub fn filter_coin_states<I>(
+    states: I,
+    filters: &CoinStateFilters,
+    min_height: u32,
+) -> Vec<CoinState>
+where
+    I: IntoIterator<Item = CoinState>,
+{
+    let mut seen = HashSet::<Bytes32>::new();
+    states
+        .into_iter()
+        .filter(|state| matches_filters(state, filters, min_height))
+        .filter(|state| seen.insert(state.coin.coin_id()))
+        .collect()
+}
+
+// the serialized size of a coin state
+fn coin_state_size(state: &CoinState) -> usize {
+    let optional_u32 = |v: Option<u32>| if v.is_some() { 5 } else { 1 };
+    32 + 32 + 8 + optional_u32(state.spent_height) + optional_u32(state.created_height)
+}
+
+// Splits the coin states answering a RequestPuzzleState for puzzle_hashes
+// into the responses to send, each at most max_size bytes serialized. The
+// coin states are ordered by the height they last changed at, and like
+// batch_coin_states() a response never ends in the middle of a height.
+// Every response but the last one is at the height before the next response
+// starts, whose header hash is looked up with header_hash. The last response
+// is finished, at the peak. There's always at least one response, even
+// without any coin states.
+pub fn puzzle_state_responses<E, F>(
+    puzzle_hashes: &[Bytes32],
+    mut states: Vec<CoinState>,
+    peak_height: u32,
+    peak_header_hash: Bytes32,
+    max_size: usize,
+    mut header_hash: F,
+) -> Result<Vec<RespondPuzzleState>, E>
+where
+    E: From<Error>,
+    F: FnMut(u32) -> Result<Option<Bytes32>, E>,
+{
+    states.sort_by_cached_key(|state| (last_height(state), state.coin.coin_id()));
+
+    // puzzle_hashes, height, header_hash, is_finished and the length prefix
+    // of coin_states
+    let empty_size = 4 + 32 * puzzle_hashes.len() + 4 + 32 + 1 + 4;
+
+    let mut ret = Vec::new();
+    let mut page = Vec::new();
+    let mut page_size = empty_size;
+    let mut states = states.into_iter().peekable();
+    while let Some(height) = states.peek().map(last_height) {
+        let mut group = Vec::new();
+        let mut group_size = 0;
+        while let Some(state) = states.next_if(|state| last_height(state) == height) {
+            group_size += coin_state_size(&state);
+            group.push(state);
+        }
+        if empty_size + group_size > max_size {
+            return Err(Error::Custom(format!(
+                "the {} coin states at height {height} don't fit in a response of {max_size} bytes",
+                group.len()
+            ))
+            .into());
+        }
+        if page_size + group_size > max_size {
+            // the page isn't empty, so there are coin states at lower heights
+            let page_height = height - 1;
+            let hash = header_hash(page_height)?
+                .ok_or_else(|| Error::Custom(format!("no header hash for height {page_height}")))?;
+            ret.push(RespondPuzzleState::new(
+                puzzle_hashes.to_vec(),
+                page_height,
+                hash,
+                false,
+                std::mem::take(&mut page),
+            ));
+            page_size = empty_size;
+        }
+        page_size += group_size;
+        page.extend(group);
+    }
+    ret.push(RespondPuzzleState::new(
+        puzzle_hashes.to_vec(),
+        peak_height,
+        peak_header_hash,
+        true,
+        page,
+    ));
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chia_protocol::Coin;
+    use chia_traits::Streamable;
+
+    fn state(i: u8, created: u32, spent: Option<u32>) -> CoinState {
+        let coin = Coin::new([i; 32].into(), [2; 32].into(), i as u64);
+        CoinState::new(coin, spent, Some(created))
+    }
+
+    fn hash(height: u32) -> Bytes32 {
+        Bytes32::new([height as u8; 32])
+    }
+
+    fn lookup(height: u32) -> Result<Option<Bytes32>, Error> {
+        Ok(Some(hash(height)))
+    }
+
+    // in the order of the responses
+    fn sorted(states: &[CoinState]) -> Vec<CoinState> {
+        let mut ret = states.to_vec();
+        ret.sort_by_key(|state| (last_height(state), state.coin.coin_id()));
+        ret
+    }
+
+    #[test]
+    fn test_filter_coin_states() {
+        let states = vec![
+            state(1, 1, None),
+            state(2, 5, Some(7)),
+            state(3, 8, None),
+            state(1, 1, None),
+            state(4, 9, Some(9)),
+        ];
+        let filters =
+            |spent, unspent, min_amount| CoinStateFilters::new(spent, unspent, true, min_amount);
+        assert_eq!(
+            filter_coin_states(states.clone(), &filters(true, true, 0), 0),
+            [states[0], states[1], states[2], states[4]]
+        );
+        assert_eq!(
+            filter_coin_states(states.clone(), &filters(false, true, 0), 0),
+            [states[0], states[2]]
+        );
+        assert_eq!(
+            filter_coin_states(states.clone(), &filters(true, false, 3), 0),
+            [states[4]]
+        );
+        // coin 2 was spent at 7, so it changed at min_height
+        assert_eq!(
+            filter_coin_states(states.clone(), &filters(true, true, 0), 7),
+            [states[1], states[2], states[4]]
+        );
+    }
+
+    #[test]
+    fn test_coin_state_size() {
+        for s in [state(1, 1, None), state(2, 5, Some(7))] {
+            assert_eq!(coin_state_size(&s), s.to_bytes().unwrap().len());
+        }
+    }
+
+    #[test]
+    fn test_responses() {
+        let phs = [Bytes32::new([2; 32])];
+        // three coin states per height, heights 1-4
+        let states: Vec<CoinState> = (0..12).map(|i| state(i, i as u32 / 3 + 1, None)).collect();
+        let empty = RespondPuzzleState::new(phs.to_vec(), 10, hash(10), true, vec![])
+            .to_bytes()
+            .unwrap()
+            .len();
+        let per_state = coin_state_size(&states[0]);
+
+        let ret =
+            puzzle_state_responses(&phs, states.clone(), 10, hash(10), usize::MAX, lookup).unwrap();
+        assert_eq!(ret.len(), 1);
+        assert!(ret[0].is_finished);
+        assert_eq!(ret[0].height, 10);
+        assert_eq!(ret[0].coin_states.len(), 12);
+
+        // room for 7 coin states, which is two heights
+        let max_size = empty + 7 * per_state;
+        let mut reversed = states.clone();
+        reversed.reverse();
+        let ret = puzzle_state_responses(&phs, reversed, 10, hash(10), max_size, lookup).unwrap();
+        assert_eq!(ret.len(), 2);
+        assert_eq!(ret[0].coin_states, sorted(&states[..6]));
+        assert_eq!(ret[0].height, 2);
+        assert_eq!(ret[0].header_hash, hash(2));
+        assert!(!ret[0].is_finished);
+        assert_eq!(ret[1].coin_states, sorted(&states[6..]));
+        assert_eq!(ret[1].height, 10);
+        assert!(ret[1].is_finished);
+        for response in &ret {
+            assert!(response.to_bytes().unwrap().len() <= max_size);
+        }
+
+        // a height that doesn't fit by itself
+        assert!(matches!(
+            puzzle_state_responses(
+                &phs,
+                states.clone(),
+                10,
+                hash(10),
+                empty + 2 * per_state,
+                lookup
+            ),
+            Err(Error::Custom(_))
+        ));
+        // the header hash isn't known
+        assert!(matches!(
+            puzzle_state_responses(&phs, states, 10, hash(10), max_size, |_| Ok::<_, Error>(
+                None
+            )),
+            Err(Error::Custom(_))
+        ));
+
+        let ret = puzzle_state_responses(&phs, vec![], 10, hash(10), 0, lookup).unwrap();
+        assert_eq!(ret.len(), 1);
+        assert!(ret[0].coin_states.is_empty());
+    }
+}
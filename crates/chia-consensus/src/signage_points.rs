@@ -0,0 +1,229 @@
+use crate::consensus_constants::ConsensusConstants;
+use crate::error::{Error, Result};
+use crate::pot_iterations::{calculate_ip_iters, calculate_sp_interval_iters};
+use chia_protocol::BlockRecord;
+
+#[cfg(feature = "py-bindings")]
+use pyo3::prelude::*;
+
+// A signage point, as the timelord would reach it. Signage point 0 is the end
+// of the previous sub-slot (and the start of this one).
+#[cfg_attr(feature = "py-bindings", pyclass(module = "gold_rs", frozen, get_all))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignagePoint {
+    pub signage_point_index: u8,
+    // the total iterations at the start of the sub-slot
+    pub sub_slot_total_iters: u128,
+    pub sub_slot_iters: u64,
+    // the iterations from the start of the sub-slot
+    pub sp_iters: u64,
+    pub total_iters: u128,
+}
+
+impl SignagePoint {
+    pub fn is_end_of_sub_slot(&self) -> bool {
+        self.signage_point_index == 0
+    }
+}
+
+#[cfg(feature = "py-bindings")]
+#[pymethods]
+impl SignagePoint {
+    #[getter(is_end_of_sub_slot)]
+    fn py_is_end_of_sub_slot(&self) -> bool {
+        self.is_end_of_sub_slot()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+// Yields the signage points (and with them, the sub-slot boundaries) following
+// a peak, without a timelord. This is a clock for tests and the simulator; the
+// sub-slot iterations stay the same, so it doesn't follow difficulty
+// adjustments at the end of an epoch.
+#[cfg_attr(feature = "py-bindings", pyclass(module = "gold_rs"))]
+#[derive(Debug, Clone)]
+pub struct SignagePointIterator {
+    num_sps: u32,
+    sub_slot_iters: u64,
+    sp_interval_iters: u64,
+    sub_slot_total_iters: u128,
+    next_index: u32,
+}
+
+impl SignagePointIterator {
+    // starts at the first signage point of the chain, at 0 iterations
+    pub fn new(constants: &ConsensusConstants) -> Result<Self> {
+        let sub_slot_iters = constants.sub_slot_iters_starting;
+        Ok(Self {
+            num_sps: constants.num_sps_sub_slot,
+            sub_slot_iters,
+            sp_interval_iters: calculate_sp_interval_iters(constants, sub_slot_iters)?,
+            sub_slot_total_iters: 0,
+            next_index: 0,
+        })
+    }
+
+    // starts at the first signage point after the infusion point of peak
+    pub fn from_peak(constants: &ConsensusConstants, peak: &BlockRecord) -> Result<Self> {
+        let sub_slot_iters = peak.sub_slot_iters;
+        let sp_interval_iters = calculate_sp_interval_iters(constants, sub_slot_iters)?;
+        let ip_iters = calculate_ip_iters(
+            constants,
+            sub_slot_iters,
+            peak.signage_point_index,
+            peak.required_iters,
+        )?;
+        let mut ret = Self {
+            num_sps: constants.num_sps_sub_slot,
+            sub_slot_iters,
+            sp_interval_iters,
+            sub_slot_total_iters: peak.total_iters.checked_sub(ip_iters as u128).ok_or_else(
+                || {
+                    Error::Custom(format!(
+                        "total iters {} of peak is below its infusion point",
+                        peak.total_iters
+                    ))
+                },
+            )?,
+            next_index: (ip_iters / sp_interval_iters) as u32 + 1,
+        };
+        ret.wrap_sub_slot();
+        Ok(ret)
+    }
+
+    fn wrap_sub_slot(&mut self) {
+        if self.next_index >= self.num_sps {
+            self.next_index = 0;
+            self.sub_slot_total_iters += self.sub_slot_iters as u128;
+        }
+    }
+
+    // skips to the start of the next sub-slot, returning its end of sub-slot
+    pub fn next_sub_slot(&mut self) -> SignagePoint {
+        if self.next_index != 0 {
+            self.next_index = self.num_sps;
+            self.wrap_sub_slot();
+        }
+        self.next().expect("the clock never ends")
+    }
+}
+
+impl Iterator for SignagePointIterator {
+    type Item = SignagePoint;
+
+    fn next(&mut self) -> Option<SignagePoint> {
+        let sp_iters = self.next_index as u64 * self.sp_interval_iters;
+        let ret = SignagePoint {
+            signage_point_index: self.next_index as u8,
+            sub_slot_total_iters: self.sub_slot_total_iters,
+            sub_slot_iters: self.sub_slot_iters,
+            sp_iters,
+            total_iters: self.sub_slot_total_iters + sp_iters as u128,
+        };
+        self.next_index += 1;
+        self.wrap_sub_slot();
+        Some(ret)
+    }
+}
+
+#[cfg(feature = "py-bindings")]
+#[pymethods]
+impl SignagePointIterator {
+    #[new]
+    #[pyo3(signature = (constants, peak=None))]
+    fn py_new(constants: &ConsensusConstants, peak: Option<&BlockRecord>) -> Result<Self> {
+        match peak {
+            Some(peak) => Self::from_peak(constants, peak),
+            None => Self::new(constants),
+        }
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> Option<SignagePoint> {
+        self.next()
+    }
+
+    #[pyo3(name = "next_sub_slot")]
+    fn py_next_sub_slot(&mut self) -> SignagePoint {
+        self.next_sub_slot()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus_constants::TEST_CONSTANTS;
+    use crate::test_block_record::BlockRecordBuilder;
+    use chia_protocol::Bytes32;
+
+    fn peak(total_iters: u128, signage_point_index: u8, required_iters: u64) -> BlockRecord {
+        BlockRecordBuilder::new(Bytes32::default())
+            .height(1)
+            .weight(1)
+            .total_iters(total_iters)
+            .signage_point_index(signage_point_index)
+            .sub_slot_iters(64 * 1000)
+            .required_iters(required_iters)
+            .build()
+    }
+
+    #[test]
+    fn test_from_genesis() {
+        let ssi = TEST_CONSTANTS.sub_slot_iters_starting;
+        let interval = ssi / 64;
+        let sps: Vec<SignagePoint> = SignagePointIterator::new(&TEST_CONSTANTS)
+            .unwrap()
+            .take(130)
+            .collect();
+        assert!(sps[0].is_end_of_sub_slot());
+        assert_eq!(sps[0].total_iters, 0);
+        assert_eq!(sps[1].total_iters, interval as u128);
+        assert_eq!(sps[63].signage_point_index, 63);
+        assert!(sps[64].is_end_of_sub_slot());
+        assert_eq!(sps[64].total_iters, ssi as u128);
+        assert_eq!(sps[129].sub_slot_total_iters, 2 * ssi as u128);
+        assert_eq!(sps[129].sp_iters, interval);
+        for pair in sps.windows(2) {
+            assert_eq!(pair[1].total_iters - pair[0].total_iters, interval as u128);
+        }
+    }
+
+    #[test]
+    fn test_from_peak() {
+        // infused 16001 iterations into the sub-slot starting at 640000
+        let mut it =
+            SignagePointIterator::from_peak(&TEST_CONSTANTS, &peak(656_001, 13, 1)).unwrap();
+        let sp = it.next().unwrap();
+        assert_eq!(sp.signage_point_index, 17);
+        assert_eq!(sp.total_iters, 657_000);
+        assert_eq!(sp.sub_slot_total_iters, 640_000);
+
+        let eos = it.next_sub_slot();
+        assert!(eos.is_end_of_sub_slot());
+        assert_eq!(eos.total_iters, 704_000);
+        assert_eq!(it.next().unwrap().total_iters, 705_000);
+
+        // infused in the last signage point interval of the sub-slot
+        let mut it =
+            SignagePointIterator::from_peak(&TEST_CONSTANTS, &peak(703_999, 60, 999)).unwrap();
+        assert_eq!(it.clone().next_sub_slot().total_iters, 704_000);
+        let sp = it.next().unwrap();
+        assert!(sp.is_end_of_sub_slot());
+        assert_eq!(sp.total_iters, 704_000);
+
+        // an overflow block, infused in the sub-slot after its signage point
+        let mut it =
+            SignagePointIterator::from_peak(&TEST_CONSTANTS, &peak(705_999, 62, 999)).unwrap();
+        let sp = it.next().unwrap();
+        assert_eq!(sp.signage_point_index, 2);
+        assert_eq!(sp.total_iters, 706_000);
+
+        assert!(SignagePointIterator::from_peak(&TEST_CONSTANTS, &peak(1_000, 62, 999)).is_err());
+    }
+}
@@ -0,0 +1,130 @@
+use crate::error::Error;
+use chia_bls::{sign, verify, PublicKey, SecretKey, Signature};
+use clvm_utils::{tree_hash_atom, tree_hash_pair};
+use std::str::FromStr;
+
+// the first element of the pair signed in CHIP-0002 mode
+pub const CHIP_0002_SIGN_MESSAGE_PREFIX: &str = "Chia Signed Message";
+
+// How a message is signed to prove ownership of a key, like the SigningMode
+// of the wallet. The names are the same as the values of its enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigningMode {
+    // the tree hash of (CHIP_0002_SIGN_MESSAGE_PREFIX . message) is signed
+    Chip0002,
+    // the message itself is signed. The wallet passes it as a string, this is
+    // its UTF-8 encoding
+    Utf8Input,
+    // the message itself is signed. The wallet passes it hex encoded, this is
+    // the decoded message
+    HexInput,
+}
+
+impl SigningMode {
+    // these are only the names the wallet uses for the modes, not domain
+    // separation tags. Every mode signs with the augmented scheme of AugSchemeMPL
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Chip0002 => "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG:CHIP-0002_",
+            Self::Utf8Input => "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG:utf8input_",
+            Self::HexInput => "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG:hexinput_",
+        }
+    }
+}
+
+impl FromStr for SigningMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        [Self::Chip0002, Self::Utf8Input, Self::HexInput]
+            .into_iter()
+            .find(|mode| mode.as_str() == s)
+            .ok_or_else(|| Error::Custom(format!("unsupported signing mode: {s}")))
+    }
+}
+
+// the bytes actually signed for the message
+pub fn message_to_sign(message: &[u8], mode: SigningMode) -> Vec<u8> {
+    match mode {
+        SigningMode::Chip0002 => tree_hash_pair(
+            tree_hash_atom(CHIP_0002_SIGN_MESSAGE_PREFIX.as_bytes()),
+            tree_hash_atom(message),
+        )
+        .to_vec(),
+        SigningMode::Utf8Input | SigningMode::HexInput => message.to_vec(),
+    }
+}
+
+pub fn sign_message(sk: &SecretKey, message: &[u8], mode: SigningMode) -> Signature {
+    sign(sk, message_to_sign(message, mode))
+}
+
+pub fn verify_message(
+    pk: &PublicKey,
+    message: &[u8],
+    signature: &Signature,
+    mode: SigningMode,
+) -> bool {
+    verify(signature, pk, message_to_sign(message, mode))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn test_message_to_sign() {
+        assert_eq!(
+            message_to_sign(b"hello", SigningMode::Chip0002),
+            hex!("a58695a4fe01f0779c51ce56dba718a18df326086b0c259b554b12aa53532926")
+        );
+        // an empty message is nil
+        assert_eq!(
+            message_to_sign(b"", SigningMode::Chip0002),
+            hex!("b690b15bc252346d3a0b3e8e2fe2e1f02587519a8d20d5b28b32161e15371b15")
+        );
+        assert_eq!(message_to_sign(b"hello", SigningMode::HexInput), b"hello");
+    }
+
+    #[test]
+    fn test_sign_verify() {
+        let sk = SecretKey::from_seed(&[7; 32]);
+        let pk = sk.public_key();
+        for mode in [
+            SigningMode::Chip0002,
+            SigningMode::Utf8Input,
+            SigningMode::HexInput,
+        ] {
+            let sig = sign_message(&sk, b"foobar", mode);
+            assert!(verify_message(&pk, b"foobar", &sig, mode));
+            assert!(!verify_message(&pk, b"foobaz", &sig, mode));
+            assert!(!verify_message(
+                &SecretKey::from_seed(&[8; 32]).public_key(),
+                b"foobar",
+                &sig,
+                mode
+            ));
+        }
+        // the modes aren't domain separated. A CHIP-0002 signature only
+        // differs because it signs the hash of the message
+        let sig = sign_message(&sk, b"foobar", SigningMode::Utf8Input);
+        assert!(verify_message(&pk, b"foobar", &sig, SigningMode::HexInput));
+        assert!(!verify_message(&pk, b"foobar", &sig, SigningMode::Chip0002));
+    }
+
+    #[test]
+    fn test_mode_names() {
+        for mode in [
+            SigningMode::Chip0002,
+            SigningMode::Utf8Input,
+            SigningMode::HexInput,
+        ] {
+            assert_eq!(mode.as_str().parse::<SigningMode>(), Ok(mode));
+        }
+        assert!(matches!(
+            "CHIP-0002".parse::<SigningMode>(),
+            Err(Error::Custom(_))
+        ));
+    }
+}
@@ -0,0 +1,518 @@
+use crate::allocator::make_allocator;
+use crate::coin_store::CoinStore;
+use crate::consensus_constants::ConsensusConstants;
+use crate::gen::conditions::EmptyVisitor;
+use crate::gen::flags::MEMPOOL_MODE;
+use crate::gen::owned_conditions::OwnedSpendBundleConditions;
+use crate::gen::run_block_generator::run_block_generator2;
+use crate::gen::signing::messages_to_sign;
+use crate::gen::solution_generator::solution_generator;
+use crate::gen::validation_error::{ErrorCode, ValidationErr};
+use chia_bls::aggregate_verify;
+use chia_protocol::{Bytes32, Coin, CoinState, SpendBundle};
+use clvmr::allocator::NodePtr;
+use clvmr::sha2::{Digest, Sha256};
+use std::collections::HashSet;
+
+#[cfg(feature = "py-bindings")]
+use pyo3::prelude::*;
+
+// the timestamp of the first block
+const GENESIS_TIMESTAMP: u64 = 1_600_000_000;
+
+const SECONDS_PER_BLOCK: u64 = 20;
+
+fn err(code: ErrorCode) -> ValidationErr {
+    ValidationErr(NodePtr::NIL, code)
+}
+
+// An in-memory blockchain for wallet tests. It keeps track of the coin set,
+// accepts spend bundles into a mempool and farms them into (simplified)
+// blocks, without any proofs of space, VDFs or block rewards. Spend bundles
+// are run in mempool mode and their timelocks, coins and aggregate signature
+// are validated against the current peak when they're submitted. Conflicting
+// spend bundles are rejected rather than replaced.
+// Coins are created with new_coin(), which mints them out of thin air.
+#[cfg_attr(feature = "py-bindings", pyclass(module = "gold_rs"))]
+#[derive(Clone, Debug)]
+pub struct Simulator {
+    constants: ConsensusConstants,
+    // the timestamps of all blocks, indexed by height
+    timestamps: Vec<u64>,
+    coins: CoinStore,
+    mempool: Vec<(SpendBundle, OwnedSpendBundleConditions)>,
+    // the coins spent by spend bundles in the mempool
+    mempool_removals: HashSet<Bytes32>,
+    minted: u64,
+}
+
+impl Simulator {
+    pub fn new(constants: &ConsensusConstants) -> Self {
+        Self {
+            constants: constants.clone(),
+            timestamps: vec![GENESIS_TIMESTAMP],
+            coins: CoinStore::new(),
+            mempool: Vec::new(),
+            mempool_removals: HashSet::new(),
+            minted: 0,
+        }
+    }
+
+    pub fn constants(&self) -> &ConsensusConstants {
+        &self.constants
+    }
+
+    // the height of the peak block
+    pub fn height(&self) -> u32 {
+        (self.timestamps.len() - 1) as u32
+    }
+
+    // the timestamp of the peak block
+    pub fn timestamp(&self) -> u64 {
+        *self.timestamps.last().expect("there's always a peak")
+    }
+
+    // the number of spend bundles waiting to be farmed
+    pub fn mempool_len(&self) -> usize {
+        self.mempool.len()
+    }
+
+    // the coin set, as of the peak
+    pub fn coin_store(&self) -> &CoinStore {
+        &self.coins
+    }
+
+    // creates an unspent coin at the current peak. Every minted coin has a
+    // unique parent coin ID
+    pub fn new_coin(&mut self, puzzle_hash: Bytes32, amount: u64) -> Coin {
+        let mut hasher = Sha256::new();
+        hasher.update(b"simulator");
+        hasher.update(self.minted.to_be_bytes());
+        self.minted += 1;
+        let parent: [u8; 32] = hasher.finalize().into();
+        let coin = Coin::new(parent.into(), puzzle_hash, amount);
+        self.coins.add_coin(coin, self.height(), None);
+        coin
+    }
+
+    // the height and timestamp of the block a coin in the coin set was
+    // created in
+    fn coin_birth(&self, coin_id: &Bytes32) -> Option<(u32, u64)> {
+        let height = self.coins.coin_state(coin_id)?.created_height?;
+        Some((height, self.timestamps[height as usize]))
+    }
+
+    // Runs the spend bundle and validates it against the peak and the coin
+    // set, the same way it would be when farming the next block. Returns the
+    // conditions of the spend bundle.
+    pub fn validate(
+        &self,
+        bundle: &SpendBundle,
+    ) -> Result<OwnedSpendBundleConditions, ValidationErr> {
+        let spends = bundle
+            .coin_spends
+            .iter()
+            .map(|cs| (cs.coin, cs.puzzle_reveal.as_slice(), cs.solution.as_slice()));
+        let generator = solution_generator(spends)?;
+        let mut a = make_allocator(MEMPOOL_MODE);
+        let conds = run_block_generator2::<&[u8], EmptyVisitor>(
+            &mut a,
+            &generator,
+            &[],
+            self.constants.max_block_cost_clvm,
+            MEMPOOL_MODE,
+        )?;
+        let conds = OwnedSpendBundleConditions::from(&a, conds)
+            .map_err(|_| err(ErrorCode::GeneratorRuntimeError))?;
+
+        let height = self.height();
+        let timestamp = self.timestamp();
+        if conds.height_absolute > height {
+            return Err(err(ErrorCode::AssertHeightAbsoluteFailed));
+        }
+        if conds.seconds_absolute > timestamp {
+            return Err(err(ErrorCode::AssertSecondsAbsoluteFailed));
+        }
+        if conds.before_height_absolute.is_some_and(|h| h <= height) {
+            return Err(err(ErrorCode::AssertBeforeHeightAbsoluteFailed));
+        }
+        if conds
+            .before_seconds_absolute
+            .is_some_and(|s| s <= timestamp)
+        {
+            return Err(err(ErrorCode::AssertBeforeSecondsAbsoluteFailed));
+        }
+
+        let additions: HashSet<Bytes32> = conds
+            .spends
+            .iter()
+            .flat_map(|spend| {
+                spend
+                    .create_coin
+                    .iter()
+                    .map(|(ph, amount, _)| Coin::new(spend.coin_id, *ph, *amount).coin_id())
+            })
+            .collect();
+
+        for spend in &conds.spends {
+            if self.mempool_removals.contains(&spend.coin_id) {
+                return Err(err(ErrorCode::DoubleSpend));
+            }
+            let (created_height, created_seconds) = match self.coins.coin_state(&spend.coin_id) {
+                Some(state) if state.spent_height.is_some() => {
+                    return Err(err(ErrorCode::DoubleSpend));
+                }
+                Some(_) => self
+                    .coin_birth(&spend.coin_id)
+                    .expect("coins in the coin set have a created height"),
+                // ephemeral coins are created in the block they're spent in
+                None if additions.contains(&spend.coin_id) => {
+                    (height + 1, timestamp + SECONDS_PER_BLOCK)
+                }
+                None => return Err(err(ErrorCode::UnknownUnspent)),
+            };
+
+            if spend
+                .height_relative
+                .is_some_and(|h| created_height as u64 + h as u64 > height as u64)
+            {
+                return Err(err(ErrorCode::AssertHeightRelativeFailed));
+            }
+            if spend
+                .seconds_relative
+                .is_some_and(|s| created_seconds.saturating_add(s) > timestamp)
+            {
+                return Err(err(ErrorCode::AssertSecondsRelativeFailed));
+            }
+            if spend
+                .before_height_relative
+                .is_some_and(|h| created_height as u64 + h as u64 <= height as u64)
+            {
+                return Err(err(ErrorCode::AssertBeforeHeightRelativeFailed));
+            }
+            if spend
+                .before_seconds_relative
+                .is_some_and(|s| created_seconds.saturating_add(s) <= timestamp)
+            {
+                return Err(err(ErrorCode::AssertBeforeSecondsRelativeFailed));
+            }
+            if spend.birth_height.is_some_and(|h| h != created_height) {
+                return Err(err(ErrorCode::AssertMyBirthHeightFailed));
+            }
+            if spend.birth_seconds.is_some_and(|s| s != created_seconds) {
+                return Err(err(ErrorCode::AssertMyBirthSecondsFailed));
+            }
+        }
+
+        let messages = messages_to_sign(&conds, &self.constants);
+        if !aggregate_verify(
+            &bundle.aggregated_signature,
+            messages.iter().map(|(pk, msg)| (pk, msg.as_slice())),
+        ) {
+            return Err(err(ErrorCode::BadAggregateSignature));
+        }
+        Ok(conds)
+    }
+
+    // validates the spend bundle and adds it to the mempool, to be included
+    // in the next block. Returns the fee of the spend bundle
+    pub fn push_tx(&mut self, bundle: SpendBundle) -> Result<u64, ValidationErr> {
+        let conds = self.validate(&bundle)?;
+        let fee = (conds.removal_amount - conds.addition_amount) as u64;
+        self.mempool_removals
+            .extend(conds.spends.iter().map(|s| s.coin_id));
+        self.mempool.push((bundle, conds));
+        Ok(fee)
+    }
+
+    // Farms a block with all spend bundles in the mempool, seconds after the
+    // current peak. Returns the states of all coins spent or created by the
+    // block.
+    pub fn farm_block_after(&mut self, seconds: u64) -> Vec<CoinState> {
+        let timestamp = self.timestamp() + seconds;
+        self.timestamps.push(timestamp);
+        let height = self.height();
+
+        for (_, conds) in std::mem::take(&mut self.mempool) {
+            for spend in &conds.spends {
+                for (ph, amount, hint) in &spend.create_coin {
+                    let coin = Coin::new(spend.coin_id, *ph, *amount);
+                    let hint = hint
+                        .as_ref()
+                        .and_then(|h| <[u8; 32]>::try_from(h.as_slice()).ok())
+                        .map(Bytes32::from);
+                    self.coins.add_coin(coin, height, hint);
+                }
+            }
+            for spend in &conds.spends {
+                let spent = self.coins.spend_coin(&spend.coin_id, height);
+                assert!(spent, "spent coins were validated");
+            }
+        }
+        self.mempool_removals.clear();
+        self.coins.changes_between(height - 1, height)
+    }
+
+    pub fn farm_block(&mut self) -> Vec<CoinState> {
+        self.farm_block_after(SECONDS_PER_BLOCK)
+    }
+
+    pub fn coin_state(&self, coin_id: &Bytes32) -> Option<CoinState> {
+        self.coins.coin_state(coin_id)
+    }
+
+    pub fn coin_states(&self, coin_ids: &[Bytes32]) -> Vec<CoinState> {
+        self.coins.coin_states(coin_ids)
+    }
+
+    pub fn coin_states_by_puzzle_hash(
+        &self,
+        puzzle_hashes: &[Bytes32],
+        include_spent: bool,
+    ) -> Vec<CoinState> {
+        self.coins
+            .coin_states_by_puzzle_hash(puzzle_hashes, include_spent)
+    }
+
+    pub fn coin_states_by_hint(&self, hints: &[Bytes32], include_spent: bool) -> Vec<CoinState> {
+        self.coins.coin_states_by_hint(hints, include_spent)
+    }
+
+    pub fn children(&self, coin_id: &Bytes32) -> Vec<CoinState> {
+        self.coins.children(coin_id)
+    }
+}
+
+#[cfg(feature = "py-bindings")]
+#[pymethods]
+impl Simulator {
+    #[new]
+    fn py_new(constants: &ConsensusConstants) -> Self {
+        Self::new(constants)
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    #[getter]
+    #[pyo3(name = "height")]
+    fn py_height(&self) -> u32 {
+        self.height()
+    }
+
+    #[getter]
+    #[pyo3(name = "timestamp")]
+    fn py_timestamp(&self) -> u64 {
+        self.timestamp()
+    }
+
+    #[pyo3(name = "mempool_len")]
+    fn py_mempool_len(&self) -> usize {
+        self.mempool_len()
+    }
+
+    #[pyo3(name = "new_coin")]
+    fn py_new_coin(&mut self, puzzle_hash: Bytes32, amount: u64) -> Coin {
+        self.new_coin(puzzle_hash, amount)
+    }
+
+    #[pyo3(name = "push_tx")]
+    fn py_push_tx(&mut self, py: Python<'_>, bundle: SpendBundle) -> PyResult<u64> {
+        Ok(py.allow_threads(|| self.push_tx(bundle))?)
+    }
+
+    #[pyo3(name = "farm_block", signature = (seconds = SECONDS_PER_BLOCK))]
+    fn py_farm_block(&mut self, seconds: u64) -> Vec<CoinState> {
+        self.farm_block_after(seconds)
+    }
+
+    #[pyo3(name = "coin_state")]
+    fn py_coin_state(&self, coin_id: Bytes32) -> Option<CoinState> {
+        self.coin_state(&coin_id)
+    }
+
+    #[pyo3(name = "coin_states")]
+    fn py_coin_states(&self, coin_ids: Vec<Bytes32>) -> Vec<CoinState> {
+        self.coin_states(&coin_ids)
+    }
+
+    #[pyo3(name = "coin_states_by_puzzle_hash", signature = (puzzle_hashes, include_spent = true))]
+    fn py_coin_states_by_puzzle_hash(
+        &self,
+        puzzle_hashes: Vec<Bytes32>,
+        include_spent: bool,
+    ) -> Vec<CoinState> {
+        self.coin_states_by_puzzle_hash(&puzzle_hashes, include_spent)
+    }
+
+    #[pyo3(name = "coin_states_by_hint", signature = (hints, include_spent = true))]
+    fn py_coin_states_by_hint(&self, hints: Vec<Bytes32>, include_spent: bool) -> Vec<CoinState> {
+        self.coin_states_by_hint(&hints, include_spent)
+    }
+
+    #[pyo3(name = "children")]
+    fn py_children(&self, coin_id: Bytes32) -> Vec<CoinState> {
+        self.children(&coin_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus_constants::TEST_CONSTANTS;
+    use chia_bls::{sign, SecretKey, Signature};
+    use chia_protocol::{CoinSpend, Program};
+    use clvm_utils::tree_hash_atom;
+    use clvmr::allocator::Allocator;
+    use clvmr::serde::node_to_bytes;
+
+    // spends a coin with the puzzle "1", returning the conditions as the
+    // solution. Conditions are (opcode, args...) lists of atoms
+    fn spend(coin: &Coin, conditions: &[&[&[u8]]]) -> CoinSpend {
+        let mut a = Allocator::new();
+        let mut list = NodePtr::NIL;
+        for cond in conditions.iter().rev() {
+            let mut c = NodePtr::NIL;
+            for arg in cond.iter().rev() {
+                let arg = a.new_atom(arg).unwrap();
+                c = a.new_pair(arg, c).unwrap();
+            }
+            list = a.new_pair(c, list).unwrap();
+        }
+        CoinSpend::new(
+            *coin,
+            Program::from(vec![1]),
+            Program::from(node_to_bytes(&a, list).unwrap()),
+        )
+    }
+
+    fn anyone_can_spend() -> Bytes32 {
+        tree_hash_atom(&[1]).into()
+    }
+
+    #[test]
+    fn test_spend_and_farm() {
+        let mut sim = Simulator::new(&TEST_CONSTANTS);
+        assert_eq!(sim.height(), 0);
+        let coin = sim.new_coin(anyone_can_spend(), 1000);
+        let state = sim.coin_state(&coin.coin_id()).unwrap();
+        assert_eq!(state.created_height, Some(0));
+        assert_eq!(state.spent_height, None);
+
+        // CREATE_COIN with a hint
+        let hint = [7_u8; 32];
+        let ph = [3_u8; 32];
+        let mut a = Allocator::new();
+        let memos = a.new_atom(&hint).unwrap();
+        let memos = a.new_pair(memos, NodePtr::NIL).unwrap();
+        let mut cond = a.new_pair(memos, NodePtr::NIL).unwrap();
+        for arg in [&[0x03, 0x84][..], &ph, &[51]] {
+            let arg = a.new_atom(arg).unwrap();
+            cond = a.new_pair(arg, cond).unwrap();
+        }
+        let solution = a.new_pair(cond, NodePtr::NIL).unwrap();
+        let cs = CoinSpend::new(
+            coin,
+            Program::from(vec![1]),
+            Program::from(node_to_bytes(&a, solution).unwrap()),
+        );
+        let bundle = SpendBundle::new(vec![cs], Signature::default());
+        assert_eq!(sim.push_tx(bundle.clone()).unwrap(), 100);
+        assert_eq!(sim.mempool_len(), 1);
+
+        // the coin is already spent in the mempool
+        assert_eq!(
+            sim.push_tx(bundle.clone()).unwrap_err().1,
+            ErrorCode::DoubleSpend
+        );
+
+        let changed = sim.farm_block();
+        assert_eq!(sim.height(), 1);
+        assert_eq!(sim.timestamp(), GENESIS_TIMESTAMP + SECONDS_PER_BLOCK);
+        assert_eq!(sim.mempool_len(), 0);
+        assert_eq!(changed.len(), 2);
+
+        let child = Coin::new(coin.coin_id(), ph.into(), 900);
+        assert_eq!(
+            sim.coin_state(&coin.coin_id()).unwrap().spent_height,
+            Some(1)
+        );
+        assert_eq!(
+            sim.children(&coin.coin_id()),
+            [CoinState::new(child, None, Some(1))]
+        );
+        assert_eq!(sim.coin_states_by_hint(&[hint.into()], false).len(), 1);
+        assert_eq!(sim.coin_states_by_puzzle_hash(&[ph.into()], true).len(), 1);
+        assert!(sim
+            .coin_states_by_puzzle_hash(&[anyone_can_spend()], false)
+            .is_empty());
+        assert_eq!(
+            sim.coin_states_by_puzzle_hash(&[anyone_can_spend()], true)
+                .len(),
+            1
+        );
+
+        // the coin is spent in the coin set
+        assert_eq!(sim.push_tx(bundle).unwrap_err().1, ErrorCode::DoubleSpend);
+
+        let unknown = Coin::new([1; 32].into(), anyone_can_spend(), 1);
+        let bundle = SpendBundle::new(vec![spend(&unknown, &[])], Signature::default());
+        assert_eq!(
+            sim.push_tx(bundle).unwrap_err().1,
+            ErrorCode::UnknownUnspent
+        );
+    }
+
+    #[test]
+    fn test_timelocks() {
+        let mut sim = Simulator::new(&TEST_CONSTANTS);
+        let coin = sim.new_coin(anyone_can_spend(), 1);
+
+        // ASSERT_HEIGHT_RELATIVE 2
+        let bundle = SpendBundle::new(vec![spend(&coin, &[&[&[82], &[2]]])], Signature::default());
+        assert_eq!(
+            sim.push_tx(bundle.clone()).unwrap_err().1,
+            ErrorCode::AssertHeightRelativeFailed
+        );
+        sim.farm_block();
+        sim.farm_block();
+        assert_eq!(sim.push_tx(bundle).unwrap(), 1);
+
+        // ASSERT_SECONDS_ABSOLUTE
+        let coin = sim.new_coin(anyone_can_spend(), 1);
+        let t = (GENESIS_TIMESTAMP + 1000).to_be_bytes();
+        let bundle = SpendBundle::new(
+            vec![spend(&coin, &[&[&[81], &t[4..]]])],
+            Signature::default(),
+        );
+        assert_eq!(
+            sim.push_tx(bundle.clone()).unwrap_err().1,
+            ErrorCode::AssertSecondsAbsoluteFailed
+        );
+        sim.farm_block_after(1000);
+        assert_eq!(sim.push_tx(bundle).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_signature() {
+        let mut sim = Simulator::new(&TEST_CONSTANTS);
+        let coin = sim.new_coin(anyone_can_spend(), 1);
+        let sk = SecretKey::from_seed(&[1; 32]);
+        let pk = sk.public_key().to_bytes();
+
+        // AGG_SIG_UNSAFE
+        let cs = spend(&coin, &[&[&[49], &pk, b"hello"]]);
+        let bundle = SpendBundle::new(vec![cs.clone()], sign(&sk, b"goodbye"));
+        assert_eq!(
+            sim.push_tx(bundle).unwrap_err().1,
+            ErrorCode::BadAggregateSignature
+        );
+        let bundle = SpendBundle::new(vec![cs], sign(&sk, b"hello"));
+        assert_eq!(sim.push_tx(bundle).unwrap(), 1);
+    }
+}
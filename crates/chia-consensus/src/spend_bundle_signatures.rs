@@ -0,0 +1,381 @@
+use crate::allocator::make_allocator;
+use crate::consensus_constants::ConsensusConstants;
+use crate::error::{Error, Result};
+use crate::gen::conditions::MempoolVisitor;
+use crate::gen::owned_conditions::OwnedSpendBundleConditions;
+use crate::gen::run_puzzle::{run_spend_bundle, run_spend_bundle_with_timings};
+use crate::gen::signing::messages_to_sign;
+use crate::gen::timings::{timed, ValidationTimings};
+use crate::gen::validation_error::{ErrorCode, ValidationErr};
+use chia_bls::{aggregate_pairing, aggregate_verify, hash_to_g2, BlsCache, PublicKey, Signature};
+use chia_protocol::{Bytes, CoinSpend, SpendBundle};
+use clvmr::allocator::NodePtr;
+
+// the (public key, message) pairs a spend bundle's signature has to cover
+fn required_signatures(
+    bundle: &SpendBundle,
+    constants: &ConsensusConstants,
+    flags: u32,
+) -> Result<Vec<(PublicKey, Bytes)>> {
+    let mut a = make_allocator(flags);
+    let conds =
+        run_spend_bundle::<MempoolVisitor>(&mut a, bundle, constants.max_block_cost_clvm, flags)?;
+    let mut ret = Vec::new();
+    for c in conds {
+        let c = OwnedSpendBundleConditions::from(&a, c)?;
+        ret.extend(messages_to_sign(&c, constants));
+    }
+    Ok(ret)
+}
+
+// like required_signatures(), but None if the spend bundle fails to run
+fn bundle_messages(
+    bundle: &SpendBundle,
+    constants: &ConsensusConstants,
+    flags: u32,
+) -> Option<Vec<(PublicKey, Bytes)>> {
+    required_signatures(bundle, constants, flags).ok()
+}
+
+fn verify(
+    cache: &mut Option<&mut BlsCache>,
+    sig: &Signature,
+    pairs: &[&(PublicKey, Bytes)],
+) -> bool {
+    match cache {
+        Some(cache) => cache.aggregate_verify(
+            pairs.iter().map(|(pk, _)| pk),
+            pairs.iter().map(|(_, msg)| msg),
+            sig,
+        ),
+        None => aggregate_verify(sig, pairs.iter().map(|(pk, msg)| (pk, msg.as_slice()))),
+    }
+}
+
+// Checks the signatures of all bundles with a single pairing check. Each
+// bundle's signature and public keys are weighted by an independent random
+// scalar r, which is checked as:
+//   e(g1, sum(r * sig)) == product(e(r * pk, H(pk + msg)))
+// Without the weights, signature material could be moved from one bundle to
+// another, so that two invalid bundles pass as a batch
+fn batch_verify(bundles: &[SpendBundle], messages: &[&Vec<(PublicKey, Bytes)>]) -> bool {
+    let mut sig = Signature::default();
+    let mut pairs = Vec::<(PublicKey, Signature)>::new();
+    for (bundle, msgs) in bundles.iter().zip(messages) {
+        if !bundle.aggregated_signature.is_valid() {
+            return false;
+        }
+        let mut scalar = [0_u8; 16];
+        if getrandom::getrandom(&mut scalar).is_err() {
+            return false;
+        }
+        // make sure the scalar isn't zero
+        scalar[0] |= 0x80;
+
+        let mut weighted_sig = bundle.aggregated_signature.clone();
+        weighted_sig.scalar_multiply(&scalar);
+        sig += &weighted_sig;
+
+        for (pk, msg) in msgs.iter() {
+            let mut aug_msg = pk.to_bytes().to_vec();
+            aug_msg.extend_from_slice(msg.as_slice());
+            let mut weighted_pk = *pk;
+            weighted_pk.scalar_multiply(&scalar);
+            pairs.push((weighted_pk, hash_to_g2(&aug_msg)));
+        }
+    }
+    pairs.push((-PublicKey::generator(), sig));
+    aggregate_pairing(pairs)
+}
+
+// Verifies the aggregate signatures of many spend bundles at once. All
+// signatures are checked with a single, randomly weighted, pairing check (see
+// batch_verify()), which is a lot cheaper than verifying every bundle
+// separately (e.g. when re-validating the mempool after a reorg).
+// Returns whether each bundle's signature is valid. Only if the combined check
+// fails are the bundles verified one at a time, to find the invalid ones.
+// When a cache is passed in, the bundles are always verified one at a time,
+// against the cached pairings.
+// Bundles that fail to run are considered invalid.
+pub fn verify_spend_bundle_signatures(
+    bundles: &[SpendBundle],
+    constants: &ConsensusConstants,
+    mut cache: Option<&mut BlsCache>,
+    flags: u32,
+) -> Vec<bool> {
+    let messages: Vec<Option<Vec<(PublicKey, Bytes)>>> = bundles
+        .iter()
+        .map(|b| bundle_messages(b, constants, flags))
+        .collect();
+
+    if cache.is_none() {
+        let all_messages: Option<Vec<&Vec<(PublicKey, Bytes)>>> =
+            messages.iter().map(Option::as_ref).collect();
+        if let Some(all_messages) = all_messages {
+            if batch_verify(bundles, &all_messages) {
+                return vec![true; bundles.len()];
+            }
+        }
+    }
+
+    bundles
+        .iter()
+        .zip(&messages)
+        .map(|(b, msgs)| {
+            let Some(msgs) = msgs else {
+                return false;
+            };
+            let pairs: Vec<&(PublicKey, Bytes)> = msgs.iter().collect();
+            verify(&mut cache, &b.aggregated_signature, &pairs)
+        })
+        .collect()
+}
+
+// Runs the spend bundle and verifies its aggregate signature against the
+// AGG_SIG_* conditions of its spends. Returns the conditions of each spend
+// (like run_spend_bundle()) along with how long every step took. A bad
+// signature fails with ErrorCode::BadAggregateSignature. For bundles from
+// untrusted peers, max_cost_per_spend limits the cost of each spend, so a
+// single spend can't use up all of max_cost before the others are checked
+pub fn validate_clvm_and_signature(
+    bundle: &SpendBundle,
+    max_cost: u64,
+    max_cost_per_spend: Option<u64>,
+    constants: &ConsensusConstants,
+    flags: u32,
+) -> Result<(Vec<OwnedSpendBundleConditions>, ValidationTimings)> {
+    let mut a = make_allocator(flags);
+    let (conds, mut timings) = run_spend_bundle_with_timings::<MempoolVisitor>(
+        &mut a,
+        bundle,
+        max_cost,
+        max_cost_per_spend,
+        flags,
+    );
+    let mut ret = Vec::<OwnedSpendBundleConditions>::new();
+    for c in conds? {
+        ret.push(OwnedSpendBundleConditions::from(&a, c)?);
+    }
+
+    let valid = timed(&mut timings.signature, || {
+        let pairs: Vec<(PublicKey, Bytes)> = ret
+            .iter()
+            .flat_map(|c| messages_to_sign(c, constants))
+            .collect();
+        aggregate_verify(
+            &bundle.aggregated_signature,
+            pairs.iter().map(|(pk, msg)| (pk, msg.as_slice())),
+        )
+    });
+    if !valid {
+        return Err(ValidationErr(NodePtr::NIL, ErrorCode::BadAggregateSignature).into());
+    }
+    Ok((ret, timings))
+}
+
+// Creates a spend bundle of the coin spends, signed by the signatures of all
+// (public key, message) pairs their AGG_SIG_* conditions require. The
+// signature of each pair is returned by sign(), which is typically just
+// chia_bls::sign() with the secret key of the public key. Fails if any coin
+// spend fails to run or if sign() fails.
+pub fn sign_coin_spends<E, F>(
+    coin_spends: Vec<CoinSpend>,
+    constants: &ConsensusConstants,
+    flags: u32,
+    mut sign: F,
+) -> std::result::Result<SpendBundle, E>
+where
+    E: From<Error>,
+    F: FnMut(&PublicKey, &[u8]) -> std::result::Result<Signature, E>,
+{
+    let mut bundle = SpendBundle::new(coin_spends, Signature::default());
+    let mut sig = Signature::default();
+    for (pk, msg) in required_signatures(&bundle, constants, flags)? {
+        sig.aggregate(&sign(&pk, &msg)?);
+    }
+    bundle.aggregated_signature = sig;
+    Ok(bundle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus_constants::TEST_CONSTANTS;
+    use chia_bls::{sign, SecretKey};
+    use chia_protocol::{Bytes32, Coin, CoinSpend, Program};
+    use clvm_utils::tree_hash;
+    use clvmr::allocator::Allocator;
+    use clvmr::serde::node_to_bytes;
+    use std::time::Duration;
+
+    // a spend bundle with a single spend, whose puzzle returns an
+    // AGG_SIG_UNSAFE condition for the message
+    fn make_bundle(sk: &SecretKey, msg: &[u8], valid: bool) -> SpendBundle {
+        let mut a = Allocator::new();
+        let pk = a.new_atom(&sk.public_key().to_bytes()).unwrap();
+        let m = a.new_atom(msg).unwrap();
+        let op = a.new_atom(&[49]).unwrap();
+        let mut cond = NodePtr::NIL;
+        for item in [m, pk, op] {
+            cond = a.new_pair(item, cond).unwrap();
+        }
+        let conds = a.new_pair(cond, NodePtr::NIL).unwrap();
+        let quote = a.one();
+        let puzzle = a.new_pair(quote, conds).unwrap();
+
+        let coin = Coin::new(Bytes32::from([msg[0]; 32]), tree_hash(&a, puzzle).into(), 1);
+        let spend = CoinSpend::new(
+            coin,
+            Program::from(node_to_bytes(&a, puzzle).unwrap()),
+            Program::default(),
+        );
+        let sig = if valid {
+            sign(sk, msg)
+        } else {
+            sign(sk, b"something else")
+        };
+        SpendBundle::new(vec![spend], sig)
+    }
+
+    #[test]
+    fn test_all_valid() {
+        let sk = SecretKey::from_seed(&[1; 32]);
+        let bundles: Vec<SpendBundle> = (1..5_u8)
+            .map(|i| make_bundle(&sk, &[i; 10], true))
+            .collect();
+        assert_eq!(
+            verify_spend_bundle_signatures(&bundles, &TEST_CONSTANTS, None, 0),
+            [true; 4]
+        );
+
+        let mut cache = BlsCache::default();
+        assert_eq!(
+            verify_spend_bundle_signatures(&bundles, &TEST_CONSTANTS, Some(&mut cache), 0),
+            [true; 4]
+        );
+        assert_eq!(cache.len(), 4);
+
+        assert!(verify_spend_bundle_signatures(&[], &TEST_CONSTANTS, None, 0).is_empty());
+    }
+
+    #[test]
+    fn test_invalid() {
+        let sk = SecretKey::from_seed(&[1; 32]);
+        let mut bundles: Vec<SpendBundle> = (1..5_u8)
+            .map(|i| make_bundle(&sk, &[i; 10], i != 3))
+            .collect();
+        assert_eq!(
+            verify_spend_bundle_signatures(&bundles, &TEST_CONSTANTS, None, 0),
+            [true, true, false, true]
+        );
+
+        // a bundle that fails to run is invalid
+        bundles[0].coin_spends[0].solution = Program::from(vec![0xff]);
+        bundles[0].coin_spends[0].puzzle_reveal = Program::from(vec![0x08]);
+        let mut cache = BlsCache::default();
+        assert_eq!(
+            verify_spend_bundle_signatures(&bundles, &TEST_CONSTANTS, Some(&mut cache), 0),
+            [false, true, false, true]
+        );
+    }
+
+    #[test]
+    fn test_offset_signatures() {
+        // two bundles whose signatures are each off by the same amount, in
+        // opposite directions. Neither is valid on its own, but their sum is
+        // the sum of the valid signatures
+        let sk = SecretKey::from_seed(&[1; 32]);
+        let mut bundles: Vec<SpendBundle> = (1..3_u8)
+            .map(|i| make_bundle(&sk, &[i; 10], true))
+            .collect();
+        let offset = sign(&sk, b"offset");
+        bundles[0].aggregated_signature += &offset;
+        bundles[1].aggregated_signature -= &offset;
+
+        let mut sig = bundles[0].aggregated_signature.clone();
+        sig += &bundles[1].aggregated_signature;
+        let pairs: Vec<(PublicKey, Bytes)> = bundles
+            .iter()
+            .flat_map(|b| required_signatures(b, &TEST_CONSTANTS, 0).unwrap())
+            .collect();
+        assert!(aggregate_verify(
+            &sig,
+            pairs.iter().map(|(pk, msg)| (pk, msg.as_slice()))
+        ));
+
+        assert_eq!(
+            verify_spend_bundle_signatures(&bundles, &TEST_CONSTANTS, None, 0),
+            [false, false]
+        );
+        let mut cache = BlsCache::default();
+        assert_eq!(
+            verify_spend_bundle_signatures(&bundles, &TEST_CONSTANTS, Some(&mut cache), 0),
+            [false, false]
+        );
+    }
+
+    #[test]
+    fn test_validate_clvm_and_signature() {
+        let sk = SecretKey::from_seed(&[1; 32]);
+        let bundle = make_bundle(&sk, &[1; 10], true);
+        let (conds, timings) =
+            validate_clvm_and_signature(&bundle, 11000000000, None, &TEST_CONSTANTS, 0)
+                .expect("validate_clvm_and_signature");
+        assert_eq!(conds.len(), 1);
+        assert_eq!(conds[0].agg_sig_unsafe.len(), 1);
+        // verifying the signature takes a lot longer than the timer resolution
+        assert!(timings.signature > Duration::ZERO);
+        assert!(timings.total() >= timings.signature);
+
+        let bundle = make_bundle(&sk, &[1; 10], false);
+        assert_eq!(
+            validate_clvm_and_signature(&bundle, 11000000000, None, &TEST_CONSTANTS, 0)
+                .unwrap_err(),
+            Error::Validation(ValidationErr(
+                NodePtr::NIL,
+                ErrorCode::BadAggregateSignature
+            ))
+        );
+
+        let bundle = make_bundle(&sk, &[1; 10], true);
+        let cost = conds[0].cost;
+        assert!(
+            validate_clvm_and_signature(&bundle, 11000000000, Some(cost), &TEST_CONSTANTS, 0)
+                .is_ok()
+        );
+        assert_eq!(
+            validate_clvm_and_signature(&bundle, 11000000000, Some(cost - 1), &TEST_CONSTANTS, 0)
+                .unwrap_err(),
+            Error::Validation(ValidationErr(NodePtr::NIL, ErrorCode::CostExceeded))
+        );
+    }
+
+    #[test]
+    fn test_sign_coin_spends() {
+        let sk = SecretKey::from_seed(&[1; 32]);
+        let coin_spends: Vec<CoinSpend> = (1..5_u8)
+            .flat_map(|i| make_bundle(&sk, &[i; 10], true).coin_spends)
+            .collect();
+
+        let mut calls = 0;
+        let bundle = sign_coin_spends(coin_spends.clone(), &TEST_CONSTANTS, 0, |pk, msg| {
+            assert_eq!(*pk, sk.public_key());
+            calls += 1;
+            Ok::<_, Error>(sign(&sk, msg))
+        })
+        .expect("sign_coin_spends");
+        assert_eq!(calls, 4);
+        assert_eq!(bundle.coin_spends, coin_spends);
+        assert_eq!(
+            verify_spend_bundle_signatures(&[bundle], &TEST_CONSTANTS, None, 0),
+            [true]
+        );
+
+        // errors from the signer are passed through
+        let err = sign_coin_spends(coin_spends, &TEST_CONSTANTS, 0, |_, _| {
+            Err(Error::Custom("no key".to_string()))
+        })
+        .unwrap_err();
+        assert_eq!(err, Error::Custom("no key".to_string()));
+    }
+}
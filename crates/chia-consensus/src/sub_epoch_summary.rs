@@ -0,0 +1,235 @@
+use crate::consensus_constants::ConsensusConstants;
+use crate::error::Error;
+use chia_protocol::{BlockRecord, Bytes32, SubEpochSummary};
+use chia_traits::Streamable;
+
+// Creates the sub-epoch summary included in the block at
+// blocks_included_height, whose grandparent is prev_prev_block (the summary
+// describes the sub-epoch that ended with the previous block).
+// new_difficulty and new_sub_slot_iters are only set at the end of an epoch,
+// when they're adjusted. The summary links to the previous one, found by
+// walking back from prev_prev_block until a block that included one. lookup
+// returns the block record with the specified header hash, or None if it's not
+// known, like for find_fork_point(). This matches make_sub_epoch_summary() of
+// the node.
+pub fn make_sub_epoch_summary<E, F>(
+    constants: &ConsensusConstants,
+    blocks_included_height: u32,
+    prev_prev_block: &BlockRecord,
+    new_difficulty: Option<u64>,
+    new_sub_slot_iters: Option<u64>,
+    mut lookup: F,
+) -> Result<SubEpochSummary, E>
+where
+    E: From<Error>,
+    F: FnMut(&Bytes32) -> Result<Option<BlockRecord>, E>,
+{
+    if prev_prev_block.height.checked_add(2) != Some(blocks_included_height) {
+        return Err(Error::Custom(format!(
+            "block at height {} is not the grandparent of height {blocks_included_height}",
+            prev_prev_block.height
+        ))
+        .into());
+    }
+
+    // the first sub-epoch doesn't have a previous one. Like the node, this
+    // assumes fewer than max_sub_slot_blocks overflow blocks are infused in
+    // the first slots
+    if (u64::from(blocks_included_height) + u64::from(constants.max_sub_slot_blocks))
+        / u64::from(constants.sub_epoch_blocks)
+        <= 1
+    {
+        return Ok(SubEpochSummary::new(
+            constants.genesis_challenge,
+            constants.genesis_challenge,
+            0,
+            None,
+            None,
+        ));
+    }
+
+    let mut curr = prev_prev_block.clone();
+    let prev_ses = loop {
+        if let Some(ses) = &curr.sub_epoch_summary_included {
+            break ses.hash();
+        }
+        curr = lookup(&curr.prev_hash)?.ok_or(Error::BlockNotFound(curr.prev_hash))?;
+    };
+
+    // a sub-epoch summary is always included in the first block of a
+    // sub-slot, so the slot hashes are set
+    let Some(reward_chain_hash) = curr
+        .finished_reward_slot_hashes
+        .as_ref()
+        .and_then(|hashes| hashes.last())
+    else {
+        return Err(Error::Custom(format!(
+            "block {} includes a sub-epoch summary but no finished reward slots",
+            curr.header_hash
+        ))
+        .into());
+    };
+    let num_blocks_overflow =
+        u8::try_from(curr.height % constants.sub_epoch_blocks).map_err(|_| {
+            Error::Custom(format!(
+                "too many overflow blocks at height {}",
+                curr.height
+            ))
+        })?;
+
+    Ok(SubEpochSummary::new(
+        prev_ses.into(),
+        *reward_chain_hash,
+        num_blocks_overflow,
+        new_difficulty,
+        new_sub_slot_iters,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus_constants::TEST_CONSTANTS;
+    use crate::error::Result;
+    use crate::test_block_record::{hash, BlockRecordBuilder};
+    use rstest::rstest;
+    use std::collections::HashMap;
+
+    // the first block of a sub-slot if it includes a sub-epoch summary
+    fn record(height: u32, ses: Option<SubEpochSummary>) -> BlockRecord {
+        let builder = BlockRecordBuilder::new(hash(height))
+            .prev_hash(if height == 0 {
+                TEST_CONSTANTS.genesis_challenge
+            } else {
+                hash(height - 1)
+            })
+            .height(height)
+            .weight(height as u128)
+            .total_iters(height as u128 * 10);
+        let Some(ses) = ses else {
+            return builder.build();
+        };
+        let slot_hashes = vec![hash(height.wrapping_add(1000))];
+        builder
+            .finished_challenge_slot_hashes(slot_hashes.clone())
+            .finished_infused_challenge_slot_hashes(slot_hashes.clone())
+            .finished_reward_slot_hashes(slot_hashes)
+            .sub_epoch_summary(ses)
+            .build()
+    }
+
+    // a chain of blocks, where the block at ses_height includes a sub-epoch
+    // summary
+    fn chain(len: u32, ses_height: u32) -> (HashMap<Bytes32, BlockRecord>, SubEpochSummary) {
+        let ses = SubEpochSummary::new(hash(7), hash(8), 0, None, None);
+        let blocks = (0..len)
+            .map(|h| record(h, (h == ses_height).then(|| ses.clone())))
+            .map(|r| (r.header_hash, r))
+            .collect();
+        (blocks, ses)
+    }
+
+    fn summary(
+        blocks: &HashMap<Bytes32, BlockRecord>,
+        height: u32,
+        new_difficulty: Option<u64>,
+    ) -> Result<SubEpochSummary> {
+        make_sub_epoch_summary(
+            &TEST_CONSTANTS,
+            height,
+            &blocks[&hash(height - 2)],
+            new_difficulty,
+            None,
+            |h| Ok(blocks.get(h).cloned()),
+        )
+    }
+
+    // summaries included below 2 * sub_epoch_blocks - max_sub_slot_blocks
+    // (640) are treated as the first one
+    #[rstest]
+    #[case(384)]
+    #[case(385)]
+    #[case(500)]
+    #[case(639)]
+    fn test_first_sub_epoch(#[case] height: u32) {
+        let (blocks, _) = chain(800, 770);
+        let ses = summary(&blocks, height, Some(5)).expect("summary");
+        assert_eq!(
+            ses,
+            SubEpochSummary::new(
+                TEST_CONSTANTS.genesis_challenge,
+                TEST_CONSTANTS.genesis_challenge,
+                0,
+                None,
+                None
+            )
+        );
+    }
+
+    #[rstest]
+    #[case(640, 386)]
+    #[case(780, 386)]
+    #[case(780, 770)]
+    fn test_next_sub_epoch(#[case] height: u32, #[case] ses_height: u32) {
+        let (blocks, prev) = chain(800, ses_height);
+        let ses = summary(&blocks, height, Some(5)).expect("summary");
+        assert_eq!(
+            ses,
+            SubEpochSummary::new(
+                prev.hash().into(),
+                hash(ses_height + 1000),
+                2,
+                Some(5),
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn test_overflow_height() {
+        let ses = SubEpochSummary::new(hash(7), hash(8), 0, None, None);
+        let prev_prev = record(u32::MAX - 2, Some(ses.clone()));
+        let summary =
+            make_sub_epoch_summary(&TEST_CONSTANTS, u32::MAX, &prev_prev, None, None, |_| {
+                Ok::<_, Error>(None)
+            })
+            .expect("summary");
+        assert_eq!(
+            summary,
+            SubEpochSummary::new(
+                ses.hash().into(),
+                hash((u32::MAX - 2).wrapping_add(1000)),
+                ((u32::MAX - 2) % 384) as u8,
+                None,
+                None
+            )
+        );
+
+        // there is no block two heights after u32::MAX - 1
+        let prev_prev = record(u32::MAX - 1, Some(ses));
+        assert!(matches!(
+            make_sub_epoch_summary(&TEST_CONSTANTS, 0, &prev_prev, None, None, |_| {
+                Ok::<_, Error>(None)
+            }),
+            Err(Error::Custom(_))
+        ));
+    }
+
+    #[test]
+    fn test_errors() {
+        let (mut blocks, _) = chain(800, 770);
+        // the parent isn't the grandparent
+        assert!(matches!(
+            make_sub_epoch_summary(&TEST_CONSTANTS, 780, &blocks[&hash(779)], None, None, |h| {
+                Ok::<_, Error>(blocks.get(h).cloned())
+            }),
+            Err(Error::Custom(_))
+        ));
+
+        blocks.remove(&hash(774));
+        assert_eq!(
+            summary(&blocks, 780, None),
+            Err(Error::BlockNotFound(hash(774)))
+        );
+    }
+}
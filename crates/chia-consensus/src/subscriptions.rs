@@ -0,0 +1,486 @@
+use crate::gen::owned_conditions::OwnedSpendBundleConditions;
+use chia_protocol::{Bytes32, Coin, CoinState, CoinStateUpdate};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+#[cfg(feature = "py-bindings")]
+use pyo3::prelude::*;
+
+// the default limit of the number of subscriptions a single peer may have,
+// the same as the full node's max_subscribe_items
+pub const DEFAULT_MAX_SUBSCRIPTIONS: usize = 200000;
+
+// One kind of subscription (puzzle hashes or coin IDs), indexed both ways
+#[derive(Clone, Debug, Default)]
+struct Subscriptions {
+    by_item: HashMap<Bytes32, HashSet<Bytes32>>,
+    by_peer: HashMap<Bytes32, HashSet<Bytes32>>,
+}
+
+impl Subscriptions {
+    fn count(&self, peer: &Bytes32) -> usize {
+        self.by_peer.get(peer).map_or(0, HashSet::len)
+    }
+
+    fn add(&mut self, peer: Bytes32, item: Bytes32) -> bool {
+        if !self.by_peer.entry(peer).or_default().insert(item) {
+            return false;
+        }
+        self.by_item.entry(item).or_default().insert(peer);
+        true
+    }
+
+    fn remove(&mut self, peer: &Bytes32, item: &Bytes32) -> bool {
+        let Some(items) = self.by_peer.get_mut(peer) else {
+            return false;
+        };
+        if !items.remove(item) {
+            return false;
+        }
+        if items.is_empty() {
+            self.by_peer.remove(peer);
+        }
+        if let Some(peers) = self.by_item.get_mut(item) {
+            peers.remove(peer);
+            if peers.is_empty() {
+                self.by_item.remove(item);
+            }
+        }
+        true
+    }
+
+    fn remove_peer(&mut self, peer: &Bytes32) {
+        for item in self.by_peer.remove(peer).unwrap_or_default() {
+            if let Some(peers) = self.by_item.get_mut(&item) {
+                peers.remove(peer);
+                if peers.is_empty() {
+                    self.by_item.remove(&item);
+                }
+            }
+        }
+    }
+
+    fn peers<'a>(&'a self, item: &Bytes32) -> impl Iterator<Item = &'a Bytes32> + 'a {
+        self.by_item.get(item).into_iter().flatten()
+    }
+
+    fn items(&self, peer: &Bytes32) -> Vec<Bytes32> {
+        let mut items: Vec<Bytes32> = self
+            .by_peer
+            .get(peer)
+            .into_iter()
+            .flatten()
+            .copied()
+            .collect();
+        items.sort();
+        items
+    }
+}
+
+// Tracks the puzzle hash and coin ID subscriptions wallet peers register with
+// RegisterForPhUpdates and RegisterForCoinUpdates, and computes the
+// CoinStateUpdate each peer should be sent when a new peak is added. Peers are
+// identified by their node ID. A puzzle hash subscription matches coins with
+// that puzzle hash as well as coins hinted to it. The limit on the number of
+// subscriptions applies to the sum of both kinds.
+#[cfg_attr(feature = "py-bindings", pyclass(module = "gold_rs"))]
+#[derive(Clone, Debug)]
+pub struct SubscriptionManager {
+    max_subscriptions: usize,
+    puzzle_hashes: Subscriptions,
+    coin_ids: Subscriptions,
+}
+
+impl Default for SubscriptionManager {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_SUBSCRIPTIONS)
+    }
+}
+
+impl SubscriptionManager {
+    pub fn new(max_subscriptions: usize) -> Self {
+        Self {
+            max_subscriptions,
+            puzzle_hashes: Subscriptions::default(),
+            coin_ids: Subscriptions::default(),
+        }
+    }
+
+    pub fn subscription_count(&self, peer: &Bytes32) -> usize {
+        self.puzzle_hashes.count(peer) + self.coin_ids.count(peer)
+    }
+
+    // subscribes the peer to the puzzle hashes, until it hits the limit.
+    // Returns the puzzle hashes that were added, i.e. not the ones the peer
+    // was already subscribed to or the ones exceeding the limit. If some were
+    // left out because of the limit, the full node responds with
+    // RejectStateReason::ExceededSubscriptionLimit
+    pub fn add_puzzle_subscriptions(
+        &mut self,
+        peer: Bytes32,
+        puzzle_hashes: &[Bytes32],
+    ) -> Vec<Bytes32> {
+        let mut room = self
+            .max_subscriptions
+            .saturating_sub(self.subscription_count(&peer));
+        let mut added = Vec::new();
+        for ph in puzzle_hashes {
+            if room == 0 {
+                break;
+            }
+            if self.puzzle_hashes.add(peer, *ph) {
+                added.push(*ph);
+                room -= 1;
+            }
+        }
+        added
+    }
+
+    // like add_puzzle_subscriptions(), but for coin IDs
+    pub fn add_coin_subscriptions(&mut self, peer: Bytes32, coin_ids: &[Bytes32]) -> Vec<Bytes32> {
+        let mut room = self
+            .max_subscriptions
+            .saturating_sub(self.subscription_count(&peer));
+        let mut added = Vec::new();
+        for id in coin_ids {
+            if room == 0 {
+                break;
+            }
+            if self.coin_ids.add(peer, *id) {
+                added.push(*id);
+                room -= 1;
+            }
+        }
+        added
+    }
+
+    // returns the puzzle hashes that were removed
+    pub fn remove_puzzle_subscriptions(
+        &mut self,
+        peer: &Bytes32,
+        puzzle_hashes: &[Bytes32],
+    ) -> Vec<Bytes32> {
+        puzzle_hashes
+            .iter()
+            .filter(|ph| self.puzzle_hashes.remove(peer, ph))
+            .copied()
+            .collect()
+    }
+
+    // returns the coin IDs that were removed
+    pub fn remove_coin_subscriptions(
+        &mut self,
+        peer: &Bytes32,
+        coin_ids: &[Bytes32],
+    ) -> Vec<Bytes32> {
+        coin_ids
+            .iter()
+            .filter(|id| self.coin_ids.remove(peer, id))
+            .copied()
+            .collect()
+    }
+
+    // removes all subscriptions of a peer, e.g. when it disconnects
+    pub fn remove_peer(&mut self, peer: &Bytes32) {
+        self.puzzle_hashes.remove_peer(peer);
+        self.coin_ids.remove_peer(peer);
+    }
+
+    pub fn puzzle_subscriptions(&self, peer: &Bytes32) -> Vec<Bytes32> {
+        self.puzzle_hashes.items(peer)
+    }
+
+    pub fn coin_subscriptions(&self, peer: &Bytes32) -> Vec<Bytes32> {
+        self.coin_ids.items(peer)
+    }
+
+    // Matches the coin states that changed with a new peak against the
+    // subscriptions. states are the coins created and spent by the new blocks
+    // (and the ones rolled back by a reorg), and hints maps coin IDs to their
+    // hint. Returns the update to send to every peer with at least one
+    // matching coin state, ordered by peer. The items of an update keep the
+    // order of states.
+    pub fn updates_for_peak(
+        &self,
+        height: u32,
+        fork_height: u32,
+        peak_hash: Bytes32,
+        states: &[CoinState],
+        hints: &HashMap<Bytes32, Bytes32>,
+    ) -> Vec<(Bytes32, CoinStateUpdate)> {
+        let mut items = BTreeMap::<Bytes32, Vec<CoinState>>::new();
+        for state in states {
+            let coin_id = state.coin.coin_id();
+            let mut peers: HashSet<&Bytes32> = self.coin_ids.peers(&coin_id).collect();
+            peers.extend(self.puzzle_hashes.peers(&state.coin.puzzle_hash));
+            if let Some(hint) = hints.get(&coin_id) {
+                peers.extend(self.puzzle_hashes.peers(hint));
+            }
+            for peer in peers {
+                items.entry(*peer).or_default().push(*state);
+            }
+        }
+        items
+            .into_iter()
+            .map(|(peer, items)| {
+                (
+                    peer,
+                    CoinStateUpdate::new(height, fork_height, peak_hash, items),
+                )
+            })
+            .collect()
+    }
+}
+
+// coin IDs, indexed by a hint or puzzle hash
+pub type CoinIndex = HashMap<Bytes32, Vec<Bytes32>>;
+
+// Indexes the coins spent and created by a block (or any set of spends) in a
+// single pass, to look up which of them match a peer's puzzle hash
+// subscriptions. Returns the coin IDs by hint and the coin IDs by puzzle
+// hash. Both spent and created coins are indexed by their puzzle hash, only
+// created coins have hints. Hints that aren't 32 bytes can't match a
+// subscription, so they're left out. The coin IDs of every entry are sorted.
+pub fn index_spends(conds: &OwnedSpendBundleConditions) -> (CoinIndex, CoinIndex) {
+    let mut by_hint = CoinIndex::new();
+    let mut by_puzzle_hash = CoinIndex::new();
+    for spend in &conds.spends {
+        by_puzzle_hash
+            .entry(spend.puzzle_hash)
+            .or_default()
+            .push(spend.coin_id);
+        for (puzzle_hash, amount, hint) in &spend.create_coin {
+            let coin_id = Coin::new(spend.coin_id, *puzzle_hash, *amount).coin_id();
+            by_puzzle_hash
+                .entry(*puzzle_hash)
+                .or_default()
+                .push(coin_id);
+            if let Some(Ok(hint)) = hint.as_ref().map(|h| Bytes32::try_from(h.as_slice())) {
+                by_hint.entry(hint).or_default().push(coin_id);
+            }
+        }
+    }
+    // a coin that's created and spent in the same block is indexed twice
+    for ids in by_hint.values_mut().chain(by_puzzle_hash.values_mut()) {
+        ids.sort();
+        ids.dedup();
+    }
+    (by_hint, by_puzzle_hash)
+}
+
+#[cfg(feature = "py-bindings")]
+#[pymethods]
+impl SubscriptionManager {
+    #[new]
+    #[pyo3(signature = (max_subscriptions = DEFAULT_MAX_SUBSCRIPTIONS))]
+    fn py_new(max_subscriptions: usize) -> Self {
+        Self::new(max_subscriptions)
+    }
+
+    #[pyo3(name = "subscription_count")]
+    fn py_subscription_count(&self, peer: Bytes32) -> usize {
+        self.subscription_count(&peer)
+    }
+
+    #[pyo3(name = "add_puzzle_subscriptions")]
+    fn py_add_puzzle_subscriptions(
+        &mut self,
+        peer: Bytes32,
+        puzzle_hashes: Vec<Bytes32>,
+    ) -> Vec<Bytes32> {
+        self.add_puzzle_subscriptions(peer, &puzzle_hashes)
+    }
+
+    #[pyo3(name = "add_coin_subscriptions")]
+    fn py_add_coin_subscriptions(&mut self, peer: Bytes32, coin_ids: Vec<Bytes32>) -> Vec<Bytes32> {
+        self.add_coin_subscriptions(peer, &coin_ids)
+    }
+
+    #[pyo3(name = "remove_puzzle_subscriptions")]
+    fn py_remove_puzzle_subscriptions(
+        &mut self,
+        peer: Bytes32,
+        puzzle_hashes: Vec<Bytes32>,
+    ) -> Vec<Bytes32> {
+        self.remove_puzzle_subscriptions(&peer, &puzzle_hashes)
+    }
+
+    #[pyo3(name = "remove_coin_subscriptions")]
+    fn py_remove_coin_subscriptions(
+        &mut self,
+        peer: Bytes32,
+        coin_ids: Vec<Bytes32>,
+    ) -> Vec<Bytes32> {
+        self.remove_coin_subscriptions(&peer, &coin_ids)
+    }
+
+    #[pyo3(name = "remove_peer")]
+    fn py_remove_peer(&mut self, peer: Bytes32) {
+        self.remove_peer(&peer);
+    }
+
+    #[pyo3(name = "puzzle_subscriptions")]
+    fn py_puzzle_subscriptions(&self, peer: Bytes32) -> Vec<Bytes32> {
+        self.puzzle_subscriptions(&peer)
+    }
+
+    #[pyo3(name = "coin_subscriptions")]
+    fn py_coin_subscriptions(&self, peer: Bytes32) -> Vec<Bytes32> {
+        self.coin_subscriptions(&peer)
+    }
+
+    #[pyo3(name = "updates_for_peak")]
+    fn py_updates_for_peak(
+        &self,
+        py: Python<'_>,
+        height: u32,
+        fork_height: u32,
+        peak_hash: Bytes32,
+        states: Vec<CoinState>,
+        hints: HashMap<Bytes32, Bytes32>,
+    ) -> Vec<(Bytes32, CoinStateUpdate)> {
+        py.allow_threads(|| self.updates_for_peak(height, fork_height, peak_hash, &states, &hints))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gen::owned_conditions::OwnedSpend;
+    use chia_protocol::Bytes;
+
+    fn b(v: u8) -> Bytes32 {
+        Bytes32::new([v; 32])
+    }
+
+    fn state(parent: u8, puzzle_hash: u8) -> CoinState {
+        CoinState::new(Coin::new(b(parent), b(puzzle_hash), 1), None, Some(10))
+    }
+
+    #[test]
+    fn test_limit() {
+        let mut mgr = SubscriptionManager::new(3);
+        assert_eq!(
+            mgr.add_puzzle_subscriptions(b(1), &[b(10), b(11), b(10)]),
+            [b(10), b(11)]
+        );
+        // the limit is shared between puzzle hash and coin subscriptions
+        assert_eq!(mgr.add_coin_subscriptions(b(1), &[b(20), b(21)]), [b(20)]);
+        assert_eq!(mgr.subscription_count(&b(1)), 3);
+        assert!(mgr.add_puzzle_subscriptions(b(1), &[b(12)]).is_empty());
+
+        // the limit is per peer
+        assert_eq!(mgr.add_coin_subscriptions(b(2), &[b(20)]), [b(20)]);
+
+        assert_eq!(
+            mgr.remove_puzzle_subscriptions(&b(1), &[b(10), b(12)]),
+            [b(10)]
+        );
+        assert_eq!(mgr.add_puzzle_subscriptions(b(1), &[b(12)]), [b(12)]);
+        assert_eq!(mgr.puzzle_subscriptions(&b(1)), [b(11), b(12)]);
+        assert_eq!(mgr.coin_subscriptions(&b(1)), [b(20)]);
+
+        mgr.remove_peer(&b(1));
+        assert_eq!(mgr.subscription_count(&b(1)), 0);
+        assert_eq!(mgr.subscription_count(&b(2)), 1);
+        assert!(mgr.puzzle_hashes.by_item.is_empty());
+    }
+
+    #[test]
+    fn test_updates_for_peak() {
+        let mut mgr = SubscriptionManager::default();
+        let by_ph = state(1, 10);
+        let hinted = state(2, 11);
+        let by_id = state(3, 12);
+        let unrelated = state(4, 13);
+
+        mgr.add_puzzle_subscriptions(b(100), &[b(10)]);
+        mgr.add_puzzle_subscriptions(b(101), &[b(20)]);
+        mgr.add_coin_subscriptions(b(101), &[by_id.coin.coin_id(), by_ph.coin.coin_id()]);
+        mgr.add_coin_subscriptions(b(102), &[b(99)]);
+
+        let hints = HashMap::from([(hinted.coin.coin_id(), b(20))]);
+        let updates =
+            mgr.updates_for_peak(10, 9, b(50), &[by_ph, hinted, by_id, unrelated], &hints);
+        assert_eq!(
+            updates,
+            [
+                (b(100), CoinStateUpdate::new(10, 9, b(50), vec![by_ph])),
+                // a coin matching multiple subscriptions is only included once
+                (
+                    b(101),
+                    CoinStateUpdate::new(10, 9, b(50), vec![by_ph, hinted, by_id])
+                ),
+            ]
+        );
+    }
+
+    fn spend(coin: Coin, create_coin: Vec<(Bytes32, u64, Option<Bytes>)>) -> OwnedSpend {
+        OwnedSpend {
+            coin_id: coin.coin_id(),
+            parent_id: coin.parent_coin_info,
+            puzzle_hash: coin.puzzle_hash,
+            coin_amount: coin.amount,
+            height_relative: None,
+            seconds_relative: None,
+            before_height_relative: None,
+            before_seconds_relative: None,
+            birth_height: None,
+            birth_seconds: None,
+            create_coin,
+            agg_sig_me: vec![],
+            agg_sig_parent: vec![],
+            agg_sig_puzzle: vec![],
+            agg_sig_amount: vec![],
+            agg_sig_puzzle_amount: vec![],
+            agg_sig_parent_amount: vec![],
+            agg_sig_parent_puzzle: vec![],
+            flags: 0,
+        }
+    }
+
+    #[test]
+    fn test_index_spends() {
+        let spent = Coin::new(b(1), b(10), 100);
+        let hinted = Coin::new(spent.coin_id(), b(11), 40);
+        let short_hint = Coin::new(spent.coin_id(), b(10), 30);
+        // spent in the same block it was created in
+        let ephemeral = Coin::new(spent.coin_id(), b(12), 30);
+
+        let conds = OwnedSpendBundleConditions {
+            spends: vec![
+                spend(
+                    spent,
+                    vec![
+                        (b(11), 40, Some(b(20).to_vec().into())),
+                        (b(10), 30, Some(vec![1, 2, 3].into())),
+                        (b(12), 30, None),
+                    ],
+                ),
+                spend(ephemeral, vec![]),
+            ],
+            reserve_fee: 0,
+            height_absolute: 0,
+            seconds_absolute: 0,
+            before_height_absolute: None,
+            before_seconds_absolute: None,
+            agg_sig_unsafe: vec![],
+            cost: 0,
+            removal_amount: 130,
+            addition_amount: 100,
+        };
+
+        let (by_hint, by_puzzle_hash) = index_spends(&conds);
+        assert_eq!(by_hint, HashMap::from([(b(20), vec![hinted.coin_id()])]));
+
+        let mut same_ph = vec![spent.coin_id(), short_hint.coin_id()];
+        same_ph.sort();
+        assert_eq!(
+            by_puzzle_hash,
+            HashMap::from([
+                (b(10), same_ph),
+                (b(11), vec![hinted.coin_id()]),
+                (b(12), vec![ephemeral.coin_id()]),
+            ])
+        );
+    }
+}
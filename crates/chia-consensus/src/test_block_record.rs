@@ -0,0 +1,153 @@
+use chia_bls::G1Element;
+use chia_protocol::{BlockRecord, Bytes32, ClassgroupElement, SubEpochSummary};
+
+// a distinct hash for every v, for header hashes and challenges in tests
+pub(crate) fn hash(v: u32) -> Bytes32 {
+    let mut ret = [0; 32];
+    ret[..4].copy_from_slice(&v.to_be_bytes());
+    Bytes32::new(ret)
+}
+
+// Builds block records for unit tests, which only need a few of the fields
+// to be set. The other fields are zero, empty or None
+pub(crate) struct BlockRecordBuilder(BlockRecord);
+
+// some of the setters are only used by tests that need the test_blocks feature
+#[allow(dead_code)]
+impl BlockRecordBuilder {
+    pub fn new(header_hash: Bytes32) -> Self {
+        Self(BlockRecord::new(
+            header_hash,
+            Bytes32::default(),
+            0,
+            0,
+            0,
+            0,
+            ClassgroupElement::default(),
+            None,
+            Bytes32::default(),
+            Bytes32::default(),
+            0,
+            Bytes32::default(),
+            Bytes32::default(),
+            0,
+            0,
+            false,
+            0,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            G1Element::default(),
+        ))
+    }
+
+    pub fn prev_hash(mut self, prev_hash: Bytes32) -> Self {
+        self.0.prev_hash = prev_hash;
+        self
+    }
+
+    pub fn height(mut self, height: u32) -> Self {
+        self.0.height = height;
+        self
+    }
+
+    pub fn weight(mut self, weight: u128) -> Self {
+        self.0.weight = weight;
+        self
+    }
+
+    pub fn total_iters(mut self, total_iters: u128) -> Self {
+        self.0.total_iters = total_iters;
+        self
+    }
+
+    pub fn signage_point_index(mut self, signage_point_index: u8) -> Self {
+        self.0.signage_point_index = signage_point_index;
+        self
+    }
+
+    pub fn sub_slot_iters(mut self, sub_slot_iters: u64) -> Self {
+        self.0.sub_slot_iters = sub_slot_iters;
+        self
+    }
+
+    pub fn required_iters(mut self, required_iters: u64) -> Self {
+        self.0.required_iters = required_iters;
+        self
+    }
+
+    pub fn finished_challenge_slot_hashes(mut self, hashes: Vec<Bytes32>) -> Self {
+        self.0.finished_challenge_slot_hashes = Some(hashes);
+        self
+    }
+
+    pub fn finished_infused_challenge_slot_hashes(mut self, hashes: Vec<Bytes32>) -> Self {
+        self.0.finished_infused_challenge_slot_hashes = Some(hashes);
+        self
+    }
+
+    pub fn finished_reward_slot_hashes(mut self, hashes: Vec<Bytes32>) -> Self {
+        self.0.finished_reward_slot_hashes = Some(hashes);
+        self
+    }
+
+    pub fn sub_epoch_summary(mut self, ses: SubEpochSummary) -> Self {
+        self.0.sub_epoch_summary_included = Some(ses);
+        self
+    }
+
+    pub fn pool_puzzle_hash(mut self, puzzle_hash: Bytes32) -> Self {
+        self.0.pool_puzzle_hash = puzzle_hash;
+        self
+    }
+
+    pub fn farmer_puzzle_hash(mut self, puzzle_hash: Bytes32) -> Self {
+        self.0.farmer_puzzle_hash = puzzle_hash;
+        self
+    }
+
+    pub fn reward_infusion_new_challenge(mut self, challenge: Bytes32) -> Self {
+        self.0.reward_infusion_new_challenge = challenge;
+        self
+    }
+
+    pub fn challenge_block_info_hash(mut self, info_hash: Bytes32) -> Self {
+        self.0.challenge_block_info_hash = info_hash;
+        self
+    }
+
+    pub fn deficit(mut self, deficit: u8) -> Self {
+        self.0.deficit = deficit;
+        self
+    }
+
+    pub fn prev_transaction_block_height(mut self, height: u32) -> Self {
+        self.0.prev_transaction_block_height = height;
+        self
+    }
+
+    // the timestamp makes it a transaction block
+    pub fn timestamp(mut self, timestamp: u64) -> Self {
+        self.0.timestamp = Some(timestamp);
+        self
+    }
+
+    pub fn prev_transaction_block_hash(mut self, prev_hash: Bytes32) -> Self {
+        self.0.prev_transaction_block_hash = Some(prev_hash);
+        self
+    }
+
+    pub fn fees(mut self, fees: u64) -> Self {
+        self.0.fees = Some(fees);
+        self
+    }
+
+    pub fn build(self) -> BlockRecord {
+        self.0
+    }
+}
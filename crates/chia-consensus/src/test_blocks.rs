@@ -0,0 +1,627 @@
+use crate::allocator::make_allocator;
+use crate::block_rewards::{create_farmer_coin, create_pool_coin};
+use crate::consensus_constants::ConsensusConstants;
+use crate::gen::conditions::EmptyVisitor;
+use crate::gen::owned_conditions::OwnedSpendBundleConditions;
+use crate::gen::run_block_generator::run_block_generator2;
+use crate::gen::solution_generator::solution_generator;
+use crate::gen::validation_error::{ErrorCode, ValidationErr};
+use crate::generator_hash::{generator_refs_root, generator_root};
+use crate::merkle_set::compute_merkle_set_root;
+use crate::proof_of_space::{calculate_pos_challenge, get_plot_id, signage_point_hash};
+use chia_bls::{sign, SecretKey, Signature};
+use chia_protocol::{
+    Bytes32, ChallengeChainSubSlot, ClassgroupElement, Coin, EndOfSubSlotBundle, Foliage,
+    FoliageBlockData, FoliageTransactionBlock, FullBlock, PoolTarget, Program, ProofOfSpace,
+    RewardChainBlock, RewardChainSubSlot, SpendBundle, SubSlotProofs, TransactionsInfo, VDFInfo,
+    VDFProof,
+};
+use chia_traits::Streamable;
+use clvm_utils::tree_hash_atom;
+use clvmr::allocator::NodePtr;
+use clvmr::sha2::{Digest, Sha256};
+
+#[cfg(feature = "py-bindings")]
+use pyo3::prelude::*;
+
+// the timestamp of the genesis block
+const GENESIS_TIMESTAMP: u64 = 1_600_000_000;
+
+const SECONDS_PER_BLOCK: u64 = 20;
+
+// the block rewards paid to the pool and farmer of every block. This doesn't
+// follow the halving schedule
+const POOL_REWARD: u64 = 1_750_000_000_000;
+const FARMER_REWARD: u64 = 250_000_000_000;
+
+// the iterations from the last signage point interval before the infusion
+// point, to the infusion point
+const REQUIRED_ITERS: u64 = 1;
+
+fn err(code: ErrorCode) -> ValidationErr {
+    ValidationErr(NodePtr::NIL, code)
+}
+
+fn sha256(parts: &[&[u8]]) -> Bytes32 {
+    let mut hasher = Sha256::new();
+    for p in parts {
+        hasher.update(p);
+    }
+    let ret: [u8; 32] = hasher.finalize().into();
+    ret.into()
+}
+
+// the hash of all coin IDs created with the same puzzle hash
+fn hash_coin_ids(ids: &mut [Bytes32]) -> Bytes32 {
+    if ids.len() == 1 {
+        return sha256(&[ids[0].as_ref()]);
+    }
+    ids.sort_by(|a, b| b.cmp(a));
+    let parts: Vec<&[u8]> = ids.iter().map(|id| id.as_ref()).collect();
+    sha256(&parts)
+}
+
+fn additions_root(additions: &[Coin]) -> Bytes32 {
+    let mut by_puzzle_hash = Vec::<(Bytes32, Vec<Bytes32>)>::new();
+    for c in additions {
+        match by_puzzle_hash
+            .iter_mut()
+            .find(|(ph, _)| *ph == c.puzzle_hash)
+        {
+            Some((_, ids)) => ids.push(c.coin_id()),
+            None => by_puzzle_hash.push((c.puzzle_hash, vec![c.coin_id()])),
+        }
+    }
+    let mut leafs = Vec::<[u8; 32]>::new();
+    for (ph, mut ids) in by_puzzle_hash {
+        leafs.push(ph.into());
+        leafs.push(hash_coin_ids(&mut ids).into());
+    }
+    compute_merkle_set_root(&mut leafs).into()
+}
+
+fn vdf_proof() -> VDFProof {
+    VDFProof::new(0, vec![].into(), true)
+}
+
+fn vdf_info(challenge: Bytes32, iters: u64) -> VDFInfo {
+    VDFInfo::new(challenge, iters, ClassgroupElement::default())
+}
+
+// Generates chains of FullBlocks for tests, deterministically and without
+// farming real plots or running real VDFs. Blocks link up (heights, weights,
+// total iterations, header hashes, transaction block hashes and timestamps),
+// are signed by the plot, pool and spend keys, and every block is a
+// transaction block that claims the rewards of the previous one. Spend bundles
+// passed in are included in the block's transactions generator.
+// The proofs of space and VDFs are placeholders, as are the transaction
+// filters. The proofs of space are for the right challenge, but only pass the
+// plot filter if it's disabled (number_zero_bits_plot_filter = 0). Every block
+// is in a separate signage point, and a new sub-slot is started every
+// slot_blocks_target blocks. Difficulty and sub-slot iterations
+// never change and no sub-epoch summaries are created.
+#[cfg_attr(feature = "py-bindings", pyclass(module = "gold_rs", frozen))]
+#[derive(Clone, Debug)]
+pub struct BlockTools {
+    constants: ConsensusConstants,
+    farmer_sk: SecretKey,
+    local_sk: SecretKey,
+    pool_sk: SecretKey,
+    farmer_puzzle_hash: Bytes32,
+    pool_puzzle_hash: Bytes32,
+}
+
+impl BlockTools {
+    // the farmer, plot and pool keys are derived from the seed. The block
+    // rewards are paid to the puzzle "1" by default, which returns its
+    // solution as its conditions, so anyone can spend them.
+    pub fn new(constants: &ConsensusConstants, seed: &[u8; 32]) -> Self {
+        let master = SecretKey::from_seed(seed);
+        let anyone_can_spend: Bytes32 = tree_hash_atom(&[1]).into();
+        Self {
+            constants: constants.clone(),
+            farmer_sk: master.derive_hardened(0),
+            local_sk: master.derive_hardened(1),
+            pool_sk: master.derive_hardened(2),
+            farmer_puzzle_hash: anyone_can_spend,
+            pool_puzzle_hash: anyone_can_spend,
+        }
+    }
+
+    pub fn with_reward_puzzle_hashes(
+        mut self,
+        farmer_puzzle_hash: Bytes32,
+        pool_puzzle_hash: Bytes32,
+    ) -> Self {
+        self.farmer_puzzle_hash = farmer_puzzle_hash;
+        self.pool_puzzle_hash = pool_puzzle_hash;
+        self
+    }
+
+    pub fn constants(&self) -> &ConsensusConstants {
+        &self.constants
+    }
+
+    // the pool and farmer reward coins of the block, as claimed by the
+    // following block
+    pub fn reward_coins(&self, block: &FullBlock) -> [Coin; 2] {
+        let data = &block.foliage.foliage_block_data;
+        let fees = block.transactions_info.as_ref().map_or(0, |ti| ti.fees);
+        [
+            create_pool_coin(
+                block.height(),
+                data.pool_target.puzzle_hash,
+                POOL_REWARD,
+                &self.constants,
+            ),
+            create_farmer_coin(
+                block.height(),
+                data.farmer_reward_puzzle_hash,
+                FARMER_REWARD + fees,
+                &self.constants,
+            ),
+        ]
+    }
+
+    // the challenge chain and reward chain challenges of the sub-slot. The
+    // first sub-slot uses the genesis challenge, every following one is the
+    // hash of the previous one's end of slot
+    fn sub_slot_challenges(&self, slot: u32) -> (Bytes32, Bytes32) {
+        let mut cc = self.constants.genesis_challenge;
+        let mut rc = self.constants.genesis_challenge;
+        for _ in 0..slot {
+            let bundle = self.end_of_sub_slot(cc, rc);
+            cc = bundle.challenge_chain.hash().into();
+            rc = bundle.reward_chain.hash().into();
+        }
+        (cc, rc)
+    }
+
+    // the end of a sub-slot, given its challenges
+    fn end_of_sub_slot(&self, cc: Bytes32, rc: Bytes32) -> EndOfSubSlotBundle {
+        let ssi = self.constants.sub_slot_iters_starting;
+        let challenge_chain = ChallengeChainSubSlot::new(vdf_info(cc, ssi), None, None, None, None);
+        let reward_chain =
+            RewardChainSubSlot::new(vdf_info(rc, ssi), challenge_chain.hash().into(), None, 0);
+        EndOfSubSlotBundle::new(
+            challenge_chain,
+            None,
+            reward_chain,
+            SubSlotProofs::new(vdf_proof(), None, vdf_proof()),
+        )
+    }
+
+    // Creates the block following prev (or the genesis block, if prev is
+    // None), including the spend bundles. The spend bundles aren't checked
+    // beyond being run, it's up to the caller to only spend coins that exist.
+    pub fn next_block(
+        &self,
+        prev: Option<&FullBlock>,
+        bundles: &[SpendBundle],
+    ) -> Result<FullBlock, ValidationErr> {
+        let c = &self.constants;
+        let height = prev.map_or(0, |b| b.height() + 1);
+        let weight = prev.map_or(0, |b| b.weight()) + c.difficulty_starting as u128;
+        let prev_hash = prev.map_or(c.genesis_challenge, |b| b.header_hash());
+
+        // pick the signage point and infusion point
+        let ssi = c.sub_slot_iters_starting;
+        let sp_interval = ssi / c.num_sps_sub_slot as u64;
+        let slot = height / c.slot_blocks_target;
+        let idx = height % c.slot_blocks_target;
+        // leave room for the infusion point, in the same sub-slot
+        let usable_sps = c.num_sps_sub_slot - c.num_sp_intervals_extra as u32 - 1;
+        let sp_index = (idx * usable_sps / c.slot_blocks_target) as u8;
+        let sp_iters = sp_index as u64 * sp_interval;
+        let ip_iters = sp_iters + c.num_sp_intervals_extra as u64 * sp_interval + REQUIRED_ITERS;
+        let total_iters = slot as u128 * ssi as u128 + ip_iters as u128;
+
+        let (cc_challenge, rc_challenge) = self.sub_slot_challenges(slot);
+        let finished_sub_slots = if idx == 0 && height > 0 {
+            let (prev_cc, prev_rc) = self.sub_slot_challenges(slot - 1);
+            vec![self.end_of_sub_slot(prev_cc, prev_rc)]
+        } else {
+            vec![]
+        };
+
+        let plot_sk = &self.local_sk + &self.farmer_sk;
+        let (cc_sp_vdf, rc_sp_vdf) = if sp_index == 0 {
+            (None, None)
+        } else {
+            (
+                Some(vdf_info(cc_challenge, sp_iters)),
+                Some(vdf_info(rc_challenge, sp_iters)),
+            )
+        };
+        let cc_sp_hash = signage_point_hash(&cc_challenge, cc_sp_vdf.as_ref());
+        let cc_sp_signature = sign(&plot_sk, cc_sp_hash);
+        let rc_sp_signature = sign(
+            &plot_sk,
+            signage_point_hash(&rc_challenge, rc_sp_vdf.as_ref()),
+        );
+
+        let mut pos = ProofOfSpace::new(
+            Bytes32::default(),
+            Some(self.pool_sk.public_key()),
+            None,
+            self.local_sk.public_key(),
+            c.min_plot_size,
+            vec![0; c.min_plot_size as usize * 8].into(),
+            self.farmer_sk.public_key(),
+        );
+        let plot_id = get_plot_id(&pos).expect("plot id");
+        pos.challenge = calculate_pos_challenge(&plot_id, &cc_challenge, &cc_sp_hash);
+
+        let reward_chain_block = RewardChainBlock::new(
+            weight,
+            height,
+            total_iters,
+            sp_index,
+            cc_challenge,
+            pos,
+            cc_sp_vdf,
+            cc_sp_signature,
+            vdf_info(cc_challenge, ip_iters),
+            rc_sp_vdf,
+            rc_sp_signature,
+            vdf_info(rc_challenge, ip_iters),
+            None,
+            true,
+        );
+
+        let pool_target = PoolTarget::new(self.pool_puzzle_hash, 0);
+        let pool_signature = sign(
+            &self.pool_sk,
+            pool_target
+                .to_bytes()
+                .map_err(|_| err(ErrorCode::InvalidPoolTarget))?,
+        );
+        let block_data = FoliageBlockData::new(
+            reward_chain_block.get_unfinished().hash().into(),
+            pool_target,
+            Some(pool_signature),
+            self.farmer_puzzle_hash,
+            Bytes32::default(),
+        );
+        let block_data_signature = sign(&plot_sk, block_data.hash());
+
+        // transactions
+        let reward_claims: Vec<Coin> = prev.map_or_else(Vec::new, |b| self.reward_coins(b).into());
+        let mut additions = Vec::<Coin>::new();
+        let mut removals = Vec::<Bytes32>::new();
+        let mut fees = 0;
+        let mut cost = 0;
+        let mut aggregated_signature = Signature::default();
+        let generator = if bundles.is_empty() {
+            None
+        } else {
+            let spends = bundles
+                .iter()
+                .flat_map(|sb| &sb.coin_spends)
+                .map(|cs| (cs.coin, cs.puzzle_reveal.as_slice(), cs.solution.as_slice()));
+            let generator = solution_generator(spends)?;
+
+            let mut a = make_allocator(0);
+            let conds = run_block_generator2::<&[u8], EmptyVisitor>(
+                &mut a,
+                &generator,
+                &[],
+                c.max_block_cost_clvm,
+                0,
+            )?;
+            let conds = OwnedSpendBundleConditions::from(&a, conds)
+                .map_err(|_| err(ErrorCode::GeneratorRuntimeError))?;
+            if conds.addition_amount > conds.removal_amount {
+                return Err(err(ErrorCode::MintingCoin));
+            }
+            fees = (conds.removal_amount - conds.addition_amount) as u64;
+            cost = conds.cost;
+            for spend in &conds.spends {
+                removals.push(spend.coin_id);
+                for (ph, amount, _hint) in &spend.create_coin {
+                    additions.push(Coin::new(spend.coin_id, *ph, *amount));
+                }
+            }
+            for sb in bundles {
+                aggregated_signature.aggregate(&sb.aggregated_signature);
+            }
+            Some(Program::from(generator))
+        };
+
+        let transactions_info = TransactionsInfo::new(
+            generator_root(generator.as_ref().map(Program::as_slice)),
+            generator_refs_root(&[]),
+            aggregated_signature,
+            fees,
+            cost,
+            reward_claims.clone(),
+        );
+
+        // the real filter is a BIP158 filter of the addition puzzle hashes
+        // and removal coin IDs. This just hashes them
+        let filter_parts: Vec<&[u8]> = additions
+            .iter()
+            .map(|coin| coin.puzzle_hash.as_ref())
+            .chain(removals.iter().map(|id| id.as_ref()))
+            .collect();
+        let filter_hash = sha256(&filter_parts);
+        additions.extend(reward_claims);
+        let mut removal_leafs: Vec<[u8; 32]> = removals.iter().map(|id| (*id).into()).collect();
+
+        let foliage_transaction_block = FoliageTransactionBlock::new(
+            prev_hash,
+            GENESIS_TIMESTAMP + height as u64 * SECONDS_PER_BLOCK,
+            filter_hash,
+            additions_root(&additions),
+            compute_merkle_set_root(&mut removal_leafs).into(),
+            transactions_info.hash().into(),
+        );
+        let ftb_hash: Bytes32 = foliage_transaction_block.hash().into();
+
+        let foliage = Foliage::new(
+            prev_hash,
+            reward_chain_block.hash().into(),
+            block_data,
+            block_data_signature,
+            Some(ftb_hash),
+            Some(sign(&plot_sk, ftb_hash)),
+        );
+
+        Ok(FullBlock::new(
+            finished_sub_slots,
+            reward_chain_block,
+            None,
+            vdf_proof(),
+            None,
+            vdf_proof(),
+            None,
+            foliage,
+            Some(foliage_transaction_block),
+            Some(transactions_info),
+            generator,
+            vec![],
+        ))
+    }
+
+    // creates count empty blocks, following prev (or starting a new chain)
+    pub fn get_consecutive_blocks(&self, count: u32, prev: Option<&FullBlock>) -> Vec<FullBlock> {
+        let mut ret = Vec::<FullBlock>::with_capacity(count as usize);
+        for _ in 0..count {
+            let block = self
+                .next_block(ret.last().or(prev), &[])
+                .expect("blocks without transactions can't fail");
+            ret.push(block);
+        }
+        ret
+    }
+}
+
+#[cfg(feature = "py-bindings")]
+#[pymethods]
+impl BlockTools {
+    #[new]
+    #[pyo3(signature = (constants, seed = [0; 32], farmer_puzzle_hash = None, pool_puzzle_hash = None))]
+    fn py_new(
+        constants: &ConsensusConstants,
+        seed: [u8; 32],
+        farmer_puzzle_hash: Option<Bytes32>,
+        pool_puzzle_hash: Option<Bytes32>,
+    ) -> Self {
+        let mut ret = Self::new(constants, &seed);
+        if let Some(ph) = farmer_puzzle_hash {
+            ret.farmer_puzzle_hash = ph;
+        }
+        if let Some(ph) = pool_puzzle_hash {
+            ret.pool_puzzle_hash = ph;
+        }
+        ret
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    #[pyo3(name = "next_block", signature = (prev, bundles = Vec::new()))]
+    fn py_next_block(
+        &self,
+        py: Python<'_>,
+        prev: Option<FullBlock>,
+        bundles: Vec<SpendBundle>,
+    ) -> PyResult<FullBlock> {
+        Ok(py.allow_threads(|| self.next_block(prev.as_ref(), &bundles))?)
+    }
+
+    #[pyo3(name = "get_consecutive_blocks", signature = (count, prev = None))]
+    fn py_get_consecutive_blocks(
+        &self,
+        py: Python<'_>,
+        count: u32,
+        prev: Option<FullBlock>,
+    ) -> Vec<FullBlock> {
+        py.allow_threads(|| self.get_consecutive_blocks(count, prev.as_ref()))
+    }
+
+    #[pyo3(name = "reward_coins")]
+    fn py_reward_coins(&self, block: &FullBlock) -> (Coin, Coin) {
+        let [pool, farmer] = self.reward_coins(block);
+        (pool, farmer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block_signatures::validate_block_signatures;
+    use crate::consensus_constants::TEST_CONSTANTS;
+    use chia_protocol::CoinSpend;
+    use clvmr::allocator::Allocator;
+    use clvmr::serde::node_to_bytes;
+
+    // a spend of a coin with the puzzle "1", creating a coin with the same
+    // puzzle and (amount - fee)
+    fn spend_anyone_can_spend(coin: &Coin, fee: u64) -> SpendBundle {
+        let mut a = Allocator::new();
+        let amount = a.new_number((coin.amount - fee).into()).unwrap();
+        let ph = a.new_atom(&coin.puzzle_hash).unwrap();
+        let op = a.new_small_number(51).unwrap();
+        let mut cond = a.new_pair(amount, NodePtr::NIL).unwrap();
+        cond = a.new_pair(ph, cond).unwrap();
+        cond = a.new_pair(op, cond).unwrap();
+        let solution = a.new_pair(cond, NodePtr::NIL).unwrap();
+        let spend = CoinSpend::new(
+            *coin,
+            Program::from(vec![1]),
+            Program::from(node_to_bytes(&a, solution).unwrap()),
+        );
+        SpendBundle::new(vec![spend], Signature::default())
+    }
+
+    #[test]
+    fn test_consecutive_blocks() {
+        let bt = BlockTools::new(&TEST_CONSTANTS, &[1; 32]);
+        let blocks = bt.get_consecutive_blocks(70, None);
+        assert_eq!(blocks.len(), 70);
+
+        let mut prev: Option<&FullBlock> = None;
+        for b in &blocks {
+            validate_block_signatures(b, &TEST_CONSTANTS, None).expect("signatures");
+            let ftb = b.foliage_transaction_block.as_ref().unwrap();
+            assert_eq!(
+                b.foliage.foliage_transaction_block_hash,
+                Some(ftb.hash().into())
+            );
+            assert_eq!(
+                ftb.transactions_info_hash,
+                b.transactions_info.as_ref().unwrap().hash().into()
+            );
+            match prev {
+                None => {
+                    assert_eq!(b.height(), 0);
+                    assert_eq!(b.prev_header_hash(), TEST_CONSTANTS.genesis_challenge);
+                    assert!(b.get_included_reward_coins().is_empty());
+                }
+                Some(p) => {
+                    assert_eq!(b.height(), p.height() + 1);
+                    assert_eq!(b.prev_header_hash(), p.header_hash());
+                    assert_eq!(
+                        b.weight(),
+                        p.weight() + TEST_CONSTANTS.difficulty_starting as u128
+                    );
+                    assert!(b.total_iters() > p.total_iters());
+                    assert!(
+                        ftb.timestamp > p.foliage_transaction_block.as_ref().unwrap().timestamp
+                    );
+                    assert_eq!(b.get_included_reward_coins(), bt.reward_coins(p));
+                }
+            }
+            // a new sub-slot every slot_blocks_target blocks
+            let new_slot = b.height() > 0 && b.height() % TEST_CONSTANTS.slot_blocks_target == 0;
+            assert_eq!(b.finished_sub_slots.len(), usize::from(new_slot));
+            assert!(b.is_fully_compactified());
+            prev = Some(b);
+        }
+
+        // the generator is deterministic, and continuing a chain works
+        assert_eq!(bt.get_consecutive_blocks(70, None), blocks);
+        let more = bt.get_consecutive_blocks(2, blocks.last());
+        assert_eq!(more[0].height(), 70);
+        assert_eq!(more[0].prev_header_hash(), blocks[69].header_hash());
+    }
+
+    #[test]
+    fn test_transaction_block() {
+        let bt = BlockTools::new(&TEST_CONSTANTS, &[2; 32]);
+        let blocks = bt.get_consecutive_blocks(2, None);
+        let [pool_coin, farmer_coin] = bt.reward_coins(&blocks[0]);
+        assert_eq!(
+            blocks[1].get_included_reward_coins(),
+            [pool_coin, farmer_coin]
+        );
+
+        let bundles = [
+            spend_anyone_can_spend(&pool_coin, 100),
+            spend_anyone_can_spend(&farmer_coin, 23),
+        ];
+        let block = bt.next_block(blocks.last(), &bundles).unwrap();
+        let ti = block.transactions_info.as_ref().unwrap();
+        assert_eq!(ti.fees, 123);
+        assert!(ti.cost > 0);
+        assert!(block.transactions_generator.is_some());
+
+        // the spends don't require any signatures
+        validate_block_signatures(&block, &TEST_CONSTANTS, None).expect("signatures");
+
+        // the fees go to the farmer of this block
+        let next = bt.next_block(Some(&block), &[]).unwrap();
+        let [_, farmer_coin] = bt.reward_coins(&block);
+        assert_eq!(farmer_coin.amount, FARMER_REWARD + 123);
+        assert!(next.get_included_reward_coins().contains(&farmer_coin));
+
+        // creating more value than is spent fails
+        let mut minting = spend_anyone_can_spend(&pool_coin, 0);
+        minting.coin_spends[0].coin.amount -= 1;
+        assert_eq!(
+            bt.next_block(Some(&block), &[minting]).unwrap_err().1,
+            ErrorCode::MintingCoin
+        );
+    }
+
+    #[test]
+    fn test_reward_puzzle_hashes() {
+        let bt = BlockTools::new(&TEST_CONSTANTS, &[3; 32])
+            .with_reward_puzzle_hashes(Bytes32::from([4; 32]), Bytes32::from([5; 32]));
+        let blocks = bt.get_consecutive_blocks(2, None);
+        let [pool, farmer] = bt.reward_coins(&blocks[0]);
+        assert_eq!(pool.puzzle_hash, Bytes32::from([5; 32]));
+        assert_eq!(farmer.puzzle_hash, Bytes32::from([4; 32]));
+        assert_eq!(pool.amount, POOL_REWARD);
+        assert_ne!(pool.coin_id(), farmer.coin_id());
+    }
+
+    #[test]
+    fn test_compact_vdf() {
+        use chia_protocol::{CompressibleVDFField, RespondCompactVDF};
+
+        let bt = BlockTools::new(&TEST_CONSTANTS, &[2; 32]);
+        let mut block = bt.next_block(None, &[]).unwrap();
+        assert!(block.uncompact_proofs().is_empty());
+
+        // a proof as it comes out of the timelord, before compactification
+        block.challenge_chain_ip_proof = VDFProof::new(1, vec![1, 2, 3].into(), false);
+        let vdf_info = block.reward_chain_block.challenge_chain_ip_vdf.clone();
+        assert!(!block.is_fully_compactified());
+        assert_eq!(
+            block.uncompact_proofs(),
+            [(CompressibleVDFField::CcIpVdf, vdf_info.clone())]
+        );
+
+        let response = RespondCompactVDF::new(
+            block.height(),
+            block.header_hash(),
+            CompressibleVDFField::CcIpVdf as u8,
+            vdf_info,
+            vdf_proof(),
+        );
+        assert!(block.can_accept_compact_vdf(&response));
+
+        let mut wrong_field = response.clone();
+        wrong_field.field_vdf = CompressibleVDFField::CcSpVdf as u8;
+        assert!(!block.can_accept_compact_vdf(&wrong_field));
+
+        let mut not_compact = response.clone();
+        not_compact.vdf_proof = block.challenge_chain_ip_proof.clone();
+        assert!(!block.can_accept_compact_vdf(&not_compact));
+
+        let mut wrong_block = response.clone();
+        wrong_block.header_hash = Bytes32::default();
+        assert!(!block.can_accept_compact_vdf(&wrong_block));
+
+        // the proof is already compact
+        block.challenge_chain_ip_proof = vdf_proof();
+        assert!(!block.can_accept_compact_vdf(&response));
+    }
+}
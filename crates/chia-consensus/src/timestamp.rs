@@ -0,0 +1,180 @@
+use crate::consensus_constants::ConsensusConstants;
+use crate::error::Error;
+use crate::gen::validation_error::{ErrorCode, ValidationErr};
+use chia_protocol::{BlockRecord, Bytes32};
+use clvmr::allocator::NodePtr;
+
+// The timestamps of the last number_of_timestamps transaction blocks, starting
+// with prev_transaction_block and walking back along the previous transaction
+// block hashes. Early in the chain there are fewer, all the way back to the
+// genesis block. lookup returns the block record with the specified header
+// hash, or None if it's not known, like for find_fork_point().
+pub fn prev_transaction_block_timestamps<E, F>(
+    constants: &ConsensusConstants,
+    prev_transaction_block: &BlockRecord,
+    mut lookup: F,
+) -> Result<Vec<u64>, E>
+where
+    E: From<Error>,
+    F: FnMut(&Bytes32) -> Result<Option<BlockRecord>, E>,
+{
+    let count = constants.number_of_timestamps as usize;
+    let mut ret = Vec::with_capacity(count);
+    let mut curr = prev_transaction_block.clone();
+    loop {
+        let Some(timestamp) = curr.timestamp else {
+            return Err(Error::Custom(format!(
+                "block at height {} is not a transaction block",
+                curr.height
+            ))
+            .into());
+        };
+        ret.push(timestamp);
+        if ret.len() == count || curr.height == 0 {
+            return Ok(ret);
+        }
+        let Some(prev_hash) = curr.prev_transaction_block_hash else {
+            return Ok(ret);
+        };
+        curr = lookup(&prev_hash)?.ok_or(Error::BlockNotFound(prev_hash))?;
+    }
+}
+
+// Validates the timestamp of a new transaction block. It can be at most
+// max_future_time2 seconds ahead of now (the local clock), and it has to be
+// later than the average of the timestamps of the previous transaction blocks,
+// most recent first (see prev_transaction_block_timestamps()). Only the first
+// number_of_timestamps of them count. The genesis block has no previous
+// transaction blocks and only has to pass the first check. This matches the
+// rules of the node.
+pub fn validate_timestamp(
+    timestamp: u64,
+    prev_transaction_block_timestamps: &[u64],
+    now: u64,
+    constants: &ConsensusConstants,
+) -> Result<(), ValidationErr> {
+    if timestamp > now.saturating_add(constants.max_future_time2 as u64) {
+        return Err(ValidationErr(
+            NodePtr::NIL,
+            ErrorCode::TimestampTooFarInFuture,
+        ));
+    }
+
+    let count = prev_transaction_block_timestamps
+        .len()
+        .min(constants.number_of_timestamps as usize);
+    if count == 0 {
+        return Ok(());
+    }
+    let sum: u128 = prev_transaction_block_timestamps[..count]
+        .iter()
+        .map(|t| *t as u128)
+        .sum();
+    if timestamp as u128 <= sum / count as u128 {
+        return Err(ValidationErr(
+            NodePtr::NIL,
+            ErrorCode::TimestampTooFarInPast,
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus_constants::TEST_CONSTANTS;
+    use crate::test_block_record::{hash, BlockRecordBuilder};
+    use rstest::rstest;
+    use std::collections::HashMap;
+
+    const NOW: u64 = 1_700_000_000;
+    // the latest valid timestamp
+    const LATEST: u64 = NOW + TEST_CONSTANTS.max_future_time2 as u64;
+
+    // every other block is a transaction block, with a timestamp of 10 times
+    // its height
+    fn record(height: u32) -> BlockRecord {
+        let prev_tx_height = if height < 2 { 0 } else { (height - 1) & !1 };
+        let builder = BlockRecordBuilder::new(hash(height))
+            .prev_hash(hash(height.wrapping_sub(1)))
+            .height(height)
+            .weight(height as u128)
+            .total_iters(height as u128 * 10)
+            .prev_transaction_block_height(prev_tx_height);
+        if height % 2 != 0 {
+            return builder.build();
+        }
+        builder
+            .timestamp(height as u64 * 10)
+            .prev_transaction_block_hash(if height == 0 {
+                TEST_CONSTANTS.genesis_challenge
+            } else {
+                hash(height - 2)
+            })
+            .fees(0)
+            .build()
+    }
+
+    fn timestamps(blocks: &HashMap<Bytes32, BlockRecord>, height: u32) -> Result<Vec<u64>, Error> {
+        prev_transaction_block_timestamps(&TEST_CONSTANTS, &blocks[&hash(height)], |h| {
+            Ok(blocks.get(h).cloned())
+        })
+    }
+
+    #[test]
+    fn test_prev_timestamps() {
+        let blocks: HashMap<Bytes32, BlockRecord> =
+            (0..40).map(record).map(|r| (r.header_hash, r)).collect();
+        assert_eq!(timestamps(&blocks, 0), Ok(vec![0]));
+        assert_eq!(timestamps(&blocks, 4), Ok(vec![40, 20, 0]));
+        let count = TEST_CONSTANTS.number_of_timestamps as u64;
+        assert_eq!(
+            timestamps(&blocks, 38),
+            Ok((0..count).map(|i| 380 - i * 20).collect())
+        );
+        assert!(matches!(timestamps(&blocks, 3), Err(Error::Custom(_))));
+
+        let mut blocks = blocks;
+        blocks.remove(&hash(30));
+        assert_eq!(timestamps(&blocks, 34), Err(Error::BlockNotFound(hash(30))));
+    }
+
+    #[rstest]
+    #[case(NOW, &[], Ok(()))]
+    #[case(LATEST, &[], Ok(()))]
+    #[case(LATEST + 1, &[], Err(ErrorCode::TimestampTooFarInFuture))]
+    #[case(LATEST + 1, &[NOW], Err(ErrorCode::TimestampTooFarInFuture))]
+    #[case(101, &[100], Ok(()))]
+    #[case(100, &[100], Err(ErrorCode::TimestampTooFarInPast))]
+    // the average is 100
+    #[case(101, &[150, 100, 50], Ok(()))]
+    #[case(100, &[150, 100, 50], Err(ErrorCode::TimestampTooFarInPast))]
+    // the average rounds down to 100
+    #[case(101, &[101, 100, 100], Ok(()))]
+    fn test_validate_timestamp(
+        #[case] timestamp: u64,
+        #[case] prev: &[u64],
+        #[case] expected: Result<(), ErrorCode>,
+    ) {
+        assert_eq!(
+            validate_timestamp(timestamp, prev, NOW, &TEST_CONSTANTS).map_err(|e| e.1),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_number_of_timestamps() {
+        // only the most recent number_of_timestamps count, so the last one is
+        // ignored
+        let count = TEST_CONSTANTS.number_of_timestamps as usize;
+        let mut prev = vec![100; count];
+        prev.push(u64::MAX);
+        validate_timestamp(101, &prev, NOW, &TEST_CONSTANTS).expect("valid");
+        assert_eq!(
+            validate_timestamp(100, &prev, NOW, &TEST_CONSTANTS)
+                .unwrap_err()
+                .1,
+            ErrorCode::TimestampTooFarInPast
+        );
+    }
+}
@@ -0,0 +1,443 @@
+use crate::error::Error;
+use chia_protocol::{
+    BlockRecord, Bytes32, HeaderBlock, SubEpochChallengeSegment, SubEpochData, WeightProof,
+};
+use chia_traits::Streamable;
+use sha2::{Digest, Sha512};
+
+#[cfg(feature = "py-bindings")]
+use pyo3::prelude::*;
+
+// the parameters of the sampling, which must match the ones of the node for
+// wallets to accept the proof
+const C: f64 = 0.5;
+const LAMBDA_L: f64 = 100.0;
+const MAX_SAMPLES: usize = 20;
+
+// The sub-epochs to sample are picked with Python's random.Random, seeded with
+// a sub-epoch summary hash. This is the same Mersenne Twister, to pick the
+// same sub-epochs as the node.
+struct MersenneTwister {
+    state: [u32; 624],
+    index: usize,
+}
+
+impl MersenneTwister {
+    // like random.Random(seed), for a bytes seed
+    fn new(seed: &[u8]) -> Self {
+        let mut bytes = seed.to_vec();
+        bytes.extend_from_slice(&Sha512::digest(seed));
+
+        // the bytes form a big endian integer, which is split into 32 bit
+        // words starting from the least significant one
+        let mut key: Vec<u32> = bytes
+            .rchunks(4)
+            .map(|chunk| chunk.iter().fold(0, |acc, b| (acc << 8) | *b as u32))
+            .collect();
+        while key.len() > 1 && key.last() == Some(&0) {
+            key.pop();
+        }
+
+        let mut ret = Self {
+            state: [0; 624],
+            index: 624,
+        };
+        ret.init_by_array(&key);
+        ret
+    }
+
+    fn init_genrand(&mut self, seed: u32) {
+        self.state[0] = seed;
+        for i in 1..624 {
+            let prev = self.state[i - 1];
+            self.state[i] = 1_812_433_253_u32
+                .wrapping_mul(prev ^ (prev >> 30))
+                .wrapping_add(i as u32);
+        }
+        self.index = 624;
+    }
+
+    fn init_by_array(&mut self, key: &[u32]) {
+        self.init_genrand(19_650_218);
+        let mut i = 1;
+        let mut j = 0;
+        for _ in 0..624.max(key.len()) {
+            let prev = self.state[i - 1];
+            self.state[i] = (self.state[i] ^ (prev ^ (prev >> 30)).wrapping_mul(1_664_525))
+                .wrapping_add(key[j])
+                .wrapping_add(j as u32);
+            i += 1;
+            j += 1;
+            if i >= 624 {
+                self.state[0] = self.state[623];
+                i = 1;
+            }
+            if j >= key.len() {
+                j = 0;
+            }
+        }
+        for _ in 0..623 {
+            let prev = self.state[i - 1];
+            self.state[i] = (self.state[i] ^ (prev ^ (prev >> 30)).wrapping_mul(1_566_083_941))
+                .wrapping_sub(i as u32);
+            i += 1;
+            if i >= 624 {
+                self.state[0] = self.state[623];
+                i = 1;
+            }
+        }
+        self.state[0] = 0x8000_0000;
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        if self.index >= 624 {
+            for i in 0..624 {
+                let y = (self.state[i] & 0x8000_0000) | (self.state[(i + 1) % 624] & 0x7fff_ffff);
+                let mut next = self.state[(i + 397) % 624] ^ (y >> 1);
+                if y & 1 != 0 {
+                    next ^= 0x9908_b0df;
+                }
+                self.state[i] = next;
+            }
+            self.index = 0;
+        }
+        let mut y = self.state[self.index];
+        self.index += 1;
+        y ^= y >> 11;
+        y ^= (y << 7) & 0x9d2c_5680;
+        y ^= (y << 15) & 0xefc6_0000;
+        y ^ (y >> 18)
+    }
+
+    // like random.random()
+    fn random(&mut self) -> f64 {
+        let a = (self.next_u32() >> 5) as f64;
+        let b = (self.next_u32() >> 6) as f64;
+        (a * 67_108_864.0 + b) * (1.0 / 9_007_199_254_740_992.0)
+    }
+}
+
+// The weights a wallet will check the proof at, sorted. The more of the total
+// weight the recent chain makes up, the fewer samples are needed. None means
+// the recent chain is heavy enough by itself, and every sub-epoch is sampled.
+fn weights_for_sampling(
+    rng: &mut MersenneTwister,
+    total_weight: u128,
+    recent_weight: u128,
+) -> Option<Vec<u128>> {
+    let delta = recent_weight as f64 / total_weight as f64;
+    let prob_of_adv_succeeding = 1.0 - C.ln() / delta.ln();
+    if prob_of_adv_succeeding <= 0.0 {
+        return None;
+    }
+    let queries = -LAMBDA_L * 2_f64.ln() / prob_of_adv_succeeding.ln();
+    let mut ret: Vec<u128> = (0..=queries as u64)
+        .map(|_| {
+            let u = rng.random();
+            ((1.0 - delta.powf(u)) * total_weight as f64) as u128
+        })
+        .collect();
+    ret.sort_unstable();
+    Some(ret)
+}
+
+// whether any of the weights falls inside the sub-epoch
+fn sample_sub_epoch(start_weight: u128, end_weight: u128, weights: Option<&[u128]>) -> bool {
+    let Some(weights) = weights else {
+        return true;
+    };
+    for weight in weights {
+        if *weight > end_weight {
+            return false;
+        }
+        if *weight > start_weight && *weight < end_weight {
+            return true;
+        }
+    }
+    false
+}
+
+// Builds weight proofs for wallets syncing to a peak, like the node's
+// WeightProofHandler. The builder is fed the block records of the blocks
+// including sub-epoch summaries as the chain grows, and picks the sub-epochs
+// to sample from those. The challenge segments of the sampled sub-epochs come
+// from the caller, since they need the full header blocks (and the node keeps
+// them cached).
+#[cfg_attr(feature = "py-bindings", pyclass(module = "gold_rs"))]
+#[derive(Debug, Clone)]
+pub struct WeightProofBuilder {
+    genesis: BlockRecord,
+    ses_blocks: Vec<BlockRecord>,
+}
+
+impl WeightProofBuilder {
+    pub fn new(genesis: BlockRecord) -> Self {
+        Self {
+            genesis,
+            ses_blocks: Vec::new(),
+        }
+    }
+
+    // adds the next block that includes a sub-epoch summary
+    pub fn add_sub_epoch(&mut self, block: BlockRecord) -> Result<(), Error> {
+        if block.sub_epoch_summary_included.is_none() {
+            return Err(Error::Custom(format!(
+                "block {} doesn't include a sub-epoch summary",
+                block.header_hash
+            )));
+        }
+        let prev_height = self.ses_blocks.last().unwrap_or(&self.genesis).height;
+        if block.height <= prev_height {
+            return Err(Error::Custom(format!(
+                "sub-epoch at height {} added after height {prev_height}",
+                block.height
+            )));
+        }
+        self.ses_blocks.push(block);
+        Ok(())
+    }
+
+    pub fn num_sub_epochs(&self) -> usize {
+        self.ses_blocks.len()
+    }
+
+    // drops the sub-epochs after the specified height, when the chain is
+    // reorged
+    pub fn rollback(&mut self, height: u32) {
+        self.ses_blocks.retain(|b| b.height <= height);
+    }
+
+    fn ses_blocks_until(&self, tip_height: u32) -> impl Iterator<Item = &BlockRecord> {
+        self.ses_blocks
+            .iter()
+            .take_while(move |b| b.height <= tip_height)
+    }
+
+    // the summaries of all sub-epochs up to the tip
+    pub fn sub_epoch_data(&self, tip_height: u32) -> Vec<SubEpochData> {
+        self.ses_blocks_until(tip_height)
+            .filter_map(|b| b.sub_epoch_summary_included.as_ref())
+            .map(|ses| {
+                SubEpochData::new(
+                    ses.reward_chain_hash,
+                    ses.num_blocks_overflow,
+                    ses.new_sub_slot_iters,
+                    ses.new_difficulty,
+                )
+            })
+            .collect()
+    }
+
+    // the seed of the sampling is the hash of the second to last sub-epoch
+    // summary before the tip, which can't be ground by the farmer of the tip
+    pub fn seed(&self, tip_height: u32) -> Option<Bytes32> {
+        let ses_blocks: Vec<&BlockRecord> = self.ses_blocks_until(tip_height).collect();
+        let block = ses_blocks.len().checked_sub(2).map(|i| ses_blocks[i])?;
+        block
+            .sub_epoch_summary_included
+            .as_ref()
+            .map(|ses| ses.hash().into())
+    }
+
+    // Builds the weight proof for the tip. recent_chain are the most recent
+    // header blocks, up to the tip. segments is called for every sampled
+    // sub-epoch, with the index of the sub-epoch, the block including its
+    // summary and the block including the previous one (or the genesis
+    // block), and returns its challenge segments.
+    pub fn build<E, F>(
+        &self,
+        tip: &BlockRecord,
+        recent_chain: Vec<HeaderBlock>,
+        mut segments: F,
+    ) -> Result<WeightProof, E>
+    where
+        E: From<Error>,
+        F: FnMut(u32, &BlockRecord, &BlockRecord) -> Result<Vec<SubEpochChallengeSegment>, E>,
+    {
+        let Some(seed) = self.seed(tip.height) else {
+            return Err(Error::Custom(format!(
+                "not enough sub-epochs for a weight proof at height {}",
+                tip.height
+            ))
+            .into());
+        };
+        let (Some(first), Some(last)) = (recent_chain.first(), recent_chain.last()) else {
+            return Err(Error::Custom("the recent chain is empty".to_string()).into());
+        };
+        let recent_weight = last.weight().saturating_sub(first.weight());
+
+        let mut rng = MersenneTwister::new(seed.as_ref());
+        let weights = weights_for_sampling(&mut rng, tip.weight, recent_weight);
+
+        let mut samples = 0;
+        let mut sub_epoch_segments = Vec::new();
+        let mut prev = &self.genesis;
+        for (sub_epoch_n, block) in self.ses_blocks_until(tip.height).enumerate() {
+            if samples >= MAX_SAMPLES {
+                break;
+            }
+            if sample_sub_epoch(prev.weight, block.weight, weights.as_deref()) {
+                samples += 1;
+                sub_epoch_segments.extend(segments(sub_epoch_n as u32, block, prev)?);
+            }
+            prev = block;
+        }
+
+        Ok(WeightProof::new(
+            self.sub_epoch_data(tip.height),
+            sub_epoch_segments,
+            recent_chain,
+        ))
+    }
+}
+
+#[cfg(feature = "py-bindings")]
+#[pymethods]
+impl WeightProofBuilder {
+    #[new]
+    fn py_new(genesis: BlockRecord) -> Self {
+        Self::new(genesis)
+    }
+
+    #[pyo3(name = "add_sub_epoch")]
+    fn py_add_sub_epoch(&mut self, block: BlockRecord) -> crate::error::Result<()> {
+        self.add_sub_epoch(block)
+    }
+
+    #[pyo3(name = "rollback")]
+    fn py_rollback(&mut self, height: u32) {
+        self.rollback(height);
+    }
+
+    fn __len__(&self) -> usize {
+        self.num_sub_epochs()
+    }
+
+    #[pyo3(name = "sub_epoch_data")]
+    fn py_sub_epoch_data(&self, tip_height: u32) -> Vec<SubEpochData> {
+        self.sub_epoch_data(tip_height)
+    }
+
+    #[pyo3(name = "seed")]
+    fn py_seed(&self, tip_height: u32) -> Option<Bytes32> {
+        self.seed(tip_height)
+    }
+
+    // segments is called as segments(sub_epoch_n, ses_block, prev_ses_block)
+    #[pyo3(name = "build")]
+    fn py_build(
+        &self,
+        tip: &BlockRecord,
+        recent_chain: Vec<HeaderBlock>,
+        segments: &Bound<'_, PyAny>,
+    ) -> PyResult<WeightProof> {
+        self.build(tip, recent_chain, |sub_epoch_n, block, prev| {
+            segments
+                .call1((sub_epoch_n, block.clone(), prev.clone()))?
+                .extract::<Vec<SubEpochChallengeSegment>>()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_block_record::{hash, BlockRecordBuilder};
+    use chia_protocol::SubEpochSummary;
+
+    fn record(height: u32, ses: Option<SubEpochSummary>) -> BlockRecord {
+        let mut builder = BlockRecordBuilder::new(hash(height))
+            .height(height)
+            .weight(height as u128 * 100)
+            .total_iters(height as u128 * 1000);
+        if let Some(ses) = ses {
+            builder = builder.sub_epoch_summary(ses);
+        }
+        builder.build()
+    }
+
+    fn ses(n: u8) -> SubEpochSummary {
+        SubEpochSummary::new(
+            Bytes32::new([n; 32]),
+            Bytes32::new([n + 100; 32]),
+            n,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_py_random() {
+        // random.Random(bytes([1] * 32)).random()
+        let mut rng = MersenneTwister::new(&[1; 32]);
+        assert_eq!(rng.random(), 0.170029994912822);
+        assert_eq!(rng.random(), 0.9266152425109991);
+        assert_eq!(rng.random(), 0.318978928662635);
+
+        let mut rng = MersenneTwister::new(&[0; 32]);
+        assert_eq!(rng.random(), 0.279945442455909);
+        assert_eq!(rng.random(), 0.6010779836124703);
+
+        // random.Random(bytes([1] * 32)).getrandbits(32)
+        let mut rng = MersenneTwister::new(&[1; 32]);
+        assert_eq!(rng.next_u32(), 730273278);
+        assert_eq!(rng.next_u32(), 2615852158);
+    }
+
+    #[test]
+    fn test_weights_for_sampling() {
+        let mut rng = MersenneTwister::new(&[1; 32]);
+        let weights = weights_for_sampling(&mut rng, 1_000_000, 10_000).expect("weights");
+        assert_eq!(weights.len(), 425);
+        assert_eq!(weights[..3], [30460, 32765, 43332]);
+        assert_eq!(weights[422..], [989773, 989877, 989907]);
+
+        // the recent chain is heavy enough
+        let mut rng = MersenneTwister::new(&[1; 32]);
+        assert_eq!(weights_for_sampling(&mut rng, 1_000_000, 600_000), None);
+    }
+
+    #[test]
+    fn test_sample_sub_epoch() {
+        let weights = [10, 20, 30];
+        assert!(sample_sub_epoch(15, 25, Some(&weights)));
+        assert!(sample_sub_epoch(0, 11, Some(&weights)));
+        assert!(!sample_sub_epoch(21, 29, Some(&weights)));
+        assert!(!sample_sub_epoch(30, 40, Some(&weights)));
+        assert!(!sample_sub_epoch(31, 40, Some(&weights)));
+        assert!(sample_sub_epoch(31, 40, None));
+    }
+
+    #[test]
+    fn test_sub_epochs() {
+        let mut builder = WeightProofBuilder::new(record(0, None));
+        assert!(matches!(
+            builder.add_sub_epoch(record(10, None)),
+            Err(Error::Custom(_))
+        ));
+        for n in 1..=3 {
+            builder
+                .add_sub_epoch(record(n as u32 * 10, Some(ses(n))))
+                .expect("add_sub_epoch");
+        }
+        assert!(matches!(
+            builder.add_sub_epoch(record(25, Some(ses(4)))),
+            Err(Error::Custom(_))
+        ));
+        assert_eq!(builder.num_sub_epochs(), 3);
+
+        let data = builder.sub_epoch_data(29);
+        assert_eq!(data.len(), 2);
+        assert_eq!(data[1].reward_chain_hash, Bytes32::new([102; 32]));
+        assert_eq!(data[1].num_blocks_overflow, 2);
+
+        // the second to last summary before the tip
+        assert_eq!(builder.seed(35), Some(ses(2).hash().into()));
+        assert_eq!(builder.seed(29), Some(ses(1).hash().into()));
+        assert_eq!(builder.seed(19), None);
+
+        builder.rollback(25);
+        assert_eq!(builder.num_sub_epochs(), 2);
+        assert_eq!(builder.seed(35), Some(ses(1).hash().into()));
+    }
+}
@@ -121,6 +121,17 @@ fuzz_target!(|data: &[u8]| {
     test::<RespondSesInfo>(data);
     test::<RequestFeeEstimates>(data);
     test::<RespondFeeEstimates>(data);
+    test::<RequestRemovePuzzleSubscriptions>(data);
+    test::<RespondRemovePuzzleSubscriptions>(data);
+    test::<RequestRemoveCoinSubscriptions>(data);
+    test::<RespondRemoveCoinSubscriptions>(data);
+    test::<CoinStateFilters>(data);
+    test::<RequestPuzzleState>(data);
+    test::<RespondPuzzleState>(data);
+    test::<RejectPuzzleState>(data);
+    test::<RequestCoinState>(data);
+    test::<RespondCoinState>(data);
+    test::<RejectCoinState>(data);
 
     // Full Node Protocol
     test::<NewPeak>(data);
@@ -148,4 +159,61 @@ fuzz_target!(|data: &[u8]| {
     test::<RespondCompactVDF>(data);
     test::<RequestPeers>(data);
     test::<RespondPeers>(data);
+    test::<NewUnfinishedBlock2>(data);
+    test::<RequestUnfinishedBlock2>(data);
+
+    // Farmer Protocol
+    test::<NewSignagePoint>(data);
+    test::<DeclareProofOfSpace>(data);
+    test::<RequestSignedValues>(data);
+    test::<FarmingInfo>(data);
+    test::<SignedValues>(data);
+
+    // Harvester Protocol
+    test::<PoolDifficulty>(data);
+    test::<HarvesterHandshake>(data);
+    test::<NewSignagePointHarvester>(data);
+    test::<ProofOfSpaceFeeInfo>(data);
+    test::<NewProofOfSpace>(data);
+    test::<SignatureRequestSourceData>(data);
+    test::<RequestSignatures>(data);
+    test::<RespondSignatures>(data);
+    test::<Plot>(data);
+    test::<RequestPlots>(data);
+    test::<RespondPlots>(data);
+    test::<PlotSyncIdentifier>(data);
+    test::<PlotSyncStart>(data);
+    test::<PlotSyncPathList>(data);
+    test::<PlotSyncPlotList>(data);
+    test::<PlotSyncDone>(data);
+    test::<PlotSyncError>(data);
+    test::<PlotSyncResponse>(data);
+
+    // Timelord Protocol
+    test::<NewPeakTimelord>(data);
+    test::<NewUnfinishedBlockTimelord>(data);
+    test::<NewInfusionPointVDF>(data);
+    test::<NewSignagePointVDF>(data);
+    test::<NewEndOfSubSlotVDF>(data);
+    test::<RequestCompactProofOfTime>(data);
+    test::<RespondCompactProofOfTime>(data);
+
+    // Introducer Protocol
+    test::<RequestPeersIntroducer>(data);
+    test::<RespondPeersIntroducer>(data);
+
+    // Pool Protocol
+    test::<AuthenticationPayload>(data);
+    test::<GetPoolInfoResponse>(data);
+    test::<PostPartialPayload>(data);
+    test::<PostPartialRequest>(data);
+    test::<PostPartialResponse>(data);
+    test::<GetFarmerResponse>(data);
+    test::<PostFarmerPayload>(data);
+    test::<PostFarmerRequest>(data);
+    test::<PostFarmerResponse>(data);
+    test::<PutFarmerPayload>(data);
+    test::<PutFarmerRequest>(data);
+    test::<PutFarmerResponse>(data);
+    test::<PoolErrorResponse>(data);
 });
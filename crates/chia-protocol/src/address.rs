@@ -0,0 +1,295 @@
+use crate::bytes::BytesImpl;
+use std::fmt;
+
+// Addresses are bech32m (BIP-350) encoded byte strings with a human readable
+// prefix, like the "xch1..." encoding of puzzle hashes. Unlike hex, they have
+// a checksum, so a mistyped address fails to decode rather than decoding to
+// the wrong bytes.
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32M_CONST: u32 = 0x2bc830a3;
+const CHECKSUM_LEN: usize = 6;
+
+// The ways an address can fail to decode. Positions are character offsets
+// into the address as it was passed in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddressError {
+    InvalidChar { c: char, pos: usize },
+    MixedCase,
+    MissingSeparator,
+    WrongPrefix { expected: String, got: String },
+    InvalidChecksum,
+    // the data doesn't end on a byte boundary
+    InvalidPadding,
+    WrongLength { expected: usize, got: usize },
+}
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidChar { c, pos } => {
+                write!(f, "invalid address character {c:?} at position {pos}")
+            }
+            Self::MixedCase => write!(f, "invalid address: mixed upper and lower case"),
+            Self::MissingSeparator => write!(f, "invalid address: missing the '1' separator"),
+            Self::WrongPrefix { expected, got } => {
+                write!(f, "expected address prefix {expected:?}, got {got:?}")
+            }
+            Self::InvalidChecksum => write!(f, "invalid address checksum"),
+            Self::InvalidPadding => write!(f, "invalid address: bad padding"),
+            Self::WrongLength { expected, got } => {
+                write!(f, "expected {expected} bytes, got {got}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AddressError {}
+
+fn polymod(values: impl IntoIterator<Item = u8>) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let mut chk: u32 = 1;
+    for v in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x1ffffff) << 5) ^ v as u32;
+        for (i, g) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 != 0 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+fn expand_prefix(prefix: &str) -> Vec<u8> {
+    let mut ret: Vec<u8> = prefix.bytes().map(|c| c >> 5).collect();
+    ret.push(0);
+    ret.extend(prefix.bytes().map(|c| c & 31));
+    ret
+}
+
+// regroups the bits of data, from from_bits to to_bits wide values. Without
+// padding, the bits left over must be fewer than from_bits, and all zero
+fn convert_bits(data: &[u8], from_bits: u32, to_bits: u32, pad: bool) -> Option<Vec<u8>> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let max_value = (1 << to_bits) - 1;
+    let mut ret = Vec::with_capacity(data.len() * from_bits as usize / to_bits as usize + 1);
+    for v in data {
+        acc = ((acc << from_bits) | *v as u32) & 0xffff;
+        bits += from_bits;
+        while bits >= to_bits {
+            bits -= to_bits;
+            ret.push(((acc >> bits) & max_value) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            ret.push(((acc << (to_bits - bits)) & max_value) as u8);
+        }
+    } else if bits >= from_bits || ((acc << (to_bits - bits)) & max_value) != 0 {
+        return None;
+    }
+    Some(ret)
+}
+
+// encodes data as an address with the given (lower case) prefix, e.g. "xch"
+pub fn encode_address(data: &[u8], prefix: &str) -> String {
+    let data = convert_bits(data, 8, 5, true).expect("padding never fails");
+    let checksum = polymod(
+        expand_prefix(prefix)
+            .into_iter()
+            .chain(data.iter().copied())
+            .chain([0; CHECKSUM_LEN]),
+    ) ^ BECH32M_CONST;
+    let checksum = (0..CHECKSUM_LEN).map(|i| ((checksum >> (5 * (5 - i))) & 31) as u8);
+
+    let mut ret = String::with_capacity(prefix.len() + 1 + data.len() + CHECKSUM_LEN);
+    ret.push_str(prefix);
+    ret.push('1');
+    ret.extend(
+        data.into_iter()
+            .chain(checksum)
+            .map(|v| CHARSET[v as usize] as char),
+    );
+    ret
+}
+
+// decodes an address, validating its checksum and that it has the expected
+// (lower case) prefix. The address may be all upper case
+pub fn decode_address(address: &str, prefix: &str) -> Result<Vec<u8>, AddressError> {
+    if let Some((pos, c)) = address
+        .chars()
+        .enumerate()
+        .find(|(_, c)| !c.is_ascii_graphic())
+    {
+        return Err(AddressError::InvalidChar { c, pos });
+    }
+    // from here on, the address is ASCII, so byte offsets are character offsets
+    if address.bytes().any(|c| c.is_ascii_lowercase())
+        && address.bytes().any(|c| c.is_ascii_uppercase())
+    {
+        return Err(AddressError::MixedCase);
+    }
+    let lower = address.to_ascii_lowercase();
+    let sep = lower.rfind('1').ok_or(AddressError::MissingSeparator)?;
+    let (got_prefix, data) = (&lower[..sep], &lower[sep + 1..]);
+    if got_prefix != prefix {
+        return Err(AddressError::WrongPrefix {
+            expected: prefix.to_string(),
+            got: address[..sep].to_string(),
+        });
+    }
+
+    let mut values = Vec::with_capacity(data.len());
+    for (i, c) in data.bytes().enumerate() {
+        let Some(v) = CHARSET.iter().position(|x| *x == c) else {
+            let pos = sep + 1 + i;
+            return Err(AddressError::InvalidChar {
+                c: address.as_bytes()[pos] as char,
+                pos,
+            });
+        };
+        values.push(v as u8);
+    }
+    if values.len() < CHECKSUM_LEN
+        || polymod(
+            expand_prefix(prefix)
+                .into_iter()
+                .chain(values.iter().copied()),
+        ) != BECH32M_CONST
+    {
+        return Err(AddressError::InvalidChecksum);
+    }
+    convert_bits(&values[..values.len() - CHECKSUM_LEN], 5, 8, false)
+        .ok_or(AddressError::InvalidPadding)
+}
+
+impl<const N: usize> BytesImpl<N> {
+    // parses an address of exactly N bytes, e.g. a puzzle hash from its
+    // "xch1..." address
+    pub fn from_address(address: &str, prefix: &str) -> Result<Self, AddressError> {
+        let buf = decode_address(address, prefix)?;
+        let got = buf.len();
+        buf.try_into()
+            .map_err(|_| AddressError::WrongLength { expected: N, got })
+    }
+
+    pub fn to_address(&self, prefix: &str) -> String {
+        encode_address(self.as_slice(), prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bytes32;
+    use rstest::rstest;
+
+    // the first three are valid bech32m test vectors from BIP-350
+    #[rstest]
+    #[case("a1lqfn3a", "a", &[])]
+    #[case("A1LQFN3A", "a", &[])]
+    #[case("?1v759aa", "?", &[])]
+    #[case(
+        "xch1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq2u30kz",
+        "xch",
+        &[0; 32]
+    )]
+    #[case(
+        "txch1llllllllllllllllllllllllllllllllllllllllllllllllllls994g7x",
+        "txch",
+        &[0xff; 32]
+    )]
+    fn test_decode_address(#[case] address: &str, #[case] prefix: &str, #[case] expected: &[u8]) {
+        assert_eq!(decode_address(address, prefix).as_deref(), Ok(expected));
+        assert_eq!(
+            encode_address(expected, prefix),
+            address.to_ascii_lowercase()
+        );
+    }
+
+    #[rstest]
+    #[case("xch1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qarc0srg6dkm", "xch", Ok(()))]
+    #[case(
+        "XCH1QQQSYQCYQ5RQWZQFPG9SCRGWPUGPZYSNZS23V9CCRYDPK8QARC0SRG6DKM",
+        "xch",
+        Ok(())
+    )]
+    #[case(
+        "xch1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qarc0srg6dkn",
+        "xch",
+        Err(AddressError::InvalidChecksum)
+    )]
+    #[case(
+        "xch1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qarc0srg6dkM",
+        "xch",
+        Err(AddressError::MixedCase)
+    )]
+    #[case(
+        "xch1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qarc0srg6dkb",
+        "xch",
+        Err(AddressError::InvalidChar { c: 'b', pos: 61 })
+    )]
+    #[case(
+        "xch1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qarc0srg6dk é",
+        "xch",
+        Err(AddressError::InvalidChar { c: ' ', pos: 61 })
+    )]
+    #[case(
+        "xch1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qarc0srg6dkm",
+        "txch",
+        Err(AddressError::WrongPrefix { expected: "txch".to_string(), got: "xch".to_string() })
+    )]
+    #[case(
+        "qqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs",
+        "xch",
+        Err(AddressError::MissingSeparator)
+    )]
+    #[case("xch1qqq", "xch", Err(AddressError::InvalidChecksum))]
+    #[case("a1lqfn3a", "a", Err(AddressError::WrongLength { expected: 32, got: 0 }))]
+    fn test_bytes32_from_address(
+        #[case] address: &str,
+        #[case] prefix: &str,
+        #[case] expected: Result<(), AddressError>,
+    ) {
+        let expected = expected.map(|_| Bytes32::new(core::array::from_fn(|i| i as u8)));
+        assert_eq!(Bytes32::from_address(address, prefix), expected);
+    }
+
+    #[test]
+    fn test_address_round_trip() {
+        for seed in 0..10 {
+            let ph = Bytes32::random(seed);
+            assert_eq!(Bytes32::from_address(&ph.to_address("xch"), "xch"), Ok(ph));
+        }
+        assert_eq!(
+            AddressError::InvalidChar { c: 'b', pos: 61 }.to_string(),
+            "invalid address character 'b' at position 61"
+        );
+        assert_eq!(
+            AddressError::WrongPrefix {
+                expected: "txch".to_string(),
+                got: "xch".to_string()
+            }
+            .to_string(),
+            "expected address prefix \"txch\", got \"xch\""
+        );
+    }
+
+    // a 5 bit group that doesn't fit in a byte, with non-zero padding
+    #[test]
+    fn test_invalid_padding() {
+        let mut values = expand_prefix("a");
+        values.extend([1, 0, 0, 0, 0, 0, 0]);
+        let checksum = polymod(values) ^ BECH32M_CONST;
+        let mut address = "a1p".to_string();
+        address.extend(
+            (0..CHECKSUM_LEN).map(|i| CHARSET[((checksum >> (5 * (5 - i))) & 31) as usize] as char),
+        );
+        assert_eq!(
+            decode_address(&address, "a"),
+            Err(AddressError::InvalidPadding)
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::HashSet;
+
+// The transactions filter of a HeaderBlock is a BIP 158 Golomb-coded set
+// (GCS) with the "basic" filter parameters. Unlike BIP 158, the SipHash key is
+// zero rather than derived from the block hash. This matches the encoding of
+// the chiabip158 library the full node uses.
+const FILTER_P: u8 = 19;
+const FILTER_M: u64 = 784931;
+
+// SipHash-2-4, as used by Bitcoin's CSipHasher
+fn siphash(k0: u64, k1: u64, data: &[u8]) -> u64 {
+    let mut v = [
+        k0 ^ 0x736f_6d65_7073_6575,
+        k1 ^ 0x646f_7261_6e64_6f6d,
+        k0 ^ 0x6c79_6765_6e65_7261,
+        k1 ^ 0x7465_6462_7974_6573,
+    ];
+
+    fn round(v: &mut [u64; 4]) {
+        v[0] = v[0].wrapping_add(v[1]);
+        v[1] = v[1].rotate_left(13) ^ v[0];
+        v[0] = v[0].rotate_left(32);
+        v[2] = v[2].wrapping_add(v[3]);
+        v[3] = v[3].rotate_left(16) ^ v[2];
+        v[0] = v[0].wrapping_add(v[3]);
+        v[3] = v[3].rotate_left(21) ^ v[0];
+        v[2] = v[2].wrapping_add(v[1]);
+        v[1] = v[1].rotate_left(17) ^ v[2];
+        v[2] = v[2].rotate_left(32);
+    }
+
+    fn compress(v: &mut [u64; 4], m: u64) {
+        v[3] ^= m;
+        round(v);
+        round(v);
+        v[0] ^= m;
+    }
+
+    let mut chunks = data.chunks_exact(8);
+    for chunk in &mut chunks {
+        compress(&mut v, u64::from_le_bytes(chunk.try_into().unwrap()));
+    }
+    let mut last = [0_u8; 8];
+    let tail = chunks.remainder();
+    last[..tail.len()].copy_from_slice(tail);
+    last[7] = data.len() as u8;
+    compress(&mut v, u64::from_le_bytes(last));
+
+    v[2] ^= 0xff;
+    for _ in 0..4 {
+        round(&mut v);
+    }
+    v[0] ^ v[1] ^ v[2] ^ v[3]
+}
+
+// writes bits most significant bit first, padding the last byte with zeros
+struct BitWriter {
+    buf: Vec<u8>,
+    bits: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u64, nbits: u8) {
+        for i in (0..nbits).rev() {
+            if self.bits % 8 == 0 {
+                self.buf.push(0);
+            }
+            if (value >> i) & 1 == 1 {
+                *self.buf.last_mut().unwrap() |= 0x80 >> (self.bits % 8);
+            }
+            self.bits += 1;
+        }
+    }
+}
+
+fn write_compact_size(buf: &mut Vec<u8>, n: u64) {
+    if n < 253 {
+        buf.push(n as u8);
+    } else if n <= 0xffff {
+        buf.push(253);
+        buf.extend_from_slice(&(n as u16).to_le_bytes());
+    } else if n <= 0xffff_ffff {
+        buf.push(254);
+        buf.extend_from_slice(&(n as u32).to_le_bytes());
+    } else {
+        buf.push(255);
+        buf.extend_from_slice(&n.to_le_bytes());
+    }
+}
+
+// Returns the encoded BIP 158 filter of the elements. Duplicate elements are
+// only included once.
+pub fn encode_bip158_filter<T: AsRef<[u8]>>(elements: &[T]) -> Vec<u8> {
+    let elements: HashSet<&[u8]> = elements.iter().map(AsRef::as_ref).collect();
+    let n = elements.len() as u64;
+    let f = n * FILTER_M;
+
+    let mut writer = BitWriter {
+        buf: Vec::new(),
+        bits: 0,
+    };
+    write_compact_size(&mut writer.buf, n);
+
+    let mut values: Vec<u64> = elements
+        .into_iter()
+        .map(|e| ((siphash(0, 0, e) as u128 * f as u128) >> 64) as u64)
+        .collect();
+    values.sort_unstable();
+
+    let mut last_value = 0;
+    for value in values {
+        // Golomb-Rice coding of the delta
+        let delta = value - last_value;
+        last_value = value;
+        let mut q = delta >> FILTER_P;
+        while q > 0 {
+            let nbits = q.min(64);
+            writer.write(u64::MAX, nbits as u8);
+            q -= nbits;
+        }
+        writer.write(0, 1);
+        writer.write(delta, FILTER_P);
+    }
+    writer.buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn test_siphash() {
+        let k0 = 0x0706_0504_0302_0100;
+        let k1 = 0x0f0e_0d0c_0b0a_0908;
+        assert_eq!(siphash(k0, k1, &[]), 0x726f_db47_dd0e_0e31);
+        assert_eq!(siphash(k0, k1, &[0]), 0x74f8_39c5_93dc_67fd);
+        let data: Vec<u8> = (0..15).collect();
+        assert_eq!(siphash(k0, k1, &data), 0xa129_ca61_49be_45e5);
+    }
+
+    #[rstest]
+    #[case(&[], "00")]
+    #[case(&[1], "01358f60")]
+    #[case(&[1, 2, 1], "026b1ec26dbd")]
+    #[case(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9], "0a73b2862496d0677a84962a0636da66910f80021647832cebd56a")]
+    fn test_encode_filter(#[case] elements: &[u8], #[case] expected: &str) {
+        let elements: Vec<[u8; 32]> = elements.iter().map(|e| [*e; 32]).collect();
+        assert_eq!(hex::encode(encode_bip158_filter(&elements)), expected);
+    }
+}
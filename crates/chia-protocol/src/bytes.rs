@@ -1,4 +1,4 @@
-use chia_traits::{chia_error, read_bytes, Streamable};
+use chia_traits::{check_bytes_len, chia_error, read_bytes, Streamable};
 use clvm_traits::{ClvmDecoder, ClvmEncoder, FromClvm, FromClvmError, ToClvm, ToClvmError};
 use clvm_utils::TreeHash;
 use sha2::{Digest, Sha256};
@@ -6,7 +6,10 @@ use std::array::TryFromSliceError;
 use std::fmt;
 use std::io::Cursor;
 use std::ops::Deref;
+use std::str::FromStr;
 
+#[cfg(feature = "py-bindings")]
+use crate::py_bytes::byte_slice;
 #[cfg(feature = "py-bindings")]
 use chia_traits::{ChiaToPython, FromJsonDict, ToJsonDict};
 #[cfg(feature = "py-bindings")]
@@ -16,7 +19,11 @@ use pyo3::exceptions::PyValueError;
 #[cfg(feature = "py-bindings")]
 use pyo3::prelude::*;
 #[cfg(feature = "py-bindings")]
+use pyo3::sync::{GILOnceCell, GILProtected};
+#[cfg(feature = "py-bindings")]
 use pyo3::types::PyBytes;
+#[cfg(feature = "py-bindings")]
+use std::cell::RefCell;
 
 #[derive(Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -78,8 +85,13 @@ impl Streamable for Bytes {
 
     fn parse<const TRUSTED: bool>(input: &mut Cursor<&[u8]>) -> chia_error::Result<Self> {
         let len = u32::parse::<TRUSTED>(input)?;
+        check_bytes_len(len)?;
         Ok(Bytes(read_bytes(input, len as usize)?.to_vec()))
     }
+
+    fn heap_size(&self) -> usize {
+        self.0.capacity()
+    }
 }
 
 #[cfg(feature = "py-bindings")]
@@ -109,6 +121,29 @@ impl FromJsonDict for Bytes {
     }
 }
 
+// bytes are serialized as 0x-prefixed hex strings, like in the RPC JSON. When
+// deserializing, the 0x prefix is optional
+#[cfg(feature = "serde")]
+fn deserialize_hex<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    use serde::de::Error;
+    let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+    hex::decode(s.strip_prefix("0x").unwrap_or(&s)).map_err(D::Error::custom)
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Bytes {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{self}"))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Bytes {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_hex(deserializer).map(Self)
+    }
+}
+
 impl<N> ToClvm<N> for Bytes {
     fn to_clvm(&self, encoder: &mut impl ClvmEncoder<Node = N>) -> Result<N, ToClvmError> {
         encoder.encode_atom(self.0.as_slice())
@@ -160,6 +195,73 @@ impl Deref for Bytes {
     }
 }
 
+// The ways a hex string can fail to decode. Positions are character offsets
+// into the string as it was passed in, including any 0x prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexError {
+    InvalidChar { c: char, pos: usize },
+    // the number of digits, which isn't even
+    OddLength(usize),
+    WrongLength { expected: usize, got: usize },
+}
+
+impl fmt::Display for HexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidChar { c, pos } => {
+                write!(f, "invalid hex character {c:?} at position {pos}")
+            }
+            Self::OddLength(len) => write!(f, "invalid hex: odd number of digits ({len})"),
+            Self::WrongLength { expected, got } => {
+                write!(f, "expected {expected} bytes, got {got}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for HexError {}
+
+// decodes a hex string, with or without a 0x prefix
+pub fn decode_hexstr(s: &str) -> Result<Vec<u8>, HexError> {
+    let (prefix, digits) = match s.get(..2) {
+        Some("0x" | "0X") => (2, &s[2..]),
+        _ => (0, s),
+    };
+    let mut ret = Vec::with_capacity(digits.len() / 2);
+    let mut high = None;
+    for (i, c) in digits.chars().enumerate() {
+        let v = c
+            .to_digit(16)
+            .ok_or(HexError::InvalidChar { c, pos: i + prefix })? as u8;
+        match high.take() {
+            Some(h) => ret.push((h << 4) | v),
+            None => high = Some(v),
+        }
+    }
+    if high.is_some() {
+        return Err(HexError::OddLength(digits.chars().count()));
+    }
+    Ok(ret)
+}
+
+// splitmix64. We use our own PRNG (rather than the rand crate) to guarantee
+// that a given seed produces the same bytes on every platform, and across
+// versions of our dependencies
+pub(crate) fn random_bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed;
+    let mut ret = Vec::<u8>::with_capacity(len + 8);
+    while ret.len() < len {
+        state = state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^= z >> 31;
+        ret.extend_from_slice(&z.to_le_bytes());
+    }
+    ret.truncate(len);
+    ret
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BytesImpl<const N: usize>([u8; N]);
@@ -188,6 +290,33 @@ impl<const N: usize> BytesImpl<N> {
     pub fn to_vec(&self) -> Vec<u8> {
         self.0.to_vec()
     }
+
+    pub const fn zeros() -> Self {
+        Self([0; N])
+    }
+
+    // deterministic, pseudo-random bytes. The same seed always produces the
+    // same value
+    pub fn random(seed: u64) -> Self {
+        Self(random_bytes(seed, N).try_into().unwrap())
+    }
+
+    // parses a hex string of exactly N bytes, with or without a 0x prefix
+    pub fn from_hexstr(s: &str) -> Result<Self, HexError> {
+        let buf = decode_hexstr(s)?;
+        let got = buf.len();
+        buf.try_into()
+            .map(Self)
+            .map_err(|_| HexError::WrongLength { expected: N, got })
+    }
+}
+
+impl<const N: usize> FromStr for BytesImpl<N> {
+    type Err = HexError;
+
+    fn from_str(s: &str) -> Result<Self, HexError> {
+        Self::from_hexstr(s)
+    }
 }
 
 impl<const N: usize> Default for BytesImpl<N> {
@@ -256,6 +385,29 @@ impl<const N: usize> FromJsonDict for BytesImpl<N> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for BytesImpl<N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{self}"))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for BytesImpl<N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+        let buf = deserialize_hex(deserializer)?;
+        if buf.len() != N {
+            return Err(D::Error::custom(format!(
+                "invalid length {} expected {}",
+                buf.len(),
+                N
+            )));
+        }
+        Ok(buf.try_into().unwrap())
+    }
+}
+
 impl<N, const LEN: usize> ToClvm<N> for BytesImpl<LEN> {
     fn to_clvm(&self, encoder: &mut impl ClvmEncoder<Node = N>) -> Result<N, ToClvmError> {
         encoder.encode_atom(self.0.as_slice())
@@ -402,13 +554,53 @@ impl<const N: usize> IntoPy<PyObject> for BytesImpl<N> {
     }
 }
 
+#[cfg(feature = "py-bindings")]
+static BYTES32_TYPE: GILOnceCell<PyObject> = GILOnceCell::new();
+
+// the number of slots in the bytes32 interning cache
+#[cfg(feature = "py-bindings")]
+const BYTES32_CACHE_SIZE: usize = 4096;
+
+// Recently converted bytes32 objects, indexed by their first two bytes. Most
+// Bytes32 values are hashes, so this spreads them evenly. Values that repeat
+// a lot (like puzzle hashes in a wallet's coin states) share a single python
+// object rather than each field allocating its own. bytes32 is immutable, and
+// has no __dict__, so sharing instances is safe.
+#[cfg(feature = "py-bindings")]
+#[allow(clippy::type_complexity)]
+static BYTES32_CACHE: GILProtected<RefCell<Vec<Option<([u8; 32], PyObject)>>>> =
+    GILProtected::new(RefCell::new(Vec::new()));
+
+#[cfg(feature = "py-bindings")]
+fn bytes32_to_python<'a>(py: Python<'a>, value: &[u8; 32]) -> PyResult<Bound<'a, PyAny>> {
+    let slot = u16::from_le_bytes([value[0], value[1]]) as usize % BYTES32_CACHE_SIZE;
+    if let Some(Some((key, obj))) = BYTES32_CACHE.get(py).borrow().get(slot) {
+        if key == value {
+            return Ok(obj.clone_ref(py).into_bound(py));
+        }
+    }
+
+    let ty = BYTES32_TYPE.get_or_try_init(py, || -> PyResult<PyObject> {
+        let bytes_module = PyModule::import_bound(py, "gold_rs.sized_bytes")?;
+        Ok(bytes_module.getattr("bytes32")?.unbind())
+    })?;
+    let obj = ty.bind(py).call1((PyBytes::new_bound(py, value),))?;
+
+    // creating the object may have run python code, so the cache is only
+    // borrowed again once it's done
+    let mut cache = BYTES32_CACHE.get(py).borrow_mut();
+    if cache.is_empty() {
+        cache.resize_with(BYTES32_CACHE_SIZE, || None);
+    }
+    cache[slot] = Some((*value, obj.clone().unbind()));
+    Ok(obj)
+}
+
 #[cfg(feature = "py-bindings")]
 impl<const N: usize> ChiaToPython for BytesImpl<N> {
     fn to_python<'a>(&self, py: Python<'a>) -> PyResult<Bound<'a, PyAny>> {
-        if N == 32 {
-            let bytes_module = PyModule::import_bound(py, "gold_rs.sized_bytes")?;
-            let ty = bytes_module.getattr("bytes32")?;
-            ty.call1((self.0.into_py(py),))
+        if let Ok(value) = <&[u8; 32]>::try_from(self.0.as_slice()) {
+            bytes32_to_python(py, value)
         } else {
             Ok(PyBytes::new_bound(py, &self.0).into_any())
         }
@@ -417,9 +609,8 @@ impl<const N: usize> ChiaToPython for BytesImpl<N> {
 
 #[cfg(feature = "py-bindings")]
 impl<'py, const N: usize> FromPyObject<'py> for BytesImpl<N> {
-    fn extract(obj: &'py PyAny) -> PyResult<Self> {
-        let b = obj.downcast::<PyBytes>()?;
-        let slice: &[u8] = b.as_bytes();
+    fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let slice: &[u8] = byte_slice(obj)?;
         let buf: [u8; N] = slice.try_into()?;
         Ok(BytesImpl::<N>(buf))
     }
@@ -553,6 +744,72 @@ mod tests {
         buf
     }
 
+    #[rstest]
+    #[case("", Ok(vec![]))]
+    #[case("0x", Ok(vec![]))]
+    #[case("00ff7A", Ok(vec![0, 0xff, 0x7a]))]
+    #[case("0XaB", Ok(vec![0xab]))]
+    #[case("abc", Err(HexError::OddLength(3)))]
+    #[case("0xabc", Err(HexError::OddLength(3)))]
+    #[case("abcg", Err(HexError::InvalidChar { c: 'g', pos: 3 }))]
+    #[case("0xabcg", Err(HexError::InvalidChar { c: 'g', pos: 5 }))]
+    #[case("0x0x", Err(HexError::InvalidChar { c: 'x', pos: 3 }))]
+    #[case("ab d", Err(HexError::InvalidChar { c: ' ', pos: 2 }))]
+    #[case("aé", Err(HexError::InvalidChar { c: 'é', pos: 1 }))]
+    fn test_decode_hexstr(#[case] input: &str, #[case] expected: Result<Vec<u8>, HexError>) {
+        assert_eq!(decode_hexstr(input), expected);
+    }
+
+    #[test]
+    fn test_bytes32_from_hexstr() {
+        let hex = "0101010101010101010101010101010101010101010101010101010101010101";
+        let expected = Bytes32::new([1; 32]);
+        assert_eq!(Bytes32::from_hexstr(hex), Ok(expected));
+        assert_eq!(Bytes32::from_hexstr(&format!("0x{hex}")), Ok(expected));
+        assert_eq!(hex.parse::<Bytes32>(), Ok(expected));
+        assert_eq!(
+            Bytes32::from_hexstr(&hex[2..]),
+            Err(HexError::WrongLength {
+                expected: 32,
+                got: 31
+            })
+        );
+        assert_eq!(
+            Bytes32::from_hexstr(&hex[1..]),
+            Err(HexError::OddLength(63))
+        );
+        assert_eq!(
+            HexError::InvalidChar { c: 'g', pos: 3 }.to_string(),
+            "invalid hex character 'g' at position 3"
+        );
+        assert_eq!(
+            HexError::WrongLength {
+                expected: 32,
+                got: 31
+            }
+            .to_string(),
+            "expected 32 bytes, got 31"
+        );
+    }
+
+    #[test]
+    fn test_zeros_and_random() {
+        assert_eq!(Bytes32::zeros(), Bytes32::default());
+        assert_eq!(Bytes32::random(1), Bytes32::random(1));
+        assert!(Bytes32::random(1) != Bytes32::random(2));
+        assert!(Bytes32::random(1) != Bytes32::zeros());
+        // a shorter value is a prefix of the longer one
+        assert_eq!(&Bytes48::random(1)[..32], Bytes32::random(1).as_slice());
+        // splitmix64, the same bytes as SizedBytes.random(1) in python
+        assert_eq!(
+            Bytes32::random(1),
+            Bytes32::from_hexstr(
+                "c15c0289ec2d0a9167ec8e65a18debbe5e5532fbeea293f80bc942ee9086c171"
+            )
+            .unwrap()
+        );
+    }
+
     #[test]
     fn test_stream_bytes32() {
         let buf = [
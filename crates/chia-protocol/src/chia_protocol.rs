@@ -146,6 +146,16 @@ impl chia_traits::ChiaToPython for ProtocolMessageTypes {
 
 pub trait ChiaProtocolMessage {
     fn msg_type() -> ProtocolMessageTypes;
+
+    // the message types a peer may reply to this message with. Empty for
+    // messages that aren't requests
+    fn responses() -> &'static [ProtocolMessageTypes] {
+        &[]
+    }
+
+    fn is_valid_response(msg_type: ProtocolMessageTypes) -> bool {
+        Self::responses().contains(&msg_type)
+    }
 }
 
 #[repr(u8)]
@@ -191,3 +201,32 @@ pub struct Handshake {
     // Key value dict to signal support for additional capabilities/features
     capabilities: Vec<(u16, String)>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NewPeakWallet, RejectBlock, RequestBlock, RequestHeaderBlocks, RespondBlock};
+
+    #[test]
+    fn test_responses() {
+        assert_eq!(
+            RequestBlock::responses(),
+            &[
+                ProtocolMessageTypes::RespondBlock,
+                ProtocolMessageTypes::RejectBlock
+            ]
+        );
+        assert!(RequestBlock::is_valid_response(RespondBlock::msg_type()));
+        assert!(RequestBlock::is_valid_response(RejectBlock::msg_type()));
+        assert!(!RequestBlock::is_valid_response(
+            ProtocolMessageTypes::RespondBlocks
+        ));
+        assert!(RequestHeaderBlocks::is_valid_response(
+            ProtocolMessageTypes::RejectBlockHeaders
+        ));
+
+        // only requests have responses
+        assert!(RespondBlock::responses().is_empty());
+        assert!(NewPeakWallet::responses().is_empty());
+    }
+}
@@ -3,7 +3,7 @@ use chia_streamable_macro::streamable;
 use crate::coin::Coin;
 use crate::program::Program;
 
-#[streamable]
+#[streamable(cached)]
 pub struct CoinSpend {
     coin: Coin,
     puzzle_reveal: Program,
@@ -1,5 +1,8 @@
 use crate::coin::Coin;
+use crate::Bytes32;
 use chia_streamable_macro::streamable;
+use chia_traits::Streamable;
+use sha2::{Digest, Sha256};
 
 #[streamable]
 #[derive(Copy)]
@@ -8,3 +11,62 @@ pub struct CoinState {
     spent_height: Option<u32>,
     created_height: Option<u32>,
 }
+
+// The checksum of a set of coin states, e.g. all the coin states returned by
+// RespondPuzzleState for a subscription. The states are sorted by coin ID
+// (and then by their serialization) and duplicates are removed, so the hash
+// doesn't depend on their order or how they were split into pages. The hash
+// is the sha256 of the streamable serialization of the sorted list.
+pub fn hash_coin_states(states: &[CoinState]) -> Bytes32 {
+    let mut sorted: Vec<(Bytes32, &CoinState)> =
+        states.iter().map(|s| (s.coin.coin_id(), s)).collect();
+    sorted.sort_by(|(lhs_id, lhs), (rhs_id, rhs)| {
+        lhs_id
+            .cmp(rhs_id)
+            .then_with(|| lhs.spent_height.cmp(&rhs.spent_height))
+            .then_with(|| lhs.created_height.cmp(&rhs.created_height))
+    });
+    sorted.dedup_by(|(_, lhs), (_, rhs)| lhs == rhs);
+
+    let mut ctx = Sha256::new();
+    (sorted.len() as u32).update_digest(&mut ctx);
+    for (_, state) in sorted {
+        state.update_digest(&mut ctx);
+    }
+    Bytes32::new(ctx.finalize().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(i: u8, spent_height: Option<u32>) -> CoinState {
+        let coin = Coin::new(Bytes32::new([i; 32]), Bytes32::new([42; 32]), i as u64);
+        CoinState::new(coin, spent_height, Some(1))
+    }
+
+    #[test]
+    fn test_hash_coin_states() {
+        let states = [state(1, None), state(2, Some(5)), state(3, None)];
+        let hash = hash_coin_states(&states);
+
+        // the same as hashing the serialization of the sorted list
+        let mut sorted = states.to_vec();
+        sorted.sort_by_key(|s| s.coin.coin_id());
+        assert_eq!(hash, Bytes32::new(sorted.hash()));
+
+        // the order and duplicates don't matter
+        let shuffled = [states[2], states[0], states[1], states[0]];
+        assert_eq!(hash_coin_states(&shuffled), hash);
+
+        // but the heights do
+        let spent = [state(1, Some(7)), states[1], states[2]];
+        assert_ne!(hash_coin_states(&spent), hash);
+        assert_ne!(hash_coin_states(&states[..2]), hash);
+
+        assert_eq!(
+            hash_coin_states(&[]),
+            Bytes32::new(Vec::<CoinState>::new().hash())
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use chia_traits::chia_error::{Error, Result};
+use chia_traits::Streamable;
+
+use crate::{FullBlock, SubEpochSegments};
+
+#[cfg(feature = "py-bindings")]
+use pyo3::prelude::*;
+#[cfg(feature = "py-bindings")]
+use pyo3::types::PyBytes;
+
+// the compression level the full node uses when storing blocks and sub-epoch
+// segments in its database
+pub const DEFAULT_COMPRESSION_LEVEL: i32 = 3;
+
+// Types the full node stores zstd-compressed in its database. The compressed
+// form is a single zstd frame of the streamable serialization, without any
+// additional framing.
+pub trait Compressed: Streamable {
+    fn to_bytes_compressed(&self, level: i32) -> Result<Vec<u8>> {
+        let buf = self.to_bytes()?;
+        zstd::stream::encode_all(buf.as_slice(), level)
+            .map_err(|e| Error::Custom(format!("zstd compression failed: {e}")))
+    }
+
+    fn from_bytes_compressed(bytes: &[u8]) -> Result<Self> {
+        let buf = zstd::stream::decode_all(bytes)
+            .map_err(|e| Error::Custom(format!("zstd decompression failed: {e}")))?;
+        Self::from_bytes(&buf)
+    }
+
+    // like from_bytes_compressed(), but without validating the contents. Only
+    // use this for data that has already been validated, like blocks read from
+    // the node's own database
+    fn from_bytes_compressed_unchecked(bytes: &[u8]) -> Result<Self> {
+        let buf = zstd::stream::decode_all(bytes)
+            .map_err(|e| Error::Custom(format!("zstd decompression failed: {e}")))?;
+        Self::from_bytes_unchecked(&buf)
+    }
+}
+
+impl Compressed for FullBlock {}
+impl Compressed for SubEpochSegments {}
+
+#[cfg(feature = "py-bindings")]
+macro_rules! py_compressed {
+    ($t:ty) => {
+        #[pymethods]
+        impl $t {
+            #[pyo3(name = "to_bytes_compressed", signature = (level = DEFAULT_COMPRESSION_LEVEL))]
+            fn py_to_bytes_compressed<'p>(
+                &self,
+                py: Python<'p>,
+                level: i32,
+            ) -> PyResult<Bound<'p, PyBytes>> {
+                let buf = py.allow_threads(|| self.to_bytes_compressed(level))?;
+                Ok(PyBytes::new_bound(py, &buf))
+            }
+
+            #[staticmethod]
+            #[pyo3(name = "from_bytes_compressed")]
+            fn py_from_bytes_compressed(py: Python<'_>, blob: &[u8]) -> PyResult<Self> {
+                Ok(py.allow_threads(|| <$t>::from_bytes_compressed(blob))?)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "py-bindings")]
+py_compressed!(FullBlock);
+#[cfg(feature = "py-bindings")]
+py_compressed!(SubEpochSegments);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SubEpochChallengeSegment;
+
+    #[test]
+    fn test_round_trip() {
+        let segments =
+            SubEpochSegments::new(vec![SubEpochChallengeSegment::new(7, vec![], None); 100]);
+        let buf = segments
+            .to_bytes_compressed(DEFAULT_COMPRESSION_LEVEL)
+            .unwrap();
+        assert!(buf.len() < segments.to_bytes().unwrap().len());
+        assert_eq!(
+            SubEpochSegments::from_bytes_compressed(&buf).unwrap(),
+            segments
+        );
+        assert_eq!(
+            SubEpochSegments::from_bytes_compressed_unchecked(&buf).unwrap(),
+            segments
+        );
+
+        // the uncompressed serialization is not accepted
+        assert!(SubEpochSegments::from_bytes_compressed(&segments.to_bytes().unwrap()).is_err());
+    }
+}
@@ -0,0 +1,63 @@
+use chia_bls::G2Element;
+use chia_streamable_macro::streamable;
+
+use crate::Bytes32;
+use crate::FoliageBlockData;
+use crate::FoliageTransactionBlock;
+use crate::PoolTarget;
+use crate::ProofOfSpace;
+use crate::RewardChainBlockUnfinished;
+
+#[streamable(message)]
+pub struct NewSignagePoint {
+    challenge_hash: Bytes32,
+    challenge_chain_sp: Bytes32,
+    reward_chain_sp: Bytes32,
+    difficulty: u64,
+    sub_slot_iters: u64,
+    signage_point_index: u8,
+    peak_height: u32,
+}
+
+#[streamable(message)]
+pub struct DeclareProofOfSpace {
+    challenge_hash: Bytes32,
+    challenge_chain_sp: Bytes32,
+    signage_point_index: u8,
+    reward_chain_sp: Bytes32,
+    proof_of_space: ProofOfSpace,
+    challenge_chain_sp_signature: G2Element,
+    reward_chain_sp_signature: G2Element,
+    farmer_puzzle_hash: Bytes32,
+    pool_target: Option<PoolTarget>,
+    pool_signature: Option<G2Element>,
+    include_signature_source_data: bool,
+}
+
+#[streamable(message)]
+pub struct RequestSignedValues {
+    quality_string: Bytes32,
+    foliage_block_data_hash: Bytes32,
+    foliage_transaction_block_hash: Bytes32,
+    foliage_block_data: Option<FoliageBlockData>,
+    foliage_transaction_block_data: Option<FoliageTransactionBlock>,
+    rc_block_unfinished: Option<RewardChainBlockUnfinished>,
+}
+
+#[streamable(message)]
+pub struct FarmingInfo {
+    challenge_hash: Bytes32,
+    sp_hash: Bytes32,
+    timestamp: u64,
+    passed: u32,
+    proofs: u32,
+    total_plots: u32,
+    lookup_time: u64,
+}
+
+#[streamable(message)]
+pub struct SignedValues {
+    quality_string: Bytes32,
+    foliage_block_data_signature: G2Element,
+    foliage_transaction_block_signature: G2Element,
+}
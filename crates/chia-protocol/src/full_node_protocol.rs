@@ -26,7 +26,7 @@ pub struct NewTransaction {
     fees: u64,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondTransaction))]
 pub struct RequestTransaction {
     transaction_id: Bytes32,
 }
@@ -36,7 +36,7 @@ pub struct RespondTransaction {
     transaction: SpendBundle,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondProofOfWeight))]
 pub struct RequestProofOfWeight {
     total_number_of_blocks: u32,
     tip: Bytes32,
@@ -48,7 +48,7 @@ pub struct RespondProofOfWeight {
     tip: Bytes32,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondBlock, RejectBlock))]
 pub struct RequestBlock {
     height: u32,
     include_transaction_block: bool,
@@ -59,7 +59,7 @@ pub struct RejectBlock {
     height: u32,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondBlocks, RejectBlocks))]
 pub struct RequestBlocks {
     start_height: u32,
     end_height: u32,
@@ -89,7 +89,7 @@ pub struct NewUnfinishedBlock {
     unfinished_reward_hash: Bytes32,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondUnfinishedBlock))]
 pub struct RequestUnfinishedBlock {
     unfinished_reward_hash: Bytes32,
 }
@@ -107,7 +107,7 @@ pub struct NewSignagePointOrEndOfSubSlot {
     last_rc_infusion: Bytes32,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondSignagePoint, RespondEndOfSubSlot))]
 pub struct RequestSignagePointOrEndOfSubSlot {
     challenge_hash: Bytes32,
     index_from_challenge: u8,
@@ -141,7 +141,7 @@ pub struct NewCompactVDF {
     vdf_info: VDFInfo,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondCompactVDF))]
 pub struct RequestCompactVDF {
     height: u32,
     header_hash: Bytes32,
@@ -158,7 +158,7 @@ pub struct RespondCompactVDF {
     vdf_proof: VDFProof,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondPeers))]
 pub struct RequestPeers {}
 
 #[streamable(message)]
@@ -172,7 +172,7 @@ pub struct NewUnfinishedBlock2 {
     foliage_hash: Option<Bytes32>,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondUnfinishedBlock))]
 pub struct RequestUnfinishedBlock2 {
     unfinished_reward_hash: Bytes32,
     foliage_hash: Option<Bytes32>,
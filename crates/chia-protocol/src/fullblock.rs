@@ -1,15 +1,46 @@
 use chia_streamable_macro::streamable;
 
+use crate::encode_bip158_filter;
 use crate::Bytes32;
 use crate::Coin;
 use crate::EndOfSubSlotBundle;
+use crate::HeaderBlock;
 use crate::Program;
+use crate::RespondCompactVDF;
 use crate::RewardChainBlock;
-use crate::VDFProof;
 use crate::{Foliage, FoliageTransactionBlock, TransactionsInfo};
+use crate::{VDFInfo, VDFProof};
 use chia_traits::Streamable;
 
-#[streamable]
+// The VDF proofs of a block that can be replaced by compact ones, the same
+// numbering as the field_vdf of the compact VDF messages
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompressibleVDFField {
+    CcEosVdf = 1,
+    IccEosVdf = 2,
+    CcSpVdf = 3,
+    CcIpVdf = 4,
+}
+
+impl CompressibleVDFField {
+    pub fn from_u8(v: u8) -> Option<Self> {
+        match v {
+            1 => Some(Self::CcEosVdf),
+            2 => Some(Self::IccEosVdf),
+            3 => Some(Self::CcSpVdf),
+            4 => Some(Self::CcIpVdf),
+            _ => None,
+        }
+    }
+}
+
+// a compact proof has no witness and was computed from the identity element
+fn is_compact(proof: &VDFProof) -> bool {
+    proof.witness_type == 0 && proof.normalized_to_identity
+}
+
+#[streamable(cached)]
 pub struct FullBlock {
     finished_sub_slots: Vec<EndOfSubSlotBundle>,
     reward_chain_block: RewardChainBlock,
@@ -60,28 +91,131 @@ impl FullBlock {
 
     pub fn is_fully_compactified(&self) -> bool {
         for sub_slot in &self.finished_sub_slots {
-            if sub_slot.proofs.challenge_chain_slot_proof.witness_type != 0
-                || !sub_slot
-                    .proofs
-                    .challenge_chain_slot_proof
-                    .normalized_to_identity
-            {
+            if !is_compact(&sub_slot.proofs.challenge_chain_slot_proof) {
                 return false;
             }
             if let Some(proof) = &sub_slot.proofs.infused_challenge_chain_slot_proof {
-                if proof.witness_type != 0 || !proof.normalized_to_identity {
+                if !is_compact(proof) {
                     return false;
                 }
             }
         }
 
         if let Some(proof) = &self.challenge_chain_sp_proof {
-            if proof.witness_type != 0 || !proof.normalized_to_identity {
+            if !is_compact(proof) {
                 return false;
             }
         }
-        self.challenge_chain_ip_proof.witness_type == 0
-            && self.challenge_chain_ip_proof.normalized_to_identity
+        is_compact(&self.challenge_chain_ip_proof)
+    }
+
+    // The VDF proofs that can still be replaced by compact proofs, along with
+    // the VDF they prove. The reward chain proofs are not included, since
+    // they're never compactified
+    pub fn uncompact_proofs(&self) -> Vec<(CompressibleVDFField, VDFInfo)> {
+        let mut ret = Vec::new();
+        for sub_slot in &self.finished_sub_slots {
+            if !is_compact(&sub_slot.proofs.challenge_chain_slot_proof) {
+                ret.push((
+                    CompressibleVDFField::CcEosVdf,
+                    sub_slot
+                        .challenge_chain
+                        .challenge_chain_end_of_slot_vdf
+                        .clone(),
+                ));
+            }
+            if let (Some(proof), Some(icc)) = (
+                &sub_slot.proofs.infused_challenge_chain_slot_proof,
+                &sub_slot.infused_challenge_chain,
+            ) {
+                if !is_compact(proof) {
+                    ret.push((
+                        CompressibleVDFField::IccEosVdf,
+                        icc.infused_challenge_chain_end_of_slot_vdf.clone(),
+                    ));
+                }
+            }
+        }
+        if let (Some(proof), Some(vdf)) = (
+            &self.challenge_chain_sp_proof,
+            &self.reward_chain_block.challenge_chain_sp_vdf,
+        ) {
+            if !is_compact(proof) {
+                ret.push((CompressibleVDFField::CcSpVdf, vdf.clone()));
+            }
+        }
+        if !is_compact(&self.challenge_chain_ip_proof) {
+            ret.push((
+                CompressibleVDFField::CcIpVdf,
+                self.reward_chain_block.challenge_chain_ip_vdf.clone(),
+            ));
+        }
+        ret
+    }
+
+    // Whether the compact proof in the response may replace one of the proofs
+    // of this block: it must refer to this block, to a VDF of the block whose
+    // proof isn't compact yet, and the new proof must be compact. This does
+    // not verify the proof itself, that requires a VDF verifier
+    pub fn can_accept_compact_vdf(&self, response: &RespondCompactVDF) -> bool {
+        if response.height != self.height()
+            || response.header_hash != self.header_hash()
+            || !is_compact(&response.vdf_proof)
+        {
+            return false;
+        }
+        let Some(field) = CompressibleVDFField::from_u8(response.field_vdf) else {
+            return false;
+        };
+        self.uncompact_proofs()
+            .iter()
+            .any(|(f, vdf)| *f == field && *vdf == response.vdf_info)
+    }
+
+    // The header block served to wallets. The transactions filter contains the
+    // puzzle hashes of the coins created by the block's transactions
+    // (tx_addition_coins) and its included reward coins, and the IDs of the
+    // coins it spends (removal_names). Non-transaction blocks have an empty
+    // filter.
+    pub fn get_header_block(
+        &self,
+        tx_addition_coins: &[Coin],
+        removal_names: &[Bytes32],
+    ) -> HeaderBlock {
+        let mut elements = Vec::<Bytes32>::new();
+        if self.is_transaction_block() {
+            elements.extend(tx_addition_coins.iter().map(|c| c.puzzle_hash));
+            elements.extend(
+                self.get_included_reward_coins()
+                    .iter()
+                    .map(|c| c.puzzle_hash),
+            );
+            elements.extend_from_slice(removal_names);
+        }
+        self.header_block_with_filter(encode_bip158_filter(&elements))
+    }
+
+    // the header block without a transactions filter (an empty byte string).
+    // This is cheaper, and enough when only the chain of headers matters, e.g.
+    // for weight proofs
+    pub fn get_block_header(&self) -> HeaderBlock {
+        self.header_block_with_filter(Vec::new())
+    }
+
+    fn header_block_with_filter(&self, transactions_filter: Vec<u8>) -> HeaderBlock {
+        HeaderBlock::new(
+            self.finished_sub_slots.clone(),
+            self.reward_chain_block.clone(),
+            self.challenge_chain_sp_proof.clone(),
+            self.challenge_chain_ip_proof.clone(),
+            self.reward_chain_sp_proof.clone(),
+            self.reward_chain_ip_proof.clone(),
+            self.infused_challenge_chain_ip_proof.clone(),
+            self.foliage.clone(),
+            self.foliage_transaction_block.clone(),
+            transactions_filter.into(),
+            self.transactions_info.clone(),
+        )
     }
 }
 
@@ -137,4 +271,31 @@ impl FullBlock {
     fn py_is_fully_compactified(&self) -> bool {
         self.is_fully_compactified()
     }
+
+    #[pyo3(name = "uncompact_proofs")]
+    fn py_uncompact_proofs(&self) -> Vec<(u8, VDFInfo)> {
+        self.uncompact_proofs()
+            .into_iter()
+            .map(|(field, vdf)| (field as u8, vdf))
+            .collect()
+    }
+
+    #[pyo3(name = "can_accept_compact_vdf")]
+    fn py_can_accept_compact_vdf(&self, response: &RespondCompactVDF) -> bool {
+        self.can_accept_compact_vdf(response)
+    }
+
+    #[pyo3(name = "get_header_block")]
+    fn py_get_header_block(
+        &self,
+        tx_addition_coins: Vec<Coin>,
+        removals_names: Vec<Bytes32>,
+    ) -> HeaderBlock {
+        self.get_header_block(&tx_addition_coins, &removals_names)
+    }
+
+    #[pyo3(name = "get_block_header")]
+    fn py_get_block_header(&self) -> HeaderBlock {
+        self.get_block_header()
+    }
 }
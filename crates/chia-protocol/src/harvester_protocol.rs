@@ -0,0 +1,186 @@
+use chia_bls::{G1Element, G2Element};
+use chia_streamable_macro::{streamable, Streamable};
+
+use crate::ProofOfSpace;
+use crate::RewardChainBlockUnfinished;
+use crate::{Bytes, Bytes32};
+
+#[streamable]
+pub struct PoolDifficulty {
+    difficulty: u64,
+    sub_slot_iters: u64,
+    pool_contract_puzzle_hash: Bytes32,
+}
+
+#[streamable(message)]
+pub struct HarvesterHandshake {
+    farmer_public_keys: Vec<G1Element>,
+    pool_public_keys: Vec<G1Element>,
+}
+
+#[streamable(message)]
+pub struct NewSignagePointHarvester {
+    challenge_hash: Bytes32,
+    difficulty: u64,
+    sub_slot_iters: u64,
+    signage_point_index: u8,
+    sp_hash: Bytes32,
+    pool_difficulties: Vec<PoolDifficulty>,
+    filter_prefix_bits: u8,
+}
+
+#[streamable]
+pub struct ProofOfSpaceFeeInfo {
+    applied_fee_threshold: u32,
+}
+
+#[streamable(message)]
+pub struct NewProofOfSpace {
+    challenge_hash: Bytes32,
+    sp_hash: Bytes32,
+    plot_identifier: String,
+    proof: ProofOfSpace,
+    signage_point_index: u8,
+    include_source_signature_data: bool,
+    farmer_reward_address_override: Option<Bytes32>,
+    fee_info: Option<ProofOfSpaceFeeInfo>,
+}
+
+#[streamable]
+pub struct SignatureRequestSourceData {
+    kind: SigningDataKind,
+    data: Bytes,
+}
+
+#[streamable(message)]
+pub struct RequestSignatures {
+    plot_identifier: String,
+    challenge_hash: Bytes32,
+    sp_hash: Bytes32,
+    messages: Vec<Bytes32>,
+    message_data: Option<Vec<Option<SignatureRequestSourceData>>>,
+    rc_block_unfinished: Option<RewardChainBlockUnfinished>,
+}
+
+#[streamable(message)]
+pub struct RespondSignatures {
+    plot_identifier: String,
+    challenge_hash: Bytes32,
+    sp_hash: Bytes32,
+    local_pk: G1Element,
+    farmer_pk: G1Element,
+    message_signatures: Vec<(Bytes32, G2Element)>,
+    include_source_signature_data: bool,
+    farmer_reward_address_override: Option<Bytes32>,
+}
+
+#[streamable]
+pub struct Plot {
+    filename: String,
+    size: u8,
+    plot_id: Bytes32,
+    pool_public_key: Option<G1Element>,
+    pool_contract_puzzle_hash: Option<Bytes32>,
+    plot_public_key: G1Element,
+    file_size: u64,
+    time_modified: u64,
+    compression_level: Option<u8>,
+}
+
+#[streamable(message)]
+pub struct RequestPlots {}
+
+#[streamable(message)]
+pub struct RespondPlots {
+    plots: Vec<Plot>,
+    failed_to_open_filenames: Vec<String>,
+    no_key_filenames: Vec<String>,
+}
+
+// the plot sync messages are sent from the harvester to the farmer, to keep
+// the farmer's view of the harvester's plots up to date. Each message is
+// acknowledged by a PlotSyncResponse
+
+#[streamable]
+pub struct PlotSyncIdentifier {
+    timestamp: u64,
+    sync_id: u64,
+    message_id: u64,
+}
+
+#[streamable(message)]
+pub struct PlotSyncStart {
+    identifier: PlotSyncIdentifier,
+    initial: bool,
+    last_sync_id: u64,
+    plot_file_count: u32,
+    harvesting_mode: u8,
+}
+
+// sent as PlotSyncRemoved, PlotSyncInvalid, PlotSyncKeysMissing and
+// PlotSyncDuplicates. The python class calls the last field "final", which is
+// a reserved word in rust, so it's only called is_final on the rust side
+#[streamable]
+pub struct PlotSyncPathList {
+    identifier: PlotSyncIdentifier,
+    data: Vec<String>,
+    #[py_name = "final"]
+    #[cfg_attr(feature = "serde", serde(rename = "final"))]
+    is_final: bool,
+}
+
+// sent as PlotSyncLoaded
+#[streamable]
+pub struct PlotSyncPlotList {
+    identifier: PlotSyncIdentifier,
+    data: Vec<Plot>,
+    #[py_name = "final"]
+    #[cfg_attr(feature = "serde", serde(rename = "final"))]
+    is_final: bool,
+}
+
+#[streamable(message)]
+pub struct PlotSyncDone {
+    identifier: PlotSyncIdentifier,
+    duration: u64,
+}
+
+#[streamable]
+pub struct PlotSyncError {
+    code: i16,
+    message: String,
+    expected_identifier: Option<PlotSyncIdentifier>,
+}
+
+#[streamable(message)]
+pub struct PlotSyncResponse {
+    identifier: PlotSyncIdentifier,
+    message_type: i16,
+    error: Option<PlotSyncError>,
+}
+
+#[cfg(feature = "py-bindings")]
+use chia_py_streamable_macro::{PyJsonDict, PyStreamable};
+
+// identifies the kind of data a RequestSignatures message refers to, so the
+// harvester (or a remote signer) can inspect it before signing
+#[repr(u8)]
+#[cfg_attr(feature = "py-bindings", derive(PyJsonDict, PyStreamable))]
+#[derive(Streamable, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum SigningDataKind {
+    FoliageBlockData = 1,
+    FoliageTransactionBlock = 2,
+    ChallengeChainVdf = 3,
+    RewardChainVdf = 4,
+    ChallengeChainSubSlot = 5,
+    RewardChainSubSlot = 6,
+    Partial = 7,
+}
+
+#[cfg(feature = "py-bindings")]
+impl chia_traits::ChiaToPython for SigningDataKind {
+    fn to_python<'a>(&self, py: pyo3::Python<'a>) -> pyo3::PyResult<pyo3::Bound<'a, pyo3::PyAny>> {
+        Ok(pyo3::IntoPy::into_py(*self, py).bind(py).clone())
+    }
+}
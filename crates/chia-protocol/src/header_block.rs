@@ -9,7 +9,7 @@ use crate::VDFProof;
 use crate::{Foliage, FoliageTransactionBlock, TransactionsInfo};
 use chia_traits::Streamable;
 
-#[streamable]
+#[streamable(cached)]
 pub struct HeaderBlock {
     // If first sb
     finished_sub_slots: Vec<EndOfSubSlotBundle>,
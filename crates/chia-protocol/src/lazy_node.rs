@@ -1,4 +1,5 @@
 use clvmr::{allocator::NodePtr, allocator::SExp, Allocator};
+use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::types::{PyBytes, PyTuple};
 use std::rc::Rc;
@@ -40,6 +41,45 @@ impl LazyNode {
             _ => None,
         }
     }
+
+    pub fn first(&self) -> PyResult<Self> {
+        match &self.allocator.sexp(self.node) {
+            SExp::Pair(first, _) => Ok(Self::new(self.allocator.clone(), *first)),
+            SExp::Atom => Err(PyValueError::new_err("first of non-cons")),
+        }
+    }
+
+    pub fn rest(&self) -> PyResult<Self> {
+        match &self.allocator.sexp(self.node) {
+            SExp::Pair(_, rest) => Ok(Self::new(self.allocator.clone(), *rest)),
+            SExp::Atom => Err(PyValueError::new_err("rest of non-cons")),
+        }
+    }
+
+    // interprets the atom as a signed, big-endian integer
+    pub fn as_int(&self, py: Python) -> PyResult<PyObject> {
+        match &self.allocator.sexp(self.node) {
+            SExp::Atom => Ok(self.allocator.number(self.node).into_py(py)),
+            SExp::Pair(..) => Err(PyValueError::new_err("as_int() of non-atom")),
+        }
+    }
+
+    // returns the atoms of the list. Just like the python Program, this never
+    // fails. It stops at the first item that isn't an atom (or at the end of the
+    // list)
+    pub fn as_atom_list(&self, py: Python) -> Vec<PyObject> {
+        let a = &self.allocator;
+        let mut ret = Vec::new();
+        let mut node = self.node;
+        while let SExp::Pair(first, rest) = a.sexp(node) {
+            let SExp::Atom = a.sexp(first) else {
+                break;
+            };
+            ret.push(PyBytes::new_bound(py, a.atom(first).as_ref()).into());
+            node = rest;
+        }
+        ret
+    }
 }
 
 impl LazyNode {
@@ -1,3 +1,5 @@
+mod address;
+mod bip158;
 mod block_record;
 mod bytes;
 mod chia_protocol;
@@ -6,19 +8,27 @@ mod coin;
 mod coin_spend;
 mod coin_state;
 mod end_of_sub_slot_bundle;
+mod farmer_protocol;
 mod fee_estimate;
 mod foliage;
 mod full_node_protocol;
 mod fullblock;
+mod harvester_protocol;
 mod header_block;
+mod introducer_protocol;
 mod peer_info;
+mod pool_protocol;
+mod pool_state;
 mod pool_target;
 mod program;
 mod proof_of_space;
+mod protocol_object;
+mod rate_limits;
 mod reward_chain_block;
 mod slots;
 mod spend_bundle;
 mod sub_epoch_summary;
+mod timelord_protocol;
 mod unfinished_block;
 mod unfinished_header_block;
 mod vdf;
@@ -28,7 +38,21 @@ mod weight_proof;
 #[cfg(feature = "py-bindings")]
 mod lazy_node;
 
+#[cfg(feature = "py-bindings")]
+mod py_bytes;
+
+#[cfg(feature = "zstd")]
+mod compressed;
+
+#[cfg(feature = "arbitrary")]
+mod random;
+
+#[cfg(feature = "serde")]
+mod serde_enums;
+
 // export shorter names
+pub use crate::address::*;
+pub use crate::bip158::*;
 pub use crate::block_record::*;
 pub use crate::bytes::*;
 pub use crate::chia_protocol::*;
@@ -37,19 +61,27 @@ pub use crate::coin::*;
 pub use crate::coin_spend::*;
 pub use crate::coin_state::*;
 pub use crate::end_of_sub_slot_bundle::*;
+pub use crate::farmer_protocol::*;
 pub use crate::fee_estimate::*;
 pub use crate::foliage::*;
 pub use crate::full_node_protocol::*;
 pub use crate::fullblock::*;
+pub use crate::harvester_protocol::*;
 pub use crate::header_block::*;
+pub use crate::introducer_protocol::*;
 pub use crate::peer_info::*;
+pub use crate::pool_protocol::*;
+pub use crate::pool_state::*;
 pub use crate::pool_target::*;
 pub use crate::program::*;
 pub use crate::proof_of_space::*;
+pub use crate::protocol_object::*;
+pub use crate::rate_limits::*;
 pub use crate::reward_chain_block::*;
 pub use crate::slots::*;
 pub use crate::spend_bundle::*;
 pub use crate::sub_epoch_summary::*;
+pub use crate::timelord_protocol::*;
 pub use crate::unfinished_block::*;
 pub use crate::unfinished_header_block::*;
 pub use crate::vdf::*;
@@ -58,3 +90,12 @@ pub use crate::weight_proof::*;
 
 #[cfg(feature = "py-bindings")]
 pub use crate::lazy_node::*;
+
+#[cfg(feature = "py-bindings")]
+pub use crate::py_bytes::*;
+
+#[cfg(feature = "zstd")]
+pub use crate::compressed::*;
+
+#[cfg(feature = "arbitrary")]
+pub use crate::random::*;
@@ -1,4 +1,8 @@
 use chia_streamable_macro::streamable;
+use std::net::{AddrParseError, IpAddr, SocketAddr};
+
+#[cfg(feature = "py-bindings")]
+use pyo3::prelude::*;
 
 #[streamable]
 pub struct TimestampedPeerInfo {
@@ -6,3 +10,93 @@ pub struct TimestampedPeerInfo {
     port: u16,
     timestamp: u64,
 }
+
+impl TimestampedPeerInfo {
+    // the host is stored as the plain IP address. IPv6 addresses are stored
+    // without brackets
+    pub fn from_socket_addr(addr: SocketAddr, timestamp: u64) -> Self {
+        Self {
+            host: addr.ip().to_string(),
+            port: addr.port(),
+            timestamp,
+        }
+    }
+
+    // parses an address of the form "1.2.3.4:8444" or "[::1]:8444"
+    pub fn from_address(address: &str, timestamp: u64) -> Result<Self, AddrParseError> {
+        Ok(Self::from_socket_addr(address.parse()?, timestamp))
+    }
+
+    // the host is expected to be an IP address. Peers sent by the introducer
+    // (and full nodes) always are. IPv6 hosts may be enclosed in brackets
+    pub fn socket_addr(&self) -> Result<SocketAddr, AddrParseError> {
+        let host = self
+            .host
+            .strip_prefix('[')
+            .and_then(|h| h.strip_suffix(']'))
+            .unwrap_or(&self.host);
+        let ip: IpAddr = host.parse()?;
+        Ok(SocketAddr::new(ip, self.port))
+    }
+
+    // the "host:port" form of this peer, with brackets around IPv6 addresses
+    pub fn address(&self) -> String {
+        match self.socket_addr() {
+            Ok(addr) => addr.to_string(),
+            Err(_) => format!("{}:{}", self.host, self.port),
+        }
+    }
+}
+
+#[cfg(feature = "py-bindings")]
+#[pymethods]
+impl TimestampedPeerInfo {
+    #[staticmethod]
+    #[pyo3(name = "from_address")]
+    fn py_from_address(address: &str, timestamp: u64) -> PyResult<Self> {
+        Self::from_address(address, timestamp)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    #[pyo3(name = "address")]
+    fn py_address(&self) -> String {
+        self.address()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("1.2.3.4:8444", "1.2.3.4", 8444)]
+    #[case("[::1]:58444", "::1", 58444)]
+    #[case("[2001:db8::ff00:42:8329]:8444", "2001:db8::ff00:42:8329", 8444)]
+    #[case("[::ffff:1.2.3.4]:8444", "::ffff:1.2.3.4", 8444)]
+    fn test_address_roundtrip(#[case] address: &str, #[case] host: &str, #[case] port: u16) {
+        let peer = TimestampedPeerInfo::from_address(address, 1337).unwrap();
+        assert_eq!(peer.host, host);
+        assert_eq!(peer.port, port);
+        assert_eq!(peer.timestamp, 1337);
+        assert_eq!(peer.address(), address);
+        assert_eq!(peer.socket_addr().unwrap(), address.parse().unwrap());
+    }
+
+    #[test]
+    fn test_bracketed_host() {
+        let peer = TimestampedPeerInfo::new("[::1]".to_string(), 8444, 0);
+        assert_eq!(peer.socket_addr().unwrap(), "[::1]:8444".parse().unwrap());
+        assert_eq!(peer.address(), "[::1]:8444");
+    }
+
+    #[test]
+    fn test_invalid_host() {
+        assert!(TimestampedPeerInfo::from_address("example.com:8444", 0).is_err());
+        assert!(TimestampedPeerInfo::from_address("::1:8444", 0).is_err());
+
+        let peer = TimestampedPeerInfo::new("example.com".to_string(), 8444, 0);
+        assert!(peer.socket_addr().is_err());
+        assert_eq!(peer.address(), "example.com:8444");
+    }
+}
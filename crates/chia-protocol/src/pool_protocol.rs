@@ -0,0 +1,190 @@
+use chia_bls::{G1Element, G2Element};
+use chia_streamable_macro::streamable;
+
+use crate::Bytes32;
+use crate::ProofOfSpace;
+
+// The pool protocol is spoken between farmers and pools, over HTTP (as JSON).
+// The payloads are signed by the farmer, which is why they need a canonical
+// (streamable) serialization.
+
+pub const POOL_PROTOCOL_VERSION: u8 = 1;
+
+#[repr(u16)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PoolErrorCode {
+    RevertedSignagePoint = 1,
+    TooLate = 2,
+    NotFound = 3,
+    InvalidProof = 4,
+    ProofNotGoodEnough = 5,
+    InvalidDifficulty = 6,
+    InvalidSignature = 7,
+    ServerException = 8,
+    InvalidP2SingletonPuzzleHash = 9,
+    FarmerNotKnown = 10,
+    FarmerAlreadyKnown = 11,
+    InvalidAuthenticationToken = 12,
+    InvalidPayoutInstructions = 13,
+    InvalidSingleton = 14,
+    DelayTimeTooShort = 15,
+    RequestFailed = 16,
+}
+
+impl TryFrom<u16> for PoolErrorCode {
+    type Error = u16;
+
+    fn try_from(value: u16) -> Result<Self, u16> {
+        use PoolErrorCode::*;
+        Ok(match value {
+            1 => RevertedSignagePoint,
+            2 => TooLate,
+            3 => NotFound,
+            4 => InvalidProof,
+            5 => ProofNotGoodEnough,
+            6 => InvalidDifficulty,
+            7 => InvalidSignature,
+            8 => ServerException,
+            9 => InvalidP2SingletonPuzzleHash,
+            10 => FarmerNotKnown,
+            11 => FarmerAlreadyKnown,
+            12 => InvalidAuthenticationToken,
+            13 => InvalidPayoutInstructions,
+            14 => InvalidSingleton,
+            15 => DelayTimeTooShort,
+            16 => RequestFailed,
+            _ => return Err(value),
+        })
+    }
+}
+
+impl From<PoolErrorCode> for u16 {
+    fn from(v: PoolErrorCode) -> u16 {
+        v as u16
+    }
+}
+
+#[streamable]
+pub struct AuthenticationPayload {
+    method_name: String,
+    launcher_id: Bytes32,
+    target_puzzle_hash: Bytes32,
+    authentication_token: u64,
+}
+
+#[streamable]
+pub struct GetPoolInfoResponse {
+    name: String,
+    logo_url: String,
+    minimum_difficulty: u64,
+    relative_lock_height: u32,
+    protocol_version: u8,
+    fee: String,
+    description: String,
+    target_puzzle_hash: Bytes32,
+    authentication_token_timeout: u8,
+}
+
+#[streamable]
+pub struct PostPartialPayload {
+    launcher_id: Bytes32,
+    authentication_token: u64,
+    proof_of_space: ProofOfSpace,
+    sp_hash: Bytes32,
+    end_of_sub_slot: bool,
+    harvester_id: Bytes32,
+}
+
+#[streamable]
+pub struct PostPartialRequest {
+    payload: PostPartialPayload,
+    aggregate_signature: G2Element,
+}
+
+#[streamable]
+pub struct PostPartialResponse {
+    new_difficulty: u64,
+}
+
+#[streamable]
+pub struct GetFarmerResponse {
+    authentication_public_key: G1Element,
+    payout_instructions: String,
+    current_difficulty: u64,
+    current_points: u64,
+}
+
+#[streamable]
+pub struct PostFarmerPayload {
+    launcher_id: Bytes32,
+    authentication_token: u64,
+    authentication_public_key: G1Element,
+    payout_instructions: String,
+    suggested_difficulty: Option<u64>,
+}
+
+#[streamable]
+pub struct PostFarmerRequest {
+    payload: PostFarmerPayload,
+    signature: G2Element,
+}
+
+#[streamable]
+pub struct PostFarmerResponse {
+    welcome_message: String,
+}
+
+#[streamable]
+pub struct PutFarmerPayload {
+    launcher_id: Bytes32,
+    authentication_token: u64,
+    authentication_public_key: Option<G1Element>,
+    payout_instructions: Option<String>,
+    suggested_difficulty: Option<u64>,
+}
+
+#[streamable]
+pub struct PutFarmerRequest {
+    payload: PutFarmerPayload,
+    signature: G2Element,
+}
+
+#[streamable]
+pub struct PutFarmerResponse {
+    authentication_public_key: Option<bool>,
+    payout_instructions: Option<bool>,
+    suggested_difficulty: Option<bool>,
+}
+
+#[streamable]
+pub struct PoolErrorResponse {
+    error_code: u16,
+    error_message: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chia_traits::Streamable;
+
+    #[test]
+    fn test_error_code() {
+        for v in 1..=16 {
+            let code = PoolErrorCode::try_from(v).expect("valid code");
+            assert_eq!(u16::from(code), v);
+        }
+        assert_eq!(PoolErrorCode::try_from(0), Err(0));
+        assert_eq!(PoolErrorCode::try_from(17), Err(17));
+    }
+
+    #[test]
+    fn test_error_response() {
+        let resp = PoolErrorResponse::new(
+            PoolErrorCode::FarmerNotKnown.into(),
+            Some("farmer not known".to_string()),
+        );
+        let bytes = resp.to_bytes().unwrap();
+        assert_eq!(&bytes[..3], &[0, 10, 1]);
+        assert_eq!(PoolErrorResponse::from_bytes(&bytes).unwrap(), resp);
+    }
+}
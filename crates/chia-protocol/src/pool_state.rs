@@ -0,0 +1,75 @@
+use chia_bls::G1Element;
+use chia_streamable_macro::streamable;
+
+use crate::Bytes32;
+
+// The states of a plotNFT (pool singleton). The state field of PoolState holds
+// one of these values
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PoolSingletonState {
+    SelfPooling = 1,
+    LeavingPool = 2,
+    FarmingToPool = 3,
+}
+
+impl PoolSingletonState {
+    pub fn from_u8(v: u8) -> Option<Self> {
+        match v {
+            1 => Some(Self::SelfPooling),
+            2 => Some(Self::LeavingPool),
+            3 => Some(Self::FarmingToPool),
+            _ => None,
+        }
+    }
+}
+
+// The state of a plotNFT, as stored (serialized) in the key-value list of its
+// launcher solution and in the solutions of travel spends
+#[streamable]
+pub struct PoolState {
+    version: u8,
+    state: u8,
+    // the puzzle hash the pool rewards are paid to. When self pooling this is
+    // the owner's own puzzle hash
+    target_puzzle_hash: Bytes32,
+    owner_pubkey: G1Element,
+    pool_url: Option<String>,
+    relative_lock_height: u32,
+}
+
+impl PoolState {
+    pub fn singleton_state(&self) -> Option<PoolSingletonState> {
+        PoolSingletonState::from_u8(self.state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chia_traits::Streamable;
+
+    #[test]
+    fn test_pool_state_round_trip() {
+        let state = PoolState::new(
+            1,
+            PoolSingletonState::FarmingToPool as u8,
+            Bytes32::new([0x11; 32]),
+            G1Element::default(),
+            Some("https://pool.example.com".to_string()),
+            100,
+        );
+        let bytes = state.to_bytes().unwrap();
+        assert_eq!(PoolState::from_bytes(&bytes).unwrap(), state);
+        assert_eq!(
+            state.singleton_state(),
+            Some(PoolSingletonState::FarmingToPool)
+        );
+    }
+
+    #[test]
+    fn test_invalid_state() {
+        let state = PoolState::new(1, 4, Bytes32::default(), G1Element::default(), None, 0);
+        assert_eq!(state.singleton_state(), None);
+    }
+}
@@ -1,8 +1,11 @@
 use crate::bytes::Bytes;
 use chia_traits::chia_error::{Error, Result};
-use chia_traits::Streamable;
-use clvm_traits::{FromClvmError, FromNodePtr, ToClvmError, ToNodePtr};
-use clvmr::allocator::NodePtr;
+use chia_traits::{check_bytes_len, strict_parsing, Streamable};
+use clvm_traits::{
+    clvm_list, match_quote, FromClvmError, FromNodePtr, MatchByte, ToClvmError, ToNodePtr,
+};
+use clvm_utils::{canonical_serialized_length, CurriedProgram};
+use clvmr::allocator::{NodePtr, SExp};
 use clvmr::cost::Cost;
 use clvmr::reduction::EvalErr;
 use clvmr::run_program;
@@ -15,7 +18,11 @@ use sha2::{Digest, Sha256};
 use std::io::Cursor;
 use std::ops::Deref;
 
-#[cfg_attr(feature = "py-bindings", pyclass, derive(PyStreamable))]
+#[cfg_attr(
+    feature = "py-bindings",
+    pyclass(module = "gold_rs"),
+    derive(PyStreamable)
+)]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Program(Bytes);
 
@@ -73,6 +80,53 @@ impl Program {
         let reduction = run_program(a, &dialect, program, arg, max_cost)?;
         Ok((reduction.0, reduction.1))
     }
+
+    // returns the program with the specified arguments curried in. i.e.
+    // (a (q . <program>) (c (q . <arg1>) (c (q . <arg2>) ... 1)))
+    pub fn curry(
+        &self,
+        a: &mut Allocator,
+        args: &[NodePtr],
+    ) -> std::result::Result<Self, ToClvmError> {
+        let program = node_from_bytes_backrefs(a, self.0.as_ref())
+            .map_err(|error| ToClvmError::Custom(error.to_string()))?;
+        let mut curried_args = a.one();
+        for arg in args.iter().rev() {
+            curried_args =
+                clvm_list!(MatchByte::<4>, (MatchByte::<1>, *arg), curried_args).to_node_ptr(a)?;
+        }
+        let curried = CurriedProgram {
+            program,
+            args: curried_args,
+        }
+        .to_node_ptr(a)?;
+        Self::from_node_ptr(a, curried).map_err(|error| ToClvmError::Custom(error.to_string()))
+    }
+
+    // the inverse of curry(). Returns the inner program and the curried
+    // arguments, or None if this isn't a curried program
+    pub fn uncurry(
+        &self,
+        a: &mut Allocator,
+    ) -> std::result::Result<Option<(NodePtr, Vec<NodePtr>)>, FromClvmError> {
+        let prg = node_from_bytes_backrefs(a, self.0.as_ref())
+            .map_err(|error| FromClvmError::Custom(error.to_string()))?;
+        let Ok(uncurried) = CurriedProgram::<NodePtr, NodePtr>::from_node_ptr(a, prg) else {
+            return Ok(None);
+        };
+
+        let mut curried_args = Vec::<NodePtr>::new();
+        let mut args = uncurried.args;
+        while let SExp::Pair(..) = a.sexp(args) {
+            // the args of curried puzzles are in the form of:
+            // (c . ((q . <arg1>) . (<rest> . ())))
+            let (_, ((_, arg), (rest, _))) =
+                <(MatchByte<4>, (match_quote!(NodePtr), (NodePtr, ())))>::from_node_ptr(a, args)?;
+            curried_args.push(arg);
+            args = rest;
+        }
+        Ok(Some((uncurried.program, curried_args)))
+    }
 }
 
 impl From<Bytes> for Program {
@@ -156,10 +210,7 @@ use chia_py_streamable_macro::PyStreamable;
 use pyo3::prelude::*;
 
 #[cfg(feature = "py-bindings")]
-use pyo3::types::{PyList, PyTuple};
-
-#[cfg(feature = "py-bindings")]
-use clvmr::allocator::SExp;
+use pyo3::types::{PyBytes, PyList, PyTuple};
 
 #[cfg(feature = "py-bindings")]
 use pyo3::exceptions::*;
@@ -301,6 +352,49 @@ fn to_program(py: Python<'_>, node: LazyNode) -> PyResult<Bound<PyAny>> {
     ty.call1((node.into_py(py),))
 }
 
+// the returned nodes (and the ones reached from them, via first(), rest() and
+// pair()) all share the same allocator, so the program is only parsed once
+#[cfg(feature = "py-bindings")]
+impl Program {
+    fn to_lazy_node(&self) -> PyResult<LazyNode> {
+        use std::rc::Rc;
+        let mut a = Allocator::new_limited(500000000);
+        let node = node_from_bytes_backrefs(&mut a, self.0.as_ref())?;
+        Ok(LazyNode::new(Rc::new(a), node))
+    }
+}
+
+#[cfg(feature = "py-bindings")]
+fn to_python_objects(py: Python<'_>, a: &Allocator, node: NodePtr) -> PyObject {
+    enum Op {
+        Node(NodePtr),
+        Pair,
+    }
+
+    let mut values = Vec::<PyObject>::new();
+    let mut ops = vec![Op::Node(node)];
+    while let Some(op) = ops.pop() {
+        match op {
+            Op::Node(node) => match a.sexp(node) {
+                SExp::Atom => {
+                    values.push(PyBytes::new_bound(py, a.atom(node).as_ref()).into_py(py));
+                }
+                SExp::Pair(first, rest) => {
+                    ops.push(Op::Pair);
+                    ops.push(Op::Node(rest));
+                    ops.push(Op::Node(first));
+                }
+            },
+            Op::Pair => {
+                let rest = values.pop().unwrap();
+                let first = values.pop().unwrap();
+                values.push(PyTuple::new_bound(py, [first, rest]).into_py(py));
+            }
+        }
+    }
+    values.pop().unwrap()
+}
+
 #[cfg(feature = "py-bindings")]
 #[pymethods]
 impl Program {
@@ -319,10 +413,15 @@ impl Program {
             .map_err(|error| PyErr::new::<PyTypeError, _>(error.to_string()))
     }
 
+    // programs rarely use back-references, so we first attempt to hash the
+    // serialized form directly, without parsing it
     fn get_tree_hash(&self) -> crate::Bytes32 {
-        clvm_utils::tree_hash_from_bytes(self.0.as_ref())
-            .unwrap()
-            .into()
+        let buf = self.0.as_ref();
+        let hash = match clvm_utils::tree_hash_serialized(buf) {
+            Ok(Some(hash)) => hash,
+            _ => clvm_utils::tree_hash_from_bytes(buf).unwrap(),
+        };
+        hash.into()
     }
 
     #[staticmethod]
@@ -401,6 +500,75 @@ impl Program {
         }
     }
 
+    #[pyo3(name = "run", signature = (args, max_cost, flags = 0))]
+    fn py_run(
+        &self,
+        py: Python<'_>,
+        args: &Bound<PyAny>,
+        max_cost: u64,
+        flags: u32,
+    ) -> PyResult<(u64, Program)> {
+        let mut a = Allocator::new_limited(500000000);
+        let clvm_args = clvm_serialize(&mut a, args)?;
+        let program = node_from_bytes_backrefs(&mut a, self.0.as_ref())?;
+        let dialect = ChiaDialect::new(flags);
+
+        let r = py.allow_threads(|| run_program(&mut a, &dialect, program, clvm_args, max_cost));
+        match r {
+            Ok(reduction) => Ok((
+                reduction.0,
+                Program::from_node_ptr(&a, reduction.1)
+                    .map_err(|error| PyErr::new::<PyValueError, _>(error.to_string()))?,
+            )),
+            Err(eval_err) => {
+                let blob = node_to_bytes(&a, eval_err.0).ok().map(hex::encode);
+                Err(PyValueError::new_err((eval_err.1, blob)))
+            }
+        }
+    }
+
+    // converts the CLVM structure into python objects. Atoms become bytes and
+    // pairs become tuples of two elements
+    #[pyo3(name = "to_python")]
+    fn py_to_python(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let mut a = Allocator::new_limited(500000000);
+        let node = node_from_bytes_backrefs(&mut a, self.0.as_ref())?;
+        Ok(to_python_objects(py, &a, node))
+    }
+
+    // the inverse of to_python(). Also accepts ints, strings and lists, just
+    // like to()
+    #[staticmethod]
+    #[pyo3(name = "from_python")]
+    fn py_from_python(o: &Bound<PyAny>) -> PyResult<Program> {
+        Self::py_to(o)
+    }
+
+    #[pyo3(name = "first")]
+    fn py_first(&self) -> PyResult<LazyNode> {
+        self.to_lazy_node()?.first()
+    }
+
+    #[pyo3(name = "rest")]
+    fn py_rest(&self) -> PyResult<LazyNode> {
+        self.to_lazy_node()?.rest()
+    }
+
+    #[pyo3(name = "pair")]
+    fn py_pair(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        self.to_lazy_node()?.pair(py)
+    }
+
+    #[pyo3(name = "as_int")]
+    fn py_as_int(&self, py: Python<'_>) -> PyResult<PyObject> {
+        self.to_lazy_node()?.as_int(py)
+    }
+
+    #[pyo3(name = "as_atom_list")]
+    fn py_as_atom_list(&self, py: Python<'_>) -> PyResult<Vec<PyObject>> {
+        Ok(self.to_lazy_node()?.as_atom_list(py))
+    }
+
     fn to_program<'a>(&self, py: Python<'a>) -> PyResult<Bound<'a, PyAny>> {
         use std::rc::Rc;
         let mut a = Allocator::new_limited(500000000);
@@ -409,13 +577,27 @@ impl Program {
         to_program(py, prg)
     }
 
-    fn uncurry<'a>(&self, py: Python<'a>) -> PyResult<(Bound<'a, PyAny>, Bound<'a, PyAny>)> {
-        use clvm_utils::CurriedProgram;
+    #[pyo3(name = "curry", signature = (*args))]
+    fn py_curry(&self, args: &Bound<PyTuple>) -> PyResult<Program> {
+        let mut a = Allocator::new_limited(500000000);
+        let mut clvm_args = Vec::<NodePtr>::with_capacity(args.len());
+        for arg in args.iter() {
+            clvm_args.push(clvm_serialize(&mut a, &arg)?);
+        }
+        self.curry(&mut a, &clvm_args)
+            .map_err(|error| PyErr::new::<PyTypeError, _>(error.to_string()))
+    }
+
+    #[pyo3(name = "uncurry")]
+    fn py_uncurry<'a>(&self, py: Python<'a>) -> PyResult<(Bound<'a, PyAny>, Bound<'a, PyAny>)> {
         use std::rc::Rc;
 
         let mut a = Allocator::new_limited(500000000);
-        let prg = node_from_bytes_backrefs(&mut a, self.0.as_ref())?;
-        let Ok(uncurried) = CurriedProgram::<NodePtr, NodePtr>::from_node_ptr(&a, prg) else {
+        let uncurried = self
+            .uncurry(&mut a)
+            .map_err(|error| PyErr::new::<PyTypeError, _>(error.to_string()))?;
+        let Some((program, curried_args)) = uncurried else {
+            let prg = node_from_bytes_backrefs(&mut a, self.0.as_ref())?;
             let a = Rc::new(a);
             let prg = LazyNode::new(a.clone(), prg);
             let ret = a.nil();
@@ -423,29 +605,12 @@ impl Program {
             return Ok((to_program(py, prg)?, to_program(py, ret)?));
         };
 
-        let mut curried_args = Vec::<NodePtr>::new();
-        let mut args = uncurried.args;
-        loop {
-            if let SExp::Atom = a.sexp(args) {
-                break;
-            }
-            // the args of curried puzzles are in the form of:
-            // (c . ((q . <arg1>) . (<rest> . ())))
-            let (_, ((_, arg), (rest, _))) =
-                <(
-                    clvm_traits::MatchByte<4>,
-                    (clvm_traits::match_quote!(NodePtr), (NodePtr, ())),
-                ) as clvm_traits::FromNodePtr>::from_node_ptr(&a, args)
-                .map_err(|error| PyErr::new::<PyTypeError, _>(error.to_string()))?;
-            curried_args.push(arg);
-            args = rest;
-        }
         let mut ret = a.nil();
         for item in curried_args.into_iter().rev() {
             ret = a.new_pair(item, ret).map_err(|_e| Error::EndOfBuffer)?;
         }
         let a = Rc::new(a);
-        let prg = LazyNode::new(a.clone(), uncurried.program);
+        let prg = LazyNode::new(a.clone(), program);
         let ret = LazyNode::new(a, ret);
         Ok((to_program(py, prg)?, to_program(py, ret)?))
     }
@@ -464,7 +629,12 @@ impl Streamable for Program {
     fn parse<const TRUSTED: bool>(input: &mut Cursor<&[u8]>) -> Result<Self> {
         let pos = input.position();
         let buf: &[u8] = &input.get_ref()[pos as usize..];
-        let len = if TRUSTED {
+        // back-references are allowed, since some programs (like
+        // transactions generators) may use them
+        let len = if strict_parsing() {
+            canonical_serialized_length(buf, true)
+                .map_err(|e| Error::NonCanonical(format!("CLVM {e}")))?
+        } else if TRUSTED {
             serialized_length_from_bytes_trusted(buf).map_err(|_e| Error::EndOfBuffer)?
         } else {
             serialized_length_from_bytes(buf).map_err(|_e| Error::EndOfBuffer)?
@@ -472,10 +642,41 @@ impl Streamable for Program {
         if buf.len() < len as usize {
             return Err(Error::EndOfBuffer);
         }
+        check_bytes_len(u32::try_from(len).map_err(|_| Error::SequenceTooLarge)?)?;
         let program = buf[..len as usize].to_vec();
         input.set_position(pos + len);
         Ok(Program(program.into()))
     }
+
+    fn heap_size(&self) -> usize {
+        self.0.heap_size()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Program {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&self.0, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Program {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        use serde::de::Error as _;
+        let bytes = <Bytes as serde::Deserialize>::deserialize(deserializer)?;
+        let len = serialized_length_from_bytes(bytes.as_slice())
+            .map_err(|_| D::Error::custom("invalid CLVM serialization"))?;
+        if len as usize != bytes.len() {
+            return Err(D::Error::custom("invalid length"));
+        }
+        Ok(Self(bytes))
+    }
 }
 
 #[cfg(feature = "py-bindings")]
@@ -545,4 +746,62 @@ mod tests {
         assert_eq!(cost, 869);
         assert_eq!(a.number(result), 1337.into());
     }
+
+    #[test]
+    fn program_curry() {
+        let a = &mut Allocator::new();
+        let prg = Program::from(vec![0x01]);
+        let arg1 = a.new_number(42.into()).unwrap();
+        let arg2 = a.new_number(75.into()).unwrap();
+
+        // (a (q . 1) (c (q . 42) (c (q . 75) 1)))
+        let curried = prg.curry(a, &[arg1, arg2]).expect("curry");
+        assert_eq!(
+            hex::encode(&curried),
+            "ff02ffff0101ffff04ffff012affff04ffff014bff01808080"
+        );
+
+        let (inner, args) = curried.uncurry(a).expect("uncurry").expect("curried");
+        assert_eq!(node_to_bytes(a, inner).unwrap(), [0x01]);
+        assert_eq!(args.len(), 2);
+        assert_eq!(a.number(args[0]), 42.into());
+        assert_eq!(a.number(args[1]), 75.into());
+
+        // no arguments
+        let curried = prg.curry(a, &[]).expect("curry");
+        let (_, args) = curried.uncurry(a).expect("uncurry").expect("curried");
+        assert!(args.is_empty());
+
+        // not a curried program
+        assert_eq!(prg.uncurry(a).expect("uncurry"), None);
+    }
+
+    #[test]
+    fn program_strict() {
+        // (q . 1), with the 1 encoded with a length prefix
+        let non_canonical = hex::decode("ff018101").unwrap();
+        assert_eq!(
+            Program::from_bytes(&non_canonical).unwrap(),
+            Program::from(non_canonical.clone())
+        );
+        assert_eq!(
+            Program::from_bytes_strict(&non_canonical),
+            Err(Error::NonCanonical(
+                "CLVM small_atom_with_prefix at offset 2".to_string()
+            ))
+        );
+        assert_eq!(
+            Program::from_bytes_strict(&[0xff, 0x01, 0x01]).unwrap(),
+            Program::from(vec![0xff, 0x01, 0x01])
+        );
+
+        // a Program inside another streamable type is checked too
+        let mut buf = vec![0, 0, 0, 1];
+        buf.extend_from_slice(&non_canonical);
+        assert!(Vec::<Program>::from_bytes(&buf).is_ok());
+        assert!(matches!(
+            Vec::<Program>::from_bytes_strict(&buf),
+            Err(Error::NonCanonical(_))
+        ));
+    }
 }
@@ -0,0 +1,209 @@
+use chia_traits::chia_error::{Error, Result};
+use chia_traits::Streamable;
+
+use crate::*;
+
+// Maps every message type to the type of its payload. Message types that
+// share a payload type (like the PlotSync* path lists) each get their own
+// variant, so the message type can always be recovered from the object.
+macro_rules! protocol_objects {
+    ($($variant:ident => $ty:ident,)*) => {
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum ProtocolObject {
+            $($variant($ty),)*
+        }
+
+        // parses the payload of a message with the specified type. Message types
+        // without a payload (or that aren't supported) fail with Error::Custom
+        pub fn parse_message(msg_type: ProtocolMessageTypes, data: &[u8]) -> Result<ProtocolObject> {
+            match msg_type {
+                $(ProtocolMessageTypes::$variant => Ok(ProtocolObject::$variant($ty::from_bytes(data)?)),)*
+                _ => Err(Error::Custom(format!(
+                    "no payload type for message type {msg_type:?}"
+                ))),
+            }
+        }
+
+        impl ProtocolObject {
+            pub fn msg_type(&self) -> ProtocolMessageTypes {
+                match self {
+                    $(Self::$variant(_) => ProtocolMessageTypes::$variant,)*
+                }
+            }
+
+            pub fn to_bytes(&self) -> Result<Vec<u8>> {
+                match self {
+                    $(Self::$variant(v) => v.to_bytes(),)*
+                }
+            }
+
+            #[cfg(feature = "py-bindings")]
+            pub fn into_python(self, py: pyo3::Python<'_>) -> pyo3::PyObject {
+                match self {
+                    $(Self::$variant(v) => pyo3::IntoPy::into_py(v, py),)*
+                }
+            }
+        }
+    };
+}
+
+protocol_objects! {
+    Handshake => Handshake,
+
+    // harvester protocol
+    HarvesterHandshake => HarvesterHandshake,
+    NewProofOfSpace => NewProofOfSpace,
+    RequestSignatures => RequestSignatures,
+    RespondSignatures => RespondSignatures,
+    NewSignagePointHarvester => NewSignagePointHarvester,
+    RequestPlots => RequestPlots,
+    RespondPlots => RespondPlots,
+    PlotSyncStart => PlotSyncStart,
+    PlotSyncLoaded => PlotSyncPlotList,
+    PlotSyncRemoved => PlotSyncPathList,
+    PlotSyncInvalid => PlotSyncPathList,
+    PlotSyncKeysMissing => PlotSyncPathList,
+    PlotSyncDuplicates => PlotSyncPathList,
+    PlotSyncDone => PlotSyncDone,
+    PlotSyncResponse => PlotSyncResponse,
+
+    // farmer protocol
+    NewSignagePoint => NewSignagePoint,
+    DeclareProofOfSpace => DeclareProofOfSpace,
+    RequestSignedValues => RequestSignedValues,
+    SignedValues => SignedValues,
+    FarmingInfo => FarmingInfo,
+
+    // timelord protocol
+    NewPeakTimelord => NewPeakTimelord,
+    NewUnfinishedBlockTimelord => NewUnfinishedBlockTimelord,
+    NewInfusionPointVdf => NewInfusionPointVDF,
+    NewSignagePointVdf => NewSignagePointVDF,
+    NewEndOfSubSlotVdf => NewEndOfSubSlotVDF,
+    RequestCompactProofOfTime => RequestCompactProofOfTime,
+    RespondCompactProofOfTime => RespondCompactProofOfTime,
+
+    // full node protocol
+    NewPeak => NewPeak,
+    NewTransaction => NewTransaction,
+    RequestTransaction => RequestTransaction,
+    RespondTransaction => RespondTransaction,
+    RequestProofOfWeight => RequestProofOfWeight,
+    RespondProofOfWeight => RespondProofOfWeight,
+    RequestBlock => RequestBlock,
+    RespondBlock => RespondBlock,
+    RejectBlock => RejectBlock,
+    RequestBlocks => RequestBlocks,
+    RespondBlocks => RespondBlocks,
+    RejectBlocks => RejectBlocks,
+    NewUnfinishedBlock => NewUnfinishedBlock,
+    RequestUnfinishedBlock => RequestUnfinishedBlock,
+    RespondUnfinishedBlock => RespondUnfinishedBlock,
+    NewSignagePointOrEndOfSubSlot => NewSignagePointOrEndOfSubSlot,
+    RequestSignagePointOrEndOfSubSlot => RequestSignagePointOrEndOfSubSlot,
+    RespondSignagePoint => RespondSignagePoint,
+    RespondEndOfSubSlot => RespondEndOfSubSlot,
+    RequestMempoolTransactions => RequestMempoolTransactions,
+    RequestCompactVDF => RequestCompactVDF,
+    RespondCompactVDF => RespondCompactVDF,
+    NewCompactVDF => NewCompactVDF,
+    RequestPeers => RequestPeers,
+    RespondPeers => RespondPeers,
+    NewUnfinishedBlock2 => NewUnfinishedBlock2,
+    RequestUnfinishedBlock2 => RequestUnfinishedBlock2,
+
+    // wallet protocol
+    RequestPuzzleSolution => RequestPuzzleSolution,
+    RespondPuzzleSolution => RespondPuzzleSolution,
+    RejectPuzzleSolution => RejectPuzzleSolution,
+    SendTransaction => SendTransaction,
+    TransactionAck => TransactionAck,
+    NewPeakWallet => NewPeakWallet,
+    RequestBlockHeader => RequestBlockHeader,
+    RespondBlockHeader => RespondBlockHeader,
+    RejectHeaderRequest => RejectHeaderRequest,
+    RequestRemovals => RequestRemovals,
+    RespondRemovals => RespondRemovals,
+    RejectRemovalsRequest => RejectRemovalsRequest,
+    RequestAdditions => RequestAdditions,
+    RespondAdditions => RespondAdditions,
+    RejectAdditionsRequest => RejectAdditionsRequest,
+    RequestHeaderBlocks => RequestHeaderBlocks,
+    RejectHeaderBlocks => RejectHeaderBlocks,
+    RespondHeaderBlocks => RespondHeaderBlocks,
+    CoinStateUpdate => CoinStateUpdate,
+    RegisterForPhUpdates => RegisterForPhUpdates,
+    RespondToPhUpdates => RespondToPhUpdates,
+    RegisterForCoinUpdates => RegisterForCoinUpdates,
+    RespondToCoinUpdates => RespondToCoinUpdates,
+    RequestChildren => RequestChildren,
+    RespondChildren => RespondChildren,
+    RequestSesInfo => RequestSesInfo,
+    RespondSesInfo => RespondSesInfo,
+    RequestBlockHeaders => RequestBlockHeaders,
+    RejectBlockHeaders => RejectBlockHeaders,
+    RespondBlockHeaders => RespondBlockHeaders,
+    RequestFeeEstimates => RequestFeeEstimates,
+    RespondFeeEstimates => RespondFeeEstimates,
+    RequestRemovePuzzleSubscriptions => RequestRemovePuzzleSubscriptions,
+    RespondRemovePuzzleSubscriptions => RespondRemovePuzzleSubscriptions,
+    RequestRemoveCoinSubscriptions => RequestRemoveCoinSubscriptions,
+    RespondRemoveCoinSubscriptions => RespondRemoveCoinSubscriptions,
+    RequestPuzzleState => RequestPuzzleState,
+    RespondPuzzleState => RespondPuzzleState,
+    RejectPuzzleState => RejectPuzzleState,
+    RequestCoinState => RequestCoinState,
+    RespondCoinState => RespondCoinState,
+    RejectCoinState => RejectCoinState,
+
+    // introducer protocol
+    RequestPeersIntroducer => RequestPeersIntroducer,
+    RespondPeersIntroducer => RespondPeersIntroducer,
+}
+
+impl ProtocolObject {
+    // parses the payload of a Message
+    pub fn from_message(msg: &Message) -> Result<Self> {
+        parse_message(msg.msg_type, msg.data.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_message() {
+        let msg = NewPeakWallet::new(Bytes32::from([1; 32]), 10, 20, 5);
+        let bytes = msg.to_bytes().unwrap();
+        let obj = parse_message(ProtocolMessageTypes::NewPeakWallet, &bytes).unwrap();
+        assert_eq!(obj, ProtocolObject::NewPeakWallet(msg));
+        assert_eq!(obj.msg_type(), ProtocolMessageTypes::NewPeakWallet);
+        assert_eq!(obj.to_bytes().unwrap(), bytes);
+
+        let wrapped = Message::new(ProtocolMessageTypes::NewPeakWallet, None, bytes.into());
+        assert_eq!(ProtocolObject::from_message(&wrapped).unwrap(), obj);
+    }
+
+    #[test]
+    fn test_shared_payload_type() {
+        let list = PlotSyncPathList::new(PlotSyncIdentifier::new(1, 2, 3), vec![], true);
+        let bytes = list.to_bytes().unwrap();
+        let obj = parse_message(ProtocolMessageTypes::PlotSyncInvalid, &bytes).unwrap();
+        assert_eq!(obj.msg_type(), ProtocolMessageTypes::PlotSyncInvalid);
+        assert_eq!(obj, ProtocolObject::PlotSyncInvalid(list));
+    }
+
+    #[test]
+    fn test_invalid_message() {
+        // trailing garbage
+        assert_eq!(
+            parse_message(ProtocolMessageTypes::RequestPeers, &[0]).unwrap_err(),
+            Error::InputTooLarge
+        );
+        assert!(matches!(
+            parse_message(ProtocolMessageTypes::NoneResponse, &[]).unwrap_err(),
+            Error::Custom(_)
+        ));
+    }
+}
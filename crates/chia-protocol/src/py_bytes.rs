@@ -0,0 +1,170 @@
+use crate::bytes::{decode_hexstr, BytesImpl};
+use pyo3::basic::CompareOp;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyString};
+
+// Fixed size byte strings, exposed to python as their own classes. The size
+// is validated when an object is constructed, so a function taking a Bytes48
+// never has to check the length. They compare equal to (and hash the same as)
+// bytes objects with the same contents.
+macro_rules! sized_bytes {
+    ($name:ident, $py_name:literal, $size:literal) => {
+        #[pyclass(name = $py_name, module = "gold_rs", frozen)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub struct $name(pub BytesImpl<$size>);
+
+        impl From<BytesImpl<$size>> for $name {
+            fn from(value: BytesImpl<$size>) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for BytesImpl<$size> {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        #[pymethods]
+        impl $name {
+            #[classattr]
+            const SIZE: usize = $size;
+
+            // a str is parsed as hex, the same as fromhex()
+            #[new]
+            fn py_new(blob: &Bound<'_, PyAny>) -> PyResult<Self> {
+                if let Ok(s) = blob.downcast::<PyString>() {
+                    return Self::fromhex(s.to_str()?);
+                }
+                Self::from_bytes(byte_slice(blob)?)
+            }
+
+            #[staticmethod]
+            pub fn from_bytes(blob: &[u8]) -> PyResult<Self> {
+                let buf: [u8; $size] = blob.try_into().map_err(|_| {
+                    PyValueError::new_err(format!(
+                        "{} must be {} bytes, got {}",
+                        $py_name,
+                        $size,
+                        blob.len()
+                    ))
+                })?;
+                Ok(Self(buf.into()))
+            }
+
+            #[staticmethod]
+            pub fn fromhex(h: &str) -> PyResult<Self> {
+                let blob = decode_hexstr(h).map_err(|e| PyValueError::new_err(e.to_string()))?;
+                Self::from_bytes(&blob)
+            }
+
+            // parses a bech32m address, like "xch1...", validating its
+            // checksum and prefix
+            #[staticmethod]
+            pub fn from_address(address: &str, prefix: &str) -> PyResult<Self> {
+                BytesImpl::from_address(address, prefix)
+                    .map(Self)
+                    .map_err(|e| PyValueError::new_err(e.to_string()))
+            }
+
+            #[staticmethod]
+            pub fn zeros() -> Self {
+                Self(BytesImpl::zeros())
+            }
+
+            #[staticmethod]
+            pub fn random(seed: u64) -> Self {
+                Self(BytesImpl::random(seed))
+            }
+
+            pub fn hex(&self) -> String {
+                hex::encode(self.0)
+            }
+
+            pub fn to_bytes<'p>(&self, py: Python<'p>) -> Bound<'p, PyBytes> {
+                PyBytes::new_bound(py, &self.0)
+            }
+
+            fn __bytes__<'p>(&self, py: Python<'p>) -> Bound<'p, PyBytes> {
+                PyBytes::new_bound(py, &self.0)
+            }
+
+            fn __len__(&self) -> usize {
+                $size
+            }
+
+            // concatenation produces a plain bytes object, since the result
+            // no longer has the fixed size
+            fn __add__<'p>(
+                &self,
+                py: Python<'p>,
+                other: &Bound<'_, PyAny>,
+            ) -> PyResult<Bound<'p, PyBytes>> {
+                Ok(PyBytes::new_bound(
+                    py,
+                    &[self.0.as_slice(), byte_slice(other)?].concat(),
+                ))
+            }
+
+            fn __radd__<'p>(
+                &self,
+                py: Python<'p>,
+                other: &Bound<'_, PyAny>,
+            ) -> PyResult<Bound<'p, PyBytes>> {
+                Ok(PyBytes::new_bound(
+                    py,
+                    &[byte_slice(other)?, self.0.as_slice()].concat(),
+                ))
+            }
+
+            fn __richcmp__(
+                &self,
+                py: Python<'_>,
+                other: &Bound<'_, PyAny>,
+                op: CompareOp,
+            ) -> PyObject {
+                match byte_slice(other) {
+                    Ok(other) => op.matches(self.0.as_slice().cmp(other)).into_py(py),
+                    Err(_) => py.NotImplemented(),
+                }
+            }
+
+            // must match the hash of the equivalent bytes object
+            fn __hash__(&self, py: Python<'_>) -> PyResult<isize> {
+                PyBytes::new_bound(py, &self.0).hash()
+            }
+
+            fn __str__(&self) -> String {
+                hex::encode(self.0)
+            }
+
+            fn __repr__(&self) -> String {
+                format!("<{}: {}>", $py_name, hex::encode(self.0))
+            }
+        }
+    };
+}
+
+sized_bytes!(PyBytes48, "Bytes48", 48);
+sized_bytes!(PyBytes96, "Bytes96", 96);
+sized_bytes!(PyBytes100, "Bytes100", 100);
+
+// the contents of a bytes object (including bytes32 and the other python
+// sized bytes subclasses) or of one of the fixed size byte classes
+pub fn byte_slice<'a>(obj: &'a Bound<'_, PyAny>) -> PyResult<&'a [u8]> {
+    if let Ok(b) = obj.downcast::<PyBytes>() {
+        Ok(b.as_bytes())
+    } else if let Ok(b) = obj.downcast::<PyBytes48>() {
+        Ok(b.get().0.as_slice())
+    } else if let Ok(b) = obj.downcast::<PyBytes96>() {
+        Ok(b.get().0.as_slice())
+    } else if let Ok(b) = obj.downcast::<PyBytes100>() {
+        Ok(b.get().0.as_slice())
+    } else {
+        Err(PyTypeError::new_err(format!(
+            "expected bytes, got {}",
+            obj.get_type().name()?
+        )))
+    }
+}
@@ -0,0 +1,236 @@
+use crate::bytes::random_bytes;
+use arbitrary::{Arbitrary, Unstructured};
+
+// the number of random bytes the objects are generated from. This bounds the
+// size of the generated objects
+const RANDOM_DATA_SIZE: usize = 64 * 1024;
+
+// Generates a deterministic, pseudo-random instance of T from the seed. The
+// same seed always produces the same object. The objects are structurally
+// valid (i.e. they round-trip through serialization) but are not expected to
+// be valid in any consensus sense.
+pub fn generate_random<T: for<'a> Arbitrary<'a>>(seed: u64) -> T {
+    let data = random_bytes(seed, RANDOM_DATA_SIZE);
+    let mut u = Unstructured::new(&data);
+    T::arbitrary(&mut u).expect("generate_random")
+}
+
+#[cfg(feature = "py-bindings")]
+macro_rules! random_instance_impl {
+    ($($ty:ident),* $(,)?) => {
+        // returns a random instance of the streamable class with the specified
+        // name. See generate_random()
+        pub fn random_instance(
+            py: pyo3::Python<'_>,
+            name: &str,
+            seed: u64,
+        ) -> pyo3::PyResult<pyo3::PyObject> {
+            match name {
+                $(stringify!($ty) => Ok(pyo3::IntoPy::into_py(
+                    generate_random::<crate::$ty>(seed),
+                    py,
+                )),)*
+                _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "unknown type: {name}"
+                ))),
+            }
+        }
+    };
+}
+
+#[cfg(feature = "py-bindings")]
+random_instance_impl!(
+    BlockRecord,
+    Message,
+    Handshake,
+    ClassgroupElement,
+    Coin,
+    CoinSpend,
+    CoinState,
+    EndOfSubSlotBundle,
+    NewSignagePoint,
+    DeclareProofOfSpace,
+    RequestSignedValues,
+    FarmingInfo,
+    SignedValues,
+    FeeRate,
+    FeeEstimate,
+    FeeEstimateGroup,
+    TransactionsInfo,
+    FoliageTransactionBlock,
+    FoliageBlockData,
+    Foliage,
+    NewPeak,
+    NewTransaction,
+    RequestTransaction,
+    RespondTransaction,
+    RequestProofOfWeight,
+    RespondProofOfWeight,
+    RequestBlock,
+    RejectBlock,
+    RequestBlocks,
+    RespondBlocks,
+    RejectBlocks,
+    RespondBlock,
+    NewUnfinishedBlock,
+    RequestUnfinishedBlock,
+    RespondUnfinishedBlock,
+    NewSignagePointOrEndOfSubSlot,
+    RequestSignagePointOrEndOfSubSlot,
+    RespondSignagePoint,
+    RespondEndOfSubSlot,
+    RequestMempoolTransactions,
+    NewCompactVDF,
+    RequestCompactVDF,
+    RespondCompactVDF,
+    RequestPeers,
+    RespondPeers,
+    NewUnfinishedBlock2,
+    RequestUnfinishedBlock2,
+    FullBlock,
+    PoolDifficulty,
+    HarvesterHandshake,
+    NewSignagePointHarvester,
+    ProofOfSpaceFeeInfo,
+    NewProofOfSpace,
+    SignatureRequestSourceData,
+    RequestSignatures,
+    RespondSignatures,
+    Plot,
+    RequestPlots,
+    RespondPlots,
+    PlotSyncIdentifier,
+    PlotSyncStart,
+    PlotSyncPathList,
+    PlotSyncPlotList,
+    PlotSyncDone,
+    PlotSyncError,
+    PlotSyncResponse,
+    HeaderBlock,
+    RequestPeersIntroducer,
+    RespondPeersIntroducer,
+    TimestampedPeerInfo,
+    AuthenticationPayload,
+    GetPoolInfoResponse,
+    PostPartialPayload,
+    PostPartialRequest,
+    PostPartialResponse,
+    GetFarmerResponse,
+    PostFarmerPayload,
+    PostFarmerRequest,
+    PostFarmerResponse,
+    PutFarmerPayload,
+    PutFarmerRequest,
+    PutFarmerResponse,
+    PoolErrorResponse,
+    PoolTarget,
+    Program,
+    ProofOfSpace,
+    RewardChainBlockUnfinished,
+    RewardChainBlock,
+    ChallengeBlockInfo,
+    ChallengeChainSubSlot,
+    InfusedChallengeChainSubSlot,
+    RewardChainSubSlot,
+    SubSlotProofs,
+    SpendBundle,
+    SubEpochSummary,
+    NewPeakTimelord,
+    NewUnfinishedBlockTimelord,
+    NewInfusionPointVDF,
+    NewSignagePointVDF,
+    NewEndOfSubSlotVDF,
+    RequestCompactProofOfTime,
+    RespondCompactProofOfTime,
+    UnfinishedBlock,
+    UnfinishedHeaderBlock,
+    VDFInfo,
+    VDFProof,
+    RequestPuzzleSolution,
+    PuzzleSolutionResponse,
+    RespondPuzzleSolution,
+    RejectPuzzleSolution,
+    SendTransaction,
+    TransactionAck,
+    NewPeakWallet,
+    RequestBlockHeader,
+    RespondBlockHeader,
+    RejectHeaderRequest,
+    RequestRemovals,
+    RespondRemovals,
+    RejectRemovalsRequest,
+    RequestAdditions,
+    RespondAdditions,
+    RejectAdditionsRequest,
+    RespondBlockHeaders,
+    RejectBlockHeaders,
+    RequestBlockHeaders,
+    RequestHeaderBlocks,
+    RejectHeaderBlocks,
+    RespondHeaderBlocks,
+    RegisterForPhUpdates,
+    RespondToPhUpdates,
+    RegisterForCoinUpdates,
+    RespondToCoinUpdates,
+    CoinStateUpdate,
+    RequestChildren,
+    RespondChildren,
+    RequestSesInfo,
+    RespondSesInfo,
+    RequestFeeEstimates,
+    RespondFeeEstimates,
+    RequestRemovePuzzleSubscriptions,
+    RespondRemovePuzzleSubscriptions,
+    RequestRemoveCoinSubscriptions,
+    RespondRemoveCoinSubscriptions,
+    CoinStateFilters,
+    RequestPuzzleState,
+    RespondPuzzleState,
+    RejectPuzzleState,
+    RequestCoinState,
+    RespondCoinState,
+    RejectCoinState,
+    SubEpochData,
+    SubSlotData,
+    SubEpochChallengeSegment,
+    SubEpochSegments,
+    RecentChainData,
+    ProofBlockHeader,
+    WeightProof
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Coin, FullBlock, SpendBundle};
+    use chia_traits::Streamable;
+
+    #[test]
+    fn test_deterministic() {
+        assert_eq!(
+            generate_random::<FullBlock>(1337),
+            generate_random::<FullBlock>(1337)
+        );
+        assert_eq!(
+            generate_random::<SpendBundle>(42),
+            generate_random::<SpendBundle>(42)
+        );
+        assert_ne!(generate_random::<Coin>(1), generate_random::<Coin>(2));
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for seed in 0..10 {
+            let block = generate_random::<FullBlock>(seed);
+            let bytes = block.to_bytes().unwrap();
+            assert_eq!(FullBlock::from_bytes(&bytes).unwrap(), block);
+        }
+    }
+
+    #[test]
+    fn test_random_bytes() {
+        assert_eq!(random_bytes(0, 3).len(), 3);
+        assert_eq!(random_bytes(7, 100)[..50], random_bytes(7, 50)[..]);
+        assert_ne!(random_bytes(7, 32), random_bytes(8, 32));
+    }
+}
@@ -0,0 +1,389 @@
+use crate::chia_protocol::{Message, ProtocolMessageTypes};
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "py-bindings")]
+use pyo3::prelude::*;
+
+const KB: u32 = 1024;
+const MB: u32 = 1024 * 1024;
+
+// the limits of a single message type, within one rate limit period
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    // the max number of messages
+    pub frequency: u32,
+    // the max size of a single message
+    pub max_size: u32,
+    // the max size of all messages. Defaults to frequency * max_size
+    pub max_total_size: Option<u64>,
+}
+
+impl RateLimit {
+    const fn new(frequency: u32, max_size: u32) -> Self {
+        Self {
+            frequency,
+            max_size,
+            max_total_size: None,
+        }
+    }
+
+    const fn with_total(frequency: u32, max_size: u32, max_total_size: u64) -> Self {
+        Self {
+            frequency,
+            max_size,
+            max_total_size: Some(max_total_size),
+        }
+    }
+
+    pub fn total_size(&self) -> u64 {
+        self.max_total_size
+            .unwrap_or(self.frequency as u64 * self.max_size as u64)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitKind {
+    // transaction messages only have their own limits
+    Transaction,
+    // all other messages also count towards the shared non-transaction limits
+    Other,
+    // messages without limits of their own use DEFAULT_RATE_LIMIT
+    Default,
+}
+
+pub const DEFAULT_RATE_LIMIT: RateLimit = RateLimit::with_total(100, MB, 100 * MB as u64);
+
+// the limits on all non-transaction messages combined
+pub const NON_TX_FREQUENCY: u32 = 1000;
+pub const NON_TX_MAX_TOTAL_SIZE: u64 = 100 * MB as u64;
+
+// the length of a rate limit period, in seconds
+pub const RATE_LIMIT_PERIOD: u64 = 60;
+
+// the rate limits of each message type
+pub fn rate_limit(msg_type: ProtocolMessageTypes) -> (RateLimitKind, RateLimit) {
+    use ProtocolMessageTypes as T;
+    use RateLimitKind::{Other, Transaction};
+
+    match msg_type {
+        T::NewTransaction | T::RequestTransaction => {
+            (Transaction, RateLimit::with_total(5000, 100, 5000 * 100))
+        }
+        T::RespondTransaction => (Transaction, RateLimit::with_total(5000, MB, 20 * MB as u64)),
+        T::SendTransaction => (Transaction, RateLimit::new(5000, MB)),
+        T::TransactionAck => (Transaction, RateLimit::new(5000, 2048)),
+
+        T::Handshake => (Other, RateLimit::with_total(5, 10 * KB, 5 * 10 * KB as u64)),
+        T::HarvesterHandshake => (Other, RateLimit::new(5, MB)),
+        T::NewSignagePointHarvester => (Other, RateLimit::new(100, 4886)),
+        T::NewProofOfSpace | T::RequestSignatures | T::RespondSignatures => {
+            (Other, RateLimit::new(100, 2048))
+        }
+        T::NewSignagePoint => (Other, RateLimit::new(200, 2048)),
+        T::DeclareProofOfSpace | T::RequestSignedValues => (Other, RateLimit::new(100, 10 * KB)),
+        T::FarmingInfo | T::SignedValues => (Other, RateLimit::new(100, KB)),
+        T::NewPeakTimelord => (Other, RateLimit::new(100, 20 * KB)),
+        T::NewUnfinishedBlockTimelord => (Other, RateLimit::new(100, 10 * KB)),
+        T::NewSignagePointVdf | T::NewInfusionPointVdf | T::NewEndOfSubSlotVdf => {
+            (Other, RateLimit::new(100, 100 * KB))
+        }
+        T::RequestCompactProofOfTime => (Other, RateLimit::new(100, 10 * KB)),
+        T::RespondCompactProofOfTime => (Other, RateLimit::new(100, 100 * KB)),
+        T::NewPeak => (Other, RateLimit::new(200, 512)),
+        T::RequestProofOfWeight => (Other, RateLimit::new(5, 100)),
+        T::RespondProofOfWeight => (Other, RateLimit::with_total(5, 50 * MB, 100 * MB as u64)),
+        T::RequestBlock | T::RejectBlock => (Other, RateLimit::new(200, 100)),
+        T::RequestBlocks => (Other, RateLimit::new(500, 100)),
+        T::RespondBlocks => (
+            Other,
+            RateLimit::with_total(100, 50 * MB, 5 * 50 * MB as u64),
+        ),
+        T::RejectBlocks => (Other, RateLimit::new(100, 100)),
+        T::RespondBlock | T::RespondUnfinishedBlock => (
+            Other,
+            RateLimit::with_total(200, 2 * MB, 10 * 2 * MB as u64),
+        ),
+        T::NewUnfinishedBlock
+        | T::RequestUnfinishedBlock
+        | T::NewUnfinishedBlock2
+        | T::RequestUnfinishedBlock2 => (Other, RateLimit::new(200, 100)),
+        T::NewSignagePointOrEndOfSubSlot | T::RequestSignagePointOrEndOfSubSlot => {
+            (Other, RateLimit::new(200, 200))
+        }
+        T::RespondSignagePoint => (Other, RateLimit::new(200, 50 * KB)),
+        T::RespondEndOfSubSlot => (Other, RateLimit::new(100, 50 * KB)),
+        T::RequestMempoolTransactions => (Other, RateLimit::new(5, MB)),
+        T::RequestCompactVDF => (Other, RateLimit::new(200, KB)),
+        T::RespondCompactVDF => (Other, RateLimit::new(200, 100 * KB)),
+        T::NewCompactVDF => (Other, RateLimit::new(100, KB)),
+        T::RequestPeers => (Other, RateLimit::new(10, 100)),
+        T::RespondPeers => (Other, RateLimit::new(10, MB)),
+        T::RequestPuzzleSolution | T::RejectPuzzleSolution => (Other, RateLimit::new(1000, 100)),
+        T::RespondPuzzleSolution => (Other, RateLimit::new(1000, MB)),
+        T::NewPeakWallet => (Other, RateLimit::new(200, 300)),
+        T::RequestBlockHeader | T::RejectHeaderRequest => (Other, RateLimit::new(500, 100)),
+        T::RespondBlockHeader => (Other, RateLimit::new(500, 500 * KB)),
+        T::RequestRemovals => (Other, RateLimit::with_total(500, 50 * KB, 10 * MB as u64)),
+        T::RespondRemovals | T::RequestAdditions | T::RespondAdditions => {
+            (Other, RateLimit::with_total(500, MB, 10 * MB as u64))
+        }
+        T::RejectRemovalsRequest | T::RejectAdditionsRequest => (Other, RateLimit::new(500, 100)),
+        T::RequestHeaderBlocks | T::RequestBlockHeaders => (Other, RateLimit::new(500, 100)),
+        T::RejectHeaderBlocks | T::RejectBlockHeaders => (Other, RateLimit::new(100, 100)),
+        T::RespondHeaderBlocks | T::RespondBlockHeaders => {
+            (Other, RateLimit::with_total(500, 2 * MB, 100 * MB as u64))
+        }
+        T::RequestPeersIntroducer => (Other, RateLimit::new(100, 100)),
+        T::RespondPeersIntroducer => (Other, RateLimit::new(100, MB)),
+        T::FarmNewBlock => (Other, RateLimit::new(200, 200)),
+        T::RequestPlots => (Other, RateLimit::new(10, 10 * MB)),
+        T::RespondPlots => (Other, RateLimit::new(10, 100 * MB)),
+        T::PlotSyncStart
+        | T::PlotSyncLoaded
+        | T::PlotSyncRemoved
+        | T::PlotSyncInvalid
+        | T::PlotSyncKeysMissing
+        | T::PlotSyncDuplicates
+        | T::PlotSyncDone => (Other, RateLimit::new(1000, 100 * MB)),
+        T::PlotSyncResponse => (Other, RateLimit::new(3000, 100 * MB)),
+        T::CoinStateUpdate
+        | T::RegisterForPhUpdates
+        | T::RespondToPhUpdates
+        | T::RegisterForCoinUpdates
+        | T::RespondToCoinUpdates
+        | T::RequestRemovePuzzleSubscriptions
+        | T::RespondRemovePuzzleSubscriptions
+        | T::RequestRemoveCoinSubscriptions
+        | T::RespondRemoveCoinSubscriptions
+        | T::RequestPuzzleState
+        | T::RespondPuzzleState
+        | T::RejectPuzzleState
+        | T::RequestCoinState
+        | T::RespondCoinState
+        | T::RejectCoinState => (Other, RateLimit::new(1000, 100 * MB)),
+        T::RequestChildren | T::RespondChildren | T::RequestSesInfo | T::RespondSesInfo => {
+            (Other, RateLimit::new(2000, MB))
+        }
+        T::RequestFeeEstimates | T::RespondFeeEstimates => (Other, RateLimit::new(10, 100)),
+        T::NoneResponse => (RateLimitKind::Default, DEFAULT_RATE_LIMIT),
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+// Tracks the number and size of messages of each type, sent or received over
+// a connection, within fixed periods of time (a minute, by default). A message
+// is allowed if it doesn't exceed the limits of its message type, nor
+// (unless it's a transaction message) the limits of all non-transaction
+// messages combined.
+// Incoming messages count towards the limits even if they're rejected (the
+// peer is expected to be disconnected). Outgoing messages only count if
+// they're allowed, since they won't be sent otherwise.
+#[cfg_attr(feature = "py-bindings", pyclass(module = "gold_rs"))]
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    incoming: bool,
+    reset_seconds: u64,
+    // the limits are scaled down to this percentage
+    percentage_of_limit: u32,
+    current_period: u64,
+    message_counts: HashMap<ProtocolMessageTypes, u32>,
+    message_sizes: HashMap<ProtocolMessageTypes, u64>,
+    non_tx_count: u32,
+    non_tx_size: u64,
+}
+
+impl RateLimiter {
+    pub fn new(incoming: bool, reset_seconds: u64, percentage_of_limit: u32) -> Self {
+        Self {
+            incoming,
+            reset_seconds: reset_seconds.max(1),
+            percentage_of_limit,
+            current_period: 0,
+            message_counts: HashMap::new(),
+            message_sizes: HashMap::new(),
+            non_tx_count: 0,
+            non_tx_size: 0,
+        }
+    }
+
+    // whether value is within the (scaled) limit
+    fn within(&self, value: u64, limit: u64) -> bool {
+        value as u128 * 100 <= limit as u128 * self.percentage_of_limit as u128
+    }
+
+    // Counts the message, sent or received at the time now (in seconds since
+    // the epoch), and returns whether it's within the limits.
+    pub fn process_msg_and_check_at(
+        &mut self,
+        msg_type: ProtocolMessageTypes,
+        size: usize,
+        now: u64,
+    ) -> bool {
+        let period = now / self.reset_seconds;
+        if period != self.current_period {
+            self.current_period = period;
+            self.message_counts.clear();
+            self.message_sizes.clear();
+            self.non_tx_count = 0;
+            self.non_tx_size = 0;
+        }
+
+        let size = size as u64;
+        let (kind, limit) = rate_limit(msg_type);
+        let count = self.message_counts.get(&msg_type).copied().unwrap_or(0) + 1;
+        let total_size = self.message_sizes.get(&msg_type).copied().unwrap_or(0) + size;
+        let non_tx_count = self.non_tx_count + 1;
+        let non_tx_size = self.non_tx_size + size;
+
+        let mut allowed = size <= limit.max_size as u64
+            && self.within(count as u64, limit.frequency as u64)
+            && self.within(total_size, limit.total_size());
+        if kind == RateLimitKind::Other {
+            allowed = allowed
+                && self.within(non_tx_count as u64, NON_TX_FREQUENCY as u64)
+                && self.within(non_tx_size, NON_TX_MAX_TOTAL_SIZE);
+        }
+
+        if self.incoming || allowed {
+            self.message_counts.insert(msg_type, count);
+            self.message_sizes.insert(msg_type, total_size);
+            if kind == RateLimitKind::Other {
+                self.non_tx_count = non_tx_count;
+                self.non_tx_size = non_tx_size;
+            }
+        }
+        allowed
+    }
+
+    pub fn process_msg_and_check(&mut self, msg: &Message) -> bool {
+        self.process_msg_and_check_at(msg.msg_type, msg.data.len(), now())
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(true, RATE_LIMIT_PERIOD, 100)
+    }
+}
+
+#[cfg(feature = "py-bindings")]
+#[pymethods]
+impl RateLimiter {
+    #[new]
+    #[pyo3(signature = (incoming, reset_seconds = RATE_LIMIT_PERIOD, percentage_of_limit = 100))]
+    fn py_new(incoming: bool, reset_seconds: u64, percentage_of_limit: u32) -> Self {
+        Self::new(incoming, reset_seconds, percentage_of_limit)
+    }
+
+    fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+        self.clone()
+    }
+
+    #[pyo3(name = "process_msg_and_check", signature = (msg, now = None))]
+    fn py_process_msg_and_check(&mut self, msg: &Message, now: Option<f64>) -> bool {
+        match now {
+            Some(now) => self.process_msg_and_check_at(msg.msg_type, msg.data.len(), now as u64),
+            None => self.process_msg_and_check(msg),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const NOW: u64 = 1_700_000_000 - 1_700_000_000 % RATE_LIMIT_PERIOD;
+
+    #[test]
+    fn test_frequency() {
+        let mut limiter = RateLimiter::new(true, RATE_LIMIT_PERIOD, 100);
+        for _ in 0..10 {
+            assert!(limiter.process_msg_and_check_at(ProtocolMessageTypes::RequestPeers, 10, NOW));
+        }
+        assert!(!limiter.process_msg_and_check_at(ProtocolMessageTypes::RequestPeers, 10, NOW));
+        // other message types have their own limits
+        assert!(limiter.process_msg_and_check_at(ProtocolMessageTypes::NewPeak, 10, NOW));
+        // the limits reset in the next period
+        assert!(limiter.process_msg_and_check_at(
+            ProtocolMessageTypes::RequestPeers,
+            10,
+            NOW + RATE_LIMIT_PERIOD
+        ));
+    }
+
+    #[test]
+    fn test_size() {
+        let mut limiter = RateLimiter::new(true, RATE_LIMIT_PERIOD, 100);
+        assert!(!limiter.process_msg_and_check_at(ProtocolMessageTypes::NewPeak, 513, NOW));
+        assert!(limiter.process_msg_and_check_at(ProtocolMessageTypes::NewPeak, 512, NOW));
+
+        // the total size of RespondTransaction is lower than frequency * max_size
+        for _ in 0..20 {
+            assert!(limiter.process_msg_and_check_at(
+                ProtocolMessageTypes::RespondTransaction,
+                MB as usize,
+                NOW
+            ));
+        }
+        assert!(!limiter.process_msg_and_check_at(
+            ProtocolMessageTypes::RespondTransaction,
+            1,
+            NOW
+        ));
+    }
+
+    #[rstest]
+    #[case(true, 11)]
+    #[case(false, 10)]
+    fn test_counting_rejected(#[case] incoming: bool, #[case] expected_count: u32) {
+        let mut limiter = RateLimiter::new(incoming, RATE_LIMIT_PERIOD, 100);
+        for _ in 0..11 {
+            limiter.process_msg_and_check_at(ProtocolMessageTypes::RequestPeers, 10, NOW);
+        }
+        assert_eq!(
+            limiter.message_counts[&ProtocolMessageTypes::RequestPeers],
+            expected_count
+        );
+    }
+
+    #[test]
+    fn test_non_tx_limit() {
+        let mut limiter = RateLimiter::new(false, RATE_LIMIT_PERIOD, 100);
+        let mut allowed = 0;
+        for msg_type in [
+            ProtocolMessageTypes::RequestBlocks,
+            ProtocolMessageTypes::RequestBlockHeader,
+            ProtocolMessageTypes::RequestHeaderBlocks,
+        ] {
+            for _ in 0..500 {
+                if limiter.process_msg_and_check_at(msg_type, 10, NOW) {
+                    allowed += 1;
+                }
+            }
+        }
+        assert_eq!(allowed, NON_TX_FREQUENCY);
+
+        // transactions don't count towards the non-transaction limits
+        assert!(limiter.process_msg_and_check_at(ProtocolMessageTypes::NewTransaction, 10, NOW));
+    }
+
+    #[test]
+    fn test_percentage_of_limit() {
+        let mut limiter = RateLimiter::new(true, RATE_LIMIT_PERIOD, 50);
+        for _ in 0..5 {
+            assert!(limiter.process_msg_and_check_at(ProtocolMessageTypes::RequestPeers, 10, NOW));
+        }
+        assert!(!limiter.process_msg_and_check_at(ProtocolMessageTypes::RequestPeers, 10, NOW));
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{NodeType, ProtocolMessageTypes, RejectStateReason, SigningDataKind};
+use chia_traits::Streamable;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// Streamable enums are serialized as their integer value, just like in the
+// RPC JSON
+macro_rules! serde_u8_enum {
+    ($($name:ident),*) => {
+        $(
+            impl Serialize for $name {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_u8(*self as u8)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $name {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let value = u8::deserialize(deserializer)?;
+                    Self::from_bytes(&[value]).map_err(|_| {
+                        D::Error::custom(format!("invalid {} value {value}", stringify!($name)))
+                    })
+                }
+            }
+        )*
+    };
+}
+
+serde_u8_enum!(
+    NodeType,
+    ProtocolMessageTypes,
+    RejectStateReason,
+    SigningDataKind
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::{Bytes, Bytes32, Coin, CoinSpend, Handshake, NodeType, Program, SpendBundle};
+    use chia_bls::G2Element;
+
+    #[test]
+    fn test_coin_json() {
+        let coin = Coin::new(Bytes32::new([1; 32]), Bytes32::new([2; 32]), 1337);
+        let json = serde_json::to_value(coin).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "parent_coin_info": format!("0x{}", "01".repeat(32)),
+                "puzzle_hash": format!("0x{}", "02".repeat(32)),
+                "amount": 1337,
+            })
+        );
+        assert_eq!(serde_json::from_value::<Coin>(json).unwrap(), coin);
+    }
+
+    #[test]
+    fn test_spend_bundle_roundtrip() {
+        let coin = Coin::new(Bytes32::new([1; 32]), Bytes32::new([2; 32]), 1337);
+        let spend = CoinSpend::new(
+            coin,
+            Program::from(Bytes::from(vec![0xff, 0x01, 0x80])),
+            Program::from(Bytes::from(vec![0x80])),
+        );
+        let bundle = SpendBundle::new(vec![spend], G2Element::default());
+        let json = serde_json::to_string(&bundle).unwrap();
+        assert_eq!(serde_json::from_str::<SpendBundle>(&json).unwrap(), bundle);
+    }
+
+    #[test]
+    fn test_invalid_program() {
+        // a truncated CLVM serialization
+        assert!(serde_json::from_str::<Program>("\"0xff01\"").is_err());
+        assert!(serde_json::from_str::<Program>("\"0xff0180\"").is_ok());
+    }
+
+    #[test]
+    fn test_enum() {
+        let handshake = Handshake::new(
+            "mainnet".to_string(),
+            "0.0.36".to_string(),
+            "2.3.0".to_string(),
+            8444,
+            NodeType::Wallet,
+            vec![(1, "1".to_string())],
+        );
+        let json = serde_json::to_value(&handshake).unwrap();
+        assert_eq!(json["node_type"], 6);
+        assert_eq!(json["capabilities"], serde_json::json!([[1, "1"]]));
+        assert_eq!(
+            serde_json::from_value::<Handshake>(json).unwrap(),
+            handshake
+        );
+
+        assert!(serde_json::from_str::<NodeType>("6").is_ok());
+        assert!(serde_json::from_str::<NodeType>("42").is_err());
+    }
+
+    #[test]
+    fn test_wrong_length() {
+        assert!(serde_json::from_str::<Bytes32>("\"0x0102\"").is_err());
+        assert_eq!(
+            serde_json::from_str::<Bytes>("\"0x0102\"").unwrap(),
+            Bytes::from(vec![1, 2])
+        );
+    }
+}
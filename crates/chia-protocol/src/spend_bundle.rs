@@ -10,11 +10,14 @@ use clvmr::cost::Cost;
 use clvmr::op_utils::{first, rest};
 use clvmr::reduction::EvalErr;
 use clvmr::Allocator;
+use sha2::{Digest, Sha256};
 
+#[cfg(feature = "py-bindings")]
+use chia_traits::ChiaToPython;
 #[cfg(feature = "py-bindings")]
 use pyo3::prelude::*;
 
-#[streamable]
+#[streamable(cached)]
 pub struct SpendBundle {
     coin_spends: Vec<CoinSpend>,
     aggregated_signature: G2Element,
@@ -28,16 +31,34 @@ impl SpendBundle {
             coin_spends.extend_from_slice(&sb.coin_spends[..]);
             aggregated_signature.aggregate(&sb.aggregated_signature);
         }
-        SpendBundle {
-            coin_spends,
-            aggregated_signature,
-        }
+        SpendBundle::new(coin_spends, aggregated_signature)
     }
 
     pub fn name(&self) -> Bytes32 {
         self.hash().into()
     }
 
+    // A short fingerprint of the set of coins this spend bundle spends. Spend
+    // bundles that spend the same coins have the same fingerprint regardless of
+    // the order of their spends or their aggregated signature, which lets nodes
+    // skip re-validating equivalent transactions they've already seen. Since
+    // it's only 8 bytes, a matching fingerprint is a hint, not proof
+    pub fn removals_fingerprint(&self) -> u64 {
+        let mut removals: Vec<Bytes32> = self
+            .coin_spends
+            .iter()
+            .map(|cs| cs.coin.coin_id())
+            .collect();
+        removals.sort_unstable();
+        removals.dedup();
+        let mut hasher = Sha256::new();
+        for coin_id in &removals {
+            hasher.update(coin_id);
+        }
+        let digest: [u8; 32] = hasher.finalize().into();
+        u64::from_be_bytes(digest[0..8].try_into().unwrap())
+    }
+
     pub fn additions(&self) -> Result<Vec<Coin>, EvalErr> {
         const CREATE_COIN_COST: Cost = 1800000;
         const CREATE_COIN: u8 = 51;
@@ -101,9 +122,10 @@ impl SpendBundle {
         SpendBundle::aggregate(&spend_bundles)
     }
 
+    // the name is cached, since the mempool asks for it repeatedly
     #[pyo3(name = "name")]
-    fn py_name(&self) -> Bytes32 {
-        self.name()
+    fn py_name<'p>(&self, py: Python<'p>) -> PyResult<Bound<'p, PyAny>> {
+        chia_traits::get_or_compute(&self.cache.name, py, || self.name().to_python(py))
     }
 
     fn removals(&self) -> Vec<Coin> {
@@ -114,6 +136,11 @@ impl SpendBundle {
         ret
     }
 
+    #[pyo3(name = "removals_fingerprint")]
+    fn py_removals_fingerprint(&self) -> u64 {
+        self.removals_fingerprint()
+    }
+
     #[pyo3(name = "additions")]
     fn py_additions(&self) -> PyResult<Vec<Coin>> {
         self.additions()
@@ -175,6 +202,31 @@ mod tests {
         assert_eq!(additions[0].amount, 1);
     }
 
+    #[test]
+    fn test_removals_fingerprint() {
+        let spend = |parent: u8| {
+            CoinSpend::new(
+                Coin::new(Bytes32::new([parent; 32]), Bytes32::default(), 1),
+                Program::default(),
+                Program::default(),
+            )
+        };
+        let bundle = SpendBundle::new(vec![spend(1), spend(2)], G2Element::default());
+        let reordered = SpendBundle::new(vec![spend(2), spend(1)], G2Element::generator());
+        assert_ne!(bundle.name(), reordered.name());
+        assert_eq!(
+            bundle.removals_fingerprint(),
+            reordered.removals_fingerprint()
+        );
+
+        let other = SpendBundle::new(vec![spend(1), spend(3)], G2Element::default());
+        assert_ne!(bundle.removals_fingerprint(), other.removals_fingerprint());
+        assert_ne!(
+            bundle.removals_fingerprint(),
+            SpendBundle::new(vec![spend(1)], G2Element::default()).removals_fingerprint()
+        );
+    }
+
     fn test_impl<F: Fn(Coin, SpendBundle)>(solution: &str, body: F) {
         let solution = hex::decode(solution).expect("hex::decode");
         let test_coin = Coin::new(
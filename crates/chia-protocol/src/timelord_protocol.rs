@@ -0,0 +1,97 @@
+use chia_streamable_macro::streamable;
+
+use crate::Bytes32;
+use crate::ChiaProtocolMessage;
+use crate::EndOfSubSlotBundle;
+use crate::Foliage;
+use crate::ProtocolMessageTypes;
+use crate::RewardChainBlock;
+use crate::RewardChainBlockUnfinished;
+use crate::SubEpochSummary;
+use crate::VDFInfo;
+use crate::VDFProof;
+
+#[streamable(message)]
+pub struct NewPeakTimelord {
+    reward_chain_block: RewardChainBlock,
+    difficulty: u64,
+    deficit: u8,
+    sub_slot_iters: u64,
+    sub_epoch_summary: Option<SubEpochSummary>,
+    previous_reward_challenges: Vec<(Bytes32, u128)>,
+    last_challenge_sb_or_eos_total_iters: u128,
+    passes_ses_height_but_not_yet_included: bool,
+}
+
+#[streamable(message)]
+pub struct NewUnfinishedBlockTimelord {
+    reward_chain_block: RewardChainBlockUnfinished,
+    difficulty: u64,
+    sub_slot_iters: u64,
+    foliage: Foliage,
+    sub_epoch_summary: Option<SubEpochSummary>,
+    rc_prev: Bytes32,
+}
+
+#[streamable]
+pub struct NewInfusionPointVDF {
+    unfinished_reward_hash: Bytes32,
+    challenge_chain_ip_vdf: VDFInfo,
+    challenge_chain_ip_proof: VDFProof,
+    reward_chain_ip_vdf: VDFInfo,
+    reward_chain_ip_proof: VDFProof,
+    infused_challenge_chain_ip_vdf: Option<VDFInfo>,
+    infused_challenge_chain_ip_proof: Option<VDFProof>,
+}
+
+#[streamable]
+pub struct NewSignagePointVDF {
+    index_from_challenge: u8,
+    challenge_chain_sp_vdf: VDFInfo,
+    challenge_chain_sp_proof: VDFProof,
+    reward_chain_sp_vdf: VDFInfo,
+    reward_chain_sp_proof: VDFProof,
+}
+
+#[streamable]
+pub struct NewEndOfSubSlotVDF {
+    end_of_sub_slot_bundle: EndOfSubSlotBundle,
+}
+
+#[streamable(message)]
+pub struct RequestCompactProofOfTime {
+    new_proof_of_time: VDFInfo,
+    header_hash: Bytes32,
+    height: u32,
+    field_vdf: u8,
+}
+
+#[streamable(message)]
+pub struct RespondCompactProofOfTime {
+    vdf_info: VDFInfo,
+    vdf_proof: VDFProof,
+    header_hash: Bytes32,
+    height: u32,
+    field_vdf: u8,
+}
+
+// the message types for these are spelled "Vdf", so we can't use
+// #[streamable(message)]
+
+impl ChiaProtocolMessage for NewInfusionPointVDF {
+    fn msg_type() -> ProtocolMessageTypes {
+        ProtocolMessageTypes::NewInfusionPointVdf
+    }
+}
+
+impl ChiaProtocolMessage for NewSignagePointVDF {
+    fn msg_type() -> ProtocolMessageTypes {
+        ProtocolMessageTypes::NewSignagePointVdf
+    }
+}
+
+impl ChiaProtocolMessage for NewEndOfSubSlotVDF {
+    fn msg_type() -> ProtocolMessageTypes {
+        ProtocolMessageTypes::NewEndOfSubSlotVdf
+    }
+}
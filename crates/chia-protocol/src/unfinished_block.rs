@@ -8,7 +8,7 @@ use crate::VDFProof;
 use crate::{Foliage, FoliageTransactionBlock, TransactionsInfo};
 use chia_traits::Streamable;
 
-#[streamable]
+#[streamable(cached)]
 pub struct UnfinishedBlock {
     // Full block, without the final VDFs
     finished_sub_slots: Vec<EndOfSubSlotBundle>, // If first sb
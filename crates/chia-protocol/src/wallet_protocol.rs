@@ -8,7 +8,7 @@ use crate::Program;
 use crate::SpendBundle;
 use crate::{Bytes, Bytes32};
 
-#[streamable(message)]
+#[streamable(responses(RespondPuzzleSolution, RejectPuzzleSolution))]
 pub struct RequestPuzzleSolution {
     coin_name: Bytes32,
     height: u32,
@@ -33,7 +33,7 @@ pub struct RejectPuzzleSolution {
     height: u32,
 }
 
-#[streamable(message)]
+#[streamable(responses(TransactionAck))]
 pub struct SendTransaction {
     transaction: SpendBundle,
 }
@@ -53,7 +53,7 @@ pub struct NewPeakWallet {
     fork_point_with_previous_peak: u32,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondBlockHeader, RejectHeaderRequest))]
 pub struct RequestBlockHeader {
     height: u32,
 }
@@ -68,7 +68,7 @@ pub struct RejectHeaderRequest {
     height: u32,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondRemovals, RejectRemovalsRequest))]
 pub struct RequestRemovals {
     height: u32,
     header_hash: Bytes32,
@@ -89,7 +89,7 @@ pub struct RejectRemovalsRequest {
     header_hash: Bytes32,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondAdditions, RejectAdditionsRequest))]
 pub struct RequestAdditions {
     height: u32,
     header_hash: Option<Bytes32>,
@@ -123,14 +123,14 @@ pub struct RejectBlockHeaders {
     end_height: u32,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondBlockHeaders, RejectBlockHeaders, RejectHeaderBlocks))]
 pub struct RequestBlockHeaders {
     start_height: u32,
     end_height: u32,
     return_filter: bool,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondHeaderBlocks, RejectHeaderBlocks, RejectBlockHeaders))]
 pub struct RequestHeaderBlocks {
     start_height: u32,
     end_height: u32,
@@ -149,7 +149,7 @@ pub struct RespondHeaderBlocks {
     header_blocks: Vec<HeaderBlock>,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondToPhUpdates))]
 pub struct RegisterForPhUpdates {
     puzzle_hashes: Vec<Bytes32>,
     min_height: u32,
@@ -162,7 +162,7 @@ pub struct RespondToPhUpdates {
     coin_states: Vec<CoinState>,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondToCoinUpdates))]
 pub struct RegisterForCoinUpdates {
     coin_ids: Vec<Bytes32>,
     min_height: u32,
@@ -183,7 +183,7 @@ pub struct CoinStateUpdate {
     items: Vec<CoinState>,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondChildren))]
 pub struct RequestChildren {
     coin_name: Bytes32,
 }
@@ -193,7 +193,7 @@ pub struct RespondChildren {
     coin_states: Vec<CoinState>,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondSesInfo))]
 pub struct RequestSesInfo {
     start_height: u32,
     end_height: u32,
@@ -205,7 +205,7 @@ pub struct RespondSesInfo {
     heights: Vec<Vec<u32>>,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondFeeEstimates))]
 pub struct RequestFeeEstimates {
     time_targets: Vec<u64>,
 }
@@ -215,7 +215,7 @@ pub struct RespondFeeEstimates {
     estimates: FeeEstimateGroup,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondRemovePuzzleSubscriptions))]
 pub struct RequestRemovePuzzleSubscriptions {
     puzzle_hashes: Option<Vec<Bytes32>>,
 }
@@ -225,7 +225,7 @@ pub struct RespondRemovePuzzleSubscriptions {
     puzzle_hashes: Vec<Bytes32>,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondRemoveCoinSubscriptions))]
 pub struct RequestRemoveCoinSubscriptions {
     coin_ids: Option<Vec<Bytes32>>,
 }
@@ -243,7 +243,7 @@ pub struct CoinStateFilters {
     min_amount: u64,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondPuzzleState, RejectPuzzleState))]
 pub struct RequestPuzzleState {
     puzzle_hashes: Vec<Bytes32>,
     previous_height: Option<u32>,
@@ -266,7 +266,7 @@ pub struct RejectPuzzleState {
     reason: RejectStateReason,
 }
 
-#[streamable(message)]
+#[streamable(responses(RespondCoinState, RejectCoinState))]
 pub struct RequestCoinState {
     coin_ids: Vec<Bytes32>,
     previous_height: Option<u32>,
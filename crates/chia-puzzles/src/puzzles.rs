@@ -1,4 +1,5 @@
 pub mod cat;
+pub mod clawback;
 pub mod did;
 pub mod nft;
 pub mod offer;
@@ -0,0 +1,494 @@
+use chia_protocol::Bytes32;
+use clvm_traits::{clvm_list, match_list, FromClvm, ToClvm};
+use clvm_utils::{tree_hash, tree_hash_atom, tree_hash_pair, CurriedProgram, ToTreeHash, TreeHash};
+use clvmr::allocator::{Allocator, NodePtr};
+use hex_literal::hex;
+
+/// The condition opcode the claim path of a clawback is locked with.
+pub const ASSERT_SECONDS_RELATIVE: u8 = 80;
+
+/// The condition the recipient's inner puzzle is augmented with: `(ASSERT_SECONDS_RELATIVE timelock)`.
+pub type TimelockCondition = match_list!(u8, u64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToClvm, FromClvm)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[clvm(curry)]
+pub struct P2OneOfManyArgs {
+    pub merkle_root: Bytes32,
+}
+
+impl P2OneOfManyArgs {
+    pub fn new(merkle_root: Bytes32) -> Self {
+        Self { merkle_root }
+    }
+
+    pub fn curry_tree_hash(merkle_root: Bytes32) -> TreeHash {
+        CurriedProgram {
+            program: P2_ONE_OF_MANY_PUZZLE_HASH,
+            args: P2OneOfManyArgs { merkle_root },
+        }
+        .tree_hash()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ToClvm, FromClvm)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[clvm(list)]
+pub struct P2OneOfManySolution<P, S> {
+    pub merkle_proof: MerkleProof,
+    pub puzzle: P,
+    pub solution: S,
+}
+
+/// The proof that a puzzle hash is one of the leaves of a merkle tree, in the format of `merkle_utils.clib`.
+/// Bit `n` of the path is set if the node `n` levels above the leaf is a right child.
+#[derive(Debug, Clone, PartialEq, Eq, ToClvm, FromClvm)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[clvm(list)]
+pub struct MerkleProof {
+    pub path: u32,
+    #[clvm(rest)]
+    pub proof: Vec<Bytes32>,
+}
+
+impl MerkleProof {
+    /// The root of the merkle tree, if `leaf` is part of it.
+    pub fn root(&self, leaf: Bytes32) -> Bytes32 {
+        let mut path = self.path;
+        let mut node = tree_hash_atom(&leaf);
+        for sibling in &self.proof {
+            node = if path & 1 == 0 {
+                tree_hash_pair(node, TreeHash::from(*sibling))
+            } else {
+                tree_hash_pair(TreeHash::from(*sibling), node)
+            };
+            path >>= 1;
+        }
+        node.into()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToClvm, FromClvm)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[clvm(curry)]
+pub struct AugmentedConditionArgs<T, I> {
+    pub condition: T,
+    pub inner_puzzle: I,
+}
+
+impl AugmentedConditionArgs<TimelockCondition, TreeHash> {
+    pub fn curry_tree_hash(timelock: u64, inner_puzzle_hash: TreeHash) -> TreeHash {
+        CurriedProgram {
+            program: AUGMENTED_CONDITION_PUZZLE_HASH,
+            args: AugmentedConditionArgs {
+                condition: clvm_list!(ASSERT_SECONDS_RELATIVE, timelock),
+                inner_puzzle: inner_puzzle_hash,
+            },
+        }
+        .tree_hash()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToClvm, FromClvm)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[clvm(list)]
+pub struct AugmentedConditionSolution<S> {
+    pub inner_solution: S,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToClvm, FromClvm)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[clvm(curry)]
+pub struct P2PuzzleHashArgs {
+    pub inner_puzzle_hash: Bytes32,
+}
+
+impl P2PuzzleHashArgs {
+    pub fn curry_tree_hash(inner_puzzle_hash: Bytes32) -> TreeHash {
+        CurriedProgram {
+            program: P2_PUZZLE_HASH_PUZZLE_HASH,
+            args: P2PuzzleHashArgs { inner_puzzle_hash },
+        }
+        .tree_hash()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToClvm, FromClvm)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[clvm(list)]
+pub struct P2PuzzleHashSolution<P, S> {
+    pub inner_puzzle: P,
+    pub inner_solution: S,
+}
+
+/// A clawback (as created by the wallet's `chia.wallet.puzzles.clawback.drivers`).
+///
+/// The coin is locked by a merkle tree of two puzzles. The recipient can claim it once the timelock has
+/// passed, and until they do, the sender can claw it back. The claim puzzle is the recipient's puzzle,
+/// augmented with an `ASSERT_SECONDS_RELATIVE` condition. The clawback puzzle is a `p2_puzzle_hash`
+/// of the sender's puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Clawback {
+    pub timelock: u64,
+    pub sender_puzzle_hash: Bytes32,
+    pub recipient_puzzle_hash: Bytes32,
+}
+
+impl Clawback {
+    pub fn claim_puzzle_hash(&self) -> TreeHash {
+        AugmentedConditionArgs::curry_tree_hash(self.timelock, self.recipient_puzzle_hash.into())
+    }
+
+    pub fn clawback_puzzle_hash(&self) -> TreeHash {
+        P2PuzzleHashArgs::curry_tree_hash(self.sender_puzzle_hash)
+    }
+
+    /// The merkle root of the claim puzzle (the left leaf) and the clawback puzzle (the right leaf).
+    pub fn merkle_root(&self) -> Bytes32 {
+        tree_hash_pair(
+            tree_hash_atom(&self.claim_puzzle_hash().to_bytes()),
+            tree_hash_atom(&self.clawback_puzzle_hash().to_bytes()),
+        )
+        .into()
+    }
+
+    /// The puzzle hash of the clawback coin.
+    pub fn puzzle_hash(&self) -> TreeHash {
+        P2OneOfManyArgs::curry_tree_hash(self.merkle_root())
+    }
+
+    pub fn claim_proof(&self) -> MerkleProof {
+        MerkleProof {
+            path: 0,
+            proof: vec![tree_hash_atom(&self.clawback_puzzle_hash().to_bytes()).into()],
+        }
+    }
+
+    pub fn clawback_proof(&self) -> MerkleProof {
+        MerkleProof {
+            path: 1,
+            proof: vec![tree_hash_atom(&self.claim_puzzle_hash().to_bytes()).into()],
+        }
+    }
+
+    /// The solution for the recipient to claim the coin.
+    /// `augmented_condition_puzzle` is the (uncurried) `AUGMENTED_CONDITION_PUZZLE`.
+    pub fn claim_solution<A, I, S>(
+        &self,
+        augmented_condition_puzzle: A,
+        inner_puzzle: I,
+        inner_solution: S,
+    ) -> P2OneOfManySolution<
+        CurriedProgram<A, AugmentedConditionArgs<TimelockCondition, I>>,
+        AugmentedConditionSolution<S>,
+    > {
+        P2OneOfManySolution {
+            merkle_proof: self.claim_proof(),
+            puzzle: CurriedProgram {
+                program: augmented_condition_puzzle,
+                args: AugmentedConditionArgs {
+                    condition: clvm_list!(ASSERT_SECONDS_RELATIVE, self.timelock),
+                    inner_puzzle,
+                },
+            },
+            solution: AugmentedConditionSolution { inner_solution },
+        }
+    }
+
+    /// The solution for the sender to claw the coin back.
+    /// `p2_puzzle_hash_puzzle` is the (uncurried) `P2_PUZZLE_HASH_PUZZLE`.
+    pub fn clawback_solution<A, I, S>(
+        &self,
+        p2_puzzle_hash_puzzle: A,
+        inner_puzzle: I,
+        inner_solution: S,
+    ) -> P2OneOfManySolution<CurriedProgram<A, P2PuzzleHashArgs>, P2PuzzleHashSolution<I, S>> {
+        P2OneOfManySolution {
+            merkle_proof: self.clawback_proof(),
+            puzzle: CurriedProgram {
+                program: p2_puzzle_hash_puzzle,
+                args: P2PuzzleHashArgs {
+                    inner_puzzle_hash: self.sender_puzzle_hash,
+                },
+            },
+            solution: P2PuzzleHashSolution {
+                inner_puzzle,
+                inner_solution,
+            },
+        }
+    }
+}
+
+/// Which of the two puzzles of a clawback a spend uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClawbackPath {
+    Claim {
+        timelock: u64,
+        recipient_puzzle_hash: Bytes32,
+    },
+    Clawback {
+        sender_puzzle_hash: Bytes32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClawbackSpend {
+    pub merkle_root: Bytes32,
+    pub path: ClawbackPath,
+    pub inner_puzzle: NodePtr,
+    pub inner_solution: NodePtr,
+}
+
+/// Detects a clawback spend from the puzzle reveal and solution of a coin spend, without running it.
+/// Returns `None` if it's not a clawback, or if the merkle proof in the solution doesn't match the
+/// merkle root curried into the puzzle.
+/// A spend only reveals one of the two puzzles, so only the sender or the recipient is known.
+pub fn parse_clawback_spend(
+    a: &Allocator,
+    puzzle: NodePtr,
+    solution: NodePtr,
+) -> Option<ClawbackSpend> {
+    let puzzle = CurriedProgram::<NodePtr, P2OneOfManyArgs>::from_clvm(a, puzzle).ok()?;
+    if tree_hash(a, puzzle.program) != P2_ONE_OF_MANY_PUZZLE_HASH {
+        return None;
+    }
+    let solution = P2OneOfManySolution::<NodePtr, NodePtr>::from_clvm(a, solution).ok()?;
+    let leaf: Bytes32 = tree_hash(a, solution.puzzle).into();
+    if solution.merkle_proof.root(leaf) != puzzle.args.merkle_root {
+        return None;
+    }
+
+    let inner = CurriedProgram::<NodePtr, NodePtr>::from_clvm(a, solution.puzzle).ok()?;
+    let mod_hash = tree_hash(a, inner.program);
+    let (path, inner_puzzle, inner_solution) = if mod_hash == AUGMENTED_CONDITION_PUZZLE_HASH {
+        let args =
+            AugmentedConditionArgs::<TimelockCondition, NodePtr>::from_clvm(a, inner.args).ok()?;
+        let (opcode, (timelock, ())) = args.condition;
+        if opcode != ASSERT_SECONDS_RELATIVE {
+            return None;
+        }
+        let solution =
+            AugmentedConditionSolution::<NodePtr>::from_clvm(a, solution.solution).ok()?;
+        let path = ClawbackPath::Claim {
+            timelock,
+            recipient_puzzle_hash: tree_hash(a, args.inner_puzzle).into(),
+        };
+        (path, args.inner_puzzle, solution.inner_solution)
+    } else if mod_hash == P2_PUZZLE_HASH_PUZZLE_HASH {
+        let args = P2PuzzleHashArgs::from_clvm(a, inner.args).ok()?;
+        let solution =
+            P2PuzzleHashSolution::<NodePtr, NodePtr>::from_clvm(a, solution.solution).ok()?;
+        let path = ClawbackPath::Clawback {
+            sender_puzzle_hash: args.inner_puzzle_hash,
+        };
+        (path, solution.inner_puzzle, solution.inner_solution)
+    } else {
+        return None;
+    };
+
+    Some(ClawbackSpend {
+        merkle_root: puzzle.args.merkle_root,
+        path,
+        inner_puzzle,
+        inner_solution,
+    })
+}
+
+/// This is the puzzle reveal of the `p2_1_of_n` puzzle, which spends one of the puzzles in a merkle tree.
+pub const P2_ONE_OF_MANY_PUZZLE: [u8; 280] = hex!(
+    "
+    ff02ffff01ff02ffff03ffff09ff05ffff02ff06ffff04ff02ffff04ffff0bff
+    ff0101ffff02ff04ffff04ff02ffff04ff17ff8080808080ffff04ff0bff8080
+    80808080ffff01ff02ff17ff2f80ffff01ff088080ff0180ffff04ffff01ffff
+    02ffff03ffff07ff0580ffff01ff0bffff0102ffff02ff04ffff04ff02ffff04
+    ff09ff80808080ffff02ff04ffff04ff02ffff04ff0dff8080808080ffff01ff
+    0bffff0101ff058080ff0180ff02ffff03ff1bffff01ff02ff06ffff04ff02ff
+    ff04ffff02ffff03ffff18ffff0101ff1380ffff01ff0bffff0102ff2bff0580
+    ffff01ff0bffff0102ff05ff2b8080ff0180ffff04ffff04ffff17ff13ffff01
+    81ff80ff3b80ff8080808080ffff010580ff0180ff018080
+    "
+);
+
+/// This is the puzzle hash of the `p2_1_of_n` puzzle.
+pub const P2_ONE_OF_MANY_PUZZLE_HASH: TreeHash = TreeHash::new(hex!(
+    "
+    46b29fd87fbeb6737600c4543931222a6c1ed3db6fa5601a3ca284a9f4efe780
+    "
+));
+
+/// This is the puzzle reveal of the `augmented_condition` puzzle, which adds a condition to the output of its inner puzzle.
+pub const AUGMENTED_CONDITION_PUZZLE: [u8; 13] = hex!("ff04ff02ffff02ff05ff0b8080");
+
+/// This is the puzzle hash of the `augmented_condition` puzzle.
+pub const AUGMENTED_CONDITION_PUZZLE_HASH: TreeHash = TreeHash::new(hex!(
+    "
+    d303eafa617bedf0bc05850dd014e10fbddf622187dc07891a2aacba9d8a93f6
+    "
+));
+
+/// This is the puzzle reveal of the `p2_puzzle_hash` puzzle, which runs any puzzle with the curried puzzle hash.
+pub const P2_PUZZLE_HASH_PUZZLE: [u8; 143] = hex!(
+    "
+    ff02ffff01ff02ffff03ffff09ff05ffff02ff02ffff04ff02ffff04ff0bff80
+    80808080ffff01ff02ff0bff1780ffff01ff088080ff0180ffff04ffff01ff02
+    ffff03ffff07ff0580ffff01ff0bffff0102ffff02ff02ffff04ff02ffff04ff
+    09ff80808080ffff02ff02ffff04ff02ffff04ff0dff8080808080ffff01ff0b
+    ffff0101ff058080ff0180ff018080
+    "
+);
+
+/// This is the puzzle hash of the `p2_puzzle_hash` puzzle.
+pub const P2_PUZZLE_HASH_PUZZLE_HASH: TreeHash = TreeHash::new(hex!(
+    "
+    13e29a62b42cd2ef72a79e4bacdc59733ca6310d65af83d349360d36ec622363
+    "
+));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::assert_puzzle_hash;
+    use clvm_traits::ToNodePtr;
+    use clvmr::chia_dialect::ChiaDialect;
+    use clvmr::reduction::Reduction;
+    use clvmr::run_program::run_program;
+    use clvmr::serde::node_from_bytes;
+
+    const CLAWBACK: Clawback = Clawback {
+        timelock: 3600,
+        sender_puzzle_hash: Bytes32::new([1; 32]),
+        recipient_puzzle_hash: Bytes32::new([2; 32]),
+    };
+
+    #[test]
+    fn puzzle_hashes() {
+        assert_puzzle_hash!(P2_ONE_OF_MANY_PUZZLE => P2_ONE_OF_MANY_PUZZLE_HASH);
+        assert_puzzle_hash!(AUGMENTED_CONDITION_PUZZLE => AUGMENTED_CONDITION_PUZZLE_HASH);
+        assert_puzzle_hash!(P2_PUZZLE_HASH_PUZZLE => P2_PUZZLE_HASH_PUZZLE_HASH);
+    }
+
+    #[test]
+    fn merkle_proofs() {
+        let claim: Bytes32 = CLAWBACK.claim_puzzle_hash().into();
+        let clawback: Bytes32 = CLAWBACK.clawback_puzzle_hash().into();
+        let root = CLAWBACK.merkle_root();
+        assert_eq!(CLAWBACK.claim_proof().root(claim), root);
+        assert_eq!(CLAWBACK.clawback_proof().root(clawback), root);
+        assert_ne!(CLAWBACK.claim_proof().root(clawback), root);
+        // a single leaf is its own tree
+        let single = MerkleProof {
+            path: 0,
+            proof: vec![],
+        };
+        assert_eq!(single.root(claim), tree_hash_atom(&claim).into());
+    }
+
+    // (q . ((51 <puzzle hash> 1))) with the puzzle hash of the inner puzzle
+    // itself, for lack of a real wallet puzzle
+    fn inner_puzzle(a: &mut Allocator) -> (NodePtr, Bytes32) {
+        let conds = clvm_list!(clvm_list!(51, Bytes32::new([3; 32]), 1))
+            .to_node_ptr(a)
+            .unwrap();
+        let puzzle = a.new_pair(a.one(), conds).unwrap();
+        let puzzle_hash = tree_hash(a, puzzle).into();
+        (puzzle, puzzle_hash)
+    }
+
+    fn run(a: &mut Allocator, clawback: &Clawback, solution: NodePtr) -> Vec<u8> {
+        let p2 = node_from_bytes(a, &P2_ONE_OF_MANY_PUZZLE).unwrap();
+        let puzzle = CurriedProgram {
+            program: p2,
+            args: P2OneOfManyArgs::new(clawback.merkle_root()),
+        }
+        .to_node_ptr(a)
+        .unwrap();
+        assert_eq!(tree_hash(a, puzzle), clawback.puzzle_hash());
+
+        let Reduction(_, conds) =
+            run_program(a, &ChiaDialect::new(0), puzzle, solution, 11_000_000_000).unwrap();
+        // the opcodes of the conditions
+        let mut ret = Vec::new();
+        for c in Vec::<NodePtr>::from_clvm(&*a, conds).unwrap() {
+            let (opcode, _) = <(u8, NodePtr)>::from_clvm(&*a, c).unwrap();
+            ret.push(opcode);
+        }
+        ret
+    }
+
+    #[test]
+    fn claim() {
+        let mut a = Allocator::new();
+        let (inner, recipient_puzzle_hash) = inner_puzzle(&mut a);
+        let clawback = Clawback {
+            recipient_puzzle_hash,
+            ..CLAWBACK
+        };
+        let aug = node_from_bytes(&mut a, &AUGMENTED_CONDITION_PUZZLE).unwrap();
+        let solution = clawback
+            .claim_solution(aug, inner, NodePtr::NIL)
+            .to_node_ptr(&mut a)
+            .unwrap();
+        assert_eq!(
+            run(&mut a, &clawback, solution),
+            [ASSERT_SECONDS_RELATIVE, 51]
+        );
+
+        let p2 = node_from_bytes(&mut a, &P2_ONE_OF_MANY_PUZZLE).unwrap();
+        let puzzle = CurriedProgram {
+            program: p2,
+            args: P2OneOfManyArgs::new(clawback.merkle_root()),
+        }
+        .to_node_ptr(&mut a)
+        .unwrap();
+        let spend = parse_clawback_spend(&a, puzzle, solution).expect("clawback");
+        assert_eq!(spend.merkle_root, clawback.merkle_root());
+        assert_eq!(
+            spend.path,
+            ClawbackPath::Claim {
+                timelock: clawback.timelock,
+                recipient_puzzle_hash,
+            }
+        );
+        assert_eq!(spend.inner_puzzle, inner);
+    }
+
+    #[test]
+    fn clawback() {
+        let mut a = Allocator::new();
+        let (inner, sender_puzzle_hash) = inner_puzzle(&mut a);
+        let clawback = Clawback {
+            sender_puzzle_hash,
+            ..CLAWBACK
+        };
+        let p2_puzzle_hash = node_from_bytes(&mut a, &P2_PUZZLE_HASH_PUZZLE).unwrap();
+        let solution = clawback
+            .clawback_solution(p2_puzzle_hash, inner, NodePtr::NIL)
+            .to_node_ptr(&mut a)
+            .unwrap();
+        // no timelock
+        assert_eq!(run(&mut a, &clawback, solution), [51]);
+
+        let p2 = node_from_bytes(&mut a, &P2_ONE_OF_MANY_PUZZLE).unwrap();
+        let puzzle = CurriedProgram {
+            program: p2,
+            args: P2OneOfManyArgs::new(clawback.merkle_root()),
+        }
+        .to_node_ptr(&mut a)
+        .unwrap();
+        let spend = parse_clawback_spend(&a, puzzle, solution).expect("clawback");
+        assert_eq!(spend.path, ClawbackPath::Clawback { sender_puzzle_hash });
+
+        // a different merkle root
+        let other = CurriedProgram {
+            program: p2,
+            args: P2OneOfManyArgs::new(CLAWBACK.merkle_root()),
+        }
+        .to_node_ptr(&mut a)
+        .unwrap();
+        assert_eq!(parse_clawback_spend(&a, other, solution), None);
+        // not a clawback at all
+        assert_eq!(parse_clawback_spend(&a, inner, NodePtr::NIL), None);
+    }
+}
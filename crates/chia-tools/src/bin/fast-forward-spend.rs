@@ -64,11 +64,11 @@ fn main() {
     )
     .expect("fast-forward");
 
-    let new_spend = CoinSpend {
-        coin: new_parent_coin,
-        puzzle_reveal: spend.puzzle_reveal,
-        solution: Program::from_node_ptr(&a, new_solution).expect("new solution"),
-    };
+    let new_spend = CoinSpend::new(
+        new_parent_coin,
+        spend.puzzle_reveal,
+        Program::from_node_ptr(&a, new_solution).expect("new solution"),
+    );
     let mut bytes = Vec::<u8>::new();
     new_spend.stream(&mut bytes).expect("stream CoinSpend");
     fs::write(args.output_file, bytes).expect("write");
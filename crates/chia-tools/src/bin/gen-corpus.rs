@@ -108,11 +108,7 @@ fn main() {
                             puzzle_hash,
                             amount,
                         };
-                        let spend = CoinSpend {
-                            coin,
-                            puzzle_reveal,
-                            solution,
-                        };
+                        let spend = CoinSpend::new(coin, puzzle_reveal, solution);
 
                         if args.spend_bundles {
                             bundle.coin_spends.push(spend.clone());
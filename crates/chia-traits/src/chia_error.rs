@@ -18,10 +18,26 @@ pub enum Error {
     InvalidEnum,
     #[error("invalid CLVM serialization")]
     InvalidClvm,
+    #[error("non-canonical encoding: {0}")]
+    NonCanonical(String),
+    #[error("invalid pickle header")]
+    InvalidPickle,
+    #[error(
+        "pickle version {0} is not supported by this version of gold_rs (expected version {1})"
+    )]
+    PickleVersion(u32, u32),
+    #[error("I/O error: {0}")]
+    Io(String),
     #[error("{0}")]
     Custom(String),
 }
 
+impl std::convert::From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Error {
+        Error::Io(err.to_string())
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[cfg(feature = "py-bindings")]
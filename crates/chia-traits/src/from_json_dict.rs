@@ -1,5 +1,5 @@
 use pyo3::exceptions::PyValueError;
-use pyo3::types::PyAnyMethods;
+use pyo3::types::{PyAnyMethods, PyString, PyStringMethods};
 use pyo3::Bound;
 use pyo3::PyAny;
 use pyo3::PyResult;
@@ -39,12 +39,30 @@ from_json_primitive!(u16);
 from_json_primitive!(i16);
 from_json_primitive!(u32);
 from_json_primitive!(i32);
-from_json_primitive!(u64);
 from_json_primitive!(i64);
-from_json_primitive!(u128);
 from_json_primitive!(i128);
 from_json_primitive!(String);
 
+// these may have been converted to strings, see with_int_as_string()
+macro_rules! from_json_large_int {
+    ($t:ty) => {
+        impl $crate::from_json_dict::FromJsonDict for $t {
+            fn from_json_dict(o: &Bound<PyAny>) -> pyo3::PyResult<Self> {
+                if let Ok(s) = o.downcast::<PyString>() {
+                    s.to_cow()?.parse().map_err(|_| {
+                        PyValueError::new_err(format!("invalid {}: {s}", stringify!($t)))
+                    })
+                } else {
+                    o.extract()
+                }
+            }
+        }
+    };
+}
+
+from_json_large_int!(u64);
+from_json_large_int!(u128);
+
 impl<T> FromJsonDict for Vec<T>
 where
     T: FromJsonDict,
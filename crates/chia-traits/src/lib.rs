@@ -1,5 +1,7 @@
 pub mod chia_error;
+pub mod pickle;
 pub mod streamable;
+pub mod streamable_cache;
 
 #[cfg(feature = "py-bindings")]
 pub mod from_json_dict;
@@ -11,9 +13,21 @@ pub mod to_json_dict;
 pub use crate::to_json_dict::*;
 
 pub use crate::chia_error::{Error, Result};
+pub use crate::pickle::Pickle;
 pub use crate::streamable::*;
+pub use crate::streamable_cache::*;
 
 #[cfg(feature = "py-bindings")]
 pub mod int;
 #[cfg(feature = "py-bindings")]
 pub use crate::int::*;
+
+#[cfg(feature = "py-bindings")]
+pub mod py_repr;
+#[cfg(feature = "py-bindings")]
+pub use crate::py_repr::*;
+
+#[cfg(feature = "py-bindings")]
+pub mod py_writer;
+#[cfg(feature = "py-bindings")]
+pub use crate::py_writer::*;
@@ -0,0 +1,165 @@
+use crate::chia_error::{Error, Result};
+use crate::streamable::Streamable;
+
+// The state of a pickled object is its streamable serialization, behind a
+// header of PICKLE_MAGIC, the format of the header (PICKLE_FORMAT) and the
+// pickle version of the type, as a big endian u32. Objects pickled before the
+// header was introduced have no header, and are considered version 0. They're
+// loaded by a different constructor (see from_legacy_pickle()), rather than
+// by looking at their first bytes, which could be anything.
+pub const PICKLE_MAGIC: [u8; 4] = *b"\xffGRS";
+pub const PICKLE_FORMAT: u8 = 1;
+
+pub trait Pickle: Streamable + Sized {
+    // bumped whenever the fields of the type change, since that changes its
+    // serialization
+    const PICKLE_VERSION: u32 = 1;
+
+    // Parses the state of a pickle with a version other than PICKLE_VERSION.
+    // Types that changed their fields can override this to convert pickles
+    // made by earlier versions. By default they're rejected.
+    fn migrate_pickle(version: u32, _state: &[u8]) -> Result<Self> {
+        Err(Error::PickleVersion(version, Self::PICKLE_VERSION))
+    }
+}
+
+pub fn to_pickle<T: Pickle>(value: &T) -> Result<Vec<u8>> {
+    let mut ret = PICKLE_MAGIC.to_vec();
+    ret.push(PICKLE_FORMAT);
+    ret.extend_from_slice(&T::PICKLE_VERSION.to_be_bytes());
+    value.stream(&mut ret)?;
+    Ok(ret)
+}
+
+// parses a pickle made by to_pickle()
+pub fn from_pickle<T: Pickle>(state: &[u8]) -> Result<T> {
+    let Some(rest) = state.strip_prefix(&PICKLE_MAGIC) else {
+        return Err(Error::InvalidPickle);
+    };
+    let Some((&format, rest)) = rest.split_first() else {
+        return Err(Error::InvalidPickle);
+    };
+    if format != PICKLE_FORMAT {
+        return Err(Error::InvalidPickle);
+    }
+    if rest.len() < 4 {
+        return Err(Error::InvalidPickle);
+    }
+    let (version, rest) = rest.split_at(4);
+    let version = u32::from_be_bytes(version.try_into().unwrap());
+    if version == T::PICKLE_VERSION {
+        T::from_bytes_unchecked(rest)
+    } else {
+        T::migrate_pickle(version, rest)
+    }
+}
+
+// parses a pickle made before the header was introduced, which holds just the
+// serialization. If the fields have changed since, it most likely fails to
+// parse, and is passed to migrate_pickle() as version 0
+pub fn from_legacy_pickle<T: Pickle>(state: &[u8]) -> Result<T> {
+    T::from_bytes_unchecked(state).or_else(|_| T::migrate_pickle(0, state))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chia_streamable_macro::Streamable;
+
+    #[derive(Streamable, PartialEq, Debug)]
+    struct Unversioned {
+        a: u32,
+    }
+
+    impl Pickle for Unversioned {}
+
+    #[derive(Streamable, PartialEq, Debug)]
+    struct Versioned {
+        a: u32,
+        b: u8,
+    }
+
+    // version 1 had no b field
+    impl Pickle for Versioned {
+        const PICKLE_VERSION: u32 = 2;
+
+        fn migrate_pickle(version: u32, state: &[u8]) -> Result<Self> {
+            match version {
+                0 | 1 => Ok(Self {
+                    a: Unversioned::from_bytes_unchecked(state)?.a,
+                    b: 0,
+                }),
+                _ => Err(Error::PickleVersion(version, Self::PICKLE_VERSION)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let state = to_pickle(&Unversioned { a: 1337 }).unwrap();
+        assert_eq!(
+            state,
+            [0xff, b'G', b'R', b'S', 1, 0, 0, 0, 1, 0, 0, 0x05, 0x39]
+        );
+        assert_eq!(from_pickle(&state), Ok(Unversioned { a: 1337 }));
+
+        let state = to_pickle(&Versioned { a: 1, b: 2 }).unwrap();
+        assert_eq!(state[5..9], [0, 0, 0, 2]);
+        assert_eq!(from_pickle(&state), Ok(Versioned { a: 1, b: 2 }));
+    }
+
+    #[test]
+    fn test_legacy() {
+        assert_eq!(from_legacy_pickle(&[0, 0, 0, 7]), Ok(Unversioned { a: 7 }));
+        assert_eq!(
+            from_legacy_pickle::<Unversioned>(&[0, 0, 0, 7, 0]),
+            Err(Error::PickleVersion(0, 1))
+        );
+        assert_eq!(
+            from_legacy_pickle(&[0, 0, 0, 7]),
+            Ok(Versioned { a: 7, b: 0 })
+        );
+
+        // a legacy pickle that happens to start with the magic bytes
+        let state = u32::from_be_bytes(PICKLE_MAGIC).to_be_bytes();
+        assert_eq!(
+            from_legacy_pickle(&state),
+            Ok(Unversioned {
+                a: u32::from_be_bytes(PICKLE_MAGIC)
+            })
+        );
+
+        // and the versioned format requires the header
+        assert_eq!(
+            from_pickle::<Unversioned>(&[0, 0, 0, 7]),
+            Err(Error::InvalidPickle)
+        );
+    }
+
+    #[test]
+    fn test_migrate() {
+        let state = to_pickle(&Unversioned { a: 42 }).unwrap();
+        assert_eq!(from_pickle(&state), Ok(Versioned { a: 42, b: 0 }));
+
+        // pickles from later versions fail, rather than being misparsed
+        let state = to_pickle(&Versioned { a: 1, b: 2 }).unwrap();
+        assert_eq!(
+            from_pickle::<Unversioned>(&state),
+            Err(Error::PickleVersion(2, 1))
+        );
+    }
+
+    #[test]
+    fn test_invalid_header() {
+        let mut state = to_pickle(&Unversioned { a: 1 }).unwrap();
+        assert_eq!(
+            from_pickle::<Unversioned>(&state[..7]),
+            Err(Error::InvalidPickle)
+        );
+        state[4] = 2;
+        assert_eq!(
+            from_pickle::<Unversioned>(&state),
+            Err(Error::InvalidPickle)
+        );
+    }
+}
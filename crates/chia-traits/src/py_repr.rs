@@ -0,0 +1,168 @@
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList, PyString};
+use std::fmt::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// The limits applied to the repr() of streamable objects. usize::MAX means
+// unlimited, which is the default, in which case repr() prints the whole
+// object (its Debug representation)
+static MAX_ITEMS: AtomicUsize = AtomicUsize::new(usize::MAX);
+static MAX_DEPTH: AtomicUsize = AtomicUsize::new(usize::MAX);
+static MAX_HEX_LEN: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReprLimits {
+    // the number of elements printed for every list, the remaining ones are
+    // summarized by a count
+    pub max_items: Option<usize>,
+    // objects and lists nested deeper than this are elided
+    pub max_depth: Option<usize>,
+    // hex strings (bytes) are truncated to this many characters
+    pub max_hex_len: Option<usize>,
+}
+
+impl ReprLimits {
+    pub fn is_unlimited(&self) -> bool {
+        self.max_items.is_none() && self.max_depth.is_none() && self.max_hex_len.is_none()
+    }
+}
+
+fn load(v: &AtomicUsize) -> Option<usize> {
+    let v = v.load(Ordering::Relaxed);
+    (v != usize::MAX).then_some(v)
+}
+
+fn store(v: &AtomicUsize, limit: Option<usize>) {
+    v.store(limit.unwrap_or(usize::MAX), Ordering::Relaxed);
+}
+
+pub fn repr_limits() -> ReprLimits {
+    ReprLimits {
+        max_items: load(&MAX_ITEMS),
+        max_depth: load(&MAX_DEPTH),
+        max_hex_len: load(&MAX_HEX_LEN),
+    }
+}
+
+// the limits are process wide, they apply to every streamable type
+pub fn set_repr_limits(limits: ReprLimits) {
+    store(&MAX_ITEMS, limits.max_items);
+    store(&MAX_DEPTH, limits.max_depth);
+    store(&MAX_HEX_LEN, limits.max_hex_len);
+}
+
+fn write_compact(
+    out: &mut String,
+    value: &Bound<'_, PyAny>,
+    limits: &ReprLimits,
+    depth: usize,
+) -> PyResult<()> {
+    let too_deep = limits.max_depth.is_some_and(|max| depth >= max);
+    if let Ok(dict) = value.downcast::<PyDict>() {
+        if too_deep {
+            out.push_str("{ .. }");
+            return Ok(());
+        }
+        out.push_str("{ ");
+        for (i, (k, v)) in dict.iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            write!(out, "{}: ", k.str()?.to_cow()?).unwrap();
+            write_compact(out, &v, limits, depth + 1)?;
+        }
+        out.push_str(" }");
+    } else if let Ok(list) = value.downcast::<PyList>() {
+        if too_deep {
+            write!(out, "[.. {} items]", list.len()).unwrap();
+            return Ok(());
+        }
+        let shown = limits.max_items.unwrap_or(usize::MAX).min(list.len());
+        out.push('[');
+        for (i, v) in list.iter().take(shown).enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            write_compact(out, &v, limits, depth + 1)?;
+        }
+        if shown < list.len() {
+            if shown > 0 {
+                out.push_str(", ");
+            }
+            write!(out, ".. {} more", list.len() - shown).unwrap();
+        }
+        out.push(']');
+    } else if let Ok(s) = value.downcast::<PyString>() {
+        let s = s.to_cow()?;
+        match limits.max_hex_len {
+            Some(max) if s.starts_with("0x") => {
+                // strings (e.g. a pool URL) may also start with 0x, they're
+                // cut on a character boundary and don't get the bytes label
+                let hex = &s[2..];
+                let end = s.char_indices().nth(max + 2).map(|(i, _)| i);
+                match end {
+                    None => out.push_str(&s),
+                    Some(end) if hex.bytes().all(|b| b.is_ascii_hexdigit()) => {
+                        write!(out, "{}.. ({} bytes)", &s[..end], hex.len() / 2).unwrap();
+                    }
+                    Some(end) => write!(out, "{}..", &s[..end]).unwrap(),
+                }
+            }
+            _ => out.push_str(&s),
+        }
+    } else {
+        out.push_str(&value.repr()?.to_cow()?);
+    }
+    Ok(())
+}
+
+// formats the JSON dict of an object, applying the limits. This is used by
+// repr() of streamable types once any limit has been set
+pub fn compact_repr(name: &str, json: &Bound<'_, PyAny>, limits: &ReprLimits) -> PyResult<String> {
+    let mut out = name.to_string();
+    out.push(' ');
+    write_compact(&mut out, json, limits, 0)?;
+    Ok(out)
+}
+
+fn truncate_lists<'py>(value: &Bound<'py, PyAny>, max_items: usize) -> PyResult<Bound<'py, PyAny>> {
+    let py = value.py();
+    if let Ok(dict) = value.downcast::<PyDict>() {
+        let ret = PyDict::new_bound(py);
+        for (k, v) in dict.iter() {
+            ret.set_item(k, truncate_lists(&v, max_items)?)?;
+        }
+        Ok(ret.into_any())
+    } else if let Ok(list) = value.downcast::<PyList>() {
+        let ret = PyList::empty_bound(py);
+        for v in list.iter().take(max_items) {
+            ret.append(truncate_lists(&v, max_items)?)?;
+        }
+        if list.len() > max_items {
+            ret.append(format!(".. {} more", list.len() - max_items))?;
+        }
+        Ok(ret.into_any())
+    } else {
+        Ok(value.clone())
+    }
+}
+
+// JSON text of the JSON dict of an object. Lists longer than max_items are cut
+// short, with a string saying how many elements were left out as the last
+// element, so the result is meant to be read, not parsed back
+pub fn pretty_json(
+    json: &Bound<'_, PyAny>,
+    indent: usize,
+    max_items: Option<usize>,
+) -> PyResult<String> {
+    let py = json.py();
+    let json = match max_items {
+        Some(max) => truncate_lists(json, max)?,
+        None => json.clone(),
+    };
+    let kwargs = PyDict::new_bound(py);
+    kwargs.set_item("indent", indent)?;
+    py.import_bound("json")?
+        .call_method("dumps", (json,), Some(&kwargs))?
+        .extract()
+}
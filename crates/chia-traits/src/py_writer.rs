@@ -0,0 +1,66 @@
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use std::io::Write;
+
+// an io::Write adapter for python file-like objects (anything with a write()
+// method taking bytes). Writes are collected into chunks of CHUNK_SIZE
+// bytes, to not call into python for every field. Since io::Error can't carry
+// the python exception, it's kept here and returned by finish()
+pub struct PyWriter<'py> {
+    file: Bound<'py, PyAny>,
+    buf: Vec<u8>,
+    error: Option<PyErr>,
+}
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+impl<'py> PyWriter<'py> {
+    pub fn new(file: Bound<'py, PyAny>) -> Self {
+        Self {
+            file,
+            buf: Vec::with_capacity(CHUNK_SIZE),
+            error: None,
+        }
+    }
+
+    fn write_chunk(&mut self) -> std::io::Result<()> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+        let chunk = PyBytes::new_bound(self.file.py(), &self.buf);
+        if let Err(e) = self.file.call_method1("write", (chunk,)) {
+            let msg = e.to_string();
+            self.error = Some(e);
+            return Err(std::io::Error::other(msg));
+        }
+        self.buf.clear();
+        Ok(())
+    }
+
+    // flushes what's left in the buffer, given the result of writing to
+    // this object. Errors raised by the file object are returned as-is
+    pub fn finish(mut self, result: crate::Result<()>) -> PyResult<()> {
+        let result = result.and_then(|()| Ok(self.write_chunk()?));
+        match (self.error.take(), result) {
+            (Some(e), _) => Err(e),
+            (None, result) => Ok(result?),
+        }
+    }
+}
+
+impl Write for PyWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.error.is_some() {
+            return Err(std::io::Error::other("write failed"));
+        }
+        self.buf.extend_from_slice(buf);
+        if self.buf.len() >= CHUNK_SIZE {
+            self.write_chunk()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.write_chunk()
+    }
+}
@@ -1,6 +1,7 @@
 use crate::chia_error::{Error, Result};
 use sha2::{Digest, Sha256};
-use std::io::Cursor;
+use std::cell::Cell;
+use std::io::{Cursor, Write};
 use std::mem::size_of;
 
 pub fn read_bytes<'a>(input: &'a mut Cursor<&[u8]>, len: usize) -> Result<&'a [u8]> {
@@ -26,7 +27,102 @@ fn test_read_bytes() {
     assert_eq!(read_bytes(&mut input, 1).unwrap_err(), Error::EndOfBuffer);
 }
 
+// Limits on the length prefixes accepted when parsing untrusted input. Lists
+// longer than max_list_len and byte buffers (including strings and CLVM
+// programs) longer than max_bytes_len are rejected with
+// Error::SequenceTooLarge, before anything is allocated for them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseLimits {
+    pub max_list_len: u32,
+    pub max_bytes_len: u32,
+}
+
+thread_local! {
+    static PARSE_LIMITS: Cell<Option<ParseLimits>> = const { Cell::new(None) };
+}
+
+// Installs the parse limits for the current thread, for as long as the guard
+// is alive. The previous limits are restored when it's dropped.
+pub struct ParseLimitsGuard(Option<ParseLimits>);
+
+impl ParseLimitsGuard {
+    pub fn new(limits: ParseLimits) -> Self {
+        Self(PARSE_LIMITS.with(|l| l.replace(Some(limits))))
+    }
+}
+
+impl Drop for ParseLimitsGuard {
+    fn drop(&mut self) {
+        PARSE_LIMITS.with(|l| l.set(self.0));
+    }
+}
+
+thread_local! {
+    static STRICT_PARSING: Cell<bool> = const { Cell::new(false) };
+}
+
+// While the guard is alive, parsing on the current thread only accepts
+// canonical encodings, for types that have more than one encoding of the same
+// value (like the CLVM serialization of Program). See from_bytes_strict()
+pub struct StrictParsingGuard(bool);
+
+impl StrictParsingGuard {
+    pub fn new() -> Self {
+        Self(STRICT_PARSING.with(|s| s.replace(true)))
+    }
+}
+
+impl Default for StrictParsingGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for StrictParsingGuard {
+    fn drop(&mut self) {
+        STRICT_PARSING.with(|s| s.set(self.0));
+    }
+}
+
+pub fn strict_parsing() -> bool {
+    STRICT_PARSING.with(Cell::get)
+}
+
+// Streamable implementations call these with the length prefix they just
+// parsed. When no limits are installed, any length is accepted
+pub fn check_list_len(len: u32) -> Result<()> {
+    match PARSE_LIMITS.with(Cell::get) {
+        Some(limits) if len > limits.max_list_len => Err(Error::SequenceTooLarge),
+        _ => Ok(()),
+    }
+}
+
+pub fn check_bytes_len(len: u32) -> Result<()> {
+    match PARSE_LIMITS.with(Cell::get) {
+        Some(limits) if len > limits.max_bytes_len => Err(Error::SequenceTooLarge),
+        _ => Ok(()),
+    }
+}
+
+// Evaluating NotNested::<T>::OK fails the build if T has #[trailing] fields.
+// Lists, options, tuples and structs do so for the types they contain, since
+// a nested value with trailing fields would consume the bytes of whatever
+// follows it.
+#[doc(hidden)]
+pub struct NotNested<T>(std::marker::PhantomData<T>);
+
+impl<T: Streamable> NotNested<T> {
+    pub const OK: () = assert!(
+        !T::HAS_TRAILING_FIELDS,
+        "types with #[trailing] fields can't be nested in other types"
+    );
+}
+
 pub trait Streamable {
+    // types with #[trailing] fields read as many of them as the input holds,
+    // so they can only be parsed at the end of the input. See NotNested
+    const HAS_TRAILING_FIELDS: bool = false;
+
     fn update_digest(&self, digest: &mut Sha256);
     fn stream(&self, out: &mut Vec<u8>) -> Result<()>;
     fn parse<const TRUSTED: bool>(input: &mut Cursor<&[u8]>) -> Result<Self>
@@ -66,11 +162,59 @@ pub trait Streamable {
             Ok(ret)
         }
     }
+    // like from_bytes(), but rejects lists and byte buffers whose length
+    // prefix exceeds the specified limits. This is meant for parsing messages
+    // from untrusted peers
+    fn from_bytes_with_limits(bytes: &[u8], max_list_len: u32, max_bytes_len: u32) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        let _limits = ParseLimitsGuard::new(ParseLimits {
+            max_list_len,
+            max_bytes_len,
+        });
+        Self::from_bytes(bytes)
+    }
+    // like from_bytes(), but fails with Error::NonCanonical unless the input
+    // is the canonical encoding of the value, i.e. the one to_bytes() would
+    // produce. This is meant for comparing implementations, where accepting
+    // an alternative encoding hides a difference
+    fn from_bytes_strict(bytes: &[u8]) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        let ret = {
+            let _strict = StrictParsingGuard::new();
+            Self::from_bytes(bytes)?
+        };
+        if ret.to_bytes()? != bytes {
+            return Err(Error::NonCanonical(
+                "the value serializes differently".to_string(),
+            ));
+        }
+        Ok(ret)
+    }
+    // writes the serialization to out. Structs and lists are written one
+    // field or element at a time, so unlike to_bytes(), the whole
+    // serialization is never held in memory at once. Types without an
+    // override of this function are serialized into a buffer first
+    fn stream_to<W: Write>(&self, out: &mut W) -> Result<()> {
+        let mut buf = Vec::<u8>::new();
+        self.stream(&mut buf)?;
+        out.write_all(&buf)?;
+        Ok(())
+    }
     fn hash(&self) -> [u8; 32] {
         let mut ctx = Sha256::new();
         self.update_digest(&mut ctx);
         ctx.finalize().into()
     }
+    // the number of bytes allocated on the heap and owned by this value, not
+    // including the size of the value itself. This is what makes
+    // sys.getsizeof() of the python bindings reflect the actual memory use
+    fn heap_size(&self) -> usize {
+        0
+    }
 }
 
 macro_rules! streamable_primitive {
@@ -123,8 +267,22 @@ impl<T: Streamable> Streamable for Vec<T> {
         }
     }
 
+    fn stream_to<W: Write>(&self, out: &mut W) -> Result<()> {
+        if self.len() > u32::MAX as usize {
+            Err(Error::InputTooLarge)
+        } else {
+            (self.len() as u32).stream_to(out)?;
+            for e in self {
+                e.stream_to(out)?;
+            }
+            Ok(())
+        }
+    }
+
     fn parse<const TRUSTED: bool>(input: &mut Cursor<&[u8]>) -> Result<Self> {
+        let () = NotNested::<T>::OK;
         let len = u32::parse::<TRUSTED>(input)?;
+        check_list_len(len)?;
 
         let mut ret = if std::mem::size_of::<T>() == 0 {
             Vec::<T>::new()
@@ -137,6 +295,10 @@ impl<T: Streamable> Streamable for Vec<T> {
         }
         Ok(ret)
     }
+
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(T::heap_size).sum::<usize>()
+    }
 }
 
 impl Streamable for String {
@@ -160,11 +322,16 @@ impl Streamable for String {
 
     fn parse<const TRUSTED: bool>(input: &mut Cursor<&[u8]>) -> Result<Self> {
         let len = u32::parse::<TRUSTED>(input)?;
+        check_bytes_len(len)?;
         Ok(String::from(
             std::str::from_utf8(read_bytes(input, len as usize)?)
                 .map_err(|_| Error::InvalidString)?,
         ))
     }
+
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
 }
 
 impl Streamable for bool {
@@ -221,7 +388,21 @@ impl<T: Streamable> Streamable for Option<T> {
         }
         Ok(())
     }
+
+    fn stream_to<W: Write>(&self, out: &mut W) -> Result<()> {
+        match self {
+            None => {
+                out.write_all(&[0])?;
+            }
+            Some(v) => {
+                out.write_all(&[1])?;
+                v.stream_to(out)?;
+            }
+        }
+        Ok(())
+    }
     fn parse<const TRUSTED: bool>(input: &mut Cursor<&[u8]>) -> Result<Self> {
+        let () = NotNested::<T>::OK;
         let val = read_bytes(input, 1)?[0];
         match val {
             0 => Ok(None),
@@ -229,6 +410,10 @@ impl<T: Streamable> Streamable for Option<T> {
             _ => Err(Error::InvalidOptional),
         }
     }
+
+    fn heap_size(&self) -> usize {
+        self.as_ref().map_or(0, T::heap_size)
+    }
 }
 
 impl<T: Streamable, U: Streamable> Streamable for (T, U) {
@@ -242,8 +427,12 @@ impl<T: Streamable, U: Streamable> Streamable for (T, U) {
         Ok(())
     }
     fn parse<const TRUSTED: bool>(input: &mut Cursor<&[u8]>) -> Result<Self> {
+        let ((), ()) = (NotNested::<T>::OK, NotNested::<U>::OK);
         Ok((T::parse::<TRUSTED>(input)?, U::parse::<TRUSTED>(input)?))
     }
+    fn heap_size(&self) -> usize {
+        self.0.heap_size() + self.1.heap_size()
+    }
 }
 
 impl<T: Streamable, U: Streamable, V: Streamable> Streamable for (T, U, V) {
@@ -259,12 +448,16 @@ impl<T: Streamable, U: Streamable, V: Streamable> Streamable for (T, U, V) {
         Ok(())
     }
     fn parse<const TRUSTED: bool>(input: &mut Cursor<&[u8]>) -> Result<Self> {
+        let ((), (), ()) = (NotNested::<T>::OK, NotNested::<U>::OK, NotNested::<V>::OK);
         Ok((
             T::parse::<TRUSTED>(input)?,
             U::parse::<TRUSTED>(input)?,
             V::parse::<TRUSTED>(input)?,
         ))
     }
+    fn heap_size(&self) -> usize {
+        self.0.heap_size() + self.1.heap_size() + self.2.heap_size()
+    }
 }
 
 impl<T: Streamable, U: Streamable, V: Streamable, W: Streamable> Streamable for (T, U, V, W) {
@@ -282,6 +475,12 @@ impl<T: Streamable, U: Streamable, V: Streamable, W: Streamable> Streamable for
         Ok(())
     }
     fn parse<const TRUSTED: bool>(input: &mut Cursor<&[u8]>) -> Result<Self> {
+        let ((), (), (), ()) = (
+            NotNested::<T>::OK,
+            NotNested::<U>::OK,
+            NotNested::<V>::OK,
+            NotNested::<W>::OK,
+        );
         Ok((
             T::parse::<TRUSTED>(input)?,
             U::parse::<TRUSTED>(input)?,
@@ -289,6 +488,58 @@ impl<T: Streamable, U: Streamable, V: Streamable, W: Streamable> Streamable for
             W::parse::<TRUSTED>(input)?,
         ))
     }
+    fn heap_size(&self) -> usize {
+        self.0.heap_size() + self.1.heap_size() + self.2.heap_size() + self.3.heap_size()
+    }
+}
+
+// A value along with whatever follows it in the input, which this version
+// doesn't know how to parse (like fields a newer peer added to a message).
+// The unknown bytes are serialized again after the value, so relaying a
+// message doesn't drop them. Since it consumes all remaining input, this is
+// only meaningful for the outermost value, like the payload of a message.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Preserved<T> {
+    pub value: T,
+    pub unknown: Vec<u8>,
+}
+
+impl<T> Preserved<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            unknown: Vec::new(),
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: Streamable> Streamable for Preserved<T> {
+    fn update_digest(&self, digest: &mut Sha256) {
+        self.value.update_digest(digest);
+        digest.update(&self.unknown);
+    }
+
+    fn stream(&self, out: &mut Vec<u8>) -> Result<()> {
+        self.value.stream(out)?;
+        out.extend_from_slice(&self.unknown);
+        Ok(())
+    }
+
+    fn parse<const TRUSTED: bool>(input: &mut Cursor<&[u8]>) -> Result<Self> {
+        let value = T::parse::<TRUSTED>(input)?;
+        let pos = input.position() as usize;
+        let unknown = input.get_ref()[pos..].to_vec();
+        input.set_position(input.get_ref().len() as u64);
+        Ok(Self { value, unknown })
+    }
+
+    fn heap_size(&self) -> usize {
+        self.value.heap_size() + self.unknown.capacity()
+    }
 }
 
 // ===== TESTS ====
@@ -490,7 +741,7 @@ fn test_parse_truncated_str() {
 use chia_streamable_macro::Streamable;
 
 #[cfg(test)]
-#[derive(Streamable, PartialEq, Debug)]
+#[derive(Streamable, PartialEq, Debug, Clone)]
 struct TestStruct {
     a: Vec<i8>,
     b: String,
@@ -523,6 +774,68 @@ fn test_parse_custom_tuple() {
     from_bytes::<TestTuple>(buf, TestTuple("baz".to_string(), 42));
 }
 
+#[cfg(test)]
+#[derive(Streamable, PartialEq, Debug)]
+struct TestTrailing {
+    a: u32,
+    #[trailing]
+    b: Option<u8>,
+    #[trailing]
+    c: Option<u16>,
+}
+
+#[test]
+fn test_parse_trailing() {
+    // serialized before b and c were added
+    from_bytes::<TestTrailing>(
+        &[0, 0, 0, 7],
+        TestTrailing {
+            a: 7,
+            b: None,
+            c: None,
+        },
+    );
+    // before c was added
+    from_bytes::<TestTrailing>(
+        &[0, 0, 0, 7, 1, 42],
+        TestTrailing {
+            a: 7,
+            b: Some(42),
+            c: None,
+        },
+    );
+    from_bytes::<TestTrailing>(
+        &[0, 0, 0, 7, 0, 1, 0x13, 0x37],
+        TestTrailing {
+            a: 7,
+            b: None,
+            c: Some(0x1337),
+        },
+    );
+    from_bytes_fail::<TestTrailing>(&[0, 0, 0, 7, 1], Error::EndOfBuffer);
+    from_bytes_fail::<TestTrailing>(&[0, 0, 0], Error::EndOfBuffer);
+}
+
+#[test]
+fn test_trailing_not_nested() {
+    assert!(TestTrailing::HAS_TRAILING_FIELDS);
+    assert!(!TestStruct::HAS_TRAILING_FIELDS);
+    // parsing a Vec<TestTrailing> or a struct with a TestTrailing field fails
+    // to compile, since the first element would consume the following ones
+}
+
+#[test]
+fn test_stream_trailing() {
+    let v = |b, c| TestTrailing { a: 7, b, c };
+    assert_eq!(stream(&v(None, None)), [0, 0, 0, 7]);
+    assert_eq!(stream(&v(Some(42), None)), [0, 0, 0, 7, 1, 42]);
+    // b is still serialized, to keep c in its place
+    assert_eq!(
+        stream(&v(None, Some(0x1337))),
+        [0, 0, 0, 7, 0, 1, 0x13, 0x37]
+    );
+}
+
 #[cfg(test)]
 fn stream<T: Streamable>(v: &T) -> Vec<u8> {
     let mut buf = Vec::<u8>::new();
@@ -532,9 +845,67 @@ fn stream<T: Streamable>(v: &T) -> Vec<u8> {
     v.update_digest(&mut ctx1);
     ctx2.update(&buf);
     assert_eq!(&ctx1.finalize(), &ctx2.finalize());
+    let mut buf2 = Vec::<u8>::new();
+    v.stream_to(&mut buf2).unwrap();
+    assert_eq!(buf, buf2);
     buf
 }
 
+#[cfg(test)]
+#[derive(Default)]
+struct TestWriter {
+    buf: Vec<u8>,
+    writes: usize,
+    max_write: usize,
+    fail_after: Option<usize>,
+}
+
+#[cfg(test)]
+impl Write for TestWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self
+            .fail_after
+            .is_some_and(|n| self.buf.len() + buf.len() > n)
+        {
+            return Err(std::io::Error::other("disk full"));
+        }
+        self.buf.extend_from_slice(buf);
+        self.writes += 1;
+        self.max_write = std::cmp::max(self.max_write, buf.len());
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_stream_to() {
+    let v = vec![
+        TestStruct {
+            a: vec![1; 100],
+            b: "foobar".to_string(),
+            c: (1, 2),
+        };
+        10
+    ];
+    let mut out = TestWriter::default();
+    v.stream_to(&mut out).unwrap();
+    assert_eq!(out.buf, v.to_bytes().unwrap());
+    // the list isn't written in one piece
+    assert!(out.writes > 10);
+    assert!(out.max_write < 200);
+
+    let mut out = TestWriter {
+        fail_after: Some(500),
+        ..Default::default()
+    };
+    assert_eq!(
+        v.stream_to(&mut out).unwrap_err(),
+        Error::Io("disk full".to_string())
+    );
+}
+
 #[test]
 fn test_stream_i32() {
     let b: i32 = 0x01020304;
@@ -757,3 +1128,138 @@ fn test_stream_enum() {
     assert_eq!(stream::<TestEnum>(&TestEnum::B), &[1_u8]);
     assert_eq!(stream::<TestEnum>(&TestEnum::C), &[255_u8]);
 }
+
+#[test]
+fn test_from_bytes_with_limits() {
+    // a list of 3 strings: "a", "bb", "ccc"
+    let buf: &[u8] = &[
+        0, 0, 0, 3, 0, 0, 0, 1, b'a', 0, 0, 0, 2, b'b', b'b', 0, 0, 0, 3, b'c', b'c', b'c',
+    ];
+    let expected = vec!["a".to_string(), "bb".to_string(), "ccc".to_string()];
+    assert_eq!(
+        Vec::<String>::from_bytes_with_limits(buf, 3, 3).unwrap(),
+        expected
+    );
+    assert_eq!(
+        Vec::<String>::from_bytes_with_limits(buf, 2, 3).unwrap_err(),
+        Error::SequenceTooLarge
+    );
+    assert_eq!(
+        Vec::<String>::from_bytes_with_limits(buf, 3, 2).unwrap_err(),
+        Error::SequenceTooLarge
+    );
+
+    // the limits are only in effect for the call to from_bytes_with_limits()
+    assert_eq!(Vec::<String>::from_bytes(buf).unwrap(), expected);
+}
+
+#[test]
+fn test_hostile_length_prefix() {
+    // claims to have 4 billion elements, but the limit rejects it before we
+    // try to parse (or allocate) any of them
+    let buf: &[u8] = &[0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0];
+    assert_eq!(
+        Vec::<u64>::from_bytes_with_limits(buf, 1000, 1000).unwrap_err(),
+        Error::SequenceTooLarge
+    );
+    assert_eq!(
+        String::from_bytes_with_limits(buf, 1000, 1000).unwrap_err(),
+        Error::SequenceTooLarge
+    );
+}
+
+#[test]
+fn test_nested_limits() {
+    let outer = ParseLimitsGuard::new(ParseLimits {
+        max_list_len: 10,
+        max_bytes_len: 10,
+    });
+    assert_eq!(check_list_len(10), Ok(()));
+    {
+        let _inner = ParseLimitsGuard::new(ParseLimits {
+            max_list_len: 1,
+            max_bytes_len: 1,
+        });
+        assert_eq!(check_list_len(2), Err(Error::SequenceTooLarge));
+    }
+    assert_eq!(check_list_len(10), Ok(()));
+    assert_eq!(check_bytes_len(11), Err(Error::SequenceTooLarge));
+    drop(outer);
+    assert_eq!(check_bytes_len(u32::MAX), Ok(()));
+}
+
+#[test]
+fn test_heap_size() {
+    assert_eq!(42_u32.heap_size(), 0);
+    assert_eq!(Some(42_u32).heap_size(), 0);
+
+    let mut v = Vec::<u32>::with_capacity(10);
+    v.push(1);
+    assert_eq!(v.heap_size(), 40);
+
+    let s = String::with_capacity(7);
+    assert_eq!(s.heap_size(), 7);
+
+    let nested: Vec<Vec<u8>> = vec![Vec::with_capacity(3), Vec::with_capacity(5)];
+    assert_eq!(
+        nested.heap_size(),
+        nested.capacity() * size_of::<Vec<u8>>() + 8
+    );
+
+    let t = TestStruct {
+        a: Vec::with_capacity(4),
+        b: String::with_capacity(3),
+        c: (1, 2),
+    };
+    assert_eq!(t.heap_size(), 7);
+    assert_eq!(TestTuple(String::with_capacity(5), 1).heap_size(), 5);
+}
+
+#[test]
+fn test_preserved() {
+    // a struct serialized by a newer version, with more fields
+    let buf: &[u8] = &[0, 0, 0, 3, b'b', b'a', b'z', 0, 0, 0, 42, 1, 2, 3];
+    let ret = Preserved::<TestTuple>::from_bytes(buf).unwrap();
+    assert_eq!(ret.value, TestTuple("baz".to_string(), 42));
+    assert_eq!(ret.unknown, [1, 2, 3]);
+    // the hash covers the unknown bytes too
+    assert_eq!(stream(&ret), buf);
+    assert_eq!(ret.heap_size(), 3 + 3);
+
+    // the plain type rejects them
+    assert_eq!(
+        TestTuple::from_bytes(buf).unwrap_err(),
+        Error::InputTooLarge
+    );
+
+    let ret = Preserved::<TestTuple>::from_bytes(&buf[..11]).unwrap();
+    assert_eq!(ret, Preserved::new(TestTuple("baz".to_string(), 42)));
+    from_bytes_fail::<Preserved<TestTuple>>(&buf[..9], Error::EndOfBuffer);
+}
+
+#[test]
+fn test_strict_parsing_guard() {
+    assert!(!strict_parsing());
+    {
+        let _outer = StrictParsingGuard::new();
+        assert!(strict_parsing());
+        {
+            let _inner = StrictParsingGuard::new();
+            assert!(strict_parsing());
+        }
+        assert!(strict_parsing());
+    }
+    assert!(!strict_parsing());
+
+    // for types with a single encoding, it's the same as from_bytes()
+    let buf: &[u8] = &[0, 0, 0, 3, b'b', b'a', b'z', 1];
+    assert_eq!(
+        <(String, bool)>::from_bytes_strict(buf).unwrap(),
+        ("baz".to_string(), true)
+    );
+    assert_eq!(
+        <(String, bool)>::from_bytes_strict(&[0, 0, 0, 0, 2]),
+        Err(Error::InvalidBool)
+    );
+    assert!(!strict_parsing());
+}
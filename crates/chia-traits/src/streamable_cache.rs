@@ -0,0 +1,63 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::OnceLock;
+
+#[cfg(feature = "py-bindings")]
+use pyo3::prelude::*;
+#[cfg(feature = "py-bindings")]
+use pyo3::sync::GILOnceCell;
+#[cfg(feature = "py-bindings")]
+use pyo3::types::PyBytes;
+
+// Values derived from an immutable streamable object, which are computed the
+// first time they're asked for. Types declared with #[streamable(cached)] have
+// a field of this type. It's not visible to python, so the cached values can't
+// be replaced from there. The cache isn't part of the value of the object: it's
+// not serialized, compared or hashed, and a clone starts out with an empty
+// cache. The python objects are freed together with the object holding them.
+#[derive(Default)]
+pub struct StreamableCache {
+    pub hash: OnceLock<isize>,
+    #[cfg(feature = "py-bindings")]
+    pub get_hash: GILOnceCell<Py<PyBytes>>,
+    #[cfg(feature = "py-bindings")]
+    pub bytes: GILOnceCell<Py<PyBytes>>,
+    #[cfg(feature = "py-bindings")]
+    pub name: GILOnceCell<PyObject>,
+}
+
+impl Clone for StreamableCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl PartialEq for StreamableCache {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for StreamableCache {}
+
+impl Hash for StreamableCache {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+
+impl fmt::Debug for StreamableCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("StreamableCache")
+    }
+}
+
+// Returns the value stored in cell, computing and storing it first if it's not
+// there yet. The same python object is returned every time.
+#[cfg(feature = "py-bindings")]
+pub fn get_or_compute<'py, T>(
+    cell: &GILOnceCell<Py<T>>,
+    py: Python<'py>,
+    compute: impl FnOnce() -> PyResult<Bound<'py, T>>,
+) -> PyResult<Bound<'py, T>> {
+    cell.get_or_try_init(py, || compute().map(Bound::unbind))
+        .map(|value| value.bind(py).clone())
+}
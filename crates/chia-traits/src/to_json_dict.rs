@@ -1,5 +1,28 @@
 use pyo3::prelude::*;
 use pyo3::types::PyList;
+use std::cell::Cell;
+
+thread_local! {
+    static INT_AS_STRING: Cell<bool> = const { Cell::new(false) };
+}
+
+// restores the previous setting, also if the conversion panics
+struct IntAsStringGuard(bool);
+
+impl Drop for IntAsStringGuard {
+    fn drop(&mut self) {
+        INT_AS_STRING.with(|v| v.set(self.0));
+    }
+}
+
+// Runs f with u64 and u128 values converted to JSON as decimal strings rather
+// than numbers. JSON consumers that store numbers as doubles (like javascript)
+// lose precision above 2^53, which e.g. difficulty_constant_factor and
+// mojo amounts exceed.
+pub fn with_int_as_string<R>(int_as_string: bool, f: impl FnOnce() -> R) -> R {
+    let _guard = IntAsStringGuard(INT_AS_STRING.with(|v| v.replace(int_as_string)));
+    f()
+}
 
 pub trait ToJsonDict {
     fn to_json_dict(&self, py: Python) -> PyResult<PyObject>;
@@ -22,12 +45,27 @@ to_json_primitive!(u16);
 to_json_primitive!(i16);
 to_json_primitive!(u32);
 to_json_primitive!(i32);
-to_json_primitive!(u64);
 to_json_primitive!(i64);
-to_json_primitive!(u128);
 to_json_primitive!(i128);
 to_json_primitive!(String);
 
+macro_rules! to_json_large_int {
+    ($t:ty) => {
+        impl $crate::to_json_dict::ToJsonDict for $t {
+            fn to_json_dict(&self, py: Python) -> pyo3::PyResult<PyObject> {
+                if INT_AS_STRING.with(Cell::get) {
+                    Ok(self.to_string().to_object(py))
+                } else {
+                    Ok(self.to_object(py))
+                }
+            }
+        }
+    };
+}
+
+to_json_large_int!(u64);
+to_json_large_int!(u128);
+
 impl<T: ToJsonDict> ToJsonDict for Vec<T> {
     fn to_json_dict(&self, py: Python) -> PyResult<PyObject> {
         let list = PyList::empty_bound(py);
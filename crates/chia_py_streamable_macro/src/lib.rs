@@ -3,6 +3,7 @@ extern crate proc_macro;
 use proc_macro2::{Ident, Span};
 use proc_macro_crate::{crate_name, FoundCrate};
 use quote::quote;
+use syn::ext::IdentExt;
 use syn::{parse_macro_input, DeriveInput, FieldsNamed, FieldsUnnamed};
 
 fn maybe_upper_fields(py_uppercase: bool, fnames: Vec<syn::Ident>) -> Vec<syn::Ident> {
@@ -16,7 +17,54 @@ fn maybe_upper_fields(py_uppercase: bool, fnames: Vec<syn::Ident>) -> Vec<syn::I
     }
 }
 
-#[proc_macro_derive(PyStreamable, attributes(py_uppercase, py_pickle))]
+// A field can be given a different name in python (and JSON) with
+// #[py_name = "..."]. This is used for fields whose python name is a reserved
+// word in rust, in which case it's returned as a raw identifier
+fn py_field_name(field: &syn::Field, name: syn::Ident) -> syn::Ident {
+    let Some(attr) = field.attrs.iter().find(|a| a.path().is_ident("py_name")) else {
+        return name;
+    };
+    let nv = attr
+        .meta
+        .require_name_value()
+        .expect("expected #[py_name = \"<name>\"]");
+    let syn::Expr::Lit(syn::ExprLit {
+        lit: syn::Lit::Str(py_name),
+        ..
+    }) = &nv.value
+    else {
+        panic!("expected #[py_name = \"<name>\"]");
+    };
+    let py_name = py_name.value();
+    syn::parse_str::<syn::Ident>(&py_name)
+        .unwrap_or_else(|_| syn::Ident::new_raw(&py_name, Span::call_site()))
+}
+
+// the field added by #[streamable(cached)] to hold the cached values. It's not
+// visible to python
+fn is_streamable_cache(field: &syn::Field) -> bool {
+    field
+        .attrs
+        .iter()
+        .any(|a| a.path().is_ident("streamable_cache"))
+}
+
+// the names of the fields as strings, without the r# prefix of raw identifiers
+fn field_name_strings(fnames: &[syn::Ident]) -> Vec<String> {
+    fnames.iter().map(|f| f.unraw().to_string()).collect()
+}
+
+#[proc_macro_derive(
+    PyStreamable,
+    attributes(
+        py_uppercase,
+        py_pickle,
+        py_pickle_version,
+        py_pickle_migrate,
+        py_cached,
+        py_name
+    )
+)]
 pub fn py_streamable_macro(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let found_crate = crate_name("chia-traits").expect("chia-traits is present in `Cargo.toml`");
 
@@ -34,11 +82,28 @@ pub fn py_streamable_macro(input: proc_macro::TokenStream) -> proc_macro::TokenS
 
     let mut py_uppercase = false;
     let mut py_pickle = false;
+    let mut py_cached = false;
+    let mut pickle_version: Option<syn::Expr> = None;
+    let mut pickle_migrate: Option<syn::Expr> = None;
     for attr in attrs.iter() {
         if attr.path().is_ident("py_uppercase") {
             py_uppercase = true;
         } else if attr.path().is_ident("py_pickle") {
             py_pickle = true;
+        } else if attr.path().is_ident("py_pickle_version") {
+            let nv = attr
+                .meta
+                .require_name_value()
+                .expect("expected #[py_pickle_version = <version>]");
+            pickle_version = Some(nv.value.clone());
+        } else if attr.path().is_ident("py_pickle_migrate") {
+            let nv = attr
+                .meta
+                .require_name_value()
+                .expect("expected #[py_pickle_migrate = <function>]");
+            pickle_migrate = Some(nv.value.clone());
+        } else if attr.path().is_ident("py_cached") {
+            py_cached = true;
         }
     }
 
@@ -72,11 +137,67 @@ pub fn py_streamable_macro(input: proc_macro::TokenStream) -> proc_macro::TokenS
         }
     };
 
+    // py_cached types are immutable and have a cache field (see
+    // chia_traits::StreamableCache), which we use to memoize the hash and
+    // serialization of the object the first time they're computed
+    let (hash_method, get_hash_method, cached_bytes_method) = if py_cached {
+        (
+            quote! {
+                fn __hash__(&self) -> pyo3::PyResult<isize> {
+                    Ok(*self.cache.hash.get_or_init(|| {
+                        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                        std::hash::Hash::hash(self, &mut hasher);
+                        std::hash::Hasher::finish(&hasher) as isize
+                    }))
+                }
+            },
+            quote! {
+                pub fn get_hash<'p>(&self, py: pyo3::Python<'p>) -> pyo3::PyResult<pyo3::Bound<'p, pyo3::types::PyBytes>> {
+                    #crate_name::get_or_compute(&self.cache.get_hash, py, || {
+                        let mut ctx = <sha2::Sha256 as sha2::Digest>::new();
+                        #crate_name::Streamable::update_digest(self, &mut ctx);
+                        Ok(pyo3::types::PyBytes::new_bound(py, sha2::Digest::finalize(ctx).as_slice()))
+                    })
+                }
+            },
+            quote! {
+                pub fn cached_bytes<'p>(&self, py: pyo3::Python<'p>) -> pyo3::PyResult<pyo3::Bound<'p, pyo3::types::PyBytes>> {
+                    #crate_name::get_or_compute(&self.cache.bytes, py, || self.py_to_bytes(py))
+                }
+            },
+        )
+    } else {
+        (
+            quote! {
+                fn __hash__(&self) -> pyo3::PyResult<isize> {
+                    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                    std::hash::Hash::hash(self, &mut hasher);
+                    Ok(std::hash::Hasher::finish(&hasher) as isize)
+                }
+            },
+            quote! {
+                pub fn get_hash<'p>(&self, py: pyo3::Python<'p>) -> pyo3::PyResult<pyo3::Bound<'p, pyo3::types::PyBytes>> {
+                    let mut ctx = <sha2::Sha256 as sha2::Digest>::new();
+                    #crate_name::Streamable::update_digest(self, &mut ctx);
+                    Ok(pyo3::types::PyBytes::new_bound(py, sha2::Digest::finalize(ctx).as_slice()))
+                }
+            },
+            quote! {},
+        )
+    };
+
     let mut py_protocol = quote! {
         #[pyo3::pymethods]
         impl #ident {
-            fn __repr__(&self) -> pyo3::PyResult<String> {
-                Ok(format!("{self:?}"))
+            // the Debug representation, unless limits have been set with
+            // set_repr_limits(), in which case large objects are abbreviated
+            fn __repr__(&self, py: pyo3::Python<'_>) -> pyo3::PyResult<String> {
+                let limits = #crate_name::repr_limits();
+                if limits.is_unlimited() {
+                    return Ok(format!("{self:?}"));
+                }
+                let json = #crate_name::to_json_dict::ToJsonDict::to_json_dict(self, py)?;
+                #crate_name::compact_repr(stringify!(#ident), json.bind(py), &limits)
             }
 
             fn __richcmp__(&self, other: pyo3::PyRef<Self>, op: pyo3::class::basic::CompareOp) -> pyo3::Py<pyo3::PyAny> {
@@ -89,10 +210,13 @@ pub fn py_streamable_macro(input: proc_macro::TokenStream) -> proc_macro::TokenS
                 }
             }
 
-            fn __hash__(&self) -> pyo3::PyResult<isize> {
-                let mut hasher = std::collections::hash_map::DefaultHasher::new();
-                std::hash::Hash::hash(self, &mut hasher);
-                Ok(std::hash::Hasher::finish(&hasher) as isize)
+            #hash_method
+
+            // the size of the python object, plus the heap memory owned by the
+            // rust value
+            fn __sizeof__(slf: &pyo3::Bound<'_, Self>) -> pyo3::PyResult<usize> {
+                let basic_size: usize = slf.get_type().getattr("__basicsize__")?.extract()?;
+                Ok(basic_size + <Self as #crate_name::Streamable>::heap_size(&*slf.borrow()))
             }
         }
 
@@ -108,21 +232,44 @@ pub fn py_streamable_macro(input: proc_macro::TokenStream) -> proc_macro::TokenS
 
     match fields {
         syn::Fields::Named(FieldsNamed { named, .. }) => {
+            let mut trailing = Vec::<bool>::new();
+            let mut py_names = Vec::<syn::Ident>::new();
+            let mut cache_names = Vec::<syn::Ident>::new();
             for f in named.iter() {
+                if is_streamable_cache(f) {
+                    cache_names.push(f.ident.as_ref().unwrap().clone());
+                    continue;
+                }
                 fnames.push(f.ident.as_ref().unwrap().clone());
                 ftypes.push(f.ty.clone());
+                trailing.push(f.attrs.iter().any(|a| a.path().is_ident("trailing")));
+                py_names.push(py_field_name(f, f.ident.as_ref().unwrap().clone()));
             }
 
-            let fnames_maybe_upper = maybe_upper_fields(py_uppercase, fnames.clone());
+            let fnames_maybe_upper = maybe_upper_fields(py_uppercase, py_names);
+            let fname_strings = field_name_strings(&fnames_maybe_upper);
+
+            // #[trailing] fields default to None, so code written before they
+            // were added keeps working
+            let signature = fnames_maybe_upper
+                .iter()
+                .zip(trailing)
+                .map(|(name, trailing)| {
+                    if trailing {
+                        quote! { #name = None }
+                    } else {
+                        quote! { #name }
+                    }
+                });
 
             py_protocol.extend(quote! {
                 #[pyo3::pymethods]
                 impl #ident {
                     #[allow(too_many_arguments)]
                     #[new]
-                    #[pyo3(signature = (#(#fnames_maybe_upper),*))]
+                    #[pyo3(signature = (#(#signature),*))]
                     pub fn py_new ( #(#fnames_maybe_upper : #ftypes),* ) -> Self {
-                        Self { #(#fnames: #fnames_maybe_upper),* }
+                        Self { #(#fnames: #fnames_maybe_upper,)* #(#cache_names: Default::default(),)* }
                     }
                 }
             });
@@ -138,7 +285,7 @@ pub fn py_streamable_macro(input: proc_macro::TokenStream) -> proc_macro::TokenS
                             for (field, value) in iter {
                                 let field = field.extract::<String>()?;
                                 match field.as_str() {
-                                    #(stringify!(#fnames_maybe_upper) => {
+                                    #(#fname_strings => {
                                         ret.#fnames = value.extract()?;
                                     }),*
                                     _ => { return Err(pyo3::exceptions::PyKeyError::new_err(format!("unknown field {field}"))); }
@@ -165,8 +312,17 @@ pub fn py_streamable_macro(input: proc_macro::TokenStream) -> proc_macro::TokenS
                 <Self as #crate_name::from_json_dict::FromJsonDict>::from_json_dict(json_dict)
             }
 
-            pub fn to_json_dict(&self, py: pyo3::Python) -> pyo3::PyResult<pyo3::PyObject> {
-                #crate_name::to_json_dict::ToJsonDict::to_json_dict(self, py)
+            #[pyo3(signature = (int_as_string=false))]
+            pub fn to_json_dict(&self, py: pyo3::Python, int_as_string: bool) -> pyo3::PyResult<pyo3::PyObject> {
+                #crate_name::to_json_dict::with_int_as_string(int_as_string, || {
+                    #crate_name::to_json_dict::ToJsonDict::to_json_dict(self, py)
+                })
+            }
+
+            #[pyo3(signature = (indent=2, max_items=None))]
+            pub fn to_pretty_json(&self, py: pyo3::Python, indent: usize, max_items: Option<usize>) -> pyo3::PyResult<String> {
+                let json = #crate_name::to_json_dict::ToJsonDict::to_json_dict(self, py)?;
+                #crate_name::pretty_json(json.bind(py), indent, max_items)
             }
         }
     });
@@ -186,6 +342,30 @@ pub fn py_streamable_macro(input: proc_macro::TokenStream) -> proc_macro::TokenS
                 <Self as #crate_name::Streamable>::from_bytes(slice).map_err(|e| <#crate_name::chia_error::Error as Into<pyo3::PyErr>>::into(e))
             }
 
+            #[staticmethod]
+            #[pyo3(name = "from_bytes_with_limits")]
+            pub fn py_from_bytes_with_limits(blob: pyo3::buffer::PyBuffer<u8>, max_list_len: u32, max_bytes_len: u32) -> pyo3::PyResult<Self> {
+                if !blob.is_c_contiguous() {
+                    panic!("from_bytes_with_limits() must be called with a contiguous buffer");
+                }
+                let slice = unsafe {
+                    std::slice::from_raw_parts(blob.buf_ptr() as *const u8, blob.len_bytes())
+                };
+                <Self as #crate_name::Streamable>::from_bytes_with_limits(slice, max_list_len, max_bytes_len).map_err(|e| <#crate_name::chia_error::Error as Into<pyo3::PyErr>>::into(e))
+            }
+
+            #[staticmethod]
+            #[pyo3(name = "from_bytes_strict")]
+            pub fn py_from_bytes_strict(blob: pyo3::buffer::PyBuffer<u8>) -> pyo3::PyResult<Self> {
+                if !blob.is_c_contiguous() {
+                    panic!("from_bytes_strict() must be called with a contiguous buffer");
+                }
+                let slice = unsafe {
+                    std::slice::from_raw_parts(blob.buf_ptr() as *const u8, blob.len_bytes())
+                };
+                <Self as #crate_name::Streamable>::from_bytes_strict(slice).map_err(|e| <#crate_name::chia_error::Error as Into<pyo3::PyErr>>::into(e))
+            }
+
             #[staticmethod]
             #[pyo3(name = "from_bytes_unchecked")]
             pub fn py_from_bytes_unchecked(blob: pyo3::buffer::PyBuffer<u8>) -> pyo3::PyResult<Self> {
@@ -216,11 +396,10 @@ pub fn py_streamable_macro(input: proc_macro::TokenStream) -> proc_macro::TokenS
                 }
             }
 
-            pub fn get_hash<'p>(&self, py: pyo3::Python<'p>) -> pyo3::PyResult<pyo3::Bound<'p, pyo3::types::PyBytes>> {
-                let mut ctx = <sha2::Sha256 as sha2::Digest>::new();
-                #crate_name::Streamable::update_digest(self, &mut ctx);
-                Ok(pyo3::types::PyBytes::new_bound(py, sha2::Digest::finalize(ctx).as_slice()))
-            }
+            #get_hash_method
+
+            #cached_bytes_method
+
             #[pyo3(name = "to_bytes")]
             pub fn py_to_bytes<'p>(&self, py: pyo3::Python<'p>) -> pyo3::PyResult<pyo3::Bound<'p, pyo3::types::PyBytes>> {
                 let mut writer = Vec::<u8>::new();
@@ -228,6 +407,15 @@ pub fn py_streamable_macro(input: proc_macro::TokenStream) -> proc_macro::TokenS
                 Ok(pyo3::types::PyBytes::new_bound(py, &writer))
             }
 
+            // writes the serialization to a file-like object, without
+            // building all of it in memory first
+            #[pyo3(name = "stream_to")]
+            pub fn py_stream_to(&self, f: &pyo3::Bound<'_, pyo3::PyAny>) -> pyo3::PyResult<()> {
+                let mut writer = #crate_name::PyWriter::new(f.clone());
+                let result = #crate_name::Streamable::stream_to(self, &mut writer);
+                writer.finish(result)
+            }
+
             pub fn stream_to_bytes<'p>(&self, py: pyo3::Python<'p>) -> pyo3::PyResult<pyo3::Bound<'p, pyo3::types::PyBytes>> {
                 self.py_to_bytes(py)
             }
@@ -247,33 +435,74 @@ pub fn py_streamable_macro(input: proc_macro::TokenStream) -> proc_macro::TokenS
     };
     py_protocol.extend(streamable);
 
+    // Objects are pickled as a call to the _from_pickle() classmethod, with
+    // the versioned serialization as argument (see chia_traits::pickle). This
+    // works for frozen types too, which can't implement __setstate__.
+    let version = pickle_version.map(|version| {
+        quote! { const PICKLE_VERSION: u32 = #version; }
+    });
+    let migrate = pickle_migrate.map(|migrate| {
+        quote! {
+            fn migrate_pickle(version: u32, state: &[u8]) -> #crate_name::chia_error::Result<Self> {
+                #migrate(version, state)
+            }
+        }
+    });
+    py_protocol.extend(quote! {
+        impl #crate_name::pickle::Pickle for #ident {
+            #version
+            #migrate
+        }
+
+        impl #ident {
+            fn py_from_pickle(state: &[u8]) -> pyo3::PyResult<Self> {
+                #crate_name::pickle::from_pickle(state).map_err(|e| {
+                    pyo3::exceptions::PyValueError::new_err(format!(
+                        "failed to unpickle {}: {e}",
+                        stringify!(#ident)
+                    ))
+                })
+            }
+        }
+
+        #[pyo3::pymethods]
+        impl #ident {
+            pub fn __reduce__<'py>(
+                &self,
+                py: pyo3::Python<'py>,
+            ) -> pyo3::PyResult<(pyo3::Bound<'py, pyo3::PyAny>, (pyo3::Bound<'py, pyo3::types::PyBytes>,))> {
+                use pyo3::prelude::PyAnyMethods;
+                let state = #crate_name::pickle::to_pickle(self)?;
+                let from_pickle = py.get_type_bound::<Self>().getattr("_from_pickle")?;
+                Ok((from_pickle, (pyo3::types::PyBytes::new_bound(py, &state),)))
+            }
+
+            #[classmethod]
+            pub fn _from_pickle(
+                _cls: &pyo3::Bound<'_, pyo3::types::PyType>,
+                state: &[u8],
+            ) -> pyo3::PyResult<Self> {
+                Self::py_from_pickle(state)
+            }
+        }
+    });
+
+    // types that were pickled with __getnewargs__() and __setstate__() before
+    // the versioned pickles, which still need to load. Those pickles have no
+    // header
     if py_pickle {
         let pickle = quote! {
             #[pyo3::pymethods]
             impl #ident {
-                pub fn __setstate__(
-                    &mut self,
-                    state: &pyo3::types::PyBytes,
-                ) -> pyo3::PyResult<()> {
-                    use chia_traits::Streamable;
-
-                    *self = Self::parse::<true>(&mut std::io::Cursor::new(state.as_bytes()))?;
-
+                pub fn __setstate__(&mut self, state: &[u8]) -> pyo3::PyResult<()> {
+                    *self = #crate_name::pickle::from_legacy_pickle(state).map_err(|e| {
+                        pyo3::exceptions::PyValueError::new_err(format!(
+                            "failed to unpickle {}: {e}",
+                            stringify!(#ident)
+                        ))
+                    })?;
                     Ok(())
                 }
-
-                pub fn __getstate__<'py>(
-                    &self,
-                    py: pyo3::Python<'py>,
-                ) -> pyo3::PyResult<pyo3::Bound<'py, pyo3::types::PyBytes>> {
-                    self.py_to_bytes(py)
-                }
-
-                pub fn __getnewargs__<'py>(&self, py: pyo3::Python<'py>) -> pyo3::PyResult<pyo3::Bound<'py, pyo3::types::PyTuple>> {
-                    let mut args = Vec::new();
-                    #( args.push(#crate_name::ChiaToPython::to_python(&self.#fnames, py)?); )*
-                    Ok(pyo3::types::PyTuple::new_bound(py, args))
-                }
             }
         };
         py_protocol.extend(pickle);
@@ -282,7 +511,7 @@ pub fn py_streamable_macro(input: proc_macro::TokenStream) -> proc_macro::TokenS
     py_protocol.into()
 }
 
-#[proc_macro_derive(PyJsonDict, attributes(py_uppercase))]
+#[proc_macro_derive(PyJsonDict, attributes(py_uppercase, py_name))]
 pub fn py_json_dict_macro(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let found_crate = crate_name("chia-traits").expect("chia-traits is present in `Cargo.toml`");
 
@@ -335,20 +564,53 @@ pub fn py_json_dict_macro(input: proc_macro::TokenStream) -> proc_macro::TokenSt
         syn::Fields::Named(FieldsNamed { named, .. }) => {
             let mut fnames = Vec::<syn::Ident>::new();
             let mut ftypes = Vec::<syn::Type>::new();
+            let mut trailing = Vec::<bool>::new();
+            let mut py_names = Vec::<syn::Ident>::new();
+            let mut cache_names = Vec::<syn::Ident>::new();
             for f in named.iter() {
+                if is_streamable_cache(f) {
+                    cache_names.push(f.ident.as_ref().unwrap().clone());
+                    continue;
+                }
                 fnames.push(f.ident.as_ref().unwrap().clone());
                 ftypes.push(f.ty.clone());
+                trailing.push(f.attrs.iter().any(|a| a.path().is_ident("trailing")));
+                py_names.push(py_field_name(f, f.ident.as_ref().unwrap().clone()));
             }
 
-            let fnames_maybe_upper = maybe_upper_fields(py_uppercase, fnames.clone());
+            let fnames_maybe_upper = maybe_upper_fields(py_uppercase, py_names);
+            let fname_strings = field_name_strings(&fnames_maybe_upper);
+
+            // #[trailing] fields may be missing from the dict, just like they
+            // may be missing from the end of the binary serialization
+            let from_json_fields = fnames
+                .iter()
+                .zip(ftypes.iter())
+                .zip(fname_strings.iter())
+                .zip(trailing)
+                .map(|(((fname, ftype), fname_string), trailing)| {
+                    if trailing {
+                        quote! {
+                            #fname: match o.get_item(#fname_string) {
+                                Ok(v) => <#ftype as #crate_name::from_json_dict::FromJsonDict>::from_json_dict(&v)?,
+                                Err(e) if e.is_instance_of::<pyo3::exceptions::PyKeyError>(o.py()) => None,
+                                Err(e) => return Err(e),
+                            },
+                        }
+                    } else {
+                        quote! {
+                            #fname: <#ftype as #crate_name::from_json_dict::FromJsonDict>::from_json_dict(&o.get_item(#fname_string)?)?,
+                        }
+                    }
+                });
 
-            py_protocol.extend( quote! {
+            py_protocol.extend(quote! {
 
                 impl #crate_name::to_json_dict::ToJsonDict for #ident {
                     fn to_json_dict(&self, py: pyo3::Python) -> pyo3::PyResult<pyo3::PyObject> {
                         use pyo3::prelude::PyDictMethods;
                         let ret = pyo3::types::PyDict::new_bound(py);
-                        #(ret.set_item(stringify!(#fnames_maybe_upper), self.#fnames.to_json_dict(py)?)?);*;
+                        #(ret.set_item(#fname_strings, self.#fnames.to_json_dict(py)?)?);*;
                         Ok(ret.into())
                     }
                 }
@@ -357,7 +619,8 @@ pub fn py_json_dict_macro(input: proc_macro::TokenStream) -> proc_macro::TokenSt
                     fn from_json_dict(o: &pyo3::Bound<pyo3::PyAny>) -> pyo3::PyResult<Self> {
                         use pyo3::prelude::PyAnyMethods;
                         Ok(Self{
-                            #(#fnames: <#ftypes as #crate_name::from_json_dict::FromJsonDict>::from_json_dict(&o.get_item(stringify!(#fnames_maybe_upper))?)?,)*
+                            #(#from_json_fields)*
+                            #(#cache_names: Default::default(),)*
                         })
                     }
                 }
@@ -371,7 +634,7 @@ pub fn py_json_dict_macro(input: proc_macro::TokenStream) -> proc_macro::TokenSt
     py_protocol.into()
 }
 
-#[proc_macro_derive(PyGetters, attributes(py_uppercase))]
+#[proc_macro_derive(PyGetters, attributes(py_uppercase, py_name))]
 pub fn py_getters_macro(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let DeriveInput {
         ident, data, attrs, ..
@@ -404,18 +667,25 @@ pub fn py_getters_macro(input: proc_macro::TokenStream) -> proc_macro::TokenStre
 
     let mut fnames = Vec::<syn::Ident>::new();
     let mut ftypes = Vec::<syn::Type>::new();
-    for f in named.into_iter() {
-        fnames.push(f.ident.unwrap());
-        ftypes.push(f.ty);
+    let mut py_names = Vec::<syn::Ident>::new();
+    for f in named.iter() {
+        if is_streamable_cache(f) {
+            continue;
+        }
+        fnames.push(f.ident.clone().unwrap());
+        ftypes.push(f.ty.clone());
+        py_names.push(py_field_name(f, f.ident.clone().unwrap()));
     }
 
-    let fnames_maybe_upper = maybe_upper_fields(py_uppercase, fnames.clone());
+    let fnames_maybe_upper = maybe_upper_fields(py_uppercase, py_names);
+    let fname_strings = field_name_strings(&fnames_maybe_upper);
 
     let ret = quote! {
         #[pyo3::pymethods]
         impl #ident {
             #(
             #[getter]
+            #[pyo3(name = #fname_strings)]
             fn #fnames_maybe_upper<'a> (&self, py: pyo3::Python<'a>) -> pyo3::PyResult<pyo3::Bound<'a, pyo3::PyAny>> {
                 #crate_name::ChiaToPython::to_python(&self.#fnames, py)
             }
@@ -4,11 +4,13 @@ use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
 use proc_macro_crate::{crate_name, FoundCrate};
 use quote::quote;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
 use syn::token::Pub;
 use syn::Lit::Int;
 use syn::{
-    parse_macro_input, Data, DeriveInput, Expr, Fields, FieldsNamed, FieldsUnnamed, Index, Type,
-    Visibility,
+    parse_macro_input, Data, DeriveInput, Expr, Fields, FieldsNamed, FieldsUnnamed, Index, Meta,
+    Token, Type, Visibility,
 };
 
 #[proc_macro_attribute]
@@ -24,7 +26,29 @@ pub fn streamable(attr: TokenStream, item: TokenStream) -> TokenStream {
         }
     };
 
-    let is_message = &attr.to_string() == "message";
+    // the attribute is a comma separated list of options:
+    // message: the type is a protocol message
+    // responses(A, B, ...): the protocol messages that are valid replies to
+    //   this (request) message. Implies message
+    // cached: the python bindings cache the hash and serialization of objects
+    let options = parse_macro_input!(attr with Punctuated::<Meta, Token![,]>::parse_terminated);
+    let mut is_message = false;
+    let mut is_cached = false;
+    let mut responses = Vec::<Ident>::new();
+    for option in &options {
+        match option {
+            Meta::Path(path) if path.is_ident("message") => is_message = true,
+            Meta::Path(path) if path.is_ident("cached") => is_cached = true,
+            Meta::List(list) if list.path.is_ident("responses") => {
+                let types = list
+                    .parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)
+                    .expect("responses() is a list of message types");
+                responses.extend(types);
+                is_message = true;
+            }
+            _ => panic!("unknown streamable option"),
+        }
+    }
 
     let mut input: DeriveInput = parse_macro_input!(item);
     let name = input.ident.clone();
@@ -52,11 +76,55 @@ pub fn streamable(attr: TokenStream, item: TokenStream) -> TokenStream {
         let init_names = field_names.clone();
 
         let initializer = match &data.fields {
+            Fields::Named(..) if is_cached => {
+                quote!( Self { #( #init_names, )* cache: Default::default() } )
+            }
             Fields::Named(..) => quote!( Self { #( #init_names ),* } ),
             Fields::Unnamed(..) => quote!( Self( #( #init_names ),* ) ),
             Fields::Unit => quote!(Self),
         };
 
+        // cached types get an extra field holding the cache. It's marked
+        // #[streamable_cache], which makes the derive macros leave it out of
+        // the serialization, JSON and python constructor
+        if is_cached {
+            let Fields::Named(named) = &mut data.fields else {
+                panic!("only structs with named fields can be cached");
+            };
+            let debug_names: Vec<&Ident> = named
+                .named
+                .iter()
+                .map(|f| f.ident.as_ref().unwrap())
+                .collect();
+            // the Debug representation is the same as the derived one would be
+            // without the cache field
+            extra_impls.push(quote! {
+                impl std::fmt::Debug for #name_ref {
+                    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        f.debug_struct(stringify!(#name_ref))
+                            #( .field(stringify!(#debug_names), &self.#debug_names) )*
+                            .finish()
+                    }
+                }
+            });
+            let field_attrs = if matches!(found_crate, FoundCrate::Itself) {
+                quote! {
+                    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
+                    #[cfg_attr(feature = "serde", serde(skip))]
+                }
+            } else {
+                quote! {}
+            };
+            let field = quote! {
+                #[streamable_cache]
+                #field_attrs
+                pub cache: chia_traits::StreamableCache
+            };
+            named
+                .named
+                .push(syn::Field::parse_named.parse2(field).expect("cache field"));
+        }
+
         if field_names.is_empty() {
             extra_impls.push(quote! {
                 impl Default for #name_ref {
@@ -82,31 +150,77 @@ pub fn streamable(attr: TokenStream, item: TokenStream) -> TokenStream {
                     fn msg_type() -> #chia_protocol::ProtocolMessageTypes {
                         #chia_protocol::ProtocolMessageTypes::#name_ref
                     }
+
+                    fn responses() -> &'static [#chia_protocol::ProtocolMessageTypes] {
+                        &[ #( #chia_protocol::ProtocolMessageTypes::#responses ),* ]
+                    }
                 }
             });
+
+            // the message types are exposed to python as class attributes.
+            // MSG_TYPE is the message ID and RESPONSES the classes of the
+            // valid replies
+            if matches!(found_crate, FoundCrate::Itself) {
+                extra_impls.push(quote! {
+                    #[cfg(feature = "py-bindings")]
+                    #[pyo3::pymethods]
+                    impl #name_ref {
+                        #[classattr]
+                        #[pyo3(name = "MSG_TYPE")]
+                        fn py_msg_type() -> u8 {
+                            #chia_protocol::ProtocolMessageTypes::#name_ref as u8
+                        }
+
+                        #[classattr]
+                        #[pyo3(name = "RESPONSES")]
+                        fn py_responses(py: pyo3::Python<'_>) -> Vec<pyo3::Py<pyo3::types::PyType>> {
+                            vec![ #( py.get_type_bound::<#responses>().unbind() ),* ]
+                        }
+                    }
+                });
+            }
         }
     } else {
         panic!("only structs are supported");
     }
 
-    let main_derives = quote! {
-        #[derive(chia_streamable_macro::Streamable, Hash, Debug, Clone, Eq, PartialEq)]
+    // cached types implement Debug themselves, to leave out the cache
+    let main_derives = if is_cached {
+        quote! {
+            #[derive(chia_streamable_macro::Streamable, Hash, Clone, Eq, PartialEq)]
+        }
+    } else {
+        quote! {
+            #[derive(chia_streamable_macro::Streamable, Hash, Debug, Clone, Eq, PartialEq)]
+        }
     };
 
-    // If you're calling the macro from `chia-protocol`, enable Python bindings and arbitrary conditionally.
+    // If you're calling the macro from `chia-protocol`, enable Python bindings, arbitrary and serde conditionally.
     // Otherwise, you're calling it from an external crate which doesn't have this infrastructure setup.
     // In that case, the caller can add these macros manually if they want to.
     let attrs = if matches!(found_crate, FoundCrate::Itself) {
-        quote! {
-            #[cfg_attr(
-                feature = "py-bindings", pyo3::pyclass(frozen), derive(
+        let py_attrs = if is_cached {
+            quote! {
+                pyo3::pyclass(module = "gold_rs", frozen), derive(
+                    chia_py_streamable_macro::PyJsonDict,
+                    chia_py_streamable_macro::PyStreamable,
+                    chia_py_streamable_macro::PyGetters
+                ), py_cached
+            }
+        } else {
+            quote! {
+                pyo3::pyclass(module = "gold_rs", frozen), derive(
                     chia_py_streamable_macro::PyJsonDict,
                     chia_py_streamable_macro::PyStreamable,
                     chia_py_streamable_macro::PyGetters
                 )
-            )]
+            }
+        };
+        quote! {
+            #[cfg_attr(feature = "py-bindings", #py_attrs)]
             #main_derives
             #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+            #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         }
     } else {
         main_derives
@@ -120,7 +234,13 @@ pub fn streamable(attr: TokenStream, item: TokenStream) -> TokenStream {
     .into()
 }
 
-#[proc_macro_derive(Streamable)]
+// Fields marked #[trailing] are optional fields added to a type after it was
+// first deployed. They must be Options at the end of the struct. When parsing,
+// they're None if the input ends before them, and when serializing, trailing
+// None fields are left out. This lets old and new versions of a protocol
+// message be parsed by the same type, as long as nothing is serialized after
+// it, which is why these types can't be parsed as part of another type.
+#[proc_macro_derive(Streamable, attributes(trailing, py_name, streamable_cache))]
 pub fn chia_streamable_macro(input: TokenStream) -> TokenStream {
     let found_crate = crate_name("chia-traits").expect("chia-traits is present in `Cargo.toml`");
 
@@ -137,6 +257,9 @@ pub fn chia_streamable_macro(input: TokenStream) -> TokenStream {
     let mut fnames = Vec::<Ident>::new();
     let mut findices = Vec::<Index>::new();
     let mut ftypes = Vec::<Type>::new();
+    let mut trailing_names = Vec::<Ident>::new();
+    let mut trailing_types = Vec::<Type>::new();
+    let mut cache_names = Vec::<Ident>::new();
     match data {
         Data::Enum(e) => {
             let mut names = Vec::<Ident>::new();
@@ -193,6 +316,9 @@ pub fn chia_streamable_macro(input: TokenStream) -> TokenStream {
         Data::Struct(s) => match s.fields {
             Fields::Unnamed(FieldsUnnamed { unnamed, .. }) => {
                 for (index, f) in unnamed.iter().enumerate() {
+                    if is_trailing(f) {
+                        panic!("#[trailing] is only supported on named fields");
+                    }
                     findices.push(Index::from(index));
                     ftypes.push(f.ty.clone());
                 }
@@ -200,14 +326,84 @@ pub fn chia_streamable_macro(input: TokenStream) -> TokenStream {
             Fields::Unit => {}
             Fields::Named(FieldsNamed { named, .. }) => {
                 for f in named.iter() {
-                    fnames.push(f.ident.as_ref().unwrap().clone());
-                    ftypes.push(f.ty.clone());
+                    let name = f.ident.as_ref().unwrap().clone();
+                    if is_streamable_cache(f) {
+                        cache_names.push(name);
+                        continue;
+                    }
+                    if is_trailing(f) {
+                        if !is_option(&f.ty) {
+                            panic!("#[trailing] field {name} must be an Option");
+                        }
+                        trailing_names.push(name);
+                        trailing_types.push(f.ty.clone());
+                    } else if !trailing_names.is_empty() {
+                        panic!("field {name} follows a #[trailing] field");
+                    } else {
+                        fnames.push(name);
+                        ftypes.push(f.ty.clone());
+                    }
                 }
             }
         },
     };
 
-    if !fnames.is_empty() {
+    if !trailing_names.is_empty() {
+        // the trailing fields up to the last one that's set are serialized
+        let trailing_indices: Vec<usize> = (0..trailing_names.len()).collect();
+        let num_trailing = quote! {
+            [#(self.#trailing_names.is_some()),*]
+                .iter()
+                .rposition(|set| *set)
+                .map_or(0, |i| i + 1)
+        };
+        let ret = quote! {
+            impl #crate_name::Streamable for #ident {
+                const HAS_TRAILING_FIELDS: bool = true;
+
+                fn update_digest(&self, digest: &mut sha2::Sha256) {
+                    #(self.#fnames.update_digest(digest);)*
+                    let num_trailing = #num_trailing;
+                    #(if #trailing_indices < num_trailing {
+                        self.#trailing_names.update_digest(digest);
+                    })*
+                }
+                fn stream(&self, out: &mut Vec<u8>) -> #crate_name::chia_error::Result<()> {
+                    #(self.#fnames.stream(out)?;)*
+                    let num_trailing = #num_trailing;
+                    #(if #trailing_indices < num_trailing {
+                        self.#trailing_names.stream(out)?;
+                    })*
+                    Ok(())
+                }
+                fn stream_to<W: std::io::Write>(&self, out: &mut W) -> #crate_name::chia_error::Result<()> {
+                    #(self.#fnames.stream_to(out)?;)*
+                    let num_trailing = #num_trailing;
+                    #(if #trailing_indices < num_trailing {
+                        self.#trailing_names.stream_to(out)?;
+                    })*
+                    Ok(())
+                }
+                fn parse<const TRUSTED: bool>(input: &mut std::io::Cursor<&[u8]>) -> #crate_name::chia_error::Result<Self> {
+                    #( let () = #crate_name::NotNested::<#ftypes>::OK; )*
+                    #( let () = #crate_name::NotNested::<#trailing_types>::OK; )*
+                    Ok(Self {
+                        #( #fnames: <#ftypes as #crate_name::Streamable>::parse::<TRUSTED>(input)?, )*
+                        #( #trailing_names: if input.position() < input.get_ref().len() as u64 {
+                            <#trailing_types as #crate_name::Streamable>::parse::<TRUSTED>(input)?
+                        } else {
+                            None
+                        }, )*
+                        #( #cache_names: Default::default(), )*
+                    })
+                }
+                fn heap_size(&self) -> usize {
+                    0 #(+ self.#fnames.heap_size())* #(+ self.#trailing_names.heap_size())*
+                }
+            }
+        };
+        ret.into()
+    } else if !fnames.is_empty() {
         let ret = quote! {
             impl #crate_name::Streamable for #ident {
                 fn update_digest(&self, digest: &mut sha2::Sha256) {
@@ -217,8 +413,19 @@ pub fn chia_streamable_macro(input: TokenStream) -> TokenStream {
                     #(self.#fnames.stream(out)?;)*
                     Ok(())
                 }
+                fn stream_to<W: std::io::Write>(&self, out: &mut W) -> #crate_name::chia_error::Result<()> {
+                    #(self.#fnames.stream_to(out)?;)*
+                    Ok(())
+                }
                 fn parse<const TRUSTED: bool>(input: &mut std::io::Cursor<&[u8]>) -> #crate_name::chia_error::Result<Self> {
-                    Ok(Self { #( #fnames: <#ftypes as #crate_name::Streamable>::parse::<TRUSTED>(input)?, )* })
+                    #( let () = #crate_name::NotNested::<#ftypes>::OK; )*
+                    Ok(Self {
+                        #( #fnames: <#ftypes as #crate_name::Streamable>::parse::<TRUSTED>(input)?, )*
+                        #( #cache_names: Default::default(), )*
+                    })
+                }
+                fn heap_size(&self) -> usize {
+                    0 #(+ self.#fnames.heap_size())*
                 }
             }
         };
@@ -233,9 +440,17 @@ pub fn chia_streamable_macro(input: TokenStream) -> TokenStream {
                     #(self.#findices.stream(out)?;)*
                     Ok(())
                 }
+                fn stream_to<W: std::io::Write>(&self, out: &mut W) -> #crate_name::chia_error::Result<()> {
+                    #(self.#findices.stream_to(out)?;)*
+                    Ok(())
+                }
                 fn parse<const TRUSTED: bool>(input: &mut std::io::Cursor<&[u8]>) -> #crate_name::chia_error::Result<Self> {
+                    #( let () = #crate_name::NotNested::<#ftypes>::OK; )*
                     Ok(Self( #( <#ftypes as #crate_name::Streamable>::parse::<TRUSTED>(input)?, )* ))
                 }
+                fn heap_size(&self) -> usize {
+                    0 #(+ self.#findices.heap_size())*
+                }
             }
         };
         ret.into()
@@ -255,3 +470,28 @@ pub fn chia_streamable_macro(input: TokenStream) -> TokenStream {
         ret.into()
     }
 }
+
+fn is_streamable_cache(field: &syn::Field) -> bool {
+    field
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("streamable_cache"))
+}
+
+fn is_trailing(field: &syn::Field) -> bool {
+    field
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("trailing"))
+}
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
@@ -0,0 +1,481 @@
+use clvmr::allocator::{Allocator, NodePtr, SExp};
+use clvmr::number::Number;
+use std::fmt;
+
+// A minimal assembler and disassembler for CLVM source, compatible with the
+// output of clvm_tools' opc and opd. This is meant for debugging and writing
+// tests, it is not a compiler. There's no support for macros or the Chialisp
+// language, just the CLVM structure and operator names.
+
+const KEYWORDS: &[(&str, u8)] = &[
+    ("q", 0x01),
+    ("a", 0x02),
+    ("i", 0x03),
+    ("c", 0x04),
+    ("f", 0x05),
+    ("r", 0x06),
+    ("l", 0x07),
+    ("x", 0x08),
+    ("=", 0x09),
+    (">s", 0x0a),
+    ("sha256", 0x0b),
+    ("substr", 0x0c),
+    ("strlen", 0x0d),
+    ("concat", 0x0e),
+    ("+", 0x10),
+    ("-", 0x11),
+    ("*", 0x12),
+    ("/", 0x13),
+    ("divmod", 0x14),
+    (">", 0x15),
+    ("ash", 0x16),
+    ("lsh", 0x17),
+    ("logand", 0x18),
+    ("logior", 0x19),
+    ("logxor", 0x1a),
+    ("lognot", 0x1b),
+    ("point_add", 0x1d),
+    ("pubkey_for_exp", 0x1e),
+    ("not", 0x20),
+    ("any", 0x21),
+    ("all", 0x22),
+    ("softfork", 0x24),
+    ("coinid", 0x30),
+    ("g1_subtract", 0x31),
+    ("g1_multiply", 0x32),
+    ("g1_negate", 0x33),
+    ("g2_add", 0x34),
+    ("g2_subtract", 0x35),
+    ("g2_multiply", 0x36),
+    ("g2_negate", 0x37),
+    ("g1_map", 0x38),
+    ("g2_map", 0x39),
+    ("bls_pairing_identity", 0x3a),
+    ("bls_verify", 0x3b),
+    ("modpow", 0x3c),
+    ("%", 0x3d),
+    ("keccak256", 0x3e),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssembleError {
+    pub message: String,
+    // byte offset into the source
+    pub offset: usize,
+}
+
+impl fmt::Display for AssembleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}", self.message, self.offset)
+    }
+}
+
+impl std::error::Error for AssembleError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    Open,
+    Close,
+    Dot,
+    Quoted(&'a str),
+    Atom(&'a str),
+}
+
+struct Tokenizer<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn error(&self, message: &str) -> AssembleError {
+        AssembleError {
+            message: message.to_string(),
+            offset: self.pos,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let bytes = self.src.as_bytes();
+        while self.pos < bytes.len() {
+            if bytes[self.pos] == b';' {
+                while self.pos < bytes.len() && bytes[self.pos] != b'\n' {
+                    self.pos += 1;
+                }
+            } else if bytes[self.pos].is_ascii_whitespace() {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    // returns the token and its offset
+    fn next(&mut self) -> Result<Option<(Token<'a>, usize)>, AssembleError> {
+        self.skip_whitespace();
+        let bytes = self.src.as_bytes();
+        let start = self.pos;
+        let Some(c) = bytes.get(start) else {
+            return Ok(None);
+        };
+        let token = match c {
+            b'(' => {
+                self.pos += 1;
+                Token::Open
+            }
+            b')' => {
+                self.pos += 1;
+                Token::Close
+            }
+            b'"' | b'\'' => {
+                let Some(len) = self.src[start + 1..].find(*c as char) else {
+                    return Err(self.error("unterminated string"));
+                };
+                self.pos = start + 1 + len + 1;
+                Token::Quoted(&self.src[start + 1..start + 1 + len])
+            }
+            _ => {
+                while self.pos < bytes.len()
+                    && !bytes[self.pos].is_ascii_whitespace()
+                    && !matches!(bytes[self.pos], b'(' | b')' | b';')
+                {
+                    self.pos += 1;
+                }
+                match &self.src[start..self.pos] {
+                    "." => Token::Dot,
+                    s => Token::Atom(s),
+                }
+            }
+        };
+        Ok(Some((token, start)))
+    }
+}
+
+fn parse_atom(a: &mut Allocator, s: &str, offset: usize) -> Result<NodePtr, AssembleError> {
+    let error = |message: &str| AssembleError {
+        message: message.to_string(),
+        offset,
+    };
+    let oom = |_| error("out of memory");
+
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+        let n: Number = s.parse().map_err(|_| error("invalid integer"))?;
+        return a.new_number(n).map_err(oom);
+    }
+    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        let buf = if hex.len() % 2 == 1 {
+            hex::decode(format!("0{hex}"))
+        } else {
+            hex::decode(hex)
+        }
+        .map_err(|_| error("invalid hex"))?;
+        return a.new_atom(&buf).map_err(oom);
+    }
+    let keyword = s.strip_prefix('#').unwrap_or(s);
+    if let Some((_, op)) = KEYWORDS.iter().find(|(name, _)| *name == keyword) {
+        return a.new_atom(&[*op]).map_err(oom);
+    }
+    a.new_atom(s.as_bytes()).map_err(oom)
+}
+
+fn parse_expr(
+    a: &mut Allocator,
+    tokens: &mut Tokenizer<'_>,
+    token: (Token<'_>, usize),
+) -> Result<NodePtr, AssembleError> {
+    let oom = |_| AssembleError {
+        message: "out of memory".to_string(),
+        offset: token.1,
+    };
+    match token.0 {
+        Token::Atom(s) => parse_atom(a, s, token.1),
+        Token::Quoted(s) => a.new_atom(s.as_bytes()).map_err(oom),
+        Token::Close => Err(AssembleError {
+            message: "unexpected )".to_string(),
+            offset: token.1,
+        }),
+        Token::Dot => Err(AssembleError {
+            message: "unexpected .".to_string(),
+            offset: token.1,
+        }),
+        Token::Open => {
+            let mut items = Vec::<NodePtr>::new();
+            let mut tail = NodePtr::NIL;
+            loop {
+                let Some(next) = tokens.next()? else {
+                    return Err(tokens.error("missing )"));
+                };
+                match next.0 {
+                    Token::Close => break,
+                    Token::Dot => {
+                        if items.is_empty() {
+                            return Err(AssembleError {
+                                message: "unexpected .".to_string(),
+                                offset: next.1,
+                            });
+                        }
+                        let Some(next) = tokens.next()? else {
+                            return Err(tokens.error("missing )"));
+                        };
+                        tail = parse_expr(a, tokens, next)?;
+                        match tokens.next()? {
+                            Some((Token::Close, _)) => break,
+                            Some((_, offset)) => {
+                                return Err(AssembleError {
+                                    message: "expected )".to_string(),
+                                    offset,
+                                });
+                            }
+                            None => {
+                                return Err(tokens.error("missing )"));
+                            }
+                        }
+                    }
+                    _ => {
+                        items.push(parse_expr(a, tokens, next)?);
+                    }
+                }
+            }
+            for item in items.into_iter().rev() {
+                tail = a.new_pair(item, tail).map_err(oom)?;
+            }
+            Ok(tail)
+        }
+    }
+}
+
+// parses CLVM source, like "(a (q . 1) (q . 2))", into the allocator. Operator
+// names are translated into their opcodes wherever they appear
+pub fn assemble(a: &mut Allocator, src: &str) -> Result<NodePtr, AssembleError> {
+    let mut tokens = Tokenizer { src, pos: 0 };
+    let Some(token) = tokens.next()? else {
+        return Err(tokens.error("empty input"));
+    };
+    let node = parse_expr(a, &mut tokens, token)?;
+    if let Some((_, offset)) = tokens.next()? {
+        return Err(AssembleError {
+            message: "unexpected trailing input".to_string(),
+            offset,
+        });
+    }
+    Ok(node)
+}
+
+// atoms of up to two bytes with a canonical integer representation are printed
+// as integers. Longer, printable, atoms as strings and the rest as hex
+fn disassemble_atom(a: &Allocator, node: NodePtr, out: &mut String) {
+    let atom = a.atom(node);
+    let buf = atom.as_ref();
+    if buf.is_empty() {
+        out.push_str("()");
+        return;
+    }
+    if buf.len() > 2 {
+        let printable = buf.iter().all(|b| {
+            b.is_ascii_graphic() || matches!(b, b' ' | b'\t' | b'\n' | b'\r' | 0x0b | 0x0c)
+        });
+        if printable {
+            let s = std::str::from_utf8(buf).expect("ascii");
+            if !s.contains('"') {
+                out.push_str(&format!("\"{s}\""));
+                return;
+            } else if !s.contains('\'') {
+                out.push_str(&format!("'{s}'"));
+                return;
+            }
+        }
+    } else {
+        let canonical = match buf {
+            [0x00] => false,
+            [0x00, b] => *b >= 0x80,
+            [0xff, b] => *b < 0x80,
+            _ => true,
+        };
+        if canonical {
+            out.push_str(&a.number(node).to_string());
+            return;
+        }
+    }
+    out.push_str("0x");
+    out.push_str(&hex::encode(buf));
+}
+
+enum DisassembleOp {
+    Node(NodePtr, bool),
+    ListRest(NodePtr),
+    CloseParen,
+}
+
+// the tree is walked with an explicit stack, since programs can be nested
+// deeper than the call stack allows. Returns None as soon as the output
+// exceeds max_len
+fn disassemble_impl(a: &Allocator, node: NodePtr, max_len: usize) -> Option<String> {
+    let mut out = String::new();
+    let mut ops = vec![DisassembleOp::Node(node, false)];
+    while let Some(op) = ops.pop() {
+        match op {
+            DisassembleOp::Node(node, allow_keyword) => match a.sexp(node) {
+                SExp::Atom => {
+                    let keyword = if allow_keyword {
+                        match a.atom(node).as_ref() {
+                            [op] => KEYWORDS.iter().find(|(_, code)| code == op),
+                            _ => None,
+                        }
+                    } else {
+                        None
+                    };
+                    if let Some((name, _)) = keyword {
+                        out.push_str(name);
+                    } else {
+                        disassemble_atom(a, node, &mut out);
+                    }
+                }
+                SExp::Pair(first, rest) => {
+                    out.push('(');
+                    ops.push(DisassembleOp::ListRest(rest));
+                    ops.push(DisassembleOp::Node(first, true));
+                }
+            },
+            DisassembleOp::ListRest(rest) => match a.sexp(rest) {
+                SExp::Pair(item, next) => {
+                    out.push(' ');
+                    // only the first item of a list is considered an operator
+                    ops.push(DisassembleOp::ListRest(next));
+                    ops.push(DisassembleOp::Node(item, false));
+                }
+                SExp::Atom => {
+                    if a.atom(rest).as_ref().is_empty() {
+                        out.push(')');
+                    } else {
+                        out.push_str(" . ");
+                        ops.push(DisassembleOp::CloseParen);
+                        ops.push(DisassembleOp::Node(rest, false));
+                    }
+                }
+            },
+            DisassembleOp::CloseParen => out.push(')'),
+        }
+        if out.len() > max_len {
+            return None;
+        }
+    }
+    Some(out)
+}
+
+// prints the CLVM structure as source. The first item of every list is
+// printed as an operator name, if it's an opcode
+pub fn disassemble(a: &Allocator, node: NodePtr) -> String {
+    disassemble_impl(a, node, usize::MAX).expect("no limit")
+}
+
+// like disassemble(), but gives up and returns None once the output is longer
+// than max_len. With back references, a small serialized program can expand
+// to an exponentially larger tree, so this is meant for untrusted input
+pub fn disassemble_with_limit(a: &Allocator, node: NodePtr, max_len: usize) -> Option<String> {
+    disassemble_impl(a, node, max_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clvmr::serde::node_to_bytes;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("()", "80")]
+    #[case("1", "01")]
+    #[case("-1", "81ff")]
+    #[case("128", "820080")]
+    #[case("0x00", "00")]
+    #[case("0xabcdef", "83abcdef")]
+    #[case("\"foobar\"", "86666f6f626172")]
+    #[case("'foo\"bar'", "87666f6f22626172")]
+    #[case("(q . 1)", "ff0101")]
+    #[case("(a (q . 1) 1)", "ff02ffff0101ff0180")]
+    #[case("(+ 2 5)", "ff10ff02ff0580")]
+    #[case("(q 2 . 3)", "ff01ff0203")]
+    #[case("((c 2 3) \"abc\")", "ffff04ff02ff0380ff8361626380")]
+    fn test_roundtrip(#[case] src: &str, #[case] expected: &str) {
+        let mut a = Allocator::new();
+        let node = assemble(&mut a, src).expect("assemble");
+        assert_eq!(hex::encode(node_to_bytes(&a, node).unwrap()), expected);
+        assert_eq!(disassemble(&a, node), src);
+    }
+
+    #[rstest]
+    // keywords are translated everywhere, and comments are ignored
+    #[case("(q . q) ; comment", "(q . 1)")]
+    #[case("  ( sha256   \"abc\"\n 0x01 )", "(sha256 \"abc\" 1)")]
+    #[case("(#a 2)", "(a 2)")]
+    #[case("(foobar)", "(\"foobar\")")]
+    #[case("(() ())", "(() ())")]
+    // keywords are only printed in the operator position
+    #[case("(c c c)", "(c 4 4)")]
+    #[case("c", "4")]
+    fn test_normalize(#[case] src: &str, #[case] expected: &str) {
+        let mut a = Allocator::new();
+        let node = assemble(&mut a, src).expect("assemble");
+        assert_eq!(disassemble(&a, node), expected);
+    }
+
+    #[rstest]
+    #[case("", 0)]
+    #[case("(1 2", 4)]
+    #[case(")", 0)]
+    #[case("(. 1)", 1)]
+    #[case("(1 . 2 3)", 7)]
+    #[case("1 2", 2)]
+    #[case("\"foo", 0)]
+    #[case("0xfoo", 0)]
+    fn test_errors(#[case] src: &str, #[case] offset: usize) {
+        let mut a = Allocator::new();
+        assert_eq!(assemble(&mut a, src).unwrap_err().offset, offset);
+    }
+
+    #[test]
+    fn test_deep_nesting() {
+        // this would overflow the stack if the tree was walked recursively
+        let mut a = Allocator::new();
+        let mut node = NodePtr::NIL;
+        for _ in 0..1_000_000 {
+            node = a.new_pair(node, NodePtr::NIL).unwrap();
+        }
+        let out = disassemble(&a, node);
+        assert_eq!(out.len(), 2_000_002);
+        assert!(out.starts_with("(((("));
+        assert!(out.ends_with("()))"));
+    }
+
+    #[test]
+    fn test_limit() {
+        let mut a = Allocator::new();
+        // every level doubles the size of the output
+        let mut node = a.new_atom(b"foobar").unwrap();
+        for _ in 0..64 {
+            node = a.new_pair(node, node).unwrap();
+        }
+        assert_eq!(disassemble_with_limit(&a, node, 1_000_000), None);
+
+        let node = assemble(&mut a, "(a (q . 1) 1)").unwrap();
+        assert_eq!(
+            disassemble_with_limit(&a, node, 13).as_deref(),
+            Some("(a (q . 1) 1)")
+        );
+        assert_eq!(disassemble_with_limit(&a, node, 12), None);
+    }
+
+    #[test]
+    fn test_hex_atoms() {
+        let mut a = Allocator::new();
+        for (buf, expected) in [
+            (&[0x00, 0x80][..], "128"),
+            (&[0x00, 0x01], "0x0001"),
+            (&[0xff, 0x7f], "0xff7f"),
+            (&[0xff, 0x80], "-128"),
+            (&[0x01, 0x02, 0x03], "0x010203"),
+        ] {
+            let node = a.new_atom(buf).unwrap();
+            assert_eq!(disassemble(&a, node), expected);
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use std::fmt;
+
+// The ways a CLVM serialization can be non-canonical, i.e. a valid
+// serialization that's not the one a conforming serializer produces. The
+// variants (except EndOfBuffer) carry the offset of the offending byte
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NonCanonical {
+    // the buffer ends in the middle of the program
+    EndOfBuffer,
+    // the atom length prefix encodes a length CLVM doesn't support
+    InvalidAtomLength(usize),
+    // a single byte atom in the range 0x00 - 0x7f must be serialized as the
+    // byte itself, without a length prefix
+    SmallAtomWithPrefix(usize),
+    // the atom length prefix is longer than it needs to be
+    NonMinimalLength(usize),
+    // a back-reference, without back-references being allowed
+    Backref(usize),
+    // the path of a back-reference is empty or has leading zero bytes
+    BackrefPath(usize),
+    // there are bytes left after the program
+    TrailingBytes(usize),
+}
+
+impl NonCanonical {
+    // the name of the rule that's violated
+    pub fn rule(&self) -> &'static str {
+        match self {
+            Self::EndOfBuffer => "end_of_buffer",
+            Self::InvalidAtomLength(_) => "invalid_atom_length",
+            Self::SmallAtomWithPrefix(_) => "small_atom_with_prefix",
+            Self::NonMinimalLength(_) => "non_minimal_length",
+            Self::Backref(_) => "backref",
+            Self::BackrefPath(_) => "backref_path",
+            Self::TrailingBytes(_) => "trailing_bytes",
+        }
+    }
+
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            Self::EndOfBuffer => None,
+            Self::InvalidAtomLength(pos)
+            | Self::SmallAtomWithPrefix(pos)
+            | Self::NonMinimalLength(pos)
+            | Self::Backref(pos)
+            | Self::BackrefPath(pos)
+            | Self::TrailingBytes(pos) => Some(*pos),
+        }
+    }
+}
+
+impl fmt::Display for NonCanonical {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.offset() {
+            Some(pos) => write!(f, "{} at offset {pos}", self.rule()),
+            None => f.write_str(self.rule()),
+        }
+    }
+}
+
+impl std::error::Error for NonCanonical {}
+
+// the smallest atom length that needs a length prefix of the index number of
+// bytes. Lengths from 0x400000000 are not supported by CLVM at all
+const MIN_LENGTH: [u64; 7] = [0, 0, 0x40, 0x2000, 0x10_0000, 0x800_0000, 0x4_0000_0000];
+
+// parses the atom starting at pos and returns its bytes
+fn parse_atom<'a>(buf: &'a [u8], pos: &mut usize) -> Result<&'a [u8], NonCanonical> {
+    let start = *pos;
+    let b = *buf.get(start).ok_or(NonCanonical::EndOfBuffer)?;
+    if b <= 0x80 {
+        *pos += 1;
+        return Ok(if b == 0x80 { &[] } else { &buf[start..=start] });
+    }
+
+    // the number of leading 1-bits is the size of the length prefix
+    let prefix_len = b.leading_ones() as usize;
+    if prefix_len >= MIN_LENGTH.len() {
+        return Err(NonCanonical::InvalidAtomLength(start));
+    }
+    let prefix = buf
+        .get(start..start + prefix_len)
+        .ok_or(NonCanonical::EndOfBuffer)?;
+    let mut len = u64::from(b & (0xff >> prefix_len));
+    for byte in &prefix[1..] {
+        len = (len << 8) | u64::from(*byte);
+    }
+    if len >= MIN_LENGTH[MIN_LENGTH.len() - 1] {
+        return Err(NonCanonical::InvalidAtomLength(start));
+    }
+    if len < MIN_LENGTH[prefix_len] {
+        return Err(NonCanonical::NonMinimalLength(start));
+    }
+
+    let atom_start = start + prefix_len;
+    let atom = usize::try_from(len)
+        .ok()
+        .and_then(|len| buf.get(atom_start..atom_start.checked_add(len)?))
+        .ok_or(NonCanonical::EndOfBuffer)?;
+    if len == 1 && atom[0] < 0x80 {
+        return Err(NonCanonical::SmallAtomWithPrefix(start));
+    }
+    *pos = atom_start + atom.len();
+    Ok(atom)
+}
+
+// Like clvmr's serialized_length_from_bytes(), but only accepts the canonical
+// serialization of the program at the start of buf. Returns the length of the
+// serialization.
+pub fn canonical_serialized_length(buf: &[u8], allow_backrefs: bool) -> Result<u64, NonCanonical> {
+    let mut pos = 0;
+    // the number of values left to parse. A pair is followed by two values
+    let mut pending: u64 = 1;
+    while pending > 0 {
+        match *buf.get(pos).ok_or(NonCanonical::EndOfBuffer)? {
+            0xff => {
+                pos += 1;
+                pending += 1;
+                continue;
+            }
+            0xfe => {
+                if !allow_backrefs {
+                    return Err(NonCanonical::Backref(pos));
+                }
+                let start = pos;
+                pos += 1;
+                let path = parse_atom(buf, &mut pos)?;
+                if matches!(path.first(), None | Some(0)) {
+                    return Err(NonCanonical::BackrefPath(start));
+                }
+            }
+            _ => {
+                parse_atom(buf, &mut pos)?;
+            }
+        }
+        pending -= 1;
+    }
+    Ok(pos as u64)
+}
+
+// Checks that buf is the canonical serialization of a CLVM program, without
+// anything following it. This is stricter than deserializing the program,
+// which accepts some alternative encodings of the same value. A program that
+// passes this check round-trips through deserialization and serialization
+// unchanged (as long as it has no back-references).
+pub fn check_canonical_serialization(buf: &[u8], allow_backrefs: bool) -> Result<(), NonCanonical> {
+    let len = canonical_serialized_length(buf, allow_backrefs)? as usize;
+    if len != buf.len() {
+        return Err(NonCanonical::TrailingBytes(len));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clvmr::serde::{node_from_bytes, node_to_bytes, serialized_length_from_bytes};
+    use clvmr::Allocator;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("80")]
+    #[case("01")]
+    #[case("7f")]
+    #[case("8180")]
+    #[case("81ff")]
+    #[case("820001")]
+    #[case("ff01ff0280")]
+    #[case("ffff8080ff8080")]
+    fn test_canonical(#[case] hex: &str) {
+        let buf = hex::decode(hex).unwrap();
+        assert_eq!(check_canonical_serialization(&buf, false), Ok(()));
+        assert_eq!(
+            canonical_serialized_length(&buf, false).unwrap(),
+            serialized_length_from_bytes(&buf).unwrap()
+        );
+        // and it round-trips
+        let mut a = Allocator::new();
+        let node = node_from_bytes(&mut a, &buf).unwrap();
+        assert_eq!(node_to_bytes(&a, node).unwrap(), buf);
+    }
+
+    #[test]
+    fn test_atom_lengths() {
+        for len in [0x3f_usize, 0x40, 0x1fff, 0x2000] {
+            let mut a = Allocator::new();
+            let atom = a.new_atom(&vec![0xaa; len]).unwrap();
+            let buf = node_to_bytes(&a, atom).unwrap();
+            assert_eq!(check_canonical_serialization(&buf, false), Ok(()));
+        }
+    }
+
+    #[rstest]
+    #[case("", NonCanonical::EndOfBuffer)]
+    #[case("ff01", NonCanonical::EndOfBuffer)]
+    #[case("8201", NonCanonical::EndOfBuffer)]
+    #[case("8101", NonCanonical::SmallAtomWithPrefix(0))]
+    #[case("ff018100", NonCanonical::SmallAtomWithPrefix(2))]
+    #[case("c00101", NonCanonical::NonMinimalLength(0))]
+    #[case("c0028080", NonCanonical::NonMinimalLength(0))]
+    #[case("c000", NonCanonical::NonMinimalLength(0))]
+    #[case("e0000280ff", NonCanonical::NonMinimalLength(0))]
+    #[case("fc0000000000", NonCanonical::NonMinimalLength(0))]
+    #[case("fc0400000000", NonCanonical::InvalidAtomLength(0))]
+    #[case("ff01fe02", NonCanonical::Backref(2))]
+    #[case("0101", NonCanonical::TrailingBytes(1))]
+    #[case("ff018080", NonCanonical::TrailingBytes(3))]
+    fn test_non_canonical(#[case] hex: &str, #[case] expected: NonCanonical) {
+        let buf = hex::decode(hex).unwrap();
+        assert_eq!(check_canonical_serialization(&buf, false), Err(expected));
+    }
+
+    #[rstest]
+    #[case("ff01fe02", Ok(()))]
+    #[case("ff01fe8102", Err(NonCanonical::SmallAtomWithPrefix(3)))]
+    #[case("ff01fe80", Err(NonCanonical::BackrefPath(2)))]
+    #[case("ff01fe820002", Err(NonCanonical::BackrefPath(2)))]
+    fn test_backrefs(#[case] hex: &str, #[case] expected: Result<(), NonCanonical>) {
+        let buf = hex::decode(hex).unwrap();
+        assert_eq!(check_canonical_serialization(&buf, true), expected);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            NonCanonical::NonMinimalLength(3).to_string(),
+            "non_minimal_length at offset 3"
+        );
+        assert_eq!(NonCanonical::EndOfBuffer.to_string(), "end_of_buffer");
+    }
+}
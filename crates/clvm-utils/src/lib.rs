@@ -24,12 +24,20 @@
 //! // (a (q . 1) (c (q . 42) (c (q . 75) 1)))
 //! assert_eq!(hex, "ff02ffff0101ffff04ffff012affff04ffff014bff01808080");
 
+#[cfg(feature = "assemble")]
+mod assemble;
+mod canonical;
 mod curried_program;
 mod curry_tree_hash;
 mod hash_encoder;
 mod tree_hash;
+mod tree_hasher;
 
+#[cfg(feature = "assemble")]
+pub use assemble::*;
+pub use canonical::*;
 pub use curried_program::*;
 pub use curry_tree_hash::*;
 pub use hash_encoder::*;
 pub use tree_hash::*;
+pub use tree_hasher::*;
@@ -1,6 +1,7 @@
 use clvmr::allocator::{Allocator, NodePtr, SExp};
 use clvmr::serde::node_from_bytes_backrefs_record;
 use clvmr::sha2::{Digest, Sha256};
+use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
 use std::{fmt, io};
@@ -162,6 +163,86 @@ pub fn tree_hash_cached(
     hashes[0]
 }
 
+pub(crate) fn bad_encoding() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, "bad encoding")
+}
+
+// returns the range of the atom payload starting at pos, and the position
+// following it
+pub(crate) fn parse_atom(buf: &[u8], pos: usize) -> io::Result<(usize, usize)> {
+    let b = buf[pos];
+    if b == 0x80 {
+        return Ok((pos + 1, pos + 1));
+    }
+    if b < 0x80 {
+        return Ok((pos, pos + 1));
+    }
+    let prefix_len = b.leading_ones() as usize;
+    if prefix_len > 6 {
+        return Err(bad_encoding());
+    }
+    let mut size = u64::from(b & (0xff >> (prefix_len + 1)));
+    let Some(prefix) = buf.get(pos + 1..pos + prefix_len) else {
+        return Err(bad_encoding());
+    };
+    for b in prefix {
+        size = (size << 8) | u64::from(*b);
+    }
+    let start = pos + prefix_len;
+    let end = usize::try_from(size)
+        .ok()
+        .and_then(|s| start.checked_add(s))
+        .filter(|end| *end <= buf.len())
+        .ok_or_else(bad_encoding)?;
+    Ok((start, end))
+}
+
+// computes the tree hash directly from the serialized form, without parsing
+// it into an Allocator. The memory use only depends on the depth of the tree.
+// Back-references can't be resolved without the tree, so if the serialization
+// uses them, this returns None
+pub fn tree_hash_serialized(buf: &[u8]) -> io::Result<Option<TreeHash>> {
+    enum Op {
+        Parse,
+        Cons,
+    }
+
+    let mut hashes = Vec::<TreeHash>::new();
+    let mut ops = vec![Op::Parse];
+    let mut pos = 0;
+    while let Some(op) = ops.pop() {
+        match op {
+            Op::Parse => match buf.get(pos) {
+                None => {
+                    return Err(bad_encoding());
+                }
+                Some(0xff) => {
+                    pos += 1;
+                    ops.push(Op::Cons);
+                    ops.push(Op::Parse);
+                    ops.push(Op::Parse);
+                }
+                Some(0xfe) => {
+                    return Ok(None);
+                }
+                Some(_) => {
+                    let (start, end) = parse_atom(buf, pos)?;
+                    hashes.push(tree_hash_atom(&buf[start..end]));
+                    pos = end;
+                }
+            },
+            Op::Cons => {
+                let rest = hashes.pop().unwrap();
+                let first = hashes.pop().unwrap();
+                hashes.push(tree_hash_pair(first, rest));
+            }
+        }
+    }
+
+    assert!(hashes.len() == 1);
+    Ok(Some(hashes[0]))
+}
+
 pub fn tree_hash_from_bytes(buf: &[u8]) -> io::Result<TreeHash> {
     let mut a = Allocator::new();
     let (node, backrefs) = node_from_bytes_backrefs_record(&mut a, buf)?;
@@ -169,6 +250,14 @@ pub fn tree_hash_from_bytes(buf: &[u8]) -> io::Result<TreeHash> {
     Ok(tree_hash_cached(&a, node, &backrefs, &mut cache))
 }
 
+// hashes the serialized programs in parallel, the hashes are returned in the
+// same order as the programs
+pub fn tree_hashes_from_bytes<T: AsRef<[u8]> + Sync>(bufs: &[T]) -> io::Result<Vec<TreeHash>> {
+    bufs.par_iter()
+        .map(|buf| tree_hash_from_bytes(buf.as_ref()))
+        .collect()
+}
+
 #[test]
 fn test_tree_hash() {
     let mut a = Allocator::new();
@@ -259,6 +348,35 @@ fn test_tree_hash_from_bytes() {
     assert_eq!(hash1, hash3);
 }
 
+#[test]
+fn test_tree_hash_serialized() {
+    use clvmr::serde::{node_to_bytes, node_to_bytes_backrefs};
+
+    let mut a = Allocator::new();
+    let atom1 = a.new_atom(&[0x80; 100]).unwrap();
+    let atom2 = a.new_atom(&[]).unwrap();
+    let atom3 = a.new_atom(&[0x7f]).unwrap();
+    let node1 = a.new_pair(atom1, atom2).unwrap();
+    let node2 = a.new_pair(atom3, node1).unwrap();
+    let root = a.new_pair(node2, node2).unwrap();
+
+    let buf = node_to_bytes(&a, root).expect("node_to_bytes");
+    assert_eq!(
+        tree_hash_serialized(&buf).expect("tree_hash_serialized"),
+        Some(tree_hash(&a, root))
+    );
+
+    let buf = node_to_bytes_backrefs(&a, root).expect("node_to_bytes_backrefs");
+    assert_eq!(
+        tree_hash_serialized(&buf).expect("tree_hash_serialized"),
+        None
+    );
+
+    // truncated
+    assert!(tree_hash_serialized(&[0xff, 0x01]).is_err());
+    assert!(tree_hash_serialized(&[0x82, 0x01]).is_err());
+}
+
 #[cfg(test)]
 use rstest::rstest;
 
@@ -387,3 +505,17 @@ fn test_tree_hash_atom() {
         test_sha256_atom(&[0xff, val]);
     }
 }
+
+#[test]
+fn test_tree_hashes_from_bytes() {
+    let bufs: Vec<&[u8]> = vec![b"\x80", b"\xff\x01\xff\x02\x80", b"\x01"];
+    let hashes = tree_hashes_from_bytes(&bufs).expect("tree_hashes_from_bytes");
+    assert_eq!(hashes.len(), 3);
+    for (buf, hash) in bufs.iter().zip(hashes) {
+        assert_eq!(tree_hash_from_bytes(buf).unwrap(), hash);
+    }
+
+    // an invalid program fails the whole batch
+    let bufs: Vec<&[u8]> = vec![b"\x80", b"\xff\x01"];
+    assert!(tree_hashes_from_bytes(&bufs).is_err());
+}
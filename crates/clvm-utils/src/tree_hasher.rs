@@ -0,0 +1,244 @@
+use crate::tree_hash::{bad_encoding, parse_atom};
+use crate::{tree_hash_atom, tree_hash_from_bytes, tree_hash_pair, TreeHash};
+use std::collections::HashMap;
+use std::io;
+
+// pairs whose serialization is smaller than this aren't cached. Looking them
+// up costs about as much as hashing them
+const MIN_CACHED_SIZE: usize = 64;
+
+// the nodes of a serialized CLVM structure, in pre-order. The first item of a
+// pair immediately follows the pair itself
+enum Node {
+    Atom(usize, usize),
+    Pair {
+        start: usize,
+        end: usize,
+        rest: usize,
+    },
+}
+
+enum ParseOp {
+    Node,
+    SetRest(usize),
+    SetEnd(usize),
+}
+
+// returns None if the serialization uses back-references
+fn parse(buf: &[u8]) -> io::Result<Option<Vec<Node>>> {
+    let mut nodes = Vec::<Node>::new();
+    let mut ops = vec![ParseOp::Node];
+    let mut pos = 0;
+    while let Some(op) = ops.pop() {
+        match op {
+            ParseOp::Node => match buf.get(pos) {
+                None => {
+                    return Err(bad_encoding());
+                }
+                Some(0xff) => {
+                    let idx = nodes.len();
+                    nodes.push(Node::Pair {
+                        start: pos,
+                        end: 0,
+                        rest: 0,
+                    });
+                    pos += 1;
+                    ops.push(ParseOp::SetEnd(idx));
+                    ops.push(ParseOp::Node);
+                    ops.push(ParseOp::SetRest(idx));
+                    ops.push(ParseOp::Node);
+                }
+                Some(0xfe) => {
+                    return Ok(None);
+                }
+                Some(_) => {
+                    let (start, end) = parse_atom(buf, pos)?;
+                    nodes.push(Node::Atom(start, end));
+                    pos = end;
+                }
+            },
+            ParseOp::SetRest(idx) => {
+                let next = nodes.len();
+                if let Node::Pair { rest, .. } = &mut nodes[idx] {
+                    *rest = next;
+                }
+            }
+            ParseOp::SetEnd(idx) => {
+                if let Node::Pair { end, .. } = &mut nodes[idx] {
+                    *end = pos;
+                }
+            }
+        }
+    }
+    if pos != buf.len() {
+        return Err(bad_encoding());
+    }
+    Ok(Some(nodes))
+}
+
+enum HashOp {
+    Node(usize),
+    Cons(Option<usize>),
+}
+
+// Computes tree hashes of serialized CLVM structures, remembering the hashes
+// of subtrees across calls. When hashing many puzzles that share large parts
+// (e.g. CAT puzzles that only differ in their inner puzzle), the shared
+// subtrees are only hashed once.
+// Subtrees are identified by their serialization, so this only works with
+// serializations that don't use back-references. Those are hashed without the
+// cache.
+pub struct TreeHasher {
+    cache: HashMap<Box<[u8]>, TreeHash>,
+    max_entries: usize,
+}
+
+impl TreeHasher {
+    // once the cache has max_entries subtrees, it's cleared
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            cache: HashMap::new(),
+            max_entries,
+        }
+    }
+
+    // the number of cached subtree hashes
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    pub fn tree_hash(&mut self, buf: &[u8]) -> io::Result<TreeHash> {
+        let Some(nodes) = parse(buf)? else {
+            return tree_hash_from_bytes(buf);
+        };
+
+        let mut hashes = Vec::<TreeHash>::new();
+        let mut ops = vec![HashOp::Node(0)];
+        while let Some(op) = ops.pop() {
+            match op {
+                HashOp::Node(idx) => match nodes[idx] {
+                    Node::Atom(start, end) => {
+                        hashes.push(tree_hash_atom(&buf[start..end]));
+                    }
+                    Node::Pair { start, end, rest } => {
+                        let cacheable = end - start >= MIN_CACHED_SIZE;
+                        if cacheable {
+                            if let Some(hash) = self.cache.get(&buf[start..end]) {
+                                hashes.push(*hash);
+                                continue;
+                            }
+                        }
+                        ops.push(HashOp::Cons(cacheable.then_some(idx)));
+                        ops.push(HashOp::Node(idx + 1));
+                        ops.push(HashOp::Node(rest));
+                    }
+                },
+                HashOp::Cons(cache_idx) => {
+                    let first = hashes.pop().unwrap();
+                    let rest = hashes.pop().unwrap();
+                    let hash = tree_hash_pair(first, rest);
+                    if let Some(Node::Pair { start, end, .. }) = cache_idx.map(|i| &nodes[i]) {
+                        if self.cache.len() >= self.max_entries {
+                            self.cache.clear();
+                        }
+                        self.cache.insert(buf[*start..*end].into(), hash);
+                    }
+                    hashes.push(hash);
+                }
+            }
+        }
+
+        assert!(hashes.len() == 1);
+        Ok(hashes[0])
+    }
+}
+
+impl Default for TreeHasher {
+    fn default() -> Self {
+        Self::new(100_000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tree_hash;
+    use clvmr::allocator::{Allocator, NodePtr};
+    use clvmr::serde::{node_to_bytes, node_to_bytes_backrefs};
+
+    // builds a list of atoms, large enough to be cached
+    fn make_list(a: &mut Allocator, items: &[&[u8]]) -> NodePtr {
+        let mut list = NodePtr::NIL;
+        for item in items.iter().rev() {
+            let atom = a.new_atom(item).unwrap();
+            list = a.new_pair(atom, list).unwrap();
+        }
+        list
+    }
+
+    #[test]
+    fn test_tree_hasher() {
+        let mut a = Allocator::new();
+        let shared = make_list(&mut a, &[&[1; 40], &[2; 40], &[], &[0x7f], &[0x80]]);
+        let mut hasher = TreeHasher::default();
+
+        for i in 0..10_u8 {
+            let inner = make_list(&mut a, &[&[i; 100], &[i]]);
+            let root = a.new_pair(shared, inner).unwrap();
+            let buf = node_to_bytes(&a, root).unwrap();
+            assert_eq!(hasher.tree_hash(&buf).unwrap(), tree_hash(&a, root));
+        }
+        assert!(!hasher.is_empty());
+        let cached = hasher.len();
+
+        // hashing the same thing again doesn't add anything to the cache
+        let buf = node_to_bytes(&a, shared).unwrap();
+        assert_eq!(hasher.tree_hash(&buf).unwrap(), tree_hash(&a, shared));
+        assert_eq!(hasher.len(), cached);
+
+        hasher.clear();
+        assert!(hasher.is_empty());
+    }
+
+    #[test]
+    fn test_max_entries() {
+        let mut a = Allocator::new();
+        let mut hasher = TreeHasher::new(3);
+        for i in 0..10_u8 {
+            let list = make_list(&mut a, &[&[i; 70], &[i; 70], &[i; 70]]);
+            let buf = node_to_bytes(&a, list).unwrap();
+            assert_eq!(hasher.tree_hash(&buf).unwrap(), tree_hash(&a, list));
+            assert!(hasher.len() <= 3);
+        }
+    }
+
+    #[test]
+    fn test_backrefs() {
+        let mut a = Allocator::new();
+        let list = make_list(&mut a, &[&[1; 100], &[2; 100]]);
+        let root = a.new_pair(list, list).unwrap();
+        let buf = node_to_bytes_backrefs(&a, root).unwrap();
+        let mut hasher = TreeHasher::default();
+        assert_eq!(hasher.tree_hash(&buf).unwrap(), tree_hash(&a, root));
+        assert!(hasher.is_empty());
+    }
+
+    #[test]
+    fn test_invalid() {
+        let mut hasher = TreeHasher::default();
+        // truncated
+        assert!(hasher.tree_hash(&[0xff, 0x01]).is_err());
+        assert!(hasher.tree_hash(&[0x85, 0x01]).is_err());
+        assert!(hasher.tree_hash(&[]).is_err());
+        // trailing garbage
+        assert!(hasher.tree_hash(&[0x01, 0x01]).is_err());
+    }
+}
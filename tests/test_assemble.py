@@ -0,0 +1,32 @@
+import pytest
+from gold_rs import assemble, disassemble
+
+
+@pytest.mark.parametrize(
+    "src,expected",
+    [
+        ("()", "80"),
+        ("-1", "81ff"),
+        ('"foobar"', "86666f6f626172"),
+        ("(q . 1)", "ff0101"),
+        ("(a (q . 1) 1)", "ff02ffff0101ff0180"),
+        ("(+ 2 5)", "ff10ff02ff0580"),
+    ],
+)
+def test_roundtrip(src: str, expected: str) -> None:
+    assert assemble(src).hex() == expected
+    assert disassemble(bytes.fromhex(expected)) == src
+
+
+def test_invalid() -> None:
+    with pytest.raises(ValueError, match="missing \\) at offset 4"):
+        assemble("(1 2")
+    with pytest.raises(ValueError):
+        disassemble(b"\xff\x01")
+
+
+def test_max_len() -> None:
+    program = bytes.fromhex("ff02ffff0101ff0180")
+    assert disassemble(program, max_len=13) == "(a (q . 1) 1)"
+    with pytest.raises(ValueError, match="exceeds max_len"):
+        disassemble(program, max_len=12)
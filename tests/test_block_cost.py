@@ -0,0 +1,47 @@
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
+from chia.types.blockchain_format.sized_bytes import bytes32
+from gold_rs import (
+    ALLOW_BACKREFS,
+    Coin,
+    ConsensusConstants,
+    get_block_cost,
+    run_block_generator,
+    run_block_generator2,
+    solution_generator,
+)
+import pytest
+
+CONSTANTS = ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS))
+MAX_COST = CONSTANTS.MAX_BLOCK_COST_CLVM
+
+
+def make_generator() -> bytes:
+    spends = [
+        (Coin(bytes32(bytes([i] * 32)), bytes32(b"\x2a" * 32), i), b"\x01", b"\x80")
+        for i in range(10)
+    ]
+    return solution_generator(spends)
+
+
+@pytest.mark.parametrize("flags", [0, ALLOW_BACKREFS])
+def test_get_block_cost(flags: int) -> None:
+    generator = make_generator()
+    run = run_block_generator2 if flags & ALLOW_BACKREFS else run_block_generator
+    err, conds = run(generator, [], MAX_COST, flags)
+    assert err is None and conds is not None
+
+    cost, byte_cost = get_block_cost(generator, [], flags, CONSTANTS)
+    assert cost == conds.cost
+    assert byte_cost == len(generator) * 12000
+
+
+def test_get_block_cost_exceeded() -> None:
+    generator = make_generator()
+    cost, _ = get_block_cost(generator, [], 0, CONSTANTS)
+    constants = ConsensusConstants.from_dict_overrides(
+        CONSTANTS, {"MAX_BLOCK_COST_CLVM": cost - 1}
+    )
+    with pytest.raises(ValueError) as e:
+        get_block_cost(generator, [], 0, constants)
+    # COST_EXCEEDED
+    assert e.value.args == ("ValidationError", 23)
@@ -0,0 +1,70 @@
+import pytest
+
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
+
+from gold_rs import (
+    ConsensusConstants,
+    FullBlock,
+    NewInfusionPointVDF,
+    UnfinishedBlock,
+    finish_block,
+)
+from gold_rs.test_support import BlockTools
+
+CONSTANTS = ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS))
+
+
+def unfinished(block: FullBlock) -> UnfinishedBlock:
+    return UnfinishedBlock(
+        block.finished_sub_slots,
+        block.reward_chain_block.get_unfinished(),
+        block.challenge_chain_sp_proof,
+        block.reward_chain_sp_proof,
+        block.foliage,
+        block.foliage_transaction_block,
+        block.transactions_info,
+        block.transactions_generator,
+        block.transactions_generator_ref_list,
+    )
+
+
+def infusion_point(block: FullBlock) -> NewInfusionPointVDF:
+    rcb = block.reward_chain_block
+    return NewInfusionPointVDF(
+        rcb.get_unfinished().get_hash(),
+        rcb.challenge_chain_ip_vdf,
+        block.challenge_chain_ip_proof,
+        rcb.reward_chain_ip_vdf,
+        block.reward_chain_ip_proof,
+        rcb.infused_challenge_chain_ip_vdf,
+        block.infused_challenge_chain_ip_proof,
+    )
+
+
+def test_finish_genesis() -> None:
+    bt = BlockTools(CONSTANTS)
+    genesis = bt.next_block(None)
+    finished = finish_block(
+        unfinished(genesis),
+        infusion_point(genesis),
+        genesis.finished_sub_slots,
+        None,
+        DEFAULT_CONSTANTS.DIFFICULTY_STARTING,
+        True,
+    )
+    assert finished == genesis
+    assert finished.header_hash == genesis.header_hash
+
+
+def test_wrong_infusion_point() -> None:
+    bt = BlockTools(CONSTANTS)
+    blocks = bt.get_consecutive_blocks(2)
+    with pytest.raises(ValueError, match="ValidationError"):
+        finish_block(
+            unfinished(blocks[0]),
+            infusion_point(blocks[1]),
+            [],
+            None,
+            DEFAULT_CONSTANTS.DIFFICULTY_STARTING,
+            True,
+        )
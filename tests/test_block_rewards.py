@@ -0,0 +1,127 @@
+from typing import Dict, List
+
+import pytest
+
+from chia.consensus.block_rewards import calculate_base_farmer_reward as py_calculate_base_farmer_reward
+from chia.consensus.block_rewards import calculate_pool_reward as py_calculate_pool_reward
+from chia.consensus.coinbase import create_farmer_coin as py_create_farmer_coin
+from chia.consensus.coinbase import create_pool_coin as py_create_pool_coin
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
+from chia.types.blockchain_format.sized_bytes import bytes32
+from gold_rs import (
+    BlockRecord,
+    ClassgroupElement,
+    Coin,
+    ConsensusConstants,
+    G1Element,
+    calculate_base_farmer_reward,
+    calculate_pool_reward,
+    create_farmer_coin,
+    create_pool_coin,
+    expected_reward_coins,
+    validate_prefarm,
+    validate_reward_claims,
+)
+
+CONSTANTS = ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS))
+BLOCKS_PER_YEAR = 1681920
+POOL_PH = bytes32([1] * 32)
+FARMER_PH = bytes32([2] * 32)
+
+
+def h(v: int) -> bytes32:
+    return bytes32(v.to_bytes(4, "big") + bytes(28))
+
+
+def record(height: int, is_tx_block: bool) -> BlockRecord:
+    return BlockRecord(
+        h(height),
+        h(height - 1) if height > 0 else DEFAULT_CONSTANTS.GENESIS_CHALLENGE,
+        height,
+        height,
+        height * 10,
+        0,
+        ClassgroupElement.get_default_element(),
+        None,
+        h(0),
+        h(0),
+        0,
+        POOL_PH,
+        FARMER_PH,
+        0,
+        0,
+        False,
+        0,
+        height if is_tx_block else None,
+        None,
+        10 if is_tx_block else None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        G1Element(),
+    )
+
+
+@pytest.mark.parametrize(
+    "height",
+    [0, 1, 1000, 3 * BLOCKS_PER_YEAR - 1, 3 * BLOCKS_PER_YEAR, 6 * BLOCKS_PER_YEAR, 9 * BLOCKS_PER_YEAR]
+    + [12 * BLOCKS_PER_YEAR, 20 * BLOCKS_PER_YEAR, 2**32 - 1],
+)
+def test_rewards(height: int) -> None:
+    assert calculate_pool_reward(height) == py_calculate_pool_reward(height)
+    assert calculate_base_farmer_reward(height) == py_calculate_base_farmer_reward(height)
+
+
+def test_reward_coins() -> None:
+    genesis = DEFAULT_CONSTANTS.GENESIS_CHALLENGE
+    for height in [0, 1, 4711, 2**32 - 1]:
+        pool_coin = create_pool_coin(height, POOL_PH, 1000, CONSTANTS)
+        assert pool_coin.name() == py_create_pool_coin(height, POOL_PH, 1000, genesis).name()
+        farmer_coin = create_farmer_coin(height, FARMER_PH, 1000, CONSTANTS)
+        assert farmer_coin.name() == py_create_farmer_coin(height, FARMER_PH, 1000, genesis).name()
+
+
+def test_expected_reward_coins() -> None:
+    # blocks 3 and 4 aren't transaction blocks
+    blocks: Dict[bytes32, BlockRecord] = {r.header_hash: r for r in [record(i, i not in (3, 4)) for i in range(7)]}
+
+    # the coin IDs of the rewards of the block, according to chia
+    def rewards(height: int, fees: int) -> List[bytes32]:
+        genesis = DEFAULT_CONSTANTS.GENESIS_CHALLENGE
+        pool_reward = py_calculate_pool_reward(height)
+        farmer_reward = py_calculate_base_farmer_reward(height) + fees
+        return [
+            py_create_pool_coin(height, POOL_PH, pool_reward, genesis).name(),
+            py_create_farmer_coin(height, FARMER_PH, farmer_reward, genesis).name(),
+        ]
+
+    def names(coins: List[Coin]) -> List[bytes32]:
+        return sorted(c.name() for c in coins)
+
+    assert names(expected_reward_coins(CONSTANTS, blocks[h(0)], blocks)) == sorted(rewards(0, 10))
+    assert names(expected_reward_coins(CONSTANTS, blocks[h(2)], blocks.get)) == sorted(rewards(2, 10))
+    expected = expected_reward_coins(CONSTANTS, blocks[h(5)], blocks)
+    assert names(expected) == sorted(rewards(5, 10) + rewards(4, 0) + rewards(3, 0))
+
+    validate_reward_claims(list(reversed(expected)), expected)
+    with pytest.raises(ValueError):
+        validate_reward_claims(expected[1:], expected)
+    with pytest.raises(ValueError):
+        validate_reward_claims(expected + expected[:1], expected)
+
+    del blocks[h(4)]
+    with pytest.raises(ValueError, match="block not found"):
+        expected_reward_coins(CONSTANTS, blocks[h(5)], blocks)
+
+
+def test_prefarm() -> None:
+    pool_ph = DEFAULT_CONSTANTS.GENESIS_PRE_FARM_POOL_PUZZLE_HASH
+    farmer_ph = DEFAULT_CONSTANTS.GENESIS_PRE_FARM_FARMER_PUZZLE_HASH
+    validate_prefarm(0, pool_ph, farmer_ph, CONSTANTS)
+    validate_prefarm(1, POOL_PH, FARMER_PH, CONSTANTS)
+    with pytest.raises(ValueError):
+        validate_prefarm(0, POOL_PH, farmer_ph, CONSTANTS)
+    with pytest.raises(ValueError):
+        validate_prefarm(0, pool_ph, FARMER_PH, CONSTANTS)
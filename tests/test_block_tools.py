@@ -0,0 +1,91 @@
+from chiabip158 import PyBIP158
+
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
+from chia.types.blockchain_format.sized_bytes import bytes32
+from gold_rs import (
+    Coin,
+    CoinSpend,
+    ConsensusConstants,
+    G2Element,
+    Program,
+    RespondCompactVDF,
+    SpendBundle,
+    VDFProof,
+)
+from gold_rs.test_support import BlockTools
+
+CONSTANTS = ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS))
+
+
+def test_consecutive_blocks() -> None:
+    bt = BlockTools(CONSTANTS)
+    blocks = bt.get_consecutive_blocks(10)
+    assert [b.height for b in blocks] == list(range(10))
+    for prev, block in zip(blocks, blocks[1:]):
+        assert block.prev_header_hash == prev.header_hash
+        assert block.weight > prev.weight
+        assert block.total_iters > prev.total_iters
+        assert block.get_included_reward_coins() == list(bt.reward_coins(prev))
+
+    assert bt.get_consecutive_blocks(10) == blocks
+    assert BlockTools(CONSTANTS, bytes([1] * 32)).get_consecutive_blocks(1) != blocks[:1]
+
+    more = bt.get_consecutive_blocks(3, blocks[-1])
+    assert more[0].prev_header_hash == blocks[-1].header_hash
+
+
+def test_transactions() -> None:
+    bt = BlockTools(CONSTANTS)
+    genesis = bt.next_block(None)
+    pool_coin, _ = bt.reward_coins(genesis)
+
+    # the reward coins use the puzzle "1", which returns its solution
+    solution = Program.to([[51, pool_coin.puzzle_hash, pool_coin.amount - 1000]])
+    spend = CoinSpend(pool_coin, Program.to(1), solution)
+    block = bt.next_block(genesis, [SpendBundle([spend], G2Element())])
+    assert block.transactions_generator is not None
+    assert block.transactions_info is not None
+    assert block.transactions_info.fees == 1000
+
+
+def test_get_header_block() -> None:
+    bt = BlockTools(CONSTANTS)
+    blocks = bt.get_consecutive_blocks(3)
+    block = blocks[2]
+    additions = [Coin(bytes32(b"\x01" * 32), bytes32(b"\x02" * 32), 1337)]
+    removals = [bytes32(b"\x03" * 32)]
+
+    hb = block.get_header_block(additions, removals)
+    assert hb.header_hash == block.header_hash
+    assert hb.height == block.height
+    assert hb.foliage == block.foliage
+    assert hb.transactions_info == block.transactions_info
+    assert hb.finished_sub_slots == block.finished_sub_slots
+
+    # the same filter the full node computes
+    elements = [bytearray(c.puzzle_hash) for c in additions + block.get_included_reward_coins()]
+    elements += [bytearray(r) for r in removals]
+    assert hb.transactions_filter == bytes(PyBIP158(elements).GetEncoded())
+
+    hb = block.get_block_header()
+    assert hb.header_hash == block.header_hash
+    assert hb.transactions_filter == b""
+
+
+def test_compact_vdf() -> None:
+    bt = BlockTools(CONSTANTS)
+    block = bt.next_block(None)
+    assert block.is_fully_compactified()
+    assert block.uncompact_proofs() == []
+
+    compact = block.challenge_chain_ip_proof
+    block = block.replace(challenge_chain_ip_proof=VDFProof(1, b"\x01\x02", False))
+    assert not block.is_fully_compactified()
+    vdf_info = block.reward_chain_block.challenge_chain_ip_vdf
+    # 4 is CC_IP_VDF
+    assert block.uncompact_proofs() == [(4, vdf_info)]
+
+    response = RespondCompactVDF(block.height, block.header_hash, 4, vdf_info, compact)
+    assert block.can_accept_compact_vdf(response)
+    assert not block.can_accept_compact_vdf(response.replace(field_vdf=3))
+    assert not block.can_accept_compact_vdf(response.replace(vdf_proof=block.challenge_chain_ip_proof))
@@ -0,0 +1,62 @@
+import pytest
+
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
+
+from gold_rs import (
+    BlockValidator,
+    CoinSpend,
+    ConsensusConstants,
+    G2Element,
+    Program,
+    SpendBundle,
+)
+from gold_rs.test_support import BlockTools
+
+# BlockTools creates generators in the format of the hard fork
+CONSTANTS = ConsensusConstants.from_dict_overrides(
+    ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS)), {"HARD_FORK_HEIGHT": 0}
+)
+
+
+def test_staged_validation() -> None:
+    bt = BlockTools(CONSTANTS)
+    genesis = bt.next_block(None)
+    pool_coin, _ = bt.reward_coins(genesis)
+    solution = Program.to([[51, pool_coin.puzzle_hash, pool_coin.amount - 1000]])
+    spend = CoinSpend(pool_coin, Program.to(1), solution)
+    block = bt.next_block(genesis, [SpendBundle([spend], G2Element())])
+
+    validator = BlockValidator(block, CONSTANTS)
+    validator.validate_header()
+    assert validator.conds is None
+
+    # the signatures can't be validated before the spends are known
+    with pytest.raises(ValueError, match="run_generator"):
+        validator.validate_signatures()
+
+    conds = validator.run_generator()
+    assert conds is not None
+    assert block.transactions_info is not None
+    assert conds.cost == block.transactions_info.cost
+    assert [s.coin_id for s in conds.spends] == [pool_coin.name()]
+    assert validator.conds is not None
+    validator.validate_signatures()
+
+
+def test_invalid_header() -> None:
+    bt = BlockTools(CONSTANTS)
+    genesis = bt.next_block(None)
+    pool_coin, _ = bt.reward_coins(genesis)
+    solution = Program.to([[51, pool_coin.puzzle_hash, pool_coin.amount]])
+    spend = CoinSpend(pool_coin, Program.to(1), solution)
+    block = bt.next_block(genesis, [SpendBundle([spend], G2Element())])
+
+    # a generator that doesn't match the transactions info
+    block = block.replace(transactions_generator=Program.to(1))
+    with pytest.raises(ValueError, match="ValidationError"):
+        BlockValidator(block, CONSTANTS).validate_header()
+
+    # blocks without transactions have nothing to run
+    validator = BlockValidator(genesis, CONSTANTS)
+    validator.validate_header()
+    assert validator.run_generator() is None
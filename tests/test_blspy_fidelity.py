@@ -194,11 +194,18 @@ import binascii
 import time
 from copy import deepcopy
 
+import pytest
+
 from gold_rs import (
     AugSchemeMPL,
     G1Element,
     G2Element,
     PrivateKey,
+    aggregate_public_keys,
+    aggregate_public_keys_with_pop,
+    pop_prove,
+    pop_verify,
+    validate_public_keys,
 )
 
 
@@ -526,6 +533,43 @@ def test_readme() -> None:
     assert ok
 
 
+def test_validate_public_keys() -> None:
+    pks = [AugSchemeMPL.key_gen(bytes([i]) * 32).get_g1() for i in range(10)]
+    assert all(pk.is_valid() and not pk.is_inf() for pk in pks)
+    assert G1Element().is_inf()
+    assert G1Element().is_valid()
+    assert G2Element().is_inf()
+
+    pks.append(G1Element())
+    assert validate_public_keys(pks) == [True] * 10 + [False]
+    assert validate_public_keys(pks, allow_infinity=True) == [True] * 11
+    assert validate_public_keys([]) == []
+
+
+def test_pop() -> None:
+    sks = [AugSchemeMPL.key_gen(bytes([i]) * 32) for i in range(1, 4)]
+    pks = [sk.get_g1() for sk in sks]
+    pops = [pop_prove(sk) for sk in sks]
+    for sk, pk, pop in zip(sks, pks, pops):
+        # the same as blspy
+        ref_sk = blspy.PrivateKey.from_bytes(bytes(sk))
+        assert bytes(pop) == bytes(blspy.PopSchemeMPL.pop_prove(ref_sk))
+        assert pop_verify(pk, pop)
+    assert not pop_verify(pks[0], pops[1])
+
+    agg = aggregate_public_keys(pks)
+    assert agg == pks[0] + pks[1] + pks[2]
+    assert aggregate_public_keys([]) == G1Element()
+    assert aggregate_public_keys_with_pop(pks, pops) == agg
+
+    with pytest.raises(ValueError, match="ProofOfPossessionCount"):
+        aggregate_public_keys_with_pop(pks, pops[:2])
+    with pytest.raises(ValueError, match="InvalidProofOfPossession\\(1\\)"):
+        aggregate_public_keys_with_pop(pks, [pops[0], pops[2], pops[1]])
+    with pytest.raises(ValueError, match="DuplicatePublicKey\\(1\\)"):
+        aggregate_public_keys_with_pop([pks[0], pks[0]], [pops[0], pops[0]])
+
+
 def test_aggregate_verify_zero_items() -> None:
     assert AugSchemeMPL.aggregate_verify([], [], G2Element())
 
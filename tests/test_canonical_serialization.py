@@ -0,0 +1,36 @@
+import pytest
+
+from gold_rs import ALLOW_BACKREFS, CoinSpend, Program, check_canonical_clvm
+
+
+@pytest.mark.parametrize(
+    "hex_program,flags,expected",
+    [
+        ("ff01ff0280", 0, None),
+        ("8180", 0, None),
+        ("ff018101", 0, ("small_atom_with_prefix", 2)),
+        ("c0028080", 0, ("non_minimal_length", 0)),
+        ("ff01fe02", 0, ("backref", 2)),
+        ("ff01fe02", ALLOW_BACKREFS, None),
+        ("ff01fe820002", ALLOW_BACKREFS, ("backref_path", 2)),
+        ("0101", 0, ("trailing_bytes", 1)),
+        ("ff01", 0, ("end_of_buffer", None)),
+    ],
+)
+def test_check_canonical_clvm(hex_program: str, flags: int, expected: object) -> None:
+    assert check_canonical_clvm(bytes.fromhex(hex_program), flags) == expected
+
+
+def test_from_bytes_strict() -> None:
+    canonical = Program.fromhex("ff01ff0280")
+    assert Program.from_bytes_strict(bytes(canonical)) == canonical
+
+    non_canonical = bytes.fromhex("ff018101")
+    assert bytes(Program.from_bytes(non_canonical)) == non_canonical
+    with pytest.raises(ValueError, match="non-canonical encoding: CLVM small_atom_with_prefix at offset 2"):
+        Program.from_bytes_strict(non_canonical)
+
+    # the programs in other streamable types are checked too
+    spend = CoinSpend.from_bytes(bytes(32) + bytes(32) + bytes(8) + non_canonical + bytes.fromhex("80"))
+    with pytest.raises(ValueError, match="non-canonical encoding"):
+        CoinSpend.from_bytes_strict(bytes(spend))
@@ -0,0 +1,60 @@
+import sqlite3
+from pathlib import Path
+from typing import List
+
+import pytest
+import zstd
+
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
+from chia.types.blockchain_format.sized_bytes import bytes32
+from gold_rs import ConsensusConstants, FullBlock
+from gold_rs.test_support import BlockTools, ChainDb
+
+CONSTANTS = ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS))
+
+
+def make_db(path: Path, blocks: List[FullBlock]) -> None:
+    with sqlite3.connect(path) as conn:
+        conn.execute("CREATE TABLE database_version(version int)")
+        conn.execute("INSERT INTO database_version VALUES(2)")
+        conn.execute(
+            "CREATE TABLE full_blocks(header_hash blob PRIMARY KEY, prev_hash blob, "
+            "height bigint, sub_epoch_summary blob, is_fully_compactified tinyint, "
+            "in_main_chain tinyint, block blob, block_record blob)"
+        )
+        conn.execute("CREATE TABLE current_peak(key int PRIMARY KEY, hash blob)")
+        for b in blocks:
+            conn.execute(
+                "INSERT INTO full_blocks VALUES(?, ?, ?, NULL, 0, 1, ?, NULL)",
+                (b.header_hash, b.prev_header_hash, b.height, zstd.compress(bytes(b))),
+            )
+        if len(blocks) > 0:
+            conn.execute("INSERT INTO current_peak VALUES(0, ?)", (blocks[-1].header_hash,))
+
+
+def test_chain_db(tmp_path: Path) -> None:
+    blocks = BlockTools(CONSTANTS).get_consecutive_blocks(150)
+    path = tmp_path / "blockchain_v2_test.sqlite"
+    make_db(path, blocks)
+
+    db = ChainDb(path)
+    assert db.peak_height() == 149
+    assert db.get_block(17) == blocks[17]
+    assert db.get_block(150) is None
+    assert db.get_block_by_hash(blocks[3].header_hash) == blocks[3]
+    assert db.get_block_by_hash(bytes32(b"\x00" * 32)) is None
+    assert db.get_blocks(10, 20) == blocks[10:20]
+    assert db.generator_refs(blocks[5]) == []
+
+    assert list(db.iter_blocks()) == blocks
+    assert list(db.iter_blocks(120)) == blocks[120:]
+    assert [b.height for b in db.iter_blocks(5, 8)] == [5, 6, 7]
+
+
+def test_unsupported_version(tmp_path: Path) -> None:
+    path = tmp_path / "blockchain_v1_test.sqlite"
+    with sqlite3.connect(path) as conn:
+        conn.execute("CREATE TABLE database_version(version int)")
+        conn.execute("INSERT INTO database_version VALUES(1)")
+    with pytest.raises(ValueError, match="unsupported database version"):
+        ChainDb(path)
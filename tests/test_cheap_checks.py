@@ -0,0 +1,65 @@
+from typing import List, Optional
+
+import pytest
+
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
+from chia.types.blockchain_format.sized_bytes import bytes32
+from chia.util.errors import Err
+
+from gold_rs import Coin, CoinSpend, ConsensusConstants, G2Element, Program, SpendBundle, cheap_checks
+
+CONSTANTS = ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS))
+MAX_COST = 11000000000
+
+CREATE_COIN_ANNOUNCEMENT = 60
+
+
+def quoted_spend(conditions: List[List[object]], amount: int = 1) -> CoinSpend:
+    puzzle = Program.to((1, conditions))
+    coin = Coin(bytes32([0] * 32), puzzle.get_tree_hash(), amount)
+    return CoinSpend(coin, puzzle, Program.to(0))
+
+
+def announcements(n: int) -> List[List[object]]:
+    return [[CREATE_COIN_ANNOUNCEMENT, b"\x00" * 32]] * n
+
+
+def check(spends: List[CoinSpend], max_cost: int = MAX_COST) -> Optional[int]:
+    bundle = SpendBundle(spends, G2Element())
+    ret = cheap_checks(bundle, CONSTANTS, max_cost)
+    if ret is None:
+        return None
+    code, coin_id = ret
+    # all failures except the cost limit are attributed to a spend
+    assert (coin_id is None) == (code == Err.BLOCK_COST_EXCEEDS_MAX.value)
+    return code
+
+
+def test_valid() -> None:
+    assert check([quoted_spend(announcements(3), 1), quoted_spend([], 2)]) is None
+
+
+def test_double_spend() -> None:
+    spend = quoted_spend([])
+    assert check([spend, spend]) == Err.DOUBLE_SPEND.value
+
+
+def test_wrong_puzzle_hash() -> None:
+    spend = quoted_spend([])
+    coin = Coin(spend.coin.parent_coin_info, bytes32([1] * 32), spend.coin.amount)
+    spend = spend.replace(coin=coin)
+    assert check([spend]) == Err.WRONG_PUZZLE_HASH.value
+
+
+@pytest.mark.parametrize("count", [1024, 1025])
+def test_announcement_limit(count: int) -> None:
+    expected = None if count <= 1024 else Err.TOO_MANY_ANNOUNCEMENTS.value
+    assert check([quoted_spend(announcements(count))]) == expected
+
+
+def test_cost_exceeded() -> None:
+    spend = quoted_spend(announcements(10))
+    size = len(bytes(spend.puzzle_reveal)) + len(bytes(spend.solution))
+    cost = size * DEFAULT_CONSTANTS.COST_PER_BYTE
+    assert check([spend], cost) is None
+    assert check([spend], cost - 1) == Err.BLOCK_COST_EXCEEDS_MAX.value
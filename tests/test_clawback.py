@@ -0,0 +1,86 @@
+from typing import List
+
+from gold_rs import (
+    Program,
+    clawback_puzzle,
+    clawback_puzzle_hash,
+    clawback_solution,
+    parse_clawback_spend,
+)
+from chia.types.blockchain_format.program import Program as ChiaProgram
+from chia.types.blockchain_format.sized_bytes import bytes32
+from chia.wallet.puzzles.clawback import drivers
+import pytest
+
+TIMELOCK = 3600
+
+CREATE_COIN = 51
+ASSERT_SECONDS_RELATIVE = 80
+
+
+def create_coin(n: int) -> List[bytes]:
+    return [bytes([CREATE_COIN]), bytes([n] * 32), (1000).to_bytes(2, "big")]
+
+
+def inner_puzzle(n: int) -> Program:
+    return Program.to((1, [create_coin(n)]))
+
+
+SENDER = inner_puzzle(1)
+RECIPIENT = inner_puzzle(2)
+SENDER_PH = bytes32(SENDER.get_tree_hash())
+RECIPIENT_PH = bytes32(RECIPIENT.get_tree_hash())
+
+
+def test_clawback_puzzle() -> None:
+    puzzle = clawback_puzzle(TIMELOCK, SENDER_PH, RECIPIENT_PH)
+    assert puzzle.get_tree_hash() == clawback_puzzle_hash(
+        TIMELOCK, SENDER_PH, RECIPIENT_PH
+    )
+    expected = drivers.create_merkle_puzzle(TIMELOCK, SENDER_PH, RECIPIENT_PH)
+    assert bytes(puzzle) == bytes(expected)
+
+
+@pytest.mark.parametrize("claim", [True, False])
+def test_clawback_solution(claim: bool) -> None:
+    inner = RECIPIENT if claim else SENDER
+    inner_solution = Program.to(0)
+
+    puzzle = clawback_puzzle(TIMELOCK, SENDER_PH, RECIPIENT_PH)
+    solution = clawback_solution(
+        TIMELOCK, SENDER_PH, RECIPIENT_PH, inner, inner_solution
+    )
+    expected = drivers.create_merkle_solution(
+        TIMELOCK,
+        SENDER_PH,
+        RECIPIENT_PH,
+        ChiaProgram.from_bytes(bytes(inner)),
+        ChiaProgram.from_bytes(bytes(inner_solution)),
+    )
+    assert bytes(solution) == bytes(expected)
+
+    _, output = puzzle.run(solution, 11000000000)
+    conditions = output.to_python()
+    assert create_coin(2 if claim else 1) in conditions
+    # only the recipient has to wait for the timelock
+    timelock = [bytes([ASSERT_SECONDS_RELATIVE]), TIMELOCK.to_bytes(2, "big")]
+    assert (timelock in conditions) == claim
+
+    parsed = parse_clawback_spend(puzzle, solution)
+    assert parsed is not None
+    _, parsed_timelock, spender_ph = parsed
+    assert parsed_timelock == (TIMELOCK if claim else None)
+    assert spender_ph == (RECIPIENT_PH if claim else SENDER_PH)
+
+
+def test_invalid_inner_puzzle() -> None:
+    with pytest.raises(ValueError, match="Invalid Clawback inner puzzle."):
+        clawback_solution(
+            TIMELOCK, SENDER_PH, RECIPIENT_PH, inner_puzzle(3), Program.to(0)
+        )
+    with pytest.raises(ValueError, match="Timelock must be at least 1 second"):
+        clawback_puzzle_hash(0, SENDER_PH, RECIPIENT_PH)
+
+
+def test_not_clawback() -> None:
+    assert parse_clawback_spend(SENDER, Program.to(0)) is None
@@ -0,0 +1,26 @@
+from typing import List, Tuple
+
+import pytest
+from chia.types.blockchain_format.sized_bytes import bytes32
+from gold_rs import Coin, select_coins
+
+
+def make_coins(amounts: List[int]) -> List[Tuple[Coin, int]]:
+    return [
+        (Coin(bytes32(i.to_bytes(32, "big")), bytes32(b"\x00" * 32), amount), i)
+        for i, amount in enumerate(amounts)
+    ]
+
+
+def test_select_coins() -> None:
+    coins = make_coins([20, 1, 100, 5, 3, 10])
+    assert [c.amount for c in select_coins(coins, 10)] == [10]
+    assert sorted(c.amount for c in select_coins(coins, 18)) == [3, 5, 10]
+    assert sorted(c.amount for c in select_coins(coins, 4, "smallest_first")) == [1, 3]
+    assert select_coins(coins, 0) == []
+
+    with pytest.raises(ValueError, match="insufficient funds"):
+        select_coins(coins, 140)
+
+    with pytest.raises(ValueError, match="unknown coin selection strategy"):
+        select_coins(coins, 10, "largest_first")
@@ -0,0 +1,62 @@
+import copy
+from hashlib import sha256
+
+from chia.types.blockchain_format.sized_bytes import bytes32
+from gold_rs import Coin, CoinStateFilters, CoinStore, hash_coin_states
+
+
+def test_coin_store() -> None:
+    store = CoinStore()
+    ph = bytes32(b"\x02" * 32)
+    coins = [Coin(bytes32(i.to_bytes(32, "big")), ph, i) for i in range(10)]
+    for i, coin in enumerate(coins):
+        assert store.add_coin(coin, i)
+    assert not store.add_coin(coins[0], 0)
+    assert len(store) == 10
+
+    assert store.spend_coin(coins[0].name(), 10)
+    assert not store.spend_coin(coins[0].name(), 11)
+    assert store.coin_state(coins[0].name()).spent_height == 10
+    assert len(store.coin_states_by_puzzle_hash([ph], include_spent=False)) == 9
+
+    filters = CoinStateFilters(True, True, False, 0)
+    states, next_height = store.batch_coin_states([ph], 0, filters, 4)
+    assert [s.coin for s in states] == coins[1:5]
+    assert next_height == 5
+    states, next_height = store.batch_coin_states([ph], 5, filters)
+    assert [s.coin for s in states] == coins[5:] + coins[:1]
+    assert next_height is None
+
+    assert [s.coin for s in store.changes_between(2, 4)] == coins[3:5]
+
+
+def test_hash_coin_states() -> None:
+    store = CoinStore()
+    ph = bytes32(b"\x02" * 32)
+    coins = [Coin(bytes32(i.to_bytes(32, "big")), ph, i) for i in range(10)]
+    for i, coin in enumerate(coins):
+        store.add_coin(coin, i)
+    store.spend_coin(coins[3].name(), 12)
+    states = store.coin_states_by_puzzle_hash([ph])
+
+    checksum = hash_coin_states(states)
+    assert checksum == hash_coin_states(list(reversed(states)))
+    assert checksum == hash_coin_states(states[:6] + states[4:])
+
+    expected = sha256(len(states).to_bytes(4, "big"))
+    for s in sorted(states, key=lambda s: s.coin.name()):
+        expected.update(bytes(s))
+    assert checksum == expected.digest()
+
+    store.spend_coin(coins[4].name(), 13)
+    assert hash_coin_states(store.coin_states_by_puzzle_hash([ph])) != checksum
+
+
+def test_copy_coin_store() -> None:
+    store = CoinStore()
+    coin = Coin(bytes32(b"\x01" * 32), bytes32(b"\x02" * 32), 1)
+    store.add_coin(coin, 1)
+    fork = copy.deepcopy(store)
+    assert fork.spend_coin(coin.name(), 2)
+    assert store.coin_state(coin.name()).spent_height is None
+    assert len(copy.copy(store)) == 1
@@ -0,0 +1,33 @@
+from chia.types.blockchain_format.sized_bytes import bytes32
+from gold_rs import (
+    ALLOW_BACKREFS,
+    Coin,
+    Program,
+    compress_generator,
+    decompress_generator,
+    run_block_generator2,
+    solution_generator,
+)
+
+MAX_COST = 11_000_000_000
+
+
+def test_compress_generator() -> None:
+    spends = [
+        (Coin(bytes32(bytes([i] * 32)), bytes32(b"\x2a" * 32), i), b"\x01", b"\x80")
+        for i in range(10)
+    ]
+    block = solution_generator(spends)
+    # returns the spends of the first referenced generator
+    # (r (a 2 (c (f 5) ())))
+    generator = bytes(Program.to([6, [2, 2, [4, [5, 5], 0]]]))
+
+    compressed = compress_generator(generator, [block], MAX_COST)
+    assert decompress_generator(compressed) == block
+
+    err, original = run_block_generator2(generator, [block], MAX_COST, ALLOW_BACKREFS)
+    assert err is None
+    err, new = run_block_generator2(compressed, [], MAX_COST, ALLOW_BACKREFS)
+    assert err is None
+    assert original is not None and new is not None
+    assert original.spends == new.spends
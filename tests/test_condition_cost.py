@@ -0,0 +1,37 @@
+import pytest
+
+from chia.types.condition_opcodes import ConditionOpcode
+
+from gold_rs import AGG_SIG_COST, CREATE_COIN_COST, condition_cost, condition_cost_table
+
+
+@pytest.mark.parametrize(
+    "opcode, cost",
+    [
+        (ConditionOpcode.CREATE_COIN, CREATE_COIN_COST),
+        (ConditionOpcode.AGG_SIG_ME, AGG_SIG_COST),
+        (ConditionOpcode.AGG_SIG_UNSAFE, AGG_SIG_COST),
+        (ConditionOpcode.AGG_SIG_PARENT, AGG_SIG_COST),
+        (ConditionOpcode.AGG_SIG_PUZZLE, AGG_SIG_COST),
+        (ConditionOpcode.AGG_SIG_AMOUNT, AGG_SIG_COST),
+        (ConditionOpcode.AGG_SIG_PUZZLE_AMOUNT, AGG_SIG_COST),
+        (ConditionOpcode.AGG_SIG_PARENT_AMOUNT, AGG_SIG_COST),
+        (ConditionOpcode.AGG_SIG_PARENT_PUZZLE, AGG_SIG_COST),
+        (ConditionOpcode.RESERVE_FEE, 0),
+        (ConditionOpcode.REMARK, 0),
+    ],
+)
+def test_condition_cost(opcode: ConditionOpcode, cost: int) -> None:
+    op = int.from_bytes(opcode.value, "big")
+    assert condition_cost(op) == cost
+    assert condition_cost_table().get(op, 0) == cost
+
+
+def test_cost_table() -> None:
+    table = condition_cost_table()
+    assert len(table) == 9
+    assert all(condition_cost(op) == cost for op, cost in table.items())
+    # the cost of unknown 2-byte conditions grows with the low byte
+    assert condition_cost(0x100) == 100
+    assert condition_cost(0x1FF) > condition_cost(0x101)
+
@@ -0,0 +1,59 @@
+from gold_rs import (
+    ENABLE_MESSAGE_CONDITIONS,
+    G1Element,
+    NO_UNKNOWN_CONDS,
+    Program,
+    conditions_to_program,
+)
+import pytest
+
+AGG_SIG_ME = 50
+CREATE_COIN = 51
+RESERVE_FEE = 52
+REMARK = 1
+SEND_MESSAGE = 66
+ASSERT_HEIGHT_RELATIVE = 82
+
+PH = b"\xab" * 32
+
+
+def test_conditions_to_program() -> None:
+    pk = G1Element()
+    conditions = [
+        (CREATE_COIN, PH, 1000),
+        (CREATE_COIN, PH, 1, [PH]),
+        (AGG_SIG_ME, pk, b"msg"),
+        (RESERVE_FEE, 0),
+        (SEND_MESSAGE, 0x3F, b"msg", PH),
+    ]
+    prg = conditions_to_program(conditions, ENABLE_MESSAGE_CONDITIONS)
+    assert prg == Program.to([list(c) for c in conditions])
+
+    # and it round-trips
+    assert conditions_to_program(prg, ENABLE_MESSAGE_CONDITIONS) == prg
+
+
+def test_canonical_form() -> None:
+    # extra arguments and memos after the hint are dropped, as are conditions
+    # that are always true
+    conditions = [
+        (CREATE_COIN, PH, 1000, [PH, b"foo"], b"bar"),
+        (REMARK, b"hello"),
+        (ASSERT_HEIGHT_RELATIVE, -1),
+        (RESERVE_FEE, 10, 20),
+    ]
+    assert conditions_to_program(conditions) == Program.to(
+        [[CREATE_COIN, PH, 1000, [PH]], [RESERVE_FEE, 10]]
+    )
+    assert conditions_to_program([]) == Program.to(0)
+
+
+def test_invalid_conditions() -> None:
+    with pytest.raises(ValueError, match="ValidationError"):
+        conditions_to_program([(CREATE_COIN, b"short", 1)])
+    with pytest.raises(ValueError, match="ValidationError"):
+        conditions_to_program([(CREATE_COIN, PH, -1)])
+    # unknown conditions are ignored, unless NO_UNKNOWN_CONDS is set
+    assert conditions_to_program([(0x13, 1)]) == Program.to(0)
+    with pytest.raises(ValueError, match="ValidationError"):
+        conditions_to_program([(0x13, 1)], NO_UNKNOWN_CONDS)
@@ -0,0 +1,60 @@
+from gold_rs import ConsensusConstants
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
+import pytest
+
+BASE = ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS))
+
+
+def test_no_overrides() -> None:
+    assert ConsensusConstants.from_dict_overrides(BASE, {}) == BASE
+    assert ConsensusConstants.from_json(BASE, "{}") == BASE
+
+
+def test_overrides() -> None:
+    challenge = bytes([0xCC] * 32)
+    constants = ConsensusConstants.from_dict_overrides(
+        BASE, {"SOFT_FORK5_HEIGHT": 1000, "GENESIS_CHALLENGE": challenge}
+    )
+    assert constants.SOFT_FORK5_HEIGHT == 1000
+    assert constants.GENESIS_CHALLENGE == challenge
+    # everything else is left alone
+    assert constants.HARD_FORK_HEIGHT == BASE.HARD_FORK_HEIGHT
+    assert constants.AGG_SIG_ME_ADDITIONAL_DATA == BASE.AGG_SIG_ME_ADDITIONAL_DATA
+
+    # hashes may be hex strings, with or without the 0x prefix
+    for value in [challenge.hex(), "0x" + challenge.hex()]:
+        constants = ConsensusConstants.from_dict_overrides(
+            BASE, {"GENESIS_CHALLENGE": value}
+        )
+        assert constants.GENESIS_CHALLENGE == challenge
+
+
+def test_from_json() -> None:
+    constants = ConsensusConstants.from_json(
+        BASE, '{"MEMPOOL_BLOCK_BUFFER": 20, "GENESIS_CHALLENGE": "' + "ab" * 32 + '"}'
+    )
+    assert constants.MEMPOOL_BLOCK_BUFFER == 20
+    assert constants.GENESIS_CHALLENGE == bytes([0xAB] * 32)
+
+
+def test_unknown_constant() -> None:
+    with pytest.raises(KeyError, match="unknown constant FOOBAR"):
+        ConsensusConstants.from_dict_overrides(BASE, {"FOOBAR": 1})
+    # field names are upper case
+    with pytest.raises(KeyError, match="unknown constant soft_fork5_height"):
+        ConsensusConstants.from_dict_overrides(BASE, {"soft_fork5_height": 1})
+
+
+@pytest.mark.parametrize(
+    "name,value",
+    [
+        ("SOFT_FORK5_HEIGHT", "foobar"),
+        ("SOFT_FORK5_HEIGHT", -1),
+        ("MIN_PLOT_SIZE", 256),
+        ("GENESIS_CHALLENGE", b"\x00" * 31),
+        ("GENESIS_CHALLENGE", "abcd"),
+    ],
+)
+def test_invalid_value(name: str, value: object) -> None:
+    with pytest.raises(ValueError, match=f"invalid value for {name}"):
+        ConsensusConstants.from_dict_overrides(BASE, {name: value})
@@ -0,0 +1,23 @@
+from gold_rs import AugSchemeMPL, DerivationCache
+from chia.wallet.puzzles.p2_delegated_puzzle_or_hidden_puzzle import puzzle_hash_for_pk
+
+
+def test_derive() -> None:
+    seed = bytes.fromhex(
+        "003206f418c701193458c013120c5906dc12663ad1520c3e596eb6092c14fe16"
+    )
+    intermediate = AugSchemeMPL.key_gen(seed).get_g1()
+
+    cache = DerivationCache(intermediate)
+    assert cache.len() == 0
+
+    keys = cache.derive(0, 20)
+    assert cache.len() == 20
+    for expected_idx, (idx, pk, ph) in enumerate(keys):
+        assert idx == expected_idx
+        assert pk == AugSchemeMPL.derive_child_pk_unhardened(intermediate, idx)
+        assert ph == puzzle_hash_for_pk(pk)
+
+    # already derived keys are served from the cache
+    assert cache.derive(10, 20)[:10] == keys[10:]
+    assert cache.len() == 30
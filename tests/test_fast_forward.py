@@ -3,6 +3,7 @@ from gold_rs import (
     Coin,
     supports_fast_forward,
     fast_forward_singleton,
+    fast_forward_chain,
 )
 import pytest
 
@@ -28,3 +29,26 @@ def test_fast_forward_singleton(file: str) -> None:
     # this is where the parent's parent coin ID lives in the solution
     expected[3:35] = parents_parent
     assert expected == new_solution
+
+
+@pytest.mark.parametrize("file", ["bb13", "e3c0"])
+def test_fast_forward_chain(file: str) -> None:
+    with open(f"ff-tests/{file}.spend", "rb") as f:
+        spend = CoinSpend.from_bytes(f.read())
+
+    ph = spend.coin.puzzle_hash
+    amount = spend.coin.amount
+    lineage = []
+    parent = Coin(bytes([0] * 32), ph, amount)
+    for _ in range(3):
+        coin = Coin(parent.name(), ph, amount)
+        lineage.append((parent, coin))
+        parent = coin
+
+    last_parent, last_coin = lineage[-1]
+    new_solution = fast_forward_chain(spend, lineage)
+    assert new_solution == fast_forward_singleton(spend, last_coin, last_parent)
+
+    # the generations must descend from one another
+    with pytest.raises(ValueError, match="generation 2"):
+        fast_forward_chain(spend, [lineage[0], lineage[1], lineage[1]])
@@ -0,0 +1,81 @@
+from typing import Dict, Optional
+
+import pytest
+
+from chia.types.blockchain_format.sized_bytes import bytes32
+from gold_rs import BlockRecord, ClassgroupElement, G1Element, compare_peaks, find_fork_point
+
+
+def h(v: int) -> bytes32:
+    return bytes32(bytes([v] * 32))
+
+
+def record(header_hash: int, prev_hash: int, height: int, weight: int, total_iters: int = 0) -> BlockRecord:
+    return BlockRecord(
+        h(header_hash),
+        h(prev_hash),
+        height,
+        weight,
+        total_iters or weight * 10,
+        0,
+        ClassgroupElement.get_default_element(),
+        None,
+        h(0),
+        h(0),
+        0,
+        h(0),
+        h(0),
+        0,
+        0,
+        False,
+        0,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        G1Element(),
+    )
+
+
+# the chain 1 <- 2 <- 3, the fork 2 <- 13 <- 14 and the unrelated block 21
+BLOCKS: Dict[bytes32, BlockRecord] = {
+    r.header_hash: r
+    for r in [
+        record(1, 0, 0, 1),
+        record(2, 1, 1, 2),
+        record(3, 2, 2, 3),
+        record(13, 2, 2, 3),
+        record(14, 13, 3, 4),
+        record(21, 20, 0, 1),
+    ]
+}
+
+
+def test_find_fork_point() -> None:
+    assert find_fork_point(BLOCKS[h(3)], BLOCKS[h(14)], BLOCKS) == 1
+    assert find_fork_point(BLOCKS[h(14)], BLOCKS[h(2)], BLOCKS) == 1
+    assert find_fork_point(BLOCKS[h(3)], BLOCKS[h(3)], BLOCKS) == 2
+    assert find_fork_point(BLOCKS[h(21)], BLOCKS[h(1)], BLOCKS) == -1
+
+    # the lookup may also be a function
+    def lookup(header_hash: bytes32) -> Optional[BlockRecord]:
+        return BLOCKS.get(header_hash)
+
+    assert find_fork_point(BLOCKS[h(3)], BLOCKS[h(14)], lookup) == 1
+
+    with pytest.raises(ValueError, match="block not found"):
+        find_fork_point(BLOCKS[h(3)], BLOCKS[h(14)], lambda _: None)
+
+
+def test_compare_peaks() -> None:
+    assert compare_peaks(BLOCKS[h(14)], BLOCKS[h(3)]) == 1
+    assert compare_peaks(BLOCKS[h(3)], BLOCKS[h(14)]) == -1
+    # same weight, fewer iterations wins
+    assert compare_peaks(record(3, 2, 2, 3, 29), BLOCKS[h(13)]) == 1
+    # all else equal, the lower header hash wins
+    assert compare_peaks(BLOCKS[h(3)], BLOCKS[h(13)]) == 1
+    assert compare_peaks(BLOCKS[h(3)], BLOCKS[h(3)]) == 0
@@ -0,0 +1,47 @@
+import pytest
+
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
+from chia.consensus.pot_iterations import calculate_prefix_bits
+from chia.full_node.mempool_check_conditions import get_flags_for_height_and_constants
+
+from gold_rs import ConsensusConstants, ForkSchedule
+
+CONSTANTS = ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS))
+
+schedule = ForkSchedule(CONSTANTS)
+
+heights = sorted(
+    {0, 1, 0xFFFFFFFF}
+    | {schedule.activation_height(f) for f in ForkSchedule.fork_names()}
+    | {max(schedule.activation_height(f) - 1, 0) for f in ForkSchedule.fork_names()}
+)
+
+
+@pytest.mark.parametrize("height", heights)
+def test_active_at(height: int) -> None:
+    active = schedule.active_at(height)
+    for fork in ForkSchedule.fork_names():
+        expected = height >= getattr(DEFAULT_CONSTANTS, f"{fork.upper()}_HEIGHT")
+        assert schedule.is_active(fork, height) == expected
+        assert (fork in active) == expected
+
+
+@pytest.mark.parametrize("height", heights)
+def test_flags_for_height(height: int) -> None:
+    assert schedule.flags_for_height(height) == get_flags_for_height_and_constants(
+        height, DEFAULT_CONSTANTS
+    )
+
+
+@pytest.mark.parametrize("height", heights)
+def test_plot_filter_bits(height: int) -> None:
+    assert schedule.plot_filter_bits(height) == calculate_prefix_bits(
+        DEFAULT_CONSTANTS, height
+    )
+
+
+def test_unknown_fork() -> None:
+    with pytest.raises(ValueError, match="unknown fork"):
+        schedule.is_active("soft_fork3", 0)
+    with pytest.raises(ValueError, match="unknown fork"):
+        schedule.activation_height("hard_fork2")
@@ -0,0 +1,172 @@
+from typing import Dict, List, Optional
+
+import pytest
+
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
+from chia.types.blockchain_format.sized_bytes import bytes32
+from chia.util.errors import Err
+from gold_rs import (
+    BlockRecord,
+    ChallengeChainSubSlot,
+    ClassgroupElement,
+    ConsensusConstants,
+    EndOfSubSlotBundle,
+    FullBlock,
+    G1Element,
+    G2Element,
+    HeaderContext,
+    RewardChainSubSlot,
+    SubSlotProofs,
+    UnfinishedHeaderBlock,
+    VDFInfo,
+    VDFProof,
+    precheck_header_blocks,
+    validate_sp_signatures,
+    validate_unfinished_header_block_linkage,
+)
+from gold_rs.test_support import BlockTools
+
+CONSTANTS = ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS))
+GENESIS = DEFAULT_CONSTANTS.GENESIS_CHALLENGE
+SSI = DEFAULT_CONSTANTS.SUB_SLOT_ITERS_STARTING
+MIN_BLOCKS = DEFAULT_CONSTANTS.MIN_BLOCKS_PER_CHALLENGE_BLOCK
+
+
+def h(v: int) -> bytes32:
+    return bytes32(v.to_bytes(4, "big") + bytes(28))
+
+
+# a block without a challenge block in its sub-slot, whose header hash is
+# h(height). Only the first block in the sub-slot has finished slot hashes
+def record(height: int, first_in_sub_slot: bool) -> BlockRecord:
+    return BlockRecord(
+        h(height),
+        h(height - 1),
+        height,
+        height,
+        height * 1000,
+        0,
+        ClassgroupElement.get_default_element(),
+        None,
+        h(1000 + height),
+        h(0),
+        SSI,
+        h(0),
+        h(0),
+        0,
+        MIN_BLOCKS,
+        False,
+        0,
+        None,
+        None,
+        None,
+        None,
+        [h(500)] if first_in_sub_slot else None,
+        None,
+        None,
+        None,
+        G1Element(),
+    )
+
+
+def sub_slot(cc_challenge: bytes32, rc_challenge: bytes32, deficit: int = MIN_BLOCKS) -> EndOfSubSlotBundle:
+    vdf = VDFInfo(cc_challenge, SSI, ClassgroupElement.get_default_element())
+    cc = ChallengeChainSubSlot(vdf, None, None, None, None)
+    rc = RewardChainSubSlot(vdf.replace(challenge=rc_challenge), cc.get_hash(), None, deficit)
+    proof = VDFProof(0, b"", True)
+    return EndOfSubSlotBundle(cc, None, rc, SubSlotProofs(proof, None, proof))
+
+
+def unfinished(block: FullBlock) -> UnfinishedHeaderBlock:
+    return UnfinishedHeaderBlock(
+        block.finished_sub_slots,
+        block.reward_chain_block.get_unfinished(),
+        block.challenge_chain_sp_proof,
+        block.reward_chain_sp_proof,
+        block.foliage,
+        block.foliage_transaction_block,
+        b"",
+    )
+
+
+def header(prev_hash: bytes32, slots: List[EndOfSubSlotBundle]) -> UnfinishedHeaderBlock:
+    hb = unfinished(BlockTools(CONSTANTS).next_block(None))
+    return hb.replace(finished_sub_slots=slots, foliage=hb.foliage.replace(prev_block_hash=prev_hash))
+
+
+def check(hb: UnfinishedHeaderBlock, prev: Optional[BlockRecord], blocks: Dict[bytes32, BlockRecord]) -> Optional[int]:
+    try:
+        validate_unfinished_header_block_linkage(hb, prev, CONSTANTS, blocks)
+        return None
+    except ValueError as e:
+        assert e.args[0] == "ValidationError"
+        return int(e.args[1])
+
+
+def test_genesis() -> None:
+    slot = sub_slot(GENESIS, GENESIS)
+    assert check(header(GENESIS, []), None, {}) is None
+    assert check(header(GENESIS, [slot]), None, {}) is None
+    assert check(header(h(1), [slot]), None, {}) == Err.INVALID_PREV_BLOCK_HASH.value
+    assert check(header(GENESIS, [sub_slot(h(1), GENESIS)]), None, {}) == Err.INVALID_PREV_CHALLENGE_SLOT_HASH.value
+    assert check(header(GENESIS, [sub_slot(GENESIS, h(1))]), None, {}) == Err.INVALID_RC_EOS_VDF.value
+    assert check(header(GENESIS, [sub_slot(GENESIS, GENESIS, 0)]), None, {}) == Err.INVALID_DEFICIT.value
+
+
+def test_empty_sub_slots() -> None:
+    prev = record(10, True)
+    slot = sub_slot(h(500), prev.reward_infusion_new_challenge)
+    empty = sub_slot(slot.challenge_chain.get_hash(), slot.reward_chain.get_hash())
+    assert check(header(prev.header_hash, [slot, empty]), prev, {}) is None
+    # the second sub-slot has to continue from the first
+    assert check(header(prev.header_hash, [slot, slot]), prev, {}) == Err.INVALID_PREV_CHALLENGE_SLOT_HASH.value
+
+
+def test_lookup() -> None:
+    # prev isn't the first block in its sub-slot
+    blocks = {r.header_hash: r for r in [record(height, height == 5) for height in range(5, 10)]}
+    prev = record(10, False)
+    hb = header(prev.header_hash, [sub_slot(h(500), prev.reward_infusion_new_challenge)])
+    assert check(hb, prev, blocks) is None
+    assert validate_unfinished_header_block_linkage(hb, prev, CONSTANTS, blocks.get) is None
+
+    del blocks[h(7)]
+    with pytest.raises(ValueError, match="block not found"):
+        validate_unfinished_header_block_linkage(hb, prev, CONSTANTS, blocks)
+
+
+def test_sp_signatures() -> None:
+    blocks = BlockTools(CONSTANTS).get_consecutive_blocks(40)
+    headers = [unfinished(b) for b in blocks]
+    validate_sp_signatures(headers, None, CONSTANTS, {})
+
+    rcb = headers[7].reward_chain_block
+    headers[7] = headers[7].replace(reward_chain_block=rcb.replace(reward_chain_sp_signature=G2Element()))
+    with pytest.raises(ValueError, match="ValidationError") as e:
+        validate_sp_signatures(headers, None, CONSTANTS, {})
+    assert e.value.args[1] == Err.INVALID_RC_SIGNATURE.value
+
+
+def test_precheck_header_blocks() -> None:
+    # BlockTools' proofs of space only pass the plot filter without one
+    constants = ConsensusConstants.from_dict_overrides(CONSTANTS, {"NUMBER_ZERO_BITS_PLOT_FILTER": 0})
+    headers = [b.get_block_header() for b in BlockTools(constants).get_consecutive_blocks(40)]
+
+    ctx = precheck_header_blocks(headers, constants)
+    assert ctx.header_hash == headers[-1].header_hash
+    assert ctx.height == 39
+    assert ctx.weight == headers[-1].weight
+
+    # in ranges, each continuing from the context of the previous one
+    ranges = HeaderContext.genesis(constants)
+    for i in range(0, 40, 16):
+        ranges = precheck_header_blocks(headers[i : i + 16], constants, ranges)
+    assert ranges == ctx
+
+    with pytest.raises(ValueError, match="ValidationError") as e:
+        precheck_header_blocks(headers[1:], constants)
+    assert e.value.args[1] == Err.INVALID_PREV_BLOCK_HASH.value
+
+    with pytest.raises(ValueError, match="ValidationError") as e:
+        precheck_header_blocks(headers, CONSTANTS)
+    assert e.value.args[1] == Err.INVALID_POSPACE.value
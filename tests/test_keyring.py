@@ -0,0 +1,38 @@
+from pathlib import Path
+
+import pytest
+from gold_rs import AugSchemeMPL, Keyring
+
+
+def test_save_open(tmp_path: Path) -> None:
+    farmer = AugSchemeMPL.key_gen(b"1" * 32)
+    wallet = AugSchemeMPL.key_gen(b"2" * 32)
+
+    keyring = Keyring()
+    keyring.add("farmer", farmer)
+    keyring.add("wallet", wallet)
+    assert len(keyring) == 2
+
+    with pytest.raises(ValueError, match="already exists"):
+        keyring.add("farmer", wallet)
+
+    path = str(tmp_path / "keyring.bin")
+    keyring.save(path, "passphrase")
+
+    loaded = Keyring.open(path, "passphrase")
+    assert loaded.labels() == ["farmer", "wallet"]
+    assert loaded.get("farmer") == farmer
+    assert loaded.get("pool") is None
+    assert loaded.get_by_fingerprint(wallet.get_g1().get_fingerprint()) == (
+        "wallet",
+        wallet,
+    )
+
+    assert loaded.remove("farmer") == farmer
+    assert loaded.labels() == ["wallet"]
+
+    with pytest.raises(ValueError, match="incorrect passphrase"):
+        Keyring.open(path, "wrong")
+
+    with pytest.raises(OSError):
+        Keyring.open(str(tmp_path / "missing"), "passphrase")
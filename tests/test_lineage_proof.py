@@ -0,0 +1,61 @@
+import pytest
+
+from chia.types.blockchain_format.coin import Coin as PyCoin
+from chia.types.blockchain_format.program import Program as PyProgram
+from chia.types.blockchain_format.sized_bytes import bytes32
+from chia.types.coin_spend import make_spend
+from chia.wallet.cat_wallet.cat_utils import construct_cat_puzzle
+from chia.wallet.puzzles.cat_loader import CAT_MOD
+from chia.wallet.puzzles import singleton_top_layer_v1_1 as singleton
+
+from gold_rs import (
+    CoinSpend,
+    LineageProof,
+    lineage_proof_for_parent,
+    verify_lineage_proof,
+)
+
+ASSET_ID = bytes32([7] * 32)
+
+
+def outer_puzzle(kind: str, inner: PyProgram) -> PyProgram:
+    if kind == "cat":
+        return construct_cat_puzzle(CAT_MOD, ASSET_ID, inner)
+    return singleton.puzzle_for_singleton(ASSET_ID, inner)
+
+
+def make_coin_spend(kind: str, inner: PyProgram, parent: bytes32, amount: int) -> CoinSpend:
+    puzzle = outer_puzzle(kind, inner)
+    coin = PyCoin(parent, puzzle.get_tree_hash(), amount)
+    return CoinSpend.from_bytes(bytes(make_spend(coin, puzzle, PyProgram.to(0))))
+
+
+@pytest.mark.parametrize("kind", ["cat", "singleton"])
+def test_lineage_proof(kind: str) -> None:
+    parent_inner = PyProgram.to((1, 1))
+    parent = make_coin_spend(kind, parent_inner, bytes32([1] * 32), 3)
+    child = make_coin_spend(kind, PyProgram.to((1, 2)), parent.coin.name(), 5)
+
+    proof = lineage_proof_for_parent(parent)
+    assert proof == LineageProof(parent.coin.parent_coin_info, parent_inner.get_tree_hash(), 3)
+    assert proof.parent_parent_coin_id == parent.coin.parent_coin_info
+    assert proof.parent_inner_puzzle_hash == parent_inner.get_tree_hash()
+    assert proof.parent_amount == 3
+    assert verify_lineage_proof(proof, child)
+
+    # the CLVM form matches the one the python wallet puts in its solutions
+    if kind == "singleton":
+        expected = singleton.lineage_proof_for_coinsol(parent).to_program()
+        assert bytes(proof.to_program()) == bytes(expected)
+
+    bad_proof = LineageProof(parent.coin.parent_coin_info, parent_inner.get_tree_hash(), 4)
+    assert bad_proof != proof
+    assert not verify_lineage_proof(bad_proof, child)
+
+
+def test_not_cat_or_singleton() -> None:
+    puzzle = PyProgram.to((1, 1))
+    coin = PyCoin(bytes32([0] * 32), puzzle.get_tree_hash(), 1)
+    spend = CoinSpend.from_bytes(bytes(make_spend(coin, puzzle, PyProgram.to(0))))
+    with pytest.raises(ValueError, match="not a CAT or singleton puzzle"):
+        lineage_proof_for_parent(spend)
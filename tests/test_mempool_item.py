@@ -0,0 +1,68 @@
+from typing import List
+
+import pytest
+
+from chia.types.blockchain_format.sized_bytes import bytes32
+
+from gold_rs import (
+    ELIGIBLE_FOR_DEDUP,
+    MEMPOOL_MODE,
+    Coin,
+    CoinSpend,
+    G2Element,
+    MempoolItem,
+    Program,
+    SpendBundle,
+    mempool_block_order,
+    mempool_eviction_order,
+    run_spend,
+)
+
+MAX_COST = 11000000000
+
+CREATE_COIN = 51
+
+
+def make_item(amount: int, outputs: List[int], height_added: int) -> MempoolItem:
+    puzzle = Program.to((1, [[CREATE_COIN, bytes32([1] * 32), a] for a in outputs]))
+    coin = Coin(bytes32([amount % 256] * 32), puzzle.get_tree_hash(), amount)
+    spend = CoinSpend(coin, puzzle, Program.to(0))
+    conds = run_spend(spend, MAX_COST, MEMPOOL_MODE)
+    return MempoolItem(SpendBundle([spend], G2Element()), conds, height_added)
+
+
+def test_mempool_item() -> None:
+    item = make_item(1000, [300, 100], 7)
+    spend = item.spend_bundle.coin_spends[0]
+
+    assert item.spend_bundle_name == item.spend_bundle.name()
+    assert item.fee == 600
+    assert item.cost > 0
+    assert item.fee_per_cost == item.fee / item.cost
+    assert item.height_added == 7
+    assert item.removals == [spend.coin]
+    assert sorted(c.amount for c in item.additions) == [100, 300]
+    assert all(c.parent_coin_info == spend.coin.name() for c in item.additions)
+    assert len(item.spend_flags) == 1
+    assert item.spend_flags[0] & ELIGIBLE_FOR_DEDUP != 0
+
+
+def test_invalid_fee() -> None:
+    with pytest.raises(ValueError, match="invalid fee"):
+        make_item(100, [300], 1)
+
+
+def test_ordering() -> None:
+    # all items have the same cost, so the fee per cost is determined by the
+    # fee
+    low = make_item(1000, [900], 1)
+    high = make_item(1001, [1], 1)
+    mid_old = make_item(1002, [500], 1)
+    mid_new = make_item(1003, [501], 2)
+    items = [mid_old, high, low, mid_new]
+
+    def names(items: List[MempoolItem]) -> List[bytes32]:
+        return [i.spend_bundle_name for i in items]
+
+    assert names(mempool_eviction_order(items)) == names([low, mid_new, mid_old, high])
+    assert names(mempool_block_order(items)) == names([high, mid_old, mid_new, low])
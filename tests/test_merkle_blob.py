@@ -0,0 +1,58 @@
+from hashlib import sha256
+
+import pytest
+from gold_rs import MerkleBlob, ProofOfInclusion
+
+
+def leaf_hash(key: int, value: int) -> bytes:
+    return sha256(
+        key.to_bytes(8, "big", signed=True) + value.to_bytes(8, "big", signed=True)
+    ).digest()
+
+
+def test_insert_delete() -> None:
+    blob = MerkleBlob()
+    assert len(blob) == 0
+    assert blob.get_root_hash() is None
+
+    for key in range(20):
+        blob.insert(key, key * 2, leaf_hash(key, key * 2))
+    assert len(blob) == 20
+    assert blob.get_keys_values() == {k: k * 2 for k in range(20)}
+
+    with pytest.raises(ValueError, match="already present"):
+        blob.insert(3, 0, leaf_hash(3, 0))
+
+    root = blob.get_root_hash()
+    reloaded = MerkleBlob(blob.get_blob())
+    assert reloaded.get_root_hash() == root
+    assert len(reloaded) == 20
+
+    for key in range(20):
+        blob.delete(key)
+    assert len(blob) == 0
+    assert blob.get_root_hash() is None
+
+    with pytest.raises(ValueError, match="unknown key"):
+        blob.delete(0)
+
+
+def test_proof_of_inclusion() -> None:
+    blob = MerkleBlob()
+    for key in range(10):
+        blob.insert(key, key, leaf_hash(key, key))
+
+    for key in range(10):
+        proof = blob.get_proof_of_inclusion(key)
+        assert proof.node_hash == leaf_hash(key, key)
+        assert proof.root_hash() == blob.get_root_hash()
+        assert proof.valid()
+        assert ProofOfInclusion.from_bytes(bytes(proof)) == proof
+
+        bad = proof.replace(node_hash=leaf_hash(key, key + 1))
+        assert not bad.valid()
+
+
+def test_invalid_blob() -> None:
+    with pytest.raises(ValueError, match="block size"):
+        MerkleBlob(b"\x00" * 10)
@@ -0,0 +1,44 @@
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
+
+from gold_rs import (
+    ConsensusConstants,
+    NetworkInfo,
+    agg_sig_additional_data,
+)
+
+CONSTANTS = ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS))
+AGG_SIG_PARENT = 43
+AGG_SIG_ME = 50
+
+
+def test_presets() -> None:
+    mainnet = NetworkInfo.mainnet()
+    assert mainnet.network_id == "mainnet"
+    assert mainnet.address_prefix == "xch"
+    assert NetworkInfo.from_network_id("mainnet") == mainnet
+
+    testnet = NetworkInfo.testnet11()
+    assert testnet.network_id == "testnet11"
+    assert testnet.address_prefix == "txch"
+    assert NetworkInfo.from_network_id("testnet11") == testnet
+
+    assert NetworkInfo.from_network_id("foobar") is None
+
+
+def test_agg_sig_additional_data() -> None:
+    mainnet = NetworkInfo.mainnet()
+    testnet = NetworkInfo.testnet11()
+    for opcode in [AGG_SIG_PARENT, AGG_SIG_ME]:
+        assert mainnet.agg_sig_additional_data(
+            opcode
+        ) != testnet.agg_sig_additional_data(opcode)
+    assert mainnet.agg_sig_additional_data(AGG_SIG_ME) == mainnet.genesis_challenge
+
+    # a network built from the constants signs the same messages as the
+    # constants do
+    network = NetworkInfo.from_constants("simulator0", CONSTANTS, "txch")
+    assert network.matches_constants(CONSTANTS)
+    for opcode in [AGG_SIG_PARENT, AGG_SIG_ME]:
+        assert network.agg_sig_additional_data(opcode) == agg_sig_additional_data(
+            CONSTANTS, opcode
+        )
@@ -0,0 +1,66 @@
+from gold_rs import (
+    parse_message,
+    NewPeakWallet,
+    RejectBlock,
+    RequestBlock,
+    RequestPeers,
+    RespondBlock,
+    RespondPeers,
+    PlotSyncIdentifier,
+    PlotSyncPathList,
+)
+import pytest
+
+# message type values, from ProtocolMessageTypes
+NEW_PEAK_WALLET = 50
+REQUEST_PEERS = 43
+PLOT_SYNC_INVALID = 81
+NONE_RESPONSE = 91
+
+
+def test_parse_message() -> None:
+    msg = NewPeakWallet(b"\x01" * 32, 10, 20, 5)
+    obj = parse_message(NEW_PEAK_WALLET, bytes(msg))
+    assert type(obj) is NewPeakWallet
+    assert obj == msg
+
+    obj = parse_message(REQUEST_PEERS, b"")
+    assert type(obj) is RequestPeers
+
+
+def test_parse_invalid_message() -> None:
+    with pytest.raises(ValueError):
+        parse_message(REQUEST_PEERS, b"\x00")
+    with pytest.raises(ValueError):
+        parse_message(NONE_RESPONSE, b"")
+    # truncated
+    msg = NewPeakWallet(b"\x01" * 32, 10, 20, 5)
+    with pytest.raises(ValueError):
+        parse_message(NEW_PEAK_WALLET, bytes(msg)[:-1])
+
+
+def test_parse_plot_sync_path_list() -> None:
+    msg = PlotSyncPathList(PlotSyncIdentifier(1, 2, 3), ["foo"], True)
+    obj = parse_message(PLOT_SYNC_INVALID, bytes(msg))
+    assert obj == msg
+
+    # the field is called "final" in python and JSON, like in chia
+    assert msg.final is True
+    assert msg.to_json_dict()["final"] is True
+    assert PlotSyncPathList.from_json_dict(msg.to_json_dict()) == msg
+    assert msg.replace(final=False).final is False
+    assert PlotSyncPathList(PlotSyncIdentifier(1, 2, 3), ["foo"], final=True) == msg
+
+
+def test_message_responses() -> None:
+    assert RequestBlock.MSG_TYPE == 26
+    assert RequestBlock.RESPONSES == [RespondBlock, RejectBlock]
+    assert RequestPeers.MSG_TYPE == REQUEST_PEERS
+    assert RequestPeers.RESPONSES == [RespondPeers]
+    # responses aren't requests themselves
+    assert RespondBlock.RESPONSES == []
+    assert NewPeakWallet.RESPONSES == []
+
+    # a reply can be checked against the request generically
+    reply = parse_message(RejectBlock.MSG_TYPE, bytes(RejectBlock(100)))
+    assert type(reply) in RequestBlock.RESPONSES
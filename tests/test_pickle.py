@@ -0,0 +1,91 @@
+import copy
+import pickle
+import struct
+
+import pytest
+
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
+from gold_rs import (
+    AugSchemeMPL,
+    Coin,
+    CoinState,
+    ConsensusConstants,
+    G1Element,
+    G2Element,
+    Program,
+    SubEpochChallengeSegment,
+)
+from gold_rs.sized_bytes import bytes32
+from gold_rs.sized_ints import uint32
+
+PICKLE_MAGIC = b"\xffGRS"
+
+sk = AugSchemeMPL.key_gen(bytes32([1] * 32))
+coin = Coin(bytes32([2] * 32), bytes32([3] * 32), 1337)
+
+
+@pytest.mark.parametrize(
+    "obj",
+    [
+        coin,
+        CoinState(coin, None, 10),
+        SubEpochChallengeSegment(7, [], None),
+        Program.from_bytes(bytes.fromhex("ff0180")),
+        sk,
+        sk.get_g1(),
+        AugSchemeMPL.sign(sk, b"foobar"),
+        ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS)),
+    ],
+)
+def test_round_trip(obj: object) -> None:
+    for protocol in range(2, pickle.HIGHEST_PROTOCOL + 1):
+        blob = pickle.dumps(obj, protocol=protocol)
+        loaded = pickle.loads(blob)
+        assert type(loaded) is type(obj)
+        assert loaded == obj
+
+
+def state_of(obj: object) -> bytes:
+    func, (state,) = obj.__reduce__()  # type: ignore[attr-defined]
+    assert func == type(obj)._from_pickle  # type: ignore[attr-defined]
+    return state
+
+
+def test_header() -> None:
+    state = state_of(coin)
+    assert state[:4] == PICKLE_MAGIC
+    # the format of the header and the pickle version of the type
+    assert state[4:9] == b"\x01" + struct.pack(">I", 1)
+    assert state[9:] == bytes(coin)
+
+
+def test_unsupported_version() -> None:
+    state = state_of(coin)
+    newer = state[:5] + struct.pack(">I", 2) + state[9:]
+    with pytest.raises(ValueError, match="failed to unpickle Coin: pickle version 2 is not supported"):
+        Coin._from_pickle(newer)
+
+    with pytest.raises(ValueError, match="invalid pickle header"):
+        Coin._from_pickle(state[:6])
+
+
+def test_legacy_pickle() -> None:
+    # the versioned format requires the header, even if the rest happens to be
+    # a valid serialization
+    with pytest.raises(ValueError, match="invalid pickle header"):
+        Coin._from_pickle(bytes(coin))
+
+    # ConsensusConstants used to be pickled with __getnewargs__() and
+    # __setstate__(), with just the serialization as state
+    constants = ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS))
+    modified = constants.replace(HARD_FORK_HEIGHT=uint32(1234))
+    obj = copy.copy(constants)
+    obj.__setstate__(bytes(modified))
+    assert obj == modified
+    with pytest.raises(ValueError, match="pickle version 0 is not supported"):
+        obj.__setstate__(bytes(modified)[:-1])
+
+
+def test_bls_types() -> None:
+    assert pickle.loads(pickle.dumps(G1Element())) == G1Element()
+    assert pickle.loads(pickle.dumps(G2Element())) == G2Element()
@@ -0,0 +1,42 @@
+from gold_rs import (
+    AuthenticationPayload,
+    PoolErrorResponse,
+    PutFarmerPayload,
+    G1Element,
+)
+from chia.protocols import pool_protocol
+from chia.types.blockchain_format.sized_bytes import bytes32
+from chia.util.ints import uint16, uint64
+
+launcher_id = bytes32(b"\x01" * 32)
+target_ph = bytes32(b"\x02" * 32)
+
+
+def test_authentication_payload() -> None:
+    py = pool_protocol.AuthenticationPayload(
+        "get_farmer", launcher_id, target_ph, uint64(1234)
+    )
+    rs = AuthenticationPayload("get_farmer", launcher_id, target_ph, uint64(1234))
+    assert bytes(rs) == bytes(py)
+    assert rs.get_hash() == py.get_hash()
+    assert AuthenticationPayload.from_bytes(bytes(py)) == rs
+    assert rs.to_json_dict() == py.to_json_dict()
+
+
+def test_put_farmer_payload() -> None:
+    pk = G1Element()
+    py = pool_protocol.PutFarmerPayload(
+        launcher_id, uint64(1), pk, None, uint64(10)
+    )
+    rs = PutFarmerPayload(launcher_id, uint64(1), pk, None, uint64(10))
+    assert bytes(rs) == bytes(py)
+    assert PutFarmerPayload.from_bytes(bytes(py)) == rs
+
+
+def test_error_response() -> None:
+    py = pool_protocol.ErrorResponse(
+        uint16(pool_protocol.PoolErrorCode.FARMER_NOT_KNOWN.value), "unknown farmer"
+    )
+    rs = PoolErrorResponse(10, "unknown farmer")
+    assert bytes(rs) == bytes(py)
+    assert rs.to_json_dict() == py.to_json_dict()
@@ -0,0 +1,113 @@
+import pytest
+
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
+from chia.pools.pool_puzzles import (
+    SINGLETON_LAUNCHER_HASH,
+    create_full_puzzle,
+    pool_state_to_inner_puzzle,
+)
+from chia.pools.pool_wallet_info import PoolSingletonState, create_pool_state
+from chia.types.blockchain_format.coin import Coin as PyCoin
+from chia.types.blockchain_format.program import Program as PyProgram
+from chia.types.blockchain_format.sized_bytes import bytes32
+from chia.types.coin_spend import make_spend
+
+from gold_rs import (
+    FARMING_TO_POOL,
+    LEAVING_POOL,
+    SELF_POOLING,
+    AugSchemeMPL,
+    CoinSpend,
+    PoolState,
+    Program,
+    parse_pool_puzzle,
+    pool_state_from_extra_data,
+    solution_to_pool_state,
+)
+
+LAUNCHER_ID = bytes32([1] * 32)
+TARGET_PUZZLE_HASH = bytes32([2] * 32)
+DELAY_PUZZLE_HASH = bytes32([3] * 32)
+OWNER_PUBKEY = AugSchemeMPL.key_gen(bytes([4] * 32)).get_g1()
+
+
+def py_pool_state(state: PoolSingletonState, lock_height: int = 100):
+    return create_pool_state(
+        state, TARGET_PUZZLE_HASH, OWNER_PUBKEY, "https://pool.example.com", lock_height
+    )
+
+
+def test_constants() -> None:
+    assert SELF_POOLING == PoolSingletonState.SELF_POOLING
+    assert LEAVING_POOL == PoolSingletonState.LEAVING_POOL
+    assert FARMING_TO_POOL == PoolSingletonState.FARMING_TO_POOL
+
+
+@pytest.mark.parametrize("lock_height", [0, 32, 1000])
+@pytest.mark.parametrize(
+    "state",
+    [
+        PoolSingletonState.SELF_POOLING,
+        PoolSingletonState.LEAVING_POOL,
+        PoolSingletonState.FARMING_TO_POOL,
+    ],
+)
+def test_parse_pool_puzzle(state: PoolSingletonState, lock_height: int) -> None:
+    pool_state = py_pool_state(state, lock_height)
+    inner = pool_state_to_inner_puzzle(
+        pool_state,
+        LAUNCHER_ID,
+        DEFAULT_CONSTANTS.GENESIS_CHALLENGE,
+        600,
+        DELAY_PUZZLE_HASH,
+    )
+    puzzle = create_full_puzzle(inner, LAUNCHER_ID)
+
+    parsed = parse_pool_puzzle(Program.from_bytes(bytes(puzzle)))
+    assert parsed.launcher_id == LAUNCHER_ID
+    assert parsed.target_puzzle_hash == TARGET_PUZZLE_HASH
+    assert parsed.owner_pubkey == OWNER_PUBKEY
+    assert parsed.pool_reward_prefix == DEFAULT_CONSTANTS.GENESIS_CHALLENGE[:16] + bytes(16)
+    if state == PoolSingletonState.LEAVING_POOL:
+        assert parsed.state == LEAVING_POOL
+        assert parsed.relative_lock_height == lock_height
+        assert parsed.escape_puzzle_hash is None
+    else:
+        # self pooling uses the same inner puzzle as farming to a pool
+        assert parsed.state == FARMING_TO_POOL
+        assert parsed.relative_lock_height is None
+        assert parsed.escape_puzzle_hash is not None
+
+
+def test_parse_not_pool_puzzle() -> None:
+    with pytest.raises(ValueError, match="not a pool singleton puzzle"):
+        parse_pool_puzzle(Program.to(1))
+
+
+def test_parse_foreign_inner_puzzle() -> None:
+    # the same curried arguments as the member puzzle, but a different mod
+    inner = PyProgram.to(1).curry(
+        TARGET_PUZZLE_HASH,
+        bytes32([7] * 32),
+        bytes(OWNER_PUBKEY),
+        DEFAULT_CONSTANTS.GENESIS_CHALLENGE[:16] + bytes(16),
+        DELAY_PUZZLE_HASH,
+    )
+    puzzle = create_full_puzzle(inner, LAUNCHER_ID)
+    with pytest.raises(ValueError, match="not a pool singleton puzzle"):
+        parse_pool_puzzle(Program.from_bytes(bytes(puzzle)))
+
+
+def test_launcher_solution() -> None:
+    pool_state = py_pool_state(PoolSingletonState.FARMING_TO_POOL)
+    extra_data = PyProgram.to(
+        [("p", bytes(pool_state)), ("t", 600), ("h", DELAY_PUZZLE_HASH)]
+    )
+    solution = PyProgram.to([bytes32([5] * 32), 1, extra_data])
+    coin = PyCoin(bytes32([6] * 32), SINGLETON_LAUNCHER_HASH, 1)
+    spend = CoinSpend.from_bytes(bytes(make_spend(coin, PyProgram.to(1), solution)))
+
+    expected = PoolState.from_bytes(bytes(pool_state))
+    assert solution_to_pool_state(spend) == expected
+    assert pool_state_from_extra_data(Program.from_bytes(bytes(extra_data))) == expected
+    assert pool_state_from_extra_data(Program.to([("t", 600)])) is None
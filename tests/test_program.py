@@ -1,4 +1,4 @@
-from gold_rs import run_chia_program, Program
+from gold_rs import profile_chia_program, run_chia_program, Program
 
 
 def test_raise() -> None:
@@ -23,3 +23,67 @@ def test_raise_program() -> None:
         assert False
     except ValueError as e:
         assert f"{e}" == "('clvm raise', '86666f6f626172')"
+
+
+def test_run() -> None:
+    # (+ 2 5)
+    prg = Program.fromhex("ff10ff02ff0580")
+    cost, result = prg.run([1300, 37], 1000)
+    assert cost == 869
+    assert result == Program.to(1337)
+
+    try:
+        prg.run([1300, 37], 10)
+        assert False
+    except ValueError as e:
+        assert e.args[0] == "cost exceeded"
+
+
+def test_to_python() -> None:
+    prg = Program.from_python([1, b"foo", (b"a", b"b"), []])
+    assert prg.to_python() == (b"\x01", (b"foo", ((b"a", b"b"), (b"", b""))))
+    assert Program.from_python(prg.to_python()) == prg
+    assert Program.from_python(1337).to_python() == (1337).to_bytes(2, "big")
+
+
+def test_structure() -> None:
+    prg = Program.to([1, [2, 3], b"foo", -5])
+    assert prg.first().as_int() == 1
+    second = prg.rest().first()
+    assert second.first().as_int() == 2
+    assert second.rest().first().as_int() == 3
+    assert prg.rest().rest().rest().first().as_int() == -5
+    assert prg.rest().rest().rest().rest().atom == b""
+
+    pair = prg.pair()
+    assert pair is not None
+    assert pair[0].atom == b"\x01"
+    assert Program.to(1).pair() is None
+
+    # stops at the first item that isn't an atom
+    assert prg.as_atom_list() == [b"\x01"]
+    assert Program.to([b"a", b"b", 3]).as_atom_list() == [b"a", b"b", b"\x03"]
+
+    try:
+        Program.to(1).first()
+        assert False
+    except ValueError as e:
+        assert f"{e}" == "first of non-cons"
+
+    try:
+        prg.as_int()
+        assert False
+    except ValueError as e:
+        assert f"{e}" == "as_int() of non-atom"
+
+
+def test_profile_chia_program() -> None:
+    # (+ (q . 5) (* (q . 7) (q . 7)) (q . 1))
+    program = bytes.fromhex("ff10ffff0105ffff12ffff0107ffff010780ffff010180")
+    cost, result, profile = profile_chia_program(program, b"\x80", 100000, 0)
+    assert result.as_int() == 55
+    assert cost == run_chia_program(program, b"\x80", 100000, 0)[0]
+    assert set(profile.keys()) == {b"\x10", b"\x12"}
+    assert profile[b"\x10"][0] == 1
+    assert profile[b"\x12"][0] == 1
+    assert profile[b"\x10"][1] + profile[b"\x12"][1] < cost
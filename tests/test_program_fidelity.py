@@ -117,6 +117,20 @@ def test_uncurry() -> None:
         assert py_prg.uncurry() == rust_prg.uncurry()
 
 
+def test_curry() -> None:
+
+    rnd = Random()
+    for _ in range(10000):
+        obj = rand_object(rnd)
+        py_prg = ChiaProgram.to(obj)
+        rust_prg = gold_rs.Program.from_program(py_prg)
+
+        args = [rand_object(rnd) for _ in range(rnd.randint(0, 3))]
+        rust_curried = rust_prg.curry(*args)
+        assert py_prg.curry(*args) == rust_curried.to_program()
+        assert py_prg.curry(*args).uncurry() == rust_curried.uncurry()
+
+
 def test_round_trip() -> None:
 
     rnd = Random()
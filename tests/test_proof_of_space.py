@@ -0,0 +1,60 @@
+import pytest
+
+from chia.types.blockchain_format.proof_of_space import (
+    calculate_plot_id_ph as py_calculate_plot_id_ph,
+    calculate_plot_id_pk as py_calculate_plot_id_pk,
+    calculate_pos_challenge as py_calculate_pos_challenge,
+    passes_plot_filter as py_passes_plot_filter,
+)
+from chia.types.blockchain_format.sized_bytes import bytes32
+from gold_rs import (
+    AugSchemeMPL,
+    ClassgroupElement,
+    ProofOfSpace,
+    VDFInfo,
+    calculate_plot_id_ph,
+    calculate_plot_id_pk,
+    calculate_pos_challenge,
+    get_plot_id,
+    passes_plot_filter,
+    signage_point_hash,
+)
+
+challenge = bytes32(b"\x02" * 32)
+sp_hash = bytes32(b"\x03" * 32)
+plot_pk = AugSchemeMPL.key_gen(b"\x01" * 32).get_g1()
+pool_pk = AugSchemeMPL.key_gen(b"\x03" * 32).get_g1()
+pool_ph = bytes32(b"\x04" * 32)
+
+
+def test_plot_id() -> None:
+    assert calculate_plot_id_pk(pool_pk, plot_pk) == py_calculate_plot_id_pk(pool_pk, plot_pk)
+    assert calculate_plot_id_ph(pool_ph, plot_pk) == py_calculate_plot_id_ph(pool_ph, plot_pk)
+
+
+def test_get_plot_id() -> None:
+    farmer_pk = AugSchemeMPL.key_gen(b"\x02" * 32).get_g1()
+    pos = ProofOfSpace(challenge, pool_pk, None, plot_pk, 32, b"", farmer_pk)
+    assert get_plot_id(pos) == calculate_plot_id_pk(pool_pk, pos.plot_public_key())
+    pos = ProofOfSpace(challenge, None, pool_ph, plot_pk, 32, b"", farmer_pk)
+    assert get_plot_id(pos) == calculate_plot_id_ph(pool_ph, pos.plot_public_key())
+    assert get_plot_id(ProofOfSpace(challenge, None, None, plot_pk, 32, b"", farmer_pk)) is None
+
+
+def test_signage_point_hash() -> None:
+    assert signage_point_hash(challenge) == challenge
+    output = ClassgroupElement.get_default_element()
+    vdf = VDFInfo(challenge, 100, output)
+    assert signage_point_hash(challenge, vdf) == output.get_hash()
+
+
+@pytest.mark.parametrize("prefix_bits", [0, 1, 5, 9])
+def test_plot_filter(prefix_bits: int) -> None:
+    for i in range(500):
+        plot_id = bytes32(i.to_bytes(32, "big"))
+        assert calculate_pos_challenge(plot_id, challenge, sp_hash) == py_calculate_pos_challenge(
+            plot_id, challenge, sp_hash
+        )
+        assert passes_plot_filter(prefix_bits, plot_id, challenge, sp_hash) == py_passes_plot_filter(
+            prefix_bits, plot_id, challenge, sp_hash
+        )
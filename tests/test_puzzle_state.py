@@ -0,0 +1,72 @@
+from typing import List, Optional
+
+import pytest
+
+from chia.types.blockchain_format.sized_bytes import bytes32
+from gold_rs import Coin, CoinState, CoinStateFilters, filter_coin_states, puzzle_state_responses
+
+ph = bytes32(b"\x02" * 32)
+
+
+def header_hash(height: int) -> bytes32:
+    return bytes32(height.to_bytes(32, "big"))
+
+
+def last_height(state: CoinState) -> int:
+    return max(state.spent_height or 0, state.created_height or 0)
+
+
+def make_states(count: int) -> List[CoinState]:
+    # one coin per height, starting at 1. Every other coin is spent
+    return [
+        CoinState(
+            Coin(bytes32(i.to_bytes(32, "big")), ph, i),
+            i + 1 if i % 2 else None,
+            i + 1 if i % 2 == 0 else i,
+        )
+        for i in range(count)
+    ]
+
+
+def test_filter_coin_states() -> None:
+    states = make_states(10)
+    assert filter_coin_states(states + states, CoinStateFilters(True, True, False, 0), 0) == states
+    unspent = filter_coin_states(states, CoinStateFilters(False, True, False, 0), 0)
+    assert all(s.spent_height is None for s in unspent)
+    assert len(unspent) == 5
+    assert filter_coin_states(states, CoinStateFilters(True, True, False, 8), 0) == states[8:]
+    assert filter_coin_states(states, CoinStateFilters(True, True, False, 0), 9) == states[8:]
+
+
+def test_responses() -> None:
+    states = make_states(1000)
+    [response] = puzzle_state_responses([ph], states, 2000, header_hash(2000), {})
+    assert response.is_finished
+    assert response.height == 2000
+    assert response.header_hash == header_hash(2000)
+    assert response.coin_states == states
+
+    max_size = 10000
+    lookups: List[int] = []
+
+    def lookup(height: int) -> Optional[bytes32]:
+        lookups.append(height)
+        return header_hash(height)
+
+    responses = puzzle_state_responses([ph], list(reversed(states)), 2000, header_hash(2000), lookup, max_size)
+    assert len(responses) > 1
+    assert [s for r in responses for s in r.coin_states] == states
+    assert [r.height for r in responses[:-1]] == lookups
+    for response, next_response in zip(responses, responses[1:]):
+        assert not response.is_finished
+        assert len(bytes(response)) <= max_size
+        # the next response starts after the height of this one
+        assert response.header_hash == header_hash(response.height)
+        assert last_height(next_response.coin_states[0]) == response.height + 1
+    assert responses[-1].is_finished
+    assert len(bytes(responses[-1])) <= max_size
+
+    with pytest.raises(ValueError, match="no header hash for height"):
+        puzzle_state_responses([ph], states, 2000, header_hash(2000), {}, max_size)
+    with pytest.raises(ValueError, match="don't fit in a response"):
+        puzzle_state_responses([ph], states, 2000, header_hash(2000), {}, 100)
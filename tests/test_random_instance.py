@@ -0,0 +1,29 @@
+from gold_rs import FullBlock, SpendBundle, Coin
+from gold_rs.test_support import random_instance
+import io
+import pytest
+
+
+@pytest.mark.parametrize("seed", range(5))
+def test_random_instance(seed: int) -> None:
+    block = random_instance("FullBlock", seed)
+    assert type(block) is FullBlock
+    assert block == random_instance("FullBlock", seed)
+    assert FullBlock.from_bytes(bytes(block)) == block
+    f = io.BytesIO()
+    block.stream_to(f)
+    assert f.getvalue() == bytes(block)
+
+    sb = random_instance("SpendBundle", seed)
+    assert type(sb) is SpendBundle
+    assert SpendBundle.from_bytes(bytes(sb)) == sb
+
+
+def test_random_instance_seed() -> None:
+    assert random_instance("Coin", 1) != random_instance("Coin", 2)
+    assert type(random_instance("Coin", 1)) is Coin
+
+
+def test_random_instance_unknown() -> None:
+    with pytest.raises(ValueError, match="unknown type"):
+        random_instance("NotAType", 0)
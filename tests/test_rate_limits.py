@@ -0,0 +1,29 @@
+from gold_rs import Message, RateLimiter
+
+NOW = 1_700_000_040.0
+
+# the ProtocolMessageTypes values
+NEW_PEAK = 20
+REQUEST_PEERS = 43
+
+
+def test_rate_limiter() -> None:
+    limiter = RateLimiter(incoming=True)
+    msg = Message(REQUEST_PEERS, None, b"a" * 10)
+    for _ in range(10):
+        assert limiter.process_msg_and_check(msg, NOW)
+    assert not limiter.process_msg_and_check(msg, NOW)
+    # a new minute
+    assert limiter.process_msg_and_check(msg, NOW + 60)
+
+    # the message is too large
+    big = Message(NEW_PEAK, None, b"a" * 513)
+    assert not limiter.process_msg_and_check(big, NOW)
+
+
+def test_percentage_of_limit() -> None:
+    limiter = RateLimiter(False, percentage_of_limit=50)
+    msg = Message(REQUEST_PEERS, None, b"")
+    for _ in range(5):
+        assert limiter.process_msg_and_check(msg, NOW)
+    assert not limiter.process_msg_and_check(msg, NOW)
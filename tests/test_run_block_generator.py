@@ -1,6 +1,22 @@
-from gold_rs import run_block_generator, run_block_generator2
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
+from gold_rs import (
+    SKIP_AGG_SIG_CHECKS,
+    ConsensusConstants,
+    Program,
+    block_generator_refs,
+    profile_block_generator,
+    run_block_generator,
+    run_block_generator2,
+    run_block_generator_with_resolver,
+    run_block_generator2_with_resolver,
+)
+from gold_rs.test_support import BlockTools
+from typing import Dict, List, Optional
+import pytest
 from run_gen import print_spend_bundle_conditions
 
+CONSTANTS = ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS))
+
 
 def test_run_block_generator_cost() -> None:
 
@@ -51,3 +67,134 @@ def test_run_block_generator_cost() -> None:
     # BLOCK_COST_EXCEEDS_MAX = 23
     assert err == 23
     assert conds is None
+
+
+def test_run_block_generator_with_resolver() -> None:
+    generator = bytes.fromhex(
+        open("generator-tests/block-225758.txt", "r").read().split("\n")[0]
+    )
+    block_ref = bytes.fromhex(open("generator-tests/block-225758.env", "r").read())
+    max_cost = 11000000000
+
+    requested: List[int] = []
+
+    def resolver(height: int) -> Optional[bytes]:
+        requested.append(height)
+        return block_ref if height == 100 else None
+
+    for run, run_with_resolver in [
+        (run_block_generator, run_block_generator_with_resolver),
+        (run_block_generator2, run_block_generator2_with_resolver),
+    ]:
+        requested.clear()
+        err, conds = run(generator, [block_ref], max_cost, 0)
+        err2, conds2 = run_with_resolver(generator, [100], resolver, max_cost, 0)
+        assert requested == [100]
+        assert err is None
+        assert err2 is None
+        assert conds is not None
+        assert conds2 is not None
+        assert print_spend_bundle_conditions(conds) == print_spend_bundle_conditions(
+            conds2
+        )
+
+        # GENERATOR_REF_HAS_NO_GENERATOR = 121
+        requested.clear()
+        err, conds = run_with_resolver(generator, [100, 99, 98], resolver, max_cost, 0)
+        assert err == 121
+        assert conds is None
+        assert requested == [100, 99]
+
+        # the refs are not fetched for a generator that fails to parse
+        requested.clear()
+        err, conds = run_with_resolver(generator[:-1], [100], resolver, max_cost, 0)
+        assert err is not None
+        assert conds is None
+        assert requested == []
+
+    def failing_resolver(height: int) -> Optional[bytes]:
+        raise ValueError("database error")
+
+    with pytest.raises(ValueError, match="database error"):
+        run_block_generator2_with_resolver(
+            generator, [100], failing_resolver, max_cost, 0
+        )
+
+
+def test_block_generator_refs() -> None:
+    block = BlockTools(CONSTANTS).get_consecutive_blocks(3)[-1]
+    assert block_generator_refs(bytes(block)) == []
+
+    block = block.replace(
+        transactions_generator=Program.from_bytes(bytes.fromhex("ff01ff8080")),
+        transactions_generator_ref_list=[2, 0, 2],
+    )
+    buf = bytes(block)
+    assert block_generator_refs(buf) == [2, 0, 2]
+
+    with pytest.raises(ValueError):
+        block_generator_refs(buf[:-1])
+    with pytest.raises(ValueError):
+        block_generator_refs(buf + b"\x00")
+
+
+def test_skip_agg_sig_checks() -> None:
+    generator = bytes.fromhex(
+        open("generator-tests/block-834768.txt", "r").read().split("\n")[0]
+    )
+    max_cost = 11000000000
+    err, conds = run_block_generator2(generator, [], max_cost, 0)
+    err2, conds2 = run_block_generator2(generator, [], max_cost, SKIP_AGG_SIG_CHECKS)
+    assert err is None
+    assert err2 is None
+    assert conds is not None
+    assert conds2 is not None
+
+    # the coin diff and the cost are the same, only the signature data is missing
+    assert conds2.cost == conds.cost
+    assert conds2.removal_amount == conds.removal_amount
+    assert conds2.addition_amount == conds.addition_amount
+    assert [s.coin_id for s in conds2.spends] == [s.coin_id for s in conds.spends]
+    assert [s.create_coin for s in conds2.spends] == [s.create_coin for s in conds.spends]
+    assert any(len(s.agg_sig_me) > 0 for s in conds.spends)
+    assert all(len(s.agg_sig_me) == 0 for s in conds2.spends)
+    assert conds2.agg_sig_unsafe == []
+
+
+def test_run_block_generator_metrics() -> None:
+    generator = bytes.fromhex(
+        open("generator-tests/block-834768.txt", "r").read().split("\n")[0]
+    )
+    metrics: List[Dict[str, float]] = []
+    err, conds = run_block_generator(generator, [], 635805370, 0, metrics=metrics.append)
+    assert err is None
+    assert len(metrics) == 1
+    assert metrics[0]["clvm"] > 0
+    # the signatures aren't validated by run_block_generator()
+    assert metrics[0]["signature"] == 0
+
+    # the timings are reported for failures too
+    err, conds = run_block_generator(generator, [], 635805370 - 1, 0, metrics=metrics.append)
+    assert err == 23
+    assert len(metrics) == 2
+
+
+def test_profile_block_generator() -> None:
+    generator = bytes.fromhex(
+        open("generator-tests/block-834768.txt", "r").read().split("\n")[0]
+    )
+    err, conds = run_block_generator(generator, [], 635805370, 0)
+    err2, conds2, profile = profile_block_generator(generator, [], 635805370, 0)
+    assert err is None and err2 is None
+    assert conds is not None and conds2 is not None
+    assert conds2.cost == conds.cost
+
+    # the generator ROM and the standard puzzles hash their arguments
+    assert profile[b"\x0b"][0] > 0
+    assert sum(cost for _, cost in profile.values()) < conds.cost
+
+    # the profile is still returned when the cost limit is exceeded
+    err, conds, profile = profile_block_generator(generator, [], 635805370 - 1, 0)
+    assert err == 23
+    assert conds is None
+    assert len(profile) > 0
@@ -1,10 +1,25 @@
-from gold_rs import run_puzzle, run_chia_program, ALLOW_BACKREFS
+from gold_rs import (
+    analyze_spend,
+    run_puzzle,
+    run_puzzle_at_height,
+    run_chia_program,
+    ALLOW_BACKREFS,
+    ELIGIBLE_FOR_DEDUP,
+    ELIGIBLE_FOR_FF,
+    ConsensusConstants,
+    G1Element,
+    MEMPOOL_MODE,
+    Program,
+)
 from gold_rs.sized_bytes import bytes32
 import pytest
 from run_gen import print_spend_bundle_conditions
 from clvm.SExp import SExp
 from clvm.casts import int_from_bytes
 from clvm_tools import binutils
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
+
+CONSTANTS = ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS))
 
 
 @pytest.mark.parametrize("flags", [0, ALLOW_BACKREFS])
@@ -135,3 +150,103 @@ addition_amount: 0
     with pytest.raises(ValueError, match="ValidationError"):
         # the puzzle does not expect (2)
         run_puzzle(puzzle, solution2, parent, amount, 11000000000, flags)
+
+
+def test_run_puzzle_at_height() -> None:
+    # AGG_SIG_ME with the infinity public key is disallowed by soft-fork 5
+    infinity = b"\xc0" + bytes(47)
+    puzzle = bytes(Program.to((1, [[50, infinity, b"msg"]])))
+    solution = bytes(Program.to(0))
+    parent = bytes32([0] * 32)
+    fork_height = DEFAULT_CONSTANTS.SOFT_FORK5_HEIGHT
+
+    conds = run_puzzle_at_height(
+        puzzle, solution, parent, 1, fork_height - 1, CONSTANTS
+    )
+    assert len(conds.spends) == 1
+
+    with pytest.raises(ValueError, match="ValidationError"):
+        run_puzzle_at_height(puzzle, solution, parent, 1, fork_height, CONSTANTS)
+
+
+@pytest.mark.parametrize(
+    "amount,conditions,reasons",
+    [
+        (1, [[73, 1], [51, "self", 1]], []),
+        (2, [[73, 2], [51, "self", 1]], ["EVEN_AMOUNT"]),
+        (1, [[51, bytes32([1] * 32), 1]], ["NO_SINGLETON_OUTPUT"]),
+        (
+            1,
+            [[71, bytes32([2] * 32)], [51, "self", 3]],
+            ["ASSERT_MY_PARENT_ID"],
+        ),
+    ],
+)
+def test_ff_ineligible_reasons(amount: int, conditions: list, reasons: list) -> None:
+    # the puzzle returns its solution as the conditions
+    puzzle = Program.to(1)
+    ph = puzzle.get_tree_hash()
+    conditions = [[ph if arg == "self" else arg for arg in c] for c in conditions]
+    solution = bytes(Program.to(conditions))
+    parent = bytes32([2] * 32)
+
+    flags, ff_reasons = analyze_spend(
+        bytes(puzzle), solution, parent, amount, 11000000000
+    )
+    assert ff_reasons == reasons
+    assert ((flags & ELIGIBLE_FOR_FF) != 0) == (reasons == [])
+
+
+@pytest.mark.parametrize(
+    "amount,conditions,flags,reasons",
+    [
+        (1, [[51, "self", 1]], ELIGIBLE_FOR_DEDUP | ELIGIBLE_FOR_FF, []),
+        (2, [[51, "self", 2]], ELIGIBLE_FOR_DEDUP, ["EVEN_AMOUNT", "NO_SINGLETON_OUTPUT"]),
+        (
+            1,
+            [[50, G1Element.generator(), b"msg"], [51, "self", 1]],
+            0,
+            ["AGG_SIG_COMMITS_TO_PARENT"],
+        ),
+    ],
+)
+def test_analyze_spend(amount: int, conditions: list, flags: int, reasons: list) -> None:
+    # the puzzle returns its solution as the conditions
+    puzzle = Program.to(1)
+    ph = puzzle.get_tree_hash()
+    conditions = [[ph if arg == "self" else arg for arg in c] for c in conditions]
+    solution = bytes(Program.to(conditions))
+    parent = bytes32([2] * 32)
+
+    # this doesn't require MEMPOOL_MODE
+    assert analyze_spend(
+        bytes(puzzle), solution, parent, amount, 11000000000
+    ) == (flags, reasons)
+
+    # it's the same analysis as the mempool's
+    spend = run_puzzle(
+        bytes(puzzle), solution, parent, amount, 11000000000, MEMPOOL_MODE
+    ).spends[0]
+    assert spend.flags & (ELIGIBLE_FOR_DEDUP | ELIGIBLE_FOR_FF) == flags
+
+    with pytest.raises(ValueError, match="ValidationError"):
+        analyze_spend(bytes(puzzle), bytes(Program.to([[51]])), parent, amount, 11000000000)
+
+
+def test_max_heap_bytes() -> None:
+    # (i (concat 1 1 1 1 1 1 1 1) () ()) concatenates the solution 8 times,
+    # and returns no conditions
+    puzzle = bytes.fromhex("ff03ff0e" + "ff01" * 8 + "80ff80ff8080")
+    solution = bytes(Program.to(b"\x42" * 100))
+    parent = bytes32([2] * 32)
+
+    conds = run_puzzle(puzzle, solution, parent, 1, 11000000000, 0)
+    assert conds.spends[0].create_coin == []
+    with pytest.raises(ValueError) as e:
+        run_puzzle(puzzle, solution, parent, 1, 11000000000, 0, max_heap_bytes=500)
+    assert e.value.args == ("ValidationError", 150)
+
+    cost, ret = run_chia_program(puzzle, solution, 11000000000, 0)
+    assert ret.atom == b""
+    with pytest.raises(ValueError, match="out of memory"):
+        run_chia_program(puzzle, solution, 11000000000, 0, max_heap_bytes=500)
@@ -0,0 +1,32 @@
+from itertools import islice
+
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
+from chia.consensus.pot_iterations import calculate_sp_iters
+
+from gold_rs import ConsensusConstants, SignagePointIterator
+
+CONSTANTS = ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS))
+SSI = DEFAULT_CONSTANTS.SUB_SLOT_ITERS_STARTING
+NUM_SPS = DEFAULT_CONSTANTS.NUM_SPS_SUB_SLOT
+
+
+def test_signage_points() -> None:
+    sps = list(islice(SignagePointIterator(CONSTANTS), 2 * NUM_SPS + 1))
+    assert [sp.signage_point_index for sp in sps] == [*range(NUM_SPS), *range(NUM_SPS), 0]
+    assert [sp.is_end_of_sub_slot for sp in sps].count(True) == 3
+
+    for sp in sps:
+        assert sp.sub_slot_iters == SSI
+        assert sp.sp_iters == calculate_sp_iters(DEFAULT_CONSTANTS, SSI, sp.signage_point_index)
+        assert sp.total_iters == sp.sub_slot_total_iters + sp.sp_iters
+    assert sps[-1].total_iters == 2 * SSI
+
+
+def test_next_sub_slot() -> None:
+    it = SignagePointIterator(CONSTANTS)
+    assert next(it).total_iters == 0
+    next(it)
+    eos = it.next_sub_slot()
+    assert eos.is_end_of_sub_slot
+    assert eos.total_iters == SSI
+    assert next(it).signage_point_index == 1
@@ -0,0 +1,39 @@
+import pytest
+
+from chia.types.blockchain_format.program import Program
+from chia.types.signing_mode import SigningMode
+from gold_rs import CHIP_0002_SIGN_MESSAGE_PREFIX, AugSchemeMPL, sign_message, verify_message
+
+sk = AugSchemeMPL.key_gen(b"\x01" * 32)
+pk = sk.get_g1()
+
+
+def test_chip_0002() -> None:
+    message = b"I own this key"
+    sig = sign_message(sk, message)
+    # the same as the wallet's signatures
+    puzzle = Program.to((CHIP_0002_SIGN_MESSAGE_PREFIX, message))
+    assert sig == AugSchemeMPL.sign(sk, puzzle.get_tree_hash())
+    assert sign_message(sk, message, SigningMode.CHIP_0002.value) == sig
+
+    assert verify_message(pk, message, sig)
+    assert not verify_message(pk, b"I own that key", sig)
+    assert not verify_message(AugSchemeMPL.key_gen(b"\x02" * 32).get_g1(), message, sig)
+
+
+@pytest.mark.parametrize(
+    "mode", [SigningMode.BLS_MESSAGE_AUGMENTED_UTF8_INPUT, SigningMode.BLS_MESSAGE_AUGMENTED_HEX_INPUT]
+)
+def test_raw_message(mode: SigningMode) -> None:
+    message = bytes.fromhex("cafe")
+    sig = sign_message(sk, message, mode.value)
+    assert sig == AugSchemeMPL.sign(sk, message)
+    assert verify_message(pk, message, sig, mode.value)
+    assert not verify_message(pk, message, sig)
+
+
+def test_unsupported_mode() -> None:
+    with pytest.raises(ValueError, match="unsupported signing mode"):
+        sign_message(sk, b"foo", "foo")
+    with pytest.raises(ValueError, match="unsupported signing mode"):
+        verify_message(pk, b"foo", sign_message(sk, b"foo"), SigningMode.CHIP_0002_P2_DELEGATED_CONDITIONS.value)
@@ -0,0 +1,55 @@
+import copy
+
+import pytest
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
+from chia.types.blockchain_format.sized_bytes import bytes32
+from gold_rs import CoinSpend, ConsensusConstants, G2Element, Program, SpendBundle
+from gold_rs.test_support import Simulator
+
+CONSTANTS = ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS))
+
+
+def test_simulator() -> None:
+    sim = Simulator(CONSTANTS)
+    assert sim.height == 0
+    ph = Program.to(1).get_tree_hash()
+    coin = sim.new_coin(ph, 1000)
+    assert sim.coin_state(coin.name()).created_height == 0
+
+    target = bytes32(b"\x03" * 32)
+    solution = Program.to([[51, target, 900]])
+    bundle = SpendBundle([CoinSpend(coin, Program.to(1), solution)], G2Element())
+    assert sim.push_tx(bundle) == 100
+    assert sim.mempool_len() == 1
+
+    # double spend
+    with pytest.raises(ValueError):
+        sim.push_tx(bundle)
+
+    changed = sim.farm_block()
+    assert sim.height == 1
+    assert sim.mempool_len() == 0
+    assert len(changed) == 2
+    assert sim.coin_state(coin.name()).spent_height == 1
+    [child] = sim.children(coin.name())
+    assert child.coin.puzzle_hash == target
+    assert child.coin.amount == 900
+    assert sim.coin_states_by_puzzle_hash([ph], include_spent=False) == []
+    assert sim.coin_states_by_puzzle_hash([target]) == [child]
+
+
+def test_copy_simulator() -> None:
+    sim = Simulator(CONSTANTS)
+    ph = Program.to(1).get_tree_hash()
+    coin = sim.new_coin(ph, 1000)
+    solution = Program.to([[51, ph, 1000]])
+    bundle = SpendBundle([CoinSpend(coin, Program.to(1), solution)], G2Element())
+
+    # a copy branches off the simulation; changes don't affect the original
+    for fork in [copy.copy(sim), copy.deepcopy(sim)]:
+        fork.push_tx(bundle)
+        fork.farm_block()
+        assert fork.height == 1
+        assert fork.coin_state(coin.name()).spent_height == 1
+    assert sim.height == 0
+    assert sim.coin_state(coin.name()).spent_height is None
@@ -0,0 +1,167 @@
+import random
+
+import pytest
+
+from gold_rs import (
+    Bytes48,
+    Bytes96,
+    Bytes100,
+    ClassgroupElement,
+    PoolTarget,
+    decode_address,
+    encode_address,
+)
+from gold_rs.sized_bytes import bytes32, bytes48, bytes96
+
+
+@pytest.mark.parametrize("cls,size", [(Bytes48, 48), (Bytes96, 96), (Bytes100, 100)])
+def test_construction(cls, size: int) -> None:
+    assert cls.SIZE == size
+    b = cls(bytes(range(size)))
+    assert len(b) == size
+    assert bytes(b) == bytes(range(size))
+    assert b.to_bytes() == bytes(range(size))
+    assert cls.from_bytes(bytes(range(size))) == b
+    assert cls(b) == b
+
+    with pytest.raises(ValueError, match=f"must be {size} bytes, got {size - 1}"):
+        cls(bytes(size - 1))
+    with pytest.raises(ValueError, match=f"must be {size} bytes, got {size + 1}"):
+        cls.from_bytes(bytes(size + 1))
+    with pytest.raises(TypeError):
+        cls(1)
+
+
+@pytest.mark.parametrize("cls,size", [(Bytes48, 48), (Bytes96, 96), (Bytes100, 100)])
+def test_hex(cls, size: int) -> None:
+    b = cls(b"\xab" * size)
+    assert b.hex() == "ab" * size
+    assert str(b) == "ab" * size
+    assert repr(b) == f"<{cls.__name__}: {'ab' * size}>"
+    assert cls.fromhex("ab" * size) == b
+    assert cls.fromhex("0x" + "ab" * size) == b
+
+    with pytest.raises(ValueError, match="invalid hex"):
+        cls.fromhex("zz" * size)
+    with pytest.raises(ValueError, match=f"must be {size} bytes"):
+        cls.fromhex("ab")
+
+    # a str is parsed as hex
+    assert cls("ab" * size) == b
+    assert cls("0X" + "AB" * size) == b
+
+
+@pytest.mark.parametrize("cls", [Bytes48, Bytes96, Bytes100, bytes32])
+def test_hex_errors(cls) -> None:
+    with pytest.raises(ValueError, match="invalid hex character 'g' at position 5"):
+        cls("0xabcg")
+    with pytest.raises(ValueError, match="invalid hex character 'z' at position 0"):
+        cls("z" * 64)
+    with pytest.raises(ValueError, match=r"odd number of digits \(3\)"):
+        cls("0xabc")
+
+
+@pytest.mark.parametrize("cls,size", [(Bytes48, 48), (Bytes96, 96), (Bytes100, 100)])
+def test_zeros_and_random(cls, size: int) -> None:
+    assert cls.zeros() == bytes(size)
+    assert cls.random(1) == cls.random(1)
+    assert cls.random(1) != cls.random(2)
+    assert cls.random(1) != cls.zeros()
+
+
+def test_bytes32() -> None:
+    h = "01" * 32
+    assert bytes32(h) == bytes32(b"\x01" * 32)
+    assert bytes32("0x" + h) == bytes32(b"\x01" * 32)
+    assert bytes32.from_hexstr("0x" + h) == bytes32(b"\x01" * 32)
+    with pytest.raises(ValueError, match="expected 32 bytes, got 31"):
+        bytes32(h[2:])
+
+    assert bytes32.zeros() == bytes(32)
+    assert isinstance(bytes32.zeros(), bytes32)
+    # an int seed is deterministic
+    assert bytes32.random(1) == bytes32.random(1)
+    assert bytes32.random(1) != bytes32.random(2)
+    assert bytes32.random(1) == bytes32.fromhex(
+        "c15c0289ec2d0a9167ec8e65a18debbe5e5532fbeea293f80bc942ee9086c171"
+    )
+    assert bytes32.random(random.Random(1)) == bytes32.random(random.Random(1))
+    with pytest.raises(OverflowError):
+        bytes32.random(-1)
+
+
+# an int seed produces the same bytes as the Rust types do
+@pytest.mark.parametrize("seed", [0, 1, 2**64 - 1])
+def test_random_matches_rust(seed: int) -> None:
+    assert bytes48.random(seed) == Bytes48.random(seed)
+    assert bytes96.random(seed) == Bytes96.random(seed)
+    assert bytes(Bytes100.random(seed))[:32] == bytes32.random(seed)
+
+
+ADDRESS = "xch1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qarc0srg6dkm"
+
+
+def test_address() -> None:
+    ph = bytes32(range(32))
+    assert encode_address(ph, "xch") == ADDRESS
+    assert decode_address(ADDRESS, "xch") == ph
+    assert bytes32.from_address(ADDRESS, "xch") == ph
+    assert isinstance(bytes32.from_address(ADDRESS, "xch"), bytes32)
+    assert bytes32.from_address(ADDRESS.upper(), "xch") == ph
+
+    b = Bytes48.random(1)
+    assert Bytes48.from_address(encode_address(b, "txch"), "txch") == b
+    with pytest.raises(ValueError, match="expected 48 bytes, got 32"):
+        Bytes48.from_address(ADDRESS, "xch")
+
+
+@pytest.mark.parametrize(
+    "address,prefix,error",
+    [
+        (ADDRESS[:-1] + "n", "xch", "invalid address checksum"),
+        (ADDRESS[:-1] + "b", "xch", "invalid address character 'b' at position 61"),
+        (ADDRESS[:-1] + "M", "xch", "mixed upper and lower case"),
+        (ADDRESS, "txch", 'expected address prefix "txch", got "xch"'),
+        (ADDRESS[4:], "xch", "missing the '1' separator"),
+    ],
+)
+def test_address_errors(address: str, prefix: str, error: str) -> None:
+    with pytest.raises(ValueError, match=error):
+        bytes32.from_address(address, prefix)
+    with pytest.raises(ValueError, match=error):
+        Bytes48.from_address(address, prefix)
+
+
+def test_concatenation() -> None:
+    a = Bytes48(b"\x01" * 48)
+    b = Bytes96(b"\x02" * 96)
+    assert a + b == b"\x01" * 48 + b"\x02" * 96
+    assert a + b"\x03" == b"\x01" * 48 + b"\x03"
+    assert b"\x03" + a == b"\x03" + b"\x01" * 48
+    assert isinstance(a + a, bytes)
+
+
+def test_comparison() -> None:
+    low = Bytes48(b"\x00" * 48)
+    high = Bytes48(b"\xff" * 48)
+    assert low < high
+    assert low <= high
+    assert high > low
+    assert high >= low
+    assert low != high
+    assert low == b"\x00" * 48
+    assert low != b"\x00" * 47
+    assert low != "foobar"
+    assert sorted([high, low]) == [low, high]
+
+    # equal objects must hash the same, including plain bytes
+    assert hash(low) == hash(b"\x00" * 48)
+    assert len({low, Bytes48(b"\x00" * 48), b"\x00" * 48}) == 1
+
+
+def test_accepted_as_fields() -> None:
+    data = Bytes100(b"\x08" + b"\x00" * 99)
+    assert ClassgroupElement(data) == ClassgroupElement(bytes(data))
+
+    with pytest.raises(ValueError):
+        PoolTarget(Bytes48(b"\x00" * 48), 0)
@@ -0,0 +1,60 @@
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
+from chia.types.blockchain_format.sized_bytes import bytes32
+from gold_rs import (
+    Coin,
+    CoinSpend,
+    ConsensusConstants,
+    Program,
+    estimate_cost,
+    estimate_generator_size,
+    solution_generator,
+    solution_generator_backrefs,
+    solution_generator_from_coin_spends,
+)
+
+CONSTANTS = ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS))
+
+
+def test_solution_generator_from_coin_spends() -> None:
+    spends = [
+        CoinSpend(
+            Coin(bytes32(i.to_bytes(32, "big")), bytes32(b"\x2a" * 32), i),
+            Program.to(1),
+            Program.to([[51, bytes32(b"\x2b" * 32), i]]),
+        )
+        for i in range(1000)
+    ]
+    tuples = [
+        (cs.coin, bytes(cs.puzzle_reveal), bytes(cs.solution)) for cs in spends
+    ]
+    generator = solution_generator_from_coin_spends(spends)
+    assert generator == solution_generator(tuples)
+
+    assert solution_generator_from_coin_spends([]) == solution_generator([])
+
+
+def test_estimate_generator_size() -> None:
+    puzzle = bytes(Program.to((1, [[51, bytes32(b"\x2b" * 32), 1000]])))
+    spends = [
+        (Coin(bytes32(i.to_bytes(32, "big")), bytes32(b"\x2a" * 32), i), puzzle, b"\x80")
+        for i in range(100)
+    ]
+    size, size_backrefs = estimate_generator_size(spends)
+    assert size == len(solution_generator(spends))
+    assert size_backrefs < size
+    assert len(solution_generator_backrefs(spends)) < size
+
+
+def test_estimate_cost() -> None:
+    # each spend creates one coin
+    puzzle = bytes(Program.to((1, [[51, bytes32(b"\x2b" * 32), 1000]])))
+    spends = [
+        (Coin(bytes32(i.to_bytes(32, "big")), bytes32(b"\x2a" * 32), i), puzzle, b"\x80")
+        for i in range(10)
+    ]
+    size, _ = estimate_generator_size(spends)
+    cost = estimate_cost(spends, CONSTANTS)
+    byte_cost = size * DEFAULT_CONSTANTS.COST_PER_BYTE
+    create_coin_cost = 10 * 1800000
+    assert cost > byte_cost + create_coin_cost
+    assert cost < DEFAULT_CONSTANTS.MAX_BLOCK_COST_CLVM
@@ -1,7 +1,22 @@
-from gold_rs import SpendBundle
+from gold_rs import (
+    AugSchemeMPL,
+    Coin,
+    CoinSpend,
+    ConsensusConstants,
+    G2Element,
+    PrivateKey,
+    Program,
+    SpendBundle,
+    sign_coin_spends,
+    validate_clvm_and_signature,
+    verify_spend_bundle_signatures,
+)
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
 from chia.types.spend_bundle import SpendBundle as PySpendBundle
 import pytest
-from typing import Type
+from typing import Dict, List, Type
+
+CONSTANTS = ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS))
 
 expected_add1 = """\
 [Coin { parent_coin_info: a48e6325fee4948d0703d1d722416331890e068d095a05049dd516ee7eab7f4b, \
@@ -211,3 +226,121 @@ def test_spend_bundle(
 
     rem = f"{removals}"
     assert rem == expected_rem
+
+
+@pytest.mark.parametrize("input_file", ["3000253", "1000101"])
+def test_cached_values(input_file: str) -> None:
+    buf = open(f"test-bundles/{input_file}.bundle", "rb").read()
+    bundle = SpendBundle.from_bytes(buf)
+    py_bundle = PySpendBundle.from_bytes(buf)
+
+    # the cached values are the same as the ones computed the first time
+    for _ in range(2):
+        assert bundle.cached_bytes() == buf
+        assert bundle.name() == py_bundle.name()
+        assert bundle.get_hash() == py_bundle.get_hash()
+        assert hash(bundle) == hash(SpendBundle.from_bytes(buf))
+
+    # and they're returned as the same objects
+    assert bundle.cached_bytes() is bundle.cached_bytes()
+    assert bundle.name() is bundle.name()
+
+    # replacing a field creates a new object, with its own cache
+    other = bundle.replace(coin_spends=bundle.coin_spends[:1])
+    assert other.name() != bundle.name()
+    assert other.cached_bytes() == bytes(other)
+    assert hash(other) != hash(bundle)
+
+    # the cache is kept on the rust side, where python can't modify it, and
+    # the object has no instance __dict__ to add attributes to
+    assert not hasattr(bundle, "__dict__")
+    with pytest.raises(AttributeError):
+        bundle.coin_spends = []
+    with pytest.raises(AttributeError):
+        bundle._name = bytes(32)
+
+
+def agg_sig_me_spend(sk: PrivateKey, msg: bytes, amount: int) -> CoinSpend:
+    puzzle = Program.to((1, [[50, sk.get_g1(), msg]]))
+    coin = Coin(bytes([amount] * 32), puzzle.get_tree_hash(), amount)
+    return CoinSpend(coin, puzzle, Program.to(0))
+
+
+def test_sign_coin_spends() -> None:
+    sks = [AugSchemeMPL.key_gen(bytes([i] * 32)) for i in range(3)]
+    keys = {bytes(sk.get_g1()): sk for sk in sks}
+    spends = [agg_sig_me_spend(sk, b"msg", i + 1) for i, sk in enumerate(sks)]
+
+    bundle = sign_coin_spends(spends, lambda pk, msg: keys.get(bytes(pk)), CONSTANTS)
+    assert bundle.coin_spends == spends
+    assert verify_spend_bundle_signatures([bundle], CONSTANTS) == [True]
+
+    # the callback may also return the signature itself
+    bundle2 = sign_coin_spends(
+        spends,
+        lambda pk, msg: AugSchemeMPL.sign(keys[bytes(pk)], msg),
+        CONSTANTS,
+    )
+    assert bundle2 == bundle
+
+    # signing bundles separately and aggregating them is equivalent
+    parts = [
+        sign_coin_spends([s], lambda pk, msg: keys.get(bytes(pk)), CONSTANTS)
+        for s in spends
+    ]
+    assert SpendBundle.aggregate(parts) == bundle
+
+    with pytest.raises(ValueError, match="no secret key"):
+        sign_coin_spends(spends, lambda pk, msg: None, CONSTANTS)
+
+    # exceptions raised by the callback are passed through
+    def fail(pk: object, msg: bytes) -> None:
+        raise KeyError("missing")
+
+    with pytest.raises(KeyError):
+        sign_coin_spends(spends, fail, CONSTANTS)
+
+    assert sign_coin_spends([], fail, CONSTANTS) == SpendBundle([], G2Element())
+
+
+def test_validate_clvm_and_signature() -> None:
+    sks = [AugSchemeMPL.key_gen(bytes([i] * 32)) for i in range(3)]
+    keys = {bytes(sk.get_g1()): sk for sk in sks}
+    spends = [agg_sig_me_spend(sk, b"msg", i + 1) for i, sk in enumerate(sks)]
+    bundle = sign_coin_spends(spends, lambda pk, msg: keys.get(bytes(pk)), CONSTANTS)
+
+    metrics: List[Dict[str, float]] = []
+    conds = validate_clvm_and_signature(bundle, 11000000000, CONSTANTS, metrics=metrics.append)
+    assert [c.spends[0].coin_id for c in conds] == [s.coin.name() for s in spends]
+    assert len(metrics) == 1
+    assert set(metrics[0]) == {"deserialize", "clvm", "parse_conditions", "signature"}
+    assert metrics[0]["signature"] > 0
+
+    with pytest.raises(ValueError) as e:
+        validate_clvm_and_signature(bundle.replace(aggregated_signature=G2Element()), 11000000000, CONSTANTS)
+    # BAD_AGGREGATE_SIGNATURE
+    assert e.value.args == ("ValidationError", 7)
+
+    # every spend costs the same
+    cost = conds[0].cost
+    conds = validate_clvm_and_signature(bundle, 11000000000, CONSTANTS, max_cost_per_spend=cost)
+    assert len(conds) == 3
+    with pytest.raises(ValueError) as e:
+        validate_clvm_and_signature(bundle, 11000000000, CONSTANTS, max_cost_per_spend=cost - 1)
+    # COST_EXCEEDED
+    assert e.value.args == ("ValidationError", 23)
+
+
+def test_removals_fingerprint() -> None:
+    sks = [AugSchemeMPL.key_gen(bytes([i] * 32)) for i in range(3)]
+    spends = [agg_sig_me_spend(sk, b"msg", i + 1) for i, sk in enumerate(sks)]
+
+    bundle = SpendBundle(spends[0:2], G2Element())
+    # the same removals with a different order and signature
+    equivalent = SpendBundle([spends[1], spends[0]], AugSchemeMPL.sign(sks[0], b"foo"))
+    assert bundle.name() != equivalent.name()
+    assert bundle.removals_fingerprint() == equivalent.removals_fingerprint()
+    assert 0 <= bundle.removals_fingerprint() < 2**64
+
+    assert bundle.removals_fingerprint() != SpendBundle(spends[1:3], G2Element()).removals_fingerprint()
+    assert bundle.removals_fingerprint() != SpendBundle(spends[0:1], G2Element()).removals_fingerprint()
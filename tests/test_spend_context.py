@@ -0,0 +1,67 @@
+from typing import List
+
+import pytest
+
+from chia.types.blockchain_format.sized_bytes import bytes32
+
+from gold_rs import (
+    Coin,
+    CoinSpend,
+    G2Element,
+    Program,
+    SpendBundle,
+    SpendContext,
+    ValidationPolicy,
+    run_spend_bundle,
+)
+
+MAX_COST = 11000000000
+
+CREATE_COIN = 51
+
+
+def quoted_spend(conditions: List[List[object]], parent: int) -> CoinSpend:
+    puzzle = Program.to((1, conditions))
+    coin = Coin(bytes32([parent] * 32), puzzle.get_tree_hash(), 1000)
+    return CoinSpend(coin, puzzle, Program.to(0))
+
+
+BUNDLE = SpendBundle(
+    [
+        quoted_spend([[CREATE_COIN, bytes32([1] * 32), 1], [CREATE_COIN, bytes32([2] * 32), 1]], 1),
+        quoted_spend([[CREATE_COIN, bytes32([1] * 32), 1]], 2),
+    ],
+    G2Element(),
+)
+
+
+def test_spend_context() -> None:
+    expected = run_spend_bundle(BUNDLE, MAX_COST, 0)
+    ctx = SpendContext(MAX_COST, 0)
+    for _ in range(2):
+        conds = [ctx.run_spend(s) for s in BUNDLE.coin_spends]
+        assert conds == expected
+        assert ctx.cost_left == MAX_COST - sum(c.cost for c in expected)
+        ctx.reset()
+        assert ctx.cost_left == MAX_COST
+        # the conditions are copies, they survive the reset
+        assert conds == expected
+
+
+def test_shared_limits() -> None:
+    expected = run_spend_bundle(BUNDLE, MAX_COST, 0)
+
+    ctx = SpendContext(expected[0].cost, 0)
+    ctx.run_spend(BUNDLE.coin_spends[0])
+    with pytest.raises(ValueError):
+        ctx.run_spend(BUNDLE.coin_spends[1])
+
+    # the limit on the number of conditions applies across spends too
+    ctx = SpendContext(MAX_COST, 0, ValidationPolicy(max_conditions=2))
+    ctx.run_spend(BUNDLE.coin_spends[0])
+    with pytest.raises(ValueError) as e:
+        ctx.run_spend(BUNDLE.coin_spends[1])
+    assert e.value.args == ("ValidationError", 149)
+
+    ctx.reset()
+    ctx.run_spend(BUNDLE.coin_spends[1])
@@ -0,0 +1,105 @@
+import hashlib
+import struct
+from typing import Any
+
+from gold_rs import (
+    MEMPOOL_MODE,
+    Coin,
+    CoinSpend,
+    G2Element,
+    Program,
+    SpendBundle,
+    SpendBundleConditions,
+    check_announcements,
+    coin_announcement_id,
+    puzzle_announcement_id,
+    run_spend_bundle,
+    spend_bundle_to_dot,
+)
+from gold_rs.sized_bytes import bytes32
+
+MAX_COST = 11000000000
+# the puzzle "1" returns its solution as its conditions
+PUZZLE = Program.fromhex("01")
+PUZZLE_HASH = PUZZLE.get_tree_hash()
+
+
+def spend(parent: bytes32, conditions: Any) -> CoinSpend:
+    return CoinSpend(Coin(parent, PUZZLE_HASH, 123), PUZZLE, Program.to(conditions))
+
+
+def make_bundle() -> SpendBundle:
+    parent = spend(bytes32(b"\x01" * 32), [[51, PUZZLE_HASH, 123], [51, b"\x02" * 32, 7], [60, b"hi"]])
+    parent_id = parent.coin.name()
+    announcement = hashlib.sha256(parent_id + b"hi").digest()
+    # spends the first coin created by parent
+    child = spend(parent_id, [[61, announcement], [61, b"\x09" * 32]])
+    return SpendBundle([parent, child], G2Element())
+
+
+def bundle_conditions(bundle: SpendBundle) -> SpendBundleConditions:
+    spends = [s for conds in run_spend_bundle(bundle, MAX_COST, MEMPOOL_MODE) for s in conds.spends]
+    return SpendBundleConditions(spends, 0, 0, 0, None, None, [], 0, 0, 0)
+
+
+def test_json_dict() -> None:
+    conds = bundle_conditions(make_bundle())
+    json = conds.to_json_dict()
+    assert len(json["spends"]) == 2
+    assert json["spends"][0]["coin_amount"] == 123
+    assert SpendBundleConditions.from_json_dict(json) == conds
+    assert conds.spends[1].to_json_dict()["coin_id"] == "0x" + conds.spends[1].coin_id.hex()
+
+
+def test_to_arrays() -> None:
+    conds = bundle_conditions(make_bundle())
+    arrays = conds.to_arrays()
+    count = len(conds.spends)
+    assert arrays["coin_id"] == b"".join(s.coin_id for s in conds.spends)
+    assert arrays["parent_id"] == b"".join(s.parent_id for s in conds.spends)
+    assert arrays["puzzle_hash"] == b"".join(s.puzzle_hash for s in conds.spends)
+    assert struct.unpack(f"<{count}Q", arrays["coin_amount"]) == tuple(s.coin_amount for s in conds.spends)
+    assert struct.unpack(f"<{count}I", arrays["flags"]) == tuple(s.flags for s in conds.spends)
+
+    empty = SpendBundleConditions([], 0, 0, 0, None, None, [], 0, 0, 0).to_arrays()
+    assert empty == {k: b"" for k in ["coin_id", "parent_id", "puzzle_hash", "coin_amount", "flags"]}
+
+
+def test_to_dot() -> None:
+    bundle = make_bundle()
+    conds = bundle_conditions(bundle)
+    parent_id = bundle.coin_spends[0].coin.name()
+    child_id = bundle.coin_spends[1].coin.name()
+    created = Coin(parent_id, bytes32(b"\x02" * 32), 7).name()
+
+    dot = conds.to_dot()
+    assert dot.startswith("digraph spend_bundle {")
+    assert f'"{parent_id.hex()}" -> "{child_id.hex()}";' in dot
+    assert f'"{parent_id.hex()}" -> "{created.hex()}";' in dot
+    assert "dotted" not in dot
+
+    dot = spend_bundle_to_dot(bundle, conds, MAX_COST, MEMPOOL_MODE)
+    assert f'"{parent_id.hex()}" -> "{child_id.hex()}" [style=dotted' in dot
+    assert f'"missing {"09" * 32}"' in dot
+
+
+def test_announcement_ids() -> None:
+    coin_id = bytes32(b"\x01" * 32)
+    assert coin_announcement_id(coin_id, b"hi") == hashlib.sha256(coin_id + b"hi").digest()
+    assert puzzle_announcement_id(PUZZLE_HASH, b"hi") == hashlib.sha256(PUZZLE_HASH + b"hi").digest()
+
+
+def test_check_announcements() -> None:
+    bundle = make_bundle()
+    spends = [(cs.coin, Program.from_bytes(bytes(cs.solution))) for cs in bundle.coin_spends]
+    parent_id = bundle.coin_spends[0].coin.name()
+    child_id = bundle.coin_spends[1].coin.name()
+
+    satisfied, unsatisfied = check_announcements(spends)
+    assert satisfied == [(coin_announcement_id(parent_id, b"hi"), False, child_id)]
+    assert unsatisfied == [(bytes32(b"\x09" * 32), False, child_id)]
+
+    # without the parent's spend, nothing is announced
+    satisfied, unsatisfied = check_announcements(spends[1:])
+    assert satisfied == []
+    assert len(unsatisfied) == 2
@@ -0,0 +1,37 @@
+from gold_rs import (
+    AugSchemeMPL,
+    Program,
+    puzzle_hash_for_pk,
+    puzzle_for_pk,
+    solution_for_conditions,
+    calculate_synthetic_secret_key,
+    calculate_synthetic_public_key,
+)
+from chia.types.blockchain_format.program import Program as ChiaProgram
+from chia.wallet.puzzles import p2_delegated_puzzle_or_hidden_puzzle as p2
+import pytest
+
+
+@pytest.mark.parametrize("idx", range(5))
+def test_standard_puzzle(idx: int) -> None:
+    sk = AugSchemeMPL.key_gen(bytes([idx]) * 32)
+    pk = sk.get_g1()
+
+    assert puzzle_hash_for_pk(pk) == p2.puzzle_hash_for_pk(pk)
+    assert bytes(puzzle_for_pk(pk)) == bytes(p2.puzzle_for_pk(pk))
+    assert puzzle_for_pk(pk).get_tree_hash() == puzzle_hash_for_pk(pk)
+
+    hidden = p2.DEFAULT_HIDDEN_PUZZLE_HASH
+    assert calculate_synthetic_secret_key(
+        sk, hidden
+    ) == p2.calculate_synthetic_secret_key(sk, hidden)
+    assert calculate_synthetic_public_key(
+        pk, hidden
+    ) == p2.calculate_synthetic_public_key(pk, hidden)
+
+
+def test_solution_for_conditions() -> None:
+    conditions = ChiaProgram.to([[51, b"1" * 32, 1000]])
+    expected = p2.solution_for_conditions(conditions)
+    actual = solution_for_conditions(Program.from_bytes(bytes(conditions)))
+    assert bytes(actual) == bytes(expected)
@@ -6,11 +6,22 @@ from gold_rs import (
     G2Element,
     Program,
     AugSchemeMPL,
+    RespondPeers,
+    TimestampedPeerInfo,
+    SubEpochChallengeSegment,
+    SubEpochSegments,
+    CoinState,
+    PoolState,
+    set_repr_limits,
 )
 from gold_rs.sized_ints import uint64
 from gold_rs.sized_bytes import bytes32
 import pytest
 import copy
+import io
+import json
+import sys
+import zstd
 
 sk = AugSchemeMPL.key_gen(bytes32.random())
 pk = sk.get_g1()
@@ -633,3 +644,138 @@ def test_program() -> None:
     # garbage at the end of the serialization
     with pytest.raises(ValueError, match="invalid CLVM serialization"):
         Program.from_json_dict("0xff808080")
+
+
+def test_from_bytes_with_limits() -> None:
+    peers = RespondPeers(
+        [TimestampedPeerInfo("1.2.3.4", 8444, 0), TimestampedPeerInfo("::1", 8444, 0)]
+    )
+    buf = bytes(peers)
+    assert RespondPeers.from_bytes_with_limits(buf, 2, 7) == peers
+
+    # too many peers
+    with pytest.raises(ValueError, match="sequence too large"):
+        RespondPeers.from_bytes_with_limits(buf, 1, 7)
+
+    # host too long
+    with pytest.raises(ValueError, match="sequence too large"):
+        RespondPeers.from_bytes_with_limits(buf, 2, 6)
+
+
+def test_to_bytes_compressed() -> None:
+    segments = SubEpochSegments([SubEpochChallengeSegment(7, [], None)] * 100)
+    buf = segments.to_bytes_compressed()
+    assert len(buf) < len(bytes(segments))
+    assert SubEpochSegments.from_bytes_compressed(buf) == segments
+    assert SubEpochSegments.from_bytes_compressed(segments.to_bytes_compressed(19)) == segments
+
+    # the same format the full node stores in its database
+    assert zstd.decompress(buf) == bytes(segments)
+    assert SubEpochSegments.from_bytes_compressed(zstd.compress(bytes(segments))) == segments
+
+    with pytest.raises(ValueError, match="zstd decompression failed"):
+        SubEpochSegments.from_bytes_compressed(bytes(segments))
+
+
+def test_stream_to() -> None:
+    segments = SubEpochSegments([SubEpochChallengeSegment(7, [], None)] * 100)
+    f = io.BytesIO()
+    segments.stream_to(f)
+    assert f.getvalue() == bytes(segments)
+
+    # it appends to whatever is already in the file
+    segments.stream_to(f)
+    assert f.getvalue() == bytes(segments) * 2
+
+    class BrokenFile:
+        def write(self, buf: bytes) -> int:
+            raise OSError("disk full")
+
+    # the exception raised by the file object propagates
+    with pytest.raises(OSError, match="disk full"):
+        segments.stream_to(BrokenFile())
+
+    with pytest.raises(AttributeError):
+        segments.stream_to(None)
+
+
+def test_bytes32_interning() -> None:
+    c1 = Coin(parent, ph, 1)
+    c2 = Coin(coin, ph, 2)
+    assert c1.puzzle_hash is c2.puzzle_hash
+    assert type(c1.puzzle_hash) is bytes32
+    assert c1.puzzle_hash == ph
+    assert c1.parent_coin_info == parent
+
+    # bytes32 instances can't be modified
+    with pytest.raises(AttributeError):
+        c1.puzzle_hash.foo = 1  # type: ignore[attr-defined]
+
+
+def test_sizeof() -> None:
+    c = Coin(parent, ph, 1)
+    state = CoinState(c, None, 10)
+    assert sys.getsizeof(state) > sys.getsizeof(c) >= 32 + 32 + 8
+
+    small = SubEpochSegments([SubEpochChallengeSegment(7, [], None)])
+    large = SubEpochSegments([SubEpochChallengeSegment(7, [], None)] * 1000)
+    assert sys.getsizeof(large) > sys.getsizeof(small) + 999 * 32
+
+
+def test_compact_repr() -> None:
+    c = Coin(parent, ph, 1)
+    full = repr(c)
+    segments = SubEpochSegments([SubEpochChallengeSegment(7, [], None)] * 100)
+
+    set_repr_limits(max_items=2, max_hex_len=8)
+    try:
+        assert repr(c) == (
+            "Coin { parent_coin_info: 0x65646564.. (32 bytes), "
+            "puzzle_hash: 0x61626162.. (32 bytes), amount: 1 }"
+        )
+        assert repr(segments).endswith(", .. 98 more] }")
+
+        set_repr_limits(max_depth=1)
+        assert repr(segments) == "SubEpochSegments { challenge_segments: [.. 100 items] }"
+
+        # strings starting with 0x aren't bytes, and are cut between characters
+        set_repr_limits(max_hex_len=8)
+        url = "0xa" + "\u00e9" * 8
+        state = PoolState(1, 1, ph, G1Element(), url, 100)
+        assert f"pool_url: {url[:10]}.., " in repr(state)
+        state = PoolState(1, 1, ph, G1Element(), "0xabcdefghij", 100)
+        assert "pool_url: 0xabcdefgh.., " in repr(state)
+    finally:
+        set_repr_limits()
+    assert repr(c) == full
+
+
+def test_to_pretty_json() -> None:
+    segments = SubEpochSegments([SubEpochChallengeSegment(7, [], None)] * 100)
+    assert json.loads(segments.to_pretty_json()) == segments.to_json_dict()
+    assert segments.to_pretty_json(indent=4).startswith('{\n    "challenge_segments"')
+
+    truncated = json.loads(segments.to_pretty_json(max_items=3))
+    assert len(truncated["challenge_segments"]) == 4
+    assert truncated["challenge_segments"][3] == ".. 97 more"
+
+
+def test_int_as_string() -> None:
+    coin = Coin(bytes32(b"a" * 32), bytes32(b"b" * 32), 2**64 - 1)
+    assert coin.to_json_dict()["amount"] == 2**64 - 1
+    json_dict = coin.to_json_dict(int_as_string=True)
+    assert json_dict["amount"] == "18446744073709551615"
+    assert Coin.from_json_dict(json_dict) == coin
+
+    # only the 64- and 128 bit integers are converted
+    peers = RespondPeers([TimestampedPeerInfo("127.0.0.1", 8444, 1337)])
+    peer = peers.to_json_dict(int_as_string=True)["peer_list"][0]
+    assert peer["port"] == 8444
+    assert peer["timestamp"] == "1337"
+    assert RespondPeers.from_json_dict(peers.to_json_dict(int_as_string=True)) == peers
+
+    # the setting doesn't stick
+    assert coin.to_json_dict()["amount"] == 2**64 - 1
+
+    with pytest.raises(ValueError, match="invalid u64"):
+        Coin.from_json_dict({**json_dict, "amount": "foobar"})
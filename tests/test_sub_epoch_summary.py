@@ -0,0 +1,97 @@
+from typing import Dict, Optional
+
+import pytest
+
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
+from chia.types.blockchain_format.sized_bytes import bytes32
+from gold_rs import (
+    BlockRecord,
+    ClassgroupElement,
+    ConsensusConstants,
+    G1Element,
+    SubEpochSummary,
+    make_sub_epoch_summary,
+)
+
+CONSTANTS = ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS))
+SUB_EPOCH_BLOCKS = DEFAULT_CONSTANTS.SUB_EPOCH_BLOCKS
+MAX_SUB_SLOT_BLOCKS = DEFAULT_CONSTANTS.MAX_SUB_SLOT_BLOCKS
+PREV_SES = SubEpochSummary(bytes32([7] * 32), bytes32([8] * 32), 0, None, None)
+
+
+def h(v: int) -> bytes32:
+    return bytes32(v.to_bytes(4, "big") + bytes(28))
+
+
+def record(height: int, ses: Optional[SubEpochSummary]) -> BlockRecord:
+    slot_hashes = None if ses is None else [h(height + 1000)]
+    return BlockRecord(
+        h(height),
+        h(height - 1) if height > 0 else DEFAULT_CONSTANTS.GENESIS_CHALLENGE,
+        height,
+        height,
+        height * 10,
+        0,
+        ClassgroupElement.get_default_element(),
+        None,
+        h(0),
+        h(0),
+        0,
+        h(0),
+        h(0),
+        0,
+        0,
+        False,
+        0,
+        None,
+        None,
+        None,
+        None,
+        slot_hashes,
+        slot_hashes,
+        slot_hashes,
+        ses,
+        G1Element(),
+    )
+
+
+# the block right after the second sub-epoch includes a summary
+BLOCKS: Dict[bytes32, BlockRecord] = {
+    r.header_hash: r
+    for r in [
+        record(height, PREV_SES if height == 2 * SUB_EPOCH_BLOCKS + 2 else None)
+        for height in range(2 * SUB_EPOCH_BLOCKS + 20)
+    ]
+}
+
+
+# summaries included below 2 * SUB_EPOCH_BLOCKS - MAX_SUB_SLOT_BLOCKS are
+# treated as the first one, to allow for overflow blocks
+@pytest.mark.parametrize(
+    "height", [SUB_EPOCH_BLOCKS, SUB_EPOCH_BLOCKS + 1, 2 * SUB_EPOCH_BLOCKS - MAX_SUB_SLOT_BLOCKS - 1]
+)
+def test_first_sub_epoch(height: int) -> None:
+    ses = make_sub_epoch_summary(CONSTANTS, BLOCKS, height, BLOCKS[h(height - 2)], 5, None)
+    assert ses.prev_subepoch_summary_hash == DEFAULT_CONSTANTS.GENESIS_CHALLENGE
+    assert ses.reward_chain_hash == DEFAULT_CONSTANTS.GENESIS_CHALLENGE
+    assert ses.num_blocks_overflow == 0
+    assert ses.new_difficulty is None
+
+
+def test_next_sub_epoch() -> None:
+    height = 2 * SUB_EPOCH_BLOCKS + 10
+    ses = make_sub_epoch_summary(CONSTANTS, BLOCKS, height, BLOCKS[h(height - 2)], 5, 6)
+    expected = SubEpochSummary(PREV_SES.get_hash(), h(2 * SUB_EPOCH_BLOCKS + 1002), 2, 5, 6)
+    assert ses == expected
+
+    # the blocks may also be looked up by a function
+    def lookup(header_hash: bytes32) -> Optional[BlockRecord]:
+        return BLOCKS.get(header_hash)
+
+    assert make_sub_epoch_summary(CONSTANTS, lookup, height, BLOCKS[h(height - 2)], 5, 6) == expected
+
+    with pytest.raises(ValueError, match="block not found"):
+        make_sub_epoch_summary(CONSTANTS, lambda _: None, height, BLOCKS[h(height - 2)], 5, 6)
+
+    with pytest.raises(ValueError, match="grandparent"):
+        make_sub_epoch_summary(CONSTANTS, BLOCKS, height, BLOCKS[h(height - 1)], 5, 6)
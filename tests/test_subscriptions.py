@@ -0,0 +1,65 @@
+from chia.types.blockchain_format.sized_bytes import bytes32
+from gold_rs import (
+    Coin,
+    CoinSpend,
+    CoinState,
+    CoinStateUpdate,
+    Program,
+    SubscriptionManager,
+    index_spends,
+    run_spend,
+)
+
+
+def b(v: int) -> bytes32:
+    return bytes32(bytes([v] * 32))
+
+
+def test_subscription_limit() -> None:
+    mgr = SubscriptionManager(3)
+    assert mgr.add_puzzle_subscriptions(b(1), [b(10), b(11), b(10)]) == [b(10), b(11)]
+    assert mgr.add_coin_subscriptions(b(1), [b(20), b(21)]) == [b(20)]
+    assert mgr.subscription_count(b(1)) == 3
+    assert mgr.add_puzzle_subscriptions(b(1), [b(12)]) == []
+
+    assert mgr.remove_coin_subscriptions(b(1), [b(20), b(21)]) == [b(20)]
+    assert mgr.add_puzzle_subscriptions(b(1), [b(12)]) == [b(12)]
+    assert mgr.puzzle_subscriptions(b(1)) == [b(10), b(11), b(12)]
+    assert mgr.coin_subscriptions(b(1)) == []
+
+    mgr.remove_peer(b(1))
+    assert mgr.subscription_count(b(1)) == 0
+
+
+def test_updates_for_peak() -> None:
+    mgr = SubscriptionManager()
+    by_ph = CoinState(Coin(b(1), b(10), 1), None, 10)
+    hinted = CoinState(Coin(b(2), b(11), 1), 10, 5)
+    unrelated = CoinState(Coin(b(3), b(12), 1), None, 10)
+
+    mgr.add_puzzle_subscriptions(b(100), [b(10), b(20)])
+    mgr.add_coin_subscriptions(b(101), [hinted.coin.name()])
+
+    updates = mgr.updates_for_peak(10, 9, b(50), [by_ph, hinted, unrelated], {hinted.coin.name(): b(20)})
+    assert updates == [
+        (b(100), CoinStateUpdate(10, 9, b(50), [by_ph, hinted])),
+        (b(101), CoinStateUpdate(10, 9, b(50), [hinted])),
+    ]
+    assert mgr.updates_for_peak(10, 9, b(50), [unrelated], {}) == []
+
+
+def test_index_spends() -> None:
+    # 51 = CREATE_COIN
+    puzzle = Program.to((1, [[51, b(11), 40, [b(20)]], [51, b(12), 30]]))
+    coin = Coin(b(1), puzzle.get_tree_hash(), 100)
+    conds = run_spend(CoinSpend(coin, puzzle, Program.to(0)), 11000000000, 0)
+
+    hinted = Coin(coin.name(), b(11), 40)
+    unhinted = Coin(coin.name(), b(12), 30)
+    by_hint, by_puzzle_hash = index_spends(conds)
+    assert by_hint == {b(20): [hinted.name()]}
+    assert by_puzzle_hash == {
+        coin.puzzle_hash: [coin.name()],
+        b(11): [hinted.name()],
+        b(12): [unhinted.name()],
+    }
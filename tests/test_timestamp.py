@@ -0,0 +1,101 @@
+from typing import Dict, List, Optional
+
+import pytest
+
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
+from chia.types.blockchain_format.sized_bytes import bytes32
+from chia.util.errors import Err
+from gold_rs import (
+    BlockRecord,
+    ClassgroupElement,
+    ConsensusConstants,
+    G1Element,
+    prev_transaction_block_timestamps,
+    validate_timestamp,
+)
+
+CONSTANTS = ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS))
+NOW = 1700000000
+MAX_FUTURE_TIME = DEFAULT_CONSTANTS.MAX_FUTURE_TIME2
+NUMBER_OF_TIMESTAMPS = DEFAULT_CONSTANTS.NUMBER_OF_TIMESTAMPS
+
+
+def h(v: int) -> bytes32:
+    return bytes32(v.to_bytes(4, "big") + bytes(28))
+
+
+# every other block is a transaction block, with a timestamp of 10 times its
+# height
+def record(height: int) -> BlockRecord:
+    is_tx_block = height % 2 == 0
+    prev_tx_hash = None
+    if is_tx_block:
+        prev_tx_hash = h(height - 2) if height > 0 else DEFAULT_CONSTANTS.GENESIS_CHALLENGE
+    return BlockRecord(
+        h(height),
+        h(height - 1) if height > 0 else DEFAULT_CONSTANTS.GENESIS_CHALLENGE,
+        height,
+        height,
+        height * 10,
+        0,
+        ClassgroupElement.get_default_element(),
+        None,
+        h(0),
+        h(0),
+        0,
+        h(0),
+        h(0),
+        0,
+        0,
+        False,
+        max(height - 2 + height % 2, 0),
+        height * 10 if is_tx_block else None,
+        prev_tx_hash,
+        0 if is_tx_block else None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        G1Element(),
+    )
+
+
+BLOCKS: Dict[bytes32, BlockRecord] = {r.header_hash: r for r in [record(height) for height in range(60)]}
+
+
+def test_prev_timestamps() -> None:
+    assert prev_transaction_block_timestamps(CONSTANTS, BLOCKS[h(0)], BLOCKS) == [0]
+    assert prev_transaction_block_timestamps(CONSTANTS, BLOCKS[h(4)], BLOCKS.get) == [40, 20, 0]
+    assert prev_transaction_block_timestamps(CONSTANTS, BLOCKS[h(58)], BLOCKS) == [
+        580 - i * 20 for i in range(NUMBER_OF_TIMESTAMPS)
+    ]
+    with pytest.raises(ValueError, match="not a transaction block"):
+        prev_transaction_block_timestamps(CONSTANTS, BLOCKS[h(3)], BLOCKS)
+    with pytest.raises(ValueError, match="block not found"):
+        prev_transaction_block_timestamps(CONSTANTS, BLOCKS[h(4)], {})
+
+
+def check(timestamp: int, prev: List[int]) -> Optional[int]:
+    try:
+        validate_timestamp(timestamp, prev, NOW, CONSTANTS)
+        return None
+    except ValueError as e:
+        assert e.args[0] == "ValidationError"
+        return int(e.args[1])
+
+
+def test_future() -> None:
+    assert check(NOW + MAX_FUTURE_TIME, []) is None
+    assert check(NOW + MAX_FUTURE_TIME + 1, [NOW]) == Err.TIMESTAMP_TOO_FAR_IN_FUTURE.value
+
+
+def test_past() -> None:
+    # the average of the previous timestamps is 100
+    assert check(101, [150, 100, 50]) is None
+    assert check(100, [150, 100, 50]) == Err.TIMESTAMP_TOO_FAR_IN_PAST.value
+
+    # only the last NUMBER_OF_TIMESTAMPS count
+    prev = [100] * NUMBER_OF_TIMESTAMPS + [10**10]
+    assert check(101, prev) is None
+    assert check(100, prev) == Err.TIMESTAMP_TOO_FAR_IN_PAST.value
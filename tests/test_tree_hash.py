@@ -2,8 +2,9 @@ import gold_rs
 
 print("gold_rs path:", gold_rs.__file__)
 
-from gold_rs import tree_hash
+from gold_rs import tree_hash, tree_hashes, TreeHasher
 from hashlib import sha256
+import pytest
 
 
 def ha(buf: bytes) -> bytes:
@@ -43,3 +44,32 @@ def test_list() -> None:
 def test_tree() -> None:
     expected = hp(hp(ha(b"\x01"), ha(b"\x02")), hp(ha(b"\x03"), ha(b"\x04")))
     assert tree_hash(b"\xff\xff\x01\x02\xff\x03\x04") == expected
+
+
+def test_tree_hasher() -> None:
+    # a large shared prefix (b"\xbf" followed by 63 bytes is a 63 byte atom),
+    # followed by a differing tail
+    shared = b"\xff\xbf" + b"\x11" * 63 + b"\xff\xbf" + b"\x22" * 63 + b"\x80"
+    hasher = TreeHasher()
+    for i in range(5):
+        blob = b"\xff" + shared + bytes([0x81, 0x80 + i])
+        assert hasher.tree_hash(blob) == tree_hash(blob)
+    cached = hasher.len()
+    assert cached > 0
+    assert hasher.tree_hash(shared) == tree_hash(shared)
+    assert hasher.len() == cached
+    hasher.clear()
+    assert hasher.len() == 0
+
+
+def test_tree_hashes() -> None:
+    blobs = [
+        b"\x80",
+        b"\xff\x01\xff\x02\xff\x03\x80",
+        b"\xff\xff\x01\x02\xff\x03\x04",
+    ] * 100
+    assert tree_hashes(blobs) == [tree_hash(b) for b in blobs]
+    assert tree_hashes([]) == []
+
+    with pytest.raises(ValueError):
+        tree_hashes([b"\x80", b"\xff\x01"])
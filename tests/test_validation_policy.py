@@ -0,0 +1,84 @@
+from typing import List, Optional
+
+import pytest
+
+from chia.types.blockchain_format.sized_bytes import bytes32
+from chia.util.errors import Err
+
+from gold_rs import (
+    Coin,
+    CoinSpend,
+    G2Element,
+    Program,
+    SpendBundle,
+    ValidationPolicy,
+    run_spend_bundle,
+)
+
+MAX_COST = 11000000000
+
+CREATE_COIN = 51
+CREATE_COIN_ANNOUNCEMENT = 60
+
+
+def quoted_spend(conditions: List[List[object]], parent: int) -> CoinSpend:
+    puzzle = Program.to((1, conditions))
+    coin = Coin(bytes32([parent] * 32), puzzle.get_tree_hash(), 1000)
+    return CoinSpend(coin, puzzle, Program.to(0))
+
+
+# two spends, with 2 and 1 CREATE_COIN and one announcement each
+BUNDLE = SpendBundle(
+    [
+        quoted_spend(
+            [
+                [CREATE_COIN, bytes32([1] * 32), 1],
+                [CREATE_COIN, bytes32([2] * 32), 1],
+                [CREATE_COIN_ANNOUNCEMENT, b"foobar"],
+            ],
+            1,
+        ),
+        quoted_spend(
+            [
+                [CREATE_COIN, bytes32([1] * 32), 1],
+                [CREATE_COIN_ANNOUNCEMENT, b"foobar"],
+            ],
+            2,
+        ),
+    ],
+    G2Element(),
+)
+
+
+def test_default_policy() -> None:
+    policy = ValidationPolicy()
+    assert policy.max_create_coin_per_spend == 2**32 - 1
+    assert policy.max_announcements_per_spend == 1024
+    assert policy.max_conditions == 2**32 - 1
+
+    assert len(run_spend_bundle(BUNDLE, MAX_COST, 0)) == 2
+    assert len(run_spend_bundle(BUNDLE, MAX_COST, 0, policy)) == 2
+
+
+@pytest.mark.parametrize(
+    "policy,expected_err",
+    [
+        (ValidationPolicy(max_create_coin_per_spend=2), None),
+        (ValidationPolicy(max_create_coin_per_spend=1), 148),
+        (ValidationPolicy(max_announcements_per_spend=1), None),
+        (
+            ValidationPolicy(max_announcements_per_spend=0),
+            Err.TOO_MANY_ANNOUNCEMENTS.value,
+        ),
+        # the limit on the number of conditions applies to the whole bundle
+        (ValidationPolicy(max_conditions=5), None),
+        (ValidationPolicy(max_conditions=4), 149),
+    ],
+)
+def test_policy(policy: ValidationPolicy, expected_err: Optional[int]) -> None:
+    if expected_err is None:
+        assert len(run_spend_bundle(BUNDLE, MAX_COST, 0, policy)) == 2
+    else:
+        with pytest.raises(ValueError) as e:
+            run_spend_bundle(BUNDLE, MAX_COST, 0, policy)
+        assert e.value.args == ("ValidationError", expected_err)
@@ -0,0 +1,122 @@
+import random
+from typing import List, Optional, Tuple
+
+import pytest
+
+from chia.consensus.default_constants import DEFAULT_CONSTANTS
+from chia.full_node.weight_proof import _get_weights_for_sampling, _sample_sub_epoch
+from chia.types.blockchain_format.sized_bytes import bytes32
+from gold_rs import (
+    BlockRecord,
+    ClassgroupElement,
+    ConsensusConstants,
+    G1Element,
+    SubEpochChallengeSegment,
+    SubEpochSummary,
+    WeightProofBuilder,
+)
+from gold_rs.test_support import BlockTools
+
+CONSTANTS = ConsensusConstants.from_bytes(bytes(DEFAULT_CONSTANTS))
+BLOCKS_PER_SUB_EPOCH = 100
+WEIGHT_PER_BLOCK = 1000
+
+
+def record(height: int, ses: Optional[SubEpochSummary]) -> BlockRecord:
+    return BlockRecord(
+        bytes32(height.to_bytes(32, "big")),
+        bytes32((height - 1 if height > 0 else 0).to_bytes(32, "big")),
+        height,
+        height * WEIGHT_PER_BLOCK,
+        height * 10 * WEIGHT_PER_BLOCK,
+        0,
+        ClassgroupElement.get_default_element(),
+        None,
+        bytes32([0] * 32),
+        bytes32([0] * 32),
+        0,
+        bytes32([0] * 32),
+        bytes32([0] * 32),
+        0,
+        0,
+        False,
+        0,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        ses,
+        G1Element(),
+    )
+
+
+def ses(n: int) -> SubEpochSummary:
+    return SubEpochSummary(bytes32([n] * 32), bytes32([n + 100] * 32), n % 3, None, None)
+
+
+def make_builder(num_sub_epochs: int) -> Tuple[WeightProofBuilder, List[BlockRecord]]:
+    builder = WeightProofBuilder(record(0, None))
+    ses_blocks = [record(n * BLOCKS_PER_SUB_EPOCH, ses(n)) for n in range(1, num_sub_epochs + 1)]
+    for block in ses_blocks:
+        builder.add_sub_epoch(block)
+    return builder, ses_blocks
+
+
+def test_build() -> None:
+    builder, ses_blocks = make_builder(50)
+    assert len(builder) == 50
+    tip = record(50 * BLOCKS_PER_SUB_EPOCH + 10, None)
+
+    bt = BlockTools(CONSTANTS)
+    recent_chain = [b.get_header_block([], []) for b in bt.get_consecutive_blocks(5)]
+
+    # which sub-epochs the node samples
+    seed = builder.seed(tip.height)
+    assert seed == ses(49).get_hash()
+    weights = _get_weights_for_sampling(random.Random(seed), tip.weight, recent_chain)
+    expected: List[int] = []
+    prev = record(0, None)
+    for n, block in enumerate(ses_blocks):
+        if len(expected) == 20:
+            break
+        if _sample_sub_epoch(prev.weight, block.weight, weights):
+            expected.append(n)
+        prev = block
+    assert len(expected) > 0
+
+    sampled: List[int] = []
+
+    def segments(n: int, block: BlockRecord, prev: BlockRecord) -> List[SubEpochChallengeSegment]:
+        assert block == ses_blocks[n]
+        assert prev == (ses_blocks[n - 1] if n > 0 else record(0, None))
+        sampled.append(n)
+        return [SubEpochChallengeSegment(n, [], None)]
+
+    wp = builder.build(tip, recent_chain, segments)
+    assert sampled == expected
+    assert [s.sub_epoch_n for s in wp.sub_epoch_segments] == expected
+    assert [d.reward_chain_hash for d in wp.sub_epochs] == [ses(n).reward_chain_hash for n in range(1, 51)]
+    assert wp.recent_chain_data == recent_chain
+
+
+def test_errors() -> None:
+    builder, _ = make_builder(1)
+    with pytest.raises(ValueError, match="doesn't include a sub-epoch summary"):
+        builder.add_sub_epoch(record(500, None))
+
+    bt = BlockTools(CONSTANTS)
+    recent_chain = [b.get_header_block([], []) for b in bt.get_consecutive_blocks(2)]
+    with pytest.raises(ValueError, match="not enough sub-epochs"):
+        builder.build(record(200, None), recent_chain, lambda n, b, p: [])
+
+    # exceptions raised by the callback are passed on
+    builder, _ = make_builder(5)
+
+    def fail(n: int, block: BlockRecord, prev: BlockRecord) -> List[SubEpochChallengeSegment]:
+        raise RuntimeError("no segments")
+
+    with pytest.raises(RuntimeError, match="no segments"):
+        builder.build(record(600, None), recent_chain, fail)
@@ -1,16 +1,27 @@
 from pathlib import Path
-from typing import List, Optional, Tuple, TextIO
+from typing import Dict, List, Optional, Set, Tuple, TextIO
 from glob import glob
 
 output_file = Path(__file__).parent.resolve() / "python" / "gold_rs" / "gold_rs.pyi"
+test_support_file = (
+    Path(__file__).parent.resolve() / "python" / "gold_rs" / "test_support.pyi"
+)
 crates_dir = Path(__file__).parent.parent.resolve() / "crates"
 input_dir = crates_dir / "chia-protocol" / "src"
 
 # enums are exposed to python as int
-enums = set(["NodeType", "ProtocolMessageTypes", "RejectStateReason"])
+enums = set(
+    ["NodeType", "ProtocolMessageTypes", "RejectStateReason", "SigningDataKind"]
+)
+
+
+# #[trailing] fields are optional arguments to the constructor
+def without_default(m: str) -> str:
+    return m.split(" = ")[0]
 
 
 def transform_type(m: str) -> str:
+    m, sep, default = m.partition(" = ")
     n, t = m.split(":")
     if "List[" in t:
         t = t.replace("List[", "Sequence[")
@@ -18,11 +29,15 @@ def transform_type(m: str) -> str:
         t = " bytes"
     elif t.strip() in enums:
         t = " int"
-    return f"{n}:{t}"
+    return f"{n}:{t}{sep}{default}"
 
 
 def print_class(
-    file: TextIO, name: str, members: List[str], extra: Optional[List[str]] = None
+    file: TextIO,
+    name: str,
+    members: List[str],
+    extra: Optional[List[str]] = None,
+    renamed_args: Optional[Dict[str, str]] = None,
 ):
 
     # f-strings don't allow backslashes, which makes it a bit tricky to
@@ -32,7 +47,14 @@ def print_class(
     def add_indent(x: str):
         return "\n    " + x
 
-    init_args = "".join([(",\n        " + transform_type(x)) for x in members])
+    def rename_arg(x: str):
+        n, sep, rest = x.partition(":")
+        return (renamed_args or {}).get(n, n) + sep + rest
+
+    init_args = "".join(
+        [(",\n        " + transform_type(rename_arg(x))) for x in members]
+    )
+    members = [without_default(m) for m in members]
 
     all_replace_parameters = []
     for m in members:
@@ -53,21 +75,28 @@ class {name}:{"".join(map(add_indent, members))}
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> {name}: ...
     def __copy__(self) -> {name}: ...
     @staticmethod
     def from_bytes(bytes) -> {name}: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> {name}: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> {name}: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> {name}: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[{name}, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> {name}: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
 """
     )
 
@@ -106,15 +135,28 @@ def rust_type_to_python(t: str) -> str:
     return ret
 
 
+# protocol messages have the MSG_TYPE and RESPONSES class attributes
+messages: Set[str] = set()
+
+
 def parse_rust_source(filename: str, upper_case: bool) -> List[Tuple[str, List[str]]]:
     ret: List[Tuple[str, List[str]]] = []
     in_struct: Optional[str] = None
     members: List[str] = []
+    trailing = False
+    py_name: Optional[str] = None
+    is_message = False
     with open(filename) as f:
         for line in f:
             if not in_struct:
+                if line.startswith("#[streamable("):
+                    is_message = "message" in line or "responses(" in line
+                    continue
                 if line.startswith("pub struct ") and "{" in line:
                     in_struct = line.split("pub struct ")[1].split("{")[0].strip()
+                    if is_message:
+                        messages.add(in_struct)
+                    is_message = False
                 elif line.startswith("streamable_struct!") and "{" in line:
                     in_struct, line = line.split("(")[1].split("{")
                     in_struct = in_struct.strip()
@@ -135,6 +177,12 @@ def parse_rust_source(filename: str, upper_case: bool) -> List[Tuple[str, List[s
 
             # we're parsing members
             # ignore macros
+            if line.strip() == "#[trailing]":
+                trailing = True
+                continue
+            if line.strip().startswith("#[py_name = "):
+                py_name = line.strip().split('"')[1]
+                continue
             if line.strip().startswith("#"):
                 continue
 
@@ -148,7 +196,12 @@ def parse_rust_source(filename: str, upper_case: bool) -> List[Tuple[str, List[s
                     rust_type, line = rust_type.rsplit("}", 1)
                     line = "}" + line
                 py_type = rust_type_to_python(rust_type)
-                members.append(f"{name.upper() if upper_case else name}: {py_type}")
+                if py_name is not None:
+                    name = py_name
+                member = f"{name.upper() if upper_case else name}: {py_type}"
+                members.append(member + " = None" if trailing else member)
+                trailing = False
+                py_name = None
 
             # did we reach the end?
             if "}" in line:
@@ -161,10 +214,22 @@ def parse_rust_source(filename: str, upper_case: bool) -> List[Tuple[str, List[s
     return ret
 
 
+# constructor arguments whose names in the stubs differ from the field names
+init_arg_names = {
+    "ProofOfSpace": {"local_public_key": "plot_public_key"},
+}
+
 extra_members = {
+    "ProofOfSpace": [
+        "plot_public_key: G1Element",
+    ],
     "Coin": [
         "def name(self) -> bytes32: ...",
     ],
+    "TimestampedPeerInfo": [
+        "@staticmethod\n    def from_address(address: str, timestamp: int) -> TimestampedPeerInfo: ...",
+        "def address(self) -> str: ...",
+    ],
     "ClassgroupElement": [
         "@staticmethod\n    def create(bytes) -> ClassgroupElement: ...",
         "@staticmethod\n    def get_default_element() -> ClassgroupElement: ...",
@@ -175,6 +240,7 @@ extra_members = {
         "partial_hash: bytes32",
         "def is_transaction_block(self) -> bool: ...",
         "total_iters: uint128",
+        "def cached_bytes(self) -> bytes: ...",
     ],
     "FullBlock": [
         "prev_header_hash: bytes32",
@@ -185,8 +251,23 @@ extra_members = {
         "weight: uint128",
         "def get_included_reward_coins(self) -> List[Coin]: ...",
         "def is_fully_compactified(self) -> bool: ...",
+        "def uncompact_proofs(self) -> List[Tuple[int, VDFInfo]]: ...",
+        "def can_accept_compact_vdf(self, response: RespondCompactVDF) -> bool: ...",
+        "def to_bytes_compressed(self, level: int = 3) -> bytes: ...",
+        "@staticmethod\n    def from_bytes_compressed(blob: bytes) -> FullBlock: ...",
+        "def get_header_block(self, tx_addition_coins: Sequence[Coin], removals_names: Sequence[bytes32]) -> HeaderBlock: ...",
+        "def get_block_header(self) -> HeaderBlock: ...",
+        "def cached_bytes(self) -> bytes: ...",
+    ],
+    "SubEpochSegments": [
+        "def to_bytes_compressed(self, level: int = 3) -> bytes: ...",
+        "@staticmethod\n    def from_bytes_compressed(blob: bytes) -> SubEpochSegments: ...",
+    ],
+    "CoinSpend": [
+        "def cached_bytes(self) -> bytes: ...",
     ],
     "HeaderBlock": [
+        "def cached_bytes(self) -> bytes: ...",
         "prev_header_hash: bytes32",
         "prev_hash: bytes32",
         "height: uint32",
@@ -218,15 +299,26 @@ extra_members = {
         "def _run(self, max_cost: int, flags: int, args: object) -> Tuple[int, ChiaProgram]: ...",
         "@staticmethod\n    def to(o: object) -> Program: ...",
         "@staticmethod\n    def from_program(p: ChiaProgram) -> Program: ...",
+        "def run(self, args: object, max_cost: int, flags: int = 0) -> Tuple[int, Program]: ...",
+        "def to_python(self) -> Any: ...",
+        "@staticmethod\n    def from_python(o: object) -> Program: ...",
+        "def first(self) -> LazyNode: ...",
+        "def rest(self) -> LazyNode: ...",
+        "def pair(self) -> Optional[Tuple[LazyNode, LazyNode]]: ...",
+        "def as_int(self) -> int: ...",
+        "def as_atom_list(self) -> List[bytes]: ...",
         "def to_program(self) -> ChiaProgram: ...",
+        "def curry(self, *args: object) -> Program: ...",
         "def uncurry(self) -> Tuple[ChiaProgram, ChiaProgram]: ...",
     ],
     "SpendBundle": [
         "@staticmethod\n    def aggregate(sbs: List[SpendBundle]) -> SpendBundle: ...",
         "def name(self) -> bytes32: ...",
         "def removals(self) -> List[Coin]: ...",
+        "def removals_fingerprint(self) -> int: ...",
         "def additions(self) -> List[Coin]: ...",
         "def debug(self) -> None: ...",
+        "def cached_bytes(self) -> bytes: ...",
     ],
     "BlockRecord": [
         "is_transaction_block: bool",
@@ -238,11 +330,19 @@ extra_members = {
         "def ip_iters(self, constants: ConsensusConstants) -> uint64: ...",
         "def sp_total_iters(self, constants: ConsensusConstants) -> uint128: ...",
     ],
+    "ConsensusConstants": [
+        "@staticmethod\n    def from_dict_overrides(base: ConsensusConstants, overrides: Dict[str, Any]) -> ConsensusConstants: ...",
+        "@staticmethod\n    def from_json(base: ConsensusConstants, overrides: str) -> ConsensusConstants: ...",
+    ],
 }
 
 classes = []
 for filepath in sorted(glob(str(input_dir / "*.rs"))):
-    if filepath.endswith("bytes.rs") or filepath.endswith("lazy_node.rs"):
+    if (
+        filepath.endswith("bytes.rs")
+        or filepath.endswith("lazy_node.rs")
+        or filepath.endswith("rate_limits.rs")
+    ):
         continue
     classes.extend(parse_rust_source(filepath, upper_case=False))
 
@@ -260,7 +360,7 @@ with open(output_file, "w") as file:
 # this file is generated by generate_type_stubs.py
 #
 
-from typing import List, Optional, Sequence, Tuple, Union, Dict, Any, ClassVar
+from typing import BinaryIO, Callable, List, Optional, Sequence, Tuple, Union, Dict, Any, ClassVar, Set
 from .sized_bytes import bytes32, bytes100
 from .sized_ints import uint8, uint16, uint32, uint64, uint128, int8, int16, int32, int64
 from chia.types.blockchain_format.program import Program as ChiaProgram
@@ -271,25 +371,360 @@ class _Unspec:
     pass
 
 def solution_generator(spends: Sequence[Tuple[Coin, bytes, bytes]]) -> bytes: ...
+def parse_message(msg_type: int, data: bytes) -> Any: ...
+def set_repr_limits(
+    max_items: Optional[int] = None, max_depth: Optional[int] = None, max_hex_len: Optional[int] = None
+) -> None: ...
+def hash_coin_states(states: Sequence[CoinState]) -> bytes32: ...
+def encode_address(data: bytes, prefix: str) -> str: ...
+def decode_address(address: str, prefix: str) -> bytes: ...
 def solution_generator_backrefs(spends: Sequence[Tuple[Coin, bytes, bytes]]) -> bytes: ...
+def solution_generator_from_coin_spends(spends: Sequence[CoinSpend]) -> bytes: ...
+def build_block_generator(
+    spends: Sequence[Tuple[Coin, bytes, bytes]], max_cost: int
+) -> Tuple[bytes, int, List[int]]: ...
+def estimate_generator_size(spends: Sequence[Tuple[Coin, bytes, bytes]]) -> Tuple[int, int]: ...
+def estimate_cost(
+    spends: Sequence[Tuple[Coin, bytes, bytes]], constants: ConsensusConstants
+) -> int: ...
+def compress_generator(
+    generator: bytes, block_refs: Sequence[bytes], max_cost: int, flags: int = 0
+) -> bytes: ...
+def decompress_generator(generator: bytes) -> bytes: ...
+def get_block_cost(
+    generator: bytes, block_refs: Sequence[bytes], flags: int, constants: ConsensusConstants
+) -> Tuple[int, int]: ...
 
 def compute_merkle_set_root(items: Sequence[bytes]) -> bytes: ...
 
 def supports_fast_forward(spend: CoinSpend) -> bool : ...
 def fast_forward_singleton(spend: CoinSpend, new_coin: Coin, new_parent: Coin) -> bytes: ...
+def fast_forward_chain(spend: CoinSpend, new_lineage: Sequence[Tuple[Coin, Coin]]) -> bytes: ...
+
+def puzzle_hash_for_pk(pk: G1Element) -> bytes32: ...
+def puzzle_for_pk(pk: G1Element) -> Program: ...
+def solution_for_conditions(conditions: Program) -> Program: ...
+def calculate_synthetic_secret_key(sk: PrivateKey, hidden_puzzle_hash: bytes32) -> PrivateKey: ...
+def calculate_synthetic_public_key(pk: G1Element, hidden_puzzle_hash: bytes32) -> G1Element: ...
+def lineage_proof_for_parent(parent_spend: CoinSpend) -> LineageProof: ...
+def verify_lineage_proof(proof: LineageProof, coin_spend: CoinSpend) -> bool: ...
+def parse_pool_puzzle(puzzle: Program) -> PoolInnerPuzzle: ...
+def solution_to_pool_state(coin_spend: CoinSpend) -> Optional[PoolState]: ...
+def pool_state_from_extra_data(extra_data: Program) -> Optional[PoolState]: ...
+def clawback_puzzle_hash(timelock: int, sender_puzzle_hash: bytes32, recipient_puzzle_hash: bytes32) -> bytes32: ...
+def clawback_puzzle(timelock: int, sender_puzzle_hash: bytes32, recipient_puzzle_hash: bytes32) -> Program: ...
+def clawback_solution(
+    timelock: int,
+    sender_puzzle_hash: bytes32,
+    recipient_puzzle_hash: bytes32,
+    inner_puzzle: Program,
+    inner_solution: Program,
+) -> Program: ...
+def parse_clawback_spend(puzzle: Program, solution: Program) -> Optional[Tuple[bytes32, Optional[int], bytes32]]: ...
 
 def run_block_generator(
-    program: ReadableBuffer, args: List[ReadableBuffer], max_cost: int, flags: int
+    program: ReadableBuffer,
+    args: List[ReadableBuffer],
+    max_cost: int,
+    flags: int,
+    max_heap_bytes: Optional[int] = None,
+    metrics: Optional[Callable[[Dict[str, float]], None]] = None,
 ) -> Tuple[Optional[int], Optional[SpendBundleConditions]]: ...
 
 def run_block_generator2(
-    program: ReadableBuffer, args: List[ReadableBuffer], max_cost: int, flags: int
+    program: ReadableBuffer,
+    args: List[ReadableBuffer],
+    max_cost: int,
+    flags: int,
+    max_heap_bytes: Optional[int] = None,
+) -> Tuple[Optional[int], Optional[SpendBundleConditions]]: ...
+
+def run_block_generator_with_resolver(
+    program: ReadableBuffer,
+    ref_list: Sequence[int],
+    resolver: Callable[[int], Optional[bytes]],
+    max_cost: int,
+    flags: int,
 ) -> Tuple[Optional[int], Optional[SpendBundleConditions]]: ...
 
+def run_block_generator2_with_resolver(
+    program: ReadableBuffer,
+    ref_list: Sequence[int],
+    resolver: Callable[[int], Optional[bytes]],
+    max_cost: int,
+    flags: int,
+) -> Tuple[Optional[int], Optional[SpendBundleConditions]]: ...
+
+def block_generator_refs(block: bytes) -> List[int]: ...
+
+def profile_block_generator(
+    program: ReadableBuffer, args: List[ReadableBuffer], max_cost: int, flags: int
+) -> Tuple[Optional[int], Optional[SpendBundleConditions], Dict[bytes, Tuple[int, int]]]: ...
+
 def run_puzzle(
-    puzzle: bytes, solution: bytes, parent_id: bytes32, amount: int, max_cost: int, flags: int
+    puzzle: bytes,
+    solution: bytes,
+    parent_id: bytes32,
+    amount: int,
+    max_cost: int,
+    flags: int,
+    max_heap_bytes: Optional[int] = None,
 ) -> SpendBundleConditions: ...
 
+def run_puzzle_at_height(
+    puzzle: bytes,
+    solution: bytes,
+    parent_id: bytes32,
+    amount: int,
+    height: int,
+    constants: ConsensusConstants,
+) -> SpendBundleConditions: ...
+
+def analyze_spend(
+    puzzle: bytes,
+    solution: bytes,
+    parent_id: bytes32,
+    amount: int,
+    max_cost: int,
+    flags: int = 0,
+) -> Tuple[int, List[str]]: ...
+
+def run_spend(
+    spend: CoinSpend, max_cost: int, flags: int
+) -> SpendBundleConditions: ...
+
+def run_spend_bundle(
+    bundle: SpendBundle, max_cost: int, flags: int, policy: Optional[ValidationPolicy] = None
+) -> List[SpendBundleConditions]: ...
+
+def spend_bundle_to_dot(
+    bundle: SpendBundle, conds: SpendBundleConditions, max_cost: int, flags: int
+) -> str: ...
+
+def coin_announcement_id(coin_id: bytes32, message: bytes) -> bytes32: ...
+def puzzle_announcement_id(puzzle_hash: bytes32, message: bytes) -> bytes32: ...
+def check_announcements(
+    spends: Sequence[Tuple[Coin, Program]], flags: int = 0
+) -> Tuple[List[Tuple[bytes32, bool, bytes32]], List[Tuple[bytes32, bool, bytes32]]]: ...
+def conditions_to_program(
+    conditions: Union[Program, Sequence[Tuple[Any, ...]]], flags: int = 0
+) -> Program: ...
+
+class ValidationPolicy:
+    max_create_coin_per_spend: int
+    max_announcements_per_spend: int
+    max_conditions: int
+    def __init__(
+        self,
+        max_create_coin_per_spend: int = 4294967295,
+        max_announcements_per_spend: int = 1024,
+        max_conditions: int = 4294967295,
+    ) -> None: ...
+
+class SpendContext:
+    cost_left: int
+    def __init__(self, max_cost: int, flags: int, policy: Optional[ValidationPolicy] = None) -> None: ...
+    def run_spend(self, spend: CoinSpend) -> SpendBundleConditions: ...
+    def reset(self) -> None: ...
+
+def cheap_checks(
+    spend_bundle: SpendBundle, constants: ConsensusConstants, max_cost: int
+) -> Optional[Tuple[int, Optional[bytes32]]]: ...
+def mempool_eviction_order(items: Sequence[MempoolItem]) -> List[MempoolItem]: ...
+def mempool_block_order(items: Sequence[MempoolItem]) -> List[MempoolItem]: ...
+def condition_cost(opcode: int) -> int: ...
+def condition_cost_table() -> Dict[int, int]: ...
+def agg_sig_additional_data(constants: ConsensusConstants, opcode: int) -> Optional[bytes32]: ...
+def messages_to_sign(
+    conds: SpendBundleConditions, constants: ConsensusConstants
+) -> List[Tuple[G1Element, bytes]]: ...
+
+class NetworkInfo:
+    network_id: str
+    genesis_challenge: bytes32
+    agg_sig_me_additional_data: bytes32
+    address_prefix: str
+    def __init__(
+        self,
+        network_id: str,
+        genesis_challenge: bytes32,
+        agg_sig_me_additional_data: bytes32,
+        address_prefix: str,
+    ) -> None: ...
+    @staticmethod
+    def mainnet() -> NetworkInfo: ...
+    @staticmethod
+    def testnet11() -> NetworkInfo: ...
+    @staticmethod
+    def from_network_id(network_id: str) -> Optional[NetworkInfo]: ...
+    @staticmethod
+    def from_constants(
+        network_id: str, constants: ConsensusConstants, address_prefix: str
+    ) -> NetworkInfo: ...
+    def matches_constants(self, constants: ConsensusConstants) -> bool: ...
+    def agg_sig_additional_data(self, opcode: int) -> Optional[bytes32]: ...
+    def messages_to_sign(self, conds: SpendBundleConditions) -> List[Tuple[G1Element, bytes]]: ...
+    def __richcmp__(self) -> Any: ...
+
+def validate_block_signatures(
+    block: FullBlock,
+    constants: ConsensusConstants,
+    conds: Optional[SpendBundleConditions] = None,
+) -> None: ...
+
+class BlockValidator:
+    block: FullBlock
+    conds: Optional[SpendBundleConditions]
+    def __init__(self, block: FullBlock, constants: ConsensusConstants) -> None: ...
+    def validate_header(self) -> None: ...
+    def run_generator(
+        self, block_refs: Sequence[bytes] = []
+    ) -> Optional[SpendBundleConditions]: ...
+    def validate_signatures(self) -> None: ...
+
+def finish_block(
+    unfinished: UnfinishedBlock,
+    ip: NewInfusionPointVDF,
+    finished_sub_slots: Sequence[EndOfSubSlotBundle],
+    prev: Optional[BlockRecord],
+    difficulty: int,
+    is_transaction_block: bool,
+) -> FullBlock: ...
+def verify_spend_bundle_signatures(
+    bundles: Sequence[SpendBundle],
+    constants: ConsensusConstants,
+    cache: Optional[BLSCache] = None,
+    flags: int = 0,
+) -> List[bool]: ...
+def validate_clvm_and_signature(
+    bundle: SpendBundle,
+    max_cost: int,
+    constants: ConsensusConstants,
+    flags: int = 0,
+    metrics: Optional[Callable[[Dict[str, float]], None]] = None,
+    max_cost_per_spend: Optional[int] = None,
+) -> List[SpendBundleConditions]: ...
+def sign_coin_spends(
+    coin_spends: Sequence[CoinSpend],
+    key_lookup: Callable[[G1Element, bytes], Union[PrivateKey, G2Element, None]],
+    constants: ConsensusConstants,
+    flags: int = 0,
+) -> SpendBundle: ...
+
+def select_coins(
+    coins: Sequence[Tuple[Coin, int]],
+    target_amount: int,
+    strategy: str = "knapsack",
+    max_coin_count: int = 500,
+) -> List[Coin]: ...
+
+def find_fork_point(
+    old_peak: BlockRecord,
+    new_peak: BlockRecord,
+    lookup: Union[Dict[bytes32, BlockRecord], Callable[[bytes32], Optional[BlockRecord]]],
+) -> int: ...
+def compare_peaks(a: BlockRecord, b: BlockRecord) -> int: ...
+def make_sub_epoch_summary(
+    constants: ConsensusConstants,
+    blocks: Union[Dict[bytes32, BlockRecord], Callable[[bytes32], Optional[BlockRecord]]],
+    blocks_included_height: int,
+    prev_prev_block: BlockRecord,
+    new_difficulty: Optional[int],
+    new_sub_slot_iters: Optional[int],
+) -> SubEpochSummary: ...
+def validate_unfinished_header_block_linkage(
+    header: UnfinishedHeaderBlock,
+    prev: Optional[BlockRecord],
+    constants: ConsensusConstants,
+    blocks: Union[Dict[bytes32, BlockRecord], Callable[[bytes32], Optional[BlockRecord]]],
+) -> None: ...
+def validate_sp_signatures(
+    headers: Sequence[UnfinishedHeaderBlock],
+    prev: Optional[BlockRecord],
+    constants: ConsensusConstants,
+    blocks: Union[Dict[bytes32, BlockRecord], Callable[[bytes32], Optional[BlockRecord]]],
+) -> None: ...
+
+class HeaderContext:
+    header_hash: bytes32
+    height: Optional[int]
+    weight: int
+    total_iters: int
+    cc_challenge: bytes32
+    rc_challenge: bytes32
+    prev_cc_challenge: bytes32
+    @staticmethod
+    def genesis(constants: ConsensusConstants) -> HeaderContext: ...
+    @staticmethod
+    def from_prev(
+        prev: BlockRecord,
+        constants: ConsensusConstants,
+        blocks: Union[Dict[bytes32, BlockRecord], Callable[[bytes32], Optional[BlockRecord]]],
+    ) -> HeaderContext: ...
+
+def precheck_header_blocks(
+    headers: Sequence[HeaderBlock],
+    constants: ConsensusConstants,
+    prev_context: Optional[HeaderContext] = None,
+) -> HeaderContext:
+    \"\"\"
+    Checks a contiguous range of headers, following prev_context (or the
+    genesis block), and returns the context following the last header.
+
+    The proofs of space are only pre-checked (plot size, plot ID, challenge and
+    plot filter). The proofs themselves and their quality strings are NOT
+    verified, the caller has to do that.
+    \"\"\"
+def calculate_pool_reward(height: int) -> int: ...
+def calculate_base_farmer_reward(height: int) -> int: ...
+def create_pool_coin(block_height: int, puzzle_hash: bytes32, reward: int, constants: ConsensusConstants) -> Coin: ...
+def create_farmer_coin(block_height: int, puzzle_hash: bytes32, reward: int, constants: ConsensusConstants) -> Coin: ...
+def expected_reward_coins(
+    constants: ConsensusConstants,
+    prev_transaction_block: BlockRecord,
+    blocks: Union[Dict[bytes32, BlockRecord], Callable[[bytes32], Optional[BlockRecord]]],
+) -> List[Coin]: ...
+def validate_reward_claims(reward_claims: Sequence[Coin], expected: Sequence[Coin]) -> None: ...
+def validate_prefarm(
+    height: int, pool_puzzle_hash: bytes32, farmer_puzzle_hash: bytes32, constants: ConsensusConstants
+) -> None: ...
+def prev_transaction_block_timestamps(
+    constants: ConsensusConstants,
+    prev_transaction_block: BlockRecord,
+    blocks: Union[Dict[bytes32, BlockRecord], Callable[[bytes32], Optional[BlockRecord]]],
+) -> List[int]: ...
+def validate_timestamp(
+    timestamp: int, prev_transaction_block_timestamps: Sequence[int], now: int, constants: ConsensusConstants
+) -> None: ...
+
+class WeightProofBuilder:
+    def __init__(self, genesis: BlockRecord) -> None: ...
+    def __len__(self) -> int: ...
+    def add_sub_epoch(self, block: BlockRecord) -> None: ...
+    def rollback(self, height: int) -> None: ...
+    def sub_epoch_data(self, tip_height: int) -> List[SubEpochData]: ...
+    def seed(self, tip_height: int) -> Optional[bytes32]: ...
+    def build(
+        self,
+        tip: BlockRecord,
+        recent_chain: Sequence[HeaderBlock],
+        segments: Callable[[int, BlockRecord, BlockRecord], List[SubEpochChallengeSegment]],
+    ) -> WeightProof: ...
+
+class SignagePoint:
+    signage_point_index: int
+    sub_slot_total_iters: int
+    sub_slot_iters: int
+    sp_iters: int
+    total_iters: int
+    is_end_of_sub_slot: bool
+
+class SignagePointIterator:
+    def __init__(self, constants: ConsensusConstants, peak: Optional[BlockRecord] = None) -> None: ...
+    def __iter__(self) -> SignagePointIterator: ...
+    def __next__(self) -> SignagePoint: ...
+    def next_sub_slot(self) -> SignagePoint: ...
+
 def deserialize_proof(
     proof: bytes
 ) -> MerkleSet: ...
@@ -314,6 +749,7 @@ LIMIT_HEAP: int = ...
 ENABLE_SOFTFORK_CONDITION: int = ...
 ENABLE_MESSAGE_CONDITIONS: int = ...
 DISALLOW_INFINITY_G1: int = ...
+SKIP_AGG_SIG_CHECKS: int = ...
 MEMPOOL_MODE: int = ...
 NO_RELATIVE_CONDITIONS_ON_EPHEMERAL: int = ...
 ENABLE_BLS_OPS: int = ...
@@ -321,24 +757,122 @@ ENABLE_SECP_OPS: int = ...
 ENABLE_BLS_OPS_OUTSIDE_GUARD: int = ...
 ENABLE_FIXED_DIV: int = ...
 ALLOW_BACKREFS: int = ...
+CREATE_COIN_COST: int = ...
+AGG_SIG_COST: int = ...
 
 ELIGIBLE_FOR_DEDUP: int = ...
 ELIGIBLE_FOR_FF: int = ...
 
 NO_UNKNOWN_OPS: int = ...
 
+SELF_POOLING: int = ...
+LEAVING_POOL: int = ...
+FARMING_TO_POOL: int = ...
+
 def run_chia_program(
-    program: bytes, args: bytes, max_cost: int, flags: int
+    program: bytes, args: bytes, max_cost: int, flags: int, max_heap_bytes: Optional[int] = None
 ) -> Tuple[int, LazyNode]: ...
+def profile_chia_program(
+    program: bytes, args: bytes, max_cost: int, flags: int
+) -> Tuple[int, LazyNode, Dict[bytes, Tuple[int, int]]]: ...
+
+class Bytes48:
+    SIZE: ClassVar[int] = ...
+    def __init__(self, blob: Union[str, bytes, Bytes48, Bytes96, Bytes100]) -> None: ...
+    @staticmethod
+    def from_bytes(blob: bytes) -> Bytes48: ...
+    @staticmethod
+    def fromhex(h: str) -> Bytes48: ...
+    @staticmethod
+    def from_address(address: str, prefix: str) -> Bytes48: ...
+    @staticmethod
+    def zeros() -> Bytes48: ...
+    @staticmethod
+    def random(seed: int) -> Bytes48: ...
+    def hex(self) -> str: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def __len__(self) -> int: ...
+    def __add__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bytes: ...
+    def __radd__(self, other: bytes) -> bytes: ...
+    def __lt__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __le__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __gt__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __ge__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class Bytes96:
+    SIZE: ClassVar[int] = ...
+    def __init__(self, blob: Union[str, bytes, Bytes48, Bytes96, Bytes100]) -> None: ...
+    @staticmethod
+    def from_bytes(blob: bytes) -> Bytes96: ...
+    @staticmethod
+    def fromhex(h: str) -> Bytes96: ...
+    @staticmethod
+    def from_address(address: str, prefix: str) -> Bytes96: ...
+    @staticmethod
+    def zeros() -> Bytes96: ...
+    @staticmethod
+    def random(seed: int) -> Bytes96: ...
+    def hex(self) -> str: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def __len__(self) -> int: ...
+    def __add__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bytes: ...
+    def __radd__(self, other: bytes) -> bytes: ...
+    def __lt__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __le__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __gt__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __ge__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class Bytes100:
+    SIZE: ClassVar[int] = ...
+    def __init__(self, blob: Union[str, bytes, Bytes48, Bytes96, Bytes100]) -> None: ...
+    @staticmethod
+    def from_bytes(blob: bytes) -> Bytes100: ...
+    @staticmethod
+    def fromhex(h: str) -> Bytes100: ...
+    @staticmethod
+    def from_address(address: str, prefix: str) -> Bytes100: ...
+    @staticmethod
+    def zeros() -> Bytes100: ...
+    @staticmethod
+    def random(seed: int) -> Bytes100: ...
+    def hex(self) -> str: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def __len__(self) -> int: ...
+    def __add__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bytes: ...
+    def __radd__(self, other: bytes) -> bytes: ...
+    def __lt__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __le__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __gt__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __ge__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __hash__(self) -> int: ...
 
 class LazyNode:
     pair: Optional[Tuple[LazyNode, LazyNode]]
     atom: Optional[bytes]
+    def first(self) -> LazyNode: ...
+    def rest(self) -> LazyNode: ...
+    def as_int(self) -> int: ...
+    def as_atom_list(self) -> List[bytes]: ...
 
 def serialized_length(program: ReadableBuffer) -> int: ...
+def check_canonical_clvm(program: ReadableBuffer, flags: int = 0) -> Optional[Tuple[str, Optional[int]]]: ...
 def tree_hash(program: ReadableBuffer) -> bytes32: ...
+def tree_hashes(programs: Sequence[bytes]) -> List[bytes32]: ...
+def assemble(src: str) -> bytes: ...
+def disassemble(program: bytes, max_len: int = 16777216) -> str: ...
 def get_puzzle_and_solution_for_coin(program: ReadableBuffer, args: ReadableBuffer, max_cost: int, find_parent: bytes32, find_amount: int, find_ph: bytes32, flags: int) -> Tuple[bytes, bytes]: ...
 
+class TreeHasher:
+    def __init__(self, max_entries: int = 100000) -> None: ...
+    def tree_hash(self, program: bytes) -> bytes32: ...
+    def len(self) -> int: ...
+    def clear(self) -> None: ...
+
 class BLSCache:
     def __init__(self, cache_size: Optional[int] = 50000) -> None: ...
     def len(self) -> int: ...
@@ -346,6 +880,148 @@ class BLSCache:
     def items(self) -> List[Tuple[bytes, bytes]]: ...
     def update(self, other: List[Tuple[bytes, bytes]]) -> None: ...
 
+class DerivationCache:
+    def __init__(self, intermediate_pk: G1Element) -> None: ...
+    def len(self) -> int: ...
+    def derive(self, start: int, count: int) -> List[Tuple[int, G1Element, bytes32]]: ...
+
+class LineageProof:
+    parent_parent_coin_id: bytes32
+    parent_inner_puzzle_hash: bytes32
+    parent_amount: int
+    def __init__(
+        self, parent_parent_coin_id: bytes32, parent_inner_puzzle_hash: bytes32, parent_amount: int
+    ) -> None: ...
+    def to_program(self) -> Program: ...
+
+class Keyring:
+    def __init__(self) -> None: ...
+    @staticmethod
+    def open(path: str, passphrase: str) -> Keyring: ...
+    def save(self, path: str, passphrase: str) -> None: ...
+    def add(self, label: str, secret_key: PrivateKey) -> None: ...
+    def remove(self, label: str) -> Optional[PrivateKey]: ...
+    def get(self, label: str) -> Optional[PrivateKey]: ...
+    def get_by_fingerprint(self, fingerprint: int) -> Optional[Tuple[str, PrivateKey]]: ...
+    def labels(self) -> List[str]: ...
+    def __len__(self) -> int: ...
+
+def validate_public_keys(pks: Sequence[G1Element], allow_infinity: bool = False) -> List[bool]: ...
+def aggregate_public_keys(pks: Sequence[G1Element]) -> G1Element: ...
+def aggregate_public_keys_with_pop(pks: Sequence[G1Element], pops: Sequence[G2Element]) -> G1Element: ...
+def pop_prove(sk: PrivateKey) -> G2Element: ...
+def pop_verify(pk: G1Element, proof: G2Element) -> bool: ...
+
+CHIP_0002_SIGN_MESSAGE_PREFIX: str = ...
+def sign_message(
+    sk: PrivateKey, message: bytes, mode: str = "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG:CHIP-0002_"
+) -> G2Element: ...
+def verify_message(
+    pk: G1Element,
+    message: bytes,
+    signature: G2Element,
+    mode: str = "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG:CHIP-0002_",
+) -> bool: ...
+
+class MempoolItem:
+    spend_bundle: SpendBundle
+    spend_bundle_name: bytes32
+    cost: int
+    fee: int
+    additions: List[Coin]
+    removals: List[Coin]
+    spend_flags: List[int]
+    height_added: int
+    fee_per_cost: float
+    def __init__(
+        self, spend_bundle: SpendBundle, conds: SpendBundleConditions, height_added: int
+    ) -> None: ...
+
+class CoinStore:
+    def __init__(self) -> None: ...
+    def __copy__(self) -> CoinStore: ...
+    def __deepcopy__(self, memo: Any) -> CoinStore: ...
+    def __len__(self) -> int: ...
+    def add_coin(self, coin: Coin, height: int, hint: Optional[bytes32] = None) -> bool: ...
+    def spend_coin(self, coin_id: bytes32, height: int) -> bool: ...
+    def coin_state(self, coin_id: bytes32) -> Optional[CoinState]: ...
+    def coin_states(self, coin_ids: Sequence[bytes32]) -> List[CoinState]: ...
+    def coin_states_by_puzzle_hash(
+        self, puzzle_hashes: Sequence[bytes32], include_spent: bool = True
+    ) -> List[CoinState]: ...
+    def coin_states_by_hint(
+        self, hints: Sequence[bytes32], include_spent: bool = True
+    ) -> List[CoinState]: ...
+    def children(self, coin_id: bytes32) -> List[CoinState]: ...
+    def batch_coin_states(
+        self,
+        puzzle_hashes: Sequence[bytes32],
+        min_height: int,
+        filters: CoinStateFilters,
+        max_items: int = 50000,
+    ) -> Tuple[List[CoinState], Optional[int]]: ...
+    def changes_between(self, from_height: int, to_height: int) -> List[CoinState]: ...
+
+def calculate_plot_id_pk(pool_public_key: G1Element, plot_public_key: G1Element) -> bytes32: ...
+def calculate_plot_id_ph(pool_contract_puzzle_hash: bytes32, plot_public_key: G1Element) -> bytes32: ...
+def get_plot_id(pos: ProofOfSpace) -> Optional[bytes32]: ...
+def signage_point_hash(challenge_hash: bytes32, cc_sp_vdf: Optional[VDFInfo] = None) -> bytes32: ...
+def calculate_plot_filter_input(plot_id: bytes32, challenge_hash: bytes32, sp_hash: bytes32) -> bytes32: ...
+def calculate_pos_challenge(plot_id: bytes32, challenge_hash: bytes32, sp_hash: bytes32) -> bytes32: ...
+def passes_plot_filter(prefix_bits: int, plot_id: bytes32, challenge_hash: bytes32, sp_hash: bytes32) -> bool: ...
+def filter_coin_states(
+    states: Sequence[CoinState], filters: CoinStateFilters, min_height: int
+) -> List[CoinState]: ...
+def puzzle_state_responses(
+    puzzle_hashes: Sequence[bytes32],
+    coin_states: Sequence[CoinState],
+    peak_height: int,
+    peak_header_hash: bytes32,
+    header_hashes: Union[Dict[int, bytes32], Callable[[int], Optional[bytes32]]],
+    max_size: int = 104857600,
+) -> List[RespondPuzzleState]: ...
+
+def index_spends(
+    conds: SpendBundleConditions,
+) -> Tuple[Dict[bytes32, List[bytes32]], Dict[bytes32, List[bytes32]]]: ...
+
+class SubscriptionManager:
+    def __init__(self, max_subscriptions: int = 200000) -> None: ...
+    def subscription_count(self, peer: bytes32) -> int: ...
+    def add_puzzle_subscriptions(self, peer: bytes32, puzzle_hashes: Sequence[bytes32]) -> List[bytes32]: ...
+    def add_coin_subscriptions(self, peer: bytes32, coin_ids: Sequence[bytes32]) -> List[bytes32]: ...
+    def remove_puzzle_subscriptions(self, peer: bytes32, puzzle_hashes: Sequence[bytes32]) -> List[bytes32]: ...
+    def remove_coin_subscriptions(self, peer: bytes32, coin_ids: Sequence[bytes32]) -> List[bytes32]: ...
+    def remove_peer(self, peer: bytes32) -> None: ...
+    def puzzle_subscriptions(self, peer: bytes32) -> List[bytes32]: ...
+    def coin_subscriptions(self, peer: bytes32) -> List[bytes32]: ...
+    def updates_for_peak(
+        self,
+        height: int,
+        fork_height: int,
+        peak_hash: bytes32,
+        states: Sequence[CoinState],
+        hints: Dict[bytes32, bytes32],
+    ) -> List[Tuple[bytes32, CoinStateUpdate]]: ...
+
+class RateLimiter:
+    def __init__(
+        self, incoming: bool, reset_seconds: int = 60, percentage_of_limit: int = 100
+    ) -> None: ...
+    def __copy__(self) -> RateLimiter: ...
+    def __deepcopy__(self, memo: Any) -> RateLimiter: ...
+    def process_msg_and_check(self, msg: Message, now: Optional[float] = None) -> bool: ...
+
+class ForkSchedule:
+    def __init__(self, constants: ConsensusConstants) -> None: ...
+    @staticmethod
+    def fork_names() -> List[str]: ...
+    def activation_height(self, fork: str) -> int: ...
+    def is_active(self, fork: str, height: int) -> bool: ...
+    def active_at(self, height: int) -> Set[str]: ...
+    def flags_for_height(self, height: int) -> int: ...
+    def plot_filter_bits(self, height: int) -> int: ...
+
 class AugSchemeMPL:
     @staticmethod
     def sign(pk: PrivateKey, msg: bytes, prepend_pk: Optional[G1Element] = None) -> G2Element: ...
@@ -373,9 +1049,50 @@ class MerkleSet:
         self,
         leafs: List[bytes32],
     ) -> None: ...
+
+class MerkleBlob:
+    def __init__(
+        self,
+        blob: bytes = b"",
+    ) -> None: ...
+    def insert(self, key: int, value: int, hash: bytes32) -> None: ...
+    def delete(self, key: int) -> None: ...
+    def get_root_hash(self) -> Optional[bytes32]: ...
+    def get_proof_of_inclusion(self, key: int) -> ProofOfInclusion: ...
+    def get_keys_values(self) -> Dict[int, int]: ...
+    def get_blob(self) -> bytes: ...
+    def __len__(self) -> int: ...
 """
     )
 
+    print_class(
+        file,
+        "ProofOfInclusionLayer",
+        ["other_hash_side: int", "other_hash: bytes32", "combined_hash: bytes32"],
+    )
+
+    print_class(
+        file,
+        "ProofOfInclusion",
+        ["node_hash: bytes32", "layers: List[ProofOfInclusionLayer]"],
+        ["def root_hash(self) -> bytes32: ...", "def valid(self) -> bool: ..."],
+    )
+
+    print_class(
+        file,
+        "PoolInnerPuzzle",
+        [
+            "launcher_id: bytes32",
+            "state: int",
+            "target_puzzle_hash: bytes32",
+            "p2_singleton_puzzle_hash: bytes32",
+            "owner_pubkey: G1Element",
+            "pool_reward_prefix: bytes32",
+            "escape_puzzle_hash: Optional[bytes32]",
+            "relative_lock_height: Optional[int]",
+        ],
+    )
+
     print_class(
         file,
         "G1Element",
@@ -387,6 +1104,8 @@ class MerkleSet:
             "def pair(self, other: G2Element) -> GTElement: ...",
             "@staticmethod",
             "def generator() -> G1Element: ...",
+            "def is_valid(self) -> bool: ...",
+            "def is_inf(self) -> bool: ...",
             "def __str__(self) -> str: ...",
             "def __add__(self, other: G1Element) -> G1Element: ...",
             "def __iadd__(self, other: G1Element) -> G1Element: ...",
@@ -402,6 +1121,8 @@ class MerkleSet:
             "def pair(self, other: G1Element) -> GTElement: ...",
             "@staticmethod",
             "def generator() -> G2Element: ...",
+            "def is_valid(self) -> bool: ...",
+            "def is_inf(self) -> bool: ...",
             "def __str__(self) -> str: ...",
             "def __add__(self, other: G2Element) -> G2Element: ...",
             "def __iadd__(self, other: G2Element) -> G2Element: ...",
@@ -471,7 +1192,88 @@ class MerkleSet:
             "removal_amount: int",
             "addition_amount: int",
         ],
+        [
+            "def to_dot(self) -> str: ...",
+            "def to_arrays(self) -> Dict[str, bytes]: ...",
+        ],
     )
 
     for item in classes:
-        print_class(file, item[0], item[1], extra_members.get(item[0]))
+        extra = extra_members.get(item[0])
+        if item[0] in messages:
+            extra = ["MSG_TYPE: ClassVar[int]", "RESPONSES: ClassVar[List[type]]"] + (
+                extra or []
+            )
+        print_class(file, item[0], item[1], extra, init_arg_names.get(item[0]))
+
+# these only exist in a wheel built with the test_support feature. They're kept
+# out of gold_rs.pyi, so type checkers reject them in code that runs against a
+# release wheel, and are imported from gold_rs.test_support instead
+with open(test_support_file, "w") as file:
+    file.write(
+        """
+#
+# this file is generated by generate_type_stubs.py
+#
+
+import os
+from typing import List, Optional, Sequence, Tuple, Union, Any
+from .sized_bytes import bytes32
+from .gold_rs import Coin, CoinState, ConsensusConstants, FullBlock, SpendBundle
+
+def random_instance(name: str, seed: int) -> Any: ...
+
+class BlockTools:
+    def __init__(
+        self,
+        constants: ConsensusConstants,
+        seed: bytes = bytes(32),
+        farmer_puzzle_hash: Optional[bytes32] = None,
+        pool_puzzle_hash: Optional[bytes32] = None,
+    ) -> None: ...
+    def __copy__(self) -> BlockTools: ...
+    def __deepcopy__(self, memo: Any) -> BlockTools: ...
+    def next_block(
+        self, prev: Optional[FullBlock], bundles: Sequence[SpendBundle] = []
+    ) -> FullBlock: ...
+    def get_consecutive_blocks(
+        self, count: int, prev: Optional[FullBlock] = None
+    ) -> List[FullBlock]: ...
+    def reward_coins(self, block: FullBlock) -> Tuple[Coin, Coin]: ...
+
+class Simulator:
+    height: int
+    timestamp: int
+    def __init__(self, constants: ConsensusConstants) -> None: ...
+    def __copy__(self) -> Simulator: ...
+    def __deepcopy__(self, memo: Any) -> Simulator: ...
+    def mempool_len(self) -> int: ...
+    def new_coin(self, puzzle_hash: bytes32, amount: int) -> Coin: ...
+    def push_tx(self, bundle: SpendBundle) -> int: ...
+    def farm_block(self, seconds: int = 20) -> List[CoinState]: ...
+    def coin_state(self, coin_id: bytes32) -> Optional[CoinState]: ...
+    def coin_states(self, coin_ids: Sequence[bytes32]) -> List[CoinState]: ...
+    def coin_states_by_puzzle_hash(
+        self, puzzle_hashes: Sequence[bytes32], include_spent: bool = True
+    ) -> List[CoinState]: ...
+    def coin_states_by_hint(
+        self, hints: Sequence[bytes32], include_spent: bool = True
+    ) -> List[CoinState]: ...
+    def children(self, coin_id: bytes32) -> List[CoinState]: ...
+
+class ChainDb:
+    def __init__(self, path: Union[str, os.PathLike[str]]) -> None: ...
+    def peak_height(self) -> Optional[int]: ...
+    def get_block(self, height: int) -> Optional[FullBlock]: ...
+    def get_block_by_hash(self, header_hash: bytes32) -> Optional[FullBlock]: ...
+    def get_blocks(self, start: int, end: int) -> List[FullBlock]: ...
+    def generator_refs(self, block: FullBlock) -> List[bytes]: ...
+    def iter_blocks(
+        self, start_height: int = 0, end_height: Optional[int] = None
+    ) -> ChainDbIterator: ...
+
+class ChainDbIterator:
+    def __iter__(self) -> ChainDbIterator: ...
+    def __next__(self) -> FullBlock: ...
+"""
+    )
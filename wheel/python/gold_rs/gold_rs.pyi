@@ -3,7 +3,7 @@
 # this file is generated by generate_type_stubs.py
 #
 
-from typing import List, Optional, Sequence, Tuple, Union, Dict, Any, ClassVar
+from typing import BinaryIO, Callable, List, Optional, Sequence, Tuple, Union, Dict, Any, ClassVar, Set
 from .sized_bytes import bytes32, bytes100
 from .sized_ints import uint8, uint16, uint32, uint64, uint128, int8, int16, int32, int64
 from chia.types.blockchain_format.program import Program as ChiaProgram
@@ -14,25 +14,360 @@ class _Unspec:
     pass
 
 def solution_generator(spends: Sequence[Tuple[Coin, bytes, bytes]]) -> bytes: ...
+def parse_message(msg_type: int, data: bytes) -> Any: ...
+def set_repr_limits(
+    max_items: Optional[int] = None, max_depth: Optional[int] = None, max_hex_len: Optional[int] = None
+) -> None: ...
+def hash_coin_states(states: Sequence[CoinState]) -> bytes32: ...
+def encode_address(data: bytes, prefix: str) -> str: ...
+def decode_address(address: str, prefix: str) -> bytes: ...
 def solution_generator_backrefs(spends: Sequence[Tuple[Coin, bytes, bytes]]) -> bytes: ...
+def solution_generator_from_coin_spends(spends: Sequence[CoinSpend]) -> bytes: ...
+def build_block_generator(
+    spends: Sequence[Tuple[Coin, bytes, bytes]], max_cost: int
+) -> Tuple[bytes, int, List[int]]: ...
+def estimate_generator_size(spends: Sequence[Tuple[Coin, bytes, bytes]]) -> Tuple[int, int]: ...
+def estimate_cost(
+    spends: Sequence[Tuple[Coin, bytes, bytes]], constants: ConsensusConstants
+) -> int: ...
+def compress_generator(
+    generator: bytes, block_refs: Sequence[bytes], max_cost: int, flags: int = 0
+) -> bytes: ...
+def decompress_generator(generator: bytes) -> bytes: ...
+def get_block_cost(
+    generator: bytes, block_refs: Sequence[bytes], flags: int, constants: ConsensusConstants
+) -> Tuple[int, int]: ...
 
 def compute_merkle_set_root(items: Sequence[bytes]) -> bytes: ...
 
 def supports_fast_forward(spend: CoinSpend) -> bool : ...
 def fast_forward_singleton(spend: CoinSpend, new_coin: Coin, new_parent: Coin) -> bytes: ...
+def fast_forward_chain(spend: CoinSpend, new_lineage: Sequence[Tuple[Coin, Coin]]) -> bytes: ...
+
+def puzzle_hash_for_pk(pk: G1Element) -> bytes32: ...
+def puzzle_for_pk(pk: G1Element) -> Program: ...
+def solution_for_conditions(conditions: Program) -> Program: ...
+def calculate_synthetic_secret_key(sk: PrivateKey, hidden_puzzle_hash: bytes32) -> PrivateKey: ...
+def calculate_synthetic_public_key(pk: G1Element, hidden_puzzle_hash: bytes32) -> G1Element: ...
+def lineage_proof_for_parent(parent_spend: CoinSpend) -> LineageProof: ...
+def verify_lineage_proof(proof: LineageProof, coin_spend: CoinSpend) -> bool: ...
+def parse_pool_puzzle(puzzle: Program) -> PoolInnerPuzzle: ...
+def solution_to_pool_state(coin_spend: CoinSpend) -> Optional[PoolState]: ...
+def pool_state_from_extra_data(extra_data: Program) -> Optional[PoolState]: ...
+def clawback_puzzle_hash(timelock: int, sender_puzzle_hash: bytes32, recipient_puzzle_hash: bytes32) -> bytes32: ...
+def clawback_puzzle(timelock: int, sender_puzzle_hash: bytes32, recipient_puzzle_hash: bytes32) -> Program: ...
+def clawback_solution(
+    timelock: int,
+    sender_puzzle_hash: bytes32,
+    recipient_puzzle_hash: bytes32,
+    inner_puzzle: Program,
+    inner_solution: Program,
+) -> Program: ...
+def parse_clawback_spend(puzzle: Program, solution: Program) -> Optional[Tuple[bytes32, Optional[int], bytes32]]: ...
 
 def run_block_generator(
-    program: ReadableBuffer, args: List[ReadableBuffer], max_cost: int, flags: int
+    program: ReadableBuffer,
+    args: List[ReadableBuffer],
+    max_cost: int,
+    flags: int,
+    max_heap_bytes: Optional[int] = None,
+    metrics: Optional[Callable[[Dict[str, float]], None]] = None,
 ) -> Tuple[Optional[int], Optional[SpendBundleConditions]]: ...
 
 def run_block_generator2(
-    program: ReadableBuffer, args: List[ReadableBuffer], max_cost: int, flags: int
+    program: ReadableBuffer,
+    args: List[ReadableBuffer],
+    max_cost: int,
+    flags: int,
+    max_heap_bytes: Optional[int] = None,
+) -> Tuple[Optional[int], Optional[SpendBundleConditions]]: ...
+
+def run_block_generator_with_resolver(
+    program: ReadableBuffer,
+    ref_list: Sequence[int],
+    resolver: Callable[[int], Optional[bytes]],
+    max_cost: int,
+    flags: int,
 ) -> Tuple[Optional[int], Optional[SpendBundleConditions]]: ...
 
+def run_block_generator2_with_resolver(
+    program: ReadableBuffer,
+    ref_list: Sequence[int],
+    resolver: Callable[[int], Optional[bytes]],
+    max_cost: int,
+    flags: int,
+) -> Tuple[Optional[int], Optional[SpendBundleConditions]]: ...
+
+def block_generator_refs(block: bytes) -> List[int]: ...
+
+def profile_block_generator(
+    program: ReadableBuffer, args: List[ReadableBuffer], max_cost: int, flags: int
+) -> Tuple[Optional[int], Optional[SpendBundleConditions], Dict[bytes, Tuple[int, int]]]: ...
+
 def run_puzzle(
-    puzzle: bytes, solution: bytes, parent_id: bytes32, amount: int, max_cost: int, flags: int
+    puzzle: bytes,
+    solution: bytes,
+    parent_id: bytes32,
+    amount: int,
+    max_cost: int,
+    flags: int,
+    max_heap_bytes: Optional[int] = None,
+) -> SpendBundleConditions: ...
+
+def run_puzzle_at_height(
+    puzzle: bytes,
+    solution: bytes,
+    parent_id: bytes32,
+    amount: int,
+    height: int,
+    constants: ConsensusConstants,
+) -> SpendBundleConditions: ...
+
+def analyze_spend(
+    puzzle: bytes,
+    solution: bytes,
+    parent_id: bytes32,
+    amount: int,
+    max_cost: int,
+    flags: int = 0,
+) -> Tuple[int, List[str]]: ...
+
+def run_spend(
+    spend: CoinSpend, max_cost: int, flags: int
 ) -> SpendBundleConditions: ...
 
+def run_spend_bundle(
+    bundle: SpendBundle, max_cost: int, flags: int, policy: Optional[ValidationPolicy] = None
+) -> List[SpendBundleConditions]: ...
+
+def spend_bundle_to_dot(
+    bundle: SpendBundle, conds: SpendBundleConditions, max_cost: int, flags: int
+) -> str: ...
+
+def coin_announcement_id(coin_id: bytes32, message: bytes) -> bytes32: ...
+def puzzle_announcement_id(puzzle_hash: bytes32, message: bytes) -> bytes32: ...
+def check_announcements(
+    spends: Sequence[Tuple[Coin, Program]], flags: int = 0
+) -> Tuple[List[Tuple[bytes32, bool, bytes32]], List[Tuple[bytes32, bool, bytes32]]]: ...
+def conditions_to_program(
+    conditions: Union[Program, Sequence[Tuple[Any, ...]]], flags: int = 0
+) -> Program: ...
+
+class ValidationPolicy:
+    max_create_coin_per_spend: int
+    max_announcements_per_spend: int
+    max_conditions: int
+    def __init__(
+        self,
+        max_create_coin_per_spend: int = 4294967295,
+        max_announcements_per_spend: int = 1024,
+        max_conditions: int = 4294967295,
+    ) -> None: ...
+
+class SpendContext:
+    cost_left: int
+    def __init__(self, max_cost: int, flags: int, policy: Optional[ValidationPolicy] = None) -> None: ...
+    def run_spend(self, spend: CoinSpend) -> SpendBundleConditions: ...
+    def reset(self) -> None: ...
+
+def cheap_checks(
+    spend_bundle: SpendBundle, constants: ConsensusConstants, max_cost: int
+) -> Optional[Tuple[int, Optional[bytes32]]]: ...
+def mempool_eviction_order(items: Sequence[MempoolItem]) -> List[MempoolItem]: ...
+def mempool_block_order(items: Sequence[MempoolItem]) -> List[MempoolItem]: ...
+def condition_cost(opcode: int) -> int: ...
+def condition_cost_table() -> Dict[int, int]: ...
+def agg_sig_additional_data(constants: ConsensusConstants, opcode: int) -> Optional[bytes32]: ...
+def messages_to_sign(
+    conds: SpendBundleConditions, constants: ConsensusConstants
+) -> List[Tuple[G1Element, bytes]]: ...
+
+class NetworkInfo:
+    network_id: str
+    genesis_challenge: bytes32
+    agg_sig_me_additional_data: bytes32
+    address_prefix: str
+    def __init__(
+        self,
+        network_id: str,
+        genesis_challenge: bytes32,
+        agg_sig_me_additional_data: bytes32,
+        address_prefix: str,
+    ) -> None: ...
+    @staticmethod
+    def mainnet() -> NetworkInfo: ...
+    @staticmethod
+    def testnet11() -> NetworkInfo: ...
+    @staticmethod
+    def from_network_id(network_id: str) -> Optional[NetworkInfo]: ...
+    @staticmethod
+    def from_constants(
+        network_id: str, constants: ConsensusConstants, address_prefix: str
+    ) -> NetworkInfo: ...
+    def matches_constants(self, constants: ConsensusConstants) -> bool: ...
+    def agg_sig_additional_data(self, opcode: int) -> Optional[bytes32]: ...
+    def messages_to_sign(self, conds: SpendBundleConditions) -> List[Tuple[G1Element, bytes]]: ...
+    def __richcmp__(self) -> Any: ...
+
+def validate_block_signatures(
+    block: FullBlock,
+    constants: ConsensusConstants,
+    conds: Optional[SpendBundleConditions] = None,
+) -> None: ...
+
+class BlockValidator:
+    block: FullBlock
+    conds: Optional[SpendBundleConditions]
+    def __init__(self, block: FullBlock, constants: ConsensusConstants) -> None: ...
+    def validate_header(self) -> None: ...
+    def run_generator(
+        self, block_refs: Sequence[bytes] = []
+    ) -> Optional[SpendBundleConditions]: ...
+    def validate_signatures(self) -> None: ...
+
+def finish_block(
+    unfinished: UnfinishedBlock,
+    ip: NewInfusionPointVDF,
+    finished_sub_slots: Sequence[EndOfSubSlotBundle],
+    prev: Optional[BlockRecord],
+    difficulty: int,
+    is_transaction_block: bool,
+) -> FullBlock: ...
+def verify_spend_bundle_signatures(
+    bundles: Sequence[SpendBundle],
+    constants: ConsensusConstants,
+    cache: Optional[BLSCache] = None,
+    flags: int = 0,
+) -> List[bool]: ...
+def validate_clvm_and_signature(
+    bundle: SpendBundle,
+    max_cost: int,
+    constants: ConsensusConstants,
+    flags: int = 0,
+    metrics: Optional[Callable[[Dict[str, float]], None]] = None,
+    max_cost_per_spend: Optional[int] = None,
+) -> List[SpendBundleConditions]: ...
+def sign_coin_spends(
+    coin_spends: Sequence[CoinSpend],
+    key_lookup: Callable[[G1Element, bytes], Union[PrivateKey, G2Element, None]],
+    constants: ConsensusConstants,
+    flags: int = 0,
+) -> SpendBundle: ...
+
+def select_coins(
+    coins: Sequence[Tuple[Coin, int]],
+    target_amount: int,
+    strategy: str = "knapsack",
+    max_coin_count: int = 500,
+) -> List[Coin]: ...
+
+def find_fork_point(
+    old_peak: BlockRecord,
+    new_peak: BlockRecord,
+    lookup: Union[Dict[bytes32, BlockRecord], Callable[[bytes32], Optional[BlockRecord]]],
+) -> int: ...
+def compare_peaks(a: BlockRecord, b: BlockRecord) -> int: ...
+def make_sub_epoch_summary(
+    constants: ConsensusConstants,
+    blocks: Union[Dict[bytes32, BlockRecord], Callable[[bytes32], Optional[BlockRecord]]],
+    blocks_included_height: int,
+    prev_prev_block: BlockRecord,
+    new_difficulty: Optional[int],
+    new_sub_slot_iters: Optional[int],
+) -> SubEpochSummary: ...
+def validate_unfinished_header_block_linkage(
+    header: UnfinishedHeaderBlock,
+    prev: Optional[BlockRecord],
+    constants: ConsensusConstants,
+    blocks: Union[Dict[bytes32, BlockRecord], Callable[[bytes32], Optional[BlockRecord]]],
+) -> None: ...
+def validate_sp_signatures(
+    headers: Sequence[UnfinishedHeaderBlock],
+    prev: Optional[BlockRecord],
+    constants: ConsensusConstants,
+    blocks: Union[Dict[bytes32, BlockRecord], Callable[[bytes32], Optional[BlockRecord]]],
+) -> None: ...
+
+class HeaderContext:
+    header_hash: bytes32
+    height: Optional[int]
+    weight: int
+    total_iters: int
+    cc_challenge: bytes32
+    rc_challenge: bytes32
+    prev_cc_challenge: bytes32
+    @staticmethod
+    def genesis(constants: ConsensusConstants) -> HeaderContext: ...
+    @staticmethod
+    def from_prev(
+        prev: BlockRecord,
+        constants: ConsensusConstants,
+        blocks: Union[Dict[bytes32, BlockRecord], Callable[[bytes32], Optional[BlockRecord]]],
+    ) -> HeaderContext: ...
+
+def precheck_header_blocks(
+    headers: Sequence[HeaderBlock],
+    constants: ConsensusConstants,
+    prev_context: Optional[HeaderContext] = None,
+) -> HeaderContext:
+    """
+    Checks a contiguous range of headers, following prev_context (or the
+    genesis block), and returns the context following the last header.
+
+    The proofs of space are only pre-checked (plot size, plot ID, challenge and
+    plot filter). The proofs themselves and their quality strings are NOT
+    verified, the caller has to do that.
+    """
+def calculate_pool_reward(height: int) -> int: ...
+def calculate_base_farmer_reward(height: int) -> int: ...
+def create_pool_coin(block_height: int, puzzle_hash: bytes32, reward: int, constants: ConsensusConstants) -> Coin: ...
+def create_farmer_coin(block_height: int, puzzle_hash: bytes32, reward: int, constants: ConsensusConstants) -> Coin: ...
+def expected_reward_coins(
+    constants: ConsensusConstants,
+    prev_transaction_block: BlockRecord,
+    blocks: Union[Dict[bytes32, BlockRecord], Callable[[bytes32], Optional[BlockRecord]]],
+) -> List[Coin]: ...
+def validate_reward_claims(reward_claims: Sequence[Coin], expected: Sequence[Coin]) -> None: ...
+def validate_prefarm(
+    height: int, pool_puzzle_hash: bytes32, farmer_puzzle_hash: bytes32, constants: ConsensusConstants
+) -> None: ...
+def prev_transaction_block_timestamps(
+    constants: ConsensusConstants,
+    prev_transaction_block: BlockRecord,
+    blocks: Union[Dict[bytes32, BlockRecord], Callable[[bytes32], Optional[BlockRecord]]],
+) -> List[int]: ...
+def validate_timestamp(
+    timestamp: int, prev_transaction_block_timestamps: Sequence[int], now: int, constants: ConsensusConstants
+) -> None: ...
+
+class WeightProofBuilder:
+    def __init__(self, genesis: BlockRecord) -> None: ...
+    def __len__(self) -> int: ...
+    def add_sub_epoch(self, block: BlockRecord) -> None: ...
+    def rollback(self, height: int) -> None: ...
+    def sub_epoch_data(self, tip_height: int) -> List[SubEpochData]: ...
+    def seed(self, tip_height: int) -> Optional[bytes32]: ...
+    def build(
+        self,
+        tip: BlockRecord,
+        recent_chain: Sequence[HeaderBlock],
+        segments: Callable[[int, BlockRecord, BlockRecord], List[SubEpochChallengeSegment]],
+    ) -> WeightProof: ...
+
+class SignagePoint:
+    signage_point_index: int
+    sub_slot_total_iters: int
+    sub_slot_iters: int
+    sp_iters: int
+    total_iters: int
+    is_end_of_sub_slot: bool
+
+class SignagePointIterator:
+    def __init__(self, constants: ConsensusConstants, peak: Optional[BlockRecord] = None) -> None: ...
+    def __iter__(self) -> SignagePointIterator: ...
+    def __next__(self) -> SignagePoint: ...
+    def next_sub_slot(self) -> SignagePoint: ...
+
 def deserialize_proof(
     proof: bytes
 ) -> MerkleSet: ...
@@ -57,6 +392,7 @@ LIMIT_HEAP: int = ...
 ENABLE_SOFTFORK_CONDITION: int = ...
 ENABLE_MESSAGE_CONDITIONS: int = ...
 DISALLOW_INFINITY_G1: int = ...
+SKIP_AGG_SIG_CHECKS: int = ...
 MEMPOOL_MODE: int = ...
 NO_RELATIVE_CONDITIONS_ON_EPHEMERAL: int = ...
 ENABLE_BLS_OPS: int = ...
@@ -64,24 +400,122 @@ ENABLE_SECP_OPS: int = ...
 ENABLE_BLS_OPS_OUTSIDE_GUARD: int = ...
 ENABLE_FIXED_DIV: int = ...
 ALLOW_BACKREFS: int = ...
+CREATE_COIN_COST: int = ...
+AGG_SIG_COST: int = ...
 
 ELIGIBLE_FOR_DEDUP: int = ...
 ELIGIBLE_FOR_FF: int = ...
 
 NO_UNKNOWN_OPS: int = ...
 
+SELF_POOLING: int = ...
+LEAVING_POOL: int = ...
+FARMING_TO_POOL: int = ...
+
 def run_chia_program(
-    program: bytes, args: bytes, max_cost: int, flags: int
+    program: bytes, args: bytes, max_cost: int, flags: int, max_heap_bytes: Optional[int] = None
 ) -> Tuple[int, LazyNode]: ...
+def profile_chia_program(
+    program: bytes, args: bytes, max_cost: int, flags: int
+) -> Tuple[int, LazyNode, Dict[bytes, Tuple[int, int]]]: ...
+
+class Bytes48:
+    SIZE: ClassVar[int] = ...
+    def __init__(self, blob: Union[str, bytes, Bytes48, Bytes96, Bytes100]) -> None: ...
+    @staticmethod
+    def from_bytes(blob: bytes) -> Bytes48: ...
+    @staticmethod
+    def fromhex(h: str) -> Bytes48: ...
+    @staticmethod
+    def from_address(address: str, prefix: str) -> Bytes48: ...
+    @staticmethod
+    def zeros() -> Bytes48: ...
+    @staticmethod
+    def random(seed: int) -> Bytes48: ...
+    def hex(self) -> str: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def __len__(self) -> int: ...
+    def __add__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bytes: ...
+    def __radd__(self, other: bytes) -> bytes: ...
+    def __lt__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __le__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __gt__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __ge__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class Bytes96:
+    SIZE: ClassVar[int] = ...
+    def __init__(self, blob: Union[str, bytes, Bytes48, Bytes96, Bytes100]) -> None: ...
+    @staticmethod
+    def from_bytes(blob: bytes) -> Bytes96: ...
+    @staticmethod
+    def fromhex(h: str) -> Bytes96: ...
+    @staticmethod
+    def from_address(address: str, prefix: str) -> Bytes96: ...
+    @staticmethod
+    def zeros() -> Bytes96: ...
+    @staticmethod
+    def random(seed: int) -> Bytes96: ...
+    def hex(self) -> str: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def __len__(self) -> int: ...
+    def __add__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bytes: ...
+    def __radd__(self, other: bytes) -> bytes: ...
+    def __lt__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __le__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __gt__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __ge__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class Bytes100:
+    SIZE: ClassVar[int] = ...
+    def __init__(self, blob: Union[str, bytes, Bytes48, Bytes96, Bytes100]) -> None: ...
+    @staticmethod
+    def from_bytes(blob: bytes) -> Bytes100: ...
+    @staticmethod
+    def fromhex(h: str) -> Bytes100: ...
+    @staticmethod
+    def from_address(address: str, prefix: str) -> Bytes100: ...
+    @staticmethod
+    def zeros() -> Bytes100: ...
+    @staticmethod
+    def random(seed: int) -> Bytes100: ...
+    def hex(self) -> str: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def __len__(self) -> int: ...
+    def __add__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bytes: ...
+    def __radd__(self, other: bytes) -> bytes: ...
+    def __lt__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __le__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __gt__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __ge__(self, other: Union[bytes, Bytes48, Bytes96, Bytes100]) -> bool: ...
+    def __hash__(self) -> int: ...
 
 class LazyNode:
     pair: Optional[Tuple[LazyNode, LazyNode]]
     atom: Optional[bytes]
+    def first(self) -> LazyNode: ...
+    def rest(self) -> LazyNode: ...
+    def as_int(self) -> int: ...
+    def as_atom_list(self) -> List[bytes]: ...
 
 def serialized_length(program: ReadableBuffer) -> int: ...
+def check_canonical_clvm(program: ReadableBuffer, flags: int = 0) -> Optional[Tuple[str, Optional[int]]]: ...
 def tree_hash(program: ReadableBuffer) -> bytes32: ...
+def tree_hashes(programs: Sequence[bytes]) -> List[bytes32]: ...
+def assemble(src: str) -> bytes: ...
+def disassemble(program: bytes, max_len: int = 16777216) -> str: ...
 def get_puzzle_and_solution_for_coin(program: ReadableBuffer, args: ReadableBuffer, max_cost: int, find_parent: bytes32, find_amount: int, find_ph: bytes32, flags: int) -> Tuple[bytes, bytes]: ...
 
+class TreeHasher:
+    def __init__(self, max_entries: int = 100000) -> None: ...
+    def tree_hash(self, program: bytes) -> bytes32: ...
+    def len(self) -> int: ...
+    def clear(self) -> None: ...
+
 class BLSCache:
     def __init__(self, cache_size: Optional[int] = 50000) -> None: ...
     def len(self) -> int: ...
@@ -89,6 +523,148 @@ class BLSCache:
     def items(self) -> List[Tuple[bytes, bytes]]: ...
     def update(self, other: List[Tuple[bytes, bytes]]) -> None: ...
 
+class DerivationCache:
+    def __init__(self, intermediate_pk: G1Element) -> None: ...
+    def len(self) -> int: ...
+    def derive(self, start: int, count: int) -> List[Tuple[int, G1Element, bytes32]]: ...
+
+class LineageProof:
+    parent_parent_coin_id: bytes32
+    parent_inner_puzzle_hash: bytes32
+    parent_amount: int
+    def __init__(
+        self, parent_parent_coin_id: bytes32, parent_inner_puzzle_hash: bytes32, parent_amount: int
+    ) -> None: ...
+    def to_program(self) -> Program: ...
+
+class Keyring:
+    def __init__(self) -> None: ...
+    @staticmethod
+    def open(path: str, passphrase: str) -> Keyring: ...
+    def save(self, path: str, passphrase: str) -> None: ...
+    def add(self, label: str, secret_key: PrivateKey) -> None: ...
+    def remove(self, label: str) -> Optional[PrivateKey]: ...
+    def get(self, label: str) -> Optional[PrivateKey]: ...
+    def get_by_fingerprint(self, fingerprint: int) -> Optional[Tuple[str, PrivateKey]]: ...
+    def labels(self) -> List[str]: ...
+    def __len__(self) -> int: ...
+
+def validate_public_keys(pks: Sequence[G1Element], allow_infinity: bool = False) -> List[bool]: ...
+def aggregate_public_keys(pks: Sequence[G1Element]) -> G1Element: ...
+def aggregate_public_keys_with_pop(pks: Sequence[G1Element], pops: Sequence[G2Element]) -> G1Element: ...
+def pop_prove(sk: PrivateKey) -> G2Element: ...
+def pop_verify(pk: G1Element, proof: G2Element) -> bool: ...
+
+CHIP_0002_SIGN_MESSAGE_PREFIX: str = ...
+def sign_message(
+    sk: PrivateKey, message: bytes, mode: str = "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG:CHIP-0002_"
+) -> G2Element: ...
+def verify_message(
+    pk: G1Element,
+    message: bytes,
+    signature: G2Element,
+    mode: str = "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG:CHIP-0002_",
+) -> bool: ...
+
+class MempoolItem:
+    spend_bundle: SpendBundle
+    spend_bundle_name: bytes32
+    cost: int
+    fee: int
+    additions: List[Coin]
+    removals: List[Coin]
+    spend_flags: List[int]
+    height_added: int
+    fee_per_cost: float
+    def __init__(
+        self, spend_bundle: SpendBundle, conds: SpendBundleConditions, height_added: int
+    ) -> None: ...
+
+class CoinStore:
+    def __init__(self) -> None: ...
+    def __copy__(self) -> CoinStore: ...
+    def __deepcopy__(self, memo: Any) -> CoinStore: ...
+    def __len__(self) -> int: ...
+    def add_coin(self, coin: Coin, height: int, hint: Optional[bytes32] = None) -> bool: ...
+    def spend_coin(self, coin_id: bytes32, height: int) -> bool: ...
+    def coin_state(self, coin_id: bytes32) -> Optional[CoinState]: ...
+    def coin_states(self, coin_ids: Sequence[bytes32]) -> List[CoinState]: ...
+    def coin_states_by_puzzle_hash(
+        self, puzzle_hashes: Sequence[bytes32], include_spent: bool = True
+    ) -> List[CoinState]: ...
+    def coin_states_by_hint(
+        self, hints: Sequence[bytes32], include_spent: bool = True
+    ) -> List[CoinState]: ...
+    def children(self, coin_id: bytes32) -> List[CoinState]: ...
+    def batch_coin_states(
+        self,
+        puzzle_hashes: Sequence[bytes32],
+        min_height: int,
+        filters: CoinStateFilters,
+        max_items: int = 50000,
+    ) -> Tuple[List[CoinState], Optional[int]]: ...
+    def changes_between(self, from_height: int, to_height: int) -> List[CoinState]: ...
+
+def calculate_plot_id_pk(pool_public_key: G1Element, plot_public_key: G1Element) -> bytes32: ...
+def calculate_plot_id_ph(pool_contract_puzzle_hash: bytes32, plot_public_key: G1Element) -> bytes32: ...
+def get_plot_id(pos: ProofOfSpace) -> Optional[bytes32]: ...
+def signage_point_hash(challenge_hash: bytes32, cc_sp_vdf: Optional[VDFInfo] = None) -> bytes32: ...
+def calculate_plot_filter_input(plot_id: bytes32, challenge_hash: bytes32, sp_hash: bytes32) -> bytes32: ...
+def calculate_pos_challenge(plot_id: bytes32, challenge_hash: bytes32, sp_hash: bytes32) -> bytes32: ...
+def passes_plot_filter(prefix_bits: int, plot_id: bytes32, challenge_hash: bytes32, sp_hash: bytes32) -> bool: ...
+def filter_coin_states(
+    states: Sequence[CoinState], filters: CoinStateFilters, min_height: int
+) -> List[CoinState]: ...
+def puzzle_state_responses(
+    puzzle_hashes: Sequence[bytes32],
+    coin_states: Sequence[CoinState],
+    peak_height: int,
+    peak_header_hash: bytes32,
+    header_hashes: Union[Dict[int, bytes32], Callable[[int], Optional[bytes32]]],
+    max_size: int = 104857600,
+) -> List[RespondPuzzleState]: ...
+
+def index_spends(
+    conds: SpendBundleConditions,
+) -> Tuple[Dict[bytes32, List[bytes32]], Dict[bytes32, List[bytes32]]]: ...
+
+class SubscriptionManager:
+    def __init__(self, max_subscriptions: int = 200000) -> None: ...
+    def subscription_count(self, peer: bytes32) -> int: ...
+    def add_puzzle_subscriptions(self, peer: bytes32, puzzle_hashes: Sequence[bytes32]) -> List[bytes32]: ...
+    def add_coin_subscriptions(self, peer: bytes32, coin_ids: Sequence[bytes32]) -> List[bytes32]: ...
+    def remove_puzzle_subscriptions(self, peer: bytes32, puzzle_hashes: Sequence[bytes32]) -> List[bytes32]: ...
+    def remove_coin_subscriptions(self, peer: bytes32, coin_ids: Sequence[bytes32]) -> List[bytes32]: ...
+    def remove_peer(self, peer: bytes32) -> None: ...
+    def puzzle_subscriptions(self, peer: bytes32) -> List[bytes32]: ...
+    def coin_subscriptions(self, peer: bytes32) -> List[bytes32]: ...
+    def updates_for_peak(
+        self,
+        height: int,
+        fork_height: int,
+        peak_hash: bytes32,
+        states: Sequence[CoinState],
+        hints: Dict[bytes32, bytes32],
+    ) -> List[Tuple[bytes32, CoinStateUpdate]]: ...
+
+class RateLimiter:
+    def __init__(
+        self, incoming: bool, reset_seconds: int = 60, percentage_of_limit: int = 100
+    ) -> None: ...
+    def __copy__(self) -> RateLimiter: ...
+    def __deepcopy__(self, memo: Any) -> RateLimiter: ...
+    def process_msg_and_check(self, msg: Message, now: Optional[float] = None) -> bool: ...
+
+class ForkSchedule:
+    def __init__(self, constants: ConsensusConstants) -> None: ...
+    @staticmethod
+    def fork_names() -> List[str]: ...
+    def activation_height(self, fork: str) -> int: ...
+    def is_active(self, fork: str, height: int) -> bool: ...
+    def active_at(self, height: int) -> Set[str]: ...
+    def flags_for_height(self, height: int) -> int: ...
+    def plot_filter_bits(self, height: int) -> int: ...
+
 class AugSchemeMPL:
     @staticmethod
     def sign(pk: PrivateKey, msg: bytes, prepend_pk: Optional[G1Element] = None) -> G2Element: ...
@@ -117,6 +693,150 @@ class MerkleSet:
         leafs: List[bytes32],
     ) -> None: ...
 
+class MerkleBlob:
+    def __init__(
+        self,
+        blob: bytes = b"",
+    ) -> None: ...
+    def insert(self, key: int, value: int, hash: bytes32) -> None: ...
+    def delete(self, key: int) -> None: ...
+    def get_root_hash(self) -> Optional[bytes32]: ...
+    def get_proof_of_inclusion(self, key: int) -> ProofOfInclusion: ...
+    def get_keys_values(self) -> Dict[int, int]: ...
+    def get_blob(self) -> bytes: ...
+    def __len__(self) -> int: ...
+
+class ProofOfInclusionLayer:
+    other_hash_side: int
+    other_hash: bytes32
+    combined_hash: bytes32
+    def __init__(
+        self,
+        other_hash_side: int,
+        other_hash: bytes,
+        combined_hash: bytes
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> ProofOfInclusionLayer: ...
+    def __copy__(self) -> ProofOfInclusionLayer: ...
+    @staticmethod
+    def from_bytes(bytes) -> ProofOfInclusionLayer: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> ProofOfInclusionLayer: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> ProofOfInclusionLayer: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> ProofOfInclusionLayer: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[ProofOfInclusionLayer, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> ProofOfInclusionLayer: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, other_hash_side: Union[ int, _Unspec] = _Unspec(),
+        other_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        combined_hash: Union[ bytes32, _Unspec] = _Unspec()) -> ProofOfInclusionLayer: ...
+
+class ProofOfInclusion:
+    node_hash: bytes32
+    layers: List[ProofOfInclusionLayer]
+    def root_hash(self) -> bytes32: ...
+    def valid(self) -> bool: ...
+    def __init__(
+        self,
+        node_hash: bytes,
+        layers: Sequence[ProofOfInclusionLayer]
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> ProofOfInclusion: ...
+    def __copy__(self) -> ProofOfInclusion: ...
+    @staticmethod
+    def from_bytes(bytes) -> ProofOfInclusion: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> ProofOfInclusion: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> ProofOfInclusion: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> ProofOfInclusion: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[ProofOfInclusion, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> ProofOfInclusion: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, node_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        layers: Union[ List[ProofOfInclusionLayer], _Unspec] = _Unspec()) -> ProofOfInclusion: ...
+
+class PoolInnerPuzzle:
+    launcher_id: bytes32
+    state: int
+    target_puzzle_hash: bytes32
+    p2_singleton_puzzle_hash: bytes32
+    owner_pubkey: G1Element
+    pool_reward_prefix: bytes32
+    escape_puzzle_hash: Optional[bytes32]
+    relative_lock_height: Optional[int]
+    def __init__(
+        self,
+        launcher_id: bytes,
+        state: int,
+        target_puzzle_hash: bytes,
+        p2_singleton_puzzle_hash: bytes,
+        owner_pubkey: G1Element,
+        pool_reward_prefix: bytes,
+        escape_puzzle_hash: Optional[bytes32],
+        relative_lock_height: Optional[int]
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> PoolInnerPuzzle: ...
+    def __copy__(self) -> PoolInnerPuzzle: ...
+    @staticmethod
+    def from_bytes(bytes) -> PoolInnerPuzzle: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PoolInnerPuzzle: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> PoolInnerPuzzle: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> PoolInnerPuzzle: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[PoolInnerPuzzle, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> PoolInnerPuzzle: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, launcher_id: Union[ bytes32, _Unspec] = _Unspec(),
+        state: Union[ int, _Unspec] = _Unspec(),
+        target_puzzle_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        p2_singleton_puzzle_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        owner_pubkey: Union[ G1Element, _Unspec] = _Unspec(),
+        pool_reward_prefix: Union[ bytes32, _Unspec] = _Unspec(),
+        escape_puzzle_hash: Union[ Optional[bytes32], _Unspec] = _Unspec(),
+        relative_lock_height: Union[ Optional[int], _Unspec] = _Unspec()) -> PoolInnerPuzzle: ...
+
 class G1Element:
     SIZE: ClassVar[int] = ...
     def __new__(cls) -> G1Element: ...
@@ -124,6 +844,8 @@ class G1Element:
     def pair(self, other: G2Element) -> GTElement: ...
     @staticmethod
     def generator() -> G1Element: ...
+    def is_valid(self) -> bool: ...
+    def is_inf(self) -> bool: ...
     def __str__(self) -> str: ...
     def __add__(self, other: G1Element) -> G1Element: ...
     def __iadd__(self, other: G1Element) -> G1Element: ...
@@ -133,21 +855,28 @@ class G1Element:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> G1Element: ...
     def __copy__(self) -> G1Element: ...
     @staticmethod
     def from_bytes(bytes) -> G1Element: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> G1Element: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> G1Element: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> G1Element: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[G1Element, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> G1Element: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
 
 class G2Element:
     SIZE: ClassVar[int] = ...
@@ -155,6 +884,8 @@ class G2Element:
     def pair(self, other: G1Element) -> GTElement: ...
     @staticmethod
     def generator() -> G2Element: ...
+    def is_valid(self) -> bool: ...
+    def is_inf(self) -> bool: ...
     def __str__(self) -> str: ...
     def __add__(self, other: G2Element) -> G2Element: ...
     def __iadd__(self, other: G2Element) -> G2Element: ...
@@ -164,21 +895,28 @@ class G2Element:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> G2Element: ...
     def __copy__(self) -> G2Element: ...
     @staticmethod
     def from_bytes(bytes) -> G2Element: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> G2Element: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> G2Element: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> G2Element: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[G2Element, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> G2Element: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
 
 class GTElement:
     SIZE: ClassVar[int] = ...
@@ -191,21 +929,28 @@ class GTElement:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> GTElement: ...
     def __copy__(self) -> GTElement: ...
     @staticmethod
     def from_bytes(bytes) -> GTElement: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> GTElement: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> GTElement: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> GTElement: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[GTElement, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> GTElement: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
 
 class PrivateKey:
     PRIVATE_KEY_SIZE: ClassVar[int] = ...
@@ -218,21 +963,28 @@ class PrivateKey:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> PrivateKey: ...
     def __copy__(self) -> PrivateKey: ...
     @staticmethod
     def from_bytes(bytes) -> PrivateKey: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PrivateKey: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> PrivateKey: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> PrivateKey: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[PrivateKey, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> PrivateKey: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
 
 class Spend:
     coin_id: bytes
@@ -279,21 +1031,28 @@ class Spend:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> Spend: ...
     def __copy__(self) -> Spend: ...
     @staticmethod
     def from_bytes(bytes) -> Spend: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> Spend: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> Spend: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> Spend: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[Spend, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> Spend: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, coin_id: Union[ bytes, _Unspec] = _Unspec(),
         parent_id: Union[ bytes, _Unspec] = _Unspec(),
         puzzle_hash: Union[ bytes, _Unspec] = _Unspec(),
@@ -325,6 +1084,8 @@ class SpendBundleConditions:
     cost: int
     removal_amount: int
     addition_amount: int
+    def to_dot(self) -> str: ...
+    def to_arrays(self) -> Dict[str, bytes]: ...
     def __init__(
         self,
         spends: Sequence[Spend],
@@ -341,21 +1102,28 @@ class SpendBundleConditions:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> SpendBundleConditions: ...
     def __copy__(self) -> SpendBundleConditions: ...
     @staticmethod
     def from_bytes(bytes) -> SpendBundleConditions: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> SpendBundleConditions: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> SpendBundleConditions: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> SpendBundleConditions: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[SpendBundleConditions, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> SpendBundleConditions: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, spends: Union[ List[Spend], _Unspec] = _Unspec(),
         reserve_fee: Union[ int, _Unspec] = _Unspec(),
         height_absolute: Union[ int, _Unspec] = _Unspec(),
@@ -434,21 +1202,28 @@ class BlockRecord:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> BlockRecord: ...
     def __copy__(self) -> BlockRecord: ...
     @staticmethod
     def from_bytes(bytes) -> BlockRecord: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> BlockRecord: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> BlockRecord: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> BlockRecord: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[BlockRecord, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> BlockRecord: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, header_hash: Union[ bytes32, _Unspec] = _Unspec(),
         prev_hash: Union[ bytes32, _Unspec] = _Unspec(),
         height: Union[ uint32, _Unspec] = _Unspec(),
@@ -489,21 +1264,28 @@ class Message:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> Message: ...
     def __copy__(self) -> Message: ...
     @staticmethod
     def from_bytes(bytes) -> Message: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> Message: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> Message: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> Message: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[Message, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> Message: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, msg_type: Union[ int, _Unspec] = _Unspec(),
         id: Union[ Optional[uint16], _Unspec] = _Unspec(),
         data: Union[ bytes, _Unspec] = _Unspec()) -> Message: ...
@@ -515,6 +1297,8 @@ class Handshake:
     server_port: uint16
     node_type: int
     capabilities: List[Tuple[uint16, str]]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         network_id: str,
@@ -527,21 +1311,28 @@ class Handshake:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> Handshake: ...
     def __copy__(self) -> Handshake: ...
     @staticmethod
     def from_bytes(bytes) -> Handshake: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> Handshake: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> Handshake: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> Handshake: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[Handshake, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> Handshake: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, network_id: Union[ str, _Unspec] = _Unspec(),
         protocol_version: Union[ str, _Unspec] = _Unspec(),
         software_version: Union[ str, _Unspec] = _Unspec(),
@@ -564,21 +1355,28 @@ class ClassgroupElement:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> ClassgroupElement: ...
     def __copy__(self) -> ClassgroupElement: ...
     @staticmethod
     def from_bytes(bytes) -> ClassgroupElement: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> ClassgroupElement: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> ClassgroupElement: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> ClassgroupElement: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[ClassgroupElement, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> ClassgroupElement: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, data: Union[ bytes100, _Unspec] = _Unspec()) -> ClassgroupElement: ...
 
 class Coin:
@@ -595,21 +1393,28 @@ class Coin:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> Coin: ...
     def __copy__(self) -> Coin: ...
     @staticmethod
     def from_bytes(bytes) -> Coin: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> Coin: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> Coin: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> Coin: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[Coin, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> Coin: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, parent_coin_info: Union[ bytes32, _Unspec] = _Unspec(),
         puzzle_hash: Union[ bytes32, _Unspec] = _Unspec(),
         amount: Union[ uint64, _Unspec] = _Unspec()) -> Coin: ...
@@ -618,6 +1423,7 @@ class CoinSpend:
     coin: Coin
     puzzle_reveal: Program
     solution: Program
+    def cached_bytes(self) -> bytes: ...
     def __init__(
         self,
         coin: Coin,
@@ -627,21 +1433,28 @@ class CoinSpend:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> CoinSpend: ...
     def __copy__(self) -> CoinSpend: ...
     @staticmethod
     def from_bytes(bytes) -> CoinSpend: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> CoinSpend: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> CoinSpend: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> CoinSpend: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[CoinSpend, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> CoinSpend: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, coin: Union[ Coin, _Unspec] = _Unspec(),
         puzzle_reveal: Union[ Program, _Unspec] = _Unspec(),
         solution: Union[ Program, _Unspec] = _Unspec()) -> CoinSpend: ...
@@ -659,21 +1472,28 @@ class CoinState:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> CoinState: ...
     def __copy__(self) -> CoinState: ...
     @staticmethod
     def from_bytes(bytes) -> CoinState: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> CoinState: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> CoinState: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> CoinState: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[CoinState, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> CoinState: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, coin: Union[ Coin, _Unspec] = _Unspec(),
         spent_height: Union[ Optional[uint32], _Unspec] = _Unspec(),
         created_height: Union[ Optional[uint32], _Unspec] = _Unspec()) -> CoinState: ...
@@ -693,147 +1513,444 @@ class EndOfSubSlotBundle:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> EndOfSubSlotBundle: ...
     def __copy__(self) -> EndOfSubSlotBundle: ...
     @staticmethod
     def from_bytes(bytes) -> EndOfSubSlotBundle: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> EndOfSubSlotBundle: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> EndOfSubSlotBundle: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> EndOfSubSlotBundle: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[EndOfSubSlotBundle, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> EndOfSubSlotBundle: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, challenge_chain: Union[ ChallengeChainSubSlot, _Unspec] = _Unspec(),
         infused_challenge_chain: Union[ Optional[InfusedChallengeChainSubSlot], _Unspec] = _Unspec(),
         reward_chain: Union[ RewardChainSubSlot, _Unspec] = _Unspec(),
         proofs: Union[ SubSlotProofs, _Unspec] = _Unspec()) -> EndOfSubSlotBundle: ...
 
-class FeeRate:
-    mojos_per_clvm_cost: uint64
+class NewSignagePoint:
+    challenge_hash: bytes32
+    challenge_chain_sp: bytes32
+    reward_chain_sp: bytes32
+    difficulty: uint64
+    sub_slot_iters: uint64
+    signage_point_index: uint8
+    peak_height: uint32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
-        mojos_per_clvm_cost: uint64
+        challenge_hash: bytes,
+        challenge_chain_sp: bytes,
+        reward_chain_sp: bytes,
+        difficulty: uint64,
+        sub_slot_iters: uint64,
+        signage_point_index: uint8,
+        peak_height: uint32
     ) -> None: ...
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
-    def __deepcopy__(self) -> FeeRate: ...
-    def __copy__(self) -> FeeRate: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> NewSignagePoint: ...
+    def __copy__(self) -> NewSignagePoint: ...
     @staticmethod
-    def from_bytes(bytes) -> FeeRate: ...
+    def from_bytes(bytes) -> NewSignagePoint: ...
     @staticmethod
-    def from_bytes_unchecked(bytes) -> FeeRate: ...
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> NewSignagePoint: ...
     @staticmethod
-    def parse_rust(ReadableBuffer, bool = False) -> Tuple[FeeRate, int]: ...
+    def from_bytes_strict(bytes) -> NewSignagePoint: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> NewSignagePoint: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[NewSignagePoint, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
-    def from_json_dict(json_dict: Any) -> FeeRate: ...
-    def replace(self, *, mojos_per_clvm_cost: Union[ uint64, _Unspec] = _Unspec()) -> FeeRate: ...
+    def from_json_dict(json_dict: Any) -> NewSignagePoint: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, challenge_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        challenge_chain_sp: Union[ bytes32, _Unspec] = _Unspec(),
+        reward_chain_sp: Union[ bytes32, _Unspec] = _Unspec(),
+        difficulty: Union[ uint64, _Unspec] = _Unspec(),
+        sub_slot_iters: Union[ uint64, _Unspec] = _Unspec(),
+        signage_point_index: Union[ uint8, _Unspec] = _Unspec(),
+        peak_height: Union[ uint32, _Unspec] = _Unspec()) -> NewSignagePoint: ...
 
-class FeeEstimate:
-    error: Optional[str]
-    time_target: uint64
-    estimated_fee_rate: FeeRate
+class DeclareProofOfSpace:
+    challenge_hash: bytes32
+    challenge_chain_sp: bytes32
+    signage_point_index: uint8
+    reward_chain_sp: bytes32
+    proof_of_space: ProofOfSpace
+    challenge_chain_sp_signature: G2Element
+    reward_chain_sp_signature: G2Element
+    farmer_puzzle_hash: bytes32
+    pool_target: Optional[PoolTarget]
+    pool_signature: Optional[G2Element]
+    include_signature_source_data: bool
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
-        error: Optional[str],
-        time_target: uint64,
-        estimated_fee_rate: FeeRate
+        challenge_hash: bytes,
+        challenge_chain_sp: bytes,
+        signage_point_index: uint8,
+        reward_chain_sp: bytes,
+        proof_of_space: ProofOfSpace,
+        challenge_chain_sp_signature: G2Element,
+        reward_chain_sp_signature: G2Element,
+        farmer_puzzle_hash: bytes,
+        pool_target: Optional[PoolTarget],
+        pool_signature: Optional[G2Element],
+        include_signature_source_data: bool
     ) -> None: ...
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
-    def __deepcopy__(self) -> FeeEstimate: ...
-    def __copy__(self) -> FeeEstimate: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> DeclareProofOfSpace: ...
+    def __copy__(self) -> DeclareProofOfSpace: ...
     @staticmethod
-    def from_bytes(bytes) -> FeeEstimate: ...
+    def from_bytes(bytes) -> DeclareProofOfSpace: ...
     @staticmethod
-    def from_bytes_unchecked(bytes) -> FeeEstimate: ...
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> DeclareProofOfSpace: ...
     @staticmethod
-    def parse_rust(ReadableBuffer, bool = False) -> Tuple[FeeEstimate, int]: ...
+    def from_bytes_strict(bytes) -> DeclareProofOfSpace: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> DeclareProofOfSpace: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[DeclareProofOfSpace, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
-    def from_json_dict(json_dict: Any) -> FeeEstimate: ...
-    def replace(self, *, error: Union[ Optional[str], _Unspec] = _Unspec(),
-        time_target: Union[ uint64, _Unspec] = _Unspec(),
-        estimated_fee_rate: Union[ FeeRate, _Unspec] = _Unspec()) -> FeeEstimate: ...
-
-class FeeEstimateGroup:
-    error: Optional[str]
-    estimates: List[FeeEstimate]
+    def from_json_dict(json_dict: Any) -> DeclareProofOfSpace: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, challenge_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        challenge_chain_sp: Union[ bytes32, _Unspec] = _Unspec(),
+        signage_point_index: Union[ uint8, _Unspec] = _Unspec(),
+        reward_chain_sp: Union[ bytes32, _Unspec] = _Unspec(),
+        proof_of_space: Union[ ProofOfSpace, _Unspec] = _Unspec(),
+        challenge_chain_sp_signature: Union[ G2Element, _Unspec] = _Unspec(),
+        reward_chain_sp_signature: Union[ G2Element, _Unspec] = _Unspec(),
+        farmer_puzzle_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        pool_target: Union[ Optional[PoolTarget], _Unspec] = _Unspec(),
+        pool_signature: Union[ Optional[G2Element], _Unspec] = _Unspec(),
+        include_signature_source_data: Union[ bool, _Unspec] = _Unspec()) -> DeclareProofOfSpace: ...
+
+class RequestSignedValues:
+    quality_string: bytes32
+    foliage_block_data_hash: bytes32
+    foliage_transaction_block_hash: bytes32
+    foliage_block_data: Optional[FoliageBlockData]
+    foliage_transaction_block_data: Optional[FoliageTransactionBlock]
+    rc_block_unfinished: Optional[RewardChainBlockUnfinished]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
-        error: Optional[str],
-        estimates: Sequence[FeeEstimate]
+        quality_string: bytes,
+        foliage_block_data_hash: bytes,
+        foliage_transaction_block_hash: bytes,
+        foliage_block_data: Optional[FoliageBlockData],
+        foliage_transaction_block_data: Optional[FoliageTransactionBlock],
+        rc_block_unfinished: Optional[RewardChainBlockUnfinished]
     ) -> None: ...
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
-    def __deepcopy__(self) -> FeeEstimateGroup: ...
-    def __copy__(self) -> FeeEstimateGroup: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> RequestSignedValues: ...
+    def __copy__(self) -> RequestSignedValues: ...
     @staticmethod
-    def from_bytes(bytes) -> FeeEstimateGroup: ...
+    def from_bytes(bytes) -> RequestSignedValues: ...
     @staticmethod
-    def from_bytes_unchecked(bytes) -> FeeEstimateGroup: ...
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestSignedValues: ...
     @staticmethod
-    def parse_rust(ReadableBuffer, bool = False) -> Tuple[FeeEstimateGroup, int]: ...
+    def from_bytes_strict(bytes) -> RequestSignedValues: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> RequestSignedValues: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestSignedValues, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
-    @staticmethod
-    def from_json_dict(json_dict: Any) -> FeeEstimateGroup: ...
-    def replace(self, *, error: Union[ Optional[str], _Unspec] = _Unspec(),
-        estimates: Union[ List[FeeEstimate], _Unspec] = _Unspec()) -> FeeEstimateGroup: ...
-
-class TransactionsInfo:
-    generator_root: bytes32
-    generator_refs_root: bytes32
-    aggregated_signature: G2Element
-    fees: uint64
-    cost: uint64
-    reward_claims_incorporated: List[Coin]
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> RequestSignedValues: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, quality_string: Union[ bytes32, _Unspec] = _Unspec(),
+        foliage_block_data_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        foliage_transaction_block_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        foliage_block_data: Union[ Optional[FoliageBlockData], _Unspec] = _Unspec(),
+        foliage_transaction_block_data: Union[ Optional[FoliageTransactionBlock], _Unspec] = _Unspec(),
+        rc_block_unfinished: Union[ Optional[RewardChainBlockUnfinished], _Unspec] = _Unspec()) -> RequestSignedValues: ...
+
+class FarmingInfo:
+    challenge_hash: bytes32
+    sp_hash: bytes32
+    timestamp: uint64
+    passed: uint32
+    proofs: uint32
+    total_plots: uint32
+    lookup_time: uint64
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
-        generator_root: bytes,
-        generator_refs_root: bytes,
-        aggregated_signature: G2Element,
-        fees: uint64,
-        cost: uint64,
+        challenge_hash: bytes,
+        sp_hash: bytes,
+        timestamp: uint64,
+        passed: uint32,
+        proofs: uint32,
+        total_plots: uint32,
+        lookup_time: uint64
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> FarmingInfo: ...
+    def __copy__(self) -> FarmingInfo: ...
+    @staticmethod
+    def from_bytes(bytes) -> FarmingInfo: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> FarmingInfo: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> FarmingInfo: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> FarmingInfo: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[FarmingInfo, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> FarmingInfo: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, challenge_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        sp_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        timestamp: Union[ uint64, _Unspec] = _Unspec(),
+        passed: Union[ uint32, _Unspec] = _Unspec(),
+        proofs: Union[ uint32, _Unspec] = _Unspec(),
+        total_plots: Union[ uint32, _Unspec] = _Unspec(),
+        lookup_time: Union[ uint64, _Unspec] = _Unspec()) -> FarmingInfo: ...
+
+class SignedValues:
+    quality_string: bytes32
+    foliage_block_data_signature: G2Element
+    foliage_transaction_block_signature: G2Element
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
+    def __init__(
+        self,
+        quality_string: bytes,
+        foliage_block_data_signature: G2Element,
+        foliage_transaction_block_signature: G2Element
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> SignedValues: ...
+    def __copy__(self) -> SignedValues: ...
+    @staticmethod
+    def from_bytes(bytes) -> SignedValues: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> SignedValues: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> SignedValues: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> SignedValues: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[SignedValues, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> SignedValues: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, quality_string: Union[ bytes32, _Unspec] = _Unspec(),
+        foliage_block_data_signature: Union[ G2Element, _Unspec] = _Unspec(),
+        foliage_transaction_block_signature: Union[ G2Element, _Unspec] = _Unspec()) -> SignedValues: ...
+
+class FeeRate:
+    mojos_per_clvm_cost: uint64
+    def __init__(
+        self,
+        mojos_per_clvm_cost: uint64
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> FeeRate: ...
+    def __copy__(self) -> FeeRate: ...
+    @staticmethod
+    def from_bytes(bytes) -> FeeRate: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> FeeRate: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> FeeRate: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> FeeRate: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[FeeRate, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> FeeRate: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, mojos_per_clvm_cost: Union[ uint64, _Unspec] = _Unspec()) -> FeeRate: ...
+
+class FeeEstimate:
+    error: Optional[str]
+    time_target: uint64
+    estimated_fee_rate: FeeRate
+    def __init__(
+        self,
+        error: Optional[str],
+        time_target: uint64,
+        estimated_fee_rate: FeeRate
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> FeeEstimate: ...
+    def __copy__(self) -> FeeEstimate: ...
+    @staticmethod
+    def from_bytes(bytes) -> FeeEstimate: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> FeeEstimate: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> FeeEstimate: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> FeeEstimate: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[FeeEstimate, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> FeeEstimate: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, error: Union[ Optional[str], _Unspec] = _Unspec(),
+        time_target: Union[ uint64, _Unspec] = _Unspec(),
+        estimated_fee_rate: Union[ FeeRate, _Unspec] = _Unspec()) -> FeeEstimate: ...
+
+class FeeEstimateGroup:
+    error: Optional[str]
+    estimates: List[FeeEstimate]
+    def __init__(
+        self,
+        error: Optional[str],
+        estimates: Sequence[FeeEstimate]
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> FeeEstimateGroup: ...
+    def __copy__(self) -> FeeEstimateGroup: ...
+    @staticmethod
+    def from_bytes(bytes) -> FeeEstimateGroup: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> FeeEstimateGroup: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> FeeEstimateGroup: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> FeeEstimateGroup: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[FeeEstimateGroup, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> FeeEstimateGroup: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, error: Union[ Optional[str], _Unspec] = _Unspec(),
+        estimates: Union[ List[FeeEstimate], _Unspec] = _Unspec()) -> FeeEstimateGroup: ...
+
+class TransactionsInfo:
+    generator_root: bytes32
+    generator_refs_root: bytes32
+    aggregated_signature: G2Element
+    fees: uint64
+    cost: uint64
+    reward_claims_incorporated: List[Coin]
+    def __init__(
+        self,
+        generator_root: bytes,
+        generator_refs_root: bytes,
+        aggregated_signature: G2Element,
+        fees: uint64,
+        cost: uint64,
         reward_claims_incorporated: Sequence[Coin]
     ) -> None: ...
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> TransactionsInfo: ...
     def __copy__(self) -> TransactionsInfo: ...
     @staticmethod
     def from_bytes(bytes) -> TransactionsInfo: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> TransactionsInfo: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> TransactionsInfo: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> TransactionsInfo: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[TransactionsInfo, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> TransactionsInfo: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, generator_root: Union[ bytes32, _Unspec] = _Unspec(),
         generator_refs_root: Union[ bytes32, _Unspec] = _Unspec(),
         aggregated_signature: Union[ G2Element, _Unspec] = _Unspec(),
@@ -860,21 +1977,28 @@ class FoliageTransactionBlock:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> FoliageTransactionBlock: ...
     def __copy__(self) -> FoliageTransactionBlock: ...
     @staticmethod
     def from_bytes(bytes) -> FoliageTransactionBlock: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> FoliageTransactionBlock: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> FoliageTransactionBlock: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> FoliageTransactionBlock: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[FoliageTransactionBlock, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> FoliageTransactionBlock: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, prev_transaction_block_hash: Union[ bytes32, _Unspec] = _Unspec(),
         timestamp: Union[ uint64, _Unspec] = _Unspec(),
         filter_hash: Union[ bytes32, _Unspec] = _Unspec(),
@@ -899,21 +2023,28 @@ class FoliageBlockData:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> FoliageBlockData: ...
     def __copy__(self) -> FoliageBlockData: ...
     @staticmethod
     def from_bytes(bytes) -> FoliageBlockData: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> FoliageBlockData: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> FoliageBlockData: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> FoliageBlockData: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[FoliageBlockData, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> FoliageBlockData: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, unfinished_reward_block_hash: Union[ bytes32, _Unspec] = _Unspec(),
         pool_target: Union[ PoolTarget, _Unspec] = _Unspec(),
         pool_signature: Union[ Optional[G2Element], _Unspec] = _Unspec(),
@@ -939,21 +2070,28 @@ class Foliage:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> Foliage: ...
     def __copy__(self) -> Foliage: ...
     @staticmethod
     def from_bytes(bytes) -> Foliage: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> Foliage: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> Foliage: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> Foliage: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[Foliage, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> Foliage: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, prev_block_hash: Union[ bytes32, _Unspec] = _Unspec(),
         reward_block_hash: Union[ bytes32, _Unspec] = _Unspec(),
         foliage_block_data: Union[ FoliageBlockData, _Unspec] = _Unspec(),
@@ -967,6 +2105,8 @@ class NewPeak:
     weight: uint128
     fork_point_with_previous_peak: uint32
     unfinished_reward_block_hash: bytes32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         header_hash: bytes,
@@ -978,21 +2118,28 @@ class NewPeak:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> NewPeak: ...
     def __copy__(self) -> NewPeak: ...
     @staticmethod
     def from_bytes(bytes) -> NewPeak: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> NewPeak: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> NewPeak: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> NewPeak: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[NewPeak, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> NewPeak: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, header_hash: Union[ bytes32, _Unspec] = _Unspec(),
         height: Union[ uint32, _Unspec] = _Unspec(),
         weight: Union[ uint128, _Unspec] = _Unspec(),
@@ -1003,6 +2150,8 @@ class NewTransaction:
     transaction_id: bytes32
     cost: uint64
     fees: uint64
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         transaction_id: bytes,
@@ -1012,27 +2161,36 @@ class NewTransaction:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> NewTransaction: ...
     def __copy__(self) -> NewTransaction: ...
     @staticmethod
     def from_bytes(bytes) -> NewTransaction: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> NewTransaction: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> NewTransaction: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> NewTransaction: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[NewTransaction, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> NewTransaction: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, transaction_id: Union[ bytes32, _Unspec] = _Unspec(),
         cost: Union[ uint64, _Unspec] = _Unspec(),
         fees: Union[ uint64, _Unspec] = _Unspec()) -> NewTransaction: ...
 
 class RequestTransaction:
     transaction_id: bytes32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         transaction_id: bytes
@@ -1040,25 +2198,34 @@ class RequestTransaction:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestTransaction: ...
     def __copy__(self) -> RequestTransaction: ...
     @staticmethod
     def from_bytes(bytes) -> RequestTransaction: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestTransaction: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestTransaction: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestTransaction: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestTransaction, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestTransaction: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, transaction_id: Union[ bytes32, _Unspec] = _Unspec()) -> RequestTransaction: ...
 
 class RespondTransaction:
     transaction: SpendBundle
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         transaction: SpendBundle
@@ -1066,26 +2233,35 @@ class RespondTransaction:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondTransaction: ...
     def __copy__(self) -> RespondTransaction: ...
     @staticmethod
     def from_bytes(bytes) -> RespondTransaction: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondTransaction: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondTransaction: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondTransaction: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondTransaction, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondTransaction: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, transaction: Union[ SpendBundle, _Unspec] = _Unspec()) -> RespondTransaction: ...
 
 class RequestProofOfWeight:
     total_number_of_blocks: uint32
     tip: bytes32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         total_number_of_blocks: uint32,
@@ -1094,27 +2270,36 @@ class RequestProofOfWeight:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestProofOfWeight: ...
     def __copy__(self) -> RequestProofOfWeight: ...
     @staticmethod
     def from_bytes(bytes) -> RequestProofOfWeight: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestProofOfWeight: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestProofOfWeight: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestProofOfWeight: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestProofOfWeight, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestProofOfWeight: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, total_number_of_blocks: Union[ uint32, _Unspec] = _Unspec(),
         tip: Union[ bytes32, _Unspec] = _Unspec()) -> RequestProofOfWeight: ...
 
 class RespondProofOfWeight:
     wp: WeightProof
     tip: bytes32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         wp: WeightProof,
@@ -1123,27 +2308,36 @@ class RespondProofOfWeight:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondProofOfWeight: ...
     def __copy__(self) -> RespondProofOfWeight: ...
     @staticmethod
     def from_bytes(bytes) -> RespondProofOfWeight: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondProofOfWeight: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondProofOfWeight: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondProofOfWeight: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondProofOfWeight, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondProofOfWeight: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, wp: Union[ WeightProof, _Unspec] = _Unspec(),
         tip: Union[ bytes32, _Unspec] = _Unspec()) -> RespondProofOfWeight: ...
 
 class RequestBlock:
     height: uint32
     include_transaction_block: bool
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         height: uint32,
@@ -1152,26 +2346,35 @@ class RequestBlock:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestBlock: ...
     def __copy__(self) -> RequestBlock: ...
     @staticmethod
     def from_bytes(bytes) -> RequestBlock: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestBlock: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestBlock: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestBlock: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestBlock, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestBlock: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, height: Union[ uint32, _Unspec] = _Unspec(),
         include_transaction_block: Union[ bool, _Unspec] = _Unspec()) -> RequestBlock: ...
 
 class RejectBlock:
     height: uint32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         height: uint32
@@ -1179,27 +2382,36 @@ class RejectBlock:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RejectBlock: ...
     def __copy__(self) -> RejectBlock: ...
     @staticmethod
     def from_bytes(bytes) -> RejectBlock: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RejectBlock: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RejectBlock: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RejectBlock: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RejectBlock, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RejectBlock: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, height: Union[ uint32, _Unspec] = _Unspec()) -> RejectBlock: ...
 
 class RequestBlocks:
     start_height: uint32
     end_height: uint32
     include_transaction_block: bool
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         start_height: uint32,
@@ -1209,21 +2421,28 @@ class RequestBlocks:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestBlocks: ...
     def __copy__(self) -> RequestBlocks: ...
     @staticmethod
     def from_bytes(bytes) -> RequestBlocks: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestBlocks: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestBlocks: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestBlocks: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestBlocks, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestBlocks: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, start_height: Union[ uint32, _Unspec] = _Unspec(),
         end_height: Union[ uint32, _Unspec] = _Unspec(),
         include_transaction_block: Union[ bool, _Unspec] = _Unspec()) -> RequestBlocks: ...
@@ -1232,6 +2451,8 @@ class RespondBlocks:
     start_height: uint32
     end_height: uint32
     blocks: List[FullBlock]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         start_height: uint32,
@@ -1241,21 +2462,28 @@ class RespondBlocks:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondBlocks: ...
     def __copy__(self) -> RespondBlocks: ...
     @staticmethod
     def from_bytes(bytes) -> RespondBlocks: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondBlocks: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondBlocks: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondBlocks: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondBlocks, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondBlocks: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, start_height: Union[ uint32, _Unspec] = _Unspec(),
         end_height: Union[ uint32, _Unspec] = _Unspec(),
         blocks: Union[ List[FullBlock], _Unspec] = _Unspec()) -> RespondBlocks: ...
@@ -1263,6 +2491,8 @@ class RespondBlocks:
 class RejectBlocks:
     start_height: uint32
     end_height: uint32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         start_height: uint32,
@@ -1271,26 +2501,35 @@ class RejectBlocks:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RejectBlocks: ...
     def __copy__(self) -> RejectBlocks: ...
     @staticmethod
     def from_bytes(bytes) -> RejectBlocks: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RejectBlocks: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RejectBlocks: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RejectBlocks: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RejectBlocks, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RejectBlocks: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, start_height: Union[ uint32, _Unspec] = _Unspec(),
         end_height: Union[ uint32, _Unspec] = _Unspec()) -> RejectBlocks: ...
 
 class RespondBlock:
     block: FullBlock
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         block: FullBlock
@@ -1298,25 +2537,34 @@ class RespondBlock:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondBlock: ...
     def __copy__(self) -> RespondBlock: ...
     @staticmethod
     def from_bytes(bytes) -> RespondBlock: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondBlock: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondBlock: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondBlock: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondBlock, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondBlock: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, block: Union[ FullBlock, _Unspec] = _Unspec()) -> RespondBlock: ...
 
 class NewUnfinishedBlock:
     unfinished_reward_hash: bytes32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         unfinished_reward_hash: bytes
@@ -1324,25 +2572,34 @@ class NewUnfinishedBlock:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> NewUnfinishedBlock: ...
     def __copy__(self) -> NewUnfinishedBlock: ...
     @staticmethod
     def from_bytes(bytes) -> NewUnfinishedBlock: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> NewUnfinishedBlock: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> NewUnfinishedBlock: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> NewUnfinishedBlock: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[NewUnfinishedBlock, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> NewUnfinishedBlock: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, unfinished_reward_hash: Union[ bytes32, _Unspec] = _Unspec()) -> NewUnfinishedBlock: ...
 
 class RequestUnfinishedBlock:
     unfinished_reward_hash: bytes32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         unfinished_reward_hash: bytes
@@ -1350,25 +2607,34 @@ class RequestUnfinishedBlock:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestUnfinishedBlock: ...
     def __copy__(self) -> RequestUnfinishedBlock: ...
     @staticmethod
     def from_bytes(bytes) -> RequestUnfinishedBlock: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestUnfinishedBlock: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestUnfinishedBlock: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestUnfinishedBlock: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestUnfinishedBlock, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestUnfinishedBlock: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, unfinished_reward_hash: Union[ bytes32, _Unspec] = _Unspec()) -> RequestUnfinishedBlock: ...
 
 class RespondUnfinishedBlock:
     unfinished_block: UnfinishedBlock
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         unfinished_block: UnfinishedBlock
@@ -1376,21 +2642,28 @@ class RespondUnfinishedBlock:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondUnfinishedBlock: ...
     def __copy__(self) -> RespondUnfinishedBlock: ...
     @staticmethod
     def from_bytes(bytes) -> RespondUnfinishedBlock: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondUnfinishedBlock: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondUnfinishedBlock: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondUnfinishedBlock: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondUnfinishedBlock, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondUnfinishedBlock: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, unfinished_block: Union[ UnfinishedBlock, _Unspec] = _Unspec()) -> RespondUnfinishedBlock: ...
 
 class NewSignagePointOrEndOfSubSlot:
@@ -1398,6 +2671,8 @@ class NewSignagePointOrEndOfSubSlot:
     challenge_hash: bytes32
     index_from_challenge: uint8
     last_rc_infusion: bytes32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         prev_challenge_hash: Optional[bytes32],
@@ -1408,21 +2683,28 @@ class NewSignagePointOrEndOfSubSlot:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> NewSignagePointOrEndOfSubSlot: ...
     def __copy__(self) -> NewSignagePointOrEndOfSubSlot: ...
     @staticmethod
     def from_bytes(bytes) -> NewSignagePointOrEndOfSubSlot: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> NewSignagePointOrEndOfSubSlot: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> NewSignagePointOrEndOfSubSlot: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> NewSignagePointOrEndOfSubSlot: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[NewSignagePointOrEndOfSubSlot, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> NewSignagePointOrEndOfSubSlot: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, prev_challenge_hash: Union[ Optional[bytes32], _Unspec] = _Unspec(),
         challenge_hash: Union[ bytes32, _Unspec] = _Unspec(),
         index_from_challenge: Union[ uint8, _Unspec] = _Unspec(),
@@ -1432,6 +2714,8 @@ class RequestSignagePointOrEndOfSubSlot:
     challenge_hash: bytes32
     index_from_challenge: uint8
     last_rc_infusion: bytes32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         challenge_hash: bytes,
@@ -1441,21 +2725,28 @@ class RequestSignagePointOrEndOfSubSlot:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestSignagePointOrEndOfSubSlot: ...
     def __copy__(self) -> RequestSignagePointOrEndOfSubSlot: ...
     @staticmethod
     def from_bytes(bytes) -> RequestSignagePointOrEndOfSubSlot: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestSignagePointOrEndOfSubSlot: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestSignagePointOrEndOfSubSlot: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestSignagePointOrEndOfSubSlot: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestSignagePointOrEndOfSubSlot, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestSignagePointOrEndOfSubSlot: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, challenge_hash: Union[ bytes32, _Unspec] = _Unspec(),
         index_from_challenge: Union[ uint8, _Unspec] = _Unspec(),
         last_rc_infusion: Union[ bytes32, _Unspec] = _Unspec()) -> RequestSignagePointOrEndOfSubSlot: ...
@@ -1466,6 +2757,8 @@ class RespondSignagePoint:
     challenge_chain_proof: VDFProof
     reward_chain_vdf: VDFInfo
     reward_chain_proof: VDFProof
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         index_from_challenge: uint8,
@@ -1477,21 +2770,28 @@ class RespondSignagePoint:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondSignagePoint: ...
     def __copy__(self) -> RespondSignagePoint: ...
     @staticmethod
     def from_bytes(bytes) -> RespondSignagePoint: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondSignagePoint: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondSignagePoint: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondSignagePoint: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondSignagePoint, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondSignagePoint: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, index_from_challenge: Union[ uint8, _Unspec] = _Unspec(),
         challenge_chain_vdf: Union[ VDFInfo, _Unspec] = _Unspec(),
         challenge_chain_proof: Union[ VDFProof, _Unspec] = _Unspec(),
@@ -1500,6 +2800,8 @@ class RespondSignagePoint:
 
 class RespondEndOfSubSlot:
     end_of_slot_bundle: EndOfSubSlotBundle
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         end_of_slot_bundle: EndOfSubSlotBundle
@@ -1507,25 +2809,34 @@ class RespondEndOfSubSlot:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondEndOfSubSlot: ...
     def __copy__(self) -> RespondEndOfSubSlot: ...
     @staticmethod
     def from_bytes(bytes) -> RespondEndOfSubSlot: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondEndOfSubSlot: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondEndOfSubSlot: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondEndOfSubSlot: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondEndOfSubSlot, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondEndOfSubSlot: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, end_of_slot_bundle: Union[ EndOfSubSlotBundle, _Unspec] = _Unspec()) -> RespondEndOfSubSlot: ...
 
 class RequestMempoolTransactions:
     filter: bytes
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         filter: bytes
@@ -1533,21 +2844,28 @@ class RequestMempoolTransactions:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestMempoolTransactions: ...
     def __copy__(self) -> RequestMempoolTransactions: ...
     @staticmethod
     def from_bytes(bytes) -> RequestMempoolTransactions: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestMempoolTransactions: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestMempoolTransactions: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestMempoolTransactions: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestMempoolTransactions, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestMempoolTransactions: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, filter: Union[ bytes, _Unspec] = _Unspec()) -> RequestMempoolTransactions: ...
 
 class NewCompactVDF:
@@ -1555,6 +2873,8 @@ class NewCompactVDF:
     header_hash: bytes32
     field_vdf: uint8
     vdf_info: VDFInfo
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         height: uint32,
@@ -1565,21 +2885,28 @@ class NewCompactVDF:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> NewCompactVDF: ...
     def __copy__(self) -> NewCompactVDF: ...
     @staticmethod
     def from_bytes(bytes) -> NewCompactVDF: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> NewCompactVDF: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> NewCompactVDF: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> NewCompactVDF: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[NewCompactVDF, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> NewCompactVDF: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, height: Union[ uint32, _Unspec] = _Unspec(),
         header_hash: Union[ bytes32, _Unspec] = _Unspec(),
         field_vdf: Union[ uint8, _Unspec] = _Unspec(),
@@ -1590,6 +2917,8 @@ class RequestCompactVDF:
     header_hash: bytes32
     field_vdf: uint8
     vdf_info: VDFInfo
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         height: uint32,
@@ -1600,21 +2929,28 @@ class RequestCompactVDF:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestCompactVDF: ...
     def __copy__(self) -> RequestCompactVDF: ...
     @staticmethod
     def from_bytes(bytes) -> RequestCompactVDF: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestCompactVDF: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestCompactVDF: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestCompactVDF: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestCompactVDF, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestCompactVDF: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, height: Union[ uint32, _Unspec] = _Unspec(),
         header_hash: Union[ bytes32, _Unspec] = _Unspec(),
         field_vdf: Union[ uint8, _Unspec] = _Unspec(),
@@ -1626,6 +2962,8 @@ class RespondCompactVDF:
     field_vdf: uint8
     vdf_info: VDFInfo
     vdf_proof: VDFProof
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         height: uint32,
@@ -1637,21 +2975,28 @@ class RespondCompactVDF:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondCompactVDF: ...
     def __copy__(self) -> RespondCompactVDF: ...
     @staticmethod
     def from_bytes(bytes) -> RespondCompactVDF: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondCompactVDF: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondCompactVDF: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondCompactVDF: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondCompactVDF, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondCompactVDF: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, height: Union[ uint32, _Unspec] = _Unspec(),
         header_hash: Union[ bytes32, _Unspec] = _Unspec(),
         field_vdf: Union[ uint8, _Unspec] = _Unspec(),
@@ -1659,30 +3004,41 @@ class RespondCompactVDF:
         vdf_proof: Union[ VDFProof, _Unspec] = _Unspec()) -> RespondCompactVDF: ...
 
 class RequestPeers:
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self
     ) -> None: ...
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestPeers: ...
     def __copy__(self) -> RequestPeers: ...
     @staticmethod
     def from_bytes(bytes) -> RequestPeers: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestPeers: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestPeers: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestPeers: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestPeers, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestPeers: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
 
 class RespondPeers:
     peer_list: List[TimestampedPeerInfo]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         peer_list: Sequence[TimestampedPeerInfo]
@@ -1690,26 +3046,35 @@ class RespondPeers:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondPeers: ...
     def __copy__(self) -> RespondPeers: ...
     @staticmethod
     def from_bytes(bytes) -> RespondPeers: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondPeers: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondPeers: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondPeers: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondPeers, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondPeers: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, peer_list: Union[ List[TimestampedPeerInfo], _Unspec] = _Unspec()) -> RespondPeers: ...
 
 class NewUnfinishedBlock2:
     unfinished_reward_hash: bytes32
     foliage_hash: Optional[bytes32]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         unfinished_reward_hash: bytes,
@@ -1718,27 +3083,36 @@ class NewUnfinishedBlock2:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> NewUnfinishedBlock2: ...
     def __copy__(self) -> NewUnfinishedBlock2: ...
     @staticmethod
     def from_bytes(bytes) -> NewUnfinishedBlock2: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> NewUnfinishedBlock2: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> NewUnfinishedBlock2: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> NewUnfinishedBlock2: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[NewUnfinishedBlock2, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> NewUnfinishedBlock2: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, unfinished_reward_hash: Union[ bytes32, _Unspec] = _Unspec(),
         foliage_hash: Union[ Optional[bytes32], _Unspec] = _Unspec()) -> NewUnfinishedBlock2: ...
 
 class RequestUnfinishedBlock2:
     unfinished_reward_hash: bytes32
     foliage_hash: Optional[bytes32]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         unfinished_reward_hash: bytes,
@@ -1747,21 +3121,28 @@ class RequestUnfinishedBlock2:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestUnfinishedBlock2: ...
     def __copy__(self) -> RequestUnfinishedBlock2: ...
     @staticmethod
     def from_bytes(bytes) -> RequestUnfinishedBlock2: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestUnfinishedBlock2: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestUnfinishedBlock2: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestUnfinishedBlock2: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestUnfinishedBlock2, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestUnfinishedBlock2: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, unfinished_reward_hash: Union[ bytes32, _Unspec] = _Unspec(),
         foliage_hash: Union[ Optional[bytes32], _Unspec] = _Unspec()) -> RequestUnfinishedBlock2: ...
 
@@ -1786,6 +3167,14 @@ class FullBlock:
     weight: uint128
     def get_included_reward_coins(self) -> List[Coin]: ...
     def is_fully_compactified(self) -> bool: ...
+    def uncompact_proofs(self) -> List[Tuple[int, VDFInfo]]: ...
+    def can_accept_compact_vdf(self, response: RespondCompactVDF) -> bool: ...
+    def to_bytes_compressed(self, level: int = 3) -> bytes: ...
+    @staticmethod
+    def from_bytes_compressed(blob: bytes) -> FullBlock: ...
+    def get_header_block(self, tx_addition_coins: Sequence[Coin], removals_names: Sequence[bytes32]) -> HeaderBlock: ...
+    def get_block_header(self) -> HeaderBlock: ...
+    def cached_bytes(self) -> bytes: ...
     def __init__(
         self,
         finished_sub_slots: Sequence[EndOfSubSlotBundle],
@@ -1804,21 +3193,28 @@ class FullBlock:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> FullBlock: ...
     def __copy__(self) -> FullBlock: ...
     @staticmethod
     def from_bytes(bytes) -> FullBlock: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> FullBlock: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> FullBlock: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> FullBlock: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[FullBlock, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> FullBlock: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, finished_sub_slots: Union[ List[EndOfSubSlotBundle], _Unspec] = _Unspec(),
         reward_chain_block: Union[ RewardChainBlock, _Unspec] = _Unspec(),
         challenge_chain_sp_proof: Union[ Optional[VDFProof], _Unspec] = _Unspec(),
@@ -1832,102 +3228,1536 @@ class FullBlock:
         transactions_generator: Union[ Optional[Program], _Unspec] = _Unspec(),
         transactions_generator_ref_list: Union[ List[uint32], _Unspec] = _Unspec()) -> FullBlock: ...
 
-class HeaderBlock:
-    finished_sub_slots: List[EndOfSubSlotBundle]
-    reward_chain_block: RewardChainBlock
-    challenge_chain_sp_proof: Optional[VDFProof]
-    challenge_chain_ip_proof: VDFProof
-    reward_chain_sp_proof: Optional[VDFProof]
-    reward_chain_ip_proof: VDFProof
-    infused_challenge_chain_ip_proof: Optional[VDFProof]
-    foliage: Foliage
-    foliage_transaction_block: Optional[FoliageTransactionBlock]
-    transactions_filter: bytes
-    transactions_info: Optional[TransactionsInfo]
-    prev_header_hash: bytes32
-    prev_hash: bytes32
-    height: uint32
-    weight: uint128
-    header_hash: bytes32
-    total_iters: uint128
-    log_string: str
-    is_transaction_block: bool
-    first_in_sub_slot: bool
+class PoolDifficulty:
+    difficulty: uint64
+    sub_slot_iters: uint64
+    pool_contract_puzzle_hash: bytes32
     def __init__(
         self,
-        finished_sub_slots: Sequence[EndOfSubSlotBundle],
-        reward_chain_block: RewardChainBlock,
-        challenge_chain_sp_proof: Optional[VDFProof],
-        challenge_chain_ip_proof: VDFProof,
-        reward_chain_sp_proof: Optional[VDFProof],
-        reward_chain_ip_proof: VDFProof,
-        infused_challenge_chain_ip_proof: Optional[VDFProof],
-        foliage: Foliage,
-        foliage_transaction_block: Optional[FoliageTransactionBlock],
-        transactions_filter: bytes,
-        transactions_info: Optional[TransactionsInfo]
+        difficulty: uint64,
+        sub_slot_iters: uint64,
+        pool_contract_puzzle_hash: bytes
     ) -> None: ...
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
-    def __deepcopy__(self) -> HeaderBlock: ...
-    def __copy__(self) -> HeaderBlock: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> PoolDifficulty: ...
+    def __copy__(self) -> PoolDifficulty: ...
     @staticmethod
-    def from_bytes(bytes) -> HeaderBlock: ...
+    def from_bytes(bytes) -> PoolDifficulty: ...
     @staticmethod
-    def from_bytes_unchecked(bytes) -> HeaderBlock: ...
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PoolDifficulty: ...
     @staticmethod
-    def parse_rust(ReadableBuffer, bool = False) -> Tuple[HeaderBlock, int]: ...
+    def from_bytes_strict(bytes) -> PoolDifficulty: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> PoolDifficulty: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[PoolDifficulty, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
-    def from_json_dict(json_dict: Any) -> HeaderBlock: ...
-    def replace(self, *, finished_sub_slots: Union[ List[EndOfSubSlotBundle], _Unspec] = _Unspec(),
-        reward_chain_block: Union[ RewardChainBlock, _Unspec] = _Unspec(),
-        challenge_chain_sp_proof: Union[ Optional[VDFProof], _Unspec] = _Unspec(),
-        challenge_chain_ip_proof: Union[ VDFProof, _Unspec] = _Unspec(),
-        reward_chain_sp_proof: Union[ Optional[VDFProof], _Unspec] = _Unspec(),
-        reward_chain_ip_proof: Union[ VDFProof, _Unspec] = _Unspec(),
-        infused_challenge_chain_ip_proof: Union[ Optional[VDFProof], _Unspec] = _Unspec(),
+    def from_json_dict(json_dict: Any) -> PoolDifficulty: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, difficulty: Union[ uint64, _Unspec] = _Unspec(),
+        sub_slot_iters: Union[ uint64, _Unspec] = _Unspec(),
+        pool_contract_puzzle_hash: Union[ bytes32, _Unspec] = _Unspec()) -> PoolDifficulty: ...
+
+class HarvesterHandshake:
+    farmer_public_keys: List[G1Element]
+    pool_public_keys: List[G1Element]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
+    def __init__(
+        self,
+        farmer_public_keys: Sequence[G1Element],
+        pool_public_keys: Sequence[G1Element]
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> HarvesterHandshake: ...
+    def __copy__(self) -> HarvesterHandshake: ...
+    @staticmethod
+    def from_bytes(bytes) -> HarvesterHandshake: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> HarvesterHandshake: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> HarvesterHandshake: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> HarvesterHandshake: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[HarvesterHandshake, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> HarvesterHandshake: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, farmer_public_keys: Union[ List[G1Element], _Unspec] = _Unspec(),
+        pool_public_keys: Union[ List[G1Element], _Unspec] = _Unspec()) -> HarvesterHandshake: ...
+
+class NewSignagePointHarvester:
+    challenge_hash: bytes32
+    difficulty: uint64
+    sub_slot_iters: uint64
+    signage_point_index: uint8
+    sp_hash: bytes32
+    pool_difficulties: List[PoolDifficulty]
+    filter_prefix_bits: uint8
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
+    def __init__(
+        self,
+        challenge_hash: bytes,
+        difficulty: uint64,
+        sub_slot_iters: uint64,
+        signage_point_index: uint8,
+        sp_hash: bytes,
+        pool_difficulties: Sequence[PoolDifficulty],
+        filter_prefix_bits: uint8
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> NewSignagePointHarvester: ...
+    def __copy__(self) -> NewSignagePointHarvester: ...
+    @staticmethod
+    def from_bytes(bytes) -> NewSignagePointHarvester: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> NewSignagePointHarvester: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> NewSignagePointHarvester: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> NewSignagePointHarvester: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[NewSignagePointHarvester, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> NewSignagePointHarvester: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, challenge_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        difficulty: Union[ uint64, _Unspec] = _Unspec(),
+        sub_slot_iters: Union[ uint64, _Unspec] = _Unspec(),
+        signage_point_index: Union[ uint8, _Unspec] = _Unspec(),
+        sp_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        pool_difficulties: Union[ List[PoolDifficulty], _Unspec] = _Unspec(),
+        filter_prefix_bits: Union[ uint8, _Unspec] = _Unspec()) -> NewSignagePointHarvester: ...
+
+class ProofOfSpaceFeeInfo:
+    applied_fee_threshold: uint32
+    def __init__(
+        self,
+        applied_fee_threshold: uint32
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> ProofOfSpaceFeeInfo: ...
+    def __copy__(self) -> ProofOfSpaceFeeInfo: ...
+    @staticmethod
+    def from_bytes(bytes) -> ProofOfSpaceFeeInfo: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> ProofOfSpaceFeeInfo: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> ProofOfSpaceFeeInfo: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> ProofOfSpaceFeeInfo: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[ProofOfSpaceFeeInfo, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> ProofOfSpaceFeeInfo: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, applied_fee_threshold: Union[ uint32, _Unspec] = _Unspec()) -> ProofOfSpaceFeeInfo: ...
+
+class NewProofOfSpace:
+    challenge_hash: bytes32
+    sp_hash: bytes32
+    plot_identifier: str
+    proof: ProofOfSpace
+    signage_point_index: uint8
+    include_source_signature_data: bool
+    farmer_reward_address_override: Optional[bytes32]
+    fee_info: Optional[ProofOfSpaceFeeInfo]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
+    def __init__(
+        self,
+        challenge_hash: bytes,
+        sp_hash: bytes,
+        plot_identifier: str,
+        proof: ProofOfSpace,
+        signage_point_index: uint8,
+        include_source_signature_data: bool,
+        farmer_reward_address_override: Optional[bytes32],
+        fee_info: Optional[ProofOfSpaceFeeInfo]
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> NewProofOfSpace: ...
+    def __copy__(self) -> NewProofOfSpace: ...
+    @staticmethod
+    def from_bytes(bytes) -> NewProofOfSpace: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> NewProofOfSpace: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> NewProofOfSpace: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> NewProofOfSpace: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[NewProofOfSpace, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> NewProofOfSpace: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, challenge_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        sp_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        plot_identifier: Union[ str, _Unspec] = _Unspec(),
+        proof: Union[ ProofOfSpace, _Unspec] = _Unspec(),
+        signage_point_index: Union[ uint8, _Unspec] = _Unspec(),
+        include_source_signature_data: Union[ bool, _Unspec] = _Unspec(),
+        farmer_reward_address_override: Union[ Optional[bytes32], _Unspec] = _Unspec(),
+        fee_info: Union[ Optional[ProofOfSpaceFeeInfo], _Unspec] = _Unspec()) -> NewProofOfSpace: ...
+
+class SignatureRequestSourceData:
+    kind: int
+    data: bytes
+    def __init__(
+        self,
+        kind: int,
+        data: bytes
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> SignatureRequestSourceData: ...
+    def __copy__(self) -> SignatureRequestSourceData: ...
+    @staticmethod
+    def from_bytes(bytes) -> SignatureRequestSourceData: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> SignatureRequestSourceData: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> SignatureRequestSourceData: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> SignatureRequestSourceData: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[SignatureRequestSourceData, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> SignatureRequestSourceData: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, kind: Union[ int, _Unspec] = _Unspec(),
+        data: Union[ bytes, _Unspec] = _Unspec()) -> SignatureRequestSourceData: ...
+
+class RequestSignatures:
+    plot_identifier: str
+    challenge_hash: bytes32
+    sp_hash: bytes32
+    messages: List[bytes32]
+    message_data: Optional[List[Optional[SignatureRequestSourceData]]]
+    rc_block_unfinished: Optional[RewardChainBlockUnfinished]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
+    def __init__(
+        self,
+        plot_identifier: str,
+        challenge_hash: bytes,
+        sp_hash: bytes,
+        messages: Sequence[bytes32],
+        message_data: Optional[Sequence[Optional[SignatureRequestSourceData]]],
+        rc_block_unfinished: Optional[RewardChainBlockUnfinished]
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> RequestSignatures: ...
+    def __copy__(self) -> RequestSignatures: ...
+    @staticmethod
+    def from_bytes(bytes) -> RequestSignatures: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestSignatures: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestSignatures: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> RequestSignatures: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestSignatures, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> RequestSignatures: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, plot_identifier: Union[ str, _Unspec] = _Unspec(),
+        challenge_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        sp_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        messages: Union[ List[bytes32], _Unspec] = _Unspec(),
+        message_data: Union[ Optional[List[Optional[SignatureRequestSourceData]]], _Unspec] = _Unspec(),
+        rc_block_unfinished: Union[ Optional[RewardChainBlockUnfinished], _Unspec] = _Unspec()) -> RequestSignatures: ...
+
+class RespondSignatures:
+    plot_identifier: str
+    challenge_hash: bytes32
+    sp_hash: bytes32
+    local_pk: G1Element
+    farmer_pk: G1Element
+    message_signatures: List[Tuple[bytes32, G2Element]]
+    include_source_signature_data: bool
+    farmer_reward_address_override: Optional[bytes32]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
+    def __init__(
+        self,
+        plot_identifier: str,
+        challenge_hash: bytes,
+        sp_hash: bytes,
+        local_pk: G1Element,
+        farmer_pk: G1Element,
+        message_signatures: Sequence[Tuple[bytes32, G2Element]],
+        include_source_signature_data: bool,
+        farmer_reward_address_override: Optional[bytes32]
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> RespondSignatures: ...
+    def __copy__(self) -> RespondSignatures: ...
+    @staticmethod
+    def from_bytes(bytes) -> RespondSignatures: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondSignatures: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondSignatures: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> RespondSignatures: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondSignatures, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> RespondSignatures: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, plot_identifier: Union[ str, _Unspec] = _Unspec(),
+        challenge_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        sp_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        local_pk: Union[ G1Element, _Unspec] = _Unspec(),
+        farmer_pk: Union[ G1Element, _Unspec] = _Unspec(),
+        message_signatures: Union[ List[Tuple[bytes32, G2Element]], _Unspec] = _Unspec(),
+        include_source_signature_data: Union[ bool, _Unspec] = _Unspec(),
+        farmer_reward_address_override: Union[ Optional[bytes32], _Unspec] = _Unspec()) -> RespondSignatures: ...
+
+class Plot:
+    filename: str
+    size: uint8
+    plot_id: bytes32
+    pool_public_key: Optional[G1Element]
+    pool_contract_puzzle_hash: Optional[bytes32]
+    plot_public_key: G1Element
+    file_size: uint64
+    time_modified: uint64
+    compression_level: Optional[uint8]
+    def __init__(
+        self,
+        filename: str,
+        size: uint8,
+        plot_id: bytes,
+        pool_public_key: Optional[G1Element],
+        pool_contract_puzzle_hash: Optional[bytes32],
+        plot_public_key: G1Element,
+        file_size: uint64,
+        time_modified: uint64,
+        compression_level: Optional[uint8]
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> Plot: ...
+    def __copy__(self) -> Plot: ...
+    @staticmethod
+    def from_bytes(bytes) -> Plot: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> Plot: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> Plot: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> Plot: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[Plot, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> Plot: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, filename: Union[ str, _Unspec] = _Unspec(),
+        size: Union[ uint8, _Unspec] = _Unspec(),
+        plot_id: Union[ bytes32, _Unspec] = _Unspec(),
+        pool_public_key: Union[ Optional[G1Element], _Unspec] = _Unspec(),
+        pool_contract_puzzle_hash: Union[ Optional[bytes32], _Unspec] = _Unspec(),
+        plot_public_key: Union[ G1Element, _Unspec] = _Unspec(),
+        file_size: Union[ uint64, _Unspec] = _Unspec(),
+        time_modified: Union[ uint64, _Unspec] = _Unspec(),
+        compression_level: Union[ Optional[uint8], _Unspec] = _Unspec()) -> Plot: ...
+
+class RequestPlots:
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
+    def __init__(
+        self
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> RequestPlots: ...
+    def __copy__(self) -> RequestPlots: ...
+    @staticmethod
+    def from_bytes(bytes) -> RequestPlots: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestPlots: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestPlots: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> RequestPlots: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestPlots, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> RequestPlots: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+
+class RespondPlots:
+    plots: List[Plot]
+    failed_to_open_filenames: List[str]
+    no_key_filenames: List[str]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
+    def __init__(
+        self,
+        plots: Sequence[Plot],
+        failed_to_open_filenames: Sequence[str],
+        no_key_filenames: Sequence[str]
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> RespondPlots: ...
+    def __copy__(self) -> RespondPlots: ...
+    @staticmethod
+    def from_bytes(bytes) -> RespondPlots: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondPlots: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondPlots: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> RespondPlots: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondPlots, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> RespondPlots: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, plots: Union[ List[Plot], _Unspec] = _Unspec(),
+        failed_to_open_filenames: Union[ List[str], _Unspec] = _Unspec(),
+        no_key_filenames: Union[ List[str], _Unspec] = _Unspec()) -> RespondPlots: ...
+
+class PlotSyncIdentifier:
+    timestamp: uint64
+    sync_id: uint64
+    message_id: uint64
+    def __init__(
+        self,
+        timestamp: uint64,
+        sync_id: uint64,
+        message_id: uint64
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> PlotSyncIdentifier: ...
+    def __copy__(self) -> PlotSyncIdentifier: ...
+    @staticmethod
+    def from_bytes(bytes) -> PlotSyncIdentifier: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PlotSyncIdentifier: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> PlotSyncIdentifier: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> PlotSyncIdentifier: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[PlotSyncIdentifier, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> PlotSyncIdentifier: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, timestamp: Union[ uint64, _Unspec] = _Unspec(),
+        sync_id: Union[ uint64, _Unspec] = _Unspec(),
+        message_id: Union[ uint64, _Unspec] = _Unspec()) -> PlotSyncIdentifier: ...
+
+class PlotSyncStart:
+    identifier: PlotSyncIdentifier
+    initial: bool
+    last_sync_id: uint64
+    plot_file_count: uint32
+    harvesting_mode: uint8
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
+    def __init__(
+        self,
+        identifier: PlotSyncIdentifier,
+        initial: bool,
+        last_sync_id: uint64,
+        plot_file_count: uint32,
+        harvesting_mode: uint8
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> PlotSyncStart: ...
+    def __copy__(self) -> PlotSyncStart: ...
+    @staticmethod
+    def from_bytes(bytes) -> PlotSyncStart: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PlotSyncStart: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> PlotSyncStart: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> PlotSyncStart: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[PlotSyncStart, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> PlotSyncStart: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, identifier: Union[ PlotSyncIdentifier, _Unspec] = _Unspec(),
+        initial: Union[ bool, _Unspec] = _Unspec(),
+        last_sync_id: Union[ uint64, _Unspec] = _Unspec(),
+        plot_file_count: Union[ uint32, _Unspec] = _Unspec(),
+        harvesting_mode: Union[ uint8, _Unspec] = _Unspec()) -> PlotSyncStart: ...
+
+class PlotSyncPathList:
+    identifier: PlotSyncIdentifier
+    data: List[str]
+    final: bool
+    def __init__(
+        self,
+        identifier: PlotSyncIdentifier,
+        data: Sequence[str],
+        final: bool
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> PlotSyncPathList: ...
+    def __copy__(self) -> PlotSyncPathList: ...
+    @staticmethod
+    def from_bytes(bytes) -> PlotSyncPathList: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PlotSyncPathList: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> PlotSyncPathList: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> PlotSyncPathList: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[PlotSyncPathList, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> PlotSyncPathList: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, identifier: Union[ PlotSyncIdentifier, _Unspec] = _Unspec(),
+        data: Union[ List[str], _Unspec] = _Unspec(),
+        final: Union[ bool, _Unspec] = _Unspec()) -> PlotSyncPathList: ...
+
+class PlotSyncPlotList:
+    identifier: PlotSyncIdentifier
+    data: List[Plot]
+    final: bool
+    def __init__(
+        self,
+        identifier: PlotSyncIdentifier,
+        data: Sequence[Plot],
+        final: bool
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> PlotSyncPlotList: ...
+    def __copy__(self) -> PlotSyncPlotList: ...
+    @staticmethod
+    def from_bytes(bytes) -> PlotSyncPlotList: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PlotSyncPlotList: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> PlotSyncPlotList: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> PlotSyncPlotList: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[PlotSyncPlotList, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> PlotSyncPlotList: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, identifier: Union[ PlotSyncIdentifier, _Unspec] = _Unspec(),
+        data: Union[ List[Plot], _Unspec] = _Unspec(),
+        final: Union[ bool, _Unspec] = _Unspec()) -> PlotSyncPlotList: ...
+
+class PlotSyncDone:
+    identifier: PlotSyncIdentifier
+    duration: uint64
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
+    def __init__(
+        self,
+        identifier: PlotSyncIdentifier,
+        duration: uint64
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> PlotSyncDone: ...
+    def __copy__(self) -> PlotSyncDone: ...
+    @staticmethod
+    def from_bytes(bytes) -> PlotSyncDone: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PlotSyncDone: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> PlotSyncDone: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> PlotSyncDone: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[PlotSyncDone, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> PlotSyncDone: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, identifier: Union[ PlotSyncIdentifier, _Unspec] = _Unspec(),
+        duration: Union[ uint64, _Unspec] = _Unspec()) -> PlotSyncDone: ...
+
+class PlotSyncError:
+    code: int16
+    message: str
+    expected_identifier: Optional[PlotSyncIdentifier]
+    def __init__(
+        self,
+        code: int16,
+        message: str,
+        expected_identifier: Optional[PlotSyncIdentifier]
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> PlotSyncError: ...
+    def __copy__(self) -> PlotSyncError: ...
+    @staticmethod
+    def from_bytes(bytes) -> PlotSyncError: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PlotSyncError: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> PlotSyncError: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> PlotSyncError: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[PlotSyncError, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> PlotSyncError: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, code: Union[ int16, _Unspec] = _Unspec(),
+        message: Union[ str, _Unspec] = _Unspec(),
+        expected_identifier: Union[ Optional[PlotSyncIdentifier], _Unspec] = _Unspec()) -> PlotSyncError: ...
+
+class PlotSyncResponse:
+    identifier: PlotSyncIdentifier
+    message_type: int16
+    error: Optional[PlotSyncError]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
+    def __init__(
+        self,
+        identifier: PlotSyncIdentifier,
+        message_type: int16,
+        error: Optional[PlotSyncError]
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> PlotSyncResponse: ...
+    def __copy__(self) -> PlotSyncResponse: ...
+    @staticmethod
+    def from_bytes(bytes) -> PlotSyncResponse: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PlotSyncResponse: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> PlotSyncResponse: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> PlotSyncResponse: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[PlotSyncResponse, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> PlotSyncResponse: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, identifier: Union[ PlotSyncIdentifier, _Unspec] = _Unspec(),
+        message_type: Union[ int16, _Unspec] = _Unspec(),
+        error: Union[ Optional[PlotSyncError], _Unspec] = _Unspec()) -> PlotSyncResponse: ...
+
+class HeaderBlock:
+    finished_sub_slots: List[EndOfSubSlotBundle]
+    reward_chain_block: RewardChainBlock
+    challenge_chain_sp_proof: Optional[VDFProof]
+    challenge_chain_ip_proof: VDFProof
+    reward_chain_sp_proof: Optional[VDFProof]
+    reward_chain_ip_proof: VDFProof
+    infused_challenge_chain_ip_proof: Optional[VDFProof]
+    foliage: Foliage
+    foliage_transaction_block: Optional[FoliageTransactionBlock]
+    transactions_filter: bytes
+    transactions_info: Optional[TransactionsInfo]
+    def cached_bytes(self) -> bytes: ...
+    prev_header_hash: bytes32
+    prev_hash: bytes32
+    height: uint32
+    weight: uint128
+    header_hash: bytes32
+    total_iters: uint128
+    log_string: str
+    is_transaction_block: bool
+    first_in_sub_slot: bool
+    def __init__(
+        self,
+        finished_sub_slots: Sequence[EndOfSubSlotBundle],
+        reward_chain_block: RewardChainBlock,
+        challenge_chain_sp_proof: Optional[VDFProof],
+        challenge_chain_ip_proof: VDFProof,
+        reward_chain_sp_proof: Optional[VDFProof],
+        reward_chain_ip_proof: VDFProof,
+        infused_challenge_chain_ip_proof: Optional[VDFProof],
+        foliage: Foliage,
+        foliage_transaction_block: Optional[FoliageTransactionBlock],
+        transactions_filter: bytes,
+        transactions_info: Optional[TransactionsInfo]
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> HeaderBlock: ...
+    def __copy__(self) -> HeaderBlock: ...
+    @staticmethod
+    def from_bytes(bytes) -> HeaderBlock: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> HeaderBlock: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> HeaderBlock: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> HeaderBlock: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[HeaderBlock, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> HeaderBlock: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, finished_sub_slots: Union[ List[EndOfSubSlotBundle], _Unspec] = _Unspec(),
+        reward_chain_block: Union[ RewardChainBlock, _Unspec] = _Unspec(),
+        challenge_chain_sp_proof: Union[ Optional[VDFProof], _Unspec] = _Unspec(),
+        challenge_chain_ip_proof: Union[ VDFProof, _Unspec] = _Unspec(),
+        reward_chain_sp_proof: Union[ Optional[VDFProof], _Unspec] = _Unspec(),
+        reward_chain_ip_proof: Union[ VDFProof, _Unspec] = _Unspec(),
+        infused_challenge_chain_ip_proof: Union[ Optional[VDFProof], _Unspec] = _Unspec(),
         foliage: Union[ Foliage, _Unspec] = _Unspec(),
         foliage_transaction_block: Union[ Optional[FoliageTransactionBlock], _Unspec] = _Unspec(),
         transactions_filter: Union[ bytes, _Unspec] = _Unspec(),
         transactions_info: Union[ Optional[TransactionsInfo], _Unspec] = _Unspec()) -> HeaderBlock: ...
 
+class RequestPeersIntroducer:
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
+    def __init__(
+        self
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> RequestPeersIntroducer: ...
+    def __copy__(self) -> RequestPeersIntroducer: ...
+    @staticmethod
+    def from_bytes(bytes) -> RequestPeersIntroducer: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestPeersIntroducer: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestPeersIntroducer: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> RequestPeersIntroducer: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestPeersIntroducer, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> RequestPeersIntroducer: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+
+class RespondPeersIntroducer:
+    peer_list: List[TimestampedPeerInfo]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
+    def __init__(
+        self,
+        peer_list: Sequence[TimestampedPeerInfo]
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> RespondPeersIntroducer: ...
+    def __copy__(self) -> RespondPeersIntroducer: ...
+    @staticmethod
+    def from_bytes(bytes) -> RespondPeersIntroducer: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondPeersIntroducer: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondPeersIntroducer: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> RespondPeersIntroducer: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondPeersIntroducer, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> RespondPeersIntroducer: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, peer_list: Union[ List[TimestampedPeerInfo], _Unspec] = _Unspec()) -> RespondPeersIntroducer: ...
+
 class TimestampedPeerInfo:
     host: str
     port: uint16
     timestamp: uint64
+    @staticmethod
+    def from_address(address: str, timestamp: int) -> TimestampedPeerInfo: ...
+    def address(self) -> str: ...
+    def __init__(
+        self,
+        host: str,
+        port: uint16,
+        timestamp: uint64
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> TimestampedPeerInfo: ...
+    def __copy__(self) -> TimestampedPeerInfo: ...
+    @staticmethod
+    def from_bytes(bytes) -> TimestampedPeerInfo: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> TimestampedPeerInfo: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> TimestampedPeerInfo: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> TimestampedPeerInfo: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[TimestampedPeerInfo, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> TimestampedPeerInfo: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, host: Union[ str, _Unspec] = _Unspec(),
+        port: Union[ uint16, _Unspec] = _Unspec(),
+        timestamp: Union[ uint64, _Unspec] = _Unspec()) -> TimestampedPeerInfo: ...
+
+class AuthenticationPayload:
+    method_name: str
+    launcher_id: bytes32
+    target_puzzle_hash: bytes32
+    authentication_token: uint64
+    def __init__(
+        self,
+        method_name: str,
+        launcher_id: bytes,
+        target_puzzle_hash: bytes,
+        authentication_token: uint64
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> AuthenticationPayload: ...
+    def __copy__(self) -> AuthenticationPayload: ...
+    @staticmethod
+    def from_bytes(bytes) -> AuthenticationPayload: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> AuthenticationPayload: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> AuthenticationPayload: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> AuthenticationPayload: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[AuthenticationPayload, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> AuthenticationPayload: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, method_name: Union[ str, _Unspec] = _Unspec(),
+        launcher_id: Union[ bytes32, _Unspec] = _Unspec(),
+        target_puzzle_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        authentication_token: Union[ uint64, _Unspec] = _Unspec()) -> AuthenticationPayload: ...
+
+class GetPoolInfoResponse:
+    name: str
+    logo_url: str
+    minimum_difficulty: uint64
+    relative_lock_height: uint32
+    protocol_version: uint8
+    fee: str
+    description: str
+    target_puzzle_hash: bytes32
+    authentication_token_timeout: uint8
+    def __init__(
+        self,
+        name: str,
+        logo_url: str,
+        minimum_difficulty: uint64,
+        relative_lock_height: uint32,
+        protocol_version: uint8,
+        fee: str,
+        description: str,
+        target_puzzle_hash: bytes,
+        authentication_token_timeout: uint8
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> GetPoolInfoResponse: ...
+    def __copy__(self) -> GetPoolInfoResponse: ...
+    @staticmethod
+    def from_bytes(bytes) -> GetPoolInfoResponse: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> GetPoolInfoResponse: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> GetPoolInfoResponse: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> GetPoolInfoResponse: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[GetPoolInfoResponse, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> GetPoolInfoResponse: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, name: Union[ str, _Unspec] = _Unspec(),
+        logo_url: Union[ str, _Unspec] = _Unspec(),
+        minimum_difficulty: Union[ uint64, _Unspec] = _Unspec(),
+        relative_lock_height: Union[ uint32, _Unspec] = _Unspec(),
+        protocol_version: Union[ uint8, _Unspec] = _Unspec(),
+        fee: Union[ str, _Unspec] = _Unspec(),
+        description: Union[ str, _Unspec] = _Unspec(),
+        target_puzzle_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        authentication_token_timeout: Union[ uint8, _Unspec] = _Unspec()) -> GetPoolInfoResponse: ...
+
+class PostPartialPayload:
+    launcher_id: bytes32
+    authentication_token: uint64
+    proof_of_space: ProofOfSpace
+    sp_hash: bytes32
+    end_of_sub_slot: bool
+    harvester_id: bytes32
+    def __init__(
+        self,
+        launcher_id: bytes,
+        authentication_token: uint64,
+        proof_of_space: ProofOfSpace,
+        sp_hash: bytes,
+        end_of_sub_slot: bool,
+        harvester_id: bytes
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> PostPartialPayload: ...
+    def __copy__(self) -> PostPartialPayload: ...
+    @staticmethod
+    def from_bytes(bytes) -> PostPartialPayload: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PostPartialPayload: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> PostPartialPayload: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> PostPartialPayload: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[PostPartialPayload, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> PostPartialPayload: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, launcher_id: Union[ bytes32, _Unspec] = _Unspec(),
+        authentication_token: Union[ uint64, _Unspec] = _Unspec(),
+        proof_of_space: Union[ ProofOfSpace, _Unspec] = _Unspec(),
+        sp_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        end_of_sub_slot: Union[ bool, _Unspec] = _Unspec(),
+        harvester_id: Union[ bytes32, _Unspec] = _Unspec()) -> PostPartialPayload: ...
+
+class PostPartialRequest:
+    payload: PostPartialPayload
+    aggregate_signature: G2Element
+    def __init__(
+        self,
+        payload: PostPartialPayload,
+        aggregate_signature: G2Element
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> PostPartialRequest: ...
+    def __copy__(self) -> PostPartialRequest: ...
+    @staticmethod
+    def from_bytes(bytes) -> PostPartialRequest: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PostPartialRequest: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> PostPartialRequest: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> PostPartialRequest: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[PostPartialRequest, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> PostPartialRequest: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, payload: Union[ PostPartialPayload, _Unspec] = _Unspec(),
+        aggregate_signature: Union[ G2Element, _Unspec] = _Unspec()) -> PostPartialRequest: ...
+
+class PostPartialResponse:
+    new_difficulty: uint64
+    def __init__(
+        self,
+        new_difficulty: uint64
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> PostPartialResponse: ...
+    def __copy__(self) -> PostPartialResponse: ...
+    @staticmethod
+    def from_bytes(bytes) -> PostPartialResponse: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PostPartialResponse: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> PostPartialResponse: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> PostPartialResponse: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[PostPartialResponse, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> PostPartialResponse: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, new_difficulty: Union[ uint64, _Unspec] = _Unspec()) -> PostPartialResponse: ...
+
+class GetFarmerResponse:
+    authentication_public_key: G1Element
+    payout_instructions: str
+    current_difficulty: uint64
+    current_points: uint64
+    def __init__(
+        self,
+        authentication_public_key: G1Element,
+        payout_instructions: str,
+        current_difficulty: uint64,
+        current_points: uint64
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> GetFarmerResponse: ...
+    def __copy__(self) -> GetFarmerResponse: ...
+    @staticmethod
+    def from_bytes(bytes) -> GetFarmerResponse: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> GetFarmerResponse: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> GetFarmerResponse: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> GetFarmerResponse: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[GetFarmerResponse, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> GetFarmerResponse: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, authentication_public_key: Union[ G1Element, _Unspec] = _Unspec(),
+        payout_instructions: Union[ str, _Unspec] = _Unspec(),
+        current_difficulty: Union[ uint64, _Unspec] = _Unspec(),
+        current_points: Union[ uint64, _Unspec] = _Unspec()) -> GetFarmerResponse: ...
+
+class PostFarmerPayload:
+    launcher_id: bytes32
+    authentication_token: uint64
+    authentication_public_key: G1Element
+    payout_instructions: str
+    suggested_difficulty: Optional[uint64]
+    def __init__(
+        self,
+        launcher_id: bytes,
+        authentication_token: uint64,
+        authentication_public_key: G1Element,
+        payout_instructions: str,
+        suggested_difficulty: Optional[uint64]
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> PostFarmerPayload: ...
+    def __copy__(self) -> PostFarmerPayload: ...
+    @staticmethod
+    def from_bytes(bytes) -> PostFarmerPayload: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PostFarmerPayload: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> PostFarmerPayload: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> PostFarmerPayload: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[PostFarmerPayload, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> PostFarmerPayload: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, launcher_id: Union[ bytes32, _Unspec] = _Unspec(),
+        authentication_token: Union[ uint64, _Unspec] = _Unspec(),
+        authentication_public_key: Union[ G1Element, _Unspec] = _Unspec(),
+        payout_instructions: Union[ str, _Unspec] = _Unspec(),
+        suggested_difficulty: Union[ Optional[uint64], _Unspec] = _Unspec()) -> PostFarmerPayload: ...
+
+class PostFarmerRequest:
+    payload: PostFarmerPayload
+    signature: G2Element
+    def __init__(
+        self,
+        payload: PostFarmerPayload,
+        signature: G2Element
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> PostFarmerRequest: ...
+    def __copy__(self) -> PostFarmerRequest: ...
+    @staticmethod
+    def from_bytes(bytes) -> PostFarmerRequest: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PostFarmerRequest: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> PostFarmerRequest: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> PostFarmerRequest: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[PostFarmerRequest, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> PostFarmerRequest: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, payload: Union[ PostFarmerPayload, _Unspec] = _Unspec(),
+        signature: Union[ G2Element, _Unspec] = _Unspec()) -> PostFarmerRequest: ...
+
+class PostFarmerResponse:
+    welcome_message: str
+    def __init__(
+        self,
+        welcome_message: str
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> PostFarmerResponse: ...
+    def __copy__(self) -> PostFarmerResponse: ...
+    @staticmethod
+    def from_bytes(bytes) -> PostFarmerResponse: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PostFarmerResponse: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> PostFarmerResponse: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> PostFarmerResponse: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[PostFarmerResponse, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> PostFarmerResponse: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, welcome_message: Union[ str, _Unspec] = _Unspec()) -> PostFarmerResponse: ...
+
+class PutFarmerPayload:
+    launcher_id: bytes32
+    authentication_token: uint64
+    authentication_public_key: Optional[G1Element]
+    payout_instructions: Optional[str]
+    suggested_difficulty: Optional[uint64]
+    def __init__(
+        self,
+        launcher_id: bytes,
+        authentication_token: uint64,
+        authentication_public_key: Optional[G1Element],
+        payout_instructions: Optional[str],
+        suggested_difficulty: Optional[uint64]
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> PutFarmerPayload: ...
+    def __copy__(self) -> PutFarmerPayload: ...
+    @staticmethod
+    def from_bytes(bytes) -> PutFarmerPayload: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PutFarmerPayload: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> PutFarmerPayload: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> PutFarmerPayload: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[PutFarmerPayload, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> PutFarmerPayload: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, launcher_id: Union[ bytes32, _Unspec] = _Unspec(),
+        authentication_token: Union[ uint64, _Unspec] = _Unspec(),
+        authentication_public_key: Union[ Optional[G1Element], _Unspec] = _Unspec(),
+        payout_instructions: Union[ Optional[str], _Unspec] = _Unspec(),
+        suggested_difficulty: Union[ Optional[uint64], _Unspec] = _Unspec()) -> PutFarmerPayload: ...
+
+class PutFarmerRequest:
+    payload: PutFarmerPayload
+    signature: G2Element
+    def __init__(
+        self,
+        payload: PutFarmerPayload,
+        signature: G2Element
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> PutFarmerRequest: ...
+    def __copy__(self) -> PutFarmerRequest: ...
+    @staticmethod
+    def from_bytes(bytes) -> PutFarmerRequest: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PutFarmerRequest: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> PutFarmerRequest: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> PutFarmerRequest: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[PutFarmerRequest, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> PutFarmerRequest: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, payload: Union[ PutFarmerPayload, _Unspec] = _Unspec(),
+        signature: Union[ G2Element, _Unspec] = _Unspec()) -> PutFarmerRequest: ...
+
+class PutFarmerResponse:
+    authentication_public_key: Optional[bool]
+    payout_instructions: Optional[bool]
+    suggested_difficulty: Optional[bool]
+    def __init__(
+        self,
+        authentication_public_key: Optional[bool],
+        payout_instructions: Optional[bool],
+        suggested_difficulty: Optional[bool]
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> PutFarmerResponse: ...
+    def __copy__(self) -> PutFarmerResponse: ...
+    @staticmethod
+    def from_bytes(bytes) -> PutFarmerResponse: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PutFarmerResponse: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> PutFarmerResponse: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> PutFarmerResponse: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[PutFarmerResponse, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> PutFarmerResponse: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, authentication_public_key: Union[ Optional[bool], _Unspec] = _Unspec(),
+        payout_instructions: Union[ Optional[bool], _Unspec] = _Unspec(),
+        suggested_difficulty: Union[ Optional[bool], _Unspec] = _Unspec()) -> PutFarmerResponse: ...
+
+class PoolErrorResponse:
+    error_code: uint16
+    error_message: Optional[str]
+    def __init__(
+        self,
+        error_code: uint16,
+        error_message: Optional[str]
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> PoolErrorResponse: ...
+    def __copy__(self) -> PoolErrorResponse: ...
+    @staticmethod
+    def from_bytes(bytes) -> PoolErrorResponse: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PoolErrorResponse: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> PoolErrorResponse: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> PoolErrorResponse: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[PoolErrorResponse, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> PoolErrorResponse: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, error_code: Union[ uint16, _Unspec] = _Unspec(),
+        error_message: Union[ Optional[str], _Unspec] = _Unspec()) -> PoolErrorResponse: ...
+
+class PoolState:
+    version: uint8
+    state: uint8
+    target_puzzle_hash: bytes32
+    owner_pubkey: G1Element
+    pool_url: Optional[str]
+    relative_lock_height: uint32
     def __init__(
         self,
-        host: str,
-        port: uint16,
-        timestamp: uint64
+        version: uint8,
+        state: uint8,
+        target_puzzle_hash: bytes,
+        owner_pubkey: G1Element,
+        pool_url: Optional[str],
+        relative_lock_height: uint32
     ) -> None: ...
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
-    def __deepcopy__(self) -> TimestampedPeerInfo: ...
-    def __copy__(self) -> TimestampedPeerInfo: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> PoolState: ...
+    def __copy__(self) -> PoolState: ...
     @staticmethod
-    def from_bytes(bytes) -> TimestampedPeerInfo: ...
+    def from_bytes(bytes) -> PoolState: ...
     @staticmethod
-    def from_bytes_unchecked(bytes) -> TimestampedPeerInfo: ...
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PoolState: ...
     @staticmethod
-    def parse_rust(ReadableBuffer, bool = False) -> Tuple[TimestampedPeerInfo, int]: ...
+    def from_bytes_strict(bytes) -> PoolState: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> PoolState: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[PoolState, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
-    def from_json_dict(json_dict: Any) -> TimestampedPeerInfo: ...
-    def replace(self, *, host: Union[ str, _Unspec] = _Unspec(),
-        port: Union[ uint16, _Unspec] = _Unspec(),
-        timestamp: Union[ uint64, _Unspec] = _Unspec()) -> TimestampedPeerInfo: ...
+    def from_json_dict(json_dict: Any) -> PoolState: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, version: Union[ uint8, _Unspec] = _Unspec(),
+        state: Union[ uint8, _Unspec] = _Unspec(),
+        target_puzzle_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        owner_pubkey: Union[ G1Element, _Unspec] = _Unspec(),
+        pool_url: Union[ Optional[str], _Unspec] = _Unspec(),
+        relative_lock_height: Union[ uint32, _Unspec] = _Unspec()) -> PoolState: ...
 
 class PoolTarget:
     puzzle_hash: bytes32
@@ -1940,21 +4770,28 @@ class PoolTarget:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> PoolTarget: ...
     def __copy__(self) -> PoolTarget: ...
     @staticmethod
     def from_bytes(bytes) -> PoolTarget: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PoolTarget: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> PoolTarget: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> PoolTarget: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[PoolTarget, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> PoolTarget: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, puzzle_hash: Union[ bytes32, _Unspec] = _Unspec(),
         max_height: Union[ uint32, _Unspec] = _Unspec()) -> PoolTarget: ...
 
@@ -1972,7 +4809,17 @@ class Program:
     def to(o: object) -> Program: ...
     @staticmethod
     def from_program(p: ChiaProgram) -> Program: ...
+    def run(self, args: object, max_cost: int, flags: int = 0) -> Tuple[int, Program]: ...
+    def to_python(self) -> Any: ...
+    @staticmethod
+    def from_python(o: object) -> Program: ...
+    def first(self) -> LazyNode: ...
+    def rest(self) -> LazyNode: ...
+    def pair(self) -> Optional[Tuple[LazyNode, LazyNode]]: ...
+    def as_int(self) -> int: ...
+    def as_atom_list(self) -> List[bytes]: ...
     def to_program(self) -> ChiaProgram: ...
+    def curry(self, *args: object) -> Program: ...
     def uncurry(self) -> Tuple[ChiaProgram, ChiaProgram]: ...
     def __init__(
         self,
@@ -1981,21 +4828,28 @@ class Program:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> Program: ...
     def __copy__(self) -> Program: ...
     @staticmethod
     def from_bytes(bytes) -> Program: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> Program: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> Program: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> Program: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[Program, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> Program: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, a0: Union[ bytes, _Unspec] = _Unspec()) -> Program: ...
 
 class ProofOfSpace:
@@ -2020,26 +4874,34 @@ class ProofOfSpace:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> ProofOfSpace: ...
     def __copy__(self) -> ProofOfSpace: ...
     @staticmethod
     def from_bytes(bytes) -> ProofOfSpace: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> ProofOfSpace: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> ProofOfSpace: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> ProofOfSpace: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[ProofOfSpace, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> ProofOfSpace: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, challenge: Union[ bytes32, _Unspec] = _Unspec(),
         pool_public_key: Union[ Optional[G1Element], _Unspec] = _Unspec(),
         pool_contract_puzzle_hash: Union[ Optional[bytes32], _Unspec] = _Unspec(),
         local_public_key: Union[ G1Element, _Unspec] = _Unspec(),
         size: Union[ uint8, _Unspec] = _Unspec(),
+        proof: Union[ bytes, _Unspec] = _Unspec(),
         farmer_public_key: Union[ G1Element, _Unspec] = _Unspec()) -> ProofOfSpace: ...
 
 class RewardChainBlockUnfinished:
@@ -2065,21 +4927,28 @@ class RewardChainBlockUnfinished:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RewardChainBlockUnfinished: ...
     def __copy__(self) -> RewardChainBlockUnfinished: ...
     @staticmethod
     def from_bytes(bytes) -> RewardChainBlockUnfinished: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RewardChainBlockUnfinished: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RewardChainBlockUnfinished: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RewardChainBlockUnfinished: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RewardChainBlockUnfinished, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RewardChainBlockUnfinished: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, total_iters: Union[ uint128, _Unspec] = _Unspec(),
         signage_point_index: Union[ uint8, _Unspec] = _Unspec(),
         pos_ss_cc_challenge_hash: Union[ bytes32, _Unspec] = _Unspec(),
@@ -2125,21 +4994,28 @@ class RewardChainBlock:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RewardChainBlock: ...
     def __copy__(self) -> RewardChainBlock: ...
     @staticmethod
     def from_bytes(bytes) -> RewardChainBlock: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RewardChainBlock: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RewardChainBlock: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RewardChainBlock: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RewardChainBlock, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RewardChainBlock: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, weight: Union[ uint128, _Unspec] = _Unspec(),
         height: Union[ uint32, _Unspec] = _Unspec(),
         total_iters: Union[ uint128, _Unspec] = _Unspec(),
@@ -2170,21 +5046,28 @@ class ChallengeBlockInfo:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> ChallengeBlockInfo: ...
     def __copy__(self) -> ChallengeBlockInfo: ...
     @staticmethod
     def from_bytes(bytes) -> ChallengeBlockInfo: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> ChallengeBlockInfo: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> ChallengeBlockInfo: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> ChallengeBlockInfo: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[ChallengeBlockInfo, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> ChallengeBlockInfo: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, proof_of_space: Union[ ProofOfSpace, _Unspec] = _Unspec(),
         challenge_chain_sp_vdf: Union[ Optional[VDFInfo], _Unspec] = _Unspec(),
         challenge_chain_sp_signature: Union[ G2Element, _Unspec] = _Unspec(),
@@ -2207,192 +5090,562 @@ class ChallengeChainSubSlot:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> ChallengeChainSubSlot: ...
     def __copy__(self) -> ChallengeChainSubSlot: ...
     @staticmethod
     def from_bytes(bytes) -> ChallengeChainSubSlot: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> ChallengeChainSubSlot: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> ChallengeChainSubSlot: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> ChallengeChainSubSlot: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[ChallengeChainSubSlot, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> ChallengeChainSubSlot: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, challenge_chain_end_of_slot_vdf: Union[ VDFInfo, _Unspec] = _Unspec(),
         infused_challenge_chain_sub_slot_hash: Union[ Optional[bytes32], _Unspec] = _Unspec(),
         subepoch_summary_hash: Union[ Optional[bytes32], _Unspec] = _Unspec(),
         new_sub_slot_iters: Union[ Optional[uint64], _Unspec] = _Unspec(),
         new_difficulty: Union[ Optional[uint64], _Unspec] = _Unspec()) -> ChallengeChainSubSlot: ...
 
-class InfusedChallengeChainSubSlot:
-    infused_challenge_chain_end_of_slot_vdf: VDFInfo
+class InfusedChallengeChainSubSlot:
+    infused_challenge_chain_end_of_slot_vdf: VDFInfo
+    def __init__(
+        self,
+        infused_challenge_chain_end_of_slot_vdf: VDFInfo
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> InfusedChallengeChainSubSlot: ...
+    def __copy__(self) -> InfusedChallengeChainSubSlot: ...
+    @staticmethod
+    def from_bytes(bytes) -> InfusedChallengeChainSubSlot: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> InfusedChallengeChainSubSlot: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> InfusedChallengeChainSubSlot: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> InfusedChallengeChainSubSlot: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[InfusedChallengeChainSubSlot, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> InfusedChallengeChainSubSlot: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, infused_challenge_chain_end_of_slot_vdf: Union[ VDFInfo, _Unspec] = _Unspec()) -> InfusedChallengeChainSubSlot: ...
+
+class RewardChainSubSlot:
+    end_of_slot_vdf: VDFInfo
+    challenge_chain_sub_slot_hash: bytes32
+    infused_challenge_chain_sub_slot_hash: Optional[bytes32]
+    deficit: uint8
+    def __init__(
+        self,
+        end_of_slot_vdf: VDFInfo,
+        challenge_chain_sub_slot_hash: bytes,
+        infused_challenge_chain_sub_slot_hash: Optional[bytes32],
+        deficit: uint8
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> RewardChainSubSlot: ...
+    def __copy__(self) -> RewardChainSubSlot: ...
+    @staticmethod
+    def from_bytes(bytes) -> RewardChainSubSlot: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RewardChainSubSlot: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RewardChainSubSlot: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> RewardChainSubSlot: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[RewardChainSubSlot, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> RewardChainSubSlot: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, end_of_slot_vdf: Union[ VDFInfo, _Unspec] = _Unspec(),
+        challenge_chain_sub_slot_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        infused_challenge_chain_sub_slot_hash: Union[ Optional[bytes32], _Unspec] = _Unspec(),
+        deficit: Union[ uint8, _Unspec] = _Unspec()) -> RewardChainSubSlot: ...
+
+class SubSlotProofs:
+    challenge_chain_slot_proof: VDFProof
+    infused_challenge_chain_slot_proof: Optional[VDFProof]
+    reward_chain_slot_proof: VDFProof
+    def __init__(
+        self,
+        challenge_chain_slot_proof: VDFProof,
+        infused_challenge_chain_slot_proof: Optional[VDFProof],
+        reward_chain_slot_proof: VDFProof
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> SubSlotProofs: ...
+    def __copy__(self) -> SubSlotProofs: ...
+    @staticmethod
+    def from_bytes(bytes) -> SubSlotProofs: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> SubSlotProofs: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> SubSlotProofs: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> SubSlotProofs: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[SubSlotProofs, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> SubSlotProofs: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, challenge_chain_slot_proof: Union[ VDFProof, _Unspec] = _Unspec(),
+        infused_challenge_chain_slot_proof: Union[ Optional[VDFProof], _Unspec] = _Unspec(),
+        reward_chain_slot_proof: Union[ VDFProof, _Unspec] = _Unspec()) -> SubSlotProofs: ...
+
+class SpendBundle:
+    coin_spends: List[CoinSpend]
+    aggregated_signature: G2Element
+    @staticmethod
+    def aggregate(sbs: List[SpendBundle]) -> SpendBundle: ...
+    def name(self) -> bytes32: ...
+    def removals(self) -> List[Coin]: ...
+    def removals_fingerprint(self) -> int: ...
+    def additions(self) -> List[Coin]: ...
+    def debug(self) -> None: ...
+    def cached_bytes(self) -> bytes: ...
+    def __init__(
+        self,
+        coin_spends: Sequence[CoinSpend],
+        aggregated_signature: G2Element
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> SpendBundle: ...
+    def __copy__(self) -> SpendBundle: ...
+    @staticmethod
+    def from_bytes(bytes) -> SpendBundle: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> SpendBundle: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> SpendBundle: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> SpendBundle: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[SpendBundle, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> SpendBundle: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, coin_spends: Union[ List[CoinSpend], _Unspec] = _Unspec(),
+        aggregated_signature: Union[ G2Element, _Unspec] = _Unspec()) -> SpendBundle: ...
+
+class SubEpochSummary:
+    prev_subepoch_summary_hash: bytes32
+    reward_chain_hash: bytes32
+    num_blocks_overflow: uint8
+    new_difficulty: Optional[uint64]
+    new_sub_slot_iters: Optional[uint64]
+    def __init__(
+        self,
+        prev_subepoch_summary_hash: bytes,
+        reward_chain_hash: bytes,
+        num_blocks_overflow: uint8,
+        new_difficulty: Optional[uint64],
+        new_sub_slot_iters: Optional[uint64]
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> SubEpochSummary: ...
+    def __copy__(self) -> SubEpochSummary: ...
+    @staticmethod
+    def from_bytes(bytes) -> SubEpochSummary: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> SubEpochSummary: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> SubEpochSummary: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> SubEpochSummary: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[SubEpochSummary, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> SubEpochSummary: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, prev_subepoch_summary_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        reward_chain_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        num_blocks_overflow: Union[ uint8, _Unspec] = _Unspec(),
+        new_difficulty: Union[ Optional[uint64], _Unspec] = _Unspec(),
+        new_sub_slot_iters: Union[ Optional[uint64], _Unspec] = _Unspec()) -> SubEpochSummary: ...
+
+class NewPeakTimelord:
+    reward_chain_block: RewardChainBlock
+    difficulty: uint64
+    deficit: uint8
+    sub_slot_iters: uint64
+    sub_epoch_summary: Optional[SubEpochSummary]
+    previous_reward_challenges: List[Tuple[bytes32, uint128]]
+    last_challenge_sb_or_eos_total_iters: uint128
+    passes_ses_height_but_not_yet_included: bool
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
+    def __init__(
+        self,
+        reward_chain_block: RewardChainBlock,
+        difficulty: uint64,
+        deficit: uint8,
+        sub_slot_iters: uint64,
+        sub_epoch_summary: Optional[SubEpochSummary],
+        previous_reward_challenges: Sequence[Tuple[bytes32, uint128]],
+        last_challenge_sb_or_eos_total_iters: uint128,
+        passes_ses_height_but_not_yet_included: bool
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> NewPeakTimelord: ...
+    def __copy__(self) -> NewPeakTimelord: ...
+    @staticmethod
+    def from_bytes(bytes) -> NewPeakTimelord: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> NewPeakTimelord: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> NewPeakTimelord: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> NewPeakTimelord: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[NewPeakTimelord, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> NewPeakTimelord: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, reward_chain_block: Union[ RewardChainBlock, _Unspec] = _Unspec(),
+        difficulty: Union[ uint64, _Unspec] = _Unspec(),
+        deficit: Union[ uint8, _Unspec] = _Unspec(),
+        sub_slot_iters: Union[ uint64, _Unspec] = _Unspec(),
+        sub_epoch_summary: Union[ Optional[SubEpochSummary], _Unspec] = _Unspec(),
+        previous_reward_challenges: Union[ List[Tuple[bytes32, uint128]], _Unspec] = _Unspec(),
+        last_challenge_sb_or_eos_total_iters: Union[ uint128, _Unspec] = _Unspec(),
+        passes_ses_height_but_not_yet_included: Union[ bool, _Unspec] = _Unspec()) -> NewPeakTimelord: ...
+
+class NewUnfinishedBlockTimelord:
+    reward_chain_block: RewardChainBlockUnfinished
+    difficulty: uint64
+    sub_slot_iters: uint64
+    foliage: Foliage
+    sub_epoch_summary: Optional[SubEpochSummary]
+    rc_prev: bytes32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
+    def __init__(
+        self,
+        reward_chain_block: RewardChainBlockUnfinished,
+        difficulty: uint64,
+        sub_slot_iters: uint64,
+        foliage: Foliage,
+        sub_epoch_summary: Optional[SubEpochSummary],
+        rc_prev: bytes
+    ) -> None: ...
+    def __hash__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> NewUnfinishedBlockTimelord: ...
+    def __copy__(self) -> NewUnfinishedBlockTimelord: ...
+    @staticmethod
+    def from_bytes(bytes) -> NewUnfinishedBlockTimelord: ...
+    @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> NewUnfinishedBlockTimelord: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> NewUnfinishedBlockTimelord: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> NewUnfinishedBlockTimelord: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[NewUnfinishedBlockTimelord, int]: ...
+    def to_bytes(self) -> bytes: ...
+    def __bytes__(self) -> bytes: ...
+    def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
+    def get_hash(self) -> bytes32: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
+    @staticmethod
+    def from_json_dict(json_dict: Any) -> NewUnfinishedBlockTimelord: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, reward_chain_block: Union[ RewardChainBlockUnfinished, _Unspec] = _Unspec(),
+        difficulty: Union[ uint64, _Unspec] = _Unspec(),
+        sub_slot_iters: Union[ uint64, _Unspec] = _Unspec(),
+        foliage: Union[ Foliage, _Unspec] = _Unspec(),
+        sub_epoch_summary: Union[ Optional[SubEpochSummary], _Unspec] = _Unspec(),
+        rc_prev: Union[ bytes32, _Unspec] = _Unspec()) -> NewUnfinishedBlockTimelord: ...
+
+class NewInfusionPointVDF:
+    unfinished_reward_hash: bytes32
+    challenge_chain_ip_vdf: VDFInfo
+    challenge_chain_ip_proof: VDFProof
+    reward_chain_ip_vdf: VDFInfo
+    reward_chain_ip_proof: VDFProof
+    infused_challenge_chain_ip_vdf: Optional[VDFInfo]
+    infused_challenge_chain_ip_proof: Optional[VDFProof]
     def __init__(
         self,
-        infused_challenge_chain_end_of_slot_vdf: VDFInfo
+        unfinished_reward_hash: bytes,
+        challenge_chain_ip_vdf: VDFInfo,
+        challenge_chain_ip_proof: VDFProof,
+        reward_chain_ip_vdf: VDFInfo,
+        reward_chain_ip_proof: VDFProof,
+        infused_challenge_chain_ip_vdf: Optional[VDFInfo],
+        infused_challenge_chain_ip_proof: Optional[VDFProof]
     ) -> None: ...
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
-    def __deepcopy__(self) -> InfusedChallengeChainSubSlot: ...
-    def __copy__(self) -> InfusedChallengeChainSubSlot: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> NewInfusionPointVDF: ...
+    def __copy__(self) -> NewInfusionPointVDF: ...
     @staticmethod
-    def from_bytes(bytes) -> InfusedChallengeChainSubSlot: ...
+    def from_bytes(bytes) -> NewInfusionPointVDF: ...
     @staticmethod
-    def from_bytes_unchecked(bytes) -> InfusedChallengeChainSubSlot: ...
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> NewInfusionPointVDF: ...
     @staticmethod
-    def parse_rust(ReadableBuffer, bool = False) -> Tuple[InfusedChallengeChainSubSlot, int]: ...
+    def from_bytes_strict(bytes) -> NewInfusionPointVDF: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> NewInfusionPointVDF: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[NewInfusionPointVDF, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
-    def from_json_dict(json_dict: Any) -> InfusedChallengeChainSubSlot: ...
-    def replace(self, *, infused_challenge_chain_end_of_slot_vdf: Union[ VDFInfo, _Unspec] = _Unspec()) -> InfusedChallengeChainSubSlot: ...
+    def from_json_dict(json_dict: Any) -> NewInfusionPointVDF: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, unfinished_reward_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        challenge_chain_ip_vdf: Union[ VDFInfo, _Unspec] = _Unspec(),
+        challenge_chain_ip_proof: Union[ VDFProof, _Unspec] = _Unspec(),
+        reward_chain_ip_vdf: Union[ VDFInfo, _Unspec] = _Unspec(),
+        reward_chain_ip_proof: Union[ VDFProof, _Unspec] = _Unspec(),
+        infused_challenge_chain_ip_vdf: Union[ Optional[VDFInfo], _Unspec] = _Unspec(),
+        infused_challenge_chain_ip_proof: Union[ Optional[VDFProof], _Unspec] = _Unspec()) -> NewInfusionPointVDF: ...
 
-class RewardChainSubSlot:
-    end_of_slot_vdf: VDFInfo
-    challenge_chain_sub_slot_hash: bytes32
-    infused_challenge_chain_sub_slot_hash: Optional[bytes32]
-    deficit: uint8
+class NewSignagePointVDF:
+    index_from_challenge: uint8
+    challenge_chain_sp_vdf: VDFInfo
+    challenge_chain_sp_proof: VDFProof
+    reward_chain_sp_vdf: VDFInfo
+    reward_chain_sp_proof: VDFProof
     def __init__(
         self,
-        end_of_slot_vdf: VDFInfo,
-        challenge_chain_sub_slot_hash: bytes,
-        infused_challenge_chain_sub_slot_hash: Optional[bytes32],
-        deficit: uint8
+        index_from_challenge: uint8,
+        challenge_chain_sp_vdf: VDFInfo,
+        challenge_chain_sp_proof: VDFProof,
+        reward_chain_sp_vdf: VDFInfo,
+        reward_chain_sp_proof: VDFProof
     ) -> None: ...
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
-    def __deepcopy__(self) -> RewardChainSubSlot: ...
-    def __copy__(self) -> RewardChainSubSlot: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> NewSignagePointVDF: ...
+    def __copy__(self) -> NewSignagePointVDF: ...
     @staticmethod
-    def from_bytes(bytes) -> RewardChainSubSlot: ...
+    def from_bytes(bytes) -> NewSignagePointVDF: ...
     @staticmethod
-    def from_bytes_unchecked(bytes) -> RewardChainSubSlot: ...
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> NewSignagePointVDF: ...
     @staticmethod
-    def parse_rust(ReadableBuffer, bool = False) -> Tuple[RewardChainSubSlot, int]: ...
+    def from_bytes_strict(bytes) -> NewSignagePointVDF: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> NewSignagePointVDF: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[NewSignagePointVDF, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
-    def from_json_dict(json_dict: Any) -> RewardChainSubSlot: ...
-    def replace(self, *, end_of_slot_vdf: Union[ VDFInfo, _Unspec] = _Unspec(),
-        challenge_chain_sub_slot_hash: Union[ bytes32, _Unspec] = _Unspec(),
-        infused_challenge_chain_sub_slot_hash: Union[ Optional[bytes32], _Unspec] = _Unspec(),
-        deficit: Union[ uint8, _Unspec] = _Unspec()) -> RewardChainSubSlot: ...
+    def from_json_dict(json_dict: Any) -> NewSignagePointVDF: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, index_from_challenge: Union[ uint8, _Unspec] = _Unspec(),
+        challenge_chain_sp_vdf: Union[ VDFInfo, _Unspec] = _Unspec(),
+        challenge_chain_sp_proof: Union[ VDFProof, _Unspec] = _Unspec(),
+        reward_chain_sp_vdf: Union[ VDFInfo, _Unspec] = _Unspec(),
+        reward_chain_sp_proof: Union[ VDFProof, _Unspec] = _Unspec()) -> NewSignagePointVDF: ...
 
-class SubSlotProofs:
-    challenge_chain_slot_proof: VDFProof
-    infused_challenge_chain_slot_proof: Optional[VDFProof]
-    reward_chain_slot_proof: VDFProof
+class NewEndOfSubSlotVDF:
+    end_of_sub_slot_bundle: EndOfSubSlotBundle
     def __init__(
         self,
-        challenge_chain_slot_proof: VDFProof,
-        infused_challenge_chain_slot_proof: Optional[VDFProof],
-        reward_chain_slot_proof: VDFProof
+        end_of_sub_slot_bundle: EndOfSubSlotBundle
     ) -> None: ...
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
-    def __deepcopy__(self) -> SubSlotProofs: ...
-    def __copy__(self) -> SubSlotProofs: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> NewEndOfSubSlotVDF: ...
+    def __copy__(self) -> NewEndOfSubSlotVDF: ...
     @staticmethod
-    def from_bytes(bytes) -> SubSlotProofs: ...
+    def from_bytes(bytes) -> NewEndOfSubSlotVDF: ...
     @staticmethod
-    def from_bytes_unchecked(bytes) -> SubSlotProofs: ...
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> NewEndOfSubSlotVDF: ...
     @staticmethod
-    def parse_rust(ReadableBuffer, bool = False) -> Tuple[SubSlotProofs, int]: ...
+    def from_bytes_strict(bytes) -> NewEndOfSubSlotVDF: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> NewEndOfSubSlotVDF: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[NewEndOfSubSlotVDF, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
-    def from_json_dict(json_dict: Any) -> SubSlotProofs: ...
-    def replace(self, *, challenge_chain_slot_proof: Union[ VDFProof, _Unspec] = _Unspec(),
-        infused_challenge_chain_slot_proof: Union[ Optional[VDFProof], _Unspec] = _Unspec(),
-        reward_chain_slot_proof: Union[ VDFProof, _Unspec] = _Unspec()) -> SubSlotProofs: ...
+    def from_json_dict(json_dict: Any) -> NewEndOfSubSlotVDF: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, end_of_sub_slot_bundle: Union[ EndOfSubSlotBundle, _Unspec] = _Unspec()) -> NewEndOfSubSlotVDF: ...
 
-class SpendBundle:
-    coin_spends: List[CoinSpend]
-    aggregated_signature: G2Element
-    @staticmethod
-    def aggregate(sbs: List[SpendBundle]) -> SpendBundle: ...
-    def name(self) -> bytes32: ...
-    def removals(self) -> List[Coin]: ...
-    def additions(self) -> List[Coin]: ...
-    def debug(self) -> None: ...
+class RequestCompactProofOfTime:
+    new_proof_of_time: VDFInfo
+    header_hash: bytes32
+    height: uint32
+    field_vdf: uint8
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
-        coin_spends: Sequence[CoinSpend],
-        aggregated_signature: G2Element
+        new_proof_of_time: VDFInfo,
+        header_hash: bytes,
+        height: uint32,
+        field_vdf: uint8
     ) -> None: ...
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
-    def __deepcopy__(self) -> SpendBundle: ...
-    def __copy__(self) -> SpendBundle: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> RequestCompactProofOfTime: ...
+    def __copy__(self) -> RequestCompactProofOfTime: ...
     @staticmethod
-    def from_bytes(bytes) -> SpendBundle: ...
+    def from_bytes(bytes) -> RequestCompactProofOfTime: ...
     @staticmethod
-    def from_bytes_unchecked(bytes) -> SpendBundle: ...
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestCompactProofOfTime: ...
     @staticmethod
-    def parse_rust(ReadableBuffer, bool = False) -> Tuple[SpendBundle, int]: ...
+    def from_bytes_strict(bytes) -> RequestCompactProofOfTime: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> RequestCompactProofOfTime: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestCompactProofOfTime, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
-    def from_json_dict(json_dict: Any) -> SpendBundle: ...
-    def replace(self, *, coin_spends: Union[ List[CoinSpend], _Unspec] = _Unspec(),
-        aggregated_signature: Union[ G2Element, _Unspec] = _Unspec()) -> SpendBundle: ...
+    def from_json_dict(json_dict: Any) -> RequestCompactProofOfTime: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, new_proof_of_time: Union[ VDFInfo, _Unspec] = _Unspec(),
+        header_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        height: Union[ uint32, _Unspec] = _Unspec(),
+        field_vdf: Union[ uint8, _Unspec] = _Unspec()) -> RequestCompactProofOfTime: ...
 
-class SubEpochSummary:
-    prev_subepoch_summary_hash: bytes32
-    reward_chain_hash: bytes32
-    num_blocks_overflow: uint8
-    new_difficulty: Optional[uint64]
-    new_sub_slot_iters: Optional[uint64]
+class RespondCompactProofOfTime:
+    vdf_info: VDFInfo
+    vdf_proof: VDFProof
+    header_hash: bytes32
+    height: uint32
+    field_vdf: uint8
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
-        prev_subepoch_summary_hash: bytes,
-        reward_chain_hash: bytes,
-        num_blocks_overflow: uint8,
-        new_difficulty: Optional[uint64],
-        new_sub_slot_iters: Optional[uint64]
+        vdf_info: VDFInfo,
+        vdf_proof: VDFProof,
+        header_hash: bytes,
+        height: uint32,
+        field_vdf: uint8
     ) -> None: ...
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
-    def __deepcopy__(self) -> SubEpochSummary: ...
-    def __copy__(self) -> SubEpochSummary: ...
+    def __sizeof__(self) -> int: ...
+    def __deepcopy__(self) -> RespondCompactProofOfTime: ...
+    def __copy__(self) -> RespondCompactProofOfTime: ...
     @staticmethod
-    def from_bytes(bytes) -> SubEpochSummary: ...
+    def from_bytes(bytes) -> RespondCompactProofOfTime: ...
     @staticmethod
-    def from_bytes_unchecked(bytes) -> SubEpochSummary: ...
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondCompactProofOfTime: ...
     @staticmethod
-    def parse_rust(ReadableBuffer, bool = False) -> Tuple[SubEpochSummary, int]: ...
+    def from_bytes_strict(bytes) -> RespondCompactProofOfTime: ...
+    @staticmethod
+    def from_bytes_unchecked(bytes) -> RespondCompactProofOfTime: ...
+    @staticmethod
+    def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondCompactProofOfTime, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
-    def from_json_dict(json_dict: Any) -> SubEpochSummary: ...
-    def replace(self, *, prev_subepoch_summary_hash: Union[ bytes32, _Unspec] = _Unspec(),
-        reward_chain_hash: Union[ bytes32, _Unspec] = _Unspec(),
-        num_blocks_overflow: Union[ uint8, _Unspec] = _Unspec(),
-        new_difficulty: Union[ Optional[uint64], _Unspec] = _Unspec(),
-        new_sub_slot_iters: Union[ Optional[uint64], _Unspec] = _Unspec()) -> SubEpochSummary: ...
+    def from_json_dict(json_dict: Any) -> RespondCompactProofOfTime: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
+    def replace(self, *, vdf_info: Union[ VDFInfo, _Unspec] = _Unspec(),
+        vdf_proof: Union[ VDFProof, _Unspec] = _Unspec(),
+        header_hash: Union[ bytes32, _Unspec] = _Unspec(),
+        height: Union[ uint32, _Unspec] = _Unspec(),
+        field_vdf: Union[ uint8, _Unspec] = _Unspec()) -> RespondCompactProofOfTime: ...
 
 class UnfinishedBlock:
     finished_sub_slots: List[EndOfSubSlotBundle]
@@ -2408,6 +5661,7 @@ class UnfinishedBlock:
     partial_hash: bytes32
     def is_transaction_block(self) -> bool: ...
     total_iters: uint128
+    def cached_bytes(self) -> bytes: ...
     def __init__(
         self,
         finished_sub_slots: Sequence[EndOfSubSlotBundle],
@@ -2423,21 +5677,28 @@ class UnfinishedBlock:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> UnfinishedBlock: ...
     def __copy__(self) -> UnfinishedBlock: ...
     @staticmethod
     def from_bytes(bytes) -> UnfinishedBlock: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> UnfinishedBlock: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> UnfinishedBlock: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> UnfinishedBlock: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[UnfinishedBlock, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> UnfinishedBlock: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, finished_sub_slots: Union[ List[EndOfSubSlotBundle], _Unspec] = _Unspec(),
         reward_chain_block: Union[ RewardChainBlockUnfinished, _Unspec] = _Unspec(),
         challenge_chain_sp_proof: Union[ Optional[VDFProof], _Unspec] = _Unspec(),
@@ -2472,21 +5733,28 @@ class UnfinishedHeaderBlock:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> UnfinishedHeaderBlock: ...
     def __copy__(self) -> UnfinishedHeaderBlock: ...
     @staticmethod
     def from_bytes(bytes) -> UnfinishedHeaderBlock: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> UnfinishedHeaderBlock: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> UnfinishedHeaderBlock: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> UnfinishedHeaderBlock: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[UnfinishedHeaderBlock, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> UnfinishedHeaderBlock: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, finished_sub_slots: Union[ List[EndOfSubSlotBundle], _Unspec] = _Unspec(),
         reward_chain_block: Union[ RewardChainBlockUnfinished, _Unspec] = _Unspec(),
         challenge_chain_sp_proof: Union[ Optional[VDFProof], _Unspec] = _Unspec(),
@@ -2508,21 +5776,28 @@ class VDFInfo:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> VDFInfo: ...
     def __copy__(self) -> VDFInfo: ...
     @staticmethod
     def from_bytes(bytes) -> VDFInfo: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> VDFInfo: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> VDFInfo: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> VDFInfo: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[VDFInfo, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> VDFInfo: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, challenge: Union[ bytes32, _Unspec] = _Unspec(),
         number_of_iterations: Union[ uint64, _Unspec] = _Unspec(),
         output: Union[ ClassgroupElement, _Unspec] = _Unspec()) -> VDFInfo: ...
@@ -2540,21 +5815,28 @@ class VDFProof:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> VDFProof: ...
     def __copy__(self) -> VDFProof: ...
     @staticmethod
     def from_bytes(bytes) -> VDFProof: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> VDFProof: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> VDFProof: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> VDFProof: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[VDFProof, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> VDFProof: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, witness_type: Union[ uint8, _Unspec] = _Unspec(),
         witness: Union[ bytes, _Unspec] = _Unspec(),
         normalized_to_identity: Union[ bool, _Unspec] = _Unspec()) -> VDFProof: ...
@@ -2562,6 +5844,8 @@ class VDFProof:
 class RequestPuzzleSolution:
     coin_name: bytes32
     height: uint32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         coin_name: bytes,
@@ -2570,21 +5854,28 @@ class RequestPuzzleSolution:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestPuzzleSolution: ...
     def __copy__(self) -> RequestPuzzleSolution: ...
     @staticmethod
     def from_bytes(bytes) -> RequestPuzzleSolution: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestPuzzleSolution: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestPuzzleSolution: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestPuzzleSolution: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestPuzzleSolution, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestPuzzleSolution: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, coin_name: Union[ bytes32, _Unspec] = _Unspec(),
         height: Union[ uint32, _Unspec] = _Unspec()) -> RequestPuzzleSolution: ...
 
@@ -2603,21 +5894,28 @@ class PuzzleSolutionResponse:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> PuzzleSolutionResponse: ...
     def __copy__(self) -> PuzzleSolutionResponse: ...
     @staticmethod
     def from_bytes(bytes) -> PuzzleSolutionResponse: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> PuzzleSolutionResponse: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> PuzzleSolutionResponse: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> PuzzleSolutionResponse: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[PuzzleSolutionResponse, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> PuzzleSolutionResponse: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, coin_name: Union[ bytes32, _Unspec] = _Unspec(),
         height: Union[ uint32, _Unspec] = _Unspec(),
         puzzle: Union[ Program, _Unspec] = _Unspec(),
@@ -2625,6 +5923,8 @@ class PuzzleSolutionResponse:
 
 class RespondPuzzleSolution:
     response: PuzzleSolutionResponse
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         response: PuzzleSolutionResponse
@@ -2632,26 +5932,35 @@ class RespondPuzzleSolution:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondPuzzleSolution: ...
     def __copy__(self) -> RespondPuzzleSolution: ...
     @staticmethod
     def from_bytes(bytes) -> RespondPuzzleSolution: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondPuzzleSolution: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondPuzzleSolution: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondPuzzleSolution: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondPuzzleSolution, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondPuzzleSolution: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, response: Union[ PuzzleSolutionResponse, _Unspec] = _Unspec()) -> RespondPuzzleSolution: ...
 
 class RejectPuzzleSolution:
     coin_name: bytes32
     height: uint32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         coin_name: bytes,
@@ -2660,26 +5969,35 @@ class RejectPuzzleSolution:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RejectPuzzleSolution: ...
     def __copy__(self) -> RejectPuzzleSolution: ...
     @staticmethod
     def from_bytes(bytes) -> RejectPuzzleSolution: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RejectPuzzleSolution: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RejectPuzzleSolution: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RejectPuzzleSolution: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RejectPuzzleSolution, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RejectPuzzleSolution: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, coin_name: Union[ bytes32, _Unspec] = _Unspec(),
         height: Union[ uint32, _Unspec] = _Unspec()) -> RejectPuzzleSolution: ...
 
 class SendTransaction:
     transaction: SpendBundle
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         transaction: SpendBundle
@@ -2687,27 +6005,36 @@ class SendTransaction:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> SendTransaction: ...
     def __copy__(self) -> SendTransaction: ...
     @staticmethod
     def from_bytes(bytes) -> SendTransaction: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> SendTransaction: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> SendTransaction: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> SendTransaction: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[SendTransaction, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> SendTransaction: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, transaction: Union[ SpendBundle, _Unspec] = _Unspec()) -> SendTransaction: ...
 
 class TransactionAck:
     txid: bytes32
     status: uint8
     error: Optional[str]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         txid: bytes,
@@ -2717,21 +6044,28 @@ class TransactionAck:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> TransactionAck: ...
     def __copy__(self) -> TransactionAck: ...
     @staticmethod
     def from_bytes(bytes) -> TransactionAck: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> TransactionAck: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> TransactionAck: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> TransactionAck: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[TransactionAck, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> TransactionAck: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, txid: Union[ bytes32, _Unspec] = _Unspec(),
         status: Union[ uint8, _Unspec] = _Unspec(),
         error: Union[ Optional[str], _Unspec] = _Unspec()) -> TransactionAck: ...
@@ -2741,6 +6075,8 @@ class NewPeakWallet:
     height: uint32
     weight: uint128
     fork_point_with_previous_peak: uint32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         header_hash: bytes,
@@ -2751,21 +6087,28 @@ class NewPeakWallet:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> NewPeakWallet: ...
     def __copy__(self) -> NewPeakWallet: ...
     @staticmethod
     def from_bytes(bytes) -> NewPeakWallet: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> NewPeakWallet: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> NewPeakWallet: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> NewPeakWallet: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[NewPeakWallet, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> NewPeakWallet: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, header_hash: Union[ bytes32, _Unspec] = _Unspec(),
         height: Union[ uint32, _Unspec] = _Unspec(),
         weight: Union[ uint128, _Unspec] = _Unspec(),
@@ -2773,6 +6116,8 @@ class NewPeakWallet:
 
 class RequestBlockHeader:
     height: uint32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         height: uint32
@@ -2780,25 +6125,34 @@ class RequestBlockHeader:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestBlockHeader: ...
     def __copy__(self) -> RequestBlockHeader: ...
     @staticmethod
     def from_bytes(bytes) -> RequestBlockHeader: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestBlockHeader: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestBlockHeader: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestBlockHeader: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestBlockHeader, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestBlockHeader: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, height: Union[ uint32, _Unspec] = _Unspec()) -> RequestBlockHeader: ...
 
 class RespondBlockHeader:
     header_block: HeaderBlock
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         header_block: HeaderBlock
@@ -2806,25 +6160,34 @@ class RespondBlockHeader:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondBlockHeader: ...
     def __copy__(self) -> RespondBlockHeader: ...
     @staticmethod
     def from_bytes(bytes) -> RespondBlockHeader: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondBlockHeader: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondBlockHeader: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondBlockHeader: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondBlockHeader, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondBlockHeader: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, header_block: Union[ HeaderBlock, _Unspec] = _Unspec()) -> RespondBlockHeader: ...
 
 class RejectHeaderRequest:
     height: uint32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         height: uint32
@@ -2832,27 +6195,36 @@ class RejectHeaderRequest:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RejectHeaderRequest: ...
     def __copy__(self) -> RejectHeaderRequest: ...
     @staticmethod
     def from_bytes(bytes) -> RejectHeaderRequest: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RejectHeaderRequest: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RejectHeaderRequest: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RejectHeaderRequest: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RejectHeaderRequest, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RejectHeaderRequest: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, height: Union[ uint32, _Unspec] = _Unspec()) -> RejectHeaderRequest: ...
 
 class RequestRemovals:
     height: uint32
     header_hash: bytes32
     coin_names: Optional[List[bytes32]]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         height: uint32,
@@ -2862,21 +6234,28 @@ class RequestRemovals:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestRemovals: ...
     def __copy__(self) -> RequestRemovals: ...
     @staticmethod
     def from_bytes(bytes) -> RequestRemovals: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestRemovals: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestRemovals: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestRemovals: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestRemovals, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestRemovals: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, height: Union[ uint32, _Unspec] = _Unspec(),
         header_hash: Union[ bytes32, _Unspec] = _Unspec(),
         coin_names: Union[ Optional[List[bytes32]], _Unspec] = _Unspec()) -> RequestRemovals: ...
@@ -2886,6 +6265,8 @@ class RespondRemovals:
     header_hash: bytes32
     coins: List[Tuple[bytes32, Optional[Coin]]]
     proofs: Optional[List[Tuple[bytes32, bytes]]]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         height: uint32,
@@ -2896,21 +6277,28 @@ class RespondRemovals:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondRemovals: ...
     def __copy__(self) -> RespondRemovals: ...
     @staticmethod
     def from_bytes(bytes) -> RespondRemovals: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondRemovals: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondRemovals: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondRemovals: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondRemovals, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondRemovals: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, height: Union[ uint32, _Unspec] = _Unspec(),
         header_hash: Union[ bytes32, _Unspec] = _Unspec(),
         coins: Union[ List[Tuple[bytes32, Optional[Coin]]], _Unspec] = _Unspec(),
@@ -2919,6 +6307,8 @@ class RespondRemovals:
 class RejectRemovalsRequest:
     height: uint32
     header_hash: bytes32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         height: uint32,
@@ -2927,21 +6317,28 @@ class RejectRemovalsRequest:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RejectRemovalsRequest: ...
     def __copy__(self) -> RejectRemovalsRequest: ...
     @staticmethod
     def from_bytes(bytes) -> RejectRemovalsRequest: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RejectRemovalsRequest: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RejectRemovalsRequest: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RejectRemovalsRequest: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RejectRemovalsRequest, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RejectRemovalsRequest: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, height: Union[ uint32, _Unspec] = _Unspec(),
         header_hash: Union[ bytes32, _Unspec] = _Unspec()) -> RejectRemovalsRequest: ...
 
@@ -2949,6 +6346,8 @@ class RequestAdditions:
     height: uint32
     header_hash: Optional[bytes32]
     puzzle_hashes: Optional[List[bytes32]]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         height: uint32,
@@ -2958,21 +6357,28 @@ class RequestAdditions:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestAdditions: ...
     def __copy__(self) -> RequestAdditions: ...
     @staticmethod
     def from_bytes(bytes) -> RequestAdditions: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestAdditions: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestAdditions: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestAdditions: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestAdditions, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestAdditions: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, height: Union[ uint32, _Unspec] = _Unspec(),
         header_hash: Union[ Optional[bytes32], _Unspec] = _Unspec(),
         puzzle_hashes: Union[ Optional[List[bytes32]], _Unspec] = _Unspec()) -> RequestAdditions: ...
@@ -2982,6 +6388,8 @@ class RespondAdditions:
     header_hash: bytes32
     coins: List[Tuple[bytes32, List[Coin]]]
     proofs: Optional[List[Tuple[bytes32, bytes, Optional[bytes]]]]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         height: uint32,
@@ -2992,21 +6400,28 @@ class RespondAdditions:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondAdditions: ...
     def __copy__(self) -> RespondAdditions: ...
     @staticmethod
     def from_bytes(bytes) -> RespondAdditions: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondAdditions: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondAdditions: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondAdditions: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondAdditions, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondAdditions: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, height: Union[ uint32, _Unspec] = _Unspec(),
         header_hash: Union[ bytes32, _Unspec] = _Unspec(),
         coins: Union[ List[Tuple[bytes32, List[Coin]]], _Unspec] = _Unspec(),
@@ -3015,6 +6430,8 @@ class RespondAdditions:
 class RejectAdditionsRequest:
     height: uint32
     header_hash: bytes32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         height: uint32,
@@ -3023,21 +6440,28 @@ class RejectAdditionsRequest:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RejectAdditionsRequest: ...
     def __copy__(self) -> RejectAdditionsRequest: ...
     @staticmethod
     def from_bytes(bytes) -> RejectAdditionsRequest: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RejectAdditionsRequest: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RejectAdditionsRequest: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RejectAdditionsRequest: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RejectAdditionsRequest, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RejectAdditionsRequest: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, height: Union[ uint32, _Unspec] = _Unspec(),
         header_hash: Union[ bytes32, _Unspec] = _Unspec()) -> RejectAdditionsRequest: ...
 
@@ -3045,6 +6469,8 @@ class RespondBlockHeaders:
     start_height: uint32
     end_height: uint32
     header_blocks: List[HeaderBlock]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         start_height: uint32,
@@ -3054,21 +6480,28 @@ class RespondBlockHeaders:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondBlockHeaders: ...
     def __copy__(self) -> RespondBlockHeaders: ...
     @staticmethod
     def from_bytes(bytes) -> RespondBlockHeaders: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondBlockHeaders: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondBlockHeaders: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondBlockHeaders: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondBlockHeaders, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondBlockHeaders: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, start_height: Union[ uint32, _Unspec] = _Unspec(),
         end_height: Union[ uint32, _Unspec] = _Unspec(),
         header_blocks: Union[ List[HeaderBlock], _Unspec] = _Unspec()) -> RespondBlockHeaders: ...
@@ -3076,6 +6509,8 @@ class RespondBlockHeaders:
 class RejectBlockHeaders:
     start_height: uint32
     end_height: uint32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         start_height: uint32,
@@ -3084,21 +6519,28 @@ class RejectBlockHeaders:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RejectBlockHeaders: ...
     def __copy__(self) -> RejectBlockHeaders: ...
     @staticmethod
     def from_bytes(bytes) -> RejectBlockHeaders: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RejectBlockHeaders: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RejectBlockHeaders: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RejectBlockHeaders: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RejectBlockHeaders, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RejectBlockHeaders: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, start_height: Union[ uint32, _Unspec] = _Unspec(),
         end_height: Union[ uint32, _Unspec] = _Unspec()) -> RejectBlockHeaders: ...
 
@@ -3106,6 +6548,8 @@ class RequestBlockHeaders:
     start_height: uint32
     end_height: uint32
     return_filter: bool
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         start_height: uint32,
@@ -3115,21 +6559,28 @@ class RequestBlockHeaders:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestBlockHeaders: ...
     def __copy__(self) -> RequestBlockHeaders: ...
     @staticmethod
     def from_bytes(bytes) -> RequestBlockHeaders: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestBlockHeaders: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestBlockHeaders: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestBlockHeaders: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestBlockHeaders, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestBlockHeaders: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, start_height: Union[ uint32, _Unspec] = _Unspec(),
         end_height: Union[ uint32, _Unspec] = _Unspec(),
         return_filter: Union[ bool, _Unspec] = _Unspec()) -> RequestBlockHeaders: ...
@@ -3137,6 +6588,8 @@ class RequestBlockHeaders:
 class RequestHeaderBlocks:
     start_height: uint32
     end_height: uint32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         start_height: uint32,
@@ -3145,27 +6598,36 @@ class RequestHeaderBlocks:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestHeaderBlocks: ...
     def __copy__(self) -> RequestHeaderBlocks: ...
     @staticmethod
     def from_bytes(bytes) -> RequestHeaderBlocks: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestHeaderBlocks: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestHeaderBlocks: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestHeaderBlocks: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestHeaderBlocks, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestHeaderBlocks: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, start_height: Union[ uint32, _Unspec] = _Unspec(),
         end_height: Union[ uint32, _Unspec] = _Unspec()) -> RequestHeaderBlocks: ...
 
 class RejectHeaderBlocks:
     start_height: uint32
     end_height: uint32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         start_height: uint32,
@@ -3174,21 +6636,28 @@ class RejectHeaderBlocks:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RejectHeaderBlocks: ...
     def __copy__(self) -> RejectHeaderBlocks: ...
     @staticmethod
     def from_bytes(bytes) -> RejectHeaderBlocks: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RejectHeaderBlocks: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RejectHeaderBlocks: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RejectHeaderBlocks: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RejectHeaderBlocks, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RejectHeaderBlocks: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, start_height: Union[ uint32, _Unspec] = _Unspec(),
         end_height: Union[ uint32, _Unspec] = _Unspec()) -> RejectHeaderBlocks: ...
 
@@ -3196,6 +6665,8 @@ class RespondHeaderBlocks:
     start_height: uint32
     end_height: uint32
     header_blocks: List[HeaderBlock]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         start_height: uint32,
@@ -3205,21 +6676,28 @@ class RespondHeaderBlocks:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondHeaderBlocks: ...
     def __copy__(self) -> RespondHeaderBlocks: ...
     @staticmethod
     def from_bytes(bytes) -> RespondHeaderBlocks: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondHeaderBlocks: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondHeaderBlocks: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondHeaderBlocks: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondHeaderBlocks, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondHeaderBlocks: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, start_height: Union[ uint32, _Unspec] = _Unspec(),
         end_height: Union[ uint32, _Unspec] = _Unspec(),
         header_blocks: Union[ List[HeaderBlock], _Unspec] = _Unspec()) -> RespondHeaderBlocks: ...
@@ -3227,6 +6705,8 @@ class RespondHeaderBlocks:
 class RegisterForPhUpdates:
     puzzle_hashes: List[bytes32]
     min_height: uint32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         puzzle_hashes: Sequence[bytes32],
@@ -3235,21 +6715,28 @@ class RegisterForPhUpdates:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RegisterForPhUpdates: ...
     def __copy__(self) -> RegisterForPhUpdates: ...
     @staticmethod
     def from_bytes(bytes) -> RegisterForPhUpdates: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RegisterForPhUpdates: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RegisterForPhUpdates: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RegisterForPhUpdates: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RegisterForPhUpdates, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RegisterForPhUpdates: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, puzzle_hashes: Union[ List[bytes32], _Unspec] = _Unspec(),
         min_height: Union[ uint32, _Unspec] = _Unspec()) -> RegisterForPhUpdates: ...
 
@@ -3257,6 +6744,8 @@ class RespondToPhUpdates:
     puzzle_hashes: List[bytes32]
     min_height: uint32
     coin_states: List[CoinState]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         puzzle_hashes: Sequence[bytes32],
@@ -3266,21 +6755,28 @@ class RespondToPhUpdates:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondToPhUpdates: ...
     def __copy__(self) -> RespondToPhUpdates: ...
     @staticmethod
     def from_bytes(bytes) -> RespondToPhUpdates: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondToPhUpdates: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondToPhUpdates: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondToPhUpdates: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondToPhUpdates, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondToPhUpdates: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, puzzle_hashes: Union[ List[bytes32], _Unspec] = _Unspec(),
         min_height: Union[ uint32, _Unspec] = _Unspec(),
         coin_states: Union[ List[CoinState], _Unspec] = _Unspec()) -> RespondToPhUpdates: ...
@@ -3288,6 +6784,8 @@ class RespondToPhUpdates:
 class RegisterForCoinUpdates:
     coin_ids: List[bytes32]
     min_height: uint32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         coin_ids: Sequence[bytes32],
@@ -3296,21 +6794,28 @@ class RegisterForCoinUpdates:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RegisterForCoinUpdates: ...
     def __copy__(self) -> RegisterForCoinUpdates: ...
     @staticmethod
     def from_bytes(bytes) -> RegisterForCoinUpdates: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RegisterForCoinUpdates: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RegisterForCoinUpdates: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RegisterForCoinUpdates: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RegisterForCoinUpdates, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RegisterForCoinUpdates: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, coin_ids: Union[ List[bytes32], _Unspec] = _Unspec(),
         min_height: Union[ uint32, _Unspec] = _Unspec()) -> RegisterForCoinUpdates: ...
 
@@ -3318,6 +6823,8 @@ class RespondToCoinUpdates:
     coin_ids: List[bytes32]
     min_height: uint32
     coin_states: List[CoinState]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         coin_ids: Sequence[bytes32],
@@ -3327,21 +6834,28 @@ class RespondToCoinUpdates:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondToCoinUpdates: ...
     def __copy__(self) -> RespondToCoinUpdates: ...
     @staticmethod
     def from_bytes(bytes) -> RespondToCoinUpdates: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondToCoinUpdates: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondToCoinUpdates: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondToCoinUpdates: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondToCoinUpdates, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondToCoinUpdates: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, coin_ids: Union[ List[bytes32], _Unspec] = _Unspec(),
         min_height: Union[ uint32, _Unspec] = _Unspec(),
         coin_states: Union[ List[CoinState], _Unspec] = _Unspec()) -> RespondToCoinUpdates: ...
@@ -3351,6 +6865,8 @@ class CoinStateUpdate:
     fork_height: uint32
     peak_hash: bytes32
     items: List[CoinState]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         height: uint32,
@@ -3361,21 +6877,28 @@ class CoinStateUpdate:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> CoinStateUpdate: ...
     def __copy__(self) -> CoinStateUpdate: ...
     @staticmethod
     def from_bytes(bytes) -> CoinStateUpdate: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> CoinStateUpdate: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> CoinStateUpdate: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> CoinStateUpdate: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[CoinStateUpdate, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> CoinStateUpdate: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, height: Union[ uint32, _Unspec] = _Unspec(),
         fork_height: Union[ uint32, _Unspec] = _Unspec(),
         peak_hash: Union[ bytes32, _Unspec] = _Unspec(),
@@ -3383,6 +6906,8 @@ class CoinStateUpdate:
 
 class RequestChildren:
     coin_name: bytes32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         coin_name: bytes
@@ -3390,25 +6915,34 @@ class RequestChildren:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestChildren: ...
     def __copy__(self) -> RequestChildren: ...
     @staticmethod
     def from_bytes(bytes) -> RequestChildren: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestChildren: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestChildren: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestChildren: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestChildren, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestChildren: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, coin_name: Union[ bytes32, _Unspec] = _Unspec()) -> RequestChildren: ...
 
 class RespondChildren:
     coin_states: List[CoinState]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         coin_states: Sequence[CoinState]
@@ -3416,26 +6950,35 @@ class RespondChildren:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondChildren: ...
     def __copy__(self) -> RespondChildren: ...
     @staticmethod
     def from_bytes(bytes) -> RespondChildren: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondChildren: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondChildren: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondChildren: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondChildren, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondChildren: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, coin_states: Union[ List[CoinState], _Unspec] = _Unspec()) -> RespondChildren: ...
 
 class RequestSesInfo:
     start_height: uint32
     end_height: uint32
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         start_height: uint32,
@@ -3444,27 +6987,36 @@ class RequestSesInfo:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestSesInfo: ...
     def __copy__(self) -> RequestSesInfo: ...
     @staticmethod
     def from_bytes(bytes) -> RequestSesInfo: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestSesInfo: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestSesInfo: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestSesInfo: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestSesInfo, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestSesInfo: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, start_height: Union[ uint32, _Unspec] = _Unspec(),
         end_height: Union[ uint32, _Unspec] = _Unspec()) -> RequestSesInfo: ...
 
 class RespondSesInfo:
     reward_chain_hash: List[bytes32]
     heights: List[List[uint32]]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         reward_chain_hash: Sequence[bytes32],
@@ -3473,26 +7025,35 @@ class RespondSesInfo:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondSesInfo: ...
     def __copy__(self) -> RespondSesInfo: ...
     @staticmethod
     def from_bytes(bytes) -> RespondSesInfo: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondSesInfo: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondSesInfo: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondSesInfo: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondSesInfo, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondSesInfo: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, reward_chain_hash: Union[ List[bytes32], _Unspec] = _Unspec(),
         heights: Union[ List[List[uint32]], _Unspec] = _Unspec()) -> RespondSesInfo: ...
 
 class RequestFeeEstimates:
     time_targets: List[uint64]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         time_targets: Sequence[uint64]
@@ -3500,25 +7061,34 @@ class RequestFeeEstimates:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestFeeEstimates: ...
     def __copy__(self) -> RequestFeeEstimates: ...
     @staticmethod
     def from_bytes(bytes) -> RequestFeeEstimates: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestFeeEstimates: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestFeeEstimates: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestFeeEstimates: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestFeeEstimates, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestFeeEstimates: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, time_targets: Union[ List[uint64], _Unspec] = _Unspec()) -> RequestFeeEstimates: ...
 
 class RespondFeeEstimates:
     estimates: FeeEstimateGroup
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         estimates: FeeEstimateGroup
@@ -3526,25 +7096,34 @@ class RespondFeeEstimates:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondFeeEstimates: ...
     def __copy__(self) -> RespondFeeEstimates: ...
     @staticmethod
     def from_bytes(bytes) -> RespondFeeEstimates: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondFeeEstimates: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondFeeEstimates: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondFeeEstimates: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondFeeEstimates, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondFeeEstimates: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, estimates: Union[ FeeEstimateGroup, _Unspec] = _Unspec()) -> RespondFeeEstimates: ...
 
 class RequestRemovePuzzleSubscriptions:
     puzzle_hashes: Optional[List[bytes32]]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         puzzle_hashes: Optional[Sequence[bytes32]]
@@ -3552,25 +7131,34 @@ class RequestRemovePuzzleSubscriptions:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestRemovePuzzleSubscriptions: ...
     def __copy__(self) -> RequestRemovePuzzleSubscriptions: ...
     @staticmethod
     def from_bytes(bytes) -> RequestRemovePuzzleSubscriptions: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestRemovePuzzleSubscriptions: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestRemovePuzzleSubscriptions: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestRemovePuzzleSubscriptions: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestRemovePuzzleSubscriptions, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestRemovePuzzleSubscriptions: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, puzzle_hashes: Union[ Optional[List[bytes32]], _Unspec] = _Unspec()) -> RequestRemovePuzzleSubscriptions: ...
 
 class RespondRemovePuzzleSubscriptions:
     puzzle_hashes: List[bytes32]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         puzzle_hashes: Sequence[bytes32]
@@ -3578,25 +7166,34 @@ class RespondRemovePuzzleSubscriptions:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondRemovePuzzleSubscriptions: ...
     def __copy__(self) -> RespondRemovePuzzleSubscriptions: ...
     @staticmethod
     def from_bytes(bytes) -> RespondRemovePuzzleSubscriptions: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondRemovePuzzleSubscriptions: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondRemovePuzzleSubscriptions: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondRemovePuzzleSubscriptions: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondRemovePuzzleSubscriptions, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondRemovePuzzleSubscriptions: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, puzzle_hashes: Union[ List[bytes32], _Unspec] = _Unspec()) -> RespondRemovePuzzleSubscriptions: ...
 
 class RequestRemoveCoinSubscriptions:
     coin_ids: Optional[List[bytes32]]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         coin_ids: Optional[Sequence[bytes32]]
@@ -3604,25 +7201,34 @@ class RequestRemoveCoinSubscriptions:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestRemoveCoinSubscriptions: ...
     def __copy__(self) -> RequestRemoveCoinSubscriptions: ...
     @staticmethod
     def from_bytes(bytes) -> RequestRemoveCoinSubscriptions: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestRemoveCoinSubscriptions: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestRemoveCoinSubscriptions: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestRemoveCoinSubscriptions: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestRemoveCoinSubscriptions, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestRemoveCoinSubscriptions: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, coin_ids: Union[ Optional[List[bytes32]], _Unspec] = _Unspec()) -> RequestRemoveCoinSubscriptions: ...
 
 class RespondRemoveCoinSubscriptions:
     coin_ids: List[bytes32]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         coin_ids: Sequence[bytes32]
@@ -3630,21 +7236,28 @@ class RespondRemoveCoinSubscriptions:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondRemoveCoinSubscriptions: ...
     def __copy__(self) -> RespondRemoveCoinSubscriptions: ...
     @staticmethod
     def from_bytes(bytes) -> RespondRemoveCoinSubscriptions: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondRemoveCoinSubscriptions: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondRemoveCoinSubscriptions: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondRemoveCoinSubscriptions: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondRemoveCoinSubscriptions, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondRemoveCoinSubscriptions: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, coin_ids: Union[ List[bytes32], _Unspec] = _Unspec()) -> RespondRemoveCoinSubscriptions: ...
 
 class CoinStateFilters:
@@ -3662,21 +7275,28 @@ class CoinStateFilters:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> CoinStateFilters: ...
     def __copy__(self) -> CoinStateFilters: ...
     @staticmethod
     def from_bytes(bytes) -> CoinStateFilters: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> CoinStateFilters: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> CoinStateFilters: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> CoinStateFilters: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[CoinStateFilters, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> CoinStateFilters: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, include_spent: Union[ bool, _Unspec] = _Unspec(),
         include_unspent: Union[ bool, _Unspec] = _Unspec(),
         include_hinted: Union[ bool, _Unspec] = _Unspec(),
@@ -3688,6 +7308,8 @@ class RequestPuzzleState:
     header_hash: bytes32
     filters: CoinStateFilters
     subscribe_when_finished: bool
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         puzzle_hashes: Sequence[bytes32],
@@ -3699,21 +7321,28 @@ class RequestPuzzleState:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestPuzzleState: ...
     def __copy__(self) -> RequestPuzzleState: ...
     @staticmethod
     def from_bytes(bytes) -> RequestPuzzleState: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestPuzzleState: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestPuzzleState: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestPuzzleState: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestPuzzleState, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestPuzzleState: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, puzzle_hashes: Union[ List[bytes32], _Unspec] = _Unspec(),
         previous_height: Union[ Optional[uint32], _Unspec] = _Unspec(),
         header_hash: Union[ bytes32, _Unspec] = _Unspec(),
@@ -3726,6 +7355,8 @@ class RespondPuzzleState:
     header_hash: bytes32
     is_finished: bool
     coin_states: List[CoinState]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         puzzle_hashes: Sequence[bytes32],
@@ -3737,21 +7368,28 @@ class RespondPuzzleState:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondPuzzleState: ...
     def __copy__(self) -> RespondPuzzleState: ...
     @staticmethod
     def from_bytes(bytes) -> RespondPuzzleState: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondPuzzleState: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondPuzzleState: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondPuzzleState: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondPuzzleState, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondPuzzleState: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, puzzle_hashes: Union[ List[bytes32], _Unspec] = _Unspec(),
         height: Union[ uint32, _Unspec] = _Unspec(),
         header_hash: Union[ bytes32, _Unspec] = _Unspec(),
@@ -3760,6 +7398,8 @@ class RespondPuzzleState:
 
 class RejectPuzzleState:
     reason: int
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         reason: int
@@ -3767,21 +7407,28 @@ class RejectPuzzleState:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RejectPuzzleState: ...
     def __copy__(self) -> RejectPuzzleState: ...
     @staticmethod
     def from_bytes(bytes) -> RejectPuzzleState: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RejectPuzzleState: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RejectPuzzleState: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RejectPuzzleState: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RejectPuzzleState, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RejectPuzzleState: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, reason: Union[ int, _Unspec] = _Unspec()) -> RejectPuzzleState: ...
 
 class RequestCoinState:
@@ -3789,6 +7436,8 @@ class RequestCoinState:
     previous_height: Optional[uint32]
     header_hash: bytes32
     subscribe: bool
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         coin_ids: Sequence[bytes32],
@@ -3799,21 +7448,28 @@ class RequestCoinState:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RequestCoinState: ...
     def __copy__(self) -> RequestCoinState: ...
     @staticmethod
     def from_bytes(bytes) -> RequestCoinState: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RequestCoinState: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RequestCoinState: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RequestCoinState: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RequestCoinState, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RequestCoinState: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, coin_ids: Union[ List[bytes32], _Unspec] = _Unspec(),
         previous_height: Union[ Optional[uint32], _Unspec] = _Unspec(),
         header_hash: Union[ bytes32, _Unspec] = _Unspec(),
@@ -3822,6 +7478,8 @@ class RequestCoinState:
 class RespondCoinState:
     coin_ids: List[bytes32]
     coin_states: List[CoinState]
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         coin_ids: Sequence[bytes32],
@@ -3830,26 +7488,35 @@ class RespondCoinState:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RespondCoinState: ...
     def __copy__(self) -> RespondCoinState: ...
     @staticmethod
     def from_bytes(bytes) -> RespondCoinState: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RespondCoinState: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RespondCoinState: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RespondCoinState: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RespondCoinState, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RespondCoinState: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, coin_ids: Union[ List[bytes32], _Unspec] = _Unspec(),
         coin_states: Union[ List[CoinState], _Unspec] = _Unspec()) -> RespondCoinState: ...
 
 class RejectCoinState:
     reason: int
+    MSG_TYPE: ClassVar[int]
+    RESPONSES: ClassVar[List[type]]
     def __init__(
         self,
         reason: int
@@ -3857,21 +7524,28 @@ class RejectCoinState:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RejectCoinState: ...
     def __copy__(self) -> RejectCoinState: ...
     @staticmethod
     def from_bytes(bytes) -> RejectCoinState: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RejectCoinState: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RejectCoinState: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RejectCoinState: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RejectCoinState, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RejectCoinState: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, reason: Union[ int, _Unspec] = _Unspec()) -> RejectCoinState: ...
 
 class SubEpochData:
@@ -3889,21 +7563,28 @@ class SubEpochData:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> SubEpochData: ...
     def __copy__(self) -> SubEpochData: ...
     @staticmethod
     def from_bytes(bytes) -> SubEpochData: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> SubEpochData: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> SubEpochData: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> SubEpochData: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[SubEpochData, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> SubEpochData: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, reward_chain_hash: Union[ bytes32, _Unspec] = _Unspec(),
         num_blocks_overflow: Union[ uint8, _Unspec] = _Unspec(),
         new_sub_slot_iters: Union[ Optional[uint64], _Unspec] = _Unspec(),
@@ -3944,21 +7625,28 @@ class SubSlotData:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> SubSlotData: ...
     def __copy__(self) -> SubSlotData: ...
     @staticmethod
     def from_bytes(bytes) -> SubSlotData: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> SubSlotData: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> SubSlotData: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> SubSlotData: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[SubSlotData, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> SubSlotData: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, proof_of_space: Union[ Optional[ProofOfSpace], _Unspec] = _Unspec(),
         cc_signage_point: Union[ Optional[VDFProof], _Unspec] = _Unspec(),
         cc_infusion_point: Union[ Optional[VDFProof], _Unspec] = _Unspec(),
@@ -3986,27 +7674,37 @@ class SubEpochChallengeSegment:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> SubEpochChallengeSegment: ...
     def __copy__(self) -> SubEpochChallengeSegment: ...
     @staticmethod
     def from_bytes(bytes) -> SubEpochChallengeSegment: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> SubEpochChallengeSegment: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> SubEpochChallengeSegment: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> SubEpochChallengeSegment: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[SubEpochChallengeSegment, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> SubEpochChallengeSegment: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, sub_epoch_n: Union[ uint32, _Unspec] = _Unspec(),
         sub_slots: Union[ List[SubSlotData], _Unspec] = _Unspec(),
         rc_slot_end_info: Union[ Optional[VDFInfo], _Unspec] = _Unspec()) -> SubEpochChallengeSegment: ...
 
 class SubEpochSegments:
     challenge_segments: List[SubEpochChallengeSegment]
+    def to_bytes_compressed(self, level: int = 3) -> bytes: ...
+    @staticmethod
+    def from_bytes_compressed(blob: bytes) -> SubEpochSegments: ...
     def __init__(
         self,
         challenge_segments: Sequence[SubEpochChallengeSegment]
@@ -4014,21 +7712,28 @@ class SubEpochSegments:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> SubEpochSegments: ...
     def __copy__(self) -> SubEpochSegments: ...
     @staticmethod
     def from_bytes(bytes) -> SubEpochSegments: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> SubEpochSegments: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> SubEpochSegments: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> SubEpochSegments: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[SubEpochSegments, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> SubEpochSegments: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, challenge_segments: Union[ List[SubEpochChallengeSegment], _Unspec] = _Unspec()) -> SubEpochSegments: ...
 
 class RecentChainData:
@@ -4040,21 +7745,28 @@ class RecentChainData:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> RecentChainData: ...
     def __copy__(self) -> RecentChainData: ...
     @staticmethod
     def from_bytes(bytes) -> RecentChainData: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> RecentChainData: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> RecentChainData: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> RecentChainData: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[RecentChainData, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> RecentChainData: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, recent_chain_data: Union[ List[HeaderBlock], _Unspec] = _Unspec()) -> RecentChainData: ...
 
 class ProofBlockHeader:
@@ -4068,21 +7780,28 @@ class ProofBlockHeader:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> ProofBlockHeader: ...
     def __copy__(self) -> ProofBlockHeader: ...
     @staticmethod
     def from_bytes(bytes) -> ProofBlockHeader: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> ProofBlockHeader: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> ProofBlockHeader: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> ProofBlockHeader: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[ProofBlockHeader, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> ProofBlockHeader: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, finished_sub_slots: Union[ List[EndOfSubSlotBundle], _Unspec] = _Unspec(),
         reward_chain_block: Union[ RewardChainBlock, _Unspec] = _Unspec()) -> ProofBlockHeader: ...
 
@@ -4099,21 +7818,28 @@ class WeightProof:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> WeightProof: ...
     def __copy__(self) -> WeightProof: ...
     @staticmethod
     def from_bytes(bytes) -> WeightProof: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> WeightProof: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> WeightProof: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> WeightProof: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[WeightProof, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> WeightProof: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, sub_epochs: Union[ List[SubEpochData], _Unspec] = _Unspec(),
         sub_epoch_segments: Union[ List[SubEpochChallengeSegment], _Unspec] = _Unspec(),
         recent_chain_data: Union[ List[HeaderBlock], _Unspec] = _Unspec()) -> WeightProof: ...
@@ -4163,6 +7889,10 @@ class ConsensusConstants:
     PLOT_FILTER_128_HEIGHT: uint32
     PLOT_FILTER_64_HEIGHT: uint32
     PLOT_FILTER_32_HEIGHT: uint32
+    @staticmethod
+    def from_dict_overrides(base: ConsensusConstants, overrides: Dict[str, Any]) -> ConsensusConstants: ...
+    @staticmethod
+    def from_json(base: ConsensusConstants, overrides: str) -> ConsensusConstants: ...
     def __init__(
         self,
         SLOT_BLOCKS_TARGET: uint32,
@@ -4213,21 +7943,28 @@ class ConsensusConstants:
     def __hash__(self) -> int: ...
     def __repr__(self) -> str: ...
     def __richcmp__(self) -> Any: ...
+    def __sizeof__(self) -> int: ...
     def __deepcopy__(self) -> ConsensusConstants: ...
     def __copy__(self) -> ConsensusConstants: ...
     @staticmethod
     def from_bytes(bytes) -> ConsensusConstants: ...
     @staticmethod
+    def from_bytes_with_limits(bytes, max_list_len: int, max_bytes_len: int) -> ConsensusConstants: ...
+    @staticmethod
+    def from_bytes_strict(bytes) -> ConsensusConstants: ...
+    @staticmethod
     def from_bytes_unchecked(bytes) -> ConsensusConstants: ...
     @staticmethod
     def parse_rust(ReadableBuffer, bool = False) -> Tuple[ConsensusConstants, int]: ...
     def to_bytes(self) -> bytes: ...
     def __bytes__(self) -> bytes: ...
     def stream_to_bytes(self) -> bytes: ...
+    def stream_to(self, f: BinaryIO) -> None: ...
     def get_hash(self) -> bytes32: ...
-    def to_json_dict(self) -> Any: ...
+    def to_json_dict(self, int_as_string: bool = False) -> Any: ...
     @staticmethod
     def from_json_dict(json_dict: Any) -> ConsensusConstants: ...
+    def to_pretty_json(self, indent: int = 2, max_items: Optional[int] = None) -> str: ...
     def replace(self, *, SLOT_BLOCKS_TARGET: Union[ uint32, _Unspec] = _Unspec(),
         MIN_BLOCKS_PER_CHALLENGE_BLOCK: Union[ uint8, _Unspec] = _Unspec(),
         MAX_SUB_SLOT_BLOCKS: Union[ uint32, _Unspec] = _Unspec(),
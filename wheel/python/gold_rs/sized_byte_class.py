@@ -2,10 +2,10 @@ from __future__ import annotations
 
 import random
 import secrets
+import string
 from typing import (
     BinaryIO,
     Iterable,
-    Optional,
     SupportsBytes,
     SupportsIndex,
     Type,
@@ -20,11 +20,44 @@ system_random = secrets.SystemRandom()
 
 def hexstr_to_bytes(input_str: str) -> bytes:
     """
-    Converts a hex string into bytes, removing the 0x if it's present.
+    Converts a hex string into bytes, removing the 0x if it's present. The
+    error for an invalid string says what's wrong with it, and where.
     """
-    if input_str.startswith("0x") or input_str.startswith("0X"):
-        return bytes.fromhex(input_str[2:])
-    return bytes.fromhex(input_str)
+    prefix = 2 if input_str.startswith("0x") or input_str.startswith("0X") else 0
+    try:
+        return bytes.fromhex(input_str[prefix:])
+    except ValueError:
+        pass
+    digits = 0
+    for pos, c in enumerate(input_str[prefix:], prefix):
+        if c in string.hexdigits:
+            digits += 1
+        elif not c.isspace():
+            raise ValueError(f"invalid hex character {c!r} at position {pos}")
+    if digits % 2 != 0:
+        raise ValueError(f"invalid hex: odd number of digits ({digits})")
+    # whitespace in the middle of a byte
+    raise ValueError(f"invalid hex: {input_str!r}")
+
+
+def splitmix64_bytes(seed: int, size: int) -> bytes:
+    """
+    Deterministic pseudo-random bytes from the splitmix64 generator. These are
+    the same bytes as the random(seed) of the Rust types produce.
+    """
+    if not 0 <= seed < 2**64:
+        raise OverflowError(f"seed must be a 64 bit unsigned integer, got {seed}")
+    mask = 2**64 - 1
+    state = seed
+    ret = bytearray()
+    while len(ret) < size:
+        state = (state + 0x9E3779B97F4A7C15) & mask
+        z = state
+        z = ((z ^ (z >> 30)) * 0xBF58476D1CE4E5B9) & mask
+        z = ((z ^ (z >> 27)) * 0x94D049BB133111EB) & mask
+        z ^= z >> 31
+        ret += z.to_bytes(8, "little")
+    return bytes(ret[:size])
 
 
 class SizedBytes(bytes):
@@ -32,18 +65,33 @@ class SizedBytes(bytes):
     to be a certain, fixed size specified by the `._size` class attribute.
     """
 
+    # instances have no __dict__, which saves memory and makes them immutable
+    __slots__ = ()
+
     _size = 0
 
+    # a str is parsed as hex, rather than rejected like it is by bytes()
+    def __new__(
+        cls: Type[_T_SizedBytes],
+        v: Union[str, Iterable[SupportsIndex], SupportsBytes],
+    ) -> _T_SizedBytes:
+        if isinstance(v, str):
+            v = hexstr_to_bytes(v)
+        return super().__new__(cls, v)
+
     # This is just a partial exposure of the underlying bytes constructor.  Liskov...
     # https://github.com/python/typeshed/blob/f8547a3f3131de90aa47005358eb3394e79cfa13/stdlib/builtins.pyi#L483-L493
-    def __init__(self, v: Union[Iterable[SupportsIndex], SupportsBytes]) -> None:
+    def __init__(self, v: Union[str, Iterable[SupportsIndex], SupportsBytes]) -> None:
         # v is unused here and that is ok since .__new__() seems to have already
         # processed the parameter when creating the instance of the class.  We have no
         # additional special action to take here beyond verifying that the newly
         # created instance satisfies the length limitation of the particular subclass.
         super().__init__()
         if len(self) != self._size:
-            raise ValueError(f"bad {type(self).__name__} initializer {v}")
+            raise ValueError(
+                f"bad {type(self).__name__} initializer {v!r}: "
+                f"expected {self._size} bytes, got {len(self)}"
+            )
 
     @classmethod
     def parse(cls: Type[_T_SizedBytes], f: BinaryIO) -> _T_SizedBytes:
@@ -59,14 +107,30 @@ class SizedBytes(bytes):
 
     @classmethod
     def from_hexstr(cls: Type[_T_SizedBytes], input_str: str) -> _T_SizedBytes:
-        if input_str.startswith("0x") or input_str.startswith("0X"):
-            return cls.fromhex(input_str[2:])
-        return cls.fromhex(input_str)
+        return cls(hexstr_to_bytes(input_str))
+
+    # parses a bech32m address, like "xch1...", validating its checksum and
+    # prefix
+    @classmethod
+    def from_address(
+        cls: Type[_T_SizedBytes], address: str, prefix: str
+    ) -> _T_SizedBytes:
+        from .gold_rs import decode_address
+
+        return cls(decode_address(address, prefix))
+
+    @classmethod
+    def zeros(cls: Type[_T_SizedBytes]) -> _T_SizedBytes:
+        return cls(bytes(cls._size))
 
+    # r may also be an int seed, for values that are the same on every run.
+    # Those are the same bytes as the random(seed) of the Rust types
     @classmethod
     def random(
-        cls: Type[_T_SizedBytes], r: Optional[random.Random] = None
+        cls: Type[_T_SizedBytes], r: Union[random.Random, int, None] = None
     ) -> _T_SizedBytes:
+        if isinstance(r, int):
+            return cls(splitmix64_bytes(r, cls._size))
         if r is None:
             getrandbits = random.getrandbits
         else:
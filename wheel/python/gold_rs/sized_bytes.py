@@ -4,28 +4,35 @@ from .sized_byte_class import SizedBytes
 
 
 class bytes4(SizedBytes):
+    __slots__ = ()
     _size = 4
 
 
 class bytes8(SizedBytes):
+    __slots__ = ()
     _size = 8
 
 
 class bytes32(SizedBytes):
+    __slots__ = ()
     _size = 32
 
 
 class bytes48(SizedBytes):
+    __slots__ = ()
     _size = 48
 
 
 class bytes96(SizedBytes):
+    __slots__ = ()
     _size = 96
 
 
 class bytes100(SizedBytes):
+    __slots__ = ()
     _size = 100
 
 
 class bytes480(SizedBytes):
+    __slots__ = ()
     _size = 480
@@ -0,0 +1,9 @@
+# These are only available in a wheel built with the test_support feature.
+# They're kept out of the gold_rs stubs, so importing them from here is the
+# only way a type checker accepts them.
+try:
+    from .gold_rs import BlockTools, ChainDb, ChainDbIterator, Simulator, random_instance
+except ImportError as e:
+    raise ImportError("gold_rs was built without the test_support feature") from e
+
+__all__ = ["BlockTools", "ChainDb", "ChainDbIterator", "Simulator", "random_instance"]
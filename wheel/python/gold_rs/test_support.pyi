@@ -0,0 +1,64 @@
+
+#
+# this file is generated by generate_type_stubs.py
+#
+
+import os
+from typing import List, Optional, Sequence, Tuple, Union, Any
+from .sized_bytes import bytes32
+from .gold_rs import Coin, CoinState, ConsensusConstants, FullBlock, SpendBundle
+
+def random_instance(name: str, seed: int) -> Any: ...
+
+class BlockTools:
+    def __init__(
+        self,
+        constants: ConsensusConstants,
+        seed: bytes = bytes(32),
+        farmer_puzzle_hash: Optional[bytes32] = None,
+        pool_puzzle_hash: Optional[bytes32] = None,
+    ) -> None: ...
+    def __copy__(self) -> BlockTools: ...
+    def __deepcopy__(self, memo: Any) -> BlockTools: ...
+    def next_block(
+        self, prev: Optional[FullBlock], bundles: Sequence[SpendBundle] = []
+    ) -> FullBlock: ...
+    def get_consecutive_blocks(
+        self, count: int, prev: Optional[FullBlock] = None
+    ) -> List[FullBlock]: ...
+    def reward_coins(self, block: FullBlock) -> Tuple[Coin, Coin]: ...
+
+class Simulator:
+    height: int
+    timestamp: int
+    def __init__(self, constants: ConsensusConstants) -> None: ...
+    def __copy__(self) -> Simulator: ...
+    def __deepcopy__(self, memo: Any) -> Simulator: ...
+    def mempool_len(self) -> int: ...
+    def new_coin(self, puzzle_hash: bytes32, amount: int) -> Coin: ...
+    def push_tx(self, bundle: SpendBundle) -> int: ...
+    def farm_block(self, seconds: int = 20) -> List[CoinState]: ...
+    def coin_state(self, coin_id: bytes32) -> Optional[CoinState]: ...
+    def coin_states(self, coin_ids: Sequence[bytes32]) -> List[CoinState]: ...
+    def coin_states_by_puzzle_hash(
+        self, puzzle_hashes: Sequence[bytes32], include_spent: bool = True
+    ) -> List[CoinState]: ...
+    def coin_states_by_hint(
+        self, hints: Sequence[bytes32], include_spent: bool = True
+    ) -> List[CoinState]: ...
+    def children(self, coin_id: bytes32) -> List[CoinState]: ...
+
+class ChainDb:
+    def __init__(self, path: Union[str, os.PathLike[str]]) -> None: ...
+    def peak_height(self) -> Optional[int]: ...
+    def get_block(self, height: int) -> Optional[FullBlock]: ...
+    def get_block_by_hash(self, header_hash: bytes32) -> Optional[FullBlock]: ...
+    def get_blocks(self, start: int, end: int) -> List[FullBlock]: ...
+    def generator_refs(self, block: FullBlock) -> List[bytes]: ...
+    def iter_blocks(
+        self, start_height: int = 0, end_height: Optional[int] = None
+    ) -> ChainDbIterator: ...
+
+class ChainDbIterator:
+    def __iter__(self) -> ChainDbIterator: ...
+    def __next__(self) -> FullBlock: ...
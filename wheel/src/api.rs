@@ -1,63 +1,192 @@
-use crate::run_generator::{run_block_generator, run_block_generator2};
-use chia_consensus::allocator::make_allocator;
+use crate::run_generator::{
+    block_generator_refs, profile_block_generator, run_block_generator, run_block_generator2,
+    run_block_generator2_with_resolver, run_block_generator_with_resolver, timings_to_dict,
+};
+use chia_consensus::allocator::{make_allocator, make_allocator_with_limit};
+use chia_consensus::block_creation::finish_block as native_finish_block;
+use chia_consensus::block_rewards::{
+    calculate_base_farmer_reward as native_calculate_base_farmer_reward,
+    calculate_pool_reward as native_calculate_pool_reward,
+    create_farmer_coin as native_create_farmer_coin, create_pool_coin as native_create_pool_coin,
+    expected_reward_coins as native_expected_reward_coins,
+    validate_prefarm as native_validate_prefarm,
+    validate_reward_claims as native_validate_reward_claims,
+};
+use chia_consensus::block_signatures::validate_block_signatures as native_validate_block_signatures;
+use chia_consensus::block_validator::BlockValidator;
+#[cfg(feature = "test_support")]
+use chia_consensus::chain_db::{ChainDb, ChainDbIterator};
+use chia_consensus::cheap_checks::cheap_checks as native_cheap_checks;
+use chia_consensus::coin_selection::{select_coins as native_select_coins, CoinSelectionStrategy};
+use chia_consensus::coin_store::CoinStore;
 use chia_consensus::consensus_constants::ConsensusConstants;
-use chia_consensus::gen::conditions::MempoolVisitor;
+use chia_consensus::error::Error as ConsensusError;
+use chia_consensus::fork_point::{
+    compare_peaks as native_compare_peaks, find_fork_point as native_find_fork_point,
+};
+use chia_consensus::fork_schedule::ForkSchedule;
+use chia_consensus::gen::block_cost::get_block_cost as native_get_block_cost;
+use chia_consensus::gen::compress_generator::{
+    compress_generator as native_compress_generator,
+    decompress_generator as native_decompress_generator,
+};
+use chia_consensus::gen::conditions::{FastForwardIneligible, MempoolVisitor};
 use chia_consensus::gen::flags::{
     AGG_SIG_ARGS, ALLOW_BACKREFS, ANALYZE_SPENDS, COND_ARGS_NIL, DISALLOW_INFINITY_G1,
     ENABLE_MESSAGE_CONDITIONS, ENABLE_SOFTFORK_CONDITION, MEMPOOL_MODE,
-    NO_RELATIVE_CONDITIONS_ON_EPHEMERAL, NO_UNKNOWN_CONDS, STRICT_ARGS_COUNT,
+    NO_RELATIVE_CONDITIONS_ON_EPHEMERAL, NO_UNKNOWN_CONDS, SKIP_AGG_SIG_CHECKS, STRICT_ARGS_COUNT,
+};
+use chia_consensus::gen::opcodes::{
+    condition_cost as native_condition_cost, condition_cost_table as native_condition_cost_table,
+    AGG_SIG_COST, CREATE_COIN_COST,
 };
 use chia_consensus::gen::owned_conditions::{OwnedSpend, OwnedSpendBundleConditions};
-use chia_consensus::gen::run_puzzle::run_puzzle as native_run_puzzle;
+use chia_consensus::gen::run_puzzle::analyze_spend as native_analyze_spend;
+use chia_consensus::gen::run_puzzle::run_puzzle_at_height as native_run_puzzle_at_height;
+use chia_consensus::gen::run_puzzle::run_puzzle_with_limits as native_run_puzzle_with_limits;
+use chia_consensus::gen::run_puzzle::run_spend as native_run_spend;
+use chia_consensus::gen::run_puzzle::run_spend_bundle_with_policy as native_run_spend_bundle_with_policy;
+use chia_consensus::gen::serialize_conditions::canonicalize_conditions as native_canonicalize_conditions;
+use chia_consensus::gen::signing::{
+    agg_sig_additional_data as native_agg_sig_additional_data,
+    messages_to_sign as native_messages_to_sign,
+};
+use chia_consensus::gen::solution_generator::build_block_generator as native_build_block_generator;
+use chia_consensus::gen::solution_generator::estimate_cost as native_estimate_cost;
+use chia_consensus::gen::solution_generator::estimate_generator_size as native_estimate_generator_size;
 use chia_consensus::gen::solution_generator::solution_generator as native_solution_generator;
 use chia_consensus::gen::solution_generator::solution_generator_backrefs as native_solution_generator_backrefs;
+use chia_consensus::gen::spend_context::SpendContext;
+use chia_consensus::gen::spend_graph::{
+    announcement_edges as native_announcement_edges,
+    coin_announcement_id as native_coin_announcement_id,
+    link_announcements as native_link_announcements,
+    puzzle_announcement_id as native_puzzle_announcement_id, to_dot as native_spends_to_dot,
+};
+use chia_consensus::header_validation::{
+    precheck_header_blocks as native_precheck_header_blocks, sub_slot_challenges,
+    validate_sp_signatures as native_validate_sp_signatures,
+    validate_unfinished_header_block_linkage as native_validate_unfinished_header_block_linkage,
+    HeaderContext,
+};
+use chia_consensus::mempool_item::{block_inclusion_order, eviction_order, MempoolItem};
+use chia_consensus::merkle_blob::{MerkleBlob, ProofOfInclusion, ProofOfInclusionLayer};
 use chia_consensus::merkle_set::compute_merkle_set_root as compute_merkle_root_impl;
 use chia_consensus::merkle_tree::{validate_merkle_proof, MerkleSet};
+use chia_consensus::network_info::NetworkInfo;
+use chia_consensus::pool_puzzle::PoolInnerPuzzle;
+use chia_consensus::proof_of_space::{
+    calculate_plot_filter_input as native_calculate_plot_filter_input,
+    calculate_plot_id_ph as native_calculate_plot_id_ph,
+    calculate_plot_id_pk as native_calculate_plot_id_pk,
+    calculate_pos_challenge as native_calculate_pos_challenge, get_plot_id as native_get_plot_id,
+    passes_plot_filter as native_passes_plot_filter,
+    signage_point_hash as native_signage_point_hash,
+};
+use chia_consensus::puzzle_state::{
+    filter_coin_states as native_filter_coin_states,
+    puzzle_state_responses as native_puzzle_state_responses, DEFAULT_MAX_RESPONSE_SIZE,
+};
+use chia_consensus::signage_points::{SignagePoint, SignagePointIterator};
+use chia_consensus::signed_message::{
+    sign_message as native_sign_message, verify_message as native_verify_message, SigningMode,
+    CHIP_0002_SIGN_MESSAGE_PREFIX,
+};
+#[cfg(feature = "test_support")]
+use chia_consensus::simulator::Simulator;
+use chia_consensus::spend_bundle_signatures::sign_coin_spends as native_sign_coin_spends;
+use chia_consensus::spend_bundle_signatures::validate_clvm_and_signature as native_validate_clvm_and_signature;
+use chia_consensus::spend_bundle_signatures::verify_spend_bundle_signatures as native_verify_spend_bundle_signatures;
+use chia_consensus::sub_epoch_summary::make_sub_epoch_summary as native_make_sub_epoch_summary;
+use chia_consensus::subscriptions::{
+    index_spends as native_index_spends, CoinIndex, SubscriptionManager,
+};
+#[cfg(feature = "test_support")]
+use chia_consensus::test_blocks::BlockTools;
+use chia_consensus::timestamp::{
+    prev_transaction_block_timestamps as native_prev_transaction_block_timestamps,
+    validate_timestamp as native_validate_timestamp,
+};
+use chia_consensus::weight_proof::WeightProofBuilder;
+#[cfg(feature = "test_support")]
+use chia_protocol::random_instance as native_random_instance;
+use chia_protocol::{
+    decode_address as native_decode_address, encode_address as native_encode_address,
+    hash_coin_states as native_hash_coin_states, parse_message as native_parse_message,
+    ProtocolMessageTypes, PyBytes100, PyBytes48, PyBytes96, RateLimiter,
+};
 use chia_protocol::{
-    BlockRecord, Bytes32, ChallengeBlockInfo, ChallengeChainSubSlot, ClassgroupElement, Coin,
-    CoinSpend, CoinState, CoinStateFilters, CoinStateUpdate, EndOfSubSlotBundle, Foliage,
-    FoliageBlockData, FoliageTransactionBlock, FullBlock, HeaderBlock,
-    InfusedChallengeChainSubSlot, NewCompactVDF, NewPeak, NewPeakWallet,
-    NewSignagePointOrEndOfSubSlot, NewTransaction, NewUnfinishedBlock, NewUnfinishedBlock2,
-    PoolTarget, Program, ProofBlockHeader, ProofOfSpace, PuzzleSolutionResponse, RecentChainData,
-    RegisterForCoinUpdates, RegisterForPhUpdates, RejectAdditionsRequest, RejectBlock,
-    RejectBlockHeaders, RejectBlocks, RejectCoinState, RejectHeaderBlocks, RejectHeaderRequest,
-    RejectPuzzleSolution, RejectPuzzleState, RejectRemovalsRequest, RequestAdditions, RequestBlock,
-    RequestBlockHeader, RequestBlockHeaders, RequestBlocks, RequestChildren, RequestCoinState,
-    RequestCompactVDF, RequestFeeEstimates, RequestHeaderBlocks, RequestMempoolTransactions,
-    RequestPeers, RequestProofOfWeight, RequestPuzzleSolution, RequestPuzzleState, RequestRemovals,
-    RequestRemoveCoinSubscriptions, RequestRemovePuzzleSubscriptions, RequestSesInfo,
-    RequestSignagePointOrEndOfSubSlot, RequestTransaction, RequestUnfinishedBlock,
+    AuthenticationPayload, BlockRecord, Bytes, Bytes32, ChallengeBlockInfo, ChallengeChainSubSlot,
+    ClassgroupElement, Coin, CoinSpend, CoinState, CoinStateFilters, CoinStateUpdate,
+    DeclareProofOfSpace, EndOfSubSlotBundle, FarmingInfo, Foliage, FoliageBlockData,
+    FoliageTransactionBlock, FullBlock, GetFarmerResponse, GetPoolInfoResponse, HarvesterHandshake,
+    HeaderBlock, InfusedChallengeChainSubSlot, NewCompactVDF, NewEndOfSubSlotVDF,
+    NewInfusionPointVDF, NewPeak, NewPeakTimelord, NewPeakWallet, NewProofOfSpace, NewSignagePoint,
+    NewSignagePointHarvester, NewSignagePointOrEndOfSubSlot, NewSignagePointVDF, NewTransaction,
+    NewUnfinishedBlock, NewUnfinishedBlock2, NewUnfinishedBlockTimelord, Plot, PlotSyncDone,
+    PlotSyncError, PlotSyncIdentifier, PlotSyncPathList, PlotSyncPlotList, PlotSyncResponse,
+    PlotSyncStart, PoolDifficulty, PoolErrorResponse, PoolSingletonState, PoolState, PoolTarget,
+    PostFarmerPayload, PostFarmerRequest, PostFarmerResponse, PostPartialPayload,
+    PostPartialRequest, PostPartialResponse, Program, ProofBlockHeader, ProofOfSpace,
+    ProofOfSpaceFeeInfo, PutFarmerPayload, PutFarmerRequest, PutFarmerResponse,
+    PuzzleSolutionResponse, RecentChainData, RegisterForCoinUpdates, RegisterForPhUpdates,
+    RejectAdditionsRequest, RejectBlock, RejectBlockHeaders, RejectBlocks, RejectCoinState,
+    RejectHeaderBlocks, RejectHeaderRequest, RejectPuzzleSolution, RejectPuzzleState,
+    RejectRemovalsRequest, RequestAdditions, RequestBlock, RequestBlockHeader, RequestBlockHeaders,
+    RequestBlocks, RequestChildren, RequestCoinState, RequestCompactProofOfTime, RequestCompactVDF,
+    RequestFeeEstimates, RequestHeaderBlocks, RequestMempoolTransactions, RequestPeers,
+    RequestPeersIntroducer, RequestPlots, RequestProofOfWeight, RequestPuzzleSolution,
+    RequestPuzzleState, RequestRemovals, RequestRemoveCoinSubscriptions,
+    RequestRemovePuzzleSubscriptions, RequestSesInfo, RequestSignagePointOrEndOfSubSlot,
+    RequestSignatures, RequestSignedValues, RequestTransaction, RequestUnfinishedBlock,
     RequestUnfinishedBlock2, RespondAdditions, RespondBlock, RespondBlockHeader,
-    RespondBlockHeaders, RespondBlocks, RespondChildren, RespondCoinState, RespondCompactVDF,
-    RespondEndOfSubSlot, RespondFeeEstimates, RespondHeaderBlocks, RespondPeers,
-    RespondProofOfWeight, RespondPuzzleSolution, RespondPuzzleState, RespondRemovals,
-    RespondRemoveCoinSubscriptions, RespondRemovePuzzleSubscriptions, RespondSesInfo,
-    RespondSignagePoint, RespondToCoinUpdates, RespondToPhUpdates, RespondTransaction,
-    RespondUnfinishedBlock, RewardChainBlock, RewardChainBlockUnfinished, RewardChainSubSlot,
-    SendTransaction, SpendBundle, SubEpochChallengeSegment, SubEpochData, SubEpochSegments,
-    SubEpochSummary, SubSlotData, SubSlotProofs, TimestampedPeerInfo, TransactionAck,
-    TransactionsInfo, UnfinishedBlock, UnfinishedHeaderBlock, VDFInfo, VDFProof, WeightProof,
+    RespondBlockHeaders, RespondBlocks, RespondChildren, RespondCoinState,
+    RespondCompactProofOfTime, RespondCompactVDF, RespondEndOfSubSlot, RespondFeeEstimates,
+    RespondHeaderBlocks, RespondPeers, RespondPeersIntroducer, RespondPlots, RespondProofOfWeight,
+    RespondPuzzleSolution, RespondPuzzleState, RespondRemovals, RespondRemoveCoinSubscriptions,
+    RespondRemovePuzzleSubscriptions, RespondSesInfo, RespondSignagePoint, RespondSignatures,
+    RespondToCoinUpdates, RespondToPhUpdates, RespondTransaction, RespondUnfinishedBlock,
+    RewardChainBlock, RewardChainBlockUnfinished, RewardChainSubSlot, SendTransaction,
+    SignatureRequestSourceData, SignedValues, SpendBundle, SubEpochChallengeSegment, SubEpochData,
+    SubEpochSegments, SubEpochSummary, SubSlotData, SubSlotProofs, TimestampedPeerInfo,
+    TransactionAck, TransactionsInfo, UnfinishedBlock, UnfinishedHeaderBlock, VDFInfo, VDFProof,
+    WeightProof,
+};
+use chia_traits::{set_repr_limits as native_set_repr_limits, ReprLimits};
+use clvm_utils::{
+    assemble as native_assemble, disassemble_with_limit as native_disassemble_with_limit,
+    tree_hash_from_bytes, tree_hashes_from_bytes, TreeHasher,
 };
-use clvm_utils::tree_hash_from_bytes;
 use clvmr::{ENABLE_BLS_OPS_OUTSIDE_GUARD, ENABLE_FIXED_DIV, LIMIT_HEAP, NO_UNKNOWN_OPS};
 use pyo3::buffer::PyBuffer;
 use pyo3::exceptions::{PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::pybacked::PyBackedBytes;
 use pyo3::types::PyBytes;
+use pyo3::types::PyDict;
 use pyo3::types::PyList;
 use pyo3::types::PyTuple;
 use pyo3::wrap_pyfunction;
+use std::collections::HashMap;
 use std::iter::zip;
 
-use crate::run_program::{run_chia_program, serialized_length};
+use crate::puzzles::{
+    calculate_synthetic_public_key, calculate_synthetic_secret_key, clawback_puzzle,
+    clawback_puzzle_hash, clawback_solution, lineage_proof_for_parent, parse_clawback_spend,
+    parse_pool_puzzle, pool_state_from_extra_data, puzzle_for_pk, puzzle_hash_for_pk,
+    solution_for_conditions, solution_to_pool_state, verify_lineage_proof, PyLineageProof,
+};
+use crate::run_program::{
+    check_canonical_clvm, profile_chia_program, run_chia_program, serialized_length,
+};
 
 use crate::adapt_response::eval_err_to_pyresult;
+use chia_consensus::fast_forward::fast_forward_chain as native_ff_chain;
 use chia_consensus::fast_forward::fast_forward_singleton as native_ff;
 use chia_consensus::gen::get_puzzle_and_solution::get_puzzle_and_solution_for_coin as parse_puzzle_solution;
 use chia_consensus::gen::validation_error::ValidationErr;
-use clvmr::allocator::NodePtr;
+use chia_consensus::gen::validation_policy::ValidationPolicy;
+use clvmr::allocator::{Allocator, NodePtr};
 use clvmr::cost::Cost;
 use clvmr::reduction::EvalErr;
 use clvmr::reduction::Reduction;
@@ -67,8 +196,11 @@ use clvmr::serde::{node_from_bytes, node_from_bytes_backrefs};
 use clvmr::ChiaDialect;
 
 use chia_bls::{
-    hash_to_g2 as native_hash_to_g2, BlsCache, DerivableKey, GTElement, PublicKey, SecretKey,
-    Signature,
+    aggregate_public_keys as native_aggregate_public_keys,
+    aggregate_public_keys_with_pop as native_aggregate_public_keys_with_pop,
+    hash_to_g2 as native_hash_to_g2, pop_prove as native_pop_prove,
+    pop_verify as native_pop_verify, validate_public_keys as native_validate_public_keys, BlsCache,
+    DerivableKey, DerivationCache, GTElement, Keyring, PublicKey, SecretKey, Signature,
 };
 
 #[pyfunction]
@@ -117,6 +249,63 @@ pub fn tree_hash(py: Python, blob: PyBuffer<u8>) -> PyResult<Bound<PyBytes>> {
     Ok(PyBytes::new_bound(py, &tree_hash_from_bytes(slice)?))
 }
 
+// like tree_hash(), but for many programs at once. The programs are hashed in
+// parallel, without holding the GIL
+#[pyfunction]
+pub fn tree_hashes(py: Python, blobs: Vec<Vec<u8>>) -> PyResult<Vec<Bytes32>> {
+    let hashes = py
+        .allow_threads(|| tree_hashes_from_bytes(&blobs))
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(hashes.into_iter().map(Bytes32::from).collect())
+}
+
+// parses CLVM source (like clvm_tools' opc) and returns its serialization
+#[pyfunction]
+pub fn assemble<'a>(py: Python<'a>, src: &str) -> PyResult<Bound<'a, PyBytes>> {
+    let mut a = Allocator::new();
+    let node = native_assemble(&mut a, src).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(PyBytes::new_bound(py, &node_to_bytes(&a, node)?))
+}
+
+// the inverse of assemble() (like clvm_tools' opd). Back references can
+// expand a small program into a huge one, so the output is limited to max_len
+// characters
+#[pyfunction]
+#[pyo3(signature = (program, max_len = 16 * 1024 * 1024))]
+pub fn disassemble(program: &[u8], max_len: usize) -> PyResult<String> {
+    let mut a = Allocator::new();
+    let node = node_from_bytes_backrefs(&mut a, program)?;
+    native_disassemble_with_limit(&a, node, max_len)
+        .ok_or_else(|| PyValueError::new_err("disassembled program exceeds max_len"))
+}
+
+#[pyclass(name = "TreeHasher")]
+struct PyTreeHasher(TreeHasher);
+
+#[pymethods]
+impl PyTreeHasher {
+    #[new]
+    #[pyo3(signature = (max_entries = 100_000))]
+    pub fn init(max_entries: usize) -> Self {
+        Self(TreeHasher::new(max_entries))
+    }
+
+    // like tree_hash(), but subtrees that have been hashed before (by this
+    // object) are looked up rather than hashed again
+    pub fn tree_hash<'a>(&mut self, py: Python<'a>, blob: &[u8]) -> PyResult<Bound<'a, PyBytes>> {
+        let hash = py.allow_threads(|| self.0.tree_hash(blob))?;
+        Ok(PyBytes::new_bound(py, &hash))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 #[pyfunction]
 pub fn get_puzzle_and_solution_for_coin(
@@ -179,6 +368,7 @@ pub fn get_puzzle_and_solution_for_coin(
 }
 
 #[pyfunction]
+#[pyo3(signature = (puzzle, solution, parent_id, amount, max_cost, flags, max_heap_bytes=None))]
 fn run_puzzle(
     puzzle: &[u8],
     solution: &[u8],
@@ -186,14 +376,180 @@ fn run_puzzle(
     amount: u64,
     max_cost: Cost,
     flags: u32,
+    max_heap_bytes: Option<usize>,
+) -> PyResult<OwnedSpendBundleConditions> {
+    let mut a = make_allocator_with_limit(LIMIT_HEAP, max_heap_bytes);
+    // when the heap is limited, running out of it is reported as OutOfMemory
+    let conds = native_run_puzzle_with_limits::<MempoolVisitor>(
+        &mut a,
+        puzzle,
+        solution,
+        parent_id,
+        amount,
+        max_cost,
+        flags,
+        max_heap_bytes.is_some(),
+    )?;
+    Ok(OwnedSpendBundleConditions::from(&a, conds)?)
+}
+
+// returns the ELIGIBLE_FOR_DEDUP and ELIGIBLE_FOR_FF flags of the spend and the
+// names of the reasons it's not eligible for fast-forward
+#[pyfunction]
+#[pyo3(signature = (puzzle, solution, parent_id, amount, max_cost, flags=0))]
+fn analyze_spend(
+    puzzle: &[u8],
+    solution: &[u8],
+    parent_id: &[u8],
+    amount: u64,
+    max_cost: Cost,
+    flags: u32,
+) -> PyResult<(u32, Vec<&'static str>)> {
+    let mut a = make_allocator(LIMIT_HEAP);
+    let analysis =
+        native_analyze_spend(&mut a, puzzle, solution, parent_id, amount, max_cost, flags)?;
+    let reasons = analysis
+        .ff_ineligible_reasons()
+        .into_iter()
+        .map(FastForwardIneligible::name)
+        .collect();
+    Ok((analysis.flags, reasons))
+}
+
+#[pyfunction]
+fn run_puzzle_at_height(
+    puzzle: &[u8],
+    solution: &[u8],
+    parent_id: &[u8],
+    amount: u64,
+    height: u32,
+    constants: &ConsensusConstants,
 ) -> PyResult<OwnedSpendBundleConditions> {
     let mut a = make_allocator(LIMIT_HEAP);
-    let conds = native_run_puzzle::<MempoolVisitor>(
-        &mut a, puzzle, solution, parent_id, amount, max_cost, flags,
+    let conds = native_run_puzzle_at_height::<MempoolVisitor>(
+        &mut a, puzzle, solution, parent_id, amount, height, constants,
     )?;
     Ok(OwnedSpendBundleConditions::from(&a, conds)?)
 }
 
+#[pyfunction]
+fn run_spend(
+    spend: &CoinSpend,
+    max_cost: Cost,
+    flags: u32,
+) -> PyResult<OwnedSpendBundleConditions> {
+    let mut a = make_allocator(LIMIT_HEAP);
+    let conds = native_run_spend::<MempoolVisitor>(&mut a, spend, max_cost, flags)?;
+    Ok(OwnedSpendBundleConditions::from(&a, conds)?)
+}
+
+#[pyfunction]
+#[pyo3(signature = (bundle, max_cost, flags, policy=None))]
+fn run_spend_bundle(
+    bundle: &SpendBundle,
+    max_cost: Cost,
+    flags: u32,
+    policy: Option<ValidationPolicy>,
+) -> PyResult<Vec<OwnedSpendBundleConditions>> {
+    let mut a = make_allocator(LIMIT_HEAP);
+    let policy = policy.unwrap_or_default();
+    let conds = native_run_spend_bundle_with_policy::<MempoolVisitor>(
+        &mut a, bundle, max_cost, flags, &policy,
+    )?;
+    let mut ret = Vec::<OwnedSpendBundleConditions>::with_capacity(conds.len());
+    for c in conds {
+        ret.push(OwnedSpendBundleConditions::from(&a, c)?);
+    }
+    Ok(ret)
+}
+
+// the conditions of the bundle as a graph in the DOT language, including the
+// announcements linking its spends
+#[pyfunction]
+fn spend_bundle_to_dot(
+    bundle: &SpendBundle,
+    conds: &OwnedSpendBundleConditions,
+    max_cost: Cost,
+    flags: u32,
+) -> PyResult<String> {
+    let mut a = make_allocator(LIMIT_HEAP);
+    let edges = native_announcement_edges(&mut a, bundle, max_cost, flags)?;
+    Ok(native_spends_to_dot(conds, &edges))
+}
+
+#[pyfunction]
+fn coin_announcement_id(coin_id: Bytes32, message: &[u8]) -> Bytes32 {
+    native_coin_announcement_id(&coin_id, message)
+}
+
+#[pyfunction]
+fn puzzle_announcement_id(puzzle_hash: Bytes32, message: &[u8]) -> Bytes32 {
+    native_puzzle_announcement_id(&puzzle_hash, message)
+}
+
+// (announcement ID, whether it's a puzzle announcement, the asserting coin ID)
+type AnnouncementAssertion = (Bytes32, bool, Bytes32);
+
+// cross-checks the announcements of spends that have already been run, each
+// one being the coin and the conditions returned by its puzzle. Returns the
+// assertions satisfied by an announcement of one of the spends, and the ones
+// that aren't (which would make the bundle fail)
+#[pyfunction]
+#[pyo3(signature = (spends, flags=0))]
+fn check_announcements(
+    spends: Vec<(Coin, Program)>,
+    flags: u32,
+) -> PyResult<(Vec<AnnouncementAssertion>, Vec<AnnouncementAssertion>)> {
+    let mut a = make_allocator(LIMIT_HEAP);
+    let mut native_spends = Vec::<(Coin, NodePtr)>::with_capacity(spends.len());
+    for (coin, conditions) in &spends {
+        let conditions = node_from_bytes_backrefs(&mut a, conditions.as_slice())?;
+        native_spends.push((*coin, conditions));
+    }
+    let mut satisfied = Vec::new();
+    let mut unsatisfied = Vec::new();
+    for edge in native_link_announcements(&a, &native_spends, flags)? {
+        let assertion = (
+            edge.announcement_id,
+            edge.puzzle_announcement,
+            edge.asserter,
+        );
+        if edge.creator.is_some() {
+            satisfied.push(assertion);
+        } else {
+            unsatisfied.push(assertion);
+        }
+    }
+    Ok((satisfied, unsatisfied))
+}
+
+// Builds the canonical CLVM list of conditions. The conditions are either a
+// Program (a list of conditions, like the output of a puzzle) or a list of
+// tuples (opcode, arg1, arg2, ...). The conditions are parsed and validated the
+// same way as the output of a puzzle, according to flags
+#[pyfunction]
+#[pyo3(signature = (conditions, flags=0))]
+fn conditions_to_program(conditions: &Bound<PyAny>, flags: u32) -> PyResult<Program> {
+    let program = if let Ok(program) = conditions.extract::<Program>() {
+        program
+    } else {
+        let py = conditions.py();
+        let list = PyList::empty_bound(py);
+        for c in conditions.iter()? {
+            let c = c?;
+            let args = c.downcast::<PyTuple>()?;
+            list.append(PyList::new_bound(py, args.iter()))?;
+        }
+        py.get_type_bound::<Program>()
+            .call_method1("to", (list,))?
+            .extract::<Program>()?
+    };
+    let mut a = make_allocator(LIMIT_HEAP);
+    let conditions = node_from_bytes_backrefs(&mut a, program.as_slice())?;
+    let canonical = native_canonicalize_conditions(&mut a, conditions, flags)?;
+    Ok(Program::new(node_to_bytes(&a, canonical)?.into()))
+}
+
 // this is like a CoinSpend but with references to the puzzle and solution,
 // rather than owning them
 type CoinSpendRef = (Coin, PyBackedBytes, PyBackedBytes);
@@ -214,7 +570,23 @@ fn convert_list_of_tuples(spends: &Bound<PyAny>) -> PyResult<Vec<CoinSpendRef>>
 #[pyfunction]
 fn solution_generator<'p>(py: Python<'p>, spends: &Bound<PyAny>) -> PyResult<Bound<'p, PyBytes>> {
     let spends = convert_list_of_tuples(spends)?;
-    Ok(PyBytes::new_bound(py, &native_solution_generator(spends)?))
+    let generator = py.allow_threads(|| native_solution_generator(spends))?;
+    Ok(PyBytes::new_bound(py, &generator))
+}
+
+// like solution_generator(), but takes the CoinSpend objects as they are,
+// borrowing their (already serialized) puzzles and solutions
+#[pyfunction]
+fn solution_generator_from_coin_spends<'p>(
+    py: Python<'p>,
+    spends: Vec<PyRef<'p, CoinSpend>>,
+) -> PyResult<Bound<'p, PyBytes>> {
+    let spends: Vec<(Coin, &[u8], &[u8])> = spends
+        .iter()
+        .map(|cs| (cs.coin, cs.puzzle_reveal.as_slice(), cs.solution.as_slice()))
+        .collect();
+    let generator = py.allow_threads(|| native_solution_generator(spends))?;
+    Ok(PyBytes::new_bound(py, &generator))
 }
 
 #[pyfunction]
@@ -229,6 +601,630 @@ fn solution_generator_backrefs<'p>(
     ))
 }
 
+#[pyfunction]
+#[pyo3(signature = (generator, block_refs, max_cost, flags=0))]
+fn compress_generator<'p>(
+    py: Python<'p>,
+    generator: &[u8],
+    block_refs: Vec<Vec<u8>>,
+    max_cost: Cost,
+    flags: u32,
+) -> PyResult<Bound<'p, PyBytes>> {
+    let compressed =
+        py.allow_threads(|| native_compress_generator(generator, &block_refs, max_cost, flags))?;
+    Ok(PyBytes::new_bound(py, &compressed))
+}
+
+#[pyfunction]
+fn decompress_generator<'p>(py: Python<'p>, generator: &[u8]) -> PyResult<Bound<'p, PyBytes>> {
+    let decompressed = py.allow_threads(|| native_decompress_generator(generator))?;
+    Ok(PyBytes::new_bound(py, &decompressed))
+}
+
+// returns the total cost and the byte cost of the block generator, raising
+// ValidationError if it fails
+#[pyfunction]
+fn get_block_cost(
+    py: Python<'_>,
+    generator: &[u8],
+    block_refs: Vec<Vec<u8>>,
+    flags: u32,
+    constants: &ConsensusConstants,
+) -> PyResult<(u64, u64)> {
+    let cost = py.allow_threads(|| {
+        native_get_block_cost(generator, &block_refs, flags | LIMIT_HEAP, constants)
+    })?;
+    Ok((cost.cost, cost.byte_cost))
+}
+
+#[pyfunction]
+fn build_block_generator<'p>(
+    py: Python<'p>,
+    spends: &Bound<PyAny>,
+    max_cost: Cost,
+) -> PyResult<(Bound<'p, PyBytes>, Cost, Vec<usize>)> {
+    let spends = convert_list_of_tuples(spends)?;
+    let (generator, cost, included) =
+        py.allow_threads(|| native_build_block_generator(spends, max_cost))?;
+    Ok((PyBytes::new_bound(py, &generator), cost, included))
+}
+
+// returns the size of the generator, without and with back references
+#[pyfunction]
+fn estimate_generator_size(spends: &Bound<PyAny>) -> PyResult<(u64, u64)> {
+    let spends = convert_list_of_tuples(spends)?;
+    Ok(native_estimate_generator_size(spends))
+}
+
+#[pyfunction]
+fn estimate_cost(
+    py: Python<'_>,
+    spends: &Bound<PyAny>,
+    constants: &ConsensusConstants,
+) -> PyResult<Cost> {
+    let spends = convert_list_of_tuples(spends)?;
+    Ok(py.allow_threads(|| native_estimate_cost(spends, constants))?)
+}
+
+// returns the payload of a message of the specified type, as the
+// corresponding streamable class
+#[pyfunction]
+fn parse_message(
+    py: Python<'_>,
+    msg_type: ProtocolMessageTypes,
+    data: &[u8],
+) -> PyResult<PyObject> {
+    Ok(native_parse_message(msg_type, data)?.into_python(py))
+}
+
+// limits the size of repr() of streamable objects, for every type. Passing no
+// limits restores the full repr()
+#[pyfunction]
+#[pyo3(signature = (max_items=None, max_depth=None, max_hex_len=None))]
+pub fn set_repr_limits(
+    max_items: Option<usize>,
+    max_depth: Option<usize>,
+    max_hex_len: Option<usize>,
+) {
+    native_set_repr_limits(ReprLimits {
+        max_items,
+        max_depth,
+        max_hex_len,
+    });
+}
+
+// deterministically generates a random instance of the streamable class with
+// the specified name, for property-based tests
+#[cfg(feature = "test_support")]
+#[pyfunction]
+fn random_instance(py: Python<'_>, name: &str, seed: u64) -> PyResult<PyObject> {
+    native_random_instance(py, name, seed)
+}
+
+#[pyfunction]
+fn hash_coin_states(states: Vec<CoinState>) -> Bytes32 {
+    native_hash_coin_states(&states)
+}
+
+// bech32m addresses, like the "xch1..." address of a puzzle hash. Decoding
+// validates the checksum and the prefix
+#[pyfunction]
+fn encode_address(data: &[u8], prefix: &str) -> String {
+    native_encode_address(data, prefix)
+}
+
+#[pyfunction]
+fn decode_address<'p>(py: Python<'p>, address: &str, prefix: &str) -> PyResult<Bound<'p, PyBytes>> {
+    let data =
+        native_decode_address(address, prefix).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(PyBytes::new_bound(py, &data))
+}
+
+// returns None if the spend bundle passes the checks, otherwise the error code
+// and the coin ID of the spend that failed (if any)
+#[pyfunction]
+fn cheap_checks(
+    py: Python<'_>,
+    spend_bundle: &SpendBundle,
+    constants: &ConsensusConstants,
+    max_cost: u64,
+) -> Option<(u32, Option<Bytes32>)> {
+    py.allow_threads(|| native_cheap_checks(spend_bundle, constants, max_cost))
+        .err()
+        .map(|e| (u32::from(e.code), e.coin_id))
+}
+
+// the items sorted in the order they should be evicted from a full mempool
+#[pyfunction]
+fn mempool_eviction_order(mut items: Vec<MempoolItem>) -> Vec<MempoolItem> {
+    items.sort_by(eviction_order);
+    items
+}
+
+// the items sorted in the order they should be considered for a block
+#[pyfunction]
+fn mempool_block_order(mut items: Vec<MempoolItem>) -> Vec<MempoolItem> {
+    items.sort_by(block_inclusion_order);
+    items
+}
+
+#[pyfunction]
+fn condition_cost(opcode: u16) -> u64 {
+    native_condition_cost(opcode)
+}
+
+#[pyfunction]
+fn condition_cost_table() -> HashMap<u16, u64> {
+    native_condition_cost_table().into_iter().collect()
+}
+
+#[pyfunction]
+fn agg_sig_additional_data(constants: &ConsensusConstants, opcode: u16) -> Option<Bytes32> {
+    native_agg_sig_additional_data(constants, opcode)
+}
+
+#[pyfunction]
+fn messages_to_sign(
+    conds: &OwnedSpendBundleConditions,
+    constants: &ConsensusConstants,
+) -> Vec<(PublicKey, Bytes)> {
+    native_messages_to_sign(conds, constants)
+}
+
+#[pyfunction]
+#[pyo3(signature = (block, constants, conds=None))]
+fn validate_block_signatures(
+    block: &FullBlock,
+    constants: &ConsensusConstants,
+    conds: Option<&OwnedSpendBundleConditions>,
+) -> PyResult<()> {
+    Ok(native_validate_block_signatures(block, constants, conds)?)
+}
+
+#[pyfunction]
+fn finish_block(
+    unfinished: &UnfinishedBlock,
+    ip: &NewInfusionPointVDF,
+    finished_sub_slots: Vec<EndOfSubSlotBundle>,
+    prev: Option<&BlockRecord>,
+    difficulty: u64,
+    is_transaction_block: bool,
+) -> PyResult<FullBlock> {
+    Ok(native_finish_block(
+        unfinished,
+        ip,
+        finished_sub_slots,
+        prev,
+        difficulty,
+        is_transaction_block,
+    )?)
+}
+
+#[pyfunction]
+#[pyo3(signature = (bundles, constants, cache=None, flags=0))]
+fn verify_spend_bundle_signatures(
+    bundles: Vec<SpendBundle>,
+    constants: &ConsensusConstants,
+    mut cache: Option<PyRefMut<'_, BlsCache>>,
+    flags: u32,
+) -> Vec<bool> {
+    native_verify_spend_bundle_signatures(
+        &bundles,
+        constants,
+        cache.as_deref_mut(),
+        flags | LIMIT_HEAP,
+    )
+}
+
+// runs the spend bundle and verifies its signature, raising ValidationError
+// if either fails. metrics, if set, is called with the time spent in each step
+// (see timings_to_dict()). max_cost_per_spend, if set, is the limit on the
+// cost of each individual spend
+#[pyfunction]
+#[pyo3(signature = (bundle, max_cost, constants, flags=0, metrics=None, max_cost_per_spend=None))]
+fn validate_clvm_and_signature(
+    py: Python<'_>,
+    bundle: &SpendBundle,
+    max_cost: Cost,
+    constants: &ConsensusConstants,
+    flags: u32,
+    metrics: Option<&Bound<PyAny>>,
+    max_cost_per_spend: Option<Cost>,
+) -> PyResult<Vec<OwnedSpendBundleConditions>> {
+    let (conds, timings) = native_validate_clvm_and_signature(
+        bundle,
+        max_cost,
+        max_cost_per_spend,
+        constants,
+        flags | LIMIT_HEAP,
+    )
+    .map_err(|e| match e {
+        ConsensusError::Validation(e) => PyErr::from(e),
+        e => PyErr::from(e),
+    })?;
+    if let Some(metrics) = metrics {
+        metrics.call1((timings_to_dict(py, &timings)?,))?;
+    }
+    Ok(conds)
+}
+
+// key_lookup is called with every (public key, message) pair that needs to be
+// signed. It returns either the secret key of the public key, in which case the
+// message is signed with it, or the signature itself
+#[pyfunction]
+#[pyo3(signature = (coin_spends, key_lookup, constants, flags=0))]
+fn sign_coin_spends(
+    py: Python<'_>,
+    coin_spends: Vec<CoinSpend>,
+    key_lookup: &Bound<'_, PyAny>,
+    constants: &ConsensusConstants,
+    flags: u32,
+) -> PyResult<SpendBundle> {
+    native_sign_coin_spends(coin_spends, constants, flags | LIMIT_HEAP, |pk, msg| {
+        let ret = key_lookup.call1((*pk, PyBytes::new_bound(py, msg)))?;
+        if let Ok(sk) = ret.extract::<SecretKey>() {
+            Ok(chia_bls::sign(&sk, msg))
+        } else if let Ok(sig) = ret.extract::<Signature>() {
+            Ok(sig)
+        } else {
+            Err(PyValueError::new_err(format!(
+                "no secret key for public key {}",
+                hex::encode(pk.to_bytes())
+            )))
+        }
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (coins, target_amount, strategy="knapsack", max_coin_count=500))]
+fn select_coins(
+    py: Python<'_>,
+    coins: Vec<(Coin, u32)>,
+    target_amount: u64,
+    strategy: &str,
+    max_coin_count: usize,
+) -> PyResult<Vec<Coin>> {
+    let strategy = match strategy {
+        "knapsack" => CoinSelectionStrategy::Knapsack,
+        "smallest_first" => CoinSelectionStrategy::SmallestFirst,
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "unknown coin selection strategy: {strategy}"
+            )));
+        }
+    };
+    Ok(py.allow_threads(|| native_select_coins(&coins, target_amount, strategy, max_coin_count))?)
+}
+
+#[pyfunction]
+fn calculate_plot_id_pk(pool_public_key: &PublicKey, plot_public_key: &PublicKey) -> Bytes32 {
+    native_calculate_plot_id_pk(pool_public_key, plot_public_key)
+}
+
+#[pyfunction]
+fn calculate_plot_id_ph(
+    pool_contract_puzzle_hash: Bytes32,
+    plot_public_key: &PublicKey,
+) -> Bytes32 {
+    native_calculate_plot_id_ph(&pool_contract_puzzle_hash, plot_public_key)
+}
+
+#[pyfunction]
+fn get_plot_id(pos: &ProofOfSpace) -> Option<Bytes32> {
+    native_get_plot_id(pos)
+}
+
+#[pyfunction]
+#[pyo3(signature = (challenge_hash, cc_sp_vdf=None))]
+fn signage_point_hash(challenge_hash: Bytes32, cc_sp_vdf: Option<VDFInfo>) -> Bytes32 {
+    native_signage_point_hash(&challenge_hash, cc_sp_vdf.as_ref())
+}
+
+#[pyfunction]
+fn calculate_plot_filter_input(
+    plot_id: Bytes32,
+    challenge_hash: Bytes32,
+    sp_hash: Bytes32,
+) -> Bytes32 {
+    native_calculate_plot_filter_input(&plot_id, &challenge_hash, &sp_hash)
+}
+
+#[pyfunction]
+fn calculate_pos_challenge(plot_id: Bytes32, challenge_hash: Bytes32, sp_hash: Bytes32) -> Bytes32 {
+    native_calculate_pos_challenge(&plot_id, &challenge_hash, &sp_hash)
+}
+
+#[pyfunction]
+fn passes_plot_filter(
+    prefix_bits: u8,
+    plot_id: Bytes32,
+    challenge_hash: Bytes32,
+    sp_hash: Bytes32,
+) -> bool {
+    native_passes_plot_filter(prefix_bits, &plot_id, &challenge_hash, &sp_hash)
+}
+
+#[pyfunction]
+fn filter_coin_states(
+    py: Python<'_>,
+    states: Vec<CoinState>,
+    filters: CoinStateFilters,
+    min_height: u32,
+) -> Vec<CoinState> {
+    py.allow_threads(|| native_filter_coin_states(states, &filters, min_height))
+}
+
+// header_hashes is either a dict mapping heights to header hashes, or a
+// function taking a height and returning its header hash (or None)
+#[pyfunction]
+#[pyo3(signature = (puzzle_hashes, coin_states, peak_height, peak_header_hash, header_hashes, max_size=DEFAULT_MAX_RESPONSE_SIZE))]
+fn puzzle_state_responses(
+    puzzle_hashes: Vec<Bytes32>,
+    coin_states: Vec<CoinState>,
+    peak_height: u32,
+    peak_header_hash: Bytes32,
+    header_hashes: &Bound<'_, PyAny>,
+    max_size: usize,
+) -> PyResult<Vec<RespondPuzzleState>> {
+    if let Ok(header_hashes) = header_hashes.downcast::<PyDict>() {
+        native_puzzle_state_responses(
+            &puzzle_hashes,
+            coin_states,
+            peak_height,
+            peak_header_hash,
+            max_size,
+            |height| {
+                header_hashes
+                    .get_item(height)?
+                    .map(|h| h.extract::<Bytes32>())
+                    .transpose()
+            },
+        )
+    } else {
+        native_puzzle_state_responses(
+            &puzzle_hashes,
+            coin_states,
+            peak_height,
+            peak_header_hash,
+            max_size,
+            |height| header_hashes.call1((height,))?.extract::<Option<Bytes32>>(),
+        )
+    }
+}
+
+// lookup is either a dict mapping header hashes to block records, or a
+// function taking a header hash and returning the block record (or None).
+// Returns -1 if the chains have no block in common, like
+// find_fork_point_in_chain()
+#[pyfunction]
+fn find_fork_point(
+    old_peak: &BlockRecord,
+    new_peak: &BlockRecord,
+    lookup: &Bound<'_, PyAny>,
+) -> PyResult<i64> {
+    let fork = if let Ok(blocks) = lookup.downcast::<PyDict>() {
+        native_find_fork_point(old_peak, new_peak, |hash| {
+            blocks
+                .get_item(*hash)?
+                .map(|b| b.extract::<BlockRecord>())
+                .transpose()
+        })
+    } else {
+        native_find_fork_point(old_peak, new_peak, |hash| {
+            lookup.call1((*hash,))?.extract::<Option<BlockRecord>>()
+        })
+    }?;
+    Ok(fork.map_or(-1, i64::from))
+}
+
+// returns 1 if the chain ending in a is preferred, -1 if the chain ending in b
+// is preferred and 0 if they're equivalent
+#[pyfunction]
+fn compare_peaks(a: &BlockRecord, b: &BlockRecord) -> i32 {
+    native_compare_peaks(a, b) as i32
+}
+
+// blocks is a dict or a lookup function, like for find_fork_point()
+#[pyfunction]
+fn calculate_pool_reward(height: u32) -> u64 {
+    native_calculate_pool_reward(height)
+}
+
+#[pyfunction]
+fn calculate_base_farmer_reward(height: u32) -> u64 {
+    native_calculate_base_farmer_reward(height)
+}
+
+#[pyfunction]
+fn create_pool_coin(
+    block_height: u32,
+    puzzle_hash: Bytes32,
+    reward: u64,
+    constants: &ConsensusConstants,
+) -> Coin {
+    native_create_pool_coin(block_height, puzzle_hash, reward, constants)
+}
+
+#[pyfunction]
+fn create_farmer_coin(
+    block_height: u32,
+    puzzle_hash: Bytes32,
+    reward: u64,
+    constants: &ConsensusConstants,
+) -> Coin {
+    native_create_farmer_coin(block_height, puzzle_hash, reward, constants)
+}
+
+// blocks is either a dict of header hash -> BlockRecord or a function
+// returning the BlockRecord (or None) for a header hash
+#[pyfunction]
+fn expected_reward_coins(
+    constants: &ConsensusConstants,
+    prev_transaction_block: &BlockRecord,
+    blocks: &Bound<'_, PyAny>,
+) -> PyResult<Vec<Coin>> {
+    if let Ok(blocks) = blocks.downcast::<PyDict>() {
+        native_expected_reward_coins(constants, prev_transaction_block, |hash| {
+            blocks
+                .get_item(*hash)?
+                .map(|b| b.extract::<BlockRecord>())
+                .transpose()
+        })
+    } else {
+        native_expected_reward_coins(constants, prev_transaction_block, |hash| {
+            blocks.call1((*hash,))?.extract::<Option<BlockRecord>>()
+        })
+    }
+}
+
+#[pyfunction]
+fn validate_reward_claims(reward_claims: Vec<Coin>, expected: Vec<Coin>) -> PyResult<()> {
+    Ok(native_validate_reward_claims(&reward_claims, &expected)?)
+}
+
+#[pyfunction]
+fn validate_prefarm(
+    height: u32,
+    pool_puzzle_hash: Bytes32,
+    farmer_puzzle_hash: Bytes32,
+    constants: &ConsensusConstants,
+) -> PyResult<()> {
+    Ok(native_validate_prefarm(
+        height,
+        &pool_puzzle_hash,
+        &farmer_puzzle_hash,
+        constants,
+    )?)
+}
+
+#[pyfunction]
+fn prev_transaction_block_timestamps(
+    constants: &ConsensusConstants,
+    prev_transaction_block: &BlockRecord,
+    blocks: &Bound<'_, PyAny>,
+) -> PyResult<Vec<u64>> {
+    if let Ok(blocks) = blocks.downcast::<PyDict>() {
+        native_prev_transaction_block_timestamps(constants, prev_transaction_block, |hash| {
+            blocks
+                .get_item(*hash)?
+                .map(|b| b.extract::<BlockRecord>())
+                .transpose()
+        })
+    } else {
+        native_prev_transaction_block_timestamps(constants, prev_transaction_block, |hash| {
+            blocks.call1((*hash,))?.extract::<Option<BlockRecord>>()
+        })
+    }
+}
+
+#[pyfunction]
+fn validate_timestamp(
+    timestamp: u64,
+    prev_transaction_block_timestamps: Vec<u64>,
+    now: u64,
+    constants: &ConsensusConstants,
+) -> PyResult<()> {
+    Ok(native_validate_timestamp(
+        timestamp,
+        &prev_transaction_block_timestamps,
+        now,
+        constants,
+    )?)
+}
+
+#[pyfunction]
+fn make_sub_epoch_summary(
+    constants: &ConsensusConstants,
+    blocks: &Bound<'_, PyAny>,
+    blocks_included_height: u32,
+    prev_prev_block: &BlockRecord,
+    new_difficulty: Option<u64>,
+    new_sub_slot_iters: Option<u64>,
+) -> PyResult<SubEpochSummary> {
+    if let Ok(blocks) = blocks.downcast::<PyDict>() {
+        native_make_sub_epoch_summary(
+            constants,
+            blocks_included_height,
+            prev_prev_block,
+            new_difficulty,
+            new_sub_slot_iters,
+            |hash| {
+                blocks
+                    .get_item(*hash)?
+                    .map(|b| b.extract::<BlockRecord>())
+                    .transpose()
+            },
+        )
+    } else {
+        native_make_sub_epoch_summary(
+            constants,
+            blocks_included_height,
+            prev_prev_block,
+            new_difficulty,
+            new_sub_slot_iters,
+            |hash| blocks.call1((*hash,))?.extract::<Option<BlockRecord>>(),
+        )
+    }
+}
+
+#[pyfunction]
+fn validate_unfinished_header_block_linkage(
+    header: &UnfinishedHeaderBlock,
+    prev: Option<&BlockRecord>,
+    constants: &ConsensusConstants,
+    blocks: &Bound<'_, PyAny>,
+) -> PyResult<()> {
+    if let Ok(blocks) = blocks.downcast::<PyDict>() {
+        native_validate_unfinished_header_block_linkage(header, prev, constants, |hash| {
+            blocks
+                .get_item(*hash)?
+                .map(|b| b.extract::<BlockRecord>())
+                .transpose()
+        })
+    } else {
+        native_validate_unfinished_header_block_linkage(header, prev, constants, |hash| {
+            blocks.call1((*hash,))?.extract::<Option<BlockRecord>>()
+        })
+    }
+}
+
+#[pyfunction]
+fn validate_sp_signatures(
+    py: Python<'_>,
+    headers: Vec<UnfinishedHeaderBlock>,
+    prev: Option<&BlockRecord>,
+    constants: &ConsensusConstants,
+    blocks: &Bound<'_, PyAny>,
+) -> PyResult<()> {
+    let challenges = if let Ok(blocks) = blocks.downcast::<PyDict>() {
+        sub_slot_challenges(prev, constants, |hash| {
+            blocks
+                .get_item(*hash)?
+                .map(|b| b.extract::<BlockRecord>())
+                .transpose()
+        })
+    } else {
+        sub_slot_challenges(prev, constants, |hash| {
+            blocks.call1((*hash,))?.extract::<Option<BlockRecord>>()
+        })
+    }?;
+    Ok(py.allow_threads(|| native_validate_sp_signatures(&headers, challenges))?)
+}
+
+// only pre-checks the proofs of space, see precheck_header_blocks() in
+// chia-consensus
+#[pyfunction]
+#[pyo3(signature = (headers, constants, prev_context=None))]
+fn precheck_header_blocks(
+    py: Python<'_>,
+    headers: Vec<HeaderBlock>,
+    constants: &ConsensusConstants,
+    prev_context: Option<HeaderContext>,
+) -> PyResult<HeaderContext> {
+    let ctx = prev_context.unwrap_or_else(|| HeaderContext::genesis(constants));
+    Ok(py.allow_threads(|| native_precheck_header_blocks(&headers, constants, &ctx))?)
+}
+
 #[pyclass]
 struct AugSchemeMPL {}
 
@@ -313,6 +1309,86 @@ impl AugSchemeMPL {
     }
 }
 
+#[pyfunction]
+#[pyo3(signature = (pks, allow_infinity=false))]
+fn validate_public_keys(py: Python<'_>, pks: Vec<PublicKey>, allow_infinity: bool) -> Vec<bool> {
+    py.allow_threads(|| native_validate_public_keys(&pks, allow_infinity))
+}
+
+#[pyfunction]
+fn aggregate_public_keys(pks: Vec<PublicKey>) -> PublicKey {
+    native_aggregate_public_keys(&pks)
+}
+
+#[pyfunction]
+fn aggregate_public_keys_with_pop(
+    pks: Vec<PublicKey>,
+    pops: Vec<Signature>,
+) -> PyResult<PublicKey> {
+    Ok(native_aggregate_public_keys_with_pop(&pks, &pops)?)
+}
+
+#[pyfunction]
+fn pop_prove(sk: &SecretKey) -> Signature {
+    native_pop_prove(sk)
+}
+
+#[pyfunction]
+fn pop_verify(pk: &PublicKey, proof: &Signature) -> bool {
+    native_pop_verify(pk, proof)
+}
+
+// mode is the value of one of the wallet's SigningMode
+#[pyfunction]
+#[pyo3(signature = (sk, message, mode=SigningMode::Chip0002.as_str()))]
+fn sign_message(sk: &SecretKey, message: &[u8], mode: &str) -> PyResult<Signature> {
+    Ok(native_sign_message(sk, message, mode.parse()?))
+}
+
+#[pyfunction]
+#[pyo3(signature = (pk, message, signature, mode=SigningMode::Chip0002.as_str()))]
+fn verify_message(
+    pk: &PublicKey,
+    message: &[u8],
+    signature: &Signature,
+    mode: &str,
+) -> PyResult<bool> {
+    Ok(native_verify_message(pk, message, signature, mode.parse()?))
+}
+
+fn standard_puzzle_hash(pk: &PublicKey) -> [u8; 32] {
+    puzzle_hash_for_pk(pk).into()
+}
+
+#[pyclass(name = "DerivationCache")]
+struct PyDerivationCache(DerivationCache);
+
+#[pymethods]
+impl PyDerivationCache {
+    #[new]
+    pub fn init(intermediate_pk: PublicKey) -> Self {
+        Self(DerivationCache::new(intermediate_pk, standard_puzzle_hash))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    // derives the unhardened child keys [start, start + count) of the
+    // intermediate key, along with their standard transaction puzzle hashes
+    pub fn derive(
+        &mut self,
+        py: Python<'_>,
+        start: u32,
+        count: u32,
+    ) -> Vec<(u32, PublicKey, Bytes32)> {
+        let keys = py.allow_threads(|| self.0.derive(start, count));
+        keys.into_iter()
+            .map(|(idx, pk, ph)| (idx, pk, ph.into()))
+            .collect()
+    }
+}
+
 #[pyfunction]
 fn supports_fast_forward(spend: &CoinSpend) -> bool {
     // the test function just attempts the rebase onto a dummy parent coin
@@ -364,16 +1440,66 @@ fn fast_forward_singleton<'p>(
     ))
 }
 
+#[pyfunction]
+fn fast_forward_chain<'p>(
+    py: Python<'p>,
+    spend: &CoinSpend,
+    new_lineage: Vec<(Coin, Coin)>,
+) -> PyResult<Bound<'p, PyBytes>> {
+    let mut a = make_allocator(LIMIT_HEAP);
+    let puzzle = node_from_bytes(&mut a, spend.puzzle_reveal.as_slice())?;
+    let solution = node_from_bytes(&mut a, spend.solution.as_slice())?;
+
+    let new_solution = native_ff_chain(&mut a, puzzle, solution, &spend.coin, &new_lineage)?;
+    Ok(PyBytes::new_bound(
+        py,
+        node_to_bytes(&a, new_solution)?.as_slice(),
+    ))
+}
+
+// the coin IDs spent and created by the spends, by hint and by puzzle hash
+#[pyfunction]
+fn index_spends(py: Python<'_>, conds: &OwnedSpendBundleConditions) -> (CoinIndex, CoinIndex) {
+    py.allow_threads(|| native_index_spends(conds))
+}
+
 #[pymodule]
 pub fn gold_rs(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     // generator functions
     m.add_function(wrap_pyfunction!(run_block_generator, m)?)?;
     m.add_function(wrap_pyfunction!(run_block_generator2, m)?)?;
+    m.add_function(wrap_pyfunction!(run_block_generator_with_resolver, m)?)?;
+    m.add_function(wrap_pyfunction!(run_block_generator2_with_resolver, m)?)?;
+    m.add_function(wrap_pyfunction!(block_generator_refs, m)?)?;
+    m.add_function(wrap_pyfunction!(profile_block_generator, m)?)?;
     m.add_function(wrap_pyfunction!(run_puzzle, m)?)?;
+    m.add_function(wrap_pyfunction!(run_puzzle_at_height, m)?)?;
+    m.add_function(wrap_pyfunction!(analyze_spend, m)?)?;
+    m.add_function(wrap_pyfunction!(run_spend, m)?)?;
+    m.add_function(wrap_pyfunction!(run_spend_bundle, m)?)?;
+    m.add_function(wrap_pyfunction!(spend_bundle_to_dot, m)?)?;
+    m.add_function(wrap_pyfunction!(coin_announcement_id, m)?)?;
+    m.add_function(wrap_pyfunction!(puzzle_announcement_id, m)?)?;
+    m.add_function(wrap_pyfunction!(check_announcements, m)?)?;
+    m.add_function(wrap_pyfunction!(conditions_to_program, m)?)?;
+    m.add_class::<ValidationPolicy>()?;
+    m.add_class::<SpendContext>()?;
     m.add_function(wrap_pyfunction!(solution_generator, m)?)?;
     m.add_function(wrap_pyfunction!(solution_generator_backrefs, m)?)?;
+    m.add_function(wrap_pyfunction!(solution_generator_from_coin_spends, m)?)?;
+    m.add_function(wrap_pyfunction!(build_block_generator, m)?)?;
+    m.add_function(wrap_pyfunction!(estimate_generator_size, m)?)?;
+    m.add_function(wrap_pyfunction!(estimate_cost, m)?)?;
+    m.add_function(wrap_pyfunction!(compress_generator, m)?)?;
+    m.add_function(wrap_pyfunction!(decompress_generator, m)?)?;
+    m.add_function(wrap_pyfunction!(get_block_cost, m)?)?;
     m.add_function(wrap_pyfunction!(supports_fast_forward, m)?)?;
     m.add_function(wrap_pyfunction!(fast_forward_singleton, m)?)?;
+    m.add_function(wrap_pyfunction!(fast_forward_chain, m)?)?;
+    m.add_function(wrap_pyfunction!(condition_cost, m)?)?;
+    m.add_function(wrap_pyfunction!(condition_cost_table, m)?)?;
+    m.add("CREATE_COIN_COST", CREATE_COIN_COST)?;
+    m.add("AGG_SIG_COST", AGG_SIG_COST)?;
     m.add_class::<OwnedSpendBundleConditions>()?;
     m.add(
         "ELIGIBLE_FOR_DEDUP",
@@ -384,12 +1510,100 @@ pub fn gold_rs(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
         chia_consensus::gen::conditions::ELIGIBLE_FOR_FF,
     )?;
     m.add_class::<OwnedSpend>()?;
+    m.add_function(wrap_pyfunction!(agg_sig_additional_data, m)?)?;
+    m.add_function(wrap_pyfunction!(messages_to_sign, m)?)?;
+    m.add_class::<NetworkInfo>()?;
+    m.add_function(wrap_pyfunction!(validate_block_signatures, m)?)?;
+    m.add_class::<BlockValidator>()?;
+    m.add_function(wrap_pyfunction!(finish_block, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_spend_bundle_signatures, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_clvm_and_signature, m)?)?;
+    m.add_function(wrap_pyfunction!(sign_coin_spends, m)?)?;
+
+    // mempool checks
+    m.add_function(wrap_pyfunction!(cheap_checks, m)?)?;
+
+    // mempool items
+    m.add_class::<MempoolItem>()?;
+    m.add_function(wrap_pyfunction!(mempool_eviction_order, m)?)?;
+    m.add_function(wrap_pyfunction!(mempool_block_order, m)?)?;
+
+    // coin selection
+    m.add_function(wrap_pyfunction!(select_coins, m)?)?;
+
+    // coin store
+    m.add_class::<CoinStore>()?;
+    m.add_function(wrap_pyfunction!(filter_coin_states, m)?)?;
+    m.add_function(wrap_pyfunction!(puzzle_state_responses, m)?)?;
+
+    // reorgs
+    m.add_function(wrap_pyfunction!(find_fork_point, m)?)?;
+    m.add_function(wrap_pyfunction!(compare_peaks, m)?)?;
+
+    // block rewards
+    m.add_function(wrap_pyfunction!(calculate_pool_reward, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_base_farmer_reward, m)?)?;
+    m.add_function(wrap_pyfunction!(create_pool_coin, m)?)?;
+    m.add_function(wrap_pyfunction!(create_farmer_coin, m)?)?;
+    m.add_function(wrap_pyfunction!(expected_reward_coins, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_reward_claims, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_prefarm, m)?)?;
+
+    // timestamps
+    m.add_function(wrap_pyfunction!(prev_transaction_block_timestamps, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_timestamp, m)?)?;
+
+    // sub-epochs
+    m.add_function(wrap_pyfunction!(make_sub_epoch_summary, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        validate_unfinished_header_block_linkage,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(validate_sp_signatures, m)?)?;
+    m.add_function(wrap_pyfunction!(precheck_header_blocks, m)?)?;
+    m.add_class::<HeaderContext>()?;
+    m.add_class::<WeightProofBuilder>()?;
+
+    // proofs of space
+    m.add_function(wrap_pyfunction!(calculate_plot_id_pk, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_plot_id_ph, m)?)?;
+    m.add_function(wrap_pyfunction!(get_plot_id, m)?)?;
+    m.add_function(wrap_pyfunction!(signage_point_hash, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_plot_filter_input, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_pos_challenge, m)?)?;
+    m.add_function(wrap_pyfunction!(passes_plot_filter, m)?)?;
+
+    // signage point clock
+    m.add_class::<SignagePoint>()?;
+    m.add_class::<SignagePointIterator>()?;
+
+    // wallet subscriptions
+    m.add_class::<SubscriptionManager>()?;
+    m.add_function(wrap_pyfunction!(index_spends, m)?)?;
 
     // constants
     m.add_class::<ConsensusConstants>()?;
+    m.add_class::<ForkSchedule>()?;
+
+    // test blocks
+    #[cfg(feature = "test_support")]
+    m.add_class::<BlockTools>()?;
+
+    // simulator
+    #[cfg(feature = "test_support")]
+    m.add_class::<Simulator>()?;
+
+    // chain database
+    #[cfg(feature = "test_support")]
+    m.add_class::<ChainDb>()?;
+    #[cfg(feature = "test_support")]
+    m.add_class::<ChainDbIterator>()?;
 
     // merkle tree
     m.add_class::<MerkleSet>()?;
+    m.add_class::<MerkleBlob>()?;
+    m.add_class::<ProofOfInclusion>()?;
+    m.add_class::<ProofOfInclusionLayer>()?;
     m.add_function(wrap_pyfunction!(confirm_included_already_hashed, m)?)?;
     m.add_function(wrap_pyfunction!(confirm_not_included_already_hashed, m)?)?;
 
@@ -409,6 +1623,24 @@ pub fn gold_rs(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     m.add("ALLOW_BACKREFS", ALLOW_BACKREFS)?;
     m.add("ANALYZE_SPENDS", ANALYZE_SPENDS)?;
     m.add("DISALLOW_INFINITY_G1", DISALLOW_INFINITY_G1)?;
+    m.add("SKIP_AGG_SIG_CHECKS", SKIP_AGG_SIG_CHECKS)?;
+
+    // protocol messages
+    m.add_function(wrap_pyfunction!(parse_message, m)?)?;
+    #[cfg(feature = "test_support")]
+    m.add_function(wrap_pyfunction!(random_instance, m)?)?;
+    m.add_function(wrap_pyfunction!(set_repr_limits, m)?)?;
+    m.add_function(wrap_pyfunction!(hash_coin_states, m)?)?;
+    m.add_function(wrap_pyfunction!(encode_address, m)?)?;
+    m.add_function(wrap_pyfunction!(decode_address, m)?)?;
+
+    // rate limits
+    m.add_class::<RateLimiter>()?;
+
+    // fixed size bytes
+    m.add_class::<PyBytes48>()?;
+    m.add_class::<PyBytes96>()?;
+    m.add_class::<PyBytes100>()?;
 
     // Chia classes
     m.add_class::<Coin>()?;
@@ -494,6 +1726,61 @@ pub fn gold_rs(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     m.add_class::<RespondCoinState>()?;
     m.add_class::<RejectCoinState>()?;
 
+    // farmer protocol
+    m.add_class::<NewSignagePoint>()?;
+    m.add_class::<DeclareProofOfSpace>()?;
+    m.add_class::<RequestSignedValues>()?;
+    m.add_class::<FarmingInfo>()?;
+    m.add_class::<SignedValues>()?;
+
+    // harvester protocol
+    m.add_class::<PoolDifficulty>()?;
+    m.add_class::<HarvesterHandshake>()?;
+    m.add_class::<NewSignagePointHarvester>()?;
+    m.add_class::<ProofOfSpaceFeeInfo>()?;
+    m.add_class::<NewProofOfSpace>()?;
+    m.add_class::<SignatureRequestSourceData>()?;
+    m.add_class::<RequestSignatures>()?;
+    m.add_class::<RespondSignatures>()?;
+    m.add_class::<Plot>()?;
+    m.add_class::<RequestPlots>()?;
+    m.add_class::<RespondPlots>()?;
+    m.add_class::<PlotSyncIdentifier>()?;
+    m.add_class::<PlotSyncStart>()?;
+    m.add_class::<PlotSyncPathList>()?;
+    m.add_class::<PlotSyncPlotList>()?;
+    m.add_class::<PlotSyncDone>()?;
+    m.add_class::<PlotSyncError>()?;
+    m.add_class::<PlotSyncResponse>()?;
+
+    // timelord protocol
+    m.add_class::<NewPeakTimelord>()?;
+    m.add_class::<NewUnfinishedBlockTimelord>()?;
+    m.add_class::<NewInfusionPointVDF>()?;
+    m.add_class::<NewSignagePointVDF>()?;
+    m.add_class::<NewEndOfSubSlotVDF>()?;
+    m.add_class::<RequestCompactProofOfTime>()?;
+    m.add_class::<RespondCompactProofOfTime>()?;
+
+    // introducer protocol
+    m.add_class::<RequestPeersIntroducer>()?;
+    m.add_class::<RespondPeersIntroducer>()?;
+
+    // pool protocol
+    m.add_class::<AuthenticationPayload>()?;
+    m.add_class::<GetPoolInfoResponse>()?;
+    m.add_class::<PostPartialPayload>()?;
+    m.add_class::<PostPartialRequest>()?;
+    m.add_class::<PostPartialResponse>()?;
+    m.add_class::<GetFarmerResponse>()?;
+    m.add_class::<PostFarmerPayload>()?;
+    m.add_class::<PostFarmerRequest>()?;
+    m.add_class::<PostFarmerResponse>()?;
+    m.add_class::<PutFarmerPayload>()?;
+    m.add_class::<PutFarmerRequest>()?;
+    m.add_class::<PutFarmerResponse>()?;
+    m.add_class::<PoolErrorResponse>()?;
+
     // full node protocol
     m.add_class::<NewPeak>()?;
     m.add_class::<NewTransaction>()?;
@@ -526,13 +1813,19 @@ pub fn gold_rs(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     // facilities from clvm_rs
 
     m.add_function(wrap_pyfunction!(run_chia_program, m)?)?;
+    m.add_function(wrap_pyfunction!(profile_chia_program, m)?)?;
     m.add("NO_UNKNOWN_OPS", NO_UNKNOWN_OPS)?;
     m.add("LIMIT_HEAP", LIMIT_HEAP)?;
     m.add("ENABLE_BLS_OPS_OUTSIDE_GUARD", ENABLE_BLS_OPS_OUTSIDE_GUARD)?;
 
     m.add_function(wrap_pyfunction!(serialized_length, m)?)?;
+    m.add_function(wrap_pyfunction!(check_canonical_clvm, m)?)?;
     m.add_function(wrap_pyfunction!(compute_merkle_set_root, m)?)?;
     m.add_function(wrap_pyfunction!(tree_hash, m)?)?;
+    m.add_function(wrap_pyfunction!(tree_hashes, m)?)?;
+    m.add_class::<PyTreeHasher>()?;
+    m.add_function(wrap_pyfunction!(assemble, m)?)?;
+    m.add_function(wrap_pyfunction!(disassemble, m)?)?;
     m.add_function(wrap_pyfunction!(get_puzzle_and_solution_for_coin, m)?)?;
 
     // facilities from chia-bls
@@ -543,6 +1836,47 @@ pub fn gold_rs(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     m.add_class::<SecretKey>()?;
     m.add_class::<AugSchemeMPL>()?;
     m.add_class::<BlsCache>()?;
+    m.add_class::<PyDerivationCache>()?;
+    m.add_class::<Keyring>()?;
+    m.add_function(wrap_pyfunction!(validate_public_keys, m)?)?;
+    m.add_function(wrap_pyfunction!(aggregate_public_keys, m)?)?;
+    m.add_function(wrap_pyfunction!(aggregate_public_keys_with_pop, m)?)?;
+    m.add_function(wrap_pyfunction!(pop_prove, m)?)?;
+    m.add_function(wrap_pyfunction!(pop_verify, m)?)?;
+    m.add_function(wrap_pyfunction!(sign_message, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_message, m)?)?;
+    m.add(
+        "CHIP_0002_SIGN_MESSAGE_PREFIX",
+        CHIP_0002_SIGN_MESSAGE_PREFIX,
+    )?;
+
+    // standard transaction puzzle
+    m.add_function(wrap_pyfunction!(puzzle_hash_for_pk, m)?)?;
+    m.add_function(wrap_pyfunction!(puzzle_for_pk, m)?)?;
+    m.add_function(wrap_pyfunction!(solution_for_conditions, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_synthetic_secret_key, m)?)?;
+    m.add_function(wrap_pyfunction!(calculate_synthetic_public_key, m)?)?;
+
+    // CAT and singleton lineage proofs
+    m.add_class::<PyLineageProof>()?;
+    m.add_function(wrap_pyfunction!(lineage_proof_for_parent, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_lineage_proof, m)?)?;
+
+    // plotNFTs
+    m.add_class::<PoolState>()?;
+    m.add_class::<PoolInnerPuzzle>()?;
+    m.add("SELF_POOLING", PoolSingletonState::SelfPooling as u8)?;
+    m.add("LEAVING_POOL", PoolSingletonState::LeavingPool as u8)?;
+    m.add("FARMING_TO_POOL", PoolSingletonState::FarmingToPool as u8)?;
+    m.add_function(wrap_pyfunction!(parse_pool_puzzle, m)?)?;
+    m.add_function(wrap_pyfunction!(solution_to_pool_state, m)?)?;
+    m.add_function(wrap_pyfunction!(pool_state_from_extra_data, m)?)?;
+
+    // clawbacks
+    m.add_function(wrap_pyfunction!(clawback_puzzle_hash, m)?)?;
+    m.add_function(wrap_pyfunction!(clawback_puzzle, m)?)?;
+    m.add_function(wrap_pyfunction!(clawback_solution, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_clawback_spend, m)?)?;
 
     Ok(())
 }
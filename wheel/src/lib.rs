@@ -2,5 +2,6 @@
 
 mod adapt_response;
 mod api;
+mod puzzles;
 mod run_generator;
 mod run_program;
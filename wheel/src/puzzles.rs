@@ -0,0 +1,296 @@
+use chia_bls::{PublicKey, SecretKey};
+use chia_consensus::lineage_proof::{
+    lineage_proof_for_parent as native_lineage_proof_for_parent,
+    verify_lineage_proof as native_verify_lineage_proof,
+};
+use chia_consensus::pool_puzzle::{
+    parse_pool_puzzle as native_parse_pool_puzzle,
+    pool_state_from_extra_data as native_pool_state_from_extra_data,
+    solution_to_pool_state as native_solution_to_pool_state, PoolInnerPuzzle,
+};
+use chia_protocol::{Bytes32, CoinSpend, PoolState, Program};
+use chia_puzzles::clawback::{
+    parse_clawback_spend as native_parse_clawback_spend, Clawback, ClawbackPath, P2OneOfManyArgs,
+    AUGMENTED_CONDITION_PUZZLE, P2_ONE_OF_MANY_PUZZLE, P2_PUZZLE_HASH_PUZZLE,
+};
+use chia_puzzles::standard::{StandardArgs, StandardSolution, STANDARD_PUZZLE};
+use chia_puzzles::{DeriveSynthetic, LineageProof};
+use clvm_traits::{FromNodePtr, ToClvm, ToNodePtr};
+use clvm_utils::{tree_hash, CurriedProgram};
+use clvmr::allocator::NodePtr;
+use clvmr::serde::node_from_bytes;
+use clvmr::Allocator;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::pyclass::CompareOp;
+
+// these are the native versions of the functions in
+// chia.wallet.puzzles.p2_delegated_puzzle_or_hidden_puzzle. The public key
+// passed in is the original public key, the synthetic key is derived from it
+// using the default hidden puzzle.
+
+#[pyfunction]
+pub fn puzzle_hash_for_pk(pk: &PublicKey) -> Bytes32 {
+    StandardArgs::curry_tree_hash(pk.derive_synthetic()).into()
+}
+
+#[pyfunction]
+pub fn puzzle_for_pk(pk: &PublicKey) -> PyResult<Program> {
+    let mut a = Allocator::new();
+    let puzzle = node_from_bytes(&mut a, &STANDARD_PUZZLE)?;
+    let curried = CurriedProgram {
+        program: puzzle,
+        args: StandardArgs::new(pk.derive_synthetic()),
+    }
+    .to_node_ptr(&mut a)
+    .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Program::from_node_ptr(&a, curried).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+// the solution spending a standard transaction coin by outputting the
+// conditions directly (as a quoted delegated puzzle)
+#[pyfunction]
+pub fn solution_for_conditions(conditions: &Program) -> PyResult<Program> {
+    let mut a = Allocator::new();
+    let conditions = conditions
+        .to_node_ptr(&mut a)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let solution = StandardSolution::<(u8, NodePtr), ()>::from_conditions(conditions)
+        .to_node_ptr(&mut a)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Program::from_node_ptr(&a, solution).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+pub fn calculate_synthetic_secret_key(sk: &SecretKey, hidden_puzzle_hash: Bytes32) -> SecretKey {
+    sk.derive_synthetic_hidden(&hidden_puzzle_hash.into())
+}
+
+#[pyfunction]
+pub fn calculate_synthetic_public_key(pk: &PublicKey, hidden_puzzle_hash: Bytes32) -> PublicKey {
+    pk.derive_synthetic_hidden(&hidden_puzzle_hash.into())
+}
+
+#[pyclass(name = "LineageProof", frozen)]
+#[derive(Clone)]
+pub struct PyLineageProof(LineageProof);
+
+#[pymethods]
+impl PyLineageProof {
+    #[new]
+    pub fn init(
+        parent_parent_coin_id: Bytes32,
+        parent_inner_puzzle_hash: Bytes32,
+        parent_amount: u64,
+    ) -> Self {
+        Self(LineageProof {
+            parent_parent_coin_id,
+            parent_inner_puzzle_hash,
+            parent_amount,
+        })
+    }
+
+    #[getter]
+    pub fn parent_parent_coin_id(&self) -> Bytes32 {
+        self.0.parent_parent_coin_id
+    }
+
+    #[getter]
+    pub fn parent_inner_puzzle_hash(&self) -> Bytes32 {
+        self.0.parent_inner_puzzle_hash
+    }
+
+    #[getter]
+    pub fn parent_amount(&self) -> u64 {
+        self.0.parent_amount
+    }
+
+    // the CLVM representation used in CAT and singleton solutions
+    pub fn to_program(&self) -> PyResult<Program> {
+        let mut a = Allocator::new();
+        let node = self
+            .0
+            .to_clvm(&mut a)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Program::from_node_ptr(&a, node).map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    pub fn __richcmp__(&self, other: &Self, op: CompareOp, py: Python<'_>) -> PyObject {
+        match op {
+            CompareOp::Eq => (self.0 == other.0).into_py(py),
+            CompareOp::Ne => (self.0 != other.0).into_py(py),
+            _ => py.NotImplemented(),
+        }
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+// the lineage proof the children of a CAT or singleton spend need to include
+// in their solutions
+#[pyfunction]
+pub fn lineage_proof_for_parent(parent_spend: &CoinSpend) -> PyResult<PyLineageProof> {
+    let mut a = Allocator::new();
+    Ok(PyLineageProof(native_lineage_proof_for_parent(
+        &mut a,
+        parent_spend,
+    )?))
+}
+
+// whether the lineage proof proves the parent of the coin being spent is a CAT
+// or singleton with the same asset ID or launcher ID
+#[pyfunction]
+pub fn verify_lineage_proof(proof: &PyLineageProof, coin_spend: &CoinSpend) -> PyResult<bool> {
+    let mut a = Allocator::new();
+    Ok(native_verify_lineage_proof(
+        &mut a,
+        &proof.0,
+        &coin_spend.coin,
+        &coin_spend.puzzle_reveal,
+    )?)
+}
+
+// parses the puzzle reveal of a plotNFT into the arguments of its pool member
+// or pool waiting room inner puzzle
+#[pyfunction]
+pub fn parse_pool_puzzle(puzzle: &Program) -> PyResult<PoolInnerPuzzle> {
+    let mut a = Allocator::new();
+    let puzzle = puzzle
+        .to_node_ptr(&mut a)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(native_parse_pool_puzzle(&a, puzzle)?)
+}
+
+// the pool state set by a plotNFT launcher or travel spend, None for other
+// spends
+#[pyfunction]
+pub fn solution_to_pool_state(coin_spend: &CoinSpend) -> PyResult<Option<PoolState>> {
+    let mut a = Allocator::new();
+    Ok(native_solution_to_pool_state(&mut a, coin_spend)?)
+}
+
+#[pyfunction]
+pub fn pool_state_from_extra_data(extra_data: &Program) -> PyResult<Option<PoolState>> {
+    let mut a = Allocator::new();
+    let extra_data = extra_data
+        .to_node_ptr(&mut a)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(native_pool_state_from_extra_data(&a, extra_data))
+}
+
+// these are the native versions of chia.wallet.puzzles.clawback.drivers. The
+// recipient can claim a clawback coin once timelock seconds have passed, until
+// then the sender can claw it back.
+
+fn clawback(
+    timelock: u64,
+    sender_puzzle_hash: Bytes32,
+    recipient_puzzle_hash: Bytes32,
+) -> PyResult<Clawback> {
+    if timelock < 1 {
+        return Err(PyValueError::new_err("Timelock must be at least 1 second"));
+    }
+    Ok(Clawback {
+        timelock,
+        sender_puzzle_hash,
+        recipient_puzzle_hash,
+    })
+}
+
+#[pyfunction]
+pub fn clawback_puzzle_hash(
+    timelock: u64,
+    sender_puzzle_hash: Bytes32,
+    recipient_puzzle_hash: Bytes32,
+) -> PyResult<Bytes32> {
+    Ok(
+        clawback(timelock, sender_puzzle_hash, recipient_puzzle_hash)?
+            .puzzle_hash()
+            .into(),
+    )
+}
+
+#[pyfunction]
+pub fn clawback_puzzle(
+    timelock: u64,
+    sender_puzzle_hash: Bytes32,
+    recipient_puzzle_hash: Bytes32,
+) -> PyResult<Program> {
+    let clawback = clawback(timelock, sender_puzzle_hash, recipient_puzzle_hash)?;
+    let mut a = Allocator::new();
+    let puzzle = node_from_bytes(&mut a, &P2_ONE_OF_MANY_PUZZLE)?;
+    let curried = CurriedProgram {
+        program: puzzle,
+        args: P2OneOfManyArgs::new(clawback.merkle_root()),
+    }
+    .to_node_ptr(&mut a)
+    .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Program::from_node_ptr(&a, curried).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+// the solution spending the clawback coin with inner_puzzle. If it's the
+// recipient's puzzle, this claims the coin, if it's the sender's puzzle this
+// claws it back
+#[pyfunction]
+pub fn clawback_solution(
+    timelock: u64,
+    sender_puzzle_hash: Bytes32,
+    recipient_puzzle_hash: Bytes32,
+    inner_puzzle: &Program,
+    inner_solution: &Program,
+) -> PyResult<Program> {
+    let clawback = clawback(timelock, sender_puzzle_hash, recipient_puzzle_hash)?;
+    let mut a = Allocator::new();
+    let inner_puzzle = inner_puzzle
+        .to_node_ptr(&mut a)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let inner_puzzle_hash: Bytes32 = tree_hash(&a, inner_puzzle).into();
+    let inner_solution = inner_solution
+        .to_node_ptr(&mut a)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let solution = if inner_puzzle_hash == sender_puzzle_hash {
+        let p2_puzzle_hash = node_from_bytes(&mut a, &P2_PUZZLE_HASH_PUZZLE)?;
+        clawback
+            .clawback_solution(p2_puzzle_hash, inner_puzzle, inner_solution)
+            .to_node_ptr(&mut a)
+    } else if inner_puzzle_hash == recipient_puzzle_hash {
+        let augmented_condition = node_from_bytes(&mut a, &AUGMENTED_CONDITION_PUZZLE)?;
+        clawback
+            .claim_solution(augmented_condition, inner_puzzle, inner_solution)
+            .to_node_ptr(&mut a)
+    } else {
+        return Err(PyValueError::new_err("Invalid Clawback inner puzzle."));
+    }
+    .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Program::from_node_ptr(&a, solution).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+// detects a clawback spend. Returns the merkle root, the timelock (None when
+// the sender claws the coin back) and the puzzle hash of whoever spends it, or
+// None if it's not a clawback spend
+#[pyfunction]
+pub fn parse_clawback_spend(
+    puzzle: &Program,
+    solution: &Program,
+) -> PyResult<Option<(Bytes32, Option<u64>, Bytes32)>> {
+    let mut a = Allocator::new();
+    let puzzle = puzzle
+        .to_node_ptr(&mut a)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let solution = solution
+        .to_node_ptr(&mut a)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(
+        native_parse_clawback_spend(&a, puzzle, solution).map(|spend| match spend.path {
+            ClawbackPath::Claim {
+                timelock,
+                recipient_puzzle_hash,
+            } => (spend.merkle_root, Some(timelock), recipient_puzzle_hash),
+            ClawbackPath::Clawback { sender_puzzle_hash } => {
+                (spend.merkle_root, None, sender_puzzle_hash)
+            }
+        }),
+    )
+}
@@ -1,26 +1,42 @@
-use chia_consensus::allocator::make_allocator;
+use chia_consensus::allocator::{make_allocator, make_allocator_with_limit};
 use chia_consensus::gen::conditions::{EmptyVisitor, MempoolVisitor};
 use chia_consensus::gen::flags::ANALYZE_SPENDS;
+use chia_consensus::gen::generator_refs::{
+    block_generator_refs as native_block_generator_refs,
+    run_block_generator2_with_resolver as native_run_block_generator2_with_resolver,
+    run_block_generator_with_resolver as native_run_block_generator_with_resolver,
+};
 use chia_consensus::gen::owned_conditions::OwnedSpendBundleConditions;
-use chia_consensus::gen::run_block_generator::run_block_generator as native_run_block_generator;
-use chia_consensus::gen::run_block_generator::run_block_generator2 as native_run_block_generator2;
+use chia_consensus::gen::profiling_dialect::CostProfile;
+use chia_consensus::gen::run_block_generator::profile_block_generator as native_profile_block_generator;
+use chia_consensus::gen::run_block_generator::run_block_generator2_with_limits as native_run_block_generator2_with_limits;
+use chia_consensus::gen::run_block_generator::run_block_generator_with_timings as native_run_block_generator_with_timings;
+use chia_consensus::gen::timings::ValidationTimings;
 use chia_consensus::gen::validation_error::{ErrorCode, ValidationErr};
 
+use clvmr::allocator::NodePtr;
 use clvmr::cost::Cost;
 
 use pyo3::buffer::PyBuffer;
 use pyo3::prelude::*;
-use pyo3::types::PyList;
+use pyo3::types::{PyAny, PyBytes, PyDict, PyList};
 
+// metrics, if set, is called with the time spent in the steps of running the
+// generator. See timings_to_dict(). When the caller limits the heap
+// (max_heap_bytes), running out of it is reported as OutOfMemory, rather than
+// as a GeneratorRuntimeError
 #[pyfunction]
+#[pyo3(signature = (program, block_refs, max_cost, flags, max_heap_bytes=None, metrics=None))]
 pub fn run_block_generator(
-    _py: Python,
+    py: Python,
     program: PyBuffer<u8>,
     block_refs: &Bound<PyList>,
     max_cost: Cost,
     flags: u32,
+    max_heap_bytes: Option<usize>,
+    metrics: Option<&Bound<PyAny>>,
 ) -> PyResult<(Option<u32>, Option<OwnedSpendBundleConditions>)> {
-    let mut allocator = make_allocator(flags);
+    let mut allocator = make_allocator_with_limit(flags, max_heap_bytes);
 
     let mut refs = Vec::<&[u8]>::new();
     for g in block_refs {
@@ -41,38 +57,131 @@ pub fn run_block_generator(
         unsafe { std::slice::from_raw_parts(program.buf_ptr() as *const u8, program.len_bytes()) };
 
     let run_block = if (flags & ANALYZE_SPENDS) == 0 {
-        native_run_block_generator::<_, EmptyVisitor>
+        native_run_block_generator_with_timings::<_, EmptyVisitor>
     } else {
-        native_run_block_generator::<_, MempoolVisitor>
+        native_run_block_generator_with_timings::<_, MempoolVisitor>
     };
 
-    Ok(
-        match run_block(&mut allocator, program, &refs, max_cost, flags) {
-            Ok(spend_bundle_conds) => {
-                let conds = OwnedSpendBundleConditions::from(&allocator, spend_bundle_conds);
-                match conds {
-                    // everything was successful
-                    Ok(c) => (None, Some(c)),
-                    Err(_) => (Some(ErrorCode::InvalidPublicKey.into()), None),
-                }
+    let (result, timings) = run_block(
+        &mut allocator,
+        program,
+        &refs,
+        max_cost,
+        flags,
+        max_heap_bytes.is_some(),
+    );
+    if let Some(metrics) = metrics {
+        metrics.call1((timings_to_dict(py, &timings)?,))?;
+    }
+
+    Ok(match result {
+        Ok(spend_bundle_conds) => {
+            let conds = OwnedSpendBundleConditions::from(&allocator, spend_bundle_conds);
+            match conds {
+                // everything was successful
+                Ok(c) => (None, Some(c)),
+                Err(_) => (Some(ErrorCode::InvalidPublicKey.into()), None),
             }
-            Err(ValidationErr(_, error_code)) => {
-                // a validation error occurred
-                (Some(error_code.into()), None)
+        }
+        Err(ValidationErr(_, error_code)) => {
+            // a validation error occurred
+            (Some(error_code.into()), None)
+        }
+    })
+}
+
+// maps every operator (opcode) to a tuple of the number of times it was
+// called and its total cost
+pub fn profile_to_dict<'py>(
+    py: Python<'py>,
+    profile: &CostProfile,
+) -> PyResult<Bound<'py, PyDict>> {
+    let ret = PyDict::new_bound(py);
+    for (opcode, stats) in profile {
+        ret.set_item(PyBytes::new_bound(py, opcode), (stats.calls, stats.cost))?;
+    }
+    Ok(ret)
+}
+
+// the duration of every step, in seconds, keyed by the step: "deserialize",
+// "clvm", "parse_conditions" and "signature"
+pub fn timings_to_dict<'py>(
+    py: Python<'py>,
+    timings: &ValidationTimings,
+) -> PyResult<Bound<'py, PyDict>> {
+    let ret = PyDict::new_bound(py);
+    ret.set_item("deserialize", timings.deserialize.as_secs_f64())?;
+    ret.set_item("clvm", timings.clvm.as_secs_f64())?;
+    ret.set_item("parse_conditions", timings.parse_conditions.as_secs_f64())?;
+    ret.set_item("signature", timings.signature.as_secs_f64())?;
+    Ok(ret)
+}
+
+// like run_block_generator(), but also returns the cost profile of the CLVM
+// operators executed by the generator and the puzzles
+#[pyfunction]
+pub fn profile_block_generator<'py>(
+    py: Python<'py>,
+    program: PyBuffer<u8>,
+    block_refs: &Bound<PyList>,
+    max_cost: Cost,
+    flags: u32,
+) -> PyResult<(
+    Option<u32>,
+    Option<OwnedSpendBundleConditions>,
+    Bound<'py, PyDict>,
+)> {
+    let mut allocator = make_allocator(flags);
+
+    let mut refs = Vec::<&[u8]>::new();
+    for g in block_refs {
+        let buf = g.extract::<PyBuffer<u8>>()?;
+
+        if !buf.is_c_contiguous() {
+            panic!("block_refs buffers must be contiguous");
+        }
+        let slice =
+            unsafe { std::slice::from_raw_parts(buf.buf_ptr() as *const u8, buf.len_bytes()) };
+        refs.push(slice);
+    }
+
+    if !program.is_c_contiguous() {
+        panic!("program buffer must be contiguous");
+    }
+    let program =
+        unsafe { std::slice::from_raw_parts(program.buf_ptr() as *const u8, program.len_bytes()) };
+
+    let profile_block = if (flags & ANALYZE_SPENDS) == 0 {
+        native_profile_block_generator::<_, EmptyVisitor>
+    } else {
+        native_profile_block_generator::<_, MempoolVisitor>
+    };
+
+    let (result, profile) = profile_block(&mut allocator, program, &refs, max_cost, flags);
+    let profile = profile_to_dict(py, &profile)?;
+    Ok(match result {
+        Ok(spend_bundle_conds) => {
+            let conds = OwnedSpendBundleConditions::from(&allocator, spend_bundle_conds);
+            match conds {
+                Ok(c) => (None, Some(c), profile),
+                Err(_) => (Some(ErrorCode::InvalidPublicKey.into()), None, profile),
             }
-        },
-    )
+        }
+        Err(ValidationErr(_, error_code)) => (Some(error_code.into()), None, profile),
+    })
 }
 
 #[pyfunction]
+#[pyo3(signature = (program, block_refs, max_cost, flags, max_heap_bytes=None))]
 pub fn run_block_generator2(
     _py: Python,
     program: PyBuffer<u8>,
     block_refs: &Bound<PyList>,
     max_cost: Cost,
     flags: u32,
+    max_heap_bytes: Option<usize>,
 ) -> PyResult<(Option<u32>, Option<OwnedSpendBundleConditions>)> {
-    let mut allocator = make_allocator(flags);
+    let mut allocator = make_allocator_with_limit(flags, max_heap_bytes);
 
     let mut refs = Vec::<&[u8]>::new();
     for g in block_refs {
@@ -93,13 +202,14 @@ pub fn run_block_generator2(
         unsafe { std::slice::from_raw_parts(program.buf_ptr() as *const u8, program.len_bytes()) };
 
     let run_block = if (flags & ANALYZE_SPENDS) == 0 {
-        native_run_block_generator2::<_, EmptyVisitor>
+        native_run_block_generator2_with_limits::<_, EmptyVisitor>
     } else {
-        native_run_block_generator2::<_, MempoolVisitor>
+        native_run_block_generator2_with_limits::<_, MempoolVisitor>
     };
 
+    let report_oom = max_heap_bytes.is_some();
     Ok(
-        match run_block(&mut allocator, program, &refs, max_cost, flags) {
+        match run_block(&mut allocator, program, &refs, max_cost, flags, report_oom) {
             Ok(spend_bundle_conds) => {
                 let conds = OwnedSpendBundleConditions::from(&allocator, spend_bundle_conds);
                 match conds {
@@ -115,3 +225,119 @@ pub fn run_block_generator2(
         },
     )
 }
+
+// resolver is a python callable taking a block height and returning the
+// transactions generator of that block (as bytes), or None if it doesn't have
+// one. It's only called once per distinct height in ref_list, and not until
+// the program has been deserialized. Exceptions raised by the resolver are
+// propagated to the caller.
+fn run_with_resolver(
+    program: PyBuffer<u8>,
+    ref_list: Vec<u32>,
+    resolver: &Bound<PyAny>,
+    max_cost: Cost,
+    flags: u32,
+    v2: bool,
+) -> PyResult<(Option<u32>, Option<OwnedSpendBundleConditions>)> {
+    let mut allocator = make_allocator(flags);
+
+    if !program.is_c_contiguous() {
+        panic!("program buffer must be contiguous");
+    }
+    let program =
+        unsafe { std::slice::from_raw_parts(program.buf_ptr() as *const u8, program.len_bytes()) };
+
+    let mut py_err: Option<PyErr> = None;
+    let mut resolve = |height: u32| -> Result<Option<Vec<u8>>, ValidationErr> {
+        match resolver
+            .call1((height,))
+            .and_then(|ret| ret.extract::<Option<Vec<u8>>>())
+        {
+            Ok(generator) => Ok(generator),
+            Err(e) => {
+                py_err = Some(e);
+                Err(ValidationErr(
+                    NodePtr::NIL,
+                    ErrorCode::GeneratorRefHasNoGenerator,
+                ))
+            }
+        }
+    };
+
+    let result = match (v2, (flags & ANALYZE_SPENDS) == 0) {
+        (false, true) => native_run_block_generator_with_resolver::<_, EmptyVisitor>(
+            &mut allocator,
+            program,
+            &ref_list,
+            &mut resolve,
+            max_cost,
+            flags,
+        ),
+        (false, false) => native_run_block_generator_with_resolver::<_, MempoolVisitor>(
+            &mut allocator,
+            program,
+            &ref_list,
+            &mut resolve,
+            max_cost,
+            flags,
+        ),
+        (true, true) => native_run_block_generator2_with_resolver::<_, EmptyVisitor>(
+            &mut allocator,
+            program,
+            &ref_list,
+            &mut resolve,
+            max_cost,
+            flags,
+        ),
+        (true, false) => native_run_block_generator2_with_resolver::<_, MempoolVisitor>(
+            &mut allocator,
+            program,
+            &ref_list,
+            &mut resolve,
+            max_cost,
+            flags,
+        ),
+    };
+
+    if let Some(e) = py_err {
+        return Err(e);
+    }
+
+    Ok(match result {
+        Ok(spend_bundle_conds) => {
+            let conds = OwnedSpendBundleConditions::from(&allocator, spend_bundle_conds);
+            match conds {
+                Ok(c) => (None, Some(c)),
+                Err(_) => (Some(ErrorCode::InvalidPublicKey.into()), None),
+            }
+        }
+        Err(ValidationErr(_, error_code)) => (Some(error_code.into()), None),
+    })
+}
+
+#[pyfunction]
+pub fn run_block_generator_with_resolver(
+    program: PyBuffer<u8>,
+    ref_list: Vec<u32>,
+    resolver: &Bound<PyAny>,
+    max_cost: Cost,
+    flags: u32,
+) -> PyResult<(Option<u32>, Option<OwnedSpendBundleConditions>)> {
+    run_with_resolver(program, ref_list, resolver, max_cost, flags, false)
+}
+
+#[pyfunction]
+pub fn run_block_generator2_with_resolver(
+    program: PyBuffer<u8>,
+    ref_list: Vec<u32>,
+    resolver: &Bound<PyAny>,
+    max_cost: Cost,
+    flags: u32,
+) -> PyResult<(Option<u32>, Option<OwnedSpendBundleConditions>)> {
+    run_with_resolver(program, ref_list, resolver, max_cost, flags, true)
+}
+
+#[pyfunction]
+pub fn block_generator_refs(block: &[u8]) -> PyResult<Vec<u32>> {
+    Ok(native_block_generator_refs(block)?)
+}
@@ -1,7 +1,9 @@
 use super::adapt_response::eval_err_to_pyresult;
-use chia_consensus::allocator::make_allocator;
+use chia_consensus::allocator::{make_allocator, make_allocator_with_limit};
 use chia_consensus::gen::flags::ALLOW_BACKREFS;
+use chia_consensus::gen::profiling_dialect::ProfilingDialect;
 use chia_protocol::LazyNode;
+use clvm_utils::check_canonical_serialization;
 use clvmr::chia_dialect::ChiaDialect;
 use clvmr::cost::Cost;
 use clvmr::reduction::Response;
@@ -9,8 +11,11 @@ use clvmr::run_program::run_program;
 use clvmr::serde::{node_from_bytes, node_from_bytes_backrefs, serialized_length_from_bytes};
 use pyo3::buffer::PyBuffer;
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 use std::rc::Rc;
 
+use crate::run_generator::profile_to_dict;
+
 #[allow(clippy::borrow_deref_ref)]
 #[pyfunction]
 pub fn serialized_length(program: PyBuffer<u8>) -> PyResult<u64> {
@@ -22,16 +27,37 @@ pub fn serialized_length(program: PyBuffer<u8>) -> PyResult<u64> {
     Ok(serialized_length_from_bytes(program)?)
 }
 
+// returns None if program is the canonical serialization of a CLVM program,
+// otherwise the name of the rule it violates and the offset of the offending
+// byte. Back-references are only accepted with the ALLOW_BACKREFS flag
+#[pyfunction]
+#[pyo3(signature = (program, flags=0))]
+pub fn check_canonical_clvm(
+    program: PyBuffer<u8>,
+    flags: u32,
+) -> Option<(&'static str, Option<usize>)> {
+    if !program.is_c_contiguous() {
+        panic!("program must be contiguous");
+    }
+    let program =
+        unsafe { std::slice::from_raw_parts(program.buf_ptr() as *const u8, program.len_bytes()) };
+    check_canonical_serialization(program, (flags & ALLOW_BACKREFS) != 0)
+        .err()
+        .map(|e| (e.rule(), e.offset()))
+}
+
 #[allow(clippy::borrow_deref_ref)]
 #[pyfunction]
+#[pyo3(signature = (program, args, max_cost, flags, max_heap_bytes=None))]
 pub fn run_chia_program(
     py: Python,
     program: &[u8],
     args: &[u8],
     max_cost: Cost,
     flags: u32,
+    max_heap_bytes: Option<usize>,
 ) -> PyResult<(Cost, LazyNode)> {
-    let mut allocator = make_allocator(flags);
+    let mut allocator = make_allocator_with_limit(flags, max_heap_bytes);
 
     let r: Response = (|| -> PyResult<Response> {
         let deserialize = if (flags & ALLOW_BACKREFS) != 0 {
@@ -53,3 +79,38 @@ pub fn run_chia_program(
         Err(eval_err) => eval_err_to_pyresult(eval_err, allocator),
     }
 }
+
+// like run_chia_program(), but also returns the number of calls and the total
+// cost of every operator, as a dict mapping the opcode to a (calls, cost)
+// tuple
+#[pyfunction]
+pub fn profile_chia_program<'py>(
+    py: Python<'py>,
+    program: &[u8],
+    args: &[u8],
+    max_cost: Cost,
+    flags: u32,
+) -> PyResult<(Cost, LazyNode, Bound<'py, PyDict>)> {
+    let mut allocator = make_allocator(flags);
+
+    let deserialize = if (flags & ALLOW_BACKREFS) != 0 {
+        node_from_bytes_backrefs
+    } else {
+        node_from_bytes
+    };
+    let program = deserialize(&mut allocator, program)?;
+    let args = deserialize(&mut allocator, args)?;
+
+    let (r, profile) = py.allow_threads(|| {
+        let dialect = ProfilingDialect::new(ChiaDialect::new(flags));
+        let r = run_program(&mut allocator, &dialect, program, args, max_cost);
+        (r, dialect.into_profile())
+    });
+    match r {
+        Ok(reduction) => {
+            let val = LazyNode::new(Rc::new(allocator), reduction.1);
+            Ok((reduction.0, val, profile_to_dict(py, &profile)?))
+        }
+        Err(eval_err) => eval_err_to_pyresult(eval_err, allocator),
+    }
+}